@@ -108,7 +108,15 @@ fn configure_validation(builder: Builder) -> Builder {
             ("CollectionParams.vectors_config", ""),
             ("ChangeAliases.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("ListCollectionAliasesRequest.collection_name", "length(min = 1, max = 255)"),
+            ("CollectionOptimizationsRequest.collection_name", "length(min = 1, max = 255)"),
+            ("UpdateCollectionOptimizationsRequest.collection_name", "length(min = 1, max = 255)"),
             ("HnswConfigDiff.ef_construct", "custom = \"crate::grpc::validate::validate_u64_range_min_4\""),
+            ("DiskAnnConfig.max_degree", "custom = \"crate::grpc::validate::validate_u64_range_min_4\""),
+            ("DiskAnnConfig.build_list_size", "custom = \"crate::grpc::validate::validate_u64_range_min_4\""),
+            ("DiskAnnConfig.search_list_size", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("DiskAnnConfig.beam_width", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("IvfConfig.num_lists", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("IvfConfig.nprobe", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("WalConfigDiff.wal_capacity_mb", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("OptimizersConfigDiff.deleted_threshold", "custom = \"crate::grpc::validate::validate_f64_range_1\""),
             ("OptimizersConfigDiff.vacuum_min_vector_number", "custom = \"crate::grpc::validate::validate_u64_range_min_100\""),
@@ -117,6 +125,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("VectorParams.size", "range(min = 1, max = 65536)"),
             ("VectorParams.hnsw_config", ""),
             ("VectorParams.quantization_config", ""),
+            ("VectorParams.disk_ann_config", ""),
+            ("VectorParams.ivf_config", ""),
             ("VectorParamsMap.map", ""),
             ("VectorParamsDiff.hnsw_config", ""),
             ("VectorParamsDiff.quantization_config", ""),
@@ -140,6 +150,20 @@ fn configure_validation(builder: Builder) -> Builder {
         .validates(&[
             ("GetCollectionInfoRequestInternal.get_collection_info_request", ""),
             ("InitiateShardTransferRequest.collection_name", "length(min = 1, max = 255)"),
+            ("GetShardDigestRequest.collection_name", "length(min = 1, max = 255)"),
+            ("GetShardDigestRequest.buckets", "range(min = 1)"),
+            ("WaitForOperationRequest.collection_name", "length(min = 1, max = 255)"),
+            ("CreateShardSnapshotRequest.collection_name", "length(min = 1, max = 255)"),
+            ("GetShardSnapshotChunkRequest.collection_name", "length(min = 1, max = 255)"),
+            ("GetShardSnapshotChunkRequest.snapshot_name", "length(min = 1)"),
+            ("GetShardSnapshotChunkRequest.length", "range(min = 1)"),
+            ("DeleteShardSnapshotRequest.collection_name", "length(min = 1, max = 255)"),
+            ("DeleteShardSnapshotRequest.snapshot_name", "length(min = 1)"),
+            ("ForceOptimizationRequest.collection_name", "length(min = 1, max = 255)"),
+            ("SampleRecallQueriesRequest.collection_name", "length(min = 1, max = 255)"),
+            ("SampleRecallQueriesRequest.sample", "range(min = 1)"),
+            ("MeasureRecallRequest.collection_name", "length(min = 1, max = 255)"),
+            ("MeasureRecallRequest.limit", "range(min = 1)"),
         ], &[])
         // Service: points.proto
         .validates(&[
@@ -153,6 +177,11 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SetPayloadPoints.collection_name", "length(min = 1, max = 255)"),
             ("DeletePayloadPoints.collection_name", "length(min = 1, max = 255)"),
             ("ClearPayloadPoints.collection_name", "length(min = 1, max = 255)"),
+            ("IncrementPayloadPoints.collection_name", "length(min = 1, max = 255)"),
+            ("IncrementPayloadPoints.key", "length(min = 1)"),
+            ("UpdatePayloadArrayPoints.collection_name", "length(min = 1, max = 255)"),
+            ("UpdatePayloadArrayPoints.key", "length(min = 1)"),
+            ("UpdatePayloadArrayPoints.values", "length(min = 1)"),
             ("UpdateBatchPoints.collection_name", "length(min = 1, max = 255)"),
             ("UpdateBatchPoints.operations", "length(min = 1)"),
             ("CreateFieldIndexCollection.collection_name", "length(min = 1, max = 255)"),
@@ -164,6 +193,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SearchPoints.limit", "range(min = 1)"),
             ("SearchPoints.params", ""),
             ("SearchPoints.vector_name", "custom = \"common::validation::validate_not_empty\""),
+            ("SearchPoints.formula", ""),
             ("SearchBatchPoints.collection_name", "length(min = 1, max = 255)"),
             ("SearchBatchPoints.search_points", ""),
             ("SearchPointGroups.collection_name", "length(min = 1, max = 255)"),
@@ -174,7 +204,10 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SearchPointGroups.group_size", "range(min = 1)"),
             ("SearchPointGroups.limit", "range(min = 1)"),
             ("SearchParams.quantization", ""),
+            ("SearchParams.nprobe", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("SearchParams.target_recall", "custom = \"crate::grpc::validate::validate_f64_range_1\""),
             ("QuantizationSearchParams.oversampling", "custom = \"crate::grpc::validate::validate_f64_range_min_1\""),
+            ("Formula.candidates", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("ScrollPoints.collection_name", "length(min = 1, max = 255)"),
             ("ScrollPoints.filter", ""),
             ("ScrollPoints.limit", "custom = \"crate::grpc::validate::validate_u32_range_min_1\""),
@@ -196,6 +229,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("Filter.should", ""),
             ("Filter.must", ""),
             ("Filter.must_not", ""),
+            ("Filter.min_should", ""),
+            ("MinShould.conditions", ""),
             ("NestedCondition.filter", ""),
             ("Condition.condition_one_of", "")
         ], &[])
@@ -209,6 +244,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SetPayloadPointsInternal.set_payload_points", ""),
             ("DeletePayloadPointsInternal.delete_payload_points", ""),
             ("ClearPayloadPointsInternal.clear_payload_points", ""),
+            ("IncrementPayloadPointsInternal.increment_payload_points", ""),
+            ("UpdatePayloadArrayPointsInternal.update_payload_array_points", ""),
             ("CreateFieldIndexCollectionInternal.create_field_index_collection", ""),
             ("DeleteFieldIndexCollectionInternal.delete_field_index_collection", ""),
             ("SearchPointsInternal.search_points", ""),
@@ -219,6 +256,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("CoreSearchPoints.limit", "range(min = 1)"),
             ("CoreSearchPoints.params", ""),
             ("CoreSearchPoints.vector_name", "custom = \"common::validation::validate_not_empty\""),
+            ("CoreSearchPoints.formula", ""),
             ("CoreSearchBatchPointsInternal.collection_name", "length(min = 1, max = 255)"),
             ("CoreSearchBatchPointsInternal.search_points", ""),
             ("RecommendPointsInternal.recommend_points", ""),
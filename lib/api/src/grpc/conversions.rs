@@ -1,16 +1,19 @@
 use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 
-use chrono::{NaiveDateTime, Timelike};
+use chrono::{DateTime, NaiveDateTime, Timelike, Utc};
+use segment::data_types::formula;
 use segment::data_types::text_index::TextIndexType;
 use segment::data_types::vectors::VectorElementType;
 use segment::types::default_quantization_ignore_value;
 use tonic::Status;
 use uuid::Uuid;
+use validator::Validate;
 
 use super::qdrant::{BinaryQuantization, CompressionRatio, GeoLineString, GroupId};
 use crate::grpc::models::{CollectionsResponse, VersionInfo};
 use crate::grpc::qdrant::condition::ConditionOneOf;
+use crate::grpc::qdrant::expression::Variant;
 use crate::grpc::qdrant::payload_index_params::IndexParams;
 use crate::grpc::qdrant::point_id::PointIdOptions;
 use crate::grpc::qdrant::r#match::MatchValue;
@@ -18,14 +21,17 @@ use crate::grpc::qdrant::value::Kind;
 use crate::grpc::qdrant::vectors::VectorsOptions;
 use crate::grpc::qdrant::with_payload_selector::SelectorOptions;
 use crate::grpc::qdrant::{
-    with_vectors_selector, CollectionDescription, CollectionOperationResponse, Condition, Distance,
-    FieldCondition, Filter, GeoBoundingBox, GeoPoint, GeoPolygon, GeoRadius, HasIdCondition,
-    HealthCheckReply, HnswConfigDiff, IsEmptyCondition, IsNullCondition, ListCollectionsResponse,
-    ListValue, Match, NamedVectors, NestedCondition, PayloadExcludeSelector,
-    PayloadIncludeSelector, PayloadIndexParams, PayloadSchemaInfo, PayloadSchemaType, PointId,
-    ProductQuantization, QuantizationConfig, QuantizationSearchParams, QuantizationType, Range,
-    RepeatedIntegers, RepeatedStrings, ScalarQuantization, ScoredPoint, SearchParams, Struct,
-    TextIndexParams, TokenizerType, Value, ValuesCount, Vector, Vectors, VectorsSelector,
+    with_vectors_selector, CardinalityEstimationProfile, CollectionDescription,
+    CollectionOperationResponse, Condition, DecayParamsExpression, Distance, DivExpression,
+    Expression, FieldCondition, Filter, Formula, GeoBoundingBox, GeoDistance, GeoPoint, GeoPolygon,
+    GeoRadius, HasIdCondition, HealthCheckReply, HnswConfigDiff, IsEmptyCondition, IsNullCondition,
+    ListCollectionsResponse, ListValue, Match, MinShould, MultExpression, NamedVectors,
+    NestedCondition, PayloadExcludeSelector, PayloadIncludeSelector, PayloadIndexParams,
+    PayloadSchemaInfo, PayloadSchemaType, PointId, PowExpression, ProductQuantization,
+    QuantizationConfig, QuantizationSearchParams, QuantizationType, Range, RecallCalibrationPoint,
+    RepeatedIntegers, RepeatedStrings, ScalarQuantization, ScoredPoint, SearchCalibration,
+    SearchParams, SearchStrategy, SegmentSearchProfile, Struct, SumExpression, TextIndexParams,
+    TokenizerType, Value, ValuesCount, Vector, VectorIndexSearchProfile, Vectors, VectorsSelector,
     WithPayloadSelector, WithVectorsSelector,
 };
 
@@ -36,7 +42,7 @@ pub fn payload_to_proto(payload: segment::types::Payload) -> HashMap<String, Val
         .collect()
 }
 
-fn json_to_proto(json_value: serde_json::Value) -> Value {
+pub fn json_to_proto(json_value: serde_json::Value) -> Value {
     match json_value {
         serde_json::Value::Null => Value {
             kind: Some(Kind::NullValue(0)),
@@ -77,7 +83,7 @@ pub fn proto_to_payloads(proto: HashMap<String, Value>) -> Result<segment::types
     Ok(map.into())
 }
 
-fn proto_to_json(proto: Value) -> Result<serde_json::Value, Status> {
+pub fn proto_to_json(proto: Value) -> Result<serde_json::Value, Status> {
     match proto.kind {
         None => Ok(serde_json::Value::default()),
         Some(kind) => match kind {
@@ -349,6 +355,42 @@ impl From<QuantizationSearchParams> for segment::types::QuantizationSearchParams
     }
 }
 
+impl From<segment::types::SearchCalibration> for SearchCalibration {
+    fn from(calibration: segment::types::SearchCalibration) -> Self {
+        Self {
+            num_queries: calibration.num_queries as u64,
+            points: calibration
+                .points
+                .into_iter()
+                .map(|point| RecallCalibrationPoint {
+                    selectivity: point.selectivity,
+                    hnsw_ef: point.hnsw_ef as u64,
+                    oversampling: point.oversampling,
+                    recall: point.recall,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<SearchCalibration> for segment::types::SearchCalibration {
+    fn from(calibration: SearchCalibration) -> Self {
+        Self {
+            num_queries: calibration.num_queries as usize,
+            points: calibration
+                .points
+                .into_iter()
+                .map(|point| segment::types::RecallCalibrationPoint {
+                    selectivity: point.selectivity,
+                    hnsw_ef: point.hnsw_ef as usize,
+                    oversampling: point.oversampling,
+                    recall: point.recall,
+                })
+                .collect(),
+        }
+    }
+}
+
 impl From<segment::types::QuantizationSearchParams> for QuantizationSearchParams {
     fn from(params: segment::types::QuantizationSearchParams) -> Self {
         Self {
@@ -363,6 +405,8 @@ impl From<SearchParams> for segment::types::SearchParams {
     fn from(params: SearchParams) -> Self {
         Self {
             hnsw_ef: params.hnsw_ef.map(|x| x as usize),
+            nprobe: params.nprobe.map(|x| x as usize),
+            target_recall: params.target_recall,
             exact: params.exact.unwrap_or(false),
             quantization: params.quantization.map(|q| q.into()),
             indexed_only: params.indexed_only.unwrap_or(false),
@@ -374,6 +418,8 @@ impl From<segment::types::SearchParams> for SearchParams {
     fn from(params: segment::types::SearchParams) -> Self {
         Self {
             hnsw_ef: params.hnsw_ef.map(|x| x as u64),
+            nprobe: params.nprobe.map(|x| x as u64),
+            target_recall: params.target_recall,
             exact: Some(params.exact),
             quantization: params.quantization.map(|q| q.into()),
             indexed_only: Some(params.indexed_only),
@@ -387,6 +433,7 @@ impl From<segment::types::PointIdType> for PointId {
             point_id_options: Some(match point_id {
                 segment::types::PointIdType::NumId(num) => PointIdOptions::Num(num),
                 segment::types::PointIdType::Uuid(uuid) => PointIdOptions::Uuid(uuid.to_string()),
+                segment::types::PointIdType::String(id) => PointIdOptions::Str(id.to_string()),
             }),
         }
     }
@@ -517,6 +564,9 @@ impl TryFrom<PointId> for segment::types::PointIdType {
                 .map_err(|_err| {
                     Status::invalid_argument(format!("Unable to parse UUID: {uui_str}"))
                 }),
+            Some(PointIdOptions::Str(id)) => segment::types::StringId::try_from(id.as_str())
+                .map(segment::types::PointIdType::String)
+                .map_err(|err| Status::invalid_argument(err.to_string())),
             _ => Err(Status::invalid_argument(
                 "No ID options provided".to_string(),
             )),
@@ -695,18 +745,44 @@ impl TryFrom<Filter> for segment::types::Filter {
     fn try_from(value: Filter) -> Result<Self, Self::Error> {
         Ok(Self {
             should: conditions_helper_from_grpc(value.should)?,
+            min_should: value.min_should.map(TryInto::try_into).transpose()?,
             must: conditions_helper_from_grpc(value.must)?,
             must_not: conditions_helper_from_grpc(value.must_not)?,
         })
     }
 }
 
+impl TryFrom<MinShould> for segment::types::MinShould {
+    type Error = Status;
+
+    fn try_from(value: MinShould) -> Result<Self, Self::Error> {
+        Ok(Self {
+            conditions: value
+                .conditions
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            min_count: value.min_count as usize,
+        })
+    }
+}
+
+impl From<segment::types::MinShould> for MinShould {
+    fn from(value: segment::types::MinShould) -> Self {
+        Self {
+            conditions: value.conditions.into_iter().map(Into::into).collect(),
+            min_count: value.min_count as u64,
+        }
+    }
+}
+
 impl From<segment::types::Filter> for Filter {
     fn from(value: segment::types::Filter) -> Self {
         Self {
             should: conditions_helper_to_grpc(value.should),
             must: conditions_helper_to_grpc(value.must),
             must_not: conditions_helper_to_grpc(value.must_not),
+            min_should: value.min_should.map(Into::into),
         }
     }
 }
@@ -1004,6 +1080,263 @@ impl From<segment::types::GeoLineString> for GeoLineString {
     }
 }
 
+impl TryFrom<Formula> for formula::Formula {
+    type Error = Status;
+
+    fn try_from(value: Formula) -> Result<Self, Self::Error> {
+        let Formula {
+            expression,
+            defaults,
+            candidates,
+        } = value;
+
+        let converted = Self {
+            expression: expression
+                .ok_or_else(|| Status::invalid_argument("Formula expression is not specified"))?
+                .try_into()?,
+            defaults: proto_to_payloads(defaults)?.into_iter().collect(),
+            candidates: candidates.map(|candidates| candidates as usize),
+        };
+        converted
+            .validate()
+            .map_err(|err| Status::invalid_argument(format!("Malformed formula: {err}")))?;
+        Ok(converted)
+    }
+}
+
+impl From<formula::Formula> for Formula {
+    fn from(value: formula::Formula) -> Self {
+        Self {
+            expression: Some(value.expression.into()),
+            defaults: value
+                .defaults
+                .into_iter()
+                .map(|(key, value)| (key, json_to_proto(value)))
+                .collect(),
+            candidates: value.candidates.map(|candidates| candidates as u64),
+        }
+    }
+}
+
+fn required_expression(
+    expression: Option<Box<Expression>>,
+    name: &str,
+) -> Result<Box<formula::Expression>, Status> {
+    let expression = expression
+        .ok_or_else(|| Status::invalid_argument(format!("Expression `{name}` is not specified")))?;
+    Ok(Box::new((*expression).try_into()?))
+}
+
+fn expressions_try_from(expressions: Vec<Expression>) -> Result<Vec<formula::Expression>, Status> {
+    expressions.into_iter().map(TryInto::try_into).collect()
+}
+
+impl TryFrom<Expression> for formula::Expression {
+    type Error = Status;
+
+    fn try_from(value: Expression) -> Result<Self, Self::Error> {
+        let variant = value
+            .variant
+            .ok_or_else(|| Status::invalid_argument("Malformed Expression type"))?;
+
+        let expression = match variant {
+            Variant::Constant(constant) => Self::Constant(constant),
+            Variant::Variable(variable) => Self::Variable(variable),
+            Variant::Filter(filter) => Self::Filter(formula::FilterExpression {
+                filter: filter.try_into()?,
+            }),
+            Variant::GeoDistance(GeoDistance { origin, to }) => {
+                Self::GeoDistance(formula::GeoDistanceExpression {
+                    geo_distance: formula::GeoDistance {
+                        origin: origin
+                            .ok_or_else(|| {
+                                Status::invalid_argument("Geo distance origin is not specified")
+                            })?
+                            .into(),
+                        to,
+                    },
+                })
+            }
+            Variant::Datetime(datetime) => Self::Datetime(formula::DatetimeExpression {
+                datetime: DateTime::parse_from_rfc3339(&datetime)
+                    .map_err(|err| {
+                        Status::invalid_argument(format!("Malformed datetime {datetime}: {err}"))
+                    })?
+                    .with_timezone(&Utc),
+            }),
+            Variant::DatetimeKey(datetime_key) => {
+                Self::DatetimeKey(formula::DatetimeKeyExpression { datetime_key })
+            }
+            Variant::Mult(mult) => Self::Mult(formula::MultExpression {
+                mult: expressions_try_from(mult.mult)?,
+            }),
+            Variant::Sum(sum) => Self::Sum(formula::SumExpression {
+                sum: expressions_try_from(sum.sum)?,
+            }),
+            Variant::Neg(neg) => Self::Neg(formula::NegExpression {
+                neg: required_expression(Some(neg), "neg")?,
+            }),
+            Variant::Div(div) => {
+                let DivExpression {
+                    left,
+                    right,
+                    by_zero_default,
+                } = *div;
+                Self::Div(formula::DivExpression {
+                    div: formula::DivParams {
+                        left: required_expression(left, "left")?,
+                        right: required_expression(right, "right")?,
+                        by_zero_default,
+                    },
+                })
+            }
+            Variant::Abs(abs) => Self::Abs(formula::AbsExpression {
+                abs: required_expression(Some(abs), "abs")?,
+            }),
+            Variant::Sqrt(sqrt) => Self::Sqrt(formula::SqrtExpression {
+                sqrt: required_expression(Some(sqrt), "sqrt")?,
+            }),
+            Variant::Pow(pow) => {
+                let PowExpression { base, exponent } = *pow;
+                Self::Pow(formula::PowExpression {
+                    pow: formula::PowParams {
+                        base: required_expression(base, "base")?,
+                        exponent: required_expression(exponent, "exponent")?,
+                    },
+                })
+            }
+            Variant::Exp(exp) => Self::Exp(formula::ExpExpression {
+                exp: required_expression(Some(exp), "exp")?,
+            }),
+            Variant::Ln(ln) => Self::Ln(formula::LnExpression {
+                ln: required_expression(Some(ln), "ln")?,
+            }),
+            Variant::LinDecay(decay) => Self::LinDecay(formula::LinDecayExpression {
+                lin_decay: (*decay).try_into()?,
+            }),
+            Variant::ExpDecay(decay) => Self::ExpDecay(formula::ExpDecayExpression {
+                exp_decay: (*decay).try_into()?,
+            }),
+            Variant::GaussDecay(decay) => Self::GaussDecay(formula::GaussDecayExpression {
+                gauss_decay: (*decay).try_into()?,
+            }),
+        };
+        Ok(expression)
+    }
+}
+
+impl From<formula::Expression> for Expression {
+    fn from(value: formula::Expression) -> Self {
+        let boxed = |expression: Box<formula::Expression>| Box::new(Expression::from(*expression));
+        let variant = match value {
+            formula::Expression::Constant(constant) => Variant::Constant(constant),
+            formula::Expression::Variable(variable) => Variant::Variable(variable),
+            formula::Expression::Filter(formula::FilterExpression { filter }) => {
+                Variant::Filter(filter.into())
+            }
+            formula::Expression::GeoDistance(formula::GeoDistanceExpression {
+                geo_distance: formula::GeoDistance { origin, to },
+            }) => Variant::GeoDistance(GeoDistance {
+                origin: Some(origin.into()),
+                to,
+            }),
+            formula::Expression::Datetime(formula::DatetimeExpression { datetime }) => {
+                Variant::Datetime(datetime.to_rfc3339())
+            }
+            formula::Expression::DatetimeKey(formula::DatetimeKeyExpression { datetime_key }) => {
+                Variant::DatetimeKey(datetime_key)
+            }
+            formula::Expression::Mult(formula::MultExpression { mult }) => {
+                Variant::Mult(Box::new(MultExpression {
+                    mult: mult.into_iter().map(Into::into).collect(),
+                }))
+            }
+            formula::Expression::Sum(formula::SumExpression { sum }) => {
+                Variant::Sum(Box::new(SumExpression {
+                    sum: sum.into_iter().map(Into::into).collect(),
+                }))
+            }
+            formula::Expression::Neg(formula::NegExpression { neg }) => Variant::Neg(boxed(neg)),
+            formula::Expression::Div(formula::DivExpression {
+                div:
+                    formula::DivParams {
+                        left,
+                        right,
+                        by_zero_default,
+                    },
+            }) => Variant::Div(Box::new(DivExpression {
+                left: Some(boxed(left)),
+                right: Some(boxed(right)),
+                by_zero_default,
+            })),
+            formula::Expression::Abs(formula::AbsExpression { abs }) => Variant::Abs(boxed(abs)),
+            formula::Expression::Sqrt(formula::SqrtExpression { sqrt }) => {
+                Variant::Sqrt(boxed(sqrt))
+            }
+            formula::Expression::Pow(formula::PowExpression {
+                pow: formula::PowParams { base, exponent },
+            }) => Variant::Pow(Box::new(PowExpression {
+                base: Some(boxed(base)),
+                exponent: Some(boxed(exponent)),
+            })),
+            formula::Expression::Exp(formula::ExpExpression { exp }) => Variant::Exp(boxed(exp)),
+            formula::Expression::Ln(formula::LnExpression { ln }) => Variant::Ln(boxed(ln)),
+            formula::Expression::LinDecay(formula::LinDecayExpression { lin_decay }) => {
+                Variant::LinDecay(Box::new(lin_decay.into()))
+            }
+            formula::Expression::ExpDecay(formula::ExpDecayExpression { exp_decay }) => {
+                Variant::ExpDecay(Box::new(exp_decay.into()))
+            }
+            formula::Expression::GaussDecay(formula::GaussDecayExpression { gauss_decay }) => {
+                Variant::GaussDecay(Box::new(gauss_decay.into()))
+            }
+        };
+        Self {
+            variant: Some(variant),
+        }
+    }
+}
+
+impl TryFrom<DecayParamsExpression> for formula::DecayParams {
+    type Error = Status;
+
+    fn try_from(value: DecayParamsExpression) -> Result<Self, Self::Error> {
+        let DecayParamsExpression {
+            x,
+            target,
+            scale,
+            midpoint,
+        } = value;
+
+        Ok(Self {
+            x: required_expression(x, "x")?,
+            target: target
+                .map(|target| required_expression(Some(target), "target"))
+                .transpose()?,
+            scale,
+            midpoint,
+        })
+    }
+}
+
+impl From<formula::DecayParams> for DecayParamsExpression {
+    fn from(value: formula::DecayParams) -> Self {
+        let formula::DecayParams {
+            x,
+            target,
+            scale,
+            midpoint,
+        } = value;
+
+        Self {
+            x: Some(Box::new((*x).into())),
+            target: target.map(|target| Box::new((*target).into())),
+            scale,
+            midpoint,
+        }
+    }
+}
+
 impl From<Range> for segment::types::Range {
     fn from(value: Range) -> Self {
         Self {
@@ -1115,6 +1448,7 @@ impl From<HnswConfigDiff> for segment::types::HnswConfig {
             max_indexing_threads: hnsw_config.max_indexing_threads.unwrap_or_default() as usize,
             on_disk: hnsw_config.on_disk,
             payload_m: hnsw_config.payload_m.map(|x| x as usize),
+            appendable: hnsw_config.appendable,
         }
     }
 }
@@ -1139,6 +1473,9 @@ impl TryFrom<Distance> for segment::types::Distance {
             Distance::Cosine => segment::types::Distance::Cosine,
             Distance::Euclid => segment::types::Distance::Euclid,
             Distance::Dot => segment::types::Distance::Dot,
+            Distance::Manhattan => segment::types::Distance::Manhattan,
+            Distance::Hamming => segment::types::Distance::Hamming,
+            Distance::Jaccard => segment::types::Distance::Jaccard,
         })
     }
 }
@@ -1151,3 +1488,103 @@ pub fn from_grpc_dist(dist: i32) -> Result<segment::types::Distance, Status> {
         Some(grpc_distance) => Ok(grpc_distance.try_into()?),
     }
 }
+
+impl From<segment::data_types::search_profile::SearchStrategy> for SearchStrategy {
+    fn from(value: segment::data_types::search_profile::SearchStrategy) -> Self {
+        use segment::data_types::search_profile::SearchStrategy as SegmentSearchStrategy;
+        match value {
+            SegmentSearchStrategy::Plain => SearchStrategy::Plain,
+            SegmentSearchStrategy::Exact => SearchStrategy::Exact,
+            SegmentSearchStrategy::Hnsw => SearchStrategy::Hnsw,
+            SegmentSearchStrategy::PayloadIndex => SearchStrategy::PayloadIndex,
+            SegmentSearchStrategy::FilteredHnsw => SearchStrategy::FilteredHnsw,
+            SegmentSearchStrategy::DiskAnn => SearchStrategy::DiskAnn,
+            SegmentSearchStrategy::Ivf => SearchStrategy::Ivf,
+            SegmentSearchStrategy::AcornHnsw => SearchStrategy::AcornHnsw,
+        }
+    }
+}
+
+impl From<SearchStrategy> for segment::data_types::search_profile::SearchStrategy {
+    fn from(value: SearchStrategy) -> Self {
+        match value {
+            SearchStrategy::Plain => Self::Plain,
+            SearchStrategy::Exact => Self::Exact,
+            SearchStrategy::Hnsw => Self::Hnsw,
+            SearchStrategy::PayloadIndex => Self::PayloadIndex,
+            SearchStrategy::FilteredHnsw => Self::FilteredHnsw,
+            SearchStrategy::DiskAnn => Self::DiskAnn,
+            SearchStrategy::Ivf => Self::Ivf,
+            SearchStrategy::AcornHnsw => Self::AcornHnsw,
+        }
+    }
+}
+
+impl From<segment::data_types::search_profile::SegmentSearchProfile> for SegmentSearchProfile {
+    fn from(value: segment::data_types::search_profile::SegmentSearchProfile) -> Self {
+        let search = value.search;
+        Self {
+            segment_id: value.segment_id,
+            vector_name: value.vector_name,
+            queries: value.queries as u64,
+            search: Some(VectorIndexSearchProfile {
+                strategy: SearchStrategy::from(search.strategy) as i32,
+                cardinality: search
+                    .cardinality
+                    .map(|cardinality| CardinalityEstimationProfile {
+                        min: cardinality.min as u64,
+                        exp: cardinality.exp as u64,
+                        max: cardinality.max as u64,
+                    }),
+                cardinality_sampled: search.cardinality_sampled,
+                hnsw_ef: search.hnsw_ef.map(|ef| ef as u64),
+                scored_vectors: search.scored_vectors as u64,
+                quantized: search.quantized,
+                candidates: search.candidates as u64,
+                rescored: search.rescored,
+            }),
+            time: value.time,
+        }
+    }
+}
+
+impl TryFrom<SegmentSearchProfile> for segment::data_types::search_profile::SegmentSearchProfile {
+    type Error = Status;
+
+    fn try_from(value: SegmentSearchProfile) -> Result<Self, Self::Error> {
+        use segment::data_types::search_profile::{
+            CardinalityEstimationProfile as SegmentCardinality, VectorIndexSearchProfile as Search,
+        };
+
+        let search = value
+            .search
+            .ok_or_else(|| Status::invalid_argument("Malformed search profile: missing search"))?;
+        let strategy = SearchStrategy::from_i32(search.strategy).ok_or_else(|| {
+            Status::invalid_argument(format!(
+                "Malformed search profile, unexpected strategy: {}",
+                search.strategy
+            ))
+        })?;
+
+        Ok(Self {
+            segment_id: value.segment_id,
+            vector_name: value.vector_name,
+            queries: value.queries as usize,
+            search: Search {
+                strategy: strategy.into(),
+                cardinality: search.cardinality.map(|cardinality| SegmentCardinality {
+                    min: cardinality.min as usize,
+                    exp: cardinality.exp as usize,
+                    max: cardinality.max as usize,
+                }),
+                cardinality_sampled: search.cardinality_sampled,
+                hnsw_ef: search.hnsw_ef.map(|ef| ef as usize),
+                scored_vectors: search.scored_vectors as usize,
+                quantized: search.quantized,
+                candidates: search.candidates as usize,
+                rescored: search.rescored,
+            },
+            time: value.time,
+        })
+    }
+}
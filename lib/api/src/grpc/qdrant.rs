@@ -21,6 +21,14 @@ pub struct VectorParams {
     /// If true - serve vectors from disk. If set to false, the vectors will be loaded in RAM.
     #[prost(bool, optional, tag = "5")]
     pub on_disk: ::core::option::Option<bool>,
+    /// Configuration of DiskANN index. If set - DiskANN graph stored on disk is used instead of HNSW
+    #[prost(message, optional, tag = "6")]
+    #[validate]
+    pub disk_ann_config: ::core::option::Option<DiskAnnConfig>,
+    /// Configuration of IVF index. If set - IVF index is used instead of HNSW
+    #[prost(message, optional, tag = "7")]
+    #[validate]
+    pub ivf_config: ::core::option::Option<IvfConfig>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -187,6 +195,77 @@ pub struct HnswConfigDiff {
     /// Number of additional payload-aware links per node in the index graph. If not set - regular M parameter will be used.
     #[prost(uint64, optional, tag = "6")]
     pub payload_m: ::core::option::Option<u64>,
+    ///
+    /// Maintain HNSW index in appendable segments, inserting new points into the graph as they arrive.
+    /// If set to false, appendable segments are searched with full scan until indexed by the optimizer.
+    #[prost(bool, optional, tag = "7")]
+    pub appendable: ::core::option::Option<bool>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DiskAnnConfig {
+    ///
+    /// Maximal number of edges per node in the index graph. Larger the value - more accurate the search, more space required.
+    #[prost(uint64, optional, tag = "1")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_4")]
+    pub max_degree: ::core::option::Option<u64>,
+    ///
+    /// Number of neighbours to consider during the index building. Larger the value - more accurate the search, more time required to build the index.
+    #[prost(uint64, optional, tag = "2")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_4")]
+    pub build_list_size: ::core::option::Option<u64>,
+    ///
+    /// Number of candidates to keep during the search, if not specified with `hnsw_ef` search param.
+    #[prost(uint64, optional, tag = "3")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub search_list_size: ::core::option::Option<u64>,
+    ///
+    /// Number of nodes read from disk in parallel on every search step.
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub beam_width: ::core::option::Option<u64>,
+    ///
+    /// Minimal size (in KiloBytes) of vectors to search with the index.
+    /// If there are fewer vectors, or fewer of them match the filter, full-scan search is used instead.
+    /// Note: 1 Kb = 1 vector of size 256
+    #[prost(uint64, optional, tag = "5")]
+    pub full_scan_threshold: ::core::option::Option<u64>,
+    ///
+    /// Number of parallel threads used for background index building. If 0 - auto selection.
+    #[prost(uint64, optional, tag = "6")]
+    pub max_indexing_threads: ::core::option::Option<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IvfConfig {
+    ///
+    /// Number of inverted lists (k-means centroids). If not set - 4 * sqrt(number of vectors) is used.
+    #[prost(uint64, optional, tag = "1")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub num_lists: ::core::option::Option<u64>,
+    ///
+    /// Number of closest lists to scan during the search, if not specified with `nprobe` search param.
+    #[prost(uint64, optional, tag = "2")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub nprobe: ::core::option::Option<u64>,
+    ///
+    /// If set - residuals of vectors to their centroids are compressed with product quantization with this ratio.
+    #[prost(enumeration = "CompressionRatio", optional, tag = "3")]
+    pub product_quantization: ::core::option::Option<i32>,
+    ///
+    /// Minimal size (in KiloBytes) of vectors to search with the index.
+    /// If there are fewer vectors, or fewer of them match the filter, full-scan search is used instead.
+    /// Note: 1 Kb = 1 vector of size 256
+    #[prost(uint64, optional, tag = "4")]
+    pub full_scan_threshold: ::core::option::Option<u64>,
+    ///
+    /// Number of parallel threads used for background index building. If 0 - auto selection.
+    #[prost(uint64, optional, tag = "5")]
+    pub max_indexing_threads: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -587,6 +666,34 @@ pub struct PayloadSchemaInfo {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecallCalibrationPoint {
+    /// Fraction of vectors, matching the filter of calibration queries
+    #[prost(double, tag = "1")]
+    pub selectivity: f64,
+    /// Size of the beam in a beam-search
+    #[prost(uint64, tag = "2")]
+    pub hnsw_ef: u64,
+    /// Oversampling factor for quantization. Not set, if vectors are not quantized
+    #[prost(double, optional, tag = "3")]
+    pub oversampling: ::core::option::Option<f64>,
+    /// Fraction of true nearest neighbors found
+    #[prost(double, tag = "4")]
+    pub recall: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchCalibration {
+    /// Number of sampled queries
+    #[prost(uint64, tag = "1")]
+    pub num_queries: u64,
+    /// Measured recall for each combination of selectivity and search parameters
+    #[prost(message, repeated, tag = "2")]
+    pub points: ::prost::alloc::vec::Vec<RecallCalibrationPoint>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CollectionInfo {
     /// operating condition of the collection
     #[prost(enumeration = "CollectionStatus", tag = "1")]
@@ -615,6 +722,12 @@ pub struct CollectionInfo {
     /// number of indexed vectors in the collection.
     #[prost(uint64, optional, tag = "10")]
     pub indexed_vectors_count: ::core::option::Option<u64>,
+    /// Measured recall of vector indexes. The lowest recall among all segments is reported
+    #[prost(map = "string, message", tag = "11")]
+    pub search_calibration: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        SearchCalibration,
+    >,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -771,6 +884,20 @@ pub struct ShardTransferInfo {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReshardingInfo {
+    /// ID of the new shard
+    #[prost(uint32, tag = "1")]
+    pub shard_id: u32,
+    /// Peer, which holds the new shard
+    #[prost(uint64, tag = "2")]
+    pub peer_id: u64,
+    /// Existing shards, which are still migrating points into the new shard
+    #[prost(uint32, repeated, tag = "3")]
+    pub pending_shards: ::prost::alloc::vec::Vec<u32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CollectionClusterInfoResponse {
     /// ID of this peer
     #[prost(uint64, tag = "1")]
@@ -787,6 +914,9 @@ pub struct CollectionClusterInfoResponse {
     /// Shard transfers
     #[prost(message, repeated, tag = "5")]
     pub shard_transfers: ::prost::alloc::vec::Vec<ShardTransferInfo>,
+    /// Resharding in progress
+    #[prost(message, optional, tag = "6")]
+    pub resharding: ::core::option::Option<ReshardingInfo>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -809,6 +939,18 @@ pub struct Replica {
     #[prost(uint64, tag = "2")]
     pub peer_id: u64,
 }
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StartResharding {
+    /// Peer to place the new shard on, the least loaded peer is selected if not specified
+    #[prost(uint64, optional, tag = "1")]
+    pub peer_id: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AbortResharding {}
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -822,7 +964,7 @@ pub struct UpdateCollectionClusterSetupRequest {
     pub timeout: ::core::option::Option<u64>,
     #[prost(
         oneof = "update_collection_cluster_setup_request::Operation",
-        tags = "2, 3, 4, 5"
+        tags = "2, 3, 4, 5, 7, 8"
     )]
     pub operation: ::core::option::Option<
         update_collection_cluster_setup_request::Operation,
@@ -842,6 +984,10 @@ pub mod update_collection_cluster_setup_request {
         AbortTransfer(super::MoveShard),
         #[prost(message, tag = "5")]
         DropReplica(super::Replica),
+        #[prost(message, tag = "7")]
+        StartResharding(super::StartResharding),
+        #[prost(message, tag = "8")]
+        AbortResharding(super::AbortResharding),
     }
 }
 #[derive(serde::Serialize)]
@@ -851,6 +997,135 @@ pub struct UpdateCollectionClusterSetupResponse {
     #[prost(bool, tag = "1")]
     pub result: bool,
 }
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CollectionOptimizationsRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RunningOptimization {
+    /// Name of the optimizer
+    #[prost(string, tag = "1")]
+    pub optimizer: ::prost::alloc::string::String,
+    /// Segments being optimized
+    #[prost(uint64, repeated, tag = "2")]
+    pub segment_ids: ::prost::alloc::vec::Vec<u64>,
+    /// Current stage of the optimization
+    #[prost(enumeration = "OptimizationStage", tag = "3")]
+    pub stage: i32,
+    /// Number of points already copied into the new segment
+    #[prost(uint64, tag = "4")]
+    pub copied_points: u64,
+    /// Total number of points to copy
+    #[prost(uint64, tag = "5")]
+    pub total_points: u64,
+    /// Time since the optimization has started, in seconds
+    #[prost(double, tag = "6")]
+    pub elapsed: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueuedOptimization {
+    /// Name of the optimizer
+    #[prost(string, tag = "1")]
+    pub optimizer: ::prost::alloc::string::String,
+    /// Segments which would be optimized
+    #[prost(uint64, repeated, tag = "2")]
+    pub segment_ids: ::prost::alloc::vec::Vec<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShardOptimizations {
+    /// Local shard id
+    #[prost(uint32, tag = "1")]
+    pub shard_id: u32,
+    /// If `true`, optimizers won't start new optimizations until resumed
+    #[prost(bool, tag = "2")]
+    pub paused: bool,
+    /// Optimizations currently in progress
+    #[prost(message, repeated, tag = "3")]
+    pub running: ::prost::alloc::vec::Vec<RunningOptimization>,
+    /// Optimizations waiting to be started
+    #[prost(message, repeated, tag = "4")]
+    pub queued: ::prost::alloc::vec::Vec<QueuedOptimization>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CollectionOptimizationsResponse {
+    /// Optimizations of local shards
+    #[prost(message, repeated, tag = "1")]
+    pub shards: ::prost::alloc::vec::Vec<ShardOptimizations>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PauseOptimizers {}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResumeOptimizers {}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MergeSegments {
+    /// Maximal number of segments to keep in each shard
+    #[prost(uint64, tag = "1")]
+    pub max_segments: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VacuumSegments {}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateCollectionOptimizationsRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    #[prost(
+        oneof = "update_collection_optimizations_request::Operation",
+        tags = "2, 3, 4, 5"
+    )]
+    pub operation: ::core::option::Option<
+        update_collection_optimizations_request::Operation,
+    >,
+}
+/// Nested message and enum types in `UpdateCollectionOptimizationsRequest`.
+pub mod update_collection_optimizations_request {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Operation {
+        #[prost(message, tag = "2")]
+        Pause(super::PauseOptimizers),
+        #[prost(message, tag = "3")]
+        Resume(super::ResumeOptimizers),
+        #[prost(message, tag = "4")]
+        Merge(super::MergeSegments),
+        #[prost(message, tag = "5")]
+        Vacuum(super::VacuumSegments),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateCollectionOptimizationsResponse {
+    #[prost(bool, tag = "1")]
+    pub result: bool,
+}
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
@@ -859,6 +1134,11 @@ pub enum Distance {
     Cosine = 1,
     Euclid = 2,
     Dot = 3,
+    Manhattan = 4,
+    /// Binary vectors, stored bit-packed
+    Hamming = 5,
+    /// Binary vectors, stored bit-packed
+    Jaccard = 6,
 }
 impl Distance {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -871,6 +1151,9 @@ impl Distance {
             Distance::Cosine => "Cosine",
             Distance::Euclid => "Euclid",
             Distance::Dot => "Dot",
+            Distance::Manhattan => "Manhattan",
+            Distance::Hamming => "Hamming",
+            Distance::Jaccard => "Jaccard",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -880,6 +1163,9 @@ impl Distance {
             "Cosine" => Some(Self::Cosine),
             "Euclid" => Some(Self::Euclid),
             "Dot" => Some(Self::Dot),
+            "Manhattan" => Some(Self::Manhattan),
+            "Hamming" => Some(Self::Hamming),
+            "Jaccard" => Some(Self::Jaccard),
             _ => None,
         }
     }
@@ -1102,6 +1388,43 @@ impl ReplicaState {
         }
     }
 }
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum OptimizationStage {
+    /// Optimized segments are being wrapped into proxies
+    Preparing = 0,
+    /// Points are being copied into the optimized segment
+    CopyingPoints = 1,
+    /// Indexes of the optimized segment are being built
+    BuildingIndex = 2,
+    /// Optimized segment is being swapped with the original ones
+    Finalizing = 3,
+}
+impl OptimizationStage {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            OptimizationStage::Preparing => "Preparing",
+            OptimizationStage::CopyingPoints => "CopyingPoints",
+            OptimizationStage::BuildingIndex => "BuildingIndex",
+            OptimizationStage::Finalizing => "Finalizing",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Preparing" => Some(Self::Preparing),
+            "CopyingPoints" => Some(Self::CopyingPoints),
+            "BuildingIndex" => Some(Self::BuildingIndex),
+            "Finalizing" => Some(Self::Finalizing),
+            _ => None,
+        }
+    }
+}
 /// Generated client implementations.
 pub mod collections_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -1450,6 +1773,64 @@ pub mod collections_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        ///
+        /// Get running and queued optimizations of local shards of a collection
+        pub async fn collection_optimizations(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CollectionOptimizationsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOptimizationsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Collections/CollectionOptimizations",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("qdrant.Collections", "CollectionOptimizations"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Pause, resume or trigger optimizations of the collection shards on all peers
+        pub async fn update_collection_optimizations(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateCollectionOptimizationsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpdateCollectionOptimizationsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Collections/UpdateCollectionOptimizations",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("qdrant.Collections", "UpdateCollectionOptimizations"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -1549,6 +1930,24 @@ pub mod collections_server {
             tonic::Response<super::UpdateCollectionClusterSetupResponse>,
             tonic::Status,
         >;
+        ///
+        /// Get running and queued optimizations of local shards of a collection
+        async fn collection_optimizations(
+            &self,
+            request: tonic::Request<super::CollectionOptimizationsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOptimizationsResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Pause, resume or trigger optimizations of the collection shards on all peers
+        async fn update_collection_optimizations(
+            &self,
+            request: tonic::Request<super::UpdateCollectionOptimizationsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpdateCollectionOptimizationsResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct CollectionsServer<T: Collections> {
@@ -2098,22 +2497,128 @@ pub mod collections_server {
                     };
                     Box::pin(fut)
                 }
-                _ => {
-                    Box::pin(async move {
-                        Ok(
-                            http::Response::builder()
-                                .status(200)
-                                .header("grpc-status", "12")
-                                .header("content-type", "application/grpc")
-                                .body(empty_body())
-                                .unwrap(),
-                        )
-                    })
-                }
-            }
-        }
-    }
-    impl<T: Collections> Clone for CollectionsServer<T> {
+                "/qdrant.Collections/CollectionOptimizations" => {
+                    #[allow(non_camel_case_types)]
+                    struct CollectionOptimizationsSvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<
+                        super::UpdateCollectionClusterSetupRequest,
+                    > for CollectionOptimizationsSvc<T> {
+                        type Response = super::CollectionOptimizationsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::UpdateCollectionClusterSetupRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::collection_optimizations(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CollectionOptimizationsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Collections/UpdateCollectionOptimizations" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateCollectionOptimizationsSvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<
+                        super::UpdateCollectionClusterSetupRequest,
+                    > for UpdateCollectionOptimizationsSvc<T> {
+                        type Response = super::UpdateCollectionOptimizationsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::UpdateCollectionClusterSetupRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::update_collection_optimizations(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdateCollectionOptimizationsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", "12")
+                                .header("content-type", "application/grpc")
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T: Collections> Clone for CollectionsServer<T> {
         fn clone(&self) -> Self {
             let inner = self.inner.clone();
             Self {
@@ -2125,42 +2630,458 @@ pub mod collections_server {
             }
         }
     }
-    impl<T: Collections> Clone for _Inner<T> {
-        fn clone(&self) -> Self {
-            Self(Arc::clone(&self.0))
+    impl<T: Collections> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(Arc::clone(&self.0))
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: Collections> tonic::server::NamedService for CollectionsServer<T> {
+        const NAME: &'static str = "qdrant.Collections";
+    }
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetCollectionInfoRequestInternal {
+    #[prost(message, optional, tag = "1")]
+    pub get_collection_info_request: ::core::option::Option<GetCollectionInfoRequest>,
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InitiateShardTransferRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Id of the temporary shard
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetShardDigestRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Id of the shard
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
+    /// Number of buckets to split the points into
+    #[prost(uint32, tag = "3")]
+    #[validate(range(min = 1))]
+    pub buckets: u32,
+    /// If set - list digests of the points in this bucket instead
+    #[prost(uint32, optional, tag = "4")]
+    pub bucket: ::core::option::Option<u32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointDigest {
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<PointId>,
+    /// Version of the point on this replica
+    #[prost(uint64, tag = "2")]
+    pub version: u64,
+    /// Hash of the point vectors and payload
+    #[prost(uint64, tag = "3")]
+    pub hash: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetShardDigestResponse {
+    /// Hash of all bucket hashes
+    #[prost(uint64, tag = "1")]
+    pub root: u64,
+    /// Hash of every bucket
+    #[prost(uint64, repeated, tag = "2")]
+    pub bucket_hashes: ::prost::alloc::vec::Vec<u64>,
+    /// Number of points in the shard
+    #[prost(uint64, tag = "3")]
+    pub points_count: u64,
+    /// Digests of the points in the requested bucket
+    #[prost(message, repeated, tag = "4")]
+    pub points: ::prost::alloc::vec::Vec<PointDigest>,
+    /// Time spent to process
+    #[prost(double, tag = "5")]
+    pub time: f64,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WaitForOperationRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Id of the shard
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
+    /// Number of the operation in the local replica
+    #[prost(uint64, tag = "3")]
+    pub operation_id: u64,
+    /// Wait timeout in milliseconds
+    #[prost(uint64, tag = "4")]
+    pub timeout: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WaitForOperationResponse {
+    /// If the operation is applied by the local replica
+    #[prost(bool, tag = "1")]
+    pub applied: bool,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateShardSnapshotRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Id of the shard
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateShardSnapshotResponse {
+    /// Name of the created snapshot file
+    #[prost(string, tag = "1")]
+    pub snapshot_name: ::prost::alloc::string::String,
+    /// Size of the snapshot file in bytes
+    #[prost(uint64, tag = "2")]
+    pub size: u64,
+    /// Time spent to process
+    #[prost(double, tag = "3")]
+    pub time: f64,
+    /// SHA256 digest of the snapshot file
+    #[prost(string, optional, tag = "4")]
+    pub checksum: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetShardSnapshotChunkRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Id of the shard
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
+    /// Name of the snapshot file
+    #[prost(string, tag = "3")]
+    #[validate(length(min = 1))]
+    pub snapshot_name: ::prost::alloc::string::String,
+    /// Offset of the chunk in bytes
+    #[prost(uint64, tag = "4")]
+    pub offset: u64,
+    /// Maximal length of the chunk in bytes
+    #[prost(uint64, tag = "5")]
+    #[validate(range(min = 1))]
+    pub length: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetShardSnapshotChunkResponse {
+    /// Content of the chunk, shorter than requested at the end of the file
+    #[prost(bytes = "vec", tag = "1")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteShardSnapshotRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Id of the shard
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
+    /// Name of the snapshot file
+    #[prost(string, tag = "3")]
+    #[validate(length(min = 1))]
+    pub snapshot_name: ::prost::alloc::string::String,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ForceOptimizationRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Id of the shard
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
+    #[prost(oneof = "force_optimization_request::Operation", tags = "3, 4")]
+    pub operation: ::core::option::Option<force_optimization_request::Operation>,
+}
+/// Nested message and enum types in `ForceOptimizationRequest`.
+pub mod force_optimization_request {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Operation {
+        #[prost(message, tag = "3")]
+        Merge(super::MergeSegments),
+        #[prost(message, tag = "4")]
+        Vacuum(super::VacuumSegments),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ForceOptimizationResponse {
+    /// If any optimization was started by the local replica
+    #[prost(bool, tag = "1")]
+    pub started: bool,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecallQuery {
+    /// Id of the sampled point
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<PointId>,
+    /// Vectors of the point, used as queries
+    #[prost(message, optional, tag = "2")]
+    pub vectors: ::core::option::Option<NamedVectors>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SampleRecallQueriesRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Id of the shard
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
+    /// Sample only points, which satisfy this conditions
+    #[prost(message, optional, tag = "3")]
+    pub filter: ::core::option::Option<Filter>,
+    /// Number of points to sample
+    #[prost(uint64, tag = "4")]
+    #[validate(range(min = 1))]
+    pub sample: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SampleRecallQueriesResponse {
+    /// Sampled queries
+    #[prost(message, repeated, tag = "1")]
+    pub queries: ::prost::alloc::vec::Vec<RecallQuery>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MeasureRecallRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Id of the shard
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
+    /// Queries to search
+    #[prost(message, repeated, tag = "3")]
+    pub queries: ::prost::alloc::vec::Vec<RecallQuery>,
+    /// Filter of the searches
+    #[prost(message, optional, tag = "4")]
+    pub filter: ::core::option::Option<Filter>,
+    /// Number of nearest neighbors to compare
+    #[prost(uint64, tag = "5")]
+    #[validate(range(min = 1))]
+    pub limit: u64,
+    /// Params of the approximate search
+    #[prost(message, optional, tag = "6")]
+    pub params: ::core::option::Option<SearchParams>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecallEstimate {
+    /// Name of the vector
+    #[prost(string, tag = "1")]
+    pub vector: ::prost::alloc::string::String,
+    /// How quantized vectors are used
+    #[prost(enumeration = "RecallQuantizationMode", tag = "2")]
+    pub quantization: i32,
+    /// Number of queries, for which the exact search found any points
+    #[prost(uint64, tag = "3")]
+    pub queries: u64,
+    /// Fraction of the exact search results, found by the approximate search
+    #[prost(double, tag = "4")]
+    pub recall: f64,
+    /// Average duration of the approximate search, in seconds
+    #[prost(double, tag = "5")]
+    pub latency: f64,
+    /// Average duration of the exact search, in seconds
+    #[prost(double, tag = "6")]
+    pub exact_latency: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SegmentRecallEstimation {
+    /// Id of the segment in the shard
+    #[prost(uint64, tag = "1")]
+    pub segment_id: u64,
+    /// Type of the segment
+    #[prost(enumeration = "SegmentType", tag = "2")]
+    pub segment_type: i32,
+    /// Number of points in the segment
+    #[prost(uint64, tag = "3")]
+    pub points_count: u64,
+    /// Recall of every measured vector and quantization mode
+    #[prost(message, repeated, tag = "4")]
+    pub estimates: ::prost::alloc::vec::Vec<RecallEstimate>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecallSearchMeasurement {
+    /// Results of the exact search
+    #[prost(message, repeated, tag = "1")]
+    pub exact: ::prost::alloc::vec::Vec<ScoredPoint>,
+    /// Results of the approximate search
+    #[prost(message, repeated, tag = "2")]
+    pub approximate: ::prost::alloc::vec::Vec<ScoredPoint>,
+    /// Duration of the exact search, in seconds
+    #[prost(double, tag = "3")]
+    pub exact_time: f64,
+    /// Duration of the approximate search, in seconds
+    #[prost(double, tag = "4")]
+    pub approximate_time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecallTargetMeasurements {
+    /// Measurement of every query
+    #[prost(message, repeated, tag = "1")]
+    pub queries: ::prost::alloc::vec::Vec<RecallSearchMeasurement>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MeasureRecallResponse {
+    /// Recall in every segment of the shard
+    #[prost(message, repeated, tag = "1")]
+    pub segments: ::prost::alloc::vec::Vec<SegmentRecallEstimation>,
+    /// Measurements of every vector and quantization mode, merged over all segments
+    #[prost(message, repeated, tag = "2")]
+    pub measurements: ::prost::alloc::vec::Vec<RecallTargetMeasurements>,
+    /// Time spent to process
+    #[prost(double, tag = "3")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum RecallQuantizationMode {
+    /// Quantized vectors are ignored
+    Disabled = 0,
+    /// Only quantized vectors are used for scoring
+    Quantized = 1,
+    /// Candidates found with quantized vectors are rescored with original vectors
+    Rescored = 2,
+}
+impl RecallQuantizationMode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            RecallQuantizationMode::Disabled => "Disabled",
+            RecallQuantizationMode::Quantized => "Quantized",
+            RecallQuantizationMode::Rescored => "Rescored",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Disabled" => Some(Self::Disabled),
+            "Quantized" => Some(Self::Quantized),
+            "Rescored" => Some(Self::Rescored),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SegmentType {
+    /// No index built for the segment
+    Plain = 0,
+    /// Segment with some sort of index built
+    Indexed = 1,
+    /// Some special index
+    Special = 2,
+}
+impl SegmentType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SegmentType::Plain => "Plain",
+            SegmentType::Indexed => "Indexed",
+            SegmentType::Special => "Special",
         }
     }
-    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(f, "{:?}", self.0)
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Plain" => Some(Self::Plain),
+            "Indexed" => Some(Self::Indexed),
+            "Special" => Some(Self::Special),
+            _ => None,
         }
     }
-    impl<T: Collections> tonic::server::NamedService for CollectionsServer<T> {
-        const NAME: &'static str = "qdrant.Collections";
-    }
-}
-#[derive(validator::Validate)]
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetCollectionInfoRequestInternal {
-    #[prost(message, optional, tag = "1")]
-    pub get_collection_info_request: ::core::option::Option<GetCollectionInfoRequest>,
-    #[prost(uint32, tag = "2")]
-    pub shard_id: u32,
-}
-#[derive(validator::Validate)]
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct InitiateShardTransferRequest {
-    /// Name of the collection
-    #[prost(string, tag = "1")]
-    #[validate(length(min = 1, max = 255))]
-    pub collection_name: ::prost::alloc::string::String,
-    /// Id of the temporary shard
-    #[prost(uint32, tag = "2")]
-    pub shard_id: u32,
 }
 /// Generated client implementations.
 pub mod collections_internal_client {
@@ -2301,6 +3222,222 @@ pub mod collections_internal_client {
                 .insert(GrpcMethod::new("qdrant.CollectionsInternal", "Initiate"));
             self.inner.unary(req, path, codec).await
         }
+        ///
+        /// Get digest of the local shard replica, used to check consistency of the replicas
+        pub async fn get_shard_digest(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetShardDigestRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetShardDigestResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.CollectionsInternal/GetShardDigest",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.CollectionsInternal", "GetShardDigest"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Wait until the local shard replica applies the operation, used by reads with a consistency token
+        pub async fn wait_for_operation(
+            &mut self,
+            request: impl tonic::IntoRequest<super::WaitForOperationRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::WaitForOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.CollectionsInternal/WaitForOperation",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.CollectionsInternal", "WaitForOperation"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Create a snapshot of the local shard replica, used by cluster snapshots
+        pub async fn create_shard_snapshot(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateShardSnapshotRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateShardSnapshotResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.CollectionsInternal/CreateShardSnapshot",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.CollectionsInternal", "CreateShardSnapshot"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Read a chunk of the local shard snapshot file, used to download shard snapshots from other peers
+        pub async fn get_shard_snapshot_chunk(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetShardSnapshotChunkRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetShardSnapshotChunkResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.CollectionsInternal/GetShardSnapshotChunk",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.CollectionsInternal", "GetShardSnapshotChunk"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Delete the local shard snapshot file
+        pub async fn delete_shard_snapshot(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeleteShardSnapshotRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.CollectionsInternal/DeleteShardSnapshot",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.CollectionsInternal", "DeleteShardSnapshot"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Start a merge or vacuum optimization in the local shard replica
+        pub async fn force_optimization(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ForceOptimizationRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ForceOptimizationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.CollectionsInternal/ForceOptimization",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.CollectionsInternal", "ForceOptimization"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Sample points of the local shard replica, which vectors are used as recall estimation queries
+        pub async fn sample_recall_queries(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SampleRecallQueriesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SampleRecallQueriesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.CollectionsInternal/SampleRecallQueries",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.CollectionsInternal", "SampleRecallQueries"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Measure recall of the approximate search in the local shard replica
+        pub async fn measure_recall(
+            &mut self,
+            request: impl tonic::IntoRequest<super::MeasureRecallRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::MeasureRecallResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.CollectionsInternal/MeasureRecall",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.CollectionsInternal", "MeasureRecall"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -2328,6 +3465,78 @@ pub mod collections_internal_server {
             tonic::Response<super::CollectionOperationResponse>,
             tonic::Status,
         >;
+        ///
+        /// Get digest of the local shard replica, used to check consistency of the replicas
+        async fn get_shard_digest(
+            &self,
+            request: tonic::Request<super::GetShardDigestRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetShardDigestResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Wait until the local shard replica applies the operation, used by reads with a consistency token
+        async fn wait_for_operation(
+            &self,
+            request: tonic::Request<super::WaitForOperationRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::WaitForOperationResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Create a snapshot of the local shard replica, used by cluster snapshots
+        async fn create_shard_snapshot(
+            &self,
+            request: tonic::Request<super::CreateShardSnapshotRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateShardSnapshotResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Read a chunk of the local shard snapshot file, used to download shard snapshots from other peers
+        async fn get_shard_snapshot_chunk(
+            &self,
+            request: tonic::Request<super::GetShardSnapshotChunkRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetShardSnapshotChunkResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Delete the local shard snapshot file
+        async fn delete_shard_snapshot(
+            &self,
+            request: tonic::Request<super::DeleteShardSnapshotRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Start a merge or vacuum optimization in the local shard replica
+        async fn force_optimization(
+            &self,
+            request: tonic::Request<super::ForceOptimizationRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ForceOptimizationResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Sample points of the local shard replica, which vectors are used as recall estimation queries
+        async fn sample_recall_queries(
+            &self,
+            request: tonic::Request<super::SampleRecallQueriesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SampleRecallQueriesResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Measure recall of the approximate search in the local shard replica
+        async fn measure_recall(
+            &self,
+            request: tonic::Request<super::MeasureRecallRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::MeasureRecallResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct CollectionsInternalServer<T: CollectionsInternal> {
@@ -2410,26 +3619,348 @@ pub mod collections_internal_server {
             match req.uri().path() {
                 "/qdrant.CollectionsInternal/Get" => {
                     #[allow(non_camel_case_types)]
-                    struct GetSvc<T: CollectionsInternal>(pub Arc<T>);
+                    struct GetSvc<T: CollectionsInternal>(pub Arc<T>);
+                    impl<
+                        T: CollectionsInternal,
+                    > tonic::server::UnaryService<
+                        super::GetCollectionInfoRequestInternal,
+                    > for GetSvc<T> {
+                        type Response = super::GetCollectionInfoResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::GetCollectionInfoRequestInternal,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CollectionsInternal>::get(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.CollectionsInternal/Initiate" => {
+                    #[allow(non_camel_case_types)]
+                    struct InitiateSvc<T: CollectionsInternal>(pub Arc<T>);
+                    impl<
+                        T: CollectionsInternal,
+                    > tonic::server::UnaryService<super::InitiateShardTransferRequest>
+                    for InitiateSvc<T> {
+                        type Response = super::CollectionOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::InitiateShardTransferRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CollectionsInternal>::initiate(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = InitiateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.CollectionsInternal/GetShardDigest" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetShardDigestSvc<T: CollectionsInternal>(pub Arc<T>);
+                    impl<
+                        T: CollectionsInternal,
+                    > tonic::server::UnaryService<super::GetShardDigestRequest>
+                    for GetShardDigestSvc<T> {
+                        type Response = super::GetShardDigestResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetShardDigestRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CollectionsInternal>::get_shard_digest(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetShardDigestSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.CollectionsInternal/WaitForOperation" => {
+                    #[allow(non_camel_case_types)]
+                    struct WaitForOperationSvc<T: CollectionsInternal>(pub Arc<T>);
+                    impl<
+                        T: CollectionsInternal,
+                    > tonic::server::UnaryService<super::WaitForOperationRequest>
+                    for WaitForOperationSvc<T> {
+                        type Response = super::WaitForOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::WaitForOperationRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CollectionsInternal>::wait_for_operation(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = WaitForOperationSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.CollectionsInternal/CreateShardSnapshot" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateShardSnapshotSvc<T: CollectionsInternal>(pub Arc<T>);
+                    impl<
+                        T: CollectionsInternal,
+                    > tonic::server::UnaryService<super::CreateShardSnapshotRequest>
+                    for CreateShardSnapshotSvc<T> {
+                        type Response = super::CreateShardSnapshotResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateShardSnapshotRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CollectionsInternal>::create_shard_snapshot(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CreateShardSnapshotSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.CollectionsInternal/GetShardSnapshotChunk" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetShardSnapshotChunkSvc<T: CollectionsInternal>(pub Arc<T>);
+                    impl<
+                        T: CollectionsInternal,
+                    > tonic::server::UnaryService<super::GetShardSnapshotChunkRequest>
+                    for GetShardSnapshotChunkSvc<T> {
+                        type Response = super::GetShardSnapshotChunkResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetShardSnapshotChunkRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CollectionsInternal>::get_shard_snapshot_chunk(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetShardSnapshotChunkSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.CollectionsInternal/DeleteShardSnapshot" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteShardSnapshotSvc<T: CollectionsInternal>(pub Arc<T>);
+                    impl<
+                        T: CollectionsInternal,
+                    > tonic::server::UnaryService<super::DeleteShardSnapshotRequest>
+                    for DeleteShardSnapshotSvc<T> {
+                        type Response = super::CollectionOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteShardSnapshotRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CollectionsInternal>::delete_shard_snapshot(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteShardSnapshotSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.CollectionsInternal/ForceOptimization" => {
+                    #[allow(non_camel_case_types)]
+                    struct ForceOptimizationSvc<T: CollectionsInternal>(pub Arc<T>);
                     impl<
                         T: CollectionsInternal,
-                    > tonic::server::UnaryService<
-                        super::GetCollectionInfoRequestInternal,
-                    > for GetSvc<T> {
-                        type Response = super::GetCollectionInfoResponse;
+                    > tonic::server::UnaryService<super::ForceOptimizationRequest>
+                    for ForceOptimizationSvc<T> {
+                        type Response = super::ForceOptimizationResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<
-                                super::GetCollectionInfoRequestInternal,
-                            >,
+                            request: tonic::Request<super::ForceOptimizationRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as CollectionsInternal>::get(&inner, request).await
+                                <T as CollectionsInternal>::force_optimization(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -2441,7 +3972,7 @@ pub mod collections_internal_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = GetSvc(inner);
+                        let method = ForceOptimizationSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -2457,25 +3988,25 @@ pub mod collections_internal_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.CollectionsInternal/Initiate" => {
+                "/qdrant.CollectionsInternal/SampleRecallQueries" => {
                     #[allow(non_camel_case_types)]
-                    struct InitiateSvc<T: CollectionsInternal>(pub Arc<T>);
+                    struct SampleRecallQueriesSvc<T: CollectionsInternal>(pub Arc<T>);
                     impl<
                         T: CollectionsInternal,
-                    > tonic::server::UnaryService<super::InitiateShardTransferRequest>
-                    for InitiateSvc<T> {
-                        type Response = super::CollectionOperationResponse;
+                    > tonic::server::UnaryService<super::SampleRecallQueriesRequest>
+                    for SampleRecallQueriesSvc<T> {
+                        type Response = super::SampleRecallQueriesResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::InitiateShardTransferRequest>,
+                            request: tonic::Request<super::SampleRecallQueriesRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as CollectionsInternal>::initiate(&inner, request).await
+                                <T as CollectionsInternal>::sample_recall_queries(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -2487,7 +4018,53 @@ pub mod collections_internal_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = InitiateSvc(inner);
+                        let method = SampleRecallQueriesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.CollectionsInternal/MeasureRecall" => {
+                    #[allow(non_camel_case_types)]
+                    struct MeasureRecallSvc<T: CollectionsInternal>(pub Arc<T>);
+                    impl<
+                        T: CollectionsInternal,
+                    > tonic::server::UnaryService<super::MeasureRecallRequest>
+                    for MeasureRecallSvc<T> {
+                        type Response = super::MeasureRecallResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::MeasureRecallRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CollectionsInternal>::measure_recall(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = MeasureRecallSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -2678,7 +4255,7 @@ pub mod read_consistency {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PointId {
-    #[prost(oneof = "point_id::PointIdOptions", tags = "1, 2")]
+    #[prost(oneof = "point_id::PointIdOptions", tags = "1, 2, 3")]
     pub point_id_options: ::core::option::Option<point_id::PointIdOptions>,
 }
 /// Nested message and enum types in `PointId`.
@@ -2693,6 +4270,9 @@ pub mod point_id {
         /// UUID
         #[prost(string, tag = "2")]
         Uuid(::prost::alloc::string::String),
+        /// Arbitrary string ID, from 1 to 64 bytes long. Canonical UUIDs must be given as `uuid`
+        #[prost(string, tag = "3")]
+        Str(::prost::alloc::string::String),
     }
 }
 #[derive(serde::Serialize)]
@@ -2760,6 +4340,9 @@ pub struct GetPoints {
     /// Options for specifying read consistency guarantees
     #[prost(message, optional, tag = "6")]
     pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Token of the update, which the result should observe
+    #[prost(string, optional, tag = "7")]
+    pub consistency_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -2834,6 +4417,9 @@ pub struct SetPayloadPoints {
     /// Write ordering guarantees
     #[prost(message, optional, tag = "6")]
     pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Option for indicate property of payload
+    #[prost(string, optional, tag = "7")]
+    pub key: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -2847,7 +4433,7 @@ pub struct DeletePayloadPoints {
     /// Wait until the changes have been applied?
     #[prost(bool, optional, tag = "2")]
     pub wait: ::core::option::Option<bool>,
-    /// List of keys to delete
+    /// List of keys to delete, nested keys are supported
     #[prost(string, repeated, tag = "3")]
     pub keys: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     /// Affected points
@@ -2880,6 +4466,65 @@ pub struct ClearPayloadPoints {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IncrementPayloadPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// Payload key of the numeric field, nested keys are supported
+    #[prost(string, tag = "3")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Number to add to the field, negative values decrease it
+    #[prost(message, optional, tag = "4")]
+    pub value: ::core::option::Option<Value>,
+    /// Number to initialize the absent field with, default is 0
+    #[prost(message, optional, tag = "5")]
+    pub initial: ::core::option::Option<Value>,
+    /// Affected points
+    #[prost(message, optional, tag = "6")]
+    pub points_selector: ::core::option::Option<PointsSelector>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "7")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdatePayloadArrayPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// Payload key of the array field, nested keys are supported
+    #[prost(string, tag = "3")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Values to add to or remove from the array
+    #[prost(message, repeated, tag = "4")]
+    #[validate(length(min = 1))]
+    pub values: ::prost::alloc::vec::Vec<Value>,
+    /// Type of the array update
+    #[prost(enumeration = "PayloadArrayOperation", tag = "5")]
+    pub operation: i32,
+    /// Affected points
+    #[prost(message, optional, tag = "6")]
+    pub points_selector: ::core::option::Option<PointsSelector>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "7")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreateFieldIndexCollection {
     /// name of the collection
     #[prost(string, tag = "1")]
@@ -3065,6 +4710,164 @@ pub struct SearchParams {
     /// guarantee that all uploaded vectors will be included in search results
     #[prost(bool, optional, tag = "4")]
     pub indexed_only: ::core::option::Option<bool>,
+    ///
+    /// Params relevant to IVF index. Number of closest inverted lists to scan.
+    /// Larger the value - more accurate the result, more time required for search.
+    #[prost(uint64, optional, tag = "5")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub nprobe: ::core::option::Option<u64>,
+    ///
+    /// Desired fraction of true nearest neighbors to find, between 0 and 1.
+    /// Search parameters are chosen by the recall of the index, calibrated in the background
+    /// after the index is built. Default parameters are used until then.
+    /// Explicitly set `hnsw_ef` and quantization `oversampling` take precedence.
+    #[prost(double, optional, tag = "6")]
+    #[validate(custom = "crate::grpc::validate::validate_f64_range_1")]
+    pub target_recall: ::core::option::Option<f64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Formula {
+    /// Expression of the final score, variable `$score` refers to the vector similarity score
+    #[prost(message, optional, tag = "1")]
+    pub expression: ::core::option::Option<Expression>,
+    /// Values of the payload keys, used if a found point has no value for the key
+    #[prost(map = "string, message", tag = "2")]
+    pub defaults: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
+    /// Number of the most similar points to rescore with the formula in each shard, default is `limit + offset`
+    #[prost(uint64, optional, tag = "3")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub candidates: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Expression {
+    #[prost(
+        oneof = "expression::Variant",
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18"
+    )]
+    pub variant: ::core::option::Option<expression::Variant>,
+}
+/// Nested message and enum types in `Expression`.
+pub mod expression {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        #[prost(double, tag = "1")]
+        Constant(f64),
+        /// Payload key with a number value, or `$score`
+        #[prost(string, tag = "2")]
+        Variable(::prost::alloc::string::String),
+        /// 1 if the point matches the filter, 0 otherwise
+        #[prost(message, tag = "3")]
+        Filter(super::Filter),
+        /// Haversine distance in meters
+        #[prost(message, tag = "4")]
+        GeoDistance(super::GeoDistance),
+        /// Datetime in RFC 3339 format, evaluated to seconds since the Unix epoch
+        #[prost(string, tag = "5")]
+        Datetime(::prost::alloc::string::String),
+        /// Payload key with a datetime in RFC 3339 format
+        #[prost(string, tag = "6")]
+        DatetimeKey(::prost::alloc::string::String),
+        /// Product of the expressions
+        #[prost(message, tag = "7")]
+        Mult(::prost::alloc::boxed::Box<super::MultExpression>),
+        /// Sum of the expressions
+        #[prost(message, tag = "8")]
+        Sum(::prost::alloc::boxed::Box<super::SumExpression>),
+        #[prost(message, tag = "9")]
+        Neg(::prost::alloc::boxed::Box<super::Expression>),
+        #[prost(message, tag = "10")]
+        Div(::prost::alloc::boxed::Box<super::DivExpression>),
+        #[prost(message, tag = "11")]
+        Abs(::prost::alloc::boxed::Box<super::Expression>),
+        #[prost(message, tag = "12")]
+        Sqrt(::prost::alloc::boxed::Box<super::Expression>),
+        #[prost(message, tag = "13")]
+        Pow(::prost::alloc::boxed::Box<super::PowExpression>),
+        /// Exponent of the expression, `e^x`
+        #[prost(message, tag = "14")]
+        Exp(::prost::alloc::boxed::Box<super::Expression>),
+        /// Natural logarithm of the expression
+        #[prost(message, tag = "15")]
+        Ln(::prost::alloc::boxed::Box<super::Expression>),
+        /// Linear decay, `max(0, 1 - (1 - midpoint) / scale * abs(x - target))`
+        #[prost(message, tag = "16")]
+        LinDecay(::prost::alloc::boxed::Box<super::DecayParamsExpression>),
+        /// Exponential decay, `exp(ln(midpoint) / scale * abs(x - target))`
+        #[prost(message, tag = "17")]
+        ExpDecay(::prost::alloc::boxed::Box<super::DecayParamsExpression>),
+        /// Gaussian decay, `exp(ln(midpoint) / scale^2 * (x - target)^2)`
+        #[prost(message, tag = "18")]
+        GaussDecay(::prost::alloc::boxed::Box<super::DecayParamsExpression>),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoDistance {
+    #[prost(message, optional, tag = "1")]
+    pub origin: ::core::option::Option<GeoPoint>,
+    /// Payload key with the geo point
+    #[prost(string, tag = "2")]
+    pub to: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MultExpression {
+    #[prost(message, repeated, tag = "1")]
+    pub mult: ::prost::alloc::vec::Vec<Expression>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SumExpression {
+    #[prost(message, repeated, tag = "1")]
+    pub sum: ::prost::alloc::vec::Vec<Expression>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DivExpression {
+    #[prost(message, optional, boxed, tag = "1")]
+    pub left: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    #[prost(message, optional, boxed, tag = "2")]
+    pub right: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    /// Result of the division, if `right` is zero
+    #[prost(double, optional, tag = "3")]
+    pub by_zero_default: ::core::option::Option<f64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PowExpression {
+    #[prost(message, optional, boxed, tag = "1")]
+    pub base: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    #[prost(message, optional, boxed, tag = "2")]
+    pub exponent: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecayParamsExpression {
+    /// Value to decay
+    #[prost(message, optional, boxed, tag = "1")]
+    pub x: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    /// Value of `x`, at which the function is 1, default is 0
+    #[prost(message, optional, boxed, tag = "2")]
+    pub target: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    /// Distance from the target, at which the function is equal to `midpoint`, default is 1
+    #[prost(double, optional, tag = "3")]
+    pub scale: ::core::option::Option<f64>,
+    /// Value of the function at the `scale` distance from the target, in range (0, 1), default is 0.5
+    #[prost(double, optional, tag = "4")]
+    pub midpoint: ::core::option::Option<f64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3109,6 +4912,16 @@ pub struct SearchPoints {
     /// Options for specifying read consistency guarantees
     #[prost(message, optional, tag = "12")]
     pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// If true - return details of how the search was performed in each shard and segment
+    #[prost(bool, optional, tag = "13")]
+    pub profile: ::core::option::Option<bool>,
+    /// Token of the update, which the result should observe
+    #[prost(string, optional, tag = "14")]
+    pub consistency_token: ::core::option::Option<::prost::alloc::string::String>,
+    /// Compute the final score of the found points with this formula
+    #[prost(message, optional, tag = "15")]
+    #[validate]
+    pub formula: ::core::option::Option<Formula>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3125,6 +4938,9 @@ pub struct SearchBatchPoints {
     /// Options for specifying read consistency guarantees
     #[prost(message, optional, tag = "3")]
     pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Token of the update, which the result should observe
+    #[prost(string, optional, tag = "4")]
+    pub consistency_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -3191,6 +5007,9 @@ pub struct SearchPointGroups {
     /// Options for specifying how to use the group id to lookup points in another collection
     #[prost(message, optional, tag = "13")]
     pub with_lookup: ::core::option::Option<WithLookup>,
+    /// Token of the update, which the result should observe
+    #[prost(string, optional, tag = "14")]
+    pub consistency_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3220,6 +5039,9 @@ pub struct ScrollPoints {
     /// Options for specifying read consistency guarantees
     #[prost(message, optional, tag = "8")]
     pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Token of the update, which the result should observe
+    #[prost(string, optional, tag = "9")]
+    pub consistency_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -3287,6 +5109,12 @@ pub struct RecommendPoints {
     /// Try to avoid vectors like this
     #[prost(message, repeated, tag = "18")]
     pub negative_vectors: ::prost::alloc::vec::Vec<Vector>,
+    /// If true - return details of how the search was performed in each shard and segment
+    #[prost(bool, optional, tag = "19")]
+    pub profile: ::core::option::Option<bool>,
+    /// Token of the update, which the result should observe
+    #[prost(string, optional, tag = "20")]
+    pub consistency_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3303,6 +5131,9 @@ pub struct RecommendBatchPoints {
     /// Options for specifying read consistency guarantees
     #[prost(message, optional, tag = "3")]
     pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Token of the update, which the result should observe
+    #[prost(string, optional, tag = "4")]
+    pub consistency_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3369,6 +5200,9 @@ pub struct RecommendPointGroups {
     /// Try to avoid vectors like this
     #[prost(message, repeated, tag = "19")]
     pub negative_vectors: ::prost::alloc::vec::Vec<Vector>,
+    /// Token of the update, which the result should observe
+    #[prost(string, optional, tag = "20")]
+    pub consistency_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3418,6 +5252,9 @@ pub mod points_update_operation {
         /// Affected points
         #[prost(message, optional, tag = "2")]
         pub points_selector: ::core::option::Option<super::PointsSelector>,
+        /// Option for indicate property of payload
+        #[prost(string, optional, tag = "3")]
+        pub key: ::core::option::Option<::prost::alloc::string::String>,
     }
     #[derive(serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
@@ -3450,6 +5287,40 @@ pub mod points_update_operation {
     }
     #[derive(serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct IncrementPayload {
+        /// Payload key of the numeric field
+        #[prost(string, tag = "1")]
+        pub key: ::prost::alloc::string::String,
+        /// Number to add to the field
+        #[prost(message, optional, tag = "2")]
+        pub value: ::core::option::Option<super::Value>,
+        /// Number to initialize the absent field with, default is 0
+        #[prost(message, optional, tag = "3")]
+        pub initial: ::core::option::Option<super::Value>,
+        /// Affected points
+        #[prost(message, optional, tag = "4")]
+        pub points_selector: ::core::option::Option<super::PointsSelector>,
+    }
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct UpdatePayloadArray {
+        /// Payload key of the array field
+        #[prost(string, tag = "1")]
+        pub key: ::prost::alloc::string::String,
+        /// Values to add to or remove from the array
+        #[prost(message, repeated, tag = "2")]
+        pub values: ::prost::alloc::vec::Vec<super::Value>,
+        /// Type of the array update
+        #[prost(enumeration = "super::PayloadArrayOperation", tag = "3")]
+        pub operation: i32,
+        /// Affected points
+        #[prost(message, optional, tag = "4")]
+        pub points_selector: ::core::option::Option<super::PointsSelector>,
+    }
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Oneof)]
     pub enum Operation {
         #[prost(message, tag = "1")]
@@ -3468,6 +5339,10 @@ pub mod points_update_operation {
         UpdateVectors(UpdateVectors),
         #[prost(message, tag = "8")]
         DeleteVectors(DeleteVectors),
+        #[prost(message, tag = "9")]
+        IncrementPayload(IncrementPayload),
+        #[prost(message, tag = "10")]
+        UpdatePayloadArray(UpdatePayloadArray),
     }
 }
 #[derive(validator::Validate)]
@@ -3509,6 +5384,9 @@ pub struct UpdateResult {
     /// Operation status
     #[prost(enumeration = "UpdateStatus", tag = "2")]
     pub status: i32,
+    /// Pass it to the following read requests to make sure they observe this update
+    #[prost(string, optional, tag = "3")]
+    pub consistency_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -3579,12 +5457,97 @@ pub struct GroupsResult {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CardinalityEstimationProfile {
+    /// Minimal possible number of matched points
+    #[prost(uint64, tag = "1")]
+    pub min: u64,
+    /// Expected number of matched points
+    #[prost(uint64, tag = "2")]
+    pub exp: u64,
+    /// Maximal possible number of matched points
+    #[prost(uint64, tag = "3")]
+    pub max: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VectorIndexSearchProfile {
+    /// Strategy chosen by the vector index
+    #[prost(enumeration = "SearchStrategy", tag = "1")]
+    pub strategy: i32,
+    /// Cardinality of the filter, estimated by the payload index
+    #[prost(message, optional, tag = "2")]
+    pub cardinality: ::core::option::Option<CardinalityEstimationProfile>,
+    /// Whether cardinality was checked by sampling points
+    #[prost(bool, tag = "3")]
+    pub cardinality_sampled: bool,
+    /// Size of the dynamic candidate list used for HNSW or DiskANN search
+    #[prost(uint64, optional, tag = "4")]
+    pub hnsw_ef: ::core::option::Option<u64>,
+    /// Number of vectors scored during the search
+    #[prost(uint64, tag = "5")]
+    pub scored_vectors: u64,
+    /// Whether quantized vectors were used for scoring
+    #[prost(bool, tag = "6")]
+    pub quantized: bool,
+    /// Number of candidates selected per query, including oversampling
+    #[prost(uint64, tag = "7")]
+    pub candidates: u64,
+    /// Whether candidates were rescored with original vectors
+    #[prost(bool, tag = "8")]
+    pub rescored: bool,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SegmentSearchProfile {
+    /// Unique identifier of the segment
+    #[prost(string, tag = "1")]
+    pub segment_id: ::prost::alloc::string::String,
+    /// Name of the searched vector
+    #[prost(string, tag = "2")]
+    pub vector_name: ::prost::alloc::string::String,
+    /// Number of queries searched together in this segment
+    #[prost(uint64, tag = "3")]
+    pub queries: u64,
+    #[prost(message, optional, tag = "4")]
+    pub search: ::core::option::Option<VectorIndexSearchProfile>,
+    /// Time spent searching the segment
+    #[prost(double, tag = "5")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShardSearchProfile {
+    #[prost(uint32, tag = "1")]
+    pub shard_id: u32,
+    /// Searches performed in every segment of the shard
+    #[prost(message, repeated, tag = "2")]
+    pub segments: ::prost::alloc::vec::Vec<SegmentSearchProfile>,
+    /// Time spent searching the shard
+    #[prost(double, tag = "3")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchProfile {
+    #[prost(message, repeated, tag = "1")]
+    pub shards: ::prost::alloc::vec::Vec<ShardSearchProfile>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SearchResponse {
     #[prost(message, repeated, tag = "1")]
     pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Details of how the search was performed, if requested
+    #[prost(message, optional, tag = "3")]
+    pub profile: ::core::option::Option<SearchProfile>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -3673,6 +5636,9 @@ pub struct RecommendResponse {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Details of how the search was performed, if requested
+    #[prost(message, optional, tag = "3")]
+    pub profile: ::core::option::Option<SearchProfile>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -3720,7 +5686,22 @@ pub struct Filter {
     /// All conditions must NOT match
     #[prost(message, repeated, tag = "3")]
     #[validate]
-    pub must_not: ::prost::alloc::vec::Vec<Condition>,
+    pub must_not: ::prost::alloc::vec::Vec<Condition>,
+    /// At least minimum amount of given conditions should match
+    #[prost(message, optional, tag = "4")]
+    #[validate]
+    pub min_should: ::core::option::Option<MinShould>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MinShould {
+    #[prost(message, repeated, tag = "1")]
+    #[validate]
+    pub conditions: ::prost::alloc::vec::Vec<Condition>,
+    #[prost(uint64, tag = "2")]
+    pub min_count: u64,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4052,6 +6033,39 @@ impl ReadConsistencyType {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum PayloadArrayOperation {
+    /// Append values to the array
+    Append = 0,
+    /// Remove all occurrences of the values from the array
+    Remove = 1,
+    /// Append values which are not present in the array yet
+    AddToSet = 2,
+}
+impl PayloadArrayOperation {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PayloadArrayOperation::Append => "Append",
+            PayloadArrayOperation::Remove => "Remove",
+            PayloadArrayOperation::AddToSet => "AddToSet",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Append" => Some(Self::Append),
+            "Remove" => Some(Self::Remove),
+            "AddToSet" => Some(Self::AddToSet),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum FieldType {
     Keyword = 0,
     Integer = 1,
@@ -4153,6 +6167,59 @@ impl UpdateStatus {
         }
     }
 }
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SearchStrategy {
+    /// All vectors of the segment are scored
+    Plain = 0,
+    /// Exact search was requested, all vectors matching the filter are scored without index
+    Exact = 1,
+    /// HNSW graph is traversed
+    Hnsw = 2,
+    /// Points matching the filter are retrieved from the payload index and scored
+    PayloadIndex = 3,
+    /// HNSW graph is traversed, filter is checked for every visited point
+    FilteredHnsw = 4,
+    /// DiskANN graph is read from disk, filter is checked for every found point
+    DiskAnn = 5,
+    /// Closest inverted lists of the IVF index are scanned, filter is checked for every scanned point
+    Ivf = 6,
+    /// HNSW graph is traversed through two hops of neighbors, which don't match a selective filter
+    AcornHnsw = 7,
+}
+impl SearchStrategy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SearchStrategy::Plain => "Plain",
+            SearchStrategy::Exact => "Exact",
+            SearchStrategy::Hnsw => "Hnsw",
+            SearchStrategy::PayloadIndex => "PayloadIndex",
+            SearchStrategy::FilteredHnsw => "FilteredHnsw",
+            SearchStrategy::DiskAnn => "DiskAnn",
+            SearchStrategy::Ivf => "Ivf",
+            SearchStrategy::AcornHnsw => "AcornHnsw",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Plain" => Some(Self::Plain),
+            "Exact" => Some(Self::Exact),
+            "Hnsw" => Some(Self::Hnsw),
+            "PayloadIndex" => Some(Self::PayloadIndex),
+            "FilteredHnsw" => Some(Self::FilteredHnsw),
+            "DiskAnn" => Some(Self::DiskAnn),
+            "Ivf" => Some(Self::Ivf),
+            "AcornHnsw" => Some(Self::AcornHnsw),
+            _ => None,
+        }
+    }
+}
 /// Generated client implementations.
 pub mod points_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -4467,6 +6534,60 @@ pub mod points_client {
             self.inner.unary(req, path, codec).await
         }
         ///
+        /// Atomically add a number to the numeric payload field of points
+        pub async fn increment_payload(
+            &mut self,
+            request: impl tonic::IntoRequest<super::IncrementPayloadPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/IncrementPayload",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "IncrementPayload"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Append values to or remove values from the array payload field of points
+        pub async fn update_payload_array(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdatePayloadArrayPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/UpdatePayloadArray",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "UpdatePayloadArray"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
         /// Create index for field in collection
         pub async fn create_field_index(
             &mut self,
@@ -4828,6 +6949,24 @@ pub mod points_server {
             tonic::Status,
         >;
         ///
+        /// Atomically add a number to the numeric payload field of points
+        async fn increment_payload(
+            &self,
+            request: tonic::Request<super::IncrementPayloadPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Append values to or remove values from the array payload field of points
+        async fn update_payload_array(
+            &self,
+            request: tonic::Request<super::UpdatePayloadArrayPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        >;
+        ///
         /// Create index for field in collection
         async fn create_field_index(
             &self,
@@ -5401,6 +7540,102 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
+///
+/// Atomically add a number to the numeric payload field of points
+                "/qdrant.Points/IncrementPayload" => {
+                    #[allow(non_camel_case_types)]
+                    struct IncrementPayloadSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::IncrementPayloadPoints>
+                    for IncrementPayloadSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::IncrementPayloadPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::increment_payload(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = IncrementPayloadSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+///
+/// Append values to or remove values from the array payload field of points
+                "/qdrant.Points/UpdatePayloadArray" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdatePayloadArraySvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::UpdatePayloadArrayPoints>
+                    for UpdatePayloadArraySvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdatePayloadArrayPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::update_payload_array(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdatePayloadArraySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.Points/CreateFieldIndex" => {
                     #[allow(non_camel_case_types)]
                     struct CreateFieldIndexSvc<T: Points>(pub Arc<T>);
@@ -6049,6 +8284,28 @@ pub struct ClearPayloadPointsInternal {
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IncrementPayloadPointsInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub increment_payload_points: ::core::option::Option<IncrementPayloadPoints>,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdatePayloadArrayPointsInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub update_payload_array_points: ::core::option::Option<UpdatePayloadArrayPoints>,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreateFieldIndexCollectionInternal {
     #[prost(message, optional, tag = "1")]
     #[validate]
@@ -6159,6 +8416,9 @@ pub struct CoreSearchPoints {
     pub with_vectors: ::core::option::Option<WithVectorsSelector>,
     #[prost(message, optional, tag = "11")]
     pub read_consistency: ::core::option::Option<ReadConsistency>,
+    #[prost(message, optional, tag = "12")]
+    #[validate]
+    pub formula: ::core::option::Option<Formula>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -6175,6 +8435,19 @@ pub struct CoreSearchBatchPointsInternal {
     pub shard_id: ::core::option::Option<u32>,
 }
 #[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CoreSearchBatchProfiledResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<BatchResult>,
+    /// Searches performed in every segment of the shard
+    #[prost(message, repeated, tag = "2")]
+    pub profiles: ::prost::alloc::vec::Vec<SegmentSearchProfile>,
+    /// Time spent to process
+    #[prost(double, tag = "3")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -6421,16 +8694,66 @@ pub mod points_internal_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.PointsInternal/DeleteVectors",
+                "/qdrant.PointsInternal/DeleteVectors",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "DeleteVectors"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_payload(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetPayloadPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/SetPayload",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "SetPayload"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn overwrite_payload(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetPayloadPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/OverwritePayload",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.PointsInternal", "DeleteVectors"));
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "OverwritePayload"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn set_payload(
+        pub async fn delete_payload(
             &mut self,
-            request: impl tonic::IntoRequest<super::SetPayloadPointsInternal>,
+            request: impl tonic::IntoRequest<super::DeletePayloadPointsInternal>,
         ) -> std::result::Result<
             tonic::Response<super::PointsOperationResponse>,
             tonic::Status,
@@ -6446,16 +8769,16 @@ pub mod points_internal_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.PointsInternal/SetPayload",
+                "/qdrant.PointsInternal/DeletePayload",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.PointsInternal", "SetPayload"));
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "DeletePayload"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn overwrite_payload(
+        pub async fn clear_payload(
             &mut self,
-            request: impl tonic::IntoRequest<super::SetPayloadPointsInternal>,
+            request: impl tonic::IntoRequest<super::ClearPayloadPointsInternal>,
         ) -> std::result::Result<
             tonic::Response<super::PointsOperationResponse>,
             tonic::Status,
@@ -6471,16 +8794,16 @@ pub mod points_internal_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.PointsInternal/OverwritePayload",
+                "/qdrant.PointsInternal/ClearPayload",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.PointsInternal", "OverwritePayload"));
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "ClearPayload"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn delete_payload(
+        pub async fn increment_payload(
             &mut self,
-            request: impl tonic::IntoRequest<super::DeletePayloadPointsInternal>,
+            request: impl tonic::IntoRequest<super::IncrementPayloadPointsInternal>,
         ) -> std::result::Result<
             tonic::Response<super::PointsOperationResponse>,
             tonic::Status,
@@ -6496,16 +8819,16 @@ pub mod points_internal_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.PointsInternal/DeletePayload",
+                "/qdrant.PointsInternal/IncrementPayload",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.PointsInternal", "DeletePayload"));
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "IncrementPayload"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn clear_payload(
+        pub async fn update_payload_array(
             &mut self,
-            request: impl tonic::IntoRequest<super::ClearPayloadPointsInternal>,
+            request: impl tonic::IntoRequest<super::UpdatePayloadArrayPointsInternal>,
         ) -> std::result::Result<
             tonic::Response<super::PointsOperationResponse>,
             tonic::Status,
@@ -6521,11 +8844,11 @@ pub mod points_internal_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.PointsInternal/ClearPayload",
+                "/qdrant.PointsInternal/UpdatePayloadArray",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.PointsInternal", "ClearPayload"));
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "UpdatePayloadArray"));
             self.inner.unary(req, path, codec).await
         }
         pub async fn create_field_index(
@@ -6650,6 +8973,31 @@ pub mod points_internal_client {
                 .insert(GrpcMethod::new("qdrant.PointsInternal", "CoreSearchBatch"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn core_search_batch_profiled(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CoreSearchBatchPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::CoreSearchBatchProfiledResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/CoreSearchBatchProfiled",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "CoreSearchBatchProfiled"));
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn scroll(
             &mut self,
             request: impl tonic::IntoRequest<super::ScrollPointsInternal>,
@@ -6812,6 +9160,20 @@ pub mod points_internal_server {
             tonic::Response<super::PointsOperationResponse>,
             tonic::Status,
         >;
+        async fn increment_payload(
+            &self,
+            request: tonic::Request<super::IncrementPayloadPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        >;
+        async fn update_payload_array(
+            &self,
+            request: tonic::Request<super::UpdatePayloadArrayPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        >;
         async fn create_field_index(
             &self,
             request: tonic::Request<super::CreateFieldIndexCollectionInternal>,
@@ -6844,6 +9206,13 @@ pub mod points_internal_server {
             tonic::Response<super::SearchBatchResponse>,
             tonic::Status,
         >;
+        async fn core_search_batch_profiled(
+            &self,
+            request: tonic::Request<super::CoreSearchBatchPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::CoreSearchBatchProfiledResponse>,
+            tonic::Status,
+        >;
         async fn scroll(
             &self,
             request: tonic::Request<super::ScrollPointsInternal>,
@@ -7358,6 +9727,98 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/IncrementPayload" => {
+                    #[allow(non_camel_case_types)]
+                    struct IncrementPayloadSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::IncrementPayloadPointsInternal>
+                    for IncrementPayloadSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::IncrementPayloadPointsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::increment_payload(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = IncrementPayloadSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/UpdatePayloadArray" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdatePayloadArraySvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::UpdatePayloadArrayPointsInternal>
+                    for UpdatePayloadArraySvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdatePayloadArrayPointsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::update_payload_array(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdatePayloadArraySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.PointsInternal/CreateFieldIndex" => {
                     #[allow(non_camel_case_types)]
                     struct CreateFieldIndexSvc<T: PointsInternal>(pub Arc<T>);
@@ -7597,6 +10058,53 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/CoreSearchBatchProfiled" => {
+                    #[allow(non_camel_case_types)]
+                    struct CoreSearchBatchProfiledSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::CoreSearchBatchPointsInternal>
+                    for CoreSearchBatchProfiledSvc<T> {
+                        type Response = super::CoreSearchBatchProfiledResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CoreSearchBatchPointsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::core_search_batch_profiled(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CoreSearchBatchProfiledSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.PointsInternal/Scroll" => {
                     #[allow(non_camel_case_types)]
                     struct ScrollSvc<T: PointsInternal>(pub Arc<T>);
@@ -8835,6 +11343,9 @@ pub struct CreateSnapshotRequest {
     #[prost(string, tag = "1")]
     #[validate(length(min = 1, max = 255))]
     pub collection_name: ::prost::alloc::string::String,
+    /// Name of an existing snapshot to create an incremental snapshot from
+    #[prost(string, optional, tag = "2")]
+    pub base_snapshot: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -8874,6 +11385,9 @@ pub struct SnapshotDescription {
     /// Size of the snapshot in bytes
     #[prost(int64, tag = "3")]
     pub size: i64,
+    /// SHA256 digest of the snapshot file
+    #[prost(string, optional, tag = "4")]
+    pub checksum: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -9140,6 +11654,33 @@ pub mod snapshots_client {
                 .insert(GrpcMethod::new("qdrant.Snapshots", "DeleteFull"));
             self.inner.unary(req, path, codec).await
         }
+        ///
+        /// Create snapshot of all shards of the collection across the cluster
+        pub async fn create_cluster(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateSnapshotRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateSnapshotResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Snapshots/CreateCluster",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Snapshots", "CreateCluster"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -9203,6 +11744,15 @@ pub mod snapshots_server {
             tonic::Response<super::DeleteSnapshotResponse>,
             tonic::Status,
         >;
+        ///
+        /// Create snapshot of all shards of the collection across the cluster
+        async fn create_cluster(
+            &self,
+            request: tonic::Request<super::CreateSnapshotRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateSnapshotResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct SnapshotsServer<T: Snapshots> {
@@ -9559,6 +12109,52 @@ pub mod snapshots_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Snapshots/CreateCluster" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateClusterSvc<T: Snapshots>(pub Arc<T>);
+                    impl<
+                        T: Snapshots,
+                    > tonic::server::UnaryService<super::CreateSnapshotRequest>
+                    for CreateClusterSvc<T> {
+                        type Response = super::CreateSnapshotResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateSnapshotRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Snapshots>::create_cluster(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CreateClusterSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -63,6 +63,8 @@ fn batch_search_bench(c: &mut Criterion) {
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            disk_ann_config: None,
+            ivf_config: None,
         }
         .into(),
         ..CollectionParams::empty()
@@ -140,6 +142,8 @@ fn batch_search_bench(c: &mut Criterion) {
                             with_payload: None,
                             with_vector: None,
                             score_threshold: None,
+                            profile: None,
+                            formula: None,
                         };
                         let result = shard
                             .search(
@@ -172,6 +176,8 @@ fn batch_search_bench(c: &mut Criterion) {
                             with_payload: None,
                             with_vector: None,
                             score_threshold: None,
+                            profile: None,
+                            formula: None,
                         };
                         searches.push(search_query);
                     }
@@ -9,6 +9,7 @@ use crate::operations::config_diff::*;
 use crate::operations::types::*;
 use crate::optimizers_builder::OptimizersConfig;
 use crate::shards::replica_set::{Change, ReplicaState};
+use crate::shards::resharding::ReshardingInfo;
 use crate::shards::shard::{PeerId, ShardId};
 
 impl Collection {
@@ -57,9 +58,10 @@ impl Collection {
     ) -> CollectionResult<()> {
         let mut config = self.collection_config.write().await;
         update_vectors_diff.check_vector_names(&config.params)?;
-        config
-            .params
-            .update_vectors_from_diff(update_vectors_diff)?;
+        let mut params = config.params.clone();
+        params.update_vectors_from_diff(update_vectors_diff)?;
+        params.check_vector_params(config.quantization_config.as_ref())?;
+        config.params = params;
         config.save(&self.path)?;
         Ok(())
     }
@@ -109,26 +111,18 @@ impl Collection {
     ) -> CollectionResult<()> {
         {
             let mut config = self.collection_config.write().await;
-            match quantization_config_diff {
-                QuantizationConfigDiff::Scalar(scalar) => {
-                    config
-                        .quantization_config
-                        .replace(QuantizationConfig::Scalar(scalar));
-                }
+            let quantization_config = match quantization_config_diff {
+                QuantizationConfigDiff::Scalar(scalar) => Some(QuantizationConfig::Scalar(scalar)),
                 QuantizationConfigDiff::Product(product) => {
-                    config
-                        .quantization_config
-                        .replace(QuantizationConfig::Product(product));
-                }
-                QuantizationConfigDiff::Binary(binary) => {
-                    config
-                        .quantization_config
-                        .replace(QuantizationConfig::Binary(binary));
-                }
-                QuantizationConfigDiff::Disabled(_) => {
-                    config.quantization_config = None;
+                    Some(QuantizationConfig::Product(product))
                 }
-            }
+                QuantizationConfigDiff::Binary(binary) => Some(QuantizationConfig::Binary(binary)),
+                QuantizationConfigDiff::Disabled(_) => None,
+            };
+            config
+                .params
+                .check_vector_params(quantization_config.as_ref())?;
+            config.quantization_config = quantization_config;
         }
         self.collection_config.read().await.save(&self.path)?;
         Ok(())
@@ -224,6 +218,13 @@ impl Collection {
                     .and_modify(|info_schema| info_schema.points += response_schema.points)
                     .or_insert(response_schema);
             }
+
+            for (vector_name, response_calibration) in response.search_calibration {
+                info.search_calibration
+                    .entry(vector_name)
+                    .or_default()
+                    .merge(&response_calibration);
+            }
         }
 
         Ok(info)
@@ -271,6 +272,10 @@ impl Collection {
             }
         }
         let shard_transfers = shards_holder.get_shard_transfer_info();
+        let resharding = shards_holder
+            .resharding_state()
+            .as_ref()
+            .map(ReshardingInfo::from);
 
         // sort by shard_id
         local_shards.sort_by_key(|k| k.shard_id);
@@ -282,6 +287,7 @@ impl Collection {
             local_shards,
             remote_shards,
             shard_transfers,
+            resharding,
         };
         Ok(info)
     }
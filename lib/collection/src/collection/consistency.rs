@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use futures::future;
+use segment::types::SeqNumberType;
+
+use super::Collection;
+use crate::operations::consistency_ops::{
+    ConsistencyCheckRequest, ConsistencyReport, ShardConsistencyReport,
+};
+use crate::operations::consistency_params::ConsistencyToken;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::consistency::PointDigest;
+use crate::shards::shard::ShardId;
+
+impl Collection {
+    /// Compare replicas of the collection shards and optionally repair the diverged points
+    ///
+    /// If `only_leading` is set, only shards, for which this peer is the highest active replica, are checked.
+    /// It allows to run scheduled checks on every peer without comparing the same shard several times.
+    pub async fn check_consistency(
+        &self,
+        request: ConsistencyCheckRequest,
+        only_leading: bool,
+    ) -> CollectionResult<ConsistencyReport> {
+        let shards_holder = self.shards_holder.read().await;
+
+        if let Some(shard_id) = request.shard_id {
+            if shards_holder.get_shard(&shard_id).is_none() {
+                return Err(CollectionError::bad_request(format!(
+                    "Shard {shard_id} does not exist"
+                )));
+            }
+        }
+
+        let mut shards: Vec<_> = shards_holder
+            .get_shards()
+            .filter(|(shard_id, _)| request.shard_id.map_or(true, |id| id == **shard_id))
+            .filter(|(_, replica_set)| {
+                !only_leading
+                    || replica_set.highest_alive_replica_peer_id() == Some(self.this_peer_id)
+            })
+            .collect();
+        shards.sort_by_key(|(shard_id, _)| **shard_id);
+
+        // Shards are checked one by one, to limit the load on the cluster
+        let mut reports = Vec::with_capacity(shards.len());
+        for (shard_id, replica_set) in shards {
+            let report = match replica_set.check_consistency(request.repair).await {
+                Ok(report) => report,
+                Err(err) => ShardConsistencyReport {
+                    shard_id: *shard_id,
+                    error: Some(err.to_string()),
+                    ..Default::default()
+                },
+            };
+            if !report.consistent {
+                log::warn!(
+                    "Replicas of shard {}:{shard_id} are inconsistent: {} points in {} buckets differ \
+                     ({} missing, {} conflicting), {} points repaired",
+                    self.id,
+                    report.inconsistent_points,
+                    report.mismatched_buckets.len(),
+                    report.missing_points,
+                    report.conflicting_points,
+                    report.repaired_points,
+                );
+            }
+            reports.push(report);
+        }
+
+        Ok(ConsistencyReport { shards: reports })
+    }
+
+    /// Digests of the points in the local replica of the shard
+    pub async fn local_point_digests(
+        &self,
+        shard_id: ShardId,
+        buckets: u32,
+        bucket: Option<u32>,
+    ) -> CollectionResult<Vec<PointDigest>> {
+        let shards_holder = self.shards_holder.read().await;
+        let replica_set = shards_holder
+            .get_shard(&shard_id)
+            .ok_or_else(|| CollectionError::service_error(format!("Shard {shard_id} not found")))?;
+        replica_set.local_point_digests(buckets, bucket).await
+    }
+
+    /// Wait until the replicas apply the updates of the consistency token.
+    ///
+    /// Shards, which are not present anymore, are ignored.
+    pub async fn wait_for_consistency_token(
+        &self,
+        token: &ConsistencyToken,
+        timeout: Duration,
+    ) -> CollectionResult<()> {
+        let shards_holder = self.shards_holder.read().await;
+        let waits = token.shard_ids().filter_map(|shard_id| {
+            let replica_set = shards_holder.get_shard(&shard_id)?;
+            let clocks = token.shard_clocks(shard_id)?;
+            Some(replica_set.wait_for_operations(clocks, timeout))
+        });
+        future::try_join_all(waits).await?;
+        Ok(())
+    }
+
+    /// Wait until the local replica of the shard applies the operation.
+    ///
+    /// Returns `false` if the operation is still not applied after the `timeout`.
+    pub async fn wait_for_local_operation(
+        &self,
+        shard_id: ShardId,
+        operation_id: SeqNumberType,
+        timeout: Duration,
+    ) -> CollectionResult<bool> {
+        let shards_holder = self.shards_holder.read().await;
+        let replica_set = shards_holder
+            .get_shard(&shard_id)
+            .ok_or_else(|| CollectionError::service_error(format!("Shard {shard_id} not found")))?;
+        replica_set
+            .wait_for_local_operation(operation_id, timeout)
+            .await
+    }
+}
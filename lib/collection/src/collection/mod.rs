@@ -1,10 +1,15 @@
 mod collection_ops;
+mod consistency;
+mod optimizations;
 mod point_ops;
+mod recall;
+mod resharding;
 mod search;
 mod shard_transfer;
 mod snapshots;
+mod wal_replay;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -17,6 +22,7 @@ use tokio::sync::{Mutex, RwLock, RwLockWriteGuard};
 
 use crate::collection_state::{ShardInfo, State};
 use crate::common::is_ready::IsReady;
+use crate::common::stoppable_task_async::StoppableAsyncTaskHandle;
 use crate::config::CollectionConfig;
 use crate::hash_ring::HashRing;
 use crate::operations::shared_storage_config::SharedStorageConfig;
@@ -43,8 +49,9 @@ pub struct Collection {
     snapshots_path: PathBuf,
     channel_service: ChannelService,
     transfer_tasks: Mutex<TransferTasksPool>,
+    /// Tasks migrating points of the existing shards during resharding, by source shard
+    resharding_tasks: Mutex<HashMap<ShardId, StoppableAsyncTaskHandle<bool>>>,
     request_shard_transfer_cb: RequestShardTransfer,
-    #[allow(dead_code)] //Might be useful in case of repartition implementation
     notify_peer_failure_cb: ChangePeerState,
     init_time: Duration,
     // One-way boolean flag that is set to true when the collection is fully initialized
@@ -56,6 +63,8 @@ pub struct Collection {
     updates_lock: RwLock<()>,
     // Update runtime handle.
     update_runtime: Handle,
+    // Search runtime handle.
+    search_runtime: Handle,
 }
 
 pub type RequestShardTransfer = Arc<dyn Fn(ShardTransfer) + Send + Sync>;
@@ -63,6 +72,9 @@ pub type RequestShardTransfer = Arc<dyn Fn(ShardTransfer) + Send + Sync>;
 pub type OnTransferFailure = Arc<dyn Fn(ShardTransfer, CollectionId, &str) + Send + Sync>;
 pub type OnTransferSuccess = Arc<dyn Fn(ShardTransfer, CollectionId) + Send + Sync>;
 
+pub type OnReshardingFailure = Arc<dyn Fn(CollectionId, &str) + Send + Sync>;
+pub type OnMigrationSuccess = Arc<dyn Fn(ShardId, CollectionId) + Send + Sync>;
+
 impl Collection {
     #[allow(clippy::too_many_arguments)]
     pub async fn new(
@@ -122,12 +134,14 @@ impl Collection {
             snapshots_path: snapshots_path.to_owned(),
             channel_service,
             transfer_tasks: Mutex::new(TransferTasksPool::new(name.clone())),
+            resharding_tasks: Mutex::new(HashMap::new()),
             request_shard_transfer_cb: request_shard_transfer.clone(),
             notify_peer_failure_cb: on_replica_failure.clone(),
             init_time: start_time.elapsed(),
             is_initialized: Arc::new(Default::default()),
             updates_lock: RwLock::new(()),
             update_runtime: update_runtime.unwrap_or_else(Handle::current),
+            search_runtime: search_runtime.unwrap_or_else(Handle::current),
         })
     }
 
@@ -209,12 +223,14 @@ impl Collection {
             snapshots_path: snapshots_path.to_owned(),
             channel_service,
             transfer_tasks: Mutex::new(TransferTasksPool::new(collection_id.clone())),
+            resharding_tasks: Mutex::new(HashMap::new()),
             request_shard_transfer_cb: request_shard_transfer.clone(),
             notify_peer_failure_cb: on_replica_failure,
             init_time: start_time.elapsed(),
             is_initialized: Arc::new(Default::default()),
             updates_lock: RwLock::new(()),
             update_runtime: update_runtime.unwrap_or_else(Handle::current),
+            search_runtime: search_runtime.unwrap_or_else(Handle::current),
         }
     }
 
@@ -362,6 +378,7 @@ impl Collection {
     pub async fn state(&self) -> State {
         let shards_holder = self.shards_holder.read().await;
         let transfers = shards_holder.shard_transfers.read().clone();
+        let resharding = shards_holder.resharding_state();
         State {
             config: self.collection_config.read().await.clone(),
             shards: shards_holder
@@ -374,6 +391,7 @@ impl Collection {
                 })
                 .collect(),
             transfers,
+            resharding,
         }
     }
 
@@ -394,10 +412,13 @@ impl Collection {
             .await
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn sync_local_state(
         &self,
         on_transfer_failure: OnTransferFailure,
         on_transfer_success: OnTransferSuccess,
+        on_resharding_failure: OnReshardingFailure,
+        on_migration_success: OnMigrationSuccess,
         on_finish_init: ChangePeerState,
         on_convert_to_listener: ChangePeerState,
         on_convert_from_listener: ChangePeerState,
@@ -440,6 +461,42 @@ impl Collection {
                 }
             }
         }
+        drop(tasks_lock);
+
+        // Check for un-reported finished migrations of the resharding
+        if let Some(resharding) = shard_holder.resharding_state() {
+            let resharding_tasks = self.resharding_tasks.lock().await;
+            let pending_sources = resharding
+                .sources_of(self.this_peer_id)
+                .filter(|shard_id| !resharding.migrated.contains(shard_id));
+            for shard_id in pending_sources {
+                let task = resharding_tasks.get(&shard_id);
+                match task.and_then(|task| task.get_result()) {
+                    None => {
+                        if task.map_or(true, |task| task.is_finished()) {
+                            log::debug!(
+                                "Migration of shard {shard_id} does not exist, but resharding is not reported as aborted. Reporting now."
+                            );
+                            on_resharding_failure(self.name(), "migration task does not exist");
+                            break;
+                        }
+                    }
+                    Some(true) => {
+                        log::debug!(
+                            "Migration of shard {shard_id} is finished successfully, but not reported. Reporting now."
+                        );
+                        on_migration_success(shard_id, self.name());
+                    }
+                    Some(false) => {
+                        log::debug!(
+                            "Migration of shard {shard_id} is failed, but resharding is not reported as aborted. Reporting now."
+                        );
+                        on_resharding_failure(self.name(), "migration failed");
+                        break;
+                    }
+                }
+            }
+        }
 
         // Check for proper replica states
         for replica_set in shard_holder.all_shards() {
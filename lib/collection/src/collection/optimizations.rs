@@ -0,0 +1,67 @@
+use futures::future;
+
+use super::Collection;
+use crate::operations::optimization_ops::OptimizationOperations;
+use crate::operations::types::{CollectionError, CollectionOptimizations, CollectionResult};
+use crate::shards::shard::ShardId;
+
+impl Collection {
+    /// Running and queued optimizations of the shards hosted on this peer
+    pub async fn optimizations(&self) -> CollectionOptimizations {
+        let shards_holder = self.shards_holder.read().await;
+        let requests = shards_holder
+            .all_shards()
+            .map(|replica_set| replica_set.optimizations());
+
+        let mut shards: Vec<_> = future::join_all(requests)
+            .await
+            .into_iter()
+            .flatten()
+            .collect();
+        shards.sort_by_key(|shard| shard.shard_id);
+
+        CollectionOptimizations { shards }
+    }
+
+    /// Pause or resume optimizers of the shards hosted on this peer
+    ///
+    /// Running optimizations are cancelled in the background when pausing.
+    pub async fn set_optimizers_paused(&self, paused: bool) -> CollectionResult<()> {
+        let shards_holder = self.shards_holder.read().await;
+        let updates = shards_holder
+            .all_shards()
+            .map(|replica_set| replica_set.set_optimizers_paused(paused));
+        future::try_join_all(updates).await?;
+        Ok(())
+    }
+
+    /// Start the merge or vacuum optimization in all replicas of all shards
+    ///
+    /// Returns `true` if any optimization was started.
+    pub async fn force_optimization(
+        &self,
+        operation: &OptimizationOperations,
+    ) -> CollectionResult<bool> {
+        let shards_holder = self.shards_holder.read().await;
+        let updates = shards_holder
+            .all_shards()
+            .map(|replica_set| replica_set.force_optimization(operation));
+        let started = future::try_join_all(updates).await?;
+        Ok(started.into_iter().any(|started| started))
+    }
+
+    /// Start the merge or vacuum optimization in the local replica of the shard
+    ///
+    /// Returns `true` if any optimization was started.
+    pub async fn force_local_optimization(
+        &self,
+        shard_id: ShardId,
+        operation: &OptimizationOperations,
+    ) -> CollectionResult<bool> {
+        let shards_holder = self.shards_holder.read().await;
+        let replica_set = shards_holder
+            .get_shard(&shard_id)
+            .ok_or_else(|| CollectionError::service_error(format!("Shard {shard_id} not found")))?;
+        replica_set.force_local_optimization(operation).await
+    }
+}
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use futures::{future, TryStreamExt as _};
 use itertools::Itertools as _;
-use segment::types::{WithPayload, WithPayloadInterface};
+use segment::types::{WithPayload, WithPayloadInterface, WithVector};
 use validator::Validate as _;
 
 use super::Collection;
@@ -56,7 +56,7 @@ impl Collection {
         operation.validate()?;
         let _update_lock = self.updates_lock.read().await;
 
-        let mut results = {
+        let results = {
             let shards_holder = self.shards_holder.read().await;
             let shard_to_op = shards_holder.split_by_shard(operation);
 
@@ -97,8 +97,19 @@ impl Collection {
                 first_err
             }
         } else {
+            // No errors at this point.
+            // Token of the batch update has to cover all affected shards.
+            let mut results = results.into_iter().map(Result::unwrap);
             // At least one result is always present.
-            results.pop().unwrap()
+            let mut res = results.next().unwrap();
+            for other in results {
+                if let Some(token) = other.consistency_token {
+                    res.consistency_token
+                        .get_or_insert_with(Default::default)
+                        .merge(token);
+                }
+            }
+            Ok(res)
         }
     }
 
@@ -131,7 +142,7 @@ impl Collection {
         let retrieved_points: Vec<_> = {
             let shards_holder = self.shards_holder.read().await;
             let target_shards = shards_holder.target_shard(shard_selection)?;
-            let scroll_futures = target_shards.into_iter().map(|shard| {
+            let scroll_futures = target_shards.iter().map(|shard| {
                 shard.scroll_by(
                     offset,
                     limit,
@@ -143,7 +154,13 @@ impl Collection {
                 )
             });
 
-            future::try_join_all(scroll_futures).await?
+            let mut retrieved_points = future::try_join_all(scroll_futures).await?;
+            if shard_selection.is_none() {
+                for (shard, points) in target_shards.iter().zip(&mut retrieved_points) {
+                    shards_holder.retain_owned_points(shard.shard_id, points, |point| point.id);
+                }
+            }
+            retrieved_points
         };
         let mut points: Vec<_> = retrieved_points
             .into_iter()
@@ -173,6 +190,12 @@ impl Collection {
         let shards_holder = self.shards_holder.read().await;
         let shards = shards_holder.target_shard(shard_selection)?;
 
+        // Source shards of the resharding might still contain migrated points
+        let (shards, shards_with_foreign_points): (Vec<_>, Vec<_>) =
+            shards.into_iter().partition(|shard| {
+                shard_selection.is_some() || !shards_holder.has_foreign_points(shard.shard_id)
+            });
+
         let request = Arc::new(request);
         let mut requests: futures::stream::FuturesUnordered<_> = shards
             .into_iter()
@@ -186,6 +209,34 @@ impl Collection {
             count += response.count;
         }
 
+        // Count only owned points of the shards, which are not cleaned up yet
+        let shards_holder = &shards_holder;
+        let owned_counts = shards_with_foreign_points.into_iter().map(|shard| {
+            let filter = request.filter.as_ref();
+            async move {
+                let points = shard
+                    .scroll_by(
+                        None,
+                        usize::MAX,
+                        &WithPayloadInterface::Bool(false),
+                        &WithVector::Bool(false),
+                        filter,
+                        None,
+                        false,
+                    )
+                    .await?;
+                let owned = points
+                    .iter()
+                    .filter(|point| shards_holder.is_owned_point(shard.shard_id, &point.id))
+                    .count();
+                Ok::<_, CollectionError>(owned)
+            }
+        });
+        count += future::try_join_all(owned_counts)
+            .await?
+            .into_iter()
+            .sum::<usize>();
+
         Ok(CountResult { count })
     }
 
@@ -204,7 +255,7 @@ impl Collection {
         let all_shard_collection_results = {
             let shard_holder = self.shards_holder.read().await;
             let target_shards = shard_holder.target_shard(shard_selection)?;
-            let retrieve_futures = target_shards.into_iter().map(|shard| {
+            let retrieve_futures = target_shards.iter().map(|shard| {
                 shard.retrieve(
                     request.clone(),
                     &with_payload,
@@ -213,7 +264,13 @@ impl Collection {
                     shard_selection.is_some(),
                 )
             });
-            future::try_join_all(retrieve_futures).await?
+            let mut all_shard_collection_results = future::try_join_all(retrieve_futures).await?;
+            if shard_selection.is_none() {
+                for (shard, points) in target_shards.iter().zip(&mut all_shard_collection_results) {
+                    shard_holder.retain_owned_points(shard.shard_id, points, |point| point.id);
+                }
+            }
+            all_shard_collection_results
         };
         let points = all_shard_collection_results.into_iter().flatten().collect();
         Ok(points)
@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use futures::future;
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+use segment::types::{Filter, SearchParams};
+
+use super::Collection;
+use crate::operations::recall_ops::{
+    RecallEstimationReport, RecallEstimationRequest, ShardRecallEstimation,
+};
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::recall::{
+    recall_estimate, RecallQuery, RecallTarget, SearchMeasurement, ShardRecallMeasurement,
+};
+use crate::shards::shard::ShardId;
+
+impl Collection {
+    /// Estimate recall of the approximate search in all shards of the collection
+    ///
+    /// Every shard is measured in one of its active replicas, the local one if possible.
+    /// The same sampled queries are searched in every shard, so results of the shards
+    /// are merged the same way as results of a regular search.
+    pub async fn estimate_recall(
+        &self,
+        request: RecallEstimationRequest,
+    ) -> CollectionResult<RecallEstimationReport> {
+        let RecallEstimationRequest {
+            sample,
+            limit,
+            filter,
+            params,
+        } = request;
+
+        let targets = RecallTarget::all(&*self.collection_config.read().await);
+        let shards_holder = self.shards_holder.read().await;
+        let target_shards = shards_holder.target_shard(None)?;
+
+        let samples = target_shards
+            .iter()
+            .map(|replica_set| replica_set.sample_recall_queries(filter.clone(), sample));
+        let mut queries: Vec<_> = future::try_join_all(samples)
+            .await?
+            .into_iter()
+            .flatten()
+            .collect();
+        queries.shuffle(&mut thread_rng());
+        queries.truncate(sample);
+        let queries = Arc::new(queries);
+
+        let measurements = target_shards.iter().map(|replica_set| async {
+            let measurement = replica_set
+                .measure_recall(queries.clone(), filter.clone(), limit, params)
+                .await?;
+            CollectionResult::Ok((replica_set.shard_id, measurement))
+        });
+        let mut measurements = future::try_join_all(measurements).await?;
+        measurements.sort_by_key(|(shard_id, _)| *shard_id);
+
+        let mut collection_measurements =
+            vec![vec![SearchMeasurement::default(); queries.len()]; targets.len()];
+        let mut shards = Vec::with_capacity(measurements.len());
+
+        for (shard_id, measurement) in measurements {
+            let estimates = targets
+                .iter()
+                .zip(&measurement.measurements)
+                .map(|(target, measurements)| recall_estimate(target, measurements))
+                .collect();
+
+            for (collection_measurements, shard_measurements) in collection_measurements
+                .iter_mut()
+                .zip(measurement.measurements)
+            {
+                for (collection_measurement, shard_measurement) in
+                    collection_measurements.iter_mut().zip(shard_measurements)
+                {
+                    collection_measurement.merge(shard_measurement, limit);
+                }
+            }
+
+            shards.push(ShardRecallEstimation {
+                shard_id,
+                estimates,
+                segments: measurement.segments,
+            });
+        }
+
+        let estimates = targets
+            .iter()
+            .zip(&collection_measurements)
+            .map(|(target, measurements)| recall_estimate(target, measurements))
+            .collect();
+
+        Ok(RecallEstimationReport {
+            sample: queries.len(),
+            estimates,
+            shards,
+        })
+    }
+
+    /// Sample recall estimation queries from the local replica of the shard
+    pub async fn sample_local_recall_queries(
+        &self,
+        shard_id: ShardId,
+        filter: Option<Filter>,
+        sample: usize,
+    ) -> CollectionResult<Vec<RecallQuery>> {
+        let shards_holder = self.shards_holder.read().await;
+        let replica_set = shards_holder
+            .get_shard(&shard_id)
+            .ok_or_else(|| CollectionError::service_error(format!("Shard {shard_id} not found")))?;
+        replica_set
+            .sample_local_recall_queries(filter, sample)
+            .await
+    }
+
+    /// Measure recall of the approximate search in the local replica of the shard
+    pub async fn measure_local_recall(
+        &self,
+        shard_id: ShardId,
+        queries: Vec<RecallQuery>,
+        filter: Option<Filter>,
+        limit: usize,
+        params: Option<SearchParams>,
+    ) -> CollectionResult<ShardRecallMeasurement> {
+        let shards_holder = self.shards_holder.read().await;
+        let replica_set = shards_holder
+            .get_shard(&shard_id)
+            .ok_or_else(|| CollectionError::service_error(format!("Shard {shard_id} not found")))?;
+        replica_set
+            .measure_local_recall(Arc::new(queries), filter, limit, params)
+            .await
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+
+use super::Collection;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::replica_set::{ReplicaState, ShardReplicaSet};
+use crate::shards::resharding::{self, ReshardingState};
+use crate::shards::shard::ShardId;
+use crate::shards::transfer::shard_transfer;
+
+impl Collection {
+    pub async fn resharding_state(&self) -> Option<ReshardingState> {
+        self.shards_holder.read().await.resharding_state()
+    }
+
+    /// Start resharding, which adds a new shard to the collection.
+    ///
+    /// Creates the new shard on all peers and starts migration of points from the existing
+    /// shards, which are assigned to this peer.
+    /// `on_finish` and `on_error` are called with the ID of the migrated shard.
+    pub async fn start_resharding<T, F, TF, FF>(
+        &self,
+        resharding: ReshardingState,
+        on_finish: T,
+        on_error: F,
+    ) -> CollectionResult<()>
+    where
+        T: Fn(ShardId) -> TF,
+        F: Fn(ShardId) -> FF,
+        TF: Future<Output = ()> + Send + 'static,
+        FF: Future<Output = ()> + Send + 'static,
+    {
+        {
+            let mut shards_holder = self.shards_holder.write().await;
+
+            let shard_number = self
+                .collection_config
+                .read()
+                .await
+                .params
+                .shard_number
+                .get();
+            let shards: HashMap<_, _> = shards_holder
+                .get_shards()
+                .map(|(shard_id, replica_set)| (*shard_id, replica_set.peers()))
+                .collect();
+            resharding::validate_resharding(
+                &resharding,
+                shard_number,
+                &shards,
+                &shards_holder.shard_transfers.read(),
+                shards_holder.resharding_state().as_ref(),
+            )?;
+
+            let is_local = resharding.peer_id == self.this_peer_id;
+            let remotes = if is_local {
+                HashSet::new()
+            } else {
+                HashSet::from([resharding.peer_id])
+            };
+
+            let replica_set = ShardReplicaSet::build(
+                resharding.shard_id,
+                self.name(),
+                self.this_peer_id,
+                is_local,
+                remotes,
+                self.notify_peer_failure_cb.clone(),
+                &self.path,
+                self.collection_config.clone(),
+                self.shared_storage_config.clone(),
+                self.channel_service.clone(),
+                self.update_runtime.clone(),
+                self.search_runtime.clone(),
+            )
+            .await?;
+
+            // The new shard only receives migrated points until resharding is finished
+            replica_set.set_replica_state(&resharding.peer_id, ReplicaState::Partial)?;
+
+            shards_holder.register_start_resharding(resharding.clone(), replica_set)?;
+        }
+
+        let mut resharding_tasks = self.resharding_tasks.lock().await;
+        for shard_id in resharding.sources_of(self.this_peer_id) {
+            let task = resharding::spawn_resharding_task(
+                self.shards_holder.clone(),
+                shard_id,
+                resharding.clone(),
+                self.id.clone(),
+                self.channel_service.clone(),
+                on_finish(shard_id),
+                on_error(shard_id),
+            );
+            resharding_tasks.insert(shard_id, task);
+        }
+
+        Ok(())
+    }
+
+    /// Handles finished migration of points from the existing shard.
+    ///
+    /// Once all existing shards are migrated, routing is switched to the new shard.
+    pub async fn finish_resharding_migration(&self, shard_id: ShardId) -> CollectionResult<()> {
+        let Some(resharding) = self.resharding_state().await else {
+            return Err(CollectionError::bad_request(format!(
+                "Collection {} is not being resharded",
+                self.id
+            )));
+        };
+
+        if !resharding
+            .pending_sources()
+            .any(|pending| pending == shard_id)
+        {
+            return Err(CollectionError::bad_request(format!(
+                "Shard {shard_id} is not migrating points into shard {}",
+                resharding.shard_id
+            )));
+        }
+
+        if let Some(task) = self.resharding_tasks.lock().await.remove(&shard_id) {
+            if let Err(err) = task.stop().await {
+                log::warn!(
+                    "Migration task of shard {}:{shard_id} failed: {err}",
+                    self.id
+                );
+            }
+        }
+
+        let all_migrated = self
+            .shards_holder
+            .read()
+            .await
+            .register_finish_migration(shard_id)?;
+
+        if all_migrated {
+            self.finish_resharding().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Switch routing to the new shard and delete migrated points from the existing shards.
+    ///
+    /// Shard holder is write-locked only to switch the routing. Migrated points are deleted
+    /// under a read lock afterwards, so reads and writes are not blocked by the cleanup.
+    /// Until then, reads drop points returned by the source shards, which they don't own anymore.
+    pub(crate) async fn finish_resharding(&self) -> CollectionResult<()> {
+        // Migrations might be still running, if finish is applied from the snapshot
+        let tasks: Vec<_> = self.resharding_tasks.lock().await.drain().collect();
+        for (_, task) in tasks {
+            task.ask_to_stop();
+        }
+
+        let resharding = {
+            let mut shards_holder = self.shards_holder.write().await;
+
+            let Some(resharding) = shards_holder.resharding_state() else {
+                return Ok(());
+            };
+
+            // Stop forwarding updates into the new shard
+            for shard_id in resharding.sources_of(self.this_peer_id) {
+                shard_transfer::revert_proxy_shard_to_local(&shards_holder, shard_id).await?;
+            }
+
+            if let Some(replica_set) = shards_holder.get_shard(&resharding.shard_id) {
+                replica_set.set_replica_state(&resharding.peer_id, ReplicaState::Active)?;
+            }
+
+            shards_holder.register_finish_resharding()?;
+
+            let mut config = self.collection_config.write().await;
+            config.params.shard_number = config.params.shard_number.saturating_add(1);
+            config.save(&self.path)?;
+
+            resharding
+        };
+
+        let shards_holder = self.shards_holder.read().await;
+        for shard_id in resharding.sources.keys() {
+            let deleted = shards_holder.delete_foreign_points(*shard_id).await?;
+            log::debug!(
+                "Deleted {deleted} points of shard {}:{shard_id} migrated into shard {}",
+                self.id,
+                resharding.shard_id,
+            );
+        }
+
+        log::info!(
+            "Resharding of collection {} into shard {} finished",
+            self.id,
+            resharding.shard_id
+        );
+
+        Ok(())
+    }
+
+    /// Handles abort of the resharding
+    ///
+    /// 1. Stop migration tasks
+    /// 2. Unwrap the proxies
+    /// 3. Remove the new shard
+    pub async fn abort_resharding(&self) -> CollectionResult<()> {
+        let tasks: Vec<_> = self.resharding_tasks.lock().await.drain().collect();
+        for (shard_id, task) in tasks {
+            if let Err(err) = task.stop().await {
+                log::warn!(
+                    "Migration task of shard {}:{shard_id} failed: {err}",
+                    self.id
+                );
+            }
+        }
+
+        let mut shards_holder = self.shards_holder.write().await;
+
+        let Some((resharding, replica_set)) = shards_holder.register_abort_resharding()? else {
+            return Ok(());
+        };
+
+        for shard_id in resharding.sources_of(self.this_peer_id) {
+            shard_transfer::revert_proxy_shard_to_local(&shards_holder, shard_id).await?;
+        }
+
+        drop(shards_holder);
+
+        if let Some(replica_set) = replica_set {
+            let shard_path = replica_set.shard_path.clone();
+            replica_set.remove_local().await?;
+            drop(replica_set);
+            tokio::fs::remove_dir_all(&shard_path).await?;
+        }
+
+        Ok(())
+    }
+}
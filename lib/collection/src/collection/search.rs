@@ -30,6 +30,25 @@ impl Collection {
         Ok(results.into_iter().next().unwrap())
     }
 
+    /// Same as [`Collection::search`], but also returns details of how the search was performed
+    pub async fn search_with_profile(
+        &self,
+        request: SearchRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: Option<ShardId>,
+    ) -> CollectionResult<(Vec<ScoredPoint>, SearchProfile)> {
+        if request.limit == 0 {
+            return Ok((vec![], SearchProfile::default()));
+        }
+        let request_batch = CoreSearchRequestBatch {
+            searches: vec![request.into()],
+        };
+        let (results, profile) = self
+            .core_search_batch_with_profile(request_batch, read_consistency, shard_selection)
+            .await?;
+        Ok((results.into_iter().next().unwrap(), profile))
+    }
+
     // ! COPY-PASTE: `core_search` is a copy-paste of `search` with different request type
     // ! please replicate any changes to both methods
     pub async fn search_batch(
@@ -192,6 +211,49 @@ impl Collection {
         }
     }
 
+    /// Search without 2-step payload retrieval, collecting profiles of the searches in every shard
+    pub async fn core_search_batch_with_profile(
+        &self,
+        request: CoreSearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: Option<ShardId>,
+    ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, SearchProfile)> {
+        // shortcuts batch if all requests with limit=0
+        if request.searches.iter().all(|s| s.limit == 0) {
+            return Ok((vec![], SearchProfile::default()));
+        }
+        let request = Arc::new(request);
+
+        // query all shards concurrently
+        let all_searches_res = {
+            let shard_holder = self.shards_holder.read().await;
+            let target_shards = shard_holder.target_shard(shard_selection)?;
+            let all_searches = target_shards.iter().map(|shard| {
+                shard.core_search_with_profile(
+                    request.clone(),
+                    read_consistency,
+                    shard_selection.is_some(),
+                )
+            });
+            let mut all_searches_res = future::try_join_all(all_searches).await?;
+            if shard_selection.is_none() {
+                for (shard, (shard_res, _)) in target_shards.iter().zip(&mut all_searches_res) {
+                    shard_holder.retain_owned_search_results(shard.shard_id, shard_res);
+                }
+            }
+            all_searches_res
+        };
+        let (all_searches_res, shards): (Vec<_>, Vec<_>) = all_searches_res.into_iter().unzip();
+
+        let request = Arc::into_inner(request)
+            .expect("We have already dropped all of the Arc clones at this point");
+
+        let result = self
+            .merge_from_shards(all_searches_res, request, shard_selection)
+            .await?;
+        Ok((result, SearchProfile { shards }))
+    }
+
     // ! COPY-PASTE: `do_core_search_batch` is a copy-paste of `do_search_batch` with different request type
     // ! please replicate any changes to both methods
     async fn do_search_batch(
@@ -209,7 +271,13 @@ impl Collection {
             let all_searches = target_shards.iter().map(|shard| {
                 shard.search(request.clone(), read_consistency, shard_selection.is_some())
             });
-            future::try_join_all(all_searches).await?
+            let mut all_searches_res = future::try_join_all(all_searches).await?;
+            if shard_selection.is_none() {
+                for (shard, shard_res) in target_shards.iter().zip(&mut all_searches_res) {
+                    shard_holder.retain_owned_search_results(shard.shard_id, shard_res);
+                }
+            }
+            all_searches_res
         };
 
         let request = Arc::into_inner(request)
@@ -237,7 +305,13 @@ impl Collection {
             let all_searches = target_shards.iter().map(|shard| {
                 shard.core_search(request.clone(), read_consistency, shard_selection.is_some())
             });
-            future::try_join_all(all_searches).await?
+            let mut all_searches_res = future::try_join_all(all_searches).await?;
+            if shard_selection.is_none() {
+                for (shard, shard_res) in target_shards.iter().zip(&mut all_searches_res) {
+                    shard_holder.retain_owned_search_results(shard.shard_id, shard_res);
+                }
+            }
+            all_searches_res
         };
 
         let request = Arc::into_inner(request)
@@ -320,7 +394,13 @@ impl Collection {
                 let distance = collection_params
                     .get_vector_params(request.query.get_vector_name())?
                     .distance;
-                let mut top_res = match distance.distance_order() {
+                // Scores computed by the formula are sorted the same way for any distance
+                let order = if request.formula.is_some() {
+                    Order::LargeBetter
+                } else {
+                    distance.distance_order()
+                };
+                let mut top_res = match order {
                     Order::LargeBetter => {
                         tools::peek_top_largest_iterable(res, request.limit + request.offset)
                     }
@@ -1,11 +1,14 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use segment::common::version::StorageVersion as _;
+use segment::utils::tar::HashingWriter;
 use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
 
 use super::Collection;
 use crate::collection::CollectionVersion;
-use crate::common::file_utils::FileCleaner;
+use crate::common::file_utils::{move_file, FileCleaner};
 use crate::config::CollectionConfig;
 use crate::operations::snapshot_ops::{self, SnapshotDescription};
 use crate::operations::types::{CollectionError, CollectionResult, NodeType};
@@ -15,12 +18,58 @@ use crate::shards::replica_set::ShardReplicaSet;
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_config::{self, ShardConfig};
 use crate::shards::shard_versioning;
+use crate::shards::snapshot_manifest::{
+    self, ShardSnapshotManifest, SnapshotBase, SnapshotManifest, SNAPSHOT_MANIFEST_FILE,
+};
 
 impl Collection {
     pub async fn list_snapshots(&self) -> CollectionResult<Vec<SnapshotDescription>> {
         snapshot_ops::list_snapshots_in_directory(&self.snapshots_path).await
     }
 
+    /// Name of the base snapshot of every incremental snapshot of the collection
+    pub async fn snapshot_bases(&self) -> CollectionResult<HashMap<String, String>> {
+        let snapshots = self.list_snapshots().await?;
+        let snapshots_path = self.snapshots_path.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut bases = HashMap::new();
+            for snapshot in snapshots {
+                let snapshot_path = snapshots_path.join(&snapshot.name);
+                let manifest = SnapshotManifest::read_from_archive(&snapshot_path)?;
+                if let Some(base) = manifest.and_then(|manifest| manifest.base) {
+                    bases.insert(snapshot.name, base.name);
+                }
+            }
+            Ok(bases)
+        })
+        .await?
+    }
+
+    /// Delete the snapshot of the collection.
+    ///
+    /// Fails if the snapshot is a base of incremental snapshots, which can't be recovered without it.
+    pub async fn delete_snapshot(&self, snapshot_name: &str) -> CollectionResult<()> {
+        let snapshot_path = self.get_snapshot_path(snapshot_name).await?;
+
+        let mut dependents: Vec<_> = self
+            .snapshot_bases()
+            .await?
+            .into_iter()
+            .filter(|(_, base)| base == snapshot_name)
+            .map(|(snapshot, _)| snapshot)
+            .collect();
+        if !dependents.is_empty() {
+            dependents.sort_unstable();
+            return Err(CollectionError::bad_request(format!(
+                "Snapshot {snapshot_name} is a base of incremental snapshots {}, delete them first",
+                dependents.join(", ")
+            )));
+        }
+
+        log::info!("Deleting collection snapshot {:?}", snapshot_path);
+        snapshot_ops::remove_snapshot(&snapshot_path).await
+    }
+
     /// Creates a snapshot of the collection.
     ///
     /// The snapshot is created in three steps:
@@ -32,13 +81,21 @@ impl Collection {
     ///
     /// * `global_temp_dir`: directory used to host snapshots while they are being created
     /// * `this_peer_id`: current peer id
+    /// * `base`: name of an existing snapshot of this collection, to create an incremental
+    ///   snapshot from. It only contains segments and WAL files changed since the base.
     ///
     /// returns: Result<SnapshotDescription, CollectionError>
     pub async fn create_snapshot(
         &self,
         global_temp_dir: &Path,
         this_peer_id: PeerId,
+        base: Option<&str>,
     ) -> CollectionResult<SnapshotDescription> {
+        let base = match base {
+            Some(base_name) => Some(self.load_snapshot_base(base_name).await?),
+            None => None,
+        };
+
         let snapshot_name = format!(
             "{}-{}-{}.snapshot",
             self.name(),
@@ -46,6 +103,15 @@ impl Collection {
             chrono::Utc::now().format("%Y-%m-%d-%H-%M-%S")
         );
 
+        if base
+            .as_ref()
+            .map_or(false, |(base, _)| base.name == snapshot_name)
+        {
+            return Err(CollectionError::bad_input(format!(
+                "Snapshot {snapshot_name} would overwrite its base snapshot"
+            )));
+        }
+
         // Final location of snapshot
         let snapshot_path = self.snapshots_path.join(&snapshot_name);
         log::info!(
@@ -60,6 +126,10 @@ impl Collection {
             .tempdir_in(global_temp_dir)?;
 
         let snapshot_temp_target_dir_path = snapshot_temp_target_dir.path().to_path_buf();
+        let mut manifest = SnapshotManifest {
+            base: base.as_ref().map(|(base, _)| base.clone()),
+            shards: Default::default(),
+        };
         // Create snapshot of each shard
         {
             let snapshot_temp_temp_dir = tempfile::Builder::new()
@@ -76,13 +146,20 @@ impl Collection {
                 fs::create_dir_all(&shard_snapshot_path).await?;
                 // If node is listener, we can save whatever currently is in the storage
                 let save_wal = self.shared_storage_config.node_type != NodeType::Listener;
+                let shard_base = base
+                    .as_ref()
+                    .and_then(|(_, base_manifest)| base_manifest.shards.get(shard_id));
                 replica_set
                     .create_snapshot(
                         snapshot_temp_temp_dir.path(),
                         &shard_snapshot_path,
                         save_wal,
+                        shard_base,
                     )
                     .await?;
+                if let Some(shard_manifest) = ShardSnapshotManifest::take(&shard_snapshot_path)? {
+                    manifest.shards.insert(*shard_id, shard_manifest);
+                }
             }
         }
 
@@ -93,6 +170,12 @@ impl Collection {
             .await
             .save(&snapshot_temp_target_dir_path)?;
 
+        // Manifest is kept outside of the snapshot folder, so it is archived only once
+        let manifest_file = tempfile::Builder::new()
+            .prefix(&format!("{snapshot_name}-manifest-"))
+            .tempfile_in(global_temp_dir)?;
+        serde_json::to_writer(manifest_file.as_file(), &manifest)?;
+
         // Dedicated temporary file for archiving this snapshot (deleted on drop)
         let mut snapshot_temp_arc_file = tempfile::Builder::new()
             .prefix(&format!("{snapshot_name}-arc-"))
@@ -101,15 +184,17 @@ impl Collection {
         // Archive snapshot folder into a single file
         log::debug!("Archiving snapshot {:?}", &snapshot_temp_target_dir_path);
         let archiving = tokio::task::spawn_blocking(move || -> CollectionResult<_> {
-            let mut builder = tar::Builder::new(snapshot_temp_arc_file.as_file_mut());
+            let mut builder =
+                tar::Builder::new(HashingWriter::new(snapshot_temp_arc_file.as_file_mut()));
+            // manifest goes first, so it can be read without scanning the whole archive
+            builder.append_path_with_name(manifest_file.path(), SNAPSHOT_MANIFEST_FILE)?;
             // archive recursively collection directory `snapshot_path_with_arc_extension` into `snapshot_path`
             builder.append_dir_all(".", &snapshot_temp_target_dir_path)?;
-            builder.finish()?;
-            drop(builder);
+            let checksum = builder.into_inner()?.checksum();
             // return ownership of the file
-            Ok(snapshot_temp_arc_file)
+            Ok((snapshot_temp_arc_file, checksum))
         });
-        snapshot_temp_arc_file = archiving.await??;
+        let (snapshot_temp_arc_file, checksum) = archiving.await??;
 
         // Move snapshot to permanent location.
         // We can't move right away, because snapshot folder can be on another mounting point.
@@ -120,6 +205,8 @@ impl Collection {
         // Ensure that the temporary file is deleted on error
         let _file_cleaner = FileCleaner::new(&snapshot_path_tmp_move);
         fs::copy(&snapshot_temp_arc_file.path(), &snapshot_path_tmp_move).await?;
+        // Checksum is stored first, so a visible snapshot always has it
+        snapshot_ops::write_checksum_for_snapshot(&snapshot_path, &checksum).await?;
         fs::rename(&snapshot_path_tmp_move, &snapshot_path).await?;
 
         log::info!(
@@ -139,10 +226,8 @@ impl Collection {
         this_peer_id: PeerId,
         is_distributed: bool,
     ) -> CollectionResult<()> {
-        // decompress archive
-        let archive_file = std::fs::File::open(snapshot_path)?;
-        let mut ar = tar::Archive::new(archive_file);
-        ar.unpack(target_dir)?;
+        // decompress archive, together with its base snapshots if it is incremental
+        snapshot_manifest::unpack_snapshot(snapshot_path, target_dir)?;
 
         let config = CollectionConfig::load(target_dir)?;
         config.validate_and_warn();
@@ -177,6 +262,40 @@ impl Collection {
         Ok(())
     }
 
+    /// Base snapshot description and its manifest, to create an incremental snapshot from
+    async fn load_snapshot_base(
+        &self,
+        base_name: &str,
+    ) -> CollectionResult<(SnapshotBase, SnapshotManifest)> {
+        let base_path = self.get_snapshot_path(base_name).await?;
+
+        let manifest = {
+            let base_path = base_path.clone();
+            tokio::task::spawn_blocking(move || SnapshotManifest::read_from_archive(&base_path))
+                .await??
+        };
+        let Some(manifest) = manifest else {
+            return Err(CollectionError::bad_input(format!(
+                "Snapshot {base_name} has no manifest and can't be used as a base, \
+                 it was created by an older version"
+            )));
+        };
+
+        let checksum = match snapshot_ops::read_checksum_for_snapshot(&base_path).await {
+            Some(checksum) => checksum,
+            None => {
+                tokio::task::spawn_blocking(move || segment::utils::tar::file_checksum(&base_path))
+                    .await??
+            }
+        };
+
+        let base = SnapshotBase {
+            name: base_name.to_string(),
+            checksum,
+        };
+        Ok((base, manifest))
+    }
+
     pub async fn recover_local_shard_from(
         &self,
         snapshot_shard_path: &Path,
@@ -288,4 +407,79 @@ impl Collection {
             .get_shard_snapshot_path(&self.snapshots_path, shard_id, snapshot_file_name)
             .await
     }
+
+    /// Read a chunk of the shard snapshot file, the chunk is shorter than `length` at the end of the file
+    pub async fn read_shard_snapshot_chunk(
+        &self,
+        shard_id: ShardId,
+        snapshot_file_name: &str,
+        offset: u64,
+        length: u64,
+    ) -> CollectionResult<Vec<u8>> {
+        let snapshot_path = self
+            .get_shard_snapshot_path(shard_id, snapshot_file_name)
+            .await?;
+        let mut file =
+            fs::File::open(&snapshot_path)
+                .await
+                .map_err(|_| CollectionError::NotFound {
+                    what: format!("Snapshot {snapshot_file_name}"),
+                })?;
+        file.seek(std::io::SeekFrom::Start(offset)).await?;
+
+        let mut data = Vec::new();
+        file.take(length).read_to_end(&mut data).await?;
+        Ok(data)
+    }
+
+    pub async fn delete_shard_snapshot(
+        &self,
+        shard_id: ShardId,
+        snapshot_file_name: &str,
+    ) -> CollectionResult<bool> {
+        let snapshot_path = self
+            .get_shard_snapshot_path(shard_id, snapshot_file_name)
+            .await?;
+        if !snapshot_path.is_file() {
+            return Err(CollectionError::NotFound {
+                what: format!("Snapshot {snapshot_file_name}"),
+            });
+        }
+        snapshot_ops::remove_snapshot(&snapshot_path).await?;
+        Ok(true)
+    }
+
+    /// Create a snapshot of the shard on one of its active replicas and save it to `target_path`.
+    ///
+    /// The local replica is preferred, otherwise the snapshot is created on a remote peer
+    /// and downloaded from it.
+    ///
+    /// Returns the peer, which the snapshot was created on.
+    pub async fn snapshot_shard_replica(
+        &self,
+        shard_id: ShardId,
+        temp_dir: &Path,
+        target_path: &Path,
+    ) -> CollectionResult<PeerId> {
+        let shards_holder = self.shards_holder.read().await;
+        let replica_set = shards_holder
+            .get_shard(&shard_id)
+            .ok_or_else(|| CollectionError::service_error(format!("Shard {shard_id} not found")))?;
+
+        if replica_set.peer_is_active(&self.this_peer_id) && replica_set.is_local().await {
+            let snapshot = shards_holder
+                .create_shard_snapshot(&self.snapshots_path, &self.name(), shard_id, temp_dir)
+                .await?;
+            let snapshot_path = shards_holder
+                .get_shard_snapshot_path(&self.snapshots_path, shard_id, &snapshot.name)
+                .await?;
+            move_file(&snapshot_path, target_path).await?;
+            fs::remove_file(snapshot_ops::get_checksum_path(&snapshot_path))
+                .await
+                .ok();
+            return Ok(self.this_peer_id);
+        }
+
+        replica_set.download_remote_snapshot(target_path).await
+    }
 }
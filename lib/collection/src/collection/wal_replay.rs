@@ -0,0 +1,82 @@
+use std::path::Path;
+
+use wal::WalOptions;
+
+use super::Collection;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::CollectionUpdateOperations;
+use crate::shards::local_shard::LocalShard;
+use crate::shards::shard::ShardId;
+use crate::wal::SerdeWal;
+use crate::wal_archive::{self, ReplayUntil};
+
+impl Collection {
+    /// Read operations from the WAL archive of the shard, which follow the state of the shard
+    /// snapshot in `snapshot_shard_path`, up to the `until` point.
+    ///
+    /// Must be called before the shard is recovered from the snapshot,
+    /// as the recovered shard moves archived operations, which it does not contain, aside.
+    pub async fn read_wal_archive(
+        &self,
+        shard_id: ShardId,
+        snapshot_shard_path: &Path,
+        until: ReplayUntil,
+    ) -> CollectionResult<Vec<(u64, CollectionUpdateOperations)>> {
+        let archive_path = self
+            .shared_storage_config
+            .wal_archive_path(&self.name(), shard_id)
+            .ok_or_else(|| {
+                CollectionError::bad_input(
+                    "WAL archive is not enabled, set `storage.wal_archive_path` in the config"
+                        .to_string(),
+                )
+            })?;
+        if !archive_path.exists() {
+            return Err(CollectionError::bad_input(format!(
+                "There is no WAL archive of shard {shard_id} on this peer"
+            )));
+        }
+
+        let wal_path = LocalShard::wal_path(snapshot_shard_path);
+        let wal_options: WalOptions = (&self.collection_config.read().await.wal_config).into();
+
+        let operations = tokio::task::spawn_blocking(move || -> CollectionResult<_> {
+            let from = {
+                let wal: SerdeWal<CollectionUpdateOperations> =
+                    SerdeWal::new(wal_path.to_str().unwrap(), wal_options)?;
+                if wal.is_empty() {
+                    wal.first_index()
+                } else {
+                    wal.last_index() + 1
+                }
+            };
+            Ok(wal_archive::read_archive(&archive_path, from, until)?)
+        })
+        .await??;
+
+        Ok(operations)
+    }
+
+    /// Apply archived operations to the local replica of the shard
+    pub async fn replay_operations(
+        &self,
+        shard_id: ShardId,
+        operations: Vec<(u64, CollectionUpdateOperations)>,
+    ) -> CollectionResult<()> {
+        let shards_holder = self.shards_holder.read().await;
+        let replica_set = shards_holder
+            .get_shard(&shard_id)
+            .ok_or_else(|| CollectionError::service_error(format!("Shard {shard_id} not found")))?;
+
+        for (op_num, operation) in operations {
+            let result = replica_set.update_local(operation, true).await?;
+            if result.is_none() {
+                return Err(CollectionError::service_error(format!(
+                    "Can't replay archived operation {op_num}, shard {shard_id} has no local replica"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -212,6 +212,7 @@ mod tests {
                 payload,
                 points: Some(points.clone()),
                 filter: None,
+                key: None,
             }),
         )
         .unwrap();
@@ -7,6 +7,7 @@ use std::sync::Arc;
 use parking_lot::{RwLock, RwLockUpgradableReadGuard};
 use segment::common::operation_error::{OperationResult, SegmentFailedState};
 use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::search_profile::SegmentSearchProfile;
 use segment::data_types::vectors::{QueryVector, VectorElementType};
 use segment::entry::entry_point::SegmentEntry;
 use segment::index::field_index::CardinalityEstimation;
@@ -296,6 +297,57 @@ impl SegmentEntry for ProxySegment {
         Ok(wrapped_results)
     }
 
+    fn search_batch_with_profile(
+        &self,
+        vector_name: &str,
+        vectors: &[&QueryVector],
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<(Vec<Vec<ScoredPoint>>, Vec<SegmentSearchProfile>)> {
+        let deleted_points = self.deleted_points.read();
+
+        // Same as in `search_batch`, deleted points must not be found in the wrapped segment
+        let wrapped_filter = if deleted_points.is_empty() {
+            None
+        } else {
+            Some(self.add_deleted_points_condition_to_filter(filter, &deleted_points))
+        };
+        let (mut wrapped_results, mut profiles) = self
+            .wrapped_segment
+            .get()
+            .read()
+            .search_batch_with_profile(
+                vector_name,
+                vectors,
+                with_payload,
+                with_vector,
+                wrapped_filter.as_ref().or(filter),
+                top,
+                params,
+                is_stopped,
+            )?;
+        let (mut write_results, mut write_profiles) =
+            self.write_segment.get().read().search_batch_with_profile(
+                vector_name,
+                vectors,
+                with_payload,
+                with_vector,
+                filter,
+                top,
+                params,
+                is_stopped,
+            )?;
+        for (index, write_result) in write_results.iter_mut().enumerate() {
+            wrapped_results[index].append(write_result)
+        }
+        profiles.append(&mut write_profiles);
+        Ok((wrapped_results, profiles))
+    }
+
     fn upsert_point(
         &mut self,
         op_num: SeqNumberType,
@@ -371,12 +423,13 @@ impl SegmentEntry for ProxySegment {
         op_num: SeqNumberType,
         point_id: PointIdType,
         payload: &Payload,
+        key: &Option<PayloadKeyType>,
     ) -> OperationResult<bool> {
         self.move_if_exists(op_num, point_id)?;
         self.write_segment
             .get()
             .write()
-            .set_payload(op_num, point_id, payload)
+            .set_payload(op_num, point_id, payload, key)
     }
 
     fn delete_payload(
@@ -782,6 +835,12 @@ impl SegmentEntry for ProxySegment {
         Ok(archive_path)
     }
 
+    fn snapshot_versions(&self) -> Vec<(String, SeqNumberType)> {
+        let mut versions = self.wrapped_segment.get().read().snapshot_versions();
+        versions.extend(self.write_segment.get().read().snapshot_versions());
+        versions
+    }
+
     fn get_telemetry_data(&self) -> SegmentTelemetry {
         self.wrapped_segment.get().read().get_telemetry_data()
     }
@@ -1122,6 +1181,7 @@ mod tests {
                 101,
                 3.into(),
                 &json!({ "color": vec!["red".to_owned()] }).into(),
+                &None,
             )
             .unwrap();
         let proxy_res = proxy_segment.read_range(None, Some(10.into()));
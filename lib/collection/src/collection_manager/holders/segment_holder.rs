@@ -17,6 +17,7 @@ use segment::types::{PointIdType, SeqNumberType};
 
 use crate::collection_manager::holders::proxy_segment::ProxySegment;
 use crate::operations::types::CollectionError;
+use crate::shards::snapshot_manifest::SegmentVersions;
 use crate::shards::update_tracker::UpdateTracker;
 
 pub type SegmentId = usize;
@@ -554,18 +555,34 @@ impl<'s> SegmentHolder {
 
     /// Take a snapshot of all segments into `snapshot_dir_path`
     ///
+    /// Segments, which have the same versions as in `base`, are skipped.
+    /// Returns versions of all segments, including the skipped ones.
+    ///
     /// Shortcuts at the first failing segment snapshot
     pub fn snapshot_all_segments(
         &self,
         temp_dir: &Path,
         snapshot_dir_path: &Path,
-    ) -> OperationResult<()> {
+        base: Option<&SegmentVersions>,
+    ) -> OperationResult<SegmentVersions> {
+        let mut versions = SegmentVersions::new();
         for segment in self.segments.values() {
             let segment_lock = segment.get();
             let read_segment = segment_lock.read();
-            read_segment.take_snapshot(temp_dir, snapshot_dir_path)?;
+
+            // Versions are taken before the snapshot, so concurrent updates land in the next one
+            let segment_versions = read_segment.snapshot_versions();
+            let unchanged = base.map_or(false, |base| {
+                segment_versions
+                    .iter()
+                    .all(|(segment_id, version)| base.get(segment_id) == Some(version))
+            });
+            if !unchanged {
+                read_segment.take_snapshot(temp_dir, snapshot_dir_path)?;
+            }
+            versions.extend(segment_versions);
         }
-        Ok(())
+        Ok(versions)
     }
 
     pub fn report_optimizer_error<E: Into<CollectionError>>(&mut self, error: E) {
@@ -766,17 +783,17 @@ mod tests {
         let mut segment2 = build_segment_1(dir.path());
 
         segment1
-            .set_payload(100, 1.into(), &json!({}).into())
+            .set_payload(100, 1.into(), &json!({}).into(), &None)
             .unwrap();
         segment1
-            .set_payload(100, 2.into(), &json!({}).into())
+            .set_payload(100, 2.into(), &json!({}).into(), &None)
             .unwrap();
 
         segment2
-            .set_payload(200, 4.into(), &json!({}).into())
+            .set_payload(200, 4.into(), &json!({}).into(), &None)
             .unwrap();
         segment2
-            .set_payload(200, 5.into(), &json!({}).into())
+            .set_payload(200, 5.into(), &json!({}).into(), &None)
             .unwrap();
 
         let mut holder = SegmentHolder::default();
@@ -813,12 +830,55 @@ mod tests {
 
         let temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
         let snapshot_dir = Builder::new().prefix("snapshot_dir").tempdir().unwrap();
-        holder
-            .snapshot_all_segments(temp_dir.path(), snapshot_dir.path())
+        let versions = holder
+            .snapshot_all_segments(temp_dir.path(), snapshot_dir.path(), None)
             .unwrap();
 
         let archive_count = read_dir(&snapshot_dir).unwrap().count();
         // one archive produced per concrete segment in the SegmentHolder
         assert_eq!(archive_count, 2);
+        assert_eq!(versions.len(), 2);
+    }
+
+    #[test]
+    fn test_snapshot_all_incremental() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let segment1 = build_segment_1(dir.path());
+        let segment2 = build_segment_2(dir.path());
+
+        let mut holder = SegmentHolder::default();
+
+        let sid1 = holder.add(segment1);
+        holder.add(segment2);
+
+        let temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
+        let base_dir = Builder::new().prefix("base_dir").tempdir().unwrap();
+        let base = holder
+            .snapshot_all_segments(temp_dir.path(), base_dir.path(), None)
+            .unwrap();
+
+        // Nothing changed, nothing to archive
+        let snapshot_dir = Builder::new().prefix("snapshot_dir").tempdir().unwrap();
+        let versions = holder
+            .snapshot_all_segments(temp_dir.path(), snapshot_dir.path(), Some(&base))
+            .unwrap();
+        assert_eq!(versions, base);
+        assert_eq!(read_dir(&snapshot_dir).unwrap().count(), 0);
+
+        // Only the updated segment is archived
+        holder
+            .get(sid1)
+            .unwrap()
+            .get()
+            .write()
+            .delete_point(100, 1.into())
+            .unwrap();
+
+        let snapshot_dir = Builder::new().prefix("snapshot_dir").tempdir().unwrap();
+        let versions = holder
+            .snapshot_all_segments(temp_dir.path(), snapshot_dir.path(), Some(&base))
+            .unwrap();
+        assert_ne!(versions, base);
+        assert_eq!(read_dir(&snapshot_dir).unwrap().count(), 1);
     }
 }
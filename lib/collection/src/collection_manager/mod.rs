@@ -1,6 +1,7 @@
 pub mod collection_updater;
 pub mod holders;
 pub mod optimizers;
+pub(crate) mod search_calibration;
 pub mod segments_searcher;
 
 mod probabilistic_segment_search_sampling;
@@ -128,16 +128,44 @@ impl ConfigMismatchOptimizer {
                         .vector_data
                         .iter()
                         .any(|(vector_name, vector_data)| {
-                            // Check HNSW mismatch
-                            match &vector_data.index {
-                                Indexes::Plain {} => {}
-                                Indexes::Hnsw(effective_hnsw) => {
+                            // Check HNSW, DiskANN and IVF mismatch
+                            let target_params =
+                                self.collection_params.vectors.get_params(vector_name);
+                            let target_disk_ann = target_params
+                                .and_then(|vector_params| vector_params.disk_ann_config.as_ref());
+                            let target_ivf = target_params
+                                .and_then(|vector_params| vector_params.ivf_config.as_ref());
+                            match (&vector_data.index, target_disk_ann, target_ivf) {
+                                // Not indexed yet, appendable HNSW is replaced on indexing
+                                (Indexes::Plain {} | Indexes::AppendableHnsw(_), _, _) => {}
+                                (Indexes::Hnsw(effective_hnsw), None, None) => {
                                     // Select segment if we have an HNSW mismatch that requires rebuild
                                     let target_hnsw = self.get_required_hnsw_config(vector_name);
                                     if effective_hnsw.mismatch_requires_rebuild(&target_hnsw) {
                                         return true;
                                     }
                                 }
+                                (
+                                    Indexes::DiskAnn(effective_disk_ann),
+                                    Some(target_disk_ann),
+                                    _,
+                                ) => {
+                                    if effective_disk_ann.mismatch_requires_rebuild(target_disk_ann)
+                                    {
+                                        return true;
+                                    }
+                                }
+                                (Indexes::Ivf(effective_ivf), None, Some(target_ivf)) => {
+                                    if effective_ivf.mismatch_requires_rebuild(target_ivf) {
+                                        return true;
+                                    }
+                                }
+                                // Index type changed
+                                (Indexes::Hnsw(_), _, _)
+                                | (Indexes::DiskAnn(_), None, _)
+                                | (Indexes::Ivf(_), _, _) => {
+                                    return true;
+                                }
                             }
 
                             if let Some(is_required_on_disk) =
@@ -286,6 +314,8 @@ mod tests {
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: None,
+                disk_ann_config: None,
+                ivf_config: None,
             }),
             ..CollectionParams::empty()
         };
@@ -307,6 +337,7 @@ mod tests {
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            appendable: None,
         };
 
         // Optimizers used in test
@@ -418,6 +449,8 @@ mod tests {
                         hnsw_config: Some(hnsw_config_vector1),
                         quantization_config: None,
                         on_disk: None,
+                        disk_ann_config: None,
+                        ivf_config: None,
                     },
                 ),
                 (
@@ -428,6 +461,8 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: None,
                         on_disk: None,
+                        disk_ann_config: None,
+                        ivf_config: None,
                     },
                 ),
             ])),
@@ -457,6 +492,7 @@ mod tests {
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            appendable: None,
         };
 
         // Optimizers used in test
@@ -583,6 +619,8 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: Some(quantization_config_vector1.clone()),
                         on_disk: None,
+                        disk_ann_config: None,
+                        ivf_config: None,
                     },
                 ),
                 (
@@ -593,6 +631,8 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: None,
                         on_disk: None,
+                        disk_ann_config: None,
+                        ivf_config: None,
                     },
                 ),
             ])),
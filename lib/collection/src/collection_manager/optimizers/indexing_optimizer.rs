@@ -302,6 +302,8 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: None,
                         on_disk: None,
+                        disk_ann_config: None,
+                        ivf_config: None,
                     },
                 )
             })
@@ -417,6 +419,8 @@ mod tests {
                     hnsw_config: None,
                     quantization_config: None,
                     on_disk: None,
+                    disk_ann_config: None,
+                    ivf_config: None,
                 }),
                 ..CollectionParams::empty()
             },
@@ -57,6 +57,13 @@ impl TrackerLog {
         });
     }
 
+    /// Trackers of optimizations, which are still running
+    pub fn running_trackers(&self) -> impl Iterator<Item = &Tracker> {
+        self.descriptions
+            .iter()
+            .filter(|tracker| tracker.state.lock().status == TrackerStatus::Optimizing)
+    }
+
     /// Convert log into list of objects usable in telemetry
     pub fn to_telemetry(&self) -> Vec<TrackerTelemetry> {
         self.descriptions
@@ -126,7 +133,9 @@ pub struct TrackerTelemetry {
 }
 
 /// Handle to an optimizer tracker, allows updating its state
-#[derive(Clone)]
+///
+/// A default handle is not attached to any tracker.
+#[derive(Clone, Default)]
 pub struct TrackerHandle {
     handle: Arc<Mutex<TrackerState>>,
 }
@@ -135,6 +144,21 @@ impl TrackerHandle {
     pub fn update(&self, status: TrackerStatus) {
         self.handle.lock().update(status);
     }
+
+    /// Report that the optimization reached the given `stage`
+    pub fn set_stage(&self, stage: OptimizationStage) {
+        self.handle.lock().progress.stage = stage;
+    }
+
+    /// Set the total number of points the optimization has to copy
+    pub fn set_total_points(&self, total_points: usize) {
+        self.handle.lock().progress.total_points = total_points;
+    }
+
+    /// Report that `points` more points were copied into the optimized segment
+    pub fn add_copied_points(&self, points: usize) {
+        self.handle.lock().progress.copied_points += points;
+    }
 }
 
 impl From<Arc<Mutex<TrackerState>>> for TrackerHandle {
@@ -147,9 +171,35 @@ impl From<Arc<Mutex<TrackerState>>> for TrackerHandle {
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct TrackerState {
     pub status: TrackerStatus,
+    pub progress: TrackerProgress,
     pub end_at: Option<DateTime<Utc>>,
 }
 
+/// Progress of a running optimization
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TrackerProgress {
+    pub stage: OptimizationStage,
+    /// Number of points already copied into the optimized segment
+    pub copied_points: usize,
+    /// Number of points in the segments being optimized
+    pub total_points: usize,
+}
+
+/// Stage of a running optimization
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, JsonSchema, Default, Eq, PartialEq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum OptimizationStage {
+    /// Optimized segments are being wrapped into proxies
+    #[default]
+    Preparing,
+    /// Points are being copied into the optimized segment
+    CopyingPoints,
+    /// Indexes of the optimized segment are being built
+    BuildingIndex,
+    /// Optimized segment is being swapped with the original ones
+    Finalizing,
+}
+
 impl TrackerState {
     /// Update the tracker state to the given `status`
     pub fn update(&mut self, status: TrackerStatus) {
@@ -23,6 +23,8 @@ use crate::collection_manager::holders::proxy_segment::ProxySegment;
 use crate::collection_manager::holders::segment_holder::{
     LockedSegment, LockedSegmentHolder, SegmentId,
 };
+use crate::collection_manager::optimizers::{OptimizationStage, TrackerHandle};
+use crate::collection_manager::search_calibration::spawn_search_calibration;
 use crate::config::CollectionParams;
 use crate::operations::config_diff::DiffConfig;
 use crate::operations::types::{CollectionError, CollectionResult};
@@ -81,7 +83,7 @@ pub trait SegmentOptimizer {
     fn temp_segment(&self, save_version: bool) -> CollectionResult<LockedSegment> {
         let collection_params = self.collection_params();
         let config = SegmentConfig {
-            vector_data: collection_params.into_base_vector_data()?,
+            vector_data: collection_params.into_base_vector_data(self.hnsw_config())?,
             payload_storage_type: if collection_params.on_disk_payload {
                 PayloadStorageType::OnDisk
             } else {
@@ -153,22 +155,32 @@ pub trait SegmentOptimizer {
         let is_on_disk = maximal_vector_store_size_bytes
             >= thresholds.memmap_threshold.saturating_mul(BYTES_IN_KB);
 
-        let mut vector_data = collection_params.into_base_vector_data()?;
+        let mut vector_data = collection_params.into_base_vector_data(self.hnsw_config())?;
 
-        // If indexing, change to HNSW index and quantization
+        // If indexing, change to HNSW, DiskANN or IVF index and quantization
         if is_indexed {
             let collection_hnsw = self.hnsw_config();
             let collection_quantization = self.quantization_config();
             vector_data.iter_mut().for_each(|(vector_name, config)| {
-                // Assign HNSW index
-                let param_hnsw = collection_params
-                    .vectors
-                    .get_params(vector_name)
-                    .and_then(|params| params.hnsw_config);
-                let vector_hnsw = param_hnsw
-                    .and_then(|c| c.update(collection_hnsw).ok())
-                    .unwrap_or_else(|| collection_hnsw.clone());
-                config.index = Indexes::Hnsw(vector_hnsw);
+                // Assign DiskANN or IVF index, if configured for the vector, HNSW index otherwise
+                let vector_params = collection_params.vectors.get_params(vector_name);
+                let param_disk_ann =
+                    vector_params.and_then(|params| params.disk_ann_config.as_ref());
+                let param_ivf = vector_params.and_then(|params| params.ivf_config.as_ref());
+                if let Some(disk_ann_config) = param_disk_ann {
+                    config.index = Indexes::DiskAnn(disk_ann_config.clone());
+                } else if let Some(ivf_config) = param_ivf {
+                    config.index = Indexes::Ivf(ivf_config.clone());
+                } else {
+                    let param_hnsw = collection_params
+                        .vectors
+                        .get_params(vector_name)
+                        .and_then(|params| params.hnsw_config);
+                    let vector_hnsw = param_hnsw
+                        .and_then(|c| c.update(collection_hnsw).ok())
+                        .unwrap_or_else(|| collection_hnsw.clone());
+                    config.index = Indexes::Hnsw(vector_hnsw);
+                }
 
                 // Assign quantization config
                 let param_quantization = collection_params
@@ -291,6 +303,7 @@ pub trait SegmentOptimizer {
     /// * `proxy_deleted_indexes` - Holds a set of Indexes, deleted while optimization was running
     /// * `proxy_created_indexes` - Holds a set of Indexes, created while optimization was running
     /// * `stopped` - flag to check if optimization was cancelled by external thread
+    /// * `progress` - handle to report progress of the optimization to
     ///
     /// # Result
     ///
@@ -302,16 +315,19 @@ pub trait SegmentOptimizer {
         proxy_deleted_indexes: Arc<RwLock<HashSet<PayloadKeyType>>>,
         proxy_created_indexes: Arc<RwLock<HashMap<PayloadKeyType, PayloadFieldSchema>>>,
         stopped: &AtomicBool,
+        progress: &TrackerHandle,
     ) -> CollectionResult<Segment> {
         let mut segment_builder = self.optimized_segment_builder(optimizing_segments)?;
 
         self.check_cancellation(stopped)?;
 
+        progress.set_stage(OptimizationStage::CopyingPoints);
         for segment in optimizing_segments {
             match segment {
                 LockedSegment::Original(segment_arc) => {
                     let segment_guard = segment_arc.read();
                     segment_builder.update_from(&segment_guard, stopped)?;
+                    progress.add_copied_points(segment_guard.available_point_count());
                 }
                 LockedSegment::Proxy(_) => panic!("Attempt to optimize segment which is already currently under optimization. Should never happen"),
             }
@@ -326,6 +342,7 @@ pub trait SegmentOptimizer {
                 .insert(field.to_owned(), schema_type.to_owned());
         }
 
+        progress.set_stage(OptimizationStage::BuildingIndex);
         let mut optimized_segment: Segment = segment_builder.build(stopped)?;
 
         // Delete points in 2 steps
@@ -383,6 +400,18 @@ pub trait SegmentOptimizer {
         segments: LockedSegmentHolder,
         ids: Vec<SegmentId>,
         stopped: &AtomicBool,
+    ) -> CollectionResult<bool> {
+        self.optimize_with_progress(segments, ids, stopped, &TrackerHandle::default())
+    }
+
+    /// Same as [`SegmentOptimizer::optimize`], but reports progress of the optimization
+    /// into the given tracker `progress` handle
+    fn optimize_with_progress(
+        &self,
+        segments: LockedSegmentHolder,
+        ids: Vec<SegmentId>,
+        stopped: &AtomicBool,
+        progress: &TrackerHandle,
     ) -> CollectionResult<bool> {
         check_process_stopped(stopped)?;
 
@@ -415,6 +444,13 @@ pub trait SegmentOptimizer {
             return Ok(false);
         }
 
+        progress.set_total_points(
+            optimizing_segments
+                .iter()
+                .map(|segment| segment.get().read().available_point_count())
+                .sum(),
+        );
+
         check_process_stopped(stopped)?;
 
         let tmp_segment = self.temp_segment(false)?;
@@ -480,6 +516,7 @@ pub trait SegmentOptimizer {
             proxy_deleted_indexes.clone(),
             proxy_created_indexes.clone(),
             stopped,
+            progress,
         ) {
             Ok(segment) => segment,
             Err(error) => {
@@ -504,6 +541,8 @@ pub trait SegmentOptimizer {
 
         // ---- SLOW PART ENDS HERE -----
 
+        progress.set_stage(OptimizationStage::Finalizing);
+
         check_process_stopped(stopped).map_err(|error| {
             self.handle_cancellation(&segments, &proxy_ids, &tmp_segment);
             error
@@ -535,7 +574,11 @@ pub trait SegmentOptimizer {
 
             optimized_segment.prefault_mmap_pages();
 
-            let (_, proxies) = write_segments_guard.swap(optimized_segment, &proxy_ids);
+            let (optimized_segment_id, proxies) =
+                write_segments_guard.swap(optimized_segment, &proxy_ids);
+
+            // Recall of new indexes is measured in the background
+            spawn_search_calibration(write_segments_guard.get(optimized_segment_id));
 
             let has_appendable_segments =
                 write_segments_guard.random_appendable_segment().is_some();
@@ -279,7 +279,7 @@ mod tests {
             segment
                 .get()
                 .write()
-                .set_payload(102, point_id, &json!({ "color": "red" }).into())
+                .set_payload(102, point_id, &json!({ "color": "red" }).into(), &None)
                 .unwrap();
         }
 
@@ -287,7 +287,7 @@ mod tests {
             segment
                 .get()
                 .write()
-                .set_payload(102, point_id, &json!({"size": 0.42}).into())
+                .set_payload(102, point_id, &json!({"size": 0.42}).into(), &None)
                 .unwrap();
         }
 
@@ -310,6 +310,8 @@ mod tests {
                     hnsw_config: None,
                     quantization_config: None,
                     on_disk: None,
+                    disk_ann_config: None,
+                    ivf_config: None,
                 }),
                 ..CollectionParams::empty()
             },
@@ -400,6 +402,8 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: None,
                         on_disk: None,
+                        disk_ann_config: None,
+                        ivf_config: None,
                     },
                 ),
                 (
@@ -410,6 +414,8 @@ mod tests {
                         hnsw_config: None,
                         quantization_config: None,
                         on_disk: None,
+                        disk_ann_config: None,
+                        ivf_config: None,
                     },
                 ),
             ])),
@@ -443,6 +449,7 @@ mod tests {
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            appendable: None,
         };
 
         // Optimizers used in test
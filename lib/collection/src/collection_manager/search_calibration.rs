@@ -0,0 +1,51 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Weak};
+use std::thread;
+
+use parking_lot::RwLock;
+use segment::segment::Segment;
+
+use crate::collection_manager::holders::segment_holder::LockedSegment;
+
+/// Measure the recall of vector indexes of the given segments in a background thread
+///
+/// Segments are only locked for a single step of the calibration at a time, so it doesn't
+/// block updates or removal of the segments. Segments, which are dropped meanwhile, are skipped.
+/// Searches with `target_recall` use the default parameters until their segment is calibrated.
+pub fn spawn_search_calibration<'a>(segments: impl IntoIterator<Item = &'a LockedSegment>) {
+    let segments: Vec<Weak<RwLock<Segment>>> = segments
+        .into_iter()
+        .filter_map(|segment| match segment {
+            LockedSegment::Original(segment) => Some(Arc::downgrade(segment)),
+            LockedSegment::Proxy(_) => None,
+        })
+        .collect();
+    if segments.is_empty() {
+        return;
+    }
+
+    let result = thread::Builder::new()
+        .name("search-calibration".to_string())
+        .spawn(move || {
+            let stopped = AtomicBool::new(false);
+            for segment in segments {
+                loop {
+                    let Some(segment) = segment.upgrade() else {
+                        break;
+                    };
+                    let calibrated = segment.read().calibrate_search_step(&stopped);
+                    match calibrated {
+                        Ok(true) => break,
+                        Ok(false) => {}
+                        Err(err) => {
+                            log::warn!("Failed to calibrate search of segment: {err}");
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+    if let Err(err) = result {
+        log::warn!("Failed to spawn search calibration thread: {err}");
+    }
+}
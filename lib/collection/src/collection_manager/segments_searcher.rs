@@ -11,10 +11,11 @@ use parking_lot::RwLock;
 use segment::common::operation_error::OperationError;
 use segment::common::BYTES_IN_KB;
 use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::search_profile::SegmentSearchProfile;
 use segment::data_types::vectors::QueryVector;
 use segment::entry::entry_point::SegmentEntry;
 use segment::types::{
-    Filter, Indexes, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SeqNumberType,
+    Filter, Indexes, Payload, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SeqNumberType,
     WithPayload, WithPayloadInterface, WithVector, VECTOR_ELEMENT_SIZE,
 };
 use tokio::runtime::Handle;
@@ -24,7 +25,7 @@ use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentH
 use crate::collection_manager::probabilistic_segment_search_sampling::find_search_sampling_over_point_distribution;
 use crate::collection_manager::search_result_aggregator::BatchResultAggregator;
 use crate::operations::types::{
-    CollectionError, CollectionResult, CoreSearchRequestBatch, QueryEnum, Record,
+    CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch, QueryEnum, Record,
 };
 
 type BatchOffset = usize;
@@ -36,7 +37,11 @@ type SegmentBatchSearchResult = Vec<Vec<ScoredPoint>>;
 type BatchSearchResult = Vec<SegmentBatchSearchResult>;
 
 // Result of batch search in one segment
-type SegmentSearchExecutedResult = CollectionResult<(SegmentBatchSearchResult, Vec<bool>)>;
+type SegmentSearchExecutedResult = CollectionResult<(
+    SegmentBatchSearchResult,
+    Vec<bool>,
+    Vec<SegmentSearchProfile>,
+)>;
 
 /// Simple implementation of segment manager
 ///  - rebuild segment for memory optimization purposes
@@ -46,19 +51,25 @@ pub struct SegmentsSearcher {}
 impl SegmentsSearcher {
     async fn execute_searches(
         searches: Vec<JoinHandle<SegmentSearchExecutedResult>>,
-    ) -> CollectionResult<(BatchSearchResult, Vec<Vec<bool>>)> {
+    ) -> CollectionResult<(BatchSearchResult, Vec<Vec<bool>>, Vec<SegmentSearchProfile>)> {
         let searches = try_join_all(searches);
         let search_results_per_segment_res = searches.await?;
 
         let mut search_results_per_segment = vec![];
         let mut further_searches_per_segment = vec![];
+        let mut profiles = vec![];
         for search_result in search_results_per_segment_res {
-            let (search_results, further_searches) = search_result?;
+            let (search_results, further_searches, mut segment_profiles) = search_result?;
             debug_assert!(search_results.len() == further_searches.len());
             search_results_per_segment.push(search_results);
             further_searches_per_segment.push(further_searches);
+            profiles.append(&mut segment_profiles);
         }
-        Ok((search_results_per_segment, further_searches_per_segment))
+        Ok((
+            search_results_per_segment,
+            further_searches_per_segment,
+            profiles,
+        ))
     }
 
     /// Processes search result of [segment_size x batch_size]
@@ -159,6 +170,30 @@ impl SegmentsSearcher {
         is_stopped: Arc<AtomicBool>,
         indexing_threshold_kb: usize,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let (result, _profiles) = Self::search_with_profile(
+            segments,
+            batch_request,
+            runtime_handle,
+            sampling_enabled,
+            is_stopped,
+            indexing_threshold_kb,
+            false,
+        )
+        .await?;
+        Ok(result)
+    }
+
+    /// Same as [`SegmentsSearcher::search`], if `profile` is set also collects
+    /// profiles of all searches performed in segments, including re-runs without sampling
+    pub async fn search_with_profile(
+        segments: Arc<RwLock<SegmentHolder>>,
+        batch_request: Arc<CoreSearchRequestBatch>,
+        runtime_handle: &Handle,
+        sampling_enabled: bool,
+        is_stopped: Arc<AtomicBool>,
+        indexing_threshold_kb: usize,
+        profile: bool,
+    ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<SegmentSearchProfile>)> {
         // Do blocking calls in a blocking task: `segment.get().read()` calls might block async runtime
         let task = {
             let segments = segments.clone();
@@ -180,7 +215,7 @@ impl SegmentsSearcher {
         };
 
         let Some(available_point_count) = task.await? else {
-            return Ok(Vec::new());
+            return Ok((Vec::new(), Vec::new()));
         };
 
         // Using block to ensure `segments` variable is dropped in the end of it
@@ -212,6 +247,7 @@ impl SegmentsSearcher {
                                 use_sampling,
                                 &is_stopped_clone,
                                 indexing_threshold_kb,
+                                profile,
                             )
                         }
                     });
@@ -222,17 +258,13 @@ impl SegmentsSearcher {
 
         // perform search on all segments concurrently
         // the resulting Vec is in the same order as the segment searches were provided.
-        let (all_search_results_per_segment, further_results) =
+        let (all_search_results_per_segment, further_results, mut profiles) =
             Self::execute_searches(searches).await?;
         debug_assert!(all_search_results_per_segment.len() == locked_segments.len());
 
         let (mut result_aggregator, searches_to_rerun) = Self::process_search_result_step1(
             all_search_results_per_segment,
-            batch_request
-                .searches
-                .iter()
-                .map(|request| request.limit + request.offset)
-                .collect(),
+            batch_request.searches.iter().map(retrieval_limit).collect(),
             further_results,
         );
         // The second step of the search is to re-run the search without sampling on some segments
@@ -262,14 +294,16 @@ impl SegmentsSearcher {
                             false,
                             &is_stopped_clone,
                             indexing_threshold_kb,
+                            profile,
                         )
                     }))
                 }
                 res
             };
 
-            let (secondary_search_results_per_segment, _) =
+            let (secondary_search_results_per_segment, _, mut secondary_profiles) =
                 Self::execute_searches(secondary_searches).await?;
+            profiles.append(&mut secondary_profiles);
 
             result_aggregator.update_point_versions(&secondary_search_results_per_segment);
 
@@ -286,8 +320,77 @@ impl SegmentsSearcher {
             }
         }
 
-        let top_scores: Vec<_> = result_aggregator.into_topk();
-        Ok(top_scores)
+        let mut top_scores: Vec<_> = result_aggregator.into_topk();
+
+        // Final scores of the requests with formulas are computed from the payload of the found points
+        if batch_request
+            .searches
+            .iter()
+            .any(|request| request.formula.is_some())
+        {
+            top_scores = runtime_handle
+                .spawn_blocking(move || {
+                    Self::rescore_with_formulas(&segments, &batch_request, top_scores)
+                })
+                .await??;
+        }
+
+        Ok((top_scores, profiles))
+    }
+
+    /// Replace scores of the found points with the scores, computed by the formulas of the requests
+    ///
+    /// Similarity scores are post-processed before evaluation, so `$score` is the same as the
+    /// score of the point in the response without a formula.
+    /// Results are sorted by the final score and cut to `limit + offset`.
+    fn rescore_with_formulas(
+        segments: &RwLock<SegmentHolder>,
+        batch_request: &CoreSearchRequestBatch,
+        mut results: Vec<Vec<ScoredPoint>>,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let with_payload = WithPayload::from(true);
+        let with_vector = WithVector::Bool(false);
+        let empty_payload = Payload::default();
+
+        for (request, points) in batch_request.searches.iter().zip(&mut results) {
+            let Some(formula) = &request.formula else {
+                continue;
+            };
+
+            let vector_name = request.query.get_vector_name();
+            let distance = segments
+                .read()
+                .iter()
+                .find_map(|(_, segment)| {
+                    let segment = segment.get();
+                    let segment = segment.read();
+                    segment
+                        .config()
+                        .vector_data
+                        .get(vector_name)
+                        .map(|config| config.distance)
+                })
+                .ok_or_else(|| {
+                    CollectionError::bad_request(format!("Vector {vector_name} doesn't exist"))
+                })?;
+
+            let point_ids: Vec<_> = points.iter().map(|point| point.id).collect();
+            let payloads: HashMap<_, _> =
+                Self::retrieve(segments, &point_ids, &with_payload, &with_vector)?
+                    .into_iter()
+                    .map(|record| (record.id, record.payload.unwrap_or_default()))
+                    .collect();
+
+            for point in points.iter_mut() {
+                let score = distance.postprocess_score(point.score);
+                let payload = payloads.get(&point.id).unwrap_or(&empty_payload);
+                point.score = formula.evaluate(point.id, score, payload)?;
+            }
+            points.sort_unstable_by(|a, b| b.cmp(a));
+            points.truncate(request.limit + request.offset);
+        }
+
+        Ok(results)
     }
 
     pub fn retrieve(
@@ -368,6 +471,16 @@ struct BatchSearchParams<'a> {
     pub params: Option<&'a SearchParams>,
 }
 
+/// Number of points to retrieve by vector similarity, before rescoring with the formula
+fn retrieval_limit(request: &CoreSearchRequest) -> usize {
+    let limit = request.limit + request.offset;
+    request
+        .formula
+        .as_ref()
+        .and_then(|formula| formula.candidates)
+        .map_or(limit, |candidates| candidates.max(limit))
+}
+
 /// Returns suggested search sampling size for a given number of points and required limit.
 fn sampling_limit(
     limit: usize,
@@ -405,12 +518,14 @@ fn effective_limit(limit: usize, ef_limit: usize, poisson_sampling: usize) -> us
 /// * `is_stopped` - Atomic bool to check if search is stopped
 /// * `indexing_threshold` - If `indexed_only` is enabled, the search will skip
 ///                          segments with more than this number Kb of un-indexed vectors
+/// * `profile` - If true, collect profiles of the performed searches
 ///
 /// # Returns
 ///
 /// Collection Result of:
 /// * Vector of ScoredPoints for each request in the batch
 /// * Vector of boolean indicating if the segment have further points to search
+/// * Vector of search profiles, empty if `profile` is false
 fn search_in_segment(
     segment: LockedSegment,
     request: Arc<CoreSearchRequestBatch>,
@@ -418,11 +533,13 @@ fn search_in_segment(
     use_sampling: bool,
     is_stopped: &AtomicBool,
     indexing_threshold_kb: usize,
-) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<bool>)> {
+    profile: bool,
+) -> SegmentSearchExecutedResult {
     let batch_size = request.searches.len();
 
     let mut result: Vec<Vec<ScoredPoint>> = Vec::with_capacity(batch_size);
     let mut further_results: Vec<bool> = Vec::with_capacity(batch_size); // if segment have more points to return
+    let mut profiles: Vec<SegmentSearchProfile> = vec![];
     let mut vectors_batch: Vec<QueryVector> = vec![];
     let mut prev_params = BatchSearchParams::default();
 
@@ -438,7 +555,7 @@ fn search_in_segment(
             filter: search_query.filter.as_ref(),
             with_payload: WithPayload::from(with_payload_interface),
             with_vector: search_query.with_vector.clone().unwrap_or_default(),
-            top: search_query.limit + search_query.offset,
+            top: retrieval_limit(search_query),
             params: search_query.params.as_ref(),
         };
 
@@ -451,7 +568,7 @@ fn search_in_segment(
             // different params means different batches
             // execute what has been batched so far
             if !vectors_batch.is_empty() {
-                let (mut res, mut further) = execute_batch_search(
+                let (mut res, mut further, mut batch_profiles) = execute_batch_search(
                     &segment,
                     &vectors_batch,
                     &prev_params,
@@ -459,9 +576,11 @@ fn search_in_segment(
                     total_points,
                     is_stopped,
                     indexing_threshold_kb,
+                    profile,
                 )?;
                 further_results.append(&mut further);
                 result.append(&mut res);
+                profiles.append(&mut batch_profiles);
                 vectors_batch.clear()
             }
             // start new batch for current search query
@@ -472,7 +591,7 @@ fn search_in_segment(
 
     // run last batch if any
     if !vectors_batch.is_empty() {
-        let (mut res, mut further) = execute_batch_search(
+        let (mut res, mut further, mut batch_profiles) = execute_batch_search(
             &segment,
             &vectors_batch,
             &prev_params,
@@ -480,14 +599,17 @@ fn search_in_segment(
             total_points,
             is_stopped,
             indexing_threshold_kb,
+            profile,
         )?;
         further_results.append(&mut further);
         result.append(&mut res);
+        profiles.append(&mut batch_profiles);
     }
 
-    Ok((result, further_results))
+    Ok((result, further_results, profiles))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn execute_batch_search(
     segment: &LockedSegment,
     vectors_batch: &Vec<QueryVector>,
@@ -496,7 +618,8 @@ fn execute_batch_search(
     total_points: usize,
     is_stopped: &AtomicBool,
     indexing_threshold_kb: usize,
-) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<bool>)> {
+    profile: bool,
+) -> SegmentSearchExecutedResult {
     let locked_segment = segment.get();
     let read_segment = locked_segment.read();
 
@@ -525,26 +648,40 @@ fn execute_batch_search(
         )?
     {
         let batch_len = vectors_batch.len();
-        return Ok((vec![vec![]; batch_len], vec![false; batch_len]));
+        return Ok((vec![vec![]; batch_len], vec![false; batch_len], vec![]));
     }
     let vectors_batch = &vectors_batch.iter().collect_vec();
-    let res = read_segment.search_batch(
-        search_params.vector_name,
-        vectors_batch,
-        &search_params.with_payload,
-        &search_params.with_vector,
-        search_params.filter,
-        top,
-        search_params.params,
-        is_stopped,
-    )?;
+    let (res, profiles) = if profile {
+        read_segment.search_batch_with_profile(
+            search_params.vector_name,
+            vectors_batch,
+            &search_params.with_payload,
+            &search_params.with_vector,
+            search_params.filter,
+            top,
+            search_params.params,
+            is_stopped,
+        )?
+    } else {
+        let res = read_segment.search_batch(
+            search_params.vector_name,
+            vectors_batch,
+            &search_params.with_payload,
+            &search_params.with_vector,
+            search_params.filter,
+            top,
+            search_params.params,
+            is_stopped,
+        )?;
+        (res, vec![])
+    };
 
     let further_results = res
         .iter()
         .map(|batch_result| batch_result.len() == top)
         .collect();
 
-    Ok((res, further_results))
+    Ok((res, further_results, profiles))
 }
 
 /// Check if the segment is indexed enough to be searched with `indexed_only` parameter
@@ -602,8 +739,8 @@ fn get_hnsw_ef_construct(config: &SegmentConfig, vector_name: &str) -> Option<us
         .vector_data
         .get(vector_name)
         .and_then(|config| match &config.index {
-            Indexes::Plain {} => None,
-            Indexes::Hnsw(hnsw) => Some(hnsw),
+            Indexes::Plain {} | Indexes::DiskAnn(_) | Indexes::Ivf(_) => None,
+            Indexes::Hnsw(hnsw) | Indexes::AppendableHnsw(hnsw) => Some(hnsw),
         })
         .map(|hnsw| hnsw.ef_construct)
 }
@@ -667,6 +804,7 @@ mod tests {
             limit: 5,
             score_threshold: None,
             offset: 0,
+            formula: None,
         };
 
         let batch_request = CoreSearchRequestBatch {
@@ -721,6 +859,8 @@ mod tests {
                 filter: None,
                 params: None,
                 score_threshold: None,
+                profile: None,
+                formula: None,
             };
             let req2 = SearchRequest {
                 vector: random_vector(&mut rnd, 4).into(),
@@ -731,6 +871,8 @@ mod tests {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                profile: None,
+                formula: None,
             };
 
             let batch_request = CoreSearchRequestBatch {
@@ -12,7 +12,7 @@ use segment::types::{
 };
 
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
-use crate::operations::payload_ops::PayloadOps;
+use crate::operations::payload_ops::{IncrementPayload, PayloadOps, UpdatePayloadArray};
 use crate::operations::point_ops::{PointInsertOperations, PointOperations, PointStruct};
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::vector_ops::{PointVectors, VectorOperations};
@@ -121,10 +121,11 @@ pub(crate) fn set_payload(
     op_num: SeqNumberType,
     payload: &Payload,
     points: &[PointIdType],
+    key: &Option<PayloadKeyType>,
 ) -> CollectionResult<usize> {
     let updated_points =
         segments.apply_points_to_appendable(op_num, points, |id, write_segment| {
-            write_segment.set_payload(op_num, id, payload)
+            write_segment.set_payload(op_num, id, payload, key)
         })?;
 
     check_unprocessed_points(points, &updated_points)?;
@@ -149,9 +150,10 @@ pub(crate) fn set_payload_by_filter(
     op_num: SeqNumberType,
     payload: &Payload,
     filter: &Filter,
+    key: &Option<PayloadKeyType>,
 ) -> CollectionResult<usize> {
     let affected_points = points_by_filter(segments, filter)?;
-    set_payload(segments, op_num, payload, &affected_points)
+    set_payload(segments, op_num, payload, &affected_points, key)
 }
 
 pub(crate) fn delete_payload(
@@ -214,6 +216,99 @@ pub(crate) fn clear_payload_by_filter(
     Ok(updated_points.len())
 }
 
+/// Read-modify-write payload of each point, holding the segment lock for the whole update
+fn update_payload_with<F>(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    points: &[PointIdType],
+    update: F,
+) -> CollectionResult<usize>
+where
+    F: Fn(&mut Payload) -> OperationResult<()>,
+{
+    let updated_points =
+        segments.apply_points_to_appendable(op_num, points, |id, write_segment| {
+            let mut payload = write_segment.payload(id)?;
+            update(&mut payload)?;
+            write_segment.set_full_payload(op_num, id, &payload)
+        })?;
+
+    check_unprocessed_points(points, &updated_points)?;
+    Ok(updated_points.len())
+}
+
+fn update_payload_with_selector<F>(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    points: Option<Vec<PointIdType>>,
+    filter: Option<Filter>,
+    update: F,
+) -> CollectionResult<usize>
+where
+    F: Fn(&mut Payload) -> OperationResult<()>,
+{
+    if let Some(points) = points {
+        update_payload_with(segments, op_num, &points, update)
+    } else if let Some(filter) = filter {
+        let affected_points = points_by_filter(segments, &filter)?;
+        update_payload_with(segments, op_num, &affected_points, update)
+    } else {
+        Err(CollectionError::BadRequest {
+            description: "No points or filter specified".to_string(),
+        })
+    }
+}
+
+pub(crate) fn increment_payload(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    operation: IncrementPayload,
+) -> CollectionResult<usize> {
+    let IncrementPayload {
+        key,
+        value,
+        initial,
+        points,
+        filter,
+    } = operation;
+    update_payload_with_selector(segments, op_num, points, filter, |payload| {
+        payload.increment(&key, &value, initial.as_ref())
+    })
+}
+
+pub(crate) fn array_append_payload(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    operation: UpdatePayloadArray,
+    unique: bool,
+) -> CollectionResult<usize> {
+    let UpdatePayloadArray {
+        key,
+        values,
+        points,
+        filter,
+    } = operation;
+    update_payload_with_selector(segments, op_num, points, filter, |payload| {
+        payload.array_append(&key, &values, unique)
+    })
+}
+
+pub(crate) fn array_remove_payload(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    operation: UpdatePayloadArray,
+) -> CollectionResult<usize> {
+    let UpdatePayloadArray {
+        key,
+        values,
+        points,
+        filter,
+    } = operation;
+    update_payload_with_selector(segments, op_num, points, filter, |payload| {
+        payload.array_remove(&key, &values)
+    })
+}
+
 pub(crate) fn create_field_index(
     segments: &SegmentHolder,
     op_num: SeqNumberType,
@@ -472,9 +567,9 @@ pub(crate) fn process_payload_operation(
         PayloadOps::SetPayload(sp) => {
             let payload: Payload = sp.payload;
             if let Some(points) = sp.points {
-                set_payload(&segments.read(), op_num, &payload, &points)
+                set_payload(&segments.read(), op_num, &payload, &points, &sp.key)
             } else if let Some(filter) = sp.filter {
-                set_payload_by_filter(&segments.read(), op_num, &payload, &filter)
+                set_payload_by_filter(&segments.read(), op_num, &payload, &filter, &sp.key)
             } else {
                 Err(CollectionError::BadRequest {
                     description: "No points or filter specified".to_string(),
@@ -510,6 +605,16 @@ pub(crate) fn process_payload_operation(
                 })
             }
         }
+        PayloadOps::Increment(operation) => increment_payload(&segments.read(), op_num, operation),
+        PayloadOps::ArrayAppend(operation) => {
+            array_append_payload(&segments.read(), op_num, operation, false)
+        }
+        PayloadOps::ArrayRemove(operation) => {
+            array_remove_payload(&segments.read(), op_num, operation)
+        }
+        PayloadOps::AddToSet(operation) => {
+            array_append_payload(&segments.read(), op_num, operation, true)
+        }
     }
 }
 
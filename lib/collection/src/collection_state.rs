@@ -7,6 +7,7 @@ use crate::collection::Collection;
 use crate::config::CollectionConfig;
 use crate::operations::types::CollectionResult;
 use crate::shards::replica_set::ReplicaState;
+use crate::shards::resharding::ReshardingState;
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::transfer::shard_transfer::ShardTransfer;
 
@@ -22,6 +23,8 @@ pub struct State {
     pub shards: HashMap<ShardId, ShardInfo>,
     #[serde(default)]
     pub transfers: HashSet<ShardTransfer>,
+    #[serde(default)]
+    pub resharding: Option<ReshardingState>,
 }
 
 impl State {
@@ -34,6 +37,7 @@ impl State {
         Self::apply_config(self.config, collection).await?;
         Self::apply_shard_transfers(self.transfers, collection, this_peer_id, abort_transfer)
             .await?;
+        Self::apply_resharding(self.resharding, self.shards.len(), collection).await?;
         Self::apply_shard_info(self.shards, collection).await?;
         Ok(())
     }
@@ -67,6 +71,33 @@ impl State {
         Ok(())
     }
 
+    async fn apply_resharding(
+        resharding: Option<ReshardingState>,
+        shard_count: usize,
+        collection: &Collection,
+    ) -> CollectionResult<()> {
+        let current_resharding = collection.resharding_state().await;
+        if current_resharding == resharding {
+            return Ok(());
+        }
+        if let Some(current_resharding) = current_resharding {
+            // Resharding, which is not in the snapshot, was finished or aborted in the meantime
+            if resharding.is_none() && current_resharding.shard_id < shard_count as ShardId {
+                collection.finish_resharding().await?;
+            } else {
+                collection.abort_resharding().await?;
+            }
+        }
+        if let Some(resharding) = resharding {
+            log::warn!(
+                "Resharding into shard {} can't be recovered from snapshot of collection {}",
+                resharding.shard_id,
+                collection.name(),
+            );
+        }
+        Ok(())
+    }
+
     async fn apply_config(
         new_config: CollectionConfig,
         collection: &Collection,
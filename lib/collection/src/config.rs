@@ -197,6 +197,35 @@ impl CollectionParams {
             })
     }
 
+    /// Check that the params of each vector are supported by its distance
+    ///
+    /// `collection_quantization` is the collection wide quantization config, used for vectors
+    /// without their own config.
+    pub fn check_vector_params(
+        &self,
+        collection_quantization: Option<&QuantizationConfig>,
+    ) -> CollectionResult<()> {
+        for (vector_name, params) in self.vectors.params_iter() {
+            // There is no appendable on disk storage for binary vectors, they are only moved to
+            // disk by the optimizer once a segment exceeds `memmap_threshold`
+            if params.on_disk.unwrap_or_default() && params.distance.is_binary() {
+                return Err(CollectionError::bad_input(format!(
+                    "`on_disk` is not supported for vector {vector_name} with {:?} distance, use `memmap_threshold` to store binary vectors on disk",
+                    params.distance,
+                )));
+            }
+            let quantized =
+                params.quantization_config.is_some() || collection_quantization.is_some();
+            if quantized && !params.distance.supports_quantization() {
+                return Err(CollectionError::bad_input(format!(
+                    "Quantization is not supported for vector {vector_name} with {:?} distance",
+                    params.distance,
+                )));
+            }
+        }
+        Ok(())
+    }
+
     /// Update collection vectors from the given update vectors config
     pub fn update_vectors_from_diff(
         &mut self,
@@ -245,18 +274,32 @@ impl CollectionParams {
     ///
     /// It is the job of the segment optimizer to change this configuration with optimized settings
     /// based on threshold configurations.
-    pub fn into_base_vector_data(&self) -> CollectionResult<HashMap<String, VectorDataConfig>> {
+    ///
+    /// Vectors with appendable HNSW enabled in `collection_hnsw` or in their own HNSW config
+    /// get an HNSW index, which is updated on every insertion.
+    pub fn into_base_vector_data(
+        &self,
+        collection_hnsw: &HnswConfig,
+    ) -> CollectionResult<HashMap<String, VectorDataConfig>> {
         Ok(self
             .vectors
             .params_iter()
             .map(|(name, params)| {
+                let vector_hnsw = params
+                    .hnsw_config
+                    .and_then(|c| c.update(collection_hnsw).ok())
+                    .unwrap_or_else(|| collection_hnsw.clone());
                 (
                     name.into(),
                     VectorDataConfig {
                         size: params.size.get() as usize,
                         distance: params.distance,
-                        // Plain (disabled) index
-                        index: Indexes::Plain {},
+                        index: if vector_hnsw.appendable.unwrap_or_default() {
+                            Indexes::AppendableHnsw(vector_hnsw)
+                        } else {
+                            // Plain (disabled) index
+                            Indexes::Plain {}
+                        },
                         // Disabled quantization
                         quantization_config: None,
                         // Default to in memory storage
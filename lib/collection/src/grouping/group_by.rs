@@ -199,6 +199,8 @@ impl From<SearchGroupsRequest> for GroupRequest {
             with_payload,
             with_vector,
             score_threshold,
+            profile: None,
+            formula: None,
         };
 
         GroupRequest {
@@ -246,6 +248,7 @@ impl From<RecommendGroupsRequest> for GroupRequest {
             score_threshold,
             using,
             lookup_from,
+            profile: None,
         };
 
         GroupRequest {
@@ -14,6 +14,7 @@ pub mod shards;
 pub mod telemetry;
 mod update_handler;
 pub mod wal;
+pub mod wal_archive;
 
 #[cfg(test)]
 mod tests;
@@ -2,9 +2,8 @@ use std::fmt::Display;
 
 use schemars::JsonSchema;
 use segment::data_types::groups::GroupId;
-use segment::types::PointIdType;
+use segment::types::{InvalidStringId, PointIdType};
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
 
 use super::WithLookup;
 
@@ -69,7 +68,7 @@ impl From<PseudoId> for GroupId {
 #[derive(Debug)]
 pub enum ConversionError {
     IntError(core::num::TryFromIntError),
-    ParseError(uuid::Error),
+    StringIdError(InvalidStringId),
 }
 
 impl TryFrom<PseudoId> for PointIdType {
@@ -77,9 +76,9 @@ impl TryFrom<PseudoId> for PointIdType {
 
     fn try_from(value: PseudoId) -> Result<Self, Self::Error> {
         match value {
-            PseudoId::String(s) => Ok(PointIdType::Uuid(
-                Uuid::try_parse(&s).map_err(ConversionError::ParseError)?,
-            )),
+            PseudoId::String(s) => {
+                PointIdType::parse_str(&s).map_err(ConversionError::StringIdError)
+            }
             PseudoId::NumberU64(n) => Ok(PointIdType::NumId(n)),
             PseudoId::NumberI64(n) => Ok(PointIdType::NumId(
                 u64::try_from(n).map_err(ConversionError::IntError)?,
@@ -93,6 +92,7 @@ impl From<PointIdType> for PseudoId {
         match id {
             PointIdType::NumId(n) => PseudoId::NumberU64(n),
             PointIdType::Uuid(u) => PseudoId::String(u.to_string()),
+            PointIdType::String(id) => PseudoId::String(id.to_string()),
         }
     }
 }
@@ -16,6 +16,10 @@ pub enum ClusterOperations {
     AbortTransfer(AbortTransferOperation),
     /// Drop replica of a shard from a peer
     DropReplica(DropReplicaOperation),
+    /// Start resharding, which adds a new shard to the collection
+    StartResharding(StartReshardingOperation),
+    /// Abort currently running resharding
+    AbortResharding(AbortReshardingOperation),
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -42,6 +46,18 @@ pub struct AbortTransferOperation {
     pub abort_transfer: MoveShard,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct StartReshardingOperation {
+    pub start_resharding: StartResharding,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct AbortReshardingOperation {
+    pub abort_resharding: AbortResharding,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct MoveShard {
@@ -57,6 +73,18 @@ pub struct Replica {
     pub peer_id: PeerId,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct StartResharding {
+    /// Peer to place the new shard on.
+    /// If not specified, the least loaded peer is selected.
+    pub peer_id: Option<PeerId>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct AbortResharding {}
+
 impl Validate for ClusterOperations {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
@@ -64,6 +92,8 @@ impl Validate for ClusterOperations {
             ClusterOperations::ReplicateShard(op) => op.validate(),
             ClusterOperations::AbortTransfer(op) => op.validate(),
             ClusterOperations::DropReplica(op) => op.validate(),
+            ClusterOperations::StartResharding(op) => op.validate(),
+            ClusterOperations::AbortResharding(op) => op.validate(),
         }
     }
 }
@@ -80,6 +80,10 @@ pub struct HnswConfigDiff {
     /// Custom M param for additional payload-aware HNSW links. If not set, default M will be used.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub payload_m: Option<usize>,
+    /// Maintain HNSW index in appendable segments, inserting new points into the graph as they arrive.
+    /// If set to false, appendable segments are searched with full scan until indexed by the optimizer. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub appendable: Option<bool>,
 }
 
 #[derive(
@@ -339,6 +343,8 @@ mod tests {
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: None,
+                disk_ann_config: None,
+                ivf_config: None,
             }
             .into(),
             ..CollectionParams::empty()
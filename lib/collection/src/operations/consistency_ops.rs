@@ -0,0 +1,60 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::shards::shard::{PeerId, ShardId};
+
+/// Compare replicas of the collection shards and optionally repair the diverged points
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct ConsistencyCheckRequest {
+    /// Check only this shard. Default: all shards
+    #[serde(default)]
+    pub shard_id: Option<ShardId>,
+    /// If `true` - copy the content of every mismatched point, on which the majority of the replicas agree, to the other replicas.
+    /// Points missing on some replicas are never restored, because they might have been deleted there.
+    /// Default: `false`, only report the mismatches
+    #[serde(default)]
+    pub repair: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct ConsistencyReport {
+    pub shards: Vec<ShardConsistencyReport>,
+}
+
+/// Result of comparing the replicas of a single shard
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardConsistencyReport {
+    pub shard_id: ShardId,
+    /// `true` if all active replicas have the same points
+    pub consistent: bool,
+    /// Digests of the active replicas, which were compared
+    pub replicas: Vec<ReplicaDigestInfo>,
+    /// Point buckets, in which the replicas differ
+    pub mismatched_buckets: Vec<u32>,
+    /// Number of points, which are missing or different on at least one replica
+    pub inconsistent_points: usize,
+    /// Number of points, which are missing on some replicas.
+    /// They are not repaired, because deletions are not tracked
+    pub missing_points: usize,
+    /// Number of points with different content, on which no majority of the replicas agrees.
+    /// They are not repaired
+    pub conflicting_points: usize,
+    /// Number of points copied to the replicas during the repair
+    pub repaired_points: usize,
+    /// Reason, why the shard could not be checked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ReplicaDigestInfo {
+    pub peer_id: PeerId,
+    pub points_count: usize,
+    /// Hex-encoded root hash of the replica points
+    pub digest: String,
+}
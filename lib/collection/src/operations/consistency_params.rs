@@ -1,13 +1,22 @@
 use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
 
 use api::grpc::qdrant::{
     read_consistency, ReadConsistency as ReadConsistencyGrpc,
     ReadConsistencyType as ReadConsistencyTypeGrpc,
 };
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use segment::types::SeqNumberType;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use validator::{Validate, ValidationError as ValidatorError, ValidationErrors};
 
+use crate::shards::shard::{PeerId, ShardId};
+
 /// Read consistency parameter
 ///
 /// Defines how many replicas should be queried to get the result
@@ -196,6 +205,116 @@ impl From<ReadConsistencyType> for ReadConsistencyTypeGrpc {
     }
 }
 
+/// How long reads wait for the replicas to apply the updates of a consistency token
+pub const CONSISTENCY_TOKEN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Identifies an update on every replica, which has accepted it.
+///
+/// Returned in the update result and accepted by the read requests:
+/// reads only use replicas, which have applied the update.
+///
+/// Operation numbers are local to every replica, so the token stores them per shard and peer.
+/// Serialized as comma-separated `<shard_id>:<peer_id>:<operation_id>` entries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsistencyToken {
+    clocks: BTreeMap<ShardId, BTreeMap<PeerId, SeqNumberType>>,
+}
+
+impl ConsistencyToken {
+    /// Record, that the replica on `peer_id` has accepted the update as `operation_id`
+    pub fn add(&mut self, shard_id: ShardId, peer_id: PeerId, operation_id: SeqNumberType) {
+        let operation = self
+            .clocks
+            .entry(shard_id)
+            .or_default()
+            .entry(peer_id)
+            .or_default();
+        *operation = (*operation).max(operation_id);
+    }
+
+    /// Combine tokens of several updates, the result covers all of them
+    pub fn merge(&mut self, other: ConsistencyToken) {
+        for (shard_id, peers) in other.clocks {
+            for (peer_id, operation_id) in peers {
+                self.add(shard_id, peer_id, operation_id);
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.clocks.is_empty()
+    }
+
+    /// Operation numbers of the update on the replicas of the shard
+    pub fn shard_clocks(&self, shard_id: ShardId) -> Option<&BTreeMap<PeerId, SeqNumberType>> {
+        self.clocks.get(&shard_id)
+    }
+
+    pub fn shard_ids(&self) -> impl Iterator<Item = ShardId> + '_ {
+        self.clocks.keys().copied()
+    }
+}
+
+impl fmt::Display for ConsistencyToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut separator = "";
+        for (shard_id, peers) in &self.clocks {
+            for (peer_id, operation_id) in peers {
+                write!(f, "{separator}{shard_id}:{peer_id}:{operation_id}")?;
+                separator = ",";
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ConsistencyToken {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut token = ConsistencyToken::default();
+        for entry in s.split(',').filter(|entry| !entry.is_empty()) {
+            let mut parts = entry.split(':').map(str::parse::<u64>);
+            let (Some(Ok(shard_id)), Some(Ok(peer_id)), Some(Ok(operation_id)), None) =
+                (parts.next(), parts.next(), parts.next(), parts.next())
+            else {
+                return Err(format!("invalid consistency token entry: {entry}"));
+            };
+            let shard_id = ShardId::try_from(shard_id)
+                .map_err(|_| format!("invalid shard id in consistency token: {shard_id}"))?;
+            token.add(shard_id, peer_id, operation_id);
+        }
+        Ok(token)
+    }
+}
+
+impl Serialize for ConsistencyToken {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ConsistencyToken {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let token = String::deserialize(deserializer)?;
+        token.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl JsonSchema for ConsistencyToken {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "ConsistencyToken".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
 #[derive(Copy, Clone, Debug, thiserror::Error)]
 #[error("Read consistency factor cannot be less than 1")]
 pub struct ValidationError;
@@ -243,4 +362,34 @@ mod tests {
         let schema_str = serde_json::to_string_pretty(&schema).unwrap();
         println!("{schema_str}")
     }
+
+    #[test]
+    fn test_consistency_token() {
+        let mut token = ConsistencyToken::default();
+        token.add(1, 10, 5);
+        token.add(0, 20, 7);
+        token.add(0, 10, 3);
+        token.add(0, 10, 2);
+
+        let mut other = ConsistencyToken::default();
+        other.add(1, 10, 8);
+        other.add(1, 20, 1);
+        token.merge(other);
+
+        let serialized = token.to_string();
+        assert_eq!(serialized, "0:10:3,0:20:7,1:10:8,1:20:1");
+        assert_eq!(serialized.parse::<ConsistencyToken>().unwrap(), token);
+
+        let json = serde_json::to_string(&token).unwrap();
+        assert_eq!(json, format!("\"{serialized}\""));
+        assert_eq!(
+            serde_json::from_str::<ConsistencyToken>(&json).unwrap(),
+            token
+        );
+
+        assert!("".parse::<ConsistencyToken>().unwrap().is_empty());
+        assert!("0:1".parse::<ConsistencyToken>().is_err());
+        assert!("0:1:2:3".parse::<ConsistencyToken>().is_err());
+        assert!("a:1:2".parse::<ConsistencyToken>().is_err());
+    }
 }
@@ -2,21 +2,24 @@ use std::collections::{BTreeMap, HashMap};
 use std::num::{NonZeroU32, NonZeroU64};
 
 use api::grpc::conversions::{from_grpc_dist, payload_to_proto, proto_to_payloads};
+use api::grpc::qdrant::force_optimization_request::Operation as ForceOptimizationPb;
 use api::grpc::qdrant::quantization_config_diff::Quantization;
 use api::grpc::qdrant::update_collection_cluster_setup_request::Operation as ClusterOperationsPb;
+use api::grpc::qdrant::update_collection_optimizations_request::Operation as OptimizationOperationsPb;
 use itertools::Itertools;
 use segment::data_types::vectors::{
     Named, NamedRecoQuery, NamedVector, VectorStruct, DEFAULT_VECTOR_NAME,
 };
-use segment::types::{Distance, QuantizationConfig};
+use segment::types::{CompressionRatio, DiskAnnConfig, Distance, IvfConfig, QuantizationConfig};
 use segment::vector_storage::query::reco_query::RecoQuery;
 use tonic::Status;
 
 use super::types::{
     BaseGroupRequest, CoreSearchRequest, GroupsResult, PointGroup, QueryEnum, RecommendExample,
-    RecommendGroupsRequest, RecommendStrategy, SearchGroupsRequest, VectorParamsDiff,
-    VectorsConfigDiff,
+    RecommendGroupsRequest, RecommendStrategy, SearchGroupsRequest, SearchProfile,
+    ShardSearchProfile, VectorParamsDiff, VectorsConfigDiff,
 };
+use crate::collection_manager::optimizers::OptimizationStage;
 use crate::config::{
     default_replication_factor, default_write_consistency_factor, CollectionConfig,
     CollectionParams, WalConfig,
@@ -24,24 +27,33 @@ use crate::config::{
 use crate::lookup::types::WithLookupInterface;
 use crate::lookup::WithLookup;
 use crate::operations::cluster_ops::{
-    AbortTransferOperation, ClusterOperations, DropReplicaOperation, MoveShard, MoveShardOperation,
-    Replica, ReplicateShardOperation,
+    AbortResharding, AbortReshardingOperation, AbortTransferOperation, ClusterOperations,
+    DropReplicaOperation, MoveShard, MoveShardOperation, Replica, ReplicateShardOperation,
+    StartResharding, StartReshardingOperation,
 };
 use crate::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, QuantizationConfigDiff,
     WalConfigDiff,
 };
+use crate::operations::optimization_ops::{
+    MergeSegments, MergeSegmentsOperation, OptimizationOperations, PauseOptimizers,
+    PauseOptimizersOperation, ResumeOptimizers, ResumeOptimizersOperation, VacuumSegments,
+    VacuumSegmentsOperation,
+};
 use crate::operations::point_ops::PointsSelector::PointIdsSelector;
 use crate::operations::point_ops::{
     Batch, FilterSelector, PointIdsList, PointStruct, PointsSelector, WriteOrdering,
 };
 use crate::operations::types::{
-    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionStatus, CountResult,
-    LocalShardInfo, LookupLocation, OptimizersStatus, RecommendRequest, Record, RemoteShardInfo,
-    SearchRequest, ShardTransferInfo, UpdateResult, UpdateStatus, VectorParams, VectorsConfig,
+    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionOptimizations,
+    CollectionStatus, CountResult, LocalShardInfo, LookupLocation, OptimizersStatus,
+    QueuedOptimization, RecommendRequest, Record, RemoteShardInfo, RunningOptimization,
+    SearchRequest, ShardOptimizations, ShardTransferInfo, UpdateResult, UpdateStatus, VectorParams,
+    VectorsConfig,
 };
 use crate::optimizers_builder::OptimizersConfig;
 use crate::shards::remote_shard::{CollectionCoreSearchRequest, CollectionSearchRequest};
+use crate::shards::resharding::ReshardingInfo;
 
 pub fn write_ordering_to_proto(ordering: WriteOrdering) -> api::grpc::qdrant::WriteOrdering {
     api::grpc::qdrant::WriteOrdering {
@@ -115,6 +127,7 @@ impl From<api::grpc::qdrant::HnswConfigDiff> for HnswConfigDiff {
             max_indexing_threads: value.max_indexing_threads.map(|v| v as usize),
             on_disk: value.on_disk,
             payload_m: value.payload_m.map(|v| v as usize),
+            appendable: value.appendable,
         }
     }
 }
@@ -128,6 +141,99 @@ impl From<HnswConfigDiff> for api::grpc::qdrant::HnswConfigDiff {
             max_indexing_threads: value.max_indexing_threads.map(|v| v as u64),
             on_disk: value.on_disk,
             payload_m: value.payload_m.map(|v| v as u64),
+            appendable: value.appendable,
+        }
+    }
+}
+
+impl From<api::grpc::qdrant::DiskAnnConfig> for DiskAnnConfig {
+    fn from(value: api::grpc::qdrant::DiskAnnConfig) -> Self {
+        let default = DiskAnnConfig::default();
+        Self {
+            max_degree: value.max_degree.map_or(default.max_degree, |v| v as usize),
+            build_list_size: value
+                .build_list_size
+                .map_or(default.build_list_size, |v| v as usize),
+            search_list_size: value
+                .search_list_size
+                .map_or(default.search_list_size, |v| v as usize),
+            beam_width: value.beam_width.map_or(default.beam_width, |v| v as usize),
+            full_scan_threshold: value
+                .full_scan_threshold
+                .map_or(default.full_scan_threshold, |v| v as usize),
+            max_indexing_threads: value
+                .max_indexing_threads
+                .map_or(default.max_indexing_threads, |v| v as usize),
+        }
+    }
+}
+
+impl From<DiskAnnConfig> for api::grpc::qdrant::DiskAnnConfig {
+    fn from(value: DiskAnnConfig) -> Self {
+        Self {
+            max_degree: Some(value.max_degree as u64),
+            build_list_size: Some(value.build_list_size as u64),
+            search_list_size: Some(value.search_list_size as u64),
+            beam_width: Some(value.beam_width as u64),
+            full_scan_threshold: Some(value.full_scan_threshold as u64),
+            max_indexing_threads: Some(value.max_indexing_threads as u64),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::IvfConfig> for IvfConfig {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::IvfConfig) -> Result<Self, Self::Error> {
+        let default = IvfConfig::default();
+        let product_quantization = match value.product_quantization {
+            None => None,
+            Some(compression) => Some(
+                match api::grpc::qdrant::CompressionRatio::from_i32(compression) {
+                    None => {
+                        return Err(Status::invalid_argument(
+                            "Unknown compression ratio".to_string(),
+                        ))
+                    }
+                    Some(api::grpc::qdrant::CompressionRatio::X4) => CompressionRatio::X4,
+                    Some(api::grpc::qdrant::CompressionRatio::X8) => CompressionRatio::X8,
+                    Some(api::grpc::qdrant::CompressionRatio::X16) => CompressionRatio::X16,
+                    Some(api::grpc::qdrant::CompressionRatio::X32) => CompressionRatio::X32,
+                    Some(api::grpc::qdrant::CompressionRatio::X64) => CompressionRatio::X64,
+                },
+            ),
+        };
+        Ok(Self {
+            num_lists: value.num_lists.map(|v| v as usize),
+            nprobe: value.nprobe.map_or(default.nprobe, |v| v as usize),
+            product_quantization,
+            full_scan_threshold: value
+                .full_scan_threshold
+                .map_or(default.full_scan_threshold, |v| v as usize),
+            max_indexing_threads: value
+                .max_indexing_threads
+                .map_or(default.max_indexing_threads, |v| v as usize),
+        })
+    }
+}
+
+impl From<IvfConfig> for api::grpc::qdrant::IvfConfig {
+    fn from(value: IvfConfig) -> Self {
+        Self {
+            num_lists: value.num_lists.map(|v| v as u64),
+            nprobe: Some(value.nprobe as u64),
+            product_quantization: value.product_quantization.map(|compression| {
+                let compression = match compression {
+                    CompressionRatio::X4 => api::grpc::qdrant::CompressionRatio::X4,
+                    CompressionRatio::X8 => api::grpc::qdrant::CompressionRatio::X8,
+                    CompressionRatio::X16 => api::grpc::qdrant::CompressionRatio::X16,
+                    CompressionRatio::X32 => api::grpc::qdrant::CompressionRatio::X32,
+                    CompressionRatio::X64 => api::grpc::qdrant::CompressionRatio::X64,
+                };
+                compression as i32
+            }),
+            full_scan_threshold: Some(value.full_scan_threshold as u64),
+            max_indexing_threads: Some(value.max_indexing_threads as u64),
         }
     }
 }
@@ -211,6 +317,7 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
             segments_count,
             config,
             payload_schema,
+            search_calibration,
         } = value;
 
         api::grpc::qdrant::CollectionInfo {
@@ -270,6 +377,7 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     max_indexing_threads: Some(config.hnsw_config.max_indexing_threads as u64),
                     on_disk: config.hnsw_config.on_disk,
                     payload_m: config.hnsw_config.payload_m.map(|v| v as u64),
+                    appendable: config.hnsw_config.appendable,
                 }),
                 optimizer_config: Some(api::grpc::qdrant::OptimizersConfigDiff {
                     deleted_threshold: Some(config.optimizer_config.deleted_threshold),
@@ -300,6 +408,10 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                 .into_iter()
                 .map(|(k, v)| (k, v.into()))
                 .collect(),
+            search_calibration: search_calibration
+                .into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
         }
     }
 }
@@ -414,6 +526,11 @@ impl TryFrom<api::grpc::qdrant::VectorParams> for VectorParams {
                 .map(grpc_to_segment_quantization_config)
                 .transpose()?,
             on_disk: vector_params.on_disk,
+            disk_ann_config: vector_params.disk_ann_config.map(Into::into),
+            ivf_config: vector_params
+                .ivf_config
+                .map(TryInto::try_into)
+                .transpose()?,
         })
     }
 }
@@ -569,6 +686,11 @@ impl TryFrom<api::grpc::qdrant::GetCollectionInfoResponse> for CollectionInfo {
                     .into_iter()
                     .map(|(k, v)| v.try_into().map(|v| (k, v)))
                     .try_collect()?,
+                search_calibration: collection_info_response
+                    .search_calibration
+                    .into_iter()
+                    .map(|(k, v)| (k, v.into()))
+                    .collect(),
             }),
         }
     }
@@ -684,6 +806,7 @@ impl From<UpdateResult> for api::grpc::qdrant::UpdateResult {
                 UpdateStatus::Acknowledged => api::grpc::qdrant::UpdateStatus::Acknowledged as i32,
                 UpdateStatus::Completed => api::grpc::qdrant::UpdateStatus::Completed as i32,
             },
+            consistency_token: value.consistency_token.map(|token| token.to_string()),
         }
     }
 }
@@ -703,6 +826,11 @@ impl TryFrom<api::grpc::qdrant::UpdateResult> for UpdateResult {
                 }
                 _ => return Err(Status::invalid_argument("Malformed UpdateStatus type")),
             },
+            consistency_token: value
+                .consistency_token
+                .map(|token| token.parse())
+                .transpose()
+                .map_err(Status::invalid_argument)?,
         })
     }
 }
@@ -743,6 +871,9 @@ impl<'a> From<CollectionSearchRequest<'a>> for api::grpc::qdrant::SearchPoints {
                 vector_name => Some(vector_name.to_string()),
             },
             read_consistency: None,
+            profile: request.profile,
+            consistency_token: None,
+            formula: request.formula.clone().map(|f| f.into()),
         }
     }
 }
@@ -792,6 +923,7 @@ impl<'a> From<CollectionCoreSearchRequest<'a>> for api::grpc::qdrant::CoreSearch
             offset: Some(request.offset as u64),
             vector_name: Some(request.query.get_vector_name().to_owned()),
             read_consistency: None,
+            formula: request.formula.clone().map(|f| f.into()),
         }
     }
 }
@@ -866,6 +998,7 @@ impl TryFrom<api::grpc::qdrant::CoreSearchPoints> for CoreSearchRequest {
                     .unwrap_or_default(),
             ),
             score_threshold: value.score_threshold,
+            formula: value.formula.map(|f| f.try_into()).transpose()?,
         })
     }
 }
@@ -895,6 +1028,8 @@ impl TryFrom<api::grpc::qdrant::SearchPoints> for SearchRequest {
                     .unwrap_or_default(),
             ),
             score_threshold: value.score_threshold,
+            profile: value.profile,
+            formula: value.formula.map(|f| f.try_into()).transpose()?,
         })
     }
 }
@@ -915,6 +1050,9 @@ impl TryFrom<api::grpc::qdrant::SearchPointGroups> for SearchGroupsRequest {
             offset: None,
             collection_name: String::new(),
             read_consistency: None,
+            profile: None,
+            consistency_token: None,
+            formula: None,
         };
 
         let SearchRequest {
@@ -926,6 +1064,8 @@ impl TryFrom<api::grpc::qdrant::SearchPointGroups> for SearchGroupsRequest {
             with_payload,
             with_vector,
             score_threshold,
+            profile: _,
+            formula: _,
         } = search_points.try_into()?;
 
         Ok(SearchGroupsRequest {
@@ -1038,6 +1178,7 @@ impl TryFrom<api::grpc::qdrant::RecommendPoints> for RecommendRequest {
             score_threshold: value.score_threshold,
             using: value.using.map(|name| name.into()),
             lookup_from: value.lookup_from.map(|x| x.into()),
+            profile: value.profile,
         })
     }
 }
@@ -1063,6 +1204,8 @@ impl TryFrom<api::grpc::qdrant::RecommendPointGroups> for RecommendGroupsRequest
             collection_name: String::new(),
             positive_vectors: value.positive_vectors,
             negative_vectors: value.negative_vectors,
+            profile: None,
+            consistency_token: None,
         };
 
         let RecommendRequest {
@@ -1078,6 +1221,7 @@ impl TryFrom<api::grpc::qdrant::RecommendPointGroups> for RecommendGroupsRequest
             score_threshold,
             limit: _,
             offset: _,
+            profile: _,
         } = recommend_points.try_into()?;
 
         Ok(RecommendGroupsRequest {
@@ -1109,6 +1253,24 @@ impl From<GroupsResult> for api::grpc::qdrant::GroupsResult {
     }
 }
 
+impl From<ShardSearchProfile> for api::grpc::qdrant::ShardSearchProfile {
+    fn from(value: ShardSearchProfile) -> Self {
+        Self {
+            shard_id: value.shard_id,
+            segments: value.segments.into_iter().map(Into::into).collect(),
+            time: value.time,
+        }
+    }
+}
+
+impl From<SearchProfile> for api::grpc::qdrant::SearchProfile {
+    fn from(value: SearchProfile) -> Self {
+        Self {
+            shards: value.shards.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
 impl From<VectorParams> for api::grpc::qdrant::VectorParams {
     fn from(value: VectorParams) -> Self {
         api::grpc::qdrant::VectorParams {
@@ -1117,11 +1279,16 @@ impl From<VectorParams> for api::grpc::qdrant::VectorParams {
                 Distance::Cosine => api::grpc::qdrant::Distance::Cosine,
                 Distance::Euclid => api::grpc::qdrant::Distance::Euclid,
                 Distance::Dot => api::grpc::qdrant::Distance::Dot,
+                Distance::Manhattan => api::grpc::qdrant::Distance::Manhattan,
+                Distance::Hamming => api::grpc::qdrant::Distance::Hamming,
+                Distance::Jaccard => api::grpc::qdrant::Distance::Jaccard,
             }
             .into(),
             hnsw_config: value.hnsw_config.map(Into::into),
             quantization_config: value.quantization_config.map(Into::into),
             on_disk: value.on_disk,
+            disk_ann_config: value.disk_ann_config.map(Into::into),
+            ivf_config: value.ivf_config.map(Into::into),
         }
     }
 }
@@ -1166,6 +1333,16 @@ impl From<ShardTransferInfo> for api::grpc::qdrant::ShardTransferInfo {
     }
 }
 
+impl From<ReshardingInfo> for api::grpc::qdrant::ReshardingInfo {
+    fn from(value: ReshardingInfo) -> Self {
+        Self {
+            shard_id: value.shard_id,
+            peer_id: value.peer_id,
+            pending_shards: value.pending_shards,
+        }
+    }
+}
+
 impl From<CollectionClusterInfo> for api::grpc::qdrant::CollectionClusterInfoResponse {
     fn from(value: CollectionClusterInfo) -> Self {
         Self {
@@ -1186,6 +1363,7 @@ impl From<CollectionClusterInfo> for api::grpc::qdrant::CollectionClusterInfoRes
                 .into_iter()
                 .map(|shard| shard.into())
                 .collect(),
+            resharding: value.resharding.map(|resharding| resharding.into()),
         }
     }
 }
@@ -1226,6 +1404,138 @@ impl From<ClusterOperationsPb> for ClusterOperations {
                     },
                 })
             }
+            ClusterOperationsPb::StartResharding(op) => {
+                ClusterOperations::StartResharding(StartReshardingOperation {
+                    start_resharding: StartResharding {
+                        peer_id: op.peer_id,
+                    },
+                })
+            }
+            ClusterOperationsPb::AbortResharding(_) => {
+                ClusterOperations::AbortResharding(AbortReshardingOperation {
+                    abort_resharding: AbortResharding {},
+                })
+            }
+        }
+    }
+}
+
+impl From<OptimizationStage> for api::grpc::qdrant::OptimizationStage {
+    fn from(value: OptimizationStage) -> Self {
+        match value {
+            OptimizationStage::Preparing => api::grpc::qdrant::OptimizationStage::Preparing,
+            OptimizationStage::CopyingPoints => api::grpc::qdrant::OptimizationStage::CopyingPoints,
+            OptimizationStage::BuildingIndex => api::grpc::qdrant::OptimizationStage::BuildingIndex,
+            OptimizationStage::Finalizing => api::grpc::qdrant::OptimizationStage::Finalizing,
+        }
+    }
+}
+
+impl From<RunningOptimization> for api::grpc::qdrant::RunningOptimization {
+    fn from(value: RunningOptimization) -> Self {
+        Self {
+            optimizer: value.optimizer,
+            segment_ids: value.segment_ids.into_iter().map(|id| id as u64).collect(),
+            stage: api::grpc::qdrant::OptimizationStage::from(value.stage) as i32,
+            copied_points: value.copied_points as u64,
+            total_points: value.total_points as u64,
+            elapsed: value.elapsed,
+        }
+    }
+}
+
+impl From<QueuedOptimization> for api::grpc::qdrant::QueuedOptimization {
+    fn from(value: QueuedOptimization) -> Self {
+        Self {
+            optimizer: value.optimizer,
+            segment_ids: value.segment_ids.into_iter().map(|id| id as u64).collect(),
+        }
+    }
+}
+
+impl From<ShardOptimizations> for api::grpc::qdrant::ShardOptimizations {
+    fn from(value: ShardOptimizations) -> Self {
+        Self {
+            shard_id: value.shard_id,
+            paused: value.paused,
+            running: value.running.into_iter().map(|op| op.into()).collect(),
+            queued: value.queued.into_iter().map(|op| op.into()).collect(),
+        }
+    }
+}
+
+impl From<CollectionOptimizations> for api::grpc::qdrant::CollectionOptimizationsResponse {
+    fn from(value: CollectionOptimizations) -> Self {
+        Self {
+            shards: value.shards.into_iter().map(|shard| shard.into()).collect(),
+        }
+    }
+}
+
+impl From<OptimizationOperationsPb> for OptimizationOperations {
+    fn from(value: OptimizationOperationsPb) -> Self {
+        match value {
+            OptimizationOperationsPb::Pause(_) => {
+                OptimizationOperations::Pause(PauseOptimizersOperation {
+                    pause: PauseOptimizers {},
+                })
+            }
+            OptimizationOperationsPb::Resume(_) => {
+                OptimizationOperations::Resume(ResumeOptimizersOperation {
+                    resume: ResumeOptimizers {},
+                })
+            }
+            OptimizationOperationsPb::Merge(op) => {
+                OptimizationOperations::Merge(MergeSegmentsOperation {
+                    merge: MergeSegments {
+                        max_segments: op.max_segments as usize,
+                    },
+                })
+            }
+            OptimizationOperationsPb::Vacuum(_) => {
+                OptimizationOperations::Vacuum(VacuumSegmentsOperation {
+                    vacuum: VacuumSegments {},
+                })
+            }
+        }
+    }
+}
+
+impl From<ForceOptimizationPb> for OptimizationOperations {
+    fn from(value: ForceOptimizationPb) -> Self {
+        match value {
+            ForceOptimizationPb::Merge(op) => {
+                OptimizationOperations::Merge(MergeSegmentsOperation {
+                    merge: MergeSegments {
+                        max_segments: op.max_segments as usize,
+                    },
+                })
+            }
+            ForceOptimizationPb::Vacuum(_) => {
+                OptimizationOperations::Vacuum(VacuumSegmentsOperation {
+                    vacuum: VacuumSegments {},
+                })
+            }
+        }
+    }
+}
+
+impl TryFrom<OptimizationOperations> for ForceOptimizationPb {
+    type Error = Status;
+
+    fn try_from(value: OptimizationOperations) -> Result<Self, Self::Error> {
+        match value {
+            OptimizationOperations::Pause(_) | OptimizationOperations::Resume(_) => Err(
+                Status::invalid_argument("Only merge and vacuum optimizations can be forced"),
+            ),
+            OptimizationOperations::Merge(op) => Ok(ForceOptimizationPb::Merge(
+                api::grpc::qdrant::MergeSegments {
+                    max_segments: op.merge.max_segments as u64,
+                },
+            )),
+            OptimizationOperations::Vacuum(_) => Ok(ForceOptimizationPb::Vacuum(
+                api::grpc::qdrant::VacuumSegments {},
+            )),
         }
     }
 }
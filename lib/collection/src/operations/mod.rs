@@ -1,10 +1,13 @@
 pub mod cluster_ops;
 pub mod config_diff;
+pub mod consistency_ops;
 pub mod consistency_params;
 pub mod conversions;
 pub mod operation_effect;
+pub mod optimization_ops;
 pub mod payload_ops;
 pub mod point_ops;
+pub mod recall_ops;
 pub mod shared_storage_config;
 pub mod snapshot_ops;
 pub mod types;
@@ -127,6 +127,26 @@ impl EstimateOperationEffectArea for PayloadOps {
                     OperationEffectArea::Empty
                 }
             }
+            PayloadOps::Increment(increment) => {
+                if let Some(points) = &increment.points {
+                    OperationEffectArea::Points(points.clone())
+                } else if let Some(filter) = &increment.filter {
+                    OperationEffectArea::Filter(filter.clone())
+                } else {
+                    OperationEffectArea::Empty
+                }
+            }
+            PayloadOps::ArrayAppend(update_array)
+            | PayloadOps::ArrayRemove(update_array)
+            | PayloadOps::AddToSet(update_array) => {
+                if let Some(points) = &update_array.points {
+                    OperationEffectArea::Points(points.clone())
+                } else if let Some(filter) = &update_array.filter {
+                    OperationEffectArea::Filter(filter.clone())
+                } else {
+                    OperationEffectArea::Empty
+                }
+            }
         }
     }
 }
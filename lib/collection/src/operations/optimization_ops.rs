@@ -0,0 +1,74 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+#[serde(untagged)]
+pub enum OptimizationOperations {
+    /// Cancel running optimizations and don't start new ones until resumed
+    Pause(PauseOptimizersOperation),
+    /// Allow optimizers to start new optimizations again
+    Resume(ResumeOptimizersOperation),
+    /// Merge the smallest segments, so there are no more than the requested number of segments
+    Merge(MergeSegmentsOperation),
+    /// Remove deleted points from all segments which have any
+    Vacuum(VacuumSegmentsOperation),
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct PauseOptimizersOperation {
+    pub pause: PauseOptimizers,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct ResumeOptimizersOperation {
+    pub resume: ResumeOptimizers,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct MergeSegmentsOperation {
+    #[validate]
+    pub merge: MergeSegments,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct VacuumSegmentsOperation {
+    pub vacuum: VacuumSegments,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct PauseOptimizers {}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct ResumeOptimizers {}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct MergeSegments {
+    /// Maximal number of segments to keep in each shard.
+    /// Segments are merged only if the merged segment fits into the configured `max_segment_size`
+    #[validate(range(min = 1))]
+    pub max_segments: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct VacuumSegments {}
+
+impl Validate for OptimizationOperations {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        match self {
+            OptimizationOperations::Pause(op) => op.validate(),
+            OptimizationOperations::Resume(op) => op.validate(),
+            OptimizationOperations::Merge(op) => op.validate(),
+            OptimizationOperations::Vacuum(op) => op.validate(),
+        }
+    }
+}
@@ -2,7 +2,8 @@ use schemars::JsonSchema;
 use segment::types::{Filter, Payload, PayloadKeyType, PointIdType};
 use serde;
 use serde::{Deserialize, Serialize};
-use validator::Validate;
+use serde_json::{Number, Value};
+use validator::{Validate, ValidationError};
 
 use super::{split_iter_by_shard, OperationToShard, SplitByShard};
 use crate::hash_ring::HashRing;
@@ -16,6 +17,10 @@ pub struct SetPayload {
     pub points: Option<Vec<PointIdType>>,
     /// Assigns payload to each point that satisfy this filter condition
     pub filter: Option<Filter>,
+    /// Assigns payload to the nested object located at this JSON path, e.g. `metadata.author`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(min = 1))]
+    pub key: Option<PayloadKeyType>,
 }
 
 #[derive(Deserialize)]
@@ -23,6 +28,8 @@ struct SetPayloadShadow {
     pub payload: Payload,
     pub points: Option<Vec<PointIdType>>,
     pub filter: Option<Filter>,
+    #[serde(default)]
+    pub key: Option<PayloadKeyType>,
 }
 
 pub struct PointsSelectorValidationError;
@@ -45,6 +52,7 @@ impl TryFrom<SetPayloadShadow> for SetPayload {
                 payload: value.payload,
                 points: value.points,
                 filter: value.filter,
+                key: value.key,
             })
         } else {
             Err(PointsSelectorValidationError)
@@ -55,7 +63,8 @@ impl TryFrom<SetPayloadShadow> for SetPayload {
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 #[serde(try_from = "DeletePayloadShadow")]
 pub struct DeletePayload {
-    /// List of payload keys to remove from payload
+    /// List of payload keys to remove from payload. Nested keys, e.g. `metadata.author.name`,
+    /// and array elements, e.g. `tags[0]`, are supported
     pub keys: Vec<PayloadKeyType>,
     /// Deletes values from each point in this list
     pub points: Option<Vec<PointIdType>>,
@@ -86,6 +95,102 @@ impl TryFrom<DeletePayloadShadow> for DeletePayload {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(try_from = "IncrementPayloadShadow")]
+pub struct IncrementPayload {
+    /// Payload key of the numeric field, nested keys are supported, e.g. `stats.views`
+    #[validate(custom = "validate_plain_payload_key")]
+    pub key: PayloadKeyType,
+    /// Value to add to the field, negative values decrease it
+    pub value: Number,
+    /// Value to initialize the field with, if it is absent. Default is 0
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial: Option<Number>,
+    /// Increments payload field of each point in this list
+    pub points: Option<Vec<PointIdType>>,
+    /// Increments payload field of points that satisfy this filter condition
+    pub filter: Option<Filter>,
+}
+
+#[derive(Deserialize)]
+struct IncrementPayloadShadow {
+    pub key: PayloadKeyType,
+    pub value: Number,
+    #[serde(default)]
+    pub initial: Option<Number>,
+    pub points: Option<Vec<PointIdType>>,
+    pub filter: Option<Filter>,
+}
+
+impl TryFrom<IncrementPayloadShadow> for IncrementPayload {
+    type Error = PointsSelectorValidationError;
+
+    fn try_from(value: IncrementPayloadShadow) -> Result<Self, Self::Error> {
+        if value.points.is_some() || value.filter.is_some() {
+            Ok(IncrementPayload {
+                key: value.key,
+                value: value.value,
+                initial: value.initial,
+                points: value.points,
+                filter: value.filter,
+            })
+        } else {
+            Err(PointsSelectorValidationError)
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(try_from = "UpdatePayloadArrayShadow")]
+pub struct UpdatePayloadArray {
+    /// Payload key of the array field, nested keys are supported, e.g. `metadata.tags`
+    #[validate(custom = "validate_plain_payload_key")]
+    pub key: PayloadKeyType,
+    /// Values to add to or remove from the array
+    #[validate(length(min = 1))]
+    pub values: Vec<Value>,
+    /// Updates payload field of each point in this list
+    pub points: Option<Vec<PointIdType>>,
+    /// Updates payload field of points that satisfy this filter condition
+    pub filter: Option<Filter>,
+}
+
+#[derive(Deserialize)]
+struct UpdatePayloadArrayShadow {
+    pub key: PayloadKeyType,
+    pub values: Vec<Value>,
+    pub points: Option<Vec<PointIdType>>,
+    pub filter: Option<Filter>,
+}
+
+impl TryFrom<UpdatePayloadArrayShadow> for UpdatePayloadArray {
+    type Error = PointsSelectorValidationError;
+
+    fn try_from(value: UpdatePayloadArrayShadow) -> Result<Self, Self::Error> {
+        if value.points.is_some() || value.filter.is_some() {
+            Ok(UpdatePayloadArray {
+                key: value.key,
+                values: value.values,
+                points: value.points,
+                filter: value.filter,
+            })
+        } else {
+            Err(PointsSelectorValidationError)
+        }
+    }
+}
+
+/// Numeric and array payload operations only support dot-separated object keys
+fn validate_plain_payload_key(key: &str) -> Result<(), ValidationError> {
+    if key.is_empty() || key.contains('[') {
+        let mut error = ValidationError::new("plain_payload_key");
+        error.message =
+            Some("must be a non-empty dot-separated path without array notation".into());
+        return Err(error);
+    }
+    Ok(())
+}
+
 /// Define operations description for point payloads manipulation
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -100,6 +205,14 @@ pub enum PayloadOps {
     ClearPayloadByFilter(Filter),
     /// Overwrite full payload with given keys
     OverwritePayload(SetPayload),
+    /// Atomically add a number to the numeric payload field
+    Increment(IncrementPayload),
+    /// Append values to the array payload field
+    ArrayAppend(UpdatePayloadArray),
+    /// Remove all occurrences of the values from the array payload field
+    ArrayRemove(UpdatePayloadArray),
+    /// Append values to the array payload field, unless they are already present
+    AddToSet(UpdatePayloadArray),
 }
 
 impl PayloadOps {
@@ -110,6 +223,10 @@ impl PayloadOps {
             PayloadOps::ClearPayload { .. } => false,
             PayloadOps::ClearPayloadByFilter(_) => false,
             PayloadOps::OverwritePayload(_) => true,
+            PayloadOps::Increment(_) => true,
+            PayloadOps::ArrayAppend(_) => true,
+            PayloadOps::ArrayRemove(_) => false,
+            PayloadOps::AddToSet(_) => true,
         }
     }
 }
@@ -122,6 +239,10 @@ impl Validate for PayloadOps {
             PayloadOps::ClearPayload { .. } => Ok(()),
             PayloadOps::ClearPayloadByFilter(_) => Ok(()),
             PayloadOps::OverwritePayload(operation) => operation.validate(),
+            PayloadOps::Increment(operation) => operation.validate(),
+            PayloadOps::ArrayAppend(operation) => operation.validate(),
+            PayloadOps::ArrayRemove(operation) => operation.validate(),
+            PayloadOps::AddToSet(operation) => operation.validate(),
         }
     }
 }
@@ -141,6 +262,57 @@ impl SplitByShard for PayloadOps {
             PayloadOps::OverwritePayload(operation) => operation
                 .split_by_shard(ring)
                 .map(PayloadOps::OverwritePayload),
+            PayloadOps::Increment(operation) => {
+                operation.split_by_shard(ring).map(PayloadOps::Increment)
+            }
+            PayloadOps::ArrayAppend(operation) => {
+                operation.split_by_shard(ring).map(PayloadOps::ArrayAppend)
+            }
+            PayloadOps::ArrayRemove(operation) => {
+                operation.split_by_shard(ring).map(PayloadOps::ArrayRemove)
+            }
+            PayloadOps::AddToSet(operation) => {
+                operation.split_by_shard(ring).map(PayloadOps::AddToSet)
+            }
+        }
+    }
+}
+
+impl SplitByShard for IncrementPayload {
+    fn split_by_shard(self, ring: &HashRing<ShardId>) -> OperationToShard<Self> {
+        match (&self.points, &self.filter) {
+            (Some(_), _) => {
+                split_iter_by_shard(self.points.unwrap(), |id| *id, ring).map(|points| {
+                    IncrementPayload {
+                        points: Some(points),
+                        key: self.key.clone(),
+                        value: self.value.clone(),
+                        initial: self.initial.clone(),
+                        filter: self.filter.clone(),
+                    }
+                })
+            }
+            (None, Some(_)) => OperationToShard::to_all(self),
+            (None, None) => OperationToShard::to_none(),
+        }
+    }
+}
+
+impl SplitByShard for UpdatePayloadArray {
+    fn split_by_shard(self, ring: &HashRing<ShardId>) -> OperationToShard<Self> {
+        match (&self.points, &self.filter) {
+            (Some(_), _) => {
+                split_iter_by_shard(self.points.unwrap(), |id| *id, ring).map(|points| {
+                    UpdatePayloadArray {
+                        points: Some(points),
+                        key: self.key.clone(),
+                        values: self.values.clone(),
+                        filter: self.filter.clone(),
+                    }
+                })
+            }
+            (None, Some(_)) => OperationToShard::to_all(self),
+            (None, None) => OperationToShard::to_none(),
         }
     }
 }
@@ -171,6 +343,7 @@ impl SplitByShard for SetPayload {
                     points: Some(points),
                     payload: self.payload.clone(),
                     filter: self.filter.clone(),
+                    key: self.key.clone(),
                 })
             }
             (None, Some(_)) => OperationToShard::to_all(self),
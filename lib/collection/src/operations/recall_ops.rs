@@ -0,0 +1,134 @@
+use schemars::JsonSchema;
+use segment::types::{Filter, QuantizationSearchParams, SearchParams, SegmentType};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::collection_manager::holders::segment_holder::SegmentId;
+use crate::shards::shard::ShardId;
+
+const fn default_recall_sample() -> usize {
+    100
+}
+
+const fn default_recall_limit() -> usize {
+    10
+}
+
+/// Estimate recall of the approximate search, by comparing it with the exact search
+///
+/// Vectors of randomly sampled stored points are used as queries.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct RecallEstimationRequest {
+    /// Number of stored points to use as queries. Default: 100
+    #[serde(default = "default_recall_sample")]
+    #[validate(range(min = 1, max = 10000))]
+    pub sample: usize,
+    /// Number of nearest neighbors to compare, recall@k is reported for this `k`. Default: 10
+    #[serde(default = "default_recall_limit")]
+    #[validate(range(min = 1, max = 1000))]
+    pub limit: usize,
+    /// Sample queries only from the points, which satisfy this conditions.
+    /// Searches are performed with the same filter.
+    #[validate]
+    pub filter: Option<Filter>,
+    /// Params of the approximate search, e.g. `hnsw_ef`.
+    /// Quantization params are overridden for each measured quantization mode.
+    #[validate]
+    pub params: Option<SearchParams>,
+}
+
+/// How quantized vectors are used by the approximate search
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum RecallQuantizationMode {
+    /// Quantized vectors are ignored, original vectors are used for scoring
+    Disabled,
+    /// Only quantized vectors are used for scoring
+    Quantized,
+    /// Candidates found with quantized vectors are rescored with original vectors
+    Rescored,
+}
+
+impl RecallQuantizationMode {
+    /// Modes to measure for a vector with or without quantization
+    pub fn measured(quantized: bool) -> &'static [RecallQuantizationMode] {
+        if quantized {
+            &[Self::Disabled, Self::Quantized, Self::Rescored]
+        } else {
+            &[Self::Disabled]
+        }
+    }
+
+    /// Params of the approximate search in this mode
+    pub fn search_params(&self, params: Option<SearchParams>) -> SearchParams {
+        let mut params = params.unwrap_or_default();
+        let oversampling = params.quantization.and_then(|q| q.oversampling);
+        params.exact = false;
+        params.quantization = Some(match self {
+            Self::Disabled => QuantizationSearchParams {
+                ignore: true,
+                rescore: Some(false),
+                oversampling: None,
+            },
+            Self::Quantized => QuantizationSearchParams {
+                ignore: false,
+                rescore: Some(false),
+                oversampling: None,
+            },
+            Self::Rescored => QuantizationSearchParams {
+                ignore: false,
+                rescore: Some(true),
+                oversampling,
+            },
+        });
+        params
+    }
+}
+
+/// Recall of the approximate search for a single vector and quantization mode
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct RecallEstimate {
+    /// Name of the vector
+    pub vector: String,
+    pub quantization: RecallQuantizationMode,
+    /// Number of queries, for which the exact search found any points
+    pub queries: usize,
+    /// Fraction of the exact search results, found by the approximate search
+    pub recall: f64,
+    /// Average duration of the approximate search, in seconds
+    pub latency: f64,
+    /// Average duration of the exact search, in seconds
+    pub exact_latency: f64,
+}
+
+/// Recall of the approximate search in a single segment
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SegmentRecallEstimation {
+    pub segment_id: SegmentId,
+    pub segment_type: SegmentType,
+    pub points_count: usize,
+    pub estimates: Vec<RecallEstimate>,
+}
+
+/// Recall of the approximate search in a single shard, and in each of its segments
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardRecallEstimation {
+    pub shard_id: ShardId,
+    pub estimates: Vec<RecallEstimate>,
+    pub segments: Vec<SegmentRecallEstimation>,
+}
+
+/// Recall of the approximate search in all shards of the collection
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct RecallEstimationReport {
+    /// Number of sampled query points
+    pub sample: usize,
+    /// Recall over all shards of the collection
+    pub estimates: Vec<RecallEstimate>,
+    pub shards: Vec<ShardRecallEstimation>,
+}
@@ -1,7 +1,9 @@
 use std::num::NonZeroUsize;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use crate::operations::types::NodeType;
+use crate::shards::shard::ShardId;
 
 /// Default timeout for search requests.
 /// In cluster mode, this should be aligned with collection timeout.
@@ -21,6 +23,7 @@ pub struct SharedStorageConfig {
     pub search_timeout: Duration,
     pub update_concurrency: Option<NonZeroUsize>,
     pub is_distributed: bool,
+    pub wal_archive_path: Option<PathBuf>,
 }
 
 impl Default for SharedStorageConfig {
@@ -33,11 +36,13 @@ impl Default for SharedStorageConfig {
             search_timeout: DEFAULT_SEARCH_TIMEOUT,
             update_concurrency: None,
             is_distributed: false,
+            wal_archive_path: None,
         }
     }
 }
 
 impl SharedStorageConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         update_queue_size: Option<usize>,
         node_type: NodeType,
@@ -46,6 +51,7 @@ impl SharedStorageConfig {
         search_timeout: Option<Duration>,
         update_concurrency: Option<NonZeroUsize>,
         is_distributed: bool,
+        wal_archive_path: Option<PathBuf>,
     ) -> Self {
         let update_queue_size = update_queue_size.unwrap_or(match node_type {
             NodeType::Normal => DEFAULT_UPDATE_QUEUE_SIZE,
@@ -59,6 +65,14 @@ impl SharedStorageConfig {
             search_timeout: search_timeout.unwrap_or(DEFAULT_SEARCH_TIMEOUT),
             update_concurrency,
             is_distributed,
+            wal_archive_path,
         }
     }
+
+    /// Directory to archive WAL of the shard into, if WAL archiving is enabled
+    pub fn wal_archive_path(&self, collection_name: &str, shard_id: ShardId) -> Option<PathBuf> {
+        self.wal_archive_path
+            .as_ref()
+            .map(|path| path.join(collection_name).join(shard_id.to_string()))
+    }
 }
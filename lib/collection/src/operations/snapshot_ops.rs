@@ -2,13 +2,17 @@ use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 use api::grpc::conversions::date_time_to_proto;
-use chrono::NaiveDateTime;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use url::Url;
 use validator::Validate;
 
-use crate::operations::types::CollectionResult;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::wal_archive::ReplayUntil;
+
+/// Extension of the file next to the snapshot, which holds its SHA-256 checksum
+const CHECKSUM_EXTENSION: &str = "checksum";
 
 /// Defines source of truth for snapshot recovery:
 /// `NoSync` means - restore snapshot without *any* additional synchronization.
@@ -35,6 +39,54 @@ pub struct SnapshotRecover {
     /// If set to `Replica`, the current state will be used as a source of truth, and after recovery if will be synchronized with the snapshot.
     #[serde(default)]
     pub priority: Option<SnapshotPriority>,
+
+    /// Optional SHA256 checksum to verify snapshot integrity before recovery.
+    #[serde(default)]
+    pub checksum: Option<String>,
+
+    /// Replay operations from the WAL archive of this peer after the snapshot is recovered.
+    /// Requires WAL archiving to be enabled with `storage.wal_archive_path`.
+    #[serde(default)]
+    pub wal_replay: Option<WalReplay>,
+}
+
+/// Point to replay archived WAL operations up to.
+/// If both limits are set, replay stops at the first one reached.
+/// If none is set, all archived operations are replayed.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Copy, Default)]
+pub struct WalReplay {
+    /// Replay operations up to this operation number in the WAL of each shard, inclusive
+    #[serde(default)]
+    pub until_operation: Option<u64>,
+    /// Replay operations written at this time or earlier, with the precision of one second
+    #[serde(default)]
+    pub until_time: Option<DateTime<Utc>>,
+}
+
+impl From<WalReplay> for ReplayUntil {
+    fn from(value: WalReplay) -> Self {
+        let WalReplay {
+            until_operation,
+            until_time,
+        } = value;
+        ReplayUntil {
+            operation: until_operation,
+            time: until_time,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+pub struct ClusterSnapshotRecover {
+    /// Location of the cluster snapshot of the collection.
+    /// Examples:
+    /// - URL `http://localhost:6333/collections/my_collection/snapshots/my_collection-cluster-2023-10-01-12-00-00.snapshot`
+    /// - Local path `file:///qdrant/snapshots/my_collection/my_collection-cluster-2023-10-01-12-00-00.snapshot`
+    pub location: Url,
+
+    /// Optional SHA256 checksum to verify snapshot integrity before recovery.
+    #[serde(default)]
+    pub checksum: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
@@ -42,6 +94,9 @@ pub struct SnapshotDescription {
     pub name: String,
     pub creation_time: Option<NaiveDateTime>,
     pub size: u64,
+    /// SHA256 checksum of the snapshot file, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
 }
 
 impl From<SnapshotDescription> for api::grpc::qdrant::SnapshotDescription {
@@ -50,6 +105,7 @@ impl From<SnapshotDescription> for api::grpc::qdrant::SnapshotDescription {
             name: value.name,
             creation_time: value.creation_time.map(date_time_to_proto),
             size: value.size as i64,
+            checksum: value.checksum,
         }
     }
 }
@@ -66,13 +122,69 @@ pub async fn get_snapshot_description(path: &Path) -> CollectionResult<SnapshotD
             })
     });
     let size = file_meta.len();
+    let checksum = read_checksum_for_snapshot(path).await;
     Ok(SnapshotDescription {
         name: name.to_string(),
         creation_time,
         size,
+        checksum,
     })
 }
 
+/// Path of the file with the checksum of the snapshot at `snapshot_path`
+pub fn get_checksum_path(snapshot_path: &Path) -> PathBuf {
+    let mut checksum_path = snapshot_path.as_os_str().to_owned();
+    checksum_path.push(".");
+    checksum_path.push(CHECKSUM_EXTENSION);
+    PathBuf::from(checksum_path)
+}
+
+/// Store the checksum of the snapshot next to it
+pub async fn write_checksum_for_snapshot(
+    snapshot_path: &Path,
+    checksum: &str,
+) -> CollectionResult<()> {
+    tokio::fs::write(get_checksum_path(snapshot_path), checksum).await?;
+    Ok(())
+}
+
+/// Stored checksum of the snapshot, `None` for snapshots created without it
+pub async fn read_checksum_for_snapshot(snapshot_path: &Path) -> Option<String> {
+    let checksum = tokio::fs::read_to_string(get_checksum_path(snapshot_path))
+        .await
+        .ok()?;
+    Some(checksum.trim().to_string())
+}
+
+/// Delete the snapshot together with its checksum file
+pub async fn remove_snapshot(snapshot_path: &Path) -> CollectionResult<()> {
+    tokio::fs::remove_file(snapshot_path).await?;
+    if let Err(err) = tokio::fs::remove_file(get_checksum_path(snapshot_path)).await {
+        if err.kind() != std::io::ErrorKind::NotFound {
+            return Err(err.into());
+        }
+    }
+    Ok(())
+}
+
+/// Check that the snapshot file has the expected checksum, before it is unpacked
+pub async fn verify_snapshot_checksum(
+    snapshot_path: &Path,
+    expected_checksum: &str,
+) -> CollectionResult<()> {
+    let path = snapshot_path.to_path_buf();
+    let checksum =
+        tokio::task::spawn_blocking(move || segment::utils::tar::file_checksum(&path)).await??;
+
+    if !checksum.eq_ignore_ascii_case(expected_checksum.trim()) {
+        return Err(CollectionError::bad_input(format!(
+            "Snapshot checksum mismatch: expected {expected_checksum}, got {checksum}. \
+             The snapshot file is corrupted or incomplete"
+        )));
+    }
+    Ok(())
+}
+
 pub async fn list_snapshots_in_directory(
     directory: &Path,
 ) -> CollectionResult<Vec<SnapshotDescription>> {
@@ -96,6 +208,10 @@ pub struct ShardSnapshotRecover {
 
     #[serde(default)]
     pub priority: Option<SnapshotPriority>,
+
+    /// Optional SHA256 checksum to verify snapshot integrity before recovery.
+    #[serde(default)]
+    pub checksum: Option<String>,
 }
 
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize, schemars::JsonSchema)]
@@ -7,6 +7,7 @@ use std::num::NonZeroU64;
 use std::time::SystemTimeError;
 
 use api::grpc::transport_channel_pool::RequestError;
+use chrono::{DateTime, Utc};
 use common::types::ScoreType;
 use common::validation::validate_range_generic;
 use io::file_operations::FileStorageError;
@@ -15,14 +16,17 @@ use merge::Merge;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::common::operation_error::OperationError;
+use segment::data_types::formula::Formula;
 use segment::data_types::groups::GroupId;
+use segment::data_types::search_profile::SegmentSearchProfile;
 use segment::data_types::vectors::{
     Named, NamedRecoQuery, NamedVectorStruct, QueryVector, VectorElementType, VectorStruct,
     VectorType, DEFAULT_VECTOR_NAME,
 };
 use segment::types::{
-    Distance, Filter, Payload, PayloadIndexInfo, PayloadKeyType, PointIdType, QuantizationConfig,
-    ScoredPoint, SearchParams, SeqNumberType, WithPayloadInterface, WithVector,
+    DiskAnnConfig, Distance, Filter, IvfConfig, Payload, PayloadIndexInfo, PayloadKeyType,
+    PointIdType, QuantizationConfig, ScoredPoint, SearchCalibration, SearchParams, SeqNumberType,
+    WithPayloadInterface, WithVector,
 };
 use serde;
 use serde::{Deserialize, Serialize};
@@ -35,11 +39,15 @@ use tonic::codegen::http::uri::InvalidUri;
 use validator::{Validate, ValidationError, ValidationErrors};
 
 use super::config_diff;
+use crate::collection_manager::holders::segment_holder::SegmentId;
+use crate::collection_manager::optimizers::OptimizationStage;
 use crate::config::{CollectionConfig, CollectionParams};
 use crate::lookup::types::WithLookupInterface;
 use crate::operations::config_diff::{HnswConfigDiff, QuantizationConfigDiff};
+use crate::operations::consistency_params::ConsistencyToken;
 use crate::save_on_disk;
 use crate::shards::replica_set::ReplicaState;
+use crate::shards::resharding::ReshardingInfo;
 use crate::shards::shard::{PeerId, ShardId};
 use crate::wal::WalError;
 
@@ -111,6 +119,10 @@ pub struct CollectionInfo {
     pub config: CollectionConfig,
     /// Types of stored payload
     pub payload_schema: HashMap<PayloadKeyType, PayloadIndexInfo>,
+    /// Measured recall of vector indexes, used for searches with `target_recall`.
+    /// The lowest recall among all segments is reported for each measurement.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub search_calibration: HashMap<String, SearchCalibration>,
 }
 
 /// Current clustering distribution for the collection
@@ -126,6 +138,8 @@ pub struct CollectionClusterInfo {
     pub remote_shards: Vec<RemoteShardInfo>,
     /// Shard transfers
     pub shard_transfers: Vec<ShardTransferInfo>,
+    /// Resharding in progress
+    pub resharding: Option<ReshardingInfo>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
@@ -138,6 +152,49 @@ pub struct ShardTransferInfo {
     pub sync: bool,
 }
 
+/// Optimizations of the collection shards, located on this peer
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct CollectionOptimizations {
+    pub shards: Vec<ShardOptimizations>,
+}
+
+/// Optimizations of a single shard
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct ShardOptimizations {
+    pub shard_id: ShardId,
+    /// If `true`, optimizers are paused and new optimizations are not started
+    pub paused: bool,
+    /// Optimizations which are currently running
+    pub running: Vec<RunningOptimization>,
+    /// Optimizations which optimizers would start next
+    pub queued: Vec<QueuedOptimization>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct RunningOptimization {
+    /// Name of the optimizer
+    pub optimizer: String,
+    /// IDs of segments being optimized
+    pub segment_ids: Vec<SegmentId>,
+    pub stage: OptimizationStage,
+    /// Number of points already copied into the optimized segment
+    pub copied_points: usize,
+    /// Number of points in the segments being optimized
+    pub total_points: usize,
+    /// Start time of the optimization
+    pub started_at: DateTime<Utc>,
+    /// Time elapsed since the start of the optimization, in seconds
+    pub elapsed: f64,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct QueuedOptimization {
+    /// Name of the optimizer
+    pub optimizer: String,
+    /// IDs of segments to optimize
+    pub segment_ids: Vec<SegmentId>,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct LocalShardInfo {
@@ -176,6 +233,9 @@ pub struct UpdateResult {
     pub operation_id: SeqNumberType,
     /// Update status
     pub status: UpdateStatus,
+    /// Pass it to the following read requests to make sure they observe this update
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consistency_token: Option<ConsistencyToken>,
 }
 
 /// Scroll request - paginate over all points which matches given condition
@@ -252,6 +312,14 @@ pub struct SearchRequest {
     /// Score of the returned result might be higher or smaller than the threshold depending on the
     /// Distance function used. E.g. for cosine similarity only higher scores will be returned.
     pub score_threshold: Option<ScoreType>,
+    /// If true, return details of how the search was performed in each shard and segment.
+    /// Ignored in batch requests. Default: false
+    #[serde(default)]
+    pub profile: Option<bool>,
+    /// Compute the final score of the found points with this formula, instead of the vector
+    /// similarity score. Score threshold is applied to the final score.
+    #[validate]
+    pub formula: Option<Formula>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -307,6 +375,8 @@ pub struct CoreSearchRequest {
     /// Whether to return the point vector with the result?
     pub with_vector: Option<WithVector>,
     pub score_threshold: Option<ScoreType>,
+    /// Formula of the final score, evaluated after vector retrieval
+    pub formula: Option<Formula>,
 }
 
 #[derive(Debug, Clone)]
@@ -314,6 +384,30 @@ pub struct CoreSearchRequestBatch {
     pub searches: Vec<CoreSearchRequest>,
 }
 
+/// Details of how the search was performed in every shard
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchProfile {
+    pub shards: Vec<ShardSearchProfile>,
+}
+
+impl SearchProfile {
+    pub fn merge(&mut self, other: SearchProfile) {
+        self.shards.extend(other.shards);
+    }
+}
+
+/// Details of how the search was performed in a single shard
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardSearchProfile {
+    pub shard_id: ShardId,
+    /// Searches performed in every segment of the shard, across all queried replicas
+    pub segments: Vec<SegmentSearchProfile>,
+    /// Time spent searching the shard, in seconds
+    pub time: f64,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 pub struct SearchGroupsRequest {
     /// Look for vectors closest to this
@@ -482,6 +576,11 @@ pub struct RecommendRequest {
     /// Note: the other collection should have the same vector size as the current collection
     #[serde(default)]
     pub lookup_from: Option<LookupLocation>,
+
+    /// If true, return details of how the search was performed in each shard and segment.
+    /// Ignored in batch requests. Default: false
+    #[serde(default)]
+    pub profile: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
@@ -935,6 +1034,16 @@ pub struct VectorParams {
     /// Default: false
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub on_disk: Option<bool>,
+    /// Params of DiskANN index. If set, indexed segments use DiskANN graph stored on disk instead of HNSW.
+    /// Not supported for binary distances.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub disk_ann_config: Option<DiskAnnConfig>,
+    /// Params of IVF index. If set, indexed segments use IVF index instead of HNSW.
+    /// New vectors are added to the index without rebuilding. Not supported for binary distances.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub ivf_config: Option<IvfConfig>,
 }
 
 /// Validate the value is in `[1, 65536]` or `None`.
@@ -1309,6 +1418,7 @@ impl From<SearchRequest> for CoreSearchRequest {
             with_payload: request.with_payload,
             with_vector: request.with_vector,
             score_threshold: request.score_threshold,
+            formula: request.formula,
         }
     }
 }
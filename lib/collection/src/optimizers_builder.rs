@@ -13,6 +13,7 @@ use crate::collection_manager::optimizers::merge_optimizer::MergeOptimizer;
 use crate::collection_manager::optimizers::segment_optimizer::OptimizerThresholds;
 use crate::collection_manager::optimizers::vacuum_optimizer::VacuumOptimizer;
 use crate::config::CollectionParams;
+use crate::operations::optimization_ops::{MergeSegmentsOperation, OptimizationOperations};
 use crate::update_handler::Optimizer;
 
 const DEFAULT_MAX_SEGMENT_PER_CPU_KB: usize = 200_000;
@@ -125,16 +126,7 @@ pub fn clear_temp_segments(shard_path: &Path) {
     }
 }
 
-pub fn build_optimizers(
-    shard_path: &Path,
-    collection_params: &CollectionParams,
-    optimizers_config: &OptimizersConfig,
-    hnsw_config: &HnswConfig,
-    quantization_config: &Option<QuantizationConfig>,
-) -> Arc<Vec<Arc<Optimizer>>> {
-    let segments_path = shard_path.join(SEGMENTS_PATH);
-    let temp_segments_path = shard_path.join(TEMP_SEGMENTS_PATH);
-
+fn optimizer_thresholds(optimizers_config: &OptimizersConfig) -> OptimizerThresholds {
     let indexing_threshold = match optimizers_config.indexing_threshold {
         None => DEFAULT_INDEXING_THRESHOLD_KB, // default value
         Some(0) => usize::MAX,                 // disable vector index
@@ -146,11 +138,23 @@ pub fn build_optimizers(
         Some(custom) => custom,
     };
 
-    let threshold_config = OptimizerThresholds {
+    OptimizerThresholds {
         memmap_threshold,
         indexing_threshold,
         max_segment_size: optimizers_config.get_max_segment_size(),
-    };
+    }
+}
+
+pub fn build_optimizers(
+    shard_path: &Path,
+    collection_params: &CollectionParams,
+    optimizers_config: &OptimizersConfig,
+    hnsw_config: &HnswConfig,
+    quantization_config: &Option<QuantizationConfig>,
+) -> Arc<Vec<Arc<Optimizer>>> {
+    let segments_path = shard_path.join(SEGMENTS_PATH);
+    let temp_segments_path = shard_path.join(TEMP_SEGMENTS_PATH);
+    let threshold_config = optimizer_thresholds(optimizers_config);
 
     Arc::new(vec![
         Arc::new(MergeOptimizer::new(
@@ -190,3 +194,46 @@ pub fn build_optimizers(
         )),
     ])
 }
+
+/// Build an optimizer for the optimization explicitly requested by the user
+///
+/// Configured optimization triggers are ignored, e.g. a forced vacuum cleans up every segment
+/// with deleted points.
+/// Returns `None` if the operation does not require an optimizer.
+pub fn build_forced_optimizer(
+    shard_path: &Path,
+    collection_params: &CollectionParams,
+    optimizers_config: &OptimizersConfig,
+    hnsw_config: &HnswConfig,
+    quantization_config: &Option<QuantizationConfig>,
+    operation: &OptimizationOperations,
+) -> Option<Arc<Optimizer>> {
+    let segments_path = shard_path.join(SEGMENTS_PATH);
+    let temp_segments_path = shard_path.join(TEMP_SEGMENTS_PATH);
+    let threshold_config = optimizer_thresholds(optimizers_config);
+
+    match operation {
+        OptimizationOperations::Pause(_) | OptimizationOperations::Resume(_) => None,
+        OptimizationOperations::Merge(MergeSegmentsOperation { merge }) => {
+            Some(Arc::new(MergeOptimizer::new(
+                merge.max_segments,
+                threshold_config,
+                segments_path,
+                temp_segments_path,
+                collection_params.clone(),
+                hnsw_config.clone(),
+                quantization_config.clone(),
+            )))
+        }
+        OptimizationOperations::Vacuum(_) => Some(Arc::new(VacuumOptimizer::new(
+            0.0,
+            0,
+            threshold_config,
+            segments_path,
+            temp_segments_path,
+            collection_params.clone(),
+            hnsw_config.clone(),
+            quantization_config.clone(),
+        ))),
+    }
+}
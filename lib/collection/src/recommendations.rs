@@ -18,7 +18,7 @@ use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::types::{
     CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch, PointRequest,
     QueryEnum, RecommendExample, RecommendRequest, RecommendRequestBatch, RecommendStrategy,
-    Record, SearchRequest, SearchRequestBatch, UsingVector,
+    Record, SearchProfile, SearchRequest, SearchRequestBatch, UsingVector,
 };
 
 fn avg_vectors<'a>(
@@ -71,6 +71,34 @@ where
     Ok(results.into_iter().next().unwrap())
 }
 
+/// Same as [`recommend_by`], but also returns details of how the search was performed
+pub async fn recommend_by_with_profile<'a, F, Fut>(
+    request: RecommendRequest,
+    collection: &Collection,
+    collection_by_name: F,
+    read_consistency: Option<ReadConsistency>,
+) -> CollectionResult<(Vec<ScoredPoint>, SearchProfile)>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
+{
+    if request.limit == 0 {
+        return Ok((vec![], SearchProfile::default()));
+    }
+    let request_batch = RecommendRequestBatch {
+        searches: vec![request],
+    };
+    let (results, profile) = do_recommend_batch_by(
+        request_batch,
+        collection,
+        collection_by_name,
+        read_consistency,
+        true,
+    )
+    .await?;
+    Ok((results.into_iter().next().unwrap(), profile))
+}
+
 async fn retrieve_points(
     collection: &Collection,
     ids: Vec<PointIdType>,
@@ -150,9 +178,34 @@ where
     F: Fn(String) -> Fut,
     Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
 {
+    let (results, _) = do_recommend_batch_by(
+        request_batch,
+        collection,
+        collection_by_name,
+        read_consistency,
+        false,
+    )
+    .await?;
+    Ok(results)
+}
+
+/// Implementation of [`recommend_batch_by`], if `profile` is set all searches are executed
+/// in a single step and details of how they were performed are collected
+async fn do_recommend_batch_by<'a, F, Fut>(
+    request_batch: RecommendRequestBatch,
+    collection: &Collection,
+    collection_by_name: F,
+    read_consistency: Option<ReadConsistency>,
+    profile: bool,
+) -> CollectionResult<(Vec<Vec<ScoredPoint>>, SearchProfile)>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
+{
+    let mut search_profile = SearchProfile::default();
     // shortcuts batch if all requests with limit=0
     if request_batch.searches.iter().all(|s| s.limit == 0) {
-        return Ok(vec![]);
+        return Ok((vec![], search_profile));
     }
     // pack all reference vector ids
     let mut all_reference_vectors_ids: HashMap<_, HashSet<PointIdType>> = Default::default();
@@ -333,7 +386,24 @@ where
             };
         }
 
-        let run_result = if !searches.is_empty() {
+        let run_result = if profile {
+            let core_searches = if !searches.is_empty() {
+                searches.into_iter().map_into().collect()
+            } else {
+                core_searches
+            };
+            let (run_result, run_profile) = collection
+                .core_search_batch_with_profile(
+                    CoreSearchRequestBatch {
+                        searches: core_searches,
+                    },
+                    read_consistency,
+                    None,
+                )
+                .await?;
+            search_profile.merge(run_profile);
+            run_result
+        } else if !searches.is_empty() {
             let search_batch_request = SearchRequestBatch { searches };
             collection
                 .search_batch(search_batch_request, read_consistency, None)
@@ -351,7 +421,7 @@ where
         run_result.into_iter().for_each(|x| results.push(x));
     }
 
-    Ok(results)
+    Ok((results, search_profile))
 }
 
 /// Groups the consecutive requests of the same strategy into separate batches
@@ -417,6 +487,7 @@ fn recommend_by_avg_vector<'a>(
         .into(),
         filter: Some(Filter {
             should: None,
+            min_should: None,
             must: filter.clone().map(|filter| vec![Condition::Filter(filter)]),
             must_not: Some(vec![Condition::HasId(HasIdCondition {
                 has_id: reference_vectors_ids.iter().cloned().collect(),
@@ -428,6 +499,8 @@ fn recommend_by_avg_vector<'a>(
         limit,
         score_threshold,
         offset,
+        profile: None,
+        formula: None,
     }
 }
 
@@ -451,6 +524,7 @@ fn recommend_by_best_score<'a>(
         query,
         filter: Some(Filter {
             should: None,
+            min_should: None,
             must: request
                 .filter
                 .clone()
@@ -465,6 +539,7 @@ fn recommend_by_best_score<'a>(
         with_payload: request.with_payload,
         with_vector: request.with_vector,
         score_threshold: request.score_threshold,
+        formula: None,
     }
 }
 
@@ -0,0 +1,411 @@
+//! Comparison of shard replicas, which might have diverged after network partitions.
+//!
+//! Points of the shard are split into buckets by the hash of the point ID.
+//! Every replica computes a two-level Merkle tree: hash of each bucket and the root hash over all buckets.
+//! Replicas are compared by the root first, and only mismatched buckets are compared point by point.
+
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use futures::future::try_join_all;
+use seahash::SeaHasher;
+use segment::data_types::named_vectors::NamedVectors;
+use segment::types::{Payload, PointIdType, SeqNumberType, WithPayload, WithVector};
+
+use crate::operations::consistency_ops::{ReplicaDigestInfo, ShardConsistencyReport};
+use crate::operations::point_ops::{PointInsertOperations, PointOperations, PointStruct};
+use crate::operations::types::{CollectionError, CollectionResult, PointRequest};
+use crate::operations::CollectionUpdateOperations;
+use crate::shards::local_shard::LocalShard;
+use crate::shards::remote_shard::RemoteShard;
+use crate::shards::shard::{PeerId, Shard, ShardId};
+use crate::shards::shard_trait::ShardOperationSS;
+
+/// Number of buckets the points of the shard are split into
+pub const DIGEST_BUCKETS: u32 = 256;
+
+/// Max number of points copied to a replica in a single update
+const REPAIR_BATCH_SIZE: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PointDigest {
+    pub id: PointIdType,
+    /// Version of the point on the replica, used to pick the latest copy among the segments.
+    /// Assigned by each replica independently, so it can't be compared between replicas.
+    pub version: SeqNumberType,
+    /// Hash of the point ID, vectors and payload
+    pub hash: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ShardDigest {
+    pub root: u64,
+    pub bucket_hashes: Vec<u64>,
+    pub points_count: usize,
+}
+
+impl ShardDigest {
+    pub fn new(points: &[PointDigest], buckets: u32) -> Self {
+        let mut bucket_hashes = vec![0; buckets as usize];
+        for point in points {
+            let bucket = point_bucket(point.id, buckets) as usize;
+            // Order of the points differs between replicas, so hashes are combined with a commutative operation.
+            // Versions are assigned by each replica independently, so only the content is compared.
+            bucket_hashes[bucket] = bucket_hashes[bucket].wrapping_add(point.hash);
+        }
+
+        let mut hasher = SeaHasher::new();
+        bucket_hashes.hash(&mut hasher);
+
+        Self {
+            root: hasher.finish(),
+            bucket_hashes,
+            points_count: points.len(),
+        }
+    }
+
+    /// Buckets, which hashes differ from the other digest
+    pub fn mismatched_buckets(&self, other: &ShardDigest) -> Vec<u32> {
+        if self.root == other.root {
+            return vec![];
+        }
+
+        let buckets = self.bucket_hashes.len().max(other.bucket_hashes.len());
+        (0..buckets)
+            .filter(|bucket| self.bucket_hashes.get(*bucket) != other.bucket_hashes.get(*bucket))
+            .map(|bucket| bucket as u32)
+            .collect()
+    }
+}
+
+pub fn point_bucket(point_id: PointIdType, buckets: u32) -> u32 {
+    let mut hasher = SeaHasher::new();
+    point_id.hash(&mut hasher);
+    (hasher.finish() % u64::from(buckets)) as u32
+}
+
+pub fn point_content_hash(point_id: PointIdType, vectors: &NamedVectors, payload: &Payload) -> u64 {
+    let mut hasher = SeaHasher::new();
+    point_id.hash(&mut hasher);
+
+    let mut vectors: Vec<_> = vectors.iter().collect();
+    vectors.sort_unstable_by_key(|(name, _)| *name);
+    for (name, vector) in vectors {
+        name.hash(&mut hasher);
+        for value in vector {
+            hasher.write_u32(value.to_bits());
+        }
+    }
+
+    // Keys of the payload objects are sorted, so the serialization is deterministic
+    if let Ok(payload) = serde_json::to_vec(payload) {
+        hasher.write(&payload);
+    }
+
+    hasher.finish()
+}
+
+impl LocalShard {
+    /// Digests of all points of the shard, or of the points in a single `bucket`.
+    ///
+    /// If a point is present in several segments, only its latest version is taken.
+    pub async fn point_digests(
+        &self,
+        buckets: u32,
+        bucket: Option<u32>,
+    ) -> CollectionResult<Vec<PointDigest>> {
+        let segments = self.segments.clone();
+        tokio::task::spawn_blocking(move || -> CollectionResult<Vec<PointDigest>> {
+            let segments = segments.read();
+            let mut digests: HashMap<PointIdType, PointDigest> = HashMap::new();
+            for (_, segment) in segments.iter() {
+                let segment = segment.get();
+                let segment = segment.read();
+                for point_id in segment.iter_points() {
+                    if bucket.map_or(false, |bucket| point_bucket(point_id, buckets) != bucket) {
+                        continue;
+                    }
+                    let Some(version) = segment.point_version(point_id) else {
+                        continue;
+                    };
+                    if digests
+                        .get(&point_id)
+                        .map_or(false, |known| known.version >= version)
+                    {
+                        continue;
+                    }
+                    let hash = point_content_hash(
+                        point_id,
+                        &segment.all_vectors(point_id)?,
+                        &segment.payload(point_id)?,
+                    );
+                    digests.insert(
+                        point_id,
+                        PointDigest {
+                            id: point_id,
+                            version,
+                            hash,
+                        },
+                    );
+                }
+            }
+            Ok(digests.into_values().collect())
+        })
+        .await?
+    }
+
+    pub async fn digest(&self, buckets: u32) -> CollectionResult<ShardDigest> {
+        let points = self.point_digests(buckets, None).await?;
+        Ok(ShardDigest::new(&points, buckets))
+    }
+}
+
+/// Replica of the shard to compare
+pub(crate) enum ReplicaRef<'a> {
+    Local(&'a Shard),
+    Remote(&'a RemoteShard),
+}
+
+impl<'a> ReplicaRef<'a> {
+    fn operations(&self) -> &ShardOperationSS {
+        match self {
+            ReplicaRef::Local(shard) => shard.get(),
+            ReplicaRef::Remote(remote) => *remote,
+        }
+    }
+
+    fn local_shard(shard: &Shard) -> CollectionResult<&LocalShard> {
+        shard.local_shard().ok_or_else(|| {
+            CollectionError::service_error(format!(
+                "Can't compute digest of the {}",
+                shard.variant_name()
+            ))
+        })
+    }
+
+    async fn digest(&self, buckets: u32) -> CollectionResult<ShardDigest> {
+        match self {
+            ReplicaRef::Local(shard) => Self::local_shard(shard)?.digest(buckets).await,
+            ReplicaRef::Remote(remote) => remote.digest(buckets).await,
+        }
+    }
+
+    async fn point_digests(&self, buckets: u32, bucket: u32) -> CollectionResult<Vec<PointDigest>> {
+        match self {
+            ReplicaRef::Local(shard) => {
+                Self::local_shard(shard)?
+                    .point_digests(buckets, Some(bucket))
+                    .await
+            }
+            ReplicaRef::Remote(remote) => remote.point_digests(buckets, bucket).await,
+        }
+    }
+}
+
+/// Compare digests of the replicas and, if `repair` is set, copy the content of every mismatched point,
+/// on which the majority of the replicas agree, to the other replicas.
+///
+/// Point versions are assigned by each replica independently, so they can't tell which content is newer.
+/// Points without a majority are only reported as conflicting.
+/// Deletions are not tracked, so points missing on some replicas are only reported, never restored.
+pub(crate) async fn check_replicas(
+    shard_id: ShardId,
+    replicas: &[(PeerId, ReplicaRef<'_>)],
+    repair: bool,
+) -> CollectionResult<ShardConsistencyReport> {
+    let buckets = DIGEST_BUCKETS;
+    let digests = try_join_all(replicas.iter().map(|(_, replica)| replica.digest(buckets))).await?;
+
+    let mut report = ShardConsistencyReport {
+        shard_id,
+        replicas: replicas
+            .iter()
+            .zip(&digests)
+            .map(|((peer_id, _), digest)| ReplicaDigestInfo {
+                peer_id: *peer_id,
+                points_count: digest.points_count,
+                digest: format!("{:016x}", digest.root),
+            })
+            .collect(),
+        ..Default::default()
+    };
+
+    let Some((first, others)) = digests.split_first() else {
+        report.consistent = true;
+        return Ok(report);
+    };
+
+    let mut mismatched_buckets: Vec<_> = others
+        .iter()
+        .flat_map(|other| first.mismatched_buckets(other))
+        .collect();
+    mismatched_buckets.sort_unstable();
+    mismatched_buckets.dedup();
+
+    // (source replica, target replica) -> points to copy
+    let mut repairs: HashMap<(usize, usize), Vec<PointIdType>> = HashMap::new();
+    for bucket in &mismatched_buckets {
+        let replica_points = try_join_all(
+            replicas
+                .iter()
+                .map(|(_, replica)| replica.point_digests(buckets, *bucket)),
+        )
+        .await?;
+
+        let mut points: HashMap<PointIdType, Vec<Option<PointDigest>>> = HashMap::new();
+        for (replica_idx, digests) in replica_points.into_iter().enumerate() {
+            for digest in digests {
+                points
+                    .entry(digest.id)
+                    .or_insert_with(|| vec![None; replicas.len()])[replica_idx] = Some(digest);
+            }
+        }
+
+        for (point_id, digests) in points {
+            // Point might have been deleted on the replicas, which miss it
+            let Some(digests) = digests.into_iter().collect::<Option<Vec<_>>>() else {
+                report.inconsistent_points += 1;
+                report.missing_points += 1;
+                continue;
+            };
+
+            let mut hash_counts: HashMap<u64, usize> = HashMap::new();
+            for digest in &digests {
+                *hash_counts.entry(digest.hash).or_default() += 1;
+            }
+            if hash_counts.len() <= 1 {
+                continue;
+            }
+            report.inconsistent_points += 1;
+
+            let majority_hash = hash_counts
+                .into_iter()
+                .find(|(_, count)| count * 2 > replicas.len())
+                .map(|(hash, _)| hash);
+            let Some(majority_hash) = majority_hash else {
+                report.conflicting_points += 1;
+                continue;
+            };
+
+            let Some(source) = digests
+                .iter()
+                .position(|digest| digest.hash == majority_hash)
+            else {
+                continue;
+            };
+            for (target, digest) in digests.iter().enumerate() {
+                if digest.hash != majority_hash {
+                    repairs.entry((source, target)).or_default().push(point_id);
+                }
+            }
+        }
+    }
+
+    report.consistent = mismatched_buckets.is_empty();
+    report.mismatched_buckets = mismatched_buckets;
+
+    if !repair {
+        return Ok(report);
+    }
+
+    for ((source, target), point_ids) in repairs {
+        let source = replicas[source].1.operations();
+        let target = replicas[target].1.operations();
+        for ids in point_ids.chunks(REPAIR_BATCH_SIZE) {
+            let records = source
+                .retrieve(
+                    Arc::new(PointRequest {
+                        ids: ids.to_vec(),
+                        with_payload: None,
+                        with_vector: WithVector::Bool(true),
+                    }),
+                    &WithPayload {
+                        enable: true,
+                        payload_selector: None,
+                    },
+                    &WithVector::Bool(true),
+                )
+                .await?;
+
+            let points: Vec<PointStruct> = records
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, String>>()?;
+            let repaired = points.len();
+
+            let operation = CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPoints(PointInsertOperations::PointsList(points)),
+            );
+            target.update(operation, true).await?;
+            report.repaired_points += repaired;
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digest(id: u64, hash: u64) -> PointDigest {
+        PointDigest {
+            id: id.into(),
+            version: 0,
+            hash,
+        }
+    }
+
+    #[test]
+    fn test_shard_digest() {
+        let points: Vec<_> = (0..1000).map(|id| digest(id, id * 7)).collect();
+
+        // Order of the points doesn't matter
+        let mut reversed = points.clone();
+        reversed.reverse();
+        let a = ShardDigest::new(&points, DIGEST_BUCKETS);
+        let b = ShardDigest::new(&reversed, DIGEST_BUCKETS);
+        assert_eq!(a, b);
+        assert!(a.mismatched_buckets(&b).is_empty());
+
+        // Changed and missing points are found in their buckets
+        let mut diverged = points.clone();
+        diverged[10].hash += 1;
+        diverged.remove(500);
+        let c = ShardDigest::new(&diverged, DIGEST_BUCKETS);
+        assert_ne!(a.root, c.root);
+        assert_eq!(c.points_count, 999);
+
+        let mut expected = vec![
+            point_bucket(10.into(), DIGEST_BUCKETS),
+            point_bucket(500.into(), DIGEST_BUCKETS),
+        ];
+        expected.sort_unstable();
+        expected.dedup();
+        assert_eq!(a.mismatched_buckets(&c), expected);
+    }
+
+    #[test]
+    fn test_point_content_hash() {
+        let payload: Payload = serde_json::json!({"a": 1, "b": "x"}).into();
+        let vectors = NamedVectors::from([
+            ("image".to_string(), vec![1.0, 2.0]),
+            ("text".to_string(), vec![3.0]),
+        ]);
+
+        let hash = point_content_hash(1.into(), &vectors, &payload);
+        // Deterministic
+        assert_eq!(hash, point_content_hash(1.into(), &vectors, &payload));
+
+        let other_vectors = NamedVectors::from([
+            ("image".to_string(), vec![1.0, 2.5]),
+            ("text".to_string(), vec![3.0]),
+        ]);
+        assert_ne!(hash, point_content_hash(1.into(), &other_vectors, &payload));
+        assert_ne!(hash, point_content_hash(2.into(), &vectors, &payload));
+
+        let other_payload: Payload = serde_json::json!({"a": 2, "b": "x"}).into();
+        assert_ne!(hash, point_content_hash(1.into(), &vectors, &other_payload));
+    }
+}
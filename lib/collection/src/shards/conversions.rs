@@ -1,23 +1,37 @@
-use api::grpc::conversions::payload_to_proto;
+use std::time::Duration;
+
+use api::grpc::conversions::{json_to_proto, payload_to_proto};
 use api::grpc::qdrant::points_selector::PointsSelectorOneOf;
 use api::grpc::qdrant::{
     ClearPayloadPoints, ClearPayloadPointsInternal, CreateFieldIndexCollection,
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollection,
     DeleteFieldIndexCollectionInternal, DeletePayloadPoints, DeletePayloadPointsInternal,
-    DeletePointVectors, DeletePoints, DeletePointsInternal, DeleteVectorsInternal, PointVectors,
-    PointsIdsList, PointsSelector, SetPayloadPoints, SetPayloadPointsInternal, SyncPoints,
-    SyncPointsInternal, UpdatePointVectors, UpdateVectorsInternal, UpsertPoints,
-    UpsertPointsInternal, VectorsSelector,
+    DeletePointVectors, DeletePoints, DeletePointsInternal, DeleteVectorsInternal,
+    IncrementPayloadPoints, IncrementPayloadPointsInternal, MeasureRecallResponse, NamedVectors,
+    PayloadArrayOperation, PointVectors, PointsIdsList, PointsSelector,
+    RecallQuantizationMode as RecallQuantizationModePb, RecallSearchMeasurement,
+    SegmentType as SegmentTypePb, SetPayloadPoints, SetPayloadPointsInternal, SyncPoints,
+    SyncPointsInternal, UpdatePayloadArrayPoints, UpdatePayloadArrayPointsInternal,
+    UpdatePointVectors, UpdateVectorsInternal, UpsertPoints, UpsertPointsInternal, Vector,
+    VectorsSelector,
+};
+use segment::types::{
+    Filter, PayloadFieldSchema, PayloadSchemaParams, PointIdType, ScoredPoint, SegmentType,
 };
-use segment::types::{Filter, PayloadFieldSchema, PayloadSchemaParams, PointIdType, ScoredPoint};
 use tonic::Status;
 
 use crate::operations::conversions::write_ordering_to_proto;
-use crate::operations::payload_ops::{DeletePayload, SetPayload};
+use crate::operations::payload_ops::{
+    DeletePayload, IncrementPayload, SetPayload, UpdatePayloadArray,
+};
 use crate::operations::point_ops::{PointInsertOperations, PointSyncOperation, WriteOrdering};
+use crate::operations::recall_ops::{
+    RecallEstimate, RecallQuantizationMode, SegmentRecallEstimation,
+};
 use crate::operations::types::CollectionResult;
 use crate::operations::vector_ops::UpdateVectors;
 use crate::operations::CreateIndex;
+use crate::shards::recall::{RecallQuery, SearchMeasurement, ShardRecallMeasurement};
 use crate::shards::shard::ShardId;
 
 pub fn internal_sync_points(
@@ -212,6 +226,7 @@ pub fn internal_set_payload(
             payload: payload_to_proto(set_payload.payload),
             points_selector,
             ordering: ordering.map(write_ordering_to_proto),
+            key: set_payload.key,
         }),
     }
 }
@@ -247,6 +262,79 @@ pub fn internal_delete_payload(
     }
 }
 
+pub fn internal_increment_payload(
+    shard_id: Option<ShardId>,
+    collection_name: String,
+    increment_payload: IncrementPayload,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+) -> IncrementPayloadPointsInternal {
+    let points_selector = if let Some(points) = increment_payload.points {
+        Some(PointsSelector {
+            points_selector_one_of: Some(PointsSelectorOneOf::Points(PointsIdsList {
+                ids: points.into_iter().map(|id| id.into()).collect(),
+            })),
+        })
+    } else {
+        increment_payload.filter.map(|filter| PointsSelector {
+            points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
+        })
+    };
+
+    IncrementPayloadPointsInternal {
+        shard_id,
+        increment_payload_points: Some(IncrementPayloadPoints {
+            collection_name,
+            wait: Some(wait),
+            key: increment_payload.key,
+            value: Some(json_to_proto(increment_payload.value.into())),
+            initial: increment_payload
+                .initial
+                .map(|initial| json_to_proto(initial.into())),
+            points_selector,
+            ordering: ordering.map(write_ordering_to_proto),
+        }),
+    }
+}
+
+pub fn internal_update_payload_array(
+    shard_id: Option<ShardId>,
+    collection_name: String,
+    update_payload_array: UpdatePayloadArray,
+    operation: PayloadArrayOperation,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+) -> UpdatePayloadArrayPointsInternal {
+    let points_selector = if let Some(points) = update_payload_array.points {
+        Some(PointsSelector {
+            points_selector_one_of: Some(PointsSelectorOneOf::Points(PointsIdsList {
+                ids: points.into_iter().map(|id| id.into()).collect(),
+            })),
+        })
+    } else {
+        update_payload_array.filter.map(|filter| PointsSelector {
+            points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
+        })
+    };
+
+    UpdatePayloadArrayPointsInternal {
+        shard_id,
+        update_payload_array_points: Some(UpdatePayloadArrayPoints {
+            collection_name,
+            wait: Some(wait),
+            key: update_payload_array.key,
+            values: update_payload_array
+                .values
+                .into_iter()
+                .map(json_to_proto)
+                .collect(),
+            operation: operation as i32,
+            points_selector,
+            ordering: ordering.map(write_ordering_to_proto),
+        }),
+    }
+}
+
 pub fn internal_clear_payload(
     shard_id: Option<ShardId>,
     collection_name: String,
@@ -392,3 +480,220 @@ pub fn try_scored_point_from_grpc(
         vector,
     })
 }
+
+impl From<RecallQuery> for api::grpc::qdrant::RecallQuery {
+    fn from(value: RecallQuery) -> Self {
+        let RecallQuery { id, vectors } = value;
+        Self {
+            id: Some(id.into()),
+            vectors: Some(NamedVectors {
+                vectors: vectors
+                    .into_iter()
+                    .map(|(name, data)| (name, Vector { data }))
+                    .collect(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::RecallQuery> for RecallQuery {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::RecallQuery) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::RecallQuery { id, vectors } = value;
+        Ok(Self {
+            id: id
+                .ok_or_else(|| Status::invalid_argument("recall query does not have an ID"))?
+                .try_into()?,
+            vectors: vectors
+                .map(|vectors| vectors.vectors)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(name, vector)| (name, vector.data))
+                .collect(),
+        })
+    }
+}
+
+impl From<RecallEstimate> for api::grpc::qdrant::RecallEstimate {
+    fn from(value: RecallEstimate) -> Self {
+        let RecallEstimate {
+            vector,
+            quantization,
+            queries,
+            recall,
+            latency,
+            exact_latency,
+        } = value;
+        let quantization = match quantization {
+            RecallQuantizationMode::Disabled => RecallQuantizationModePb::Disabled,
+            RecallQuantizationMode::Quantized => RecallQuantizationModePb::Quantized,
+            RecallQuantizationMode::Rescored => RecallQuantizationModePb::Rescored,
+        };
+        Self {
+            vector,
+            quantization: quantization as i32,
+            queries: queries as u64,
+            recall,
+            latency,
+            exact_latency,
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::RecallEstimate> for RecallEstimate {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::RecallEstimate) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::RecallEstimate {
+            vector,
+            quantization,
+            queries,
+            recall,
+            latency,
+            exact_latency,
+        } = value;
+        let quantization = match RecallQuantizationModePb::from_i32(quantization) {
+            Some(RecallQuantizationModePb::Disabled) => RecallQuantizationMode::Disabled,
+            Some(RecallQuantizationModePb::Quantized) => RecallQuantizationMode::Quantized,
+            Some(RecallQuantizationModePb::Rescored) => RecallQuantizationMode::Rescored,
+            None => {
+                return Err(Status::invalid_argument(format!(
+                    "unknown recall quantization mode {quantization}"
+                )))
+            }
+        };
+        Ok(Self {
+            vector,
+            quantization,
+            queries: queries as usize,
+            recall,
+            latency,
+            exact_latency,
+        })
+    }
+}
+
+impl From<SegmentRecallEstimation> for api::grpc::qdrant::SegmentRecallEstimation {
+    fn from(value: SegmentRecallEstimation) -> Self {
+        let SegmentRecallEstimation {
+            segment_id,
+            segment_type,
+            points_count,
+            estimates,
+        } = value;
+        let segment_type = match segment_type {
+            SegmentType::Plain => SegmentTypePb::Plain,
+            SegmentType::Indexed => SegmentTypePb::Indexed,
+            SegmentType::Special => SegmentTypePb::Special,
+        };
+        Self {
+            segment_id: segment_id as u64,
+            segment_type: segment_type as i32,
+            points_count: points_count as u64,
+            estimates: estimates.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::SegmentRecallEstimation> for SegmentRecallEstimation {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::SegmentRecallEstimation) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::SegmentRecallEstimation {
+            segment_id,
+            segment_type,
+            points_count,
+            estimates,
+        } = value;
+        let segment_type = match SegmentTypePb::from_i32(segment_type) {
+            Some(SegmentTypePb::Plain) => SegmentType::Plain,
+            Some(SegmentTypePb::Indexed) => SegmentType::Indexed,
+            Some(SegmentTypePb::Special) => SegmentType::Special,
+            None => {
+                return Err(Status::invalid_argument(format!(
+                    "unknown segment type {segment_type}"
+                )))
+            }
+        };
+        Ok(Self {
+            segment_id: segment_id as usize,
+            segment_type,
+            points_count: points_count as usize,
+            estimates: estimates
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<SearchMeasurement> for RecallSearchMeasurement {
+    fn from(value: SearchMeasurement) -> Self {
+        let SearchMeasurement {
+            exact,
+            approximate,
+            exact_time,
+            approximate_time,
+        } = value;
+        Self {
+            exact: exact.into_iter().map(Into::into).collect(),
+            approximate: approximate.into_iter().map(Into::into).collect(),
+            exact_time: exact_time.as_secs_f64(),
+            approximate_time: approximate_time.as_secs_f64(),
+        }
+    }
+}
+
+impl TryFrom<RecallSearchMeasurement> for SearchMeasurement {
+    type Error = Status;
+
+    fn try_from(value: RecallSearchMeasurement) -> Result<Self, Self::Error> {
+        let RecallSearchMeasurement {
+            exact,
+            approximate,
+            exact_time,
+            approximate_time,
+        } = value;
+        let try_points_from_grpc = |points: Vec<api::grpc::qdrant::ScoredPoint>| {
+            points
+                .into_iter()
+                .map(|point| try_scored_point_from_grpc(point, false))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(Self {
+            exact: try_points_from_grpc(exact)?,
+            approximate: try_points_from_grpc(approximate)?,
+            exact_time: Duration::try_from_secs_f64(exact_time).unwrap_or_default(),
+            approximate_time: Duration::try_from_secs_f64(approximate_time).unwrap_or_default(),
+        })
+    }
+}
+
+impl TryFrom<MeasureRecallResponse> for ShardRecallMeasurement {
+    type Error = Status;
+
+    fn try_from(value: MeasureRecallResponse) -> Result<Self, Self::Error> {
+        let MeasureRecallResponse {
+            segments,
+            measurements,
+            time: _,
+        } = value;
+        Ok(Self {
+            segments: segments
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            measurements: measurements
+                .into_iter()
+                .map(|target| {
+                    target
+                        .queries
+                        .into_iter()
+                        .map(TryInto::try_into)
+                        .collect::<Result<_, _>>()
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
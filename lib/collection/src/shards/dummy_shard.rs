@@ -2,6 +2,7 @@ use std::path::Path;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use segment::data_types::search_profile::SegmentSearchProfile;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -13,6 +14,7 @@ use crate::operations::types::{
 };
 use crate::operations::CollectionUpdateOperations;
 use crate::shards::shard_trait::ShardOperation;
+use crate::shards::snapshot_manifest::ShardSnapshotManifest;
 use crate::shards::telemetry::LocalShardTelemetry;
 
 #[derive(Clone, Debug)]
@@ -32,6 +34,7 @@ impl DummyShard {
         _temp_path: &Path,
         _target_path: &Path,
         _save_wal: bool,
+        _base: Option<&ShardSnapshotManifest>,
     ) -> CollectionResult<()> {
         self.dummy()
     }
@@ -100,6 +103,14 @@ impl ShardOperation for DummyShard {
         self.dummy()
     }
 
+    async fn core_search_with_profile(
+        &self,
+        _: Arc<CoreSearchRequestBatch>,
+        _: &Handle,
+    ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<SegmentSearchProfile>)> {
+        self.dummy()
+    }
+
     async fn count(&self, _: Arc<CountRequest>) -> CollectionResult<CountResult> {
         self.dummy()
     }
@@ -2,6 +2,7 @@ use std::path::Path;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use segment::data_types::search_profile::SegmentSearchProfile;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
     WithVector,
@@ -10,15 +11,20 @@ use tokio::runtime::Handle;
 use tokio::sync::Mutex;
 
 use super::update_tracker::UpdateTracker;
+use crate::hash_ring::HashRing;
 use crate::operations::point_ops::{PointOperations, PointStruct, PointSyncOperation};
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch, CountRequest,
     CountResult, PointRequest, Record, SearchRequestBatch, UpdateResult,
 };
-use crate::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
+use crate::operations::{
+    CollectionUpdateOperations, CreateIndex, FieldIndexOperations, OperationToShard, SplitByShard,
+};
 use crate::shards::local_shard::LocalShard;
 use crate::shards::remote_shard::RemoteShard;
+use crate::shards::shard::ShardId;
 use crate::shards::shard_trait::ShardOperation;
+use crate::shards::snapshot_manifest::ShardSnapshotManifest;
 use crate::shards::telemetry::LocalShardTelemetry;
 
 /// ForwardProxyShard
@@ -27,9 +33,14 @@ use crate::shards::telemetry::LocalShardTelemetry;
 ///
 /// It can be used to provide all read and write operations while the wrapped shard is being transferred to another node.
 /// Proxy forwards all operations to remote shards.
+///
+/// During resharding, only points which belong to the remote shard according to the
+/// resharding hash ring are forwarded.
 pub struct ForwardProxyShard {
     pub(crate) wrapped_shard: LocalShard,
     pub(crate) remote_shard: RemoteShard,
+    /// Hash ring of the collection being resharded, if any.
+    resharding_ring: Option<HashRing<ShardId>>,
     /// Lock required to protect transfer-in-progress updates.
     /// It should block data updating operations while the batch is being transferred.
     update_lock: Mutex<()>,
@@ -40,10 +51,59 @@ impl ForwardProxyShard {
         Self {
             wrapped_shard,
             remote_shard,
+            resharding_ring: None,
+            update_lock: Mutex::new(()),
+        }
+    }
+
+    /// Create a proxy, which forwards only points that belong to the remote shard
+    /// according to the given hash ring.
+    pub fn new_for_resharding(
+        wrapped_shard: LocalShard,
+        remote_shard: RemoteShard,
+        resharding_ring: HashRing<ShardId>,
+    ) -> Self {
+        Self {
+            wrapped_shard,
+            remote_shard,
+            resharding_ring: Some(resharding_ring),
             update_lock: Mutex::new(()),
         }
     }
 
+    /// Check if the point should be forwarded to the remote shard
+    fn is_forwarded(&self, point_id: PointIdType) -> bool {
+        match &self.resharding_ring {
+            None => true,
+            Some(ring) => ring.get(&point_id) == Some(&self.remote_shard.id),
+        }
+    }
+
+    /// Select the part of the operation, which should be forwarded to the remote shard.
+    fn forwarded_operation(
+        &self,
+        operation: CollectionUpdateOperations,
+    ) -> Option<CollectionUpdateOperations> {
+        let Some(ring) = &self.resharding_ring else {
+            return Some(operation);
+        };
+
+        // Sync operations are specific to the wrapped shard
+        if let CollectionUpdateOperations::PointOperation(PointOperations::SyncPoints(_)) =
+            &operation
+        {
+            return None;
+        }
+
+        match operation.split_by_shard(ring) {
+            OperationToShard::ToAll(operation) => Some(operation),
+            OperationToShard::ByShard(by_shard) => by_shard
+                .into_iter()
+                .find(|(shard_id, _)| *shard_id == self.remote_shard.id)
+                .map(|(_, operation)| operation),
+        }
+    }
+
     /// Create payload indexes in the remote shard same as in the wrapped shard.
     pub async fn transfer_indexes(&self) -> CollectionResult<()> {
         let _update_lock = self.update_lock.lock().await;
@@ -93,8 +153,11 @@ impl ForwardProxyShard {
             Some(batch.pop().unwrap().id)
         };
 
-        let points: Result<Vec<PointStruct>, String> =
-            batch.into_iter().map(|point| point.try_into()).collect();
+        let points: Result<Vec<PointStruct>, String> = batch
+            .into_iter()
+            .filter(|point| self.is_forwarded(point.id))
+            .map(|point| point.try_into())
+            .collect();
 
         let points = points?;
 
@@ -128,9 +191,10 @@ impl ForwardProxyShard {
         temp_path: &Path,
         target_path: &Path,
         save_wal: bool,
+        base: Option<&ShardSnapshotManifest>,
     ) -> CollectionResult<()> {
         self.wrapped_shard
-            .create_snapshot(temp_path, target_path, save_wal)
+            .create_snapshot(temp_path, target_path, save_wal, base)
             .await
     }
 
@@ -159,7 +223,11 @@ impl ShardOperation for ForwardProxyShard {
         let local_shard = &self.wrapped_shard;
         // Shard update is within a write lock scope, because we need a way to block the shard updates
         // during the transfer restart and finalization.
-        local_shard.update(operation.clone(), wait).await?;
+        let result = local_shard.update(operation.clone(), wait).await?;
+
+        let Some(operation) = self.forwarded_operation(operation) else {
+            return Ok(result);
+        };
 
         self.remote_shard
             .update(operation, false)
@@ -219,6 +287,17 @@ impl ShardOperation for ForwardProxyShard {
             .await
     }
 
+    async fn core_search_with_profile(
+        &self,
+        request: Arc<CoreSearchRequestBatch>,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<SegmentSearchProfile>)> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .core_search_with_profile(request, search_runtime_handle)
+            .await
+    }
+
     async fn count(&self, request: Arc<CountRequest>) -> CollectionResult<CountResult> {
         let local_shard = &self.wrapped_shard;
         local_shard.count(request).await
@@ -7,6 +7,7 @@ use std::sync::Arc;
 use std::thread;
 
 use arc_swap::ArcSwap;
+use chrono::Utc;
 use indicatif::{ProgressBar, ProgressStyle};
 use itertools::Itertools;
 use parking_lot::{Mutex as ParkingMutex, RwLock};
@@ -17,7 +18,7 @@ use segment::segment::Segment;
 use segment::segment_constructor::{build_segment, load_segment};
 use segment::types::{
     CompressionRatio, Filter, PayloadIndexInfo, PayloadKeyType, PayloadStorageType, PointIdType,
-    QuantizationConfig, SegmentConfig, SegmentType,
+    QuantizationConfig, SearchCalibration, SegmentConfig, SegmentType,
 };
 use segment::utils::mem::Mem;
 use tokio::fs::{copy, create_dir_all, remove_dir_all};
@@ -30,16 +31,20 @@ use super::update_tracker::UpdateTracker;
 use crate::collection_manager::collection_updater::CollectionUpdater;
 use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder};
 use crate::collection_manager::optimizers::TrackerLog;
+use crate::collection_manager::search_calibration::spawn_search_calibration;
 use crate::common::file_utils::move_dir;
 use crate::config::CollectionConfig;
+use crate::operations::optimization_ops::OptimizationOperations;
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CollectionStatus, OptimizersStatus,
+    QueuedOptimization, RunningOptimization, ShardOptimizations,
 };
 use crate::operations::CollectionUpdateOperations;
-use crate::optimizers_builder::{build_optimizers, clear_temp_segments};
+use crate::optimizers_builder::{build_forced_optimizer, build_optimizers, clear_temp_segments};
 use crate::shards::shard::ShardId;
 use crate::shards::shard_config::{ShardConfig, SHARD_CONFIG_FILE};
+use crate::shards::snapshot_manifest::ShardSnapshotManifest;
 use crate::shards::telemetry::{LocalShardTelemetry, OptimizerTelemetry};
 use crate::shards::CollectionId;
 use crate::update_handler::{Optimizer, UpdateHandler, UpdateSignal};
@@ -105,12 +110,14 @@ impl LocalShard {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         segment_holder: SegmentHolder,
         collection_config: Arc<TokioRwLock<CollectionConfig>>,
         shared_storage_config: Arc<SharedStorageConfig>,
         wal: SerdeWal<CollectionUpdateOperations>,
         optimizers: Arc<Vec<Arc<Optimizer>>>,
+        optimizers_paused: bool,
         shard_path: &Path,
         update_runtime: Handle,
     ) -> Self {
@@ -128,6 +135,7 @@ impl LocalShard {
             locked_wal.clone(),
             config.optimizer_config.flush_interval_sec,
             config.optimizer_config.max_optimization_threads,
+            optimizers_paused,
         );
 
         let (update_sender, update_receiver) =
@@ -172,11 +180,14 @@ impl LocalShard {
         let segments_path = Self::segments_path(shard_path);
         let mut segment_holder = SegmentHolder::default();
 
-        let wal: SerdeWal<CollectionUpdateOperations> = SerdeWal::new(
+        let mut wal: SerdeWal<CollectionUpdateOperations> = SerdeWal::new(
             wal_path.to_str().unwrap(),
             (&collection_config_read.wal_config).into(),
         )
         .map_err(|e| CollectionError::service_error(format!("Wal error: {e}")))?;
+        if let Some(archive_path) = shared_storage_config.wal_archive_path(&collection_id, id) {
+            wal.enable_archive(&archive_path)?;
+        }
 
         let segment_dirs = std::fs::read_dir(&segments_path).map_err(|err| {
             CollectionError::service_error(format!(
@@ -247,12 +258,17 @@ impl LocalShard {
 
         drop(collection_config_read); // release `shared_config` from borrow checker
 
+        // Optimizers stay paused across restarts
+        let optimizers_paused = ShardConfig::load(shard_path)?
+            .map_or(false, |shard_config| shard_config.optimizers_paused);
+
         let collection = LocalShard::new(
             segment_holder,
             collection_config,
             shared_storage_config,
             wal,
             optimizers,
+            optimizers_paused,
             shard_path,
             update_runtime,
         )
@@ -281,6 +297,15 @@ impl LocalShard {
             }
         }
 
+        // Finish calibration of indexes, which were built before the restart
+        spawn_search_calibration(
+            collection
+                .segments
+                .read()
+                .iter()
+                .map(|(_, segment)| segment),
+        );
+
         Ok(collection)
     }
 
@@ -349,7 +374,7 @@ impl LocalShard {
         let mut segment_holder = SegmentHolder::default();
         let mut build_handlers = vec![];
 
-        let vector_params = config.params.into_base_vector_data()?;
+        let vector_params = config.params.into_base_vector_data(&config.hnsw_config)?;
         let segment_number = config.optimizer_config.get_number_segments();
 
         for _sid in 0..segment_number {
@@ -388,8 +413,11 @@ impl LocalShard {
             segment_holder.add(segment);
         }
 
-        let wal: SerdeWal<CollectionUpdateOperations> =
+        let mut wal: SerdeWal<CollectionUpdateOperations> =
             SerdeWal::new(wal_path.to_str().unwrap(), (&config.wal_config).into())?;
+        if let Some(archive_path) = shared_storage_config.wal_archive_path(&collection_id, id) {
+            wal.enable_archive(&archive_path)?;
+        }
 
         let optimizers = build_optimizers(
             shard_path,
@@ -401,12 +429,17 @@ impl LocalShard {
 
         drop(config); // release `shared_config` from borrow checker
 
+        // Optimizers stay paused across restarts
+        let optimizers_paused = ShardConfig::load(shard_path)?
+            .map_or(false, |shard_config| shard_config.optimizers_paused);
+
         let collection = LocalShard::new(
             segment_holder,
             collection_config,
             shared_storage_config,
             wal,
             optimizers,
+            optimizers_paused,
             shard_path,
             update_runtime,
         )
@@ -482,6 +515,7 @@ impl LocalShard {
         }
 
         self.segments.read().flush_all(true)?;
+        self.update_tracker.set_applied_operation(wal.last_index());
         bar.finish();
 
         Ok(())
@@ -514,6 +548,109 @@ impl LocalShard {
         Ok(())
     }
 
+    /// Running and queued optimizations of this shard
+    pub async fn optimizations(&self, shard_id: ShardId) -> ShardOptimizations {
+        let update_handler = self.update_handler.lock().await;
+
+        let now = Utc::now();
+        let running = self
+            .optimizers_log
+            .lock()
+            .running_trackers()
+            .map(|tracker| {
+                let progress = tracker.state.lock().progress;
+                RunningOptimization {
+                    optimizer: tracker.name.clone(),
+                    segment_ids: tracker.segment_ids.clone(),
+                    stage: progress.stage,
+                    copied_points: progress.copied_points,
+                    total_points: progress.total_points,
+                    started_at: tracker.start_at,
+                    elapsed: (now - tracker.start_at)
+                        .to_std()
+                        .unwrap_or_default()
+                        .as_secs_f64(),
+                }
+            })
+            .collect();
+
+        let queued = update_handler
+            .queued_optimizations()
+            .into_iter()
+            .map(|(optimizer, segment_ids)| QueuedOptimization {
+                optimizer,
+                segment_ids,
+            })
+            .collect();
+
+        ShardOptimizations {
+            shard_id,
+            paused: update_handler.optimizers_paused(),
+            running,
+            queued,
+        }
+    }
+
+    /// Don't start new optimizations until resumed, running ones are cancelled in the background
+    ///
+    /// Paused state is persisted in the shard config, so optimizers stay paused after restart.
+    pub async fn pause_optimizers(&self) -> CollectionResult<()> {
+        self.save_optimizers_paused(true)?;
+        self.update_handler.lock().await.pause_optimizers();
+        Ok(())
+    }
+
+    /// Allow optimizers to start new optimizations again
+    pub async fn resume_optimizers(&self) -> CollectionResult<()> {
+        self.save_optimizers_paused(false)?;
+        self.update_handler.lock().await.resume_optimizers();
+        self.update_sender.load().send(UpdateSignal::Nop).await?;
+        Ok(())
+    }
+
+    fn save_optimizers_paused(&self, optimizers_paused: bool) -> CollectionResult<()> {
+        let mut shard_config =
+            ShardConfig::load(&self.path)?.unwrap_or_else(ShardConfig::new_replica_set);
+        if shard_config.optimizers_paused != optimizers_paused {
+            shard_config.optimizers_paused = optimizers_paused;
+            shard_config.save(&self.path)?;
+        }
+        Ok(())
+    }
+
+    /// Start the optimization, explicitly requested by the user
+    ///
+    /// Returns `true` if any optimization was started.
+    pub async fn force_optimization(
+        &self,
+        operation: &OptimizationOperations,
+    ) -> CollectionResult<bool> {
+        let optimizer = {
+            let config = self.collection_config.read().await;
+            build_forced_optimizer(
+                &self.path,
+                &config.params,
+                &config.optimizer_config,
+                &config.hnsw_config,
+                &config.quantization_config,
+                operation,
+            )
+        };
+
+        let Some(optimizer) = optimizer else {
+            return Ok(false);
+        };
+
+        let launched = self
+            .update_handler
+            .lock()
+            .await
+            .launch_forced_optimization(Arc::new(vec![optimizer]))
+            .await;
+
+        Ok(launched > 0)
+    }
+
     /// Finishes ongoing update tasks
     pub async fn stop_gracefully(&self) {
         if let Err(err) = self.update_sender.load().send(UpdateSignal::Stop).await {
@@ -551,11 +688,15 @@ impl LocalShard {
     }
 
     /// Create snapshot for local shard into `target_path`
+    ///
+    /// If `base` is given, segments and WAL files which are unchanged since the base snapshot
+    /// are not included, but listed in the snapshot manifest.
     pub async fn create_snapshot(
         &self,
         temp_path: &Path,
         target_path: &Path,
         save_wal: bool,
+        base: Option<&ShardSnapshotManifest>,
     ) -> CollectionResult<()> {
         let snapshot_shard_path = target_path;
 
@@ -578,19 +719,30 @@ impl LocalShard {
         }
 
         let temp_path = temp_path.to_owned();
+        let base = base.cloned();
 
         tokio::task::spawn_blocking(move || {
             let segments_read = segments.read();
 
             // Do not change segments while snapshotting
-            segments_read.snapshot_all_segments(&temp_path, &snapshot_segments_shard_path)?;
+            let segment_versions = segments_read.snapshot_all_segments(
+                &temp_path,
+                &snapshot_segments_shard_path,
+                base.as_ref().map(|base| &base.segments),
+            )?;
 
             if save_wal {
                 // snapshot all shard's WAL
-                Self::snapshot_wal(wal, &snapshot_shard_path_owned)
+                Self::snapshot_wal(wal, &snapshot_shard_path_owned)?;
             } else {
-                Self::snapshot_empty_wal(wal, &snapshot_shard_path_owned)
+                Self::snapshot_empty_wal(wal, &snapshot_shard_path_owned)?;
             }
+
+            ShardSnapshotManifest::create(
+                &snapshot_shard_path_owned,
+                segment_versions,
+                base.as_ref(),
+            )
         })
         .await??;
 
@@ -765,6 +917,7 @@ impl LocalShard {
         let mut segments_count = 0;
         let mut status = CollectionStatus::Green;
         let mut schema: HashMap<PayloadKeyType, PayloadIndexInfo> = Default::default();
+        let mut search_calibration: HashMap<String, SearchCalibration> = Default::default();
         for (_idx, segment) in segments.iter() {
             segments_count += 1;
 
@@ -786,6 +939,14 @@ impl LocalShard {
                     }
                 }
             }
+            for (vector_name, vector_data_info) in segment_info.vector_data {
+                if let Some(calibration) = vector_data_info.search_calibration {
+                    search_calibration
+                        .entry(vector_name)
+                        .or_default()
+                        .merge(&calibration);
+                }
+            }
         }
         if !segments.failed_operation.is_empty() || segments.optimizer_errors.is_some() {
             status = CollectionStatus::Red;
@@ -805,6 +966,7 @@ impl LocalShard {
             segments_count,
             config: collection_config,
             payload_schema: schema,
+            search_calibration,
         }
     }
 
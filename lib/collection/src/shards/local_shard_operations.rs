@@ -3,6 +3,7 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use futures::future::try_join_all;
 use itertools::Itertools;
+use segment::data_types::search_profile::SegmentSearchProfile;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -26,7 +27,8 @@ impl LocalShard {
         &self,
         core_request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
-    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        profile: bool,
+    ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<SegmentSearchProfile>)> {
         let (collection_params, indexing_threshold_kb) = {
             let collection_config = self.collection_config.read().await;
             (
@@ -45,16 +47,17 @@ impl LocalShard {
 
         let is_stopped = StoppingGuard::new();
 
-        let search_request = SegmentsSearcher::search(
+        let search_request = SegmentsSearcher::search_with_profile(
             self.segments.clone(),
             core_request.clone(),
             search_runtime_handle,
             true,
             is_stopped.get_is_stopped(),
             indexing_threshold_kb,
+            profile,
         );
         let timeout = self.shared_storage_config.search_timeout;
-        let res: Vec<Vec<ScoredPoint>> = tokio::select! {
+        let (res, profiles) = tokio::select! {
             res = search_request => res,
             _ = tokio::time::sleep(timeout) => {
                 is_stopped.stop();
@@ -72,15 +75,23 @@ impl LocalShard {
                     .get_vector_params(vector_name)
                     .unwrap()
                     .distance;
+                // Scores computed by the formula are final, larger is better
+                let has_formula = req.formula.is_some();
                 let processed_res = vector_res.into_iter().map(|mut scored_point| {
-                    scored_point.score = distance.postprocess_score(scored_point.score);
+                    if !has_formula {
+                        scored_point.score = distance.postprocess_score(scored_point.score);
+                    }
                     scored_point
                 });
 
                 if let Some(threshold) = req.score_threshold {
                     processed_res
                         .take_while(|scored_point| {
-                            distance.check_threshold(scored_point.score, threshold)
+                            if has_formula {
+                                scored_point.score > threshold
+                            } else {
+                                distance.check_threshold(scored_point.score, threshold)
+                            }
                         })
                         .collect()
                 } else {
@@ -88,7 +99,7 @@ impl LocalShard {
                 }
             })
             .collect();
-        Ok(top_results)
+        Ok((top_results, profiles))
     }
 }
 #[async_trait]
@@ -127,11 +138,13 @@ impl ShardOperation for LocalShard {
             Ok(UpdateResult {
                 operation_id,
                 status: UpdateStatus::Completed,
+                consistency_token: None,
             })
         } else {
             Ok(UpdateResult {
                 operation_id,
                 status: UpdateStatus::Acknowledged,
+                consistency_token: None,
             })
         }
     }
@@ -193,11 +206,14 @@ impl ShardOperation for LocalShard {
         request: Arc<SearchRequestBatch>,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
-        self.do_search(
-            Arc::new(request.as_ref().clone().into()),
-            search_runtime_handle,
-        )
-        .await
+        let (res, _) = self
+            .do_search(
+                Arc::new(request.as_ref().clone().into()),
+                search_runtime_handle,
+                false,
+            )
+            .await?;
+        Ok(res)
     }
 
     // ! COPY-PASTE: `core_search` is a copy-paste of `search` with different request type
@@ -207,7 +223,18 @@ impl ShardOperation for LocalShard {
         request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
-        self.do_search(request, search_runtime_handle).await
+        let (res, _) = self
+            .do_search(request, search_runtime_handle, false)
+            .await?;
+        Ok(res)
+    }
+
+    async fn core_search_with_profile(
+        &self,
+        request: Arc<CoreSearchRequestBatch>,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<SegmentSearchProfile>)> {
+        self.do_search(request, search_runtime_handle, true).await
     }
 
     async fn count(&self, request: Arc<CountRequest>) -> CollectionResult<CountResult> {
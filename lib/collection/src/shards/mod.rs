@@ -1,21 +1,26 @@
 pub mod channel_service;
 pub mod collection_shard_distribution;
+pub mod consistency;
 mod conversions;
 pub mod dummy_shard;
 pub mod forward_proxy_shard;
 pub mod local_shard;
 pub mod local_shard_operations;
+pub mod peer_labels;
 pub mod proxy_shard;
 pub mod queue_proxy_shard;
+pub mod recall;
 pub mod remote_shard;
 #[allow(dead_code)]
 pub mod replica_set;
+pub mod resharding;
 pub mod resolve;
 pub mod shard;
 pub mod shard_config;
 pub mod shard_holder;
 pub mod shard_trait;
 pub mod shard_versioning;
+pub mod snapshot_manifest;
 pub mod telemetry;
 pub mod transfer;
 pub mod update_tracker;
@@ -0,0 +1,67 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::shards::shard::PeerId;
+
+/// Labels of the peer, describing its location in the cluster.
+/// For example: `zone: eu-west-1a`, `rack: r12`.
+pub type PeerLabels = BTreeMap<String, String>;
+
+pub type PeerLabelsById = HashMap<PeerId, PeerLabels>;
+
+/// Number of label values, which the peer shares with the given peers.
+///
+/// Replicas of the same shard should be placed on peers with the lowest number,
+/// so they are spread across distinct zones, racks, etc.
+/// Peers without labels never share anything.
+pub fn shared_labels_count<'a>(
+    labels_by_id: &PeerLabelsById,
+    peer_id: PeerId,
+    other_peers: impl IntoIterator<Item = &'a PeerId>,
+) -> usize {
+    let Some(labels) = labels_by_id.get(&peer_id) else {
+        return 0;
+    };
+
+    other_peers
+        .into_iter()
+        .filter(|other| **other != peer_id)
+        .filter_map(|other| labels_by_id.get(other))
+        .map(|other_labels| {
+            labels
+                .iter()
+                .filter(|(key, value)| other_labels.get(*key) == Some(*value))
+                .count()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(zone: &str, rack: &str) -> PeerLabels {
+        PeerLabels::from([
+            ("zone".to_string(), zone.to_string()),
+            ("rack".to_string(), rack.to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_shared_labels_count() {
+        let labels_by_id = PeerLabelsById::from([
+            (1, labels("a", "r1")),
+            (2, labels("a", "r2")),
+            (3, labels("b", "r3")),
+            (4, labels("a", "r1")),
+        ]);
+
+        assert_eq!(shared_labels_count(&labels_by_id, 1, &[1]), 0);
+        assert_eq!(shared_labels_count(&labels_by_id, 1, &[3]), 0);
+        assert_eq!(shared_labels_count(&labels_by_id, 1, &[2]), 1);
+        assert_eq!(shared_labels_count(&labels_by_id, 1, &[4]), 2);
+        assert_eq!(shared_labels_count(&labels_by_id, 1, &[2, 3, 4]), 3);
+        // Peer without labels
+        assert_eq!(shared_labels_count(&labels_by_id, 5, &[1, 2]), 0);
+        assert_eq!(shared_labels_count(&labels_by_id, 1, &[5]), 0);
+    }
+}
@@ -5,6 +5,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::data_types::search_profile::SegmentSearchProfile;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
     WithVector,
@@ -24,6 +25,7 @@ use crate::operations::types::{
 use crate::operations::CollectionUpdateOperations;
 use crate::shards::local_shard::LocalShard;
 use crate::shards::shard_trait::ShardOperation;
+use crate::shards::snapshot_manifest::ShardSnapshotManifest;
 use crate::shards::telemetry::LocalShardTelemetry;
 use crate::update_handler::UpdateSignal;
 
@@ -36,7 +38,7 @@ type ChangedPointsSet = Arc<RwLock<HashSet<PointIdType>>>;
 /// It can be used to provide all read and write operations while the wrapped shard is being transferred to another node.
 /// It keeps track of changed points during the shard transfer to assure consistency.
 pub struct ProxyShard {
-    wrapped_shard: LocalShard,
+    pub(crate) wrapped_shard: LocalShard,
     changed_points: ChangedPointsSet,
     pub changed_alot: AtomicBool,
 }
@@ -68,9 +70,10 @@ impl ProxyShard {
         temp_path: &Path,
         target_path: &Path,
         save_wal: bool,
+        base: Option<&ShardSnapshotManifest>,
     ) -> CollectionResult<()> {
         self.wrapped_shard
-            .create_snapshot(temp_path, target_path, save_wal)
+            .create_snapshot(temp_path, target_path, save_wal, base)
             .await
     }
 
@@ -226,6 +229,17 @@ impl ShardOperation for ProxyShard {
             .await
     }
 
+    async fn core_search_with_profile(
+        &self,
+        request: Arc<CoreSearchRequestBatch>,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<SegmentSearchProfile>)> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .core_search_with_profile(request, search_runtime_handle)
+            .await
+    }
+
     /// Forward read-only `count` to `wrapped_shard`
     async fn count(&self, request: Arc<CountRequest>) -> CollectionResult<CountResult> {
         let local_shard = &self.wrapped_shard;
@@ -3,6 +3,7 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use segment::data_types::search_profile::SegmentSearchProfile;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -19,6 +20,7 @@ use crate::operations::types::{
 use crate::operations::CollectionUpdateOperations;
 use crate::shards::local_shard::LocalShard;
 use crate::shards::shard_trait::ShardOperation;
+use crate::shards::snapshot_manifest::ShardSnapshotManifest;
 use crate::shards::telemetry::LocalShardTelemetry;
 
 /// ForwardQueue shard
@@ -69,9 +71,10 @@ impl QueueProxyShard {
         temp_path: &Path,
         target_path: &Path,
         save_wal: bool,
+        base: Option<&ShardSnapshotManifest>,
     ) -> CollectionResult<()> {
         self.wrapped_shard
-            .create_snapshot(temp_path, target_path, save_wal)
+            .create_snapshot(temp_path, target_path, save_wal, base)
             .await
     }
 
@@ -233,6 +236,17 @@ impl ShardOperation for QueueProxyShard {
             .await
     }
 
+    async fn core_search_with_profile(
+        &self,
+        request: Arc<CoreSearchRequestBatch>,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<SegmentSearchProfile>)> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .core_search_with_profile(request, search_runtime_handle)
+            .await
+    }
+
     /// Forward read-only `count` to `wrapped_shard`
     async fn count(&self, request: Arc<CountRequest>) -> CollectionResult<CountResult> {
         let local_shard = &self.wrapped_shard;
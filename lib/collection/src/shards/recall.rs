@@ -0,0 +1,295 @@
+//! Estimation of the approximate search recall.
+//!
+//! Every query is searched in every segment twice: with `exact: true` and with the approximate
+//! search in each quantization mode. Recall is the fraction of the exact results, which are also
+//! found by the approximate search. Results of segments and shards are merged per query, the same
+//! way as results of a regular search, to estimate the recall of the whole shard or collection.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+use segment::data_types::vectors::{QueryVector, VectorElementType};
+use segment::types::{
+    Condition, Filter, PointIdType, ScoredPoint, SearchParams, WithPayload, WithVector,
+};
+
+use crate::config::CollectionConfig;
+use crate::operations::recall_ops::{
+    RecallEstimate, RecallQuantizationMode, SegmentRecallEstimation,
+};
+use crate::operations::types::CollectionResult;
+use crate::shards::local_shard::LocalShard;
+
+/// Stored point, which vectors are used as queries
+#[derive(Debug, Clone)]
+pub struct RecallQuery {
+    pub id: PointIdType,
+    pub vectors: HashMap<String, Vec<VectorElementType>>,
+}
+
+/// Vector and quantization mode, for which the recall is measured
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecallTarget {
+    pub vector: String,
+    pub quantization: RecallQuantizationMode,
+}
+
+impl RecallTarget {
+    pub fn all(collection_config: &CollectionConfig) -> Vec<RecallTarget> {
+        let mut targets = Vec::new();
+        for (vector, params) in collection_config.params.vectors.params_iter() {
+            let quantized = params.quantization_config.is_some()
+                || collection_config.quantization_config.is_some();
+            for &quantization in RecallQuantizationMode::measured(quantized) {
+                targets.push(RecallTarget {
+                    vector: vector.to_string(),
+                    quantization,
+                });
+            }
+        }
+        targets.sort_by(|a, b| a.vector.cmp(&b.vector));
+        targets
+    }
+}
+
+/// Exact and approximate search results of a single query
+#[derive(Debug, Clone, Default)]
+pub struct SearchMeasurement {
+    pub exact: Vec<ScoredPoint>,
+    pub approximate: Vec<ScoredPoint>,
+    pub exact_time: Duration,
+    pub approximate_time: Duration,
+}
+
+impl SearchMeasurement {
+    /// Combine results of the same query from another segment or shard
+    pub fn merge(&mut self, other: SearchMeasurement, limit: usize) {
+        self.exact.extend(other.exact);
+        self.exact = top_results(std::mem::take(&mut self.exact), limit);
+        self.approximate.extend(other.approximate);
+        self.approximate = top_results(std::mem::take(&mut self.approximate), limit);
+        self.exact_time += other.exact_time;
+        self.approximate_time += other.approximate_time;
+    }
+}
+
+/// Best `limit` results, a point found in several segments is taken once
+///
+/// Scores are not post-processed yet, so larger is better for any distance.
+fn top_results(mut points: Vec<ScoredPoint>, limit: usize) -> Vec<ScoredPoint> {
+    points.sort_unstable_by(|a, b| b.cmp(a));
+    let mut seen = HashSet::new();
+    points.retain(|point| seen.insert(point.id));
+    points.truncate(limit);
+    points
+}
+
+pub fn recall_estimate(
+    target: &RecallTarget,
+    measurements: &[SearchMeasurement],
+) -> RecallEstimate {
+    let mut queries = 0;
+    let mut expected = 0;
+    let mut found = 0;
+    let mut exact_time = Duration::ZERO;
+    let mut approximate_time = Duration::ZERO;
+
+    for measurement in measurements {
+        if measurement.exact.is_empty() {
+            continue;
+        }
+        let exact_ids: HashSet<_> = measurement.exact.iter().map(|point| point.id).collect();
+        queries += 1;
+        expected += exact_ids.len();
+        found += measurement
+            .approximate
+            .iter()
+            .filter(|point| exact_ids.contains(&point.id))
+            .count();
+        exact_time += measurement.exact_time;
+        approximate_time += measurement.approximate_time;
+    }
+
+    let average = |time: Duration| {
+        if queries > 0 {
+            time.as_secs_f64() / queries as f64
+        } else {
+            0.0
+        }
+    };
+
+    RecallEstimate {
+        vector: target.vector.clone(),
+        quantization: target.quantization,
+        queries,
+        recall: if expected > 0 {
+            found as f64 / expected as f64
+        } else {
+            1.0
+        },
+        latency: average(approximate_time),
+        exact_latency: average(exact_time),
+    }
+}
+
+/// Filter of the searches for the query, which excludes the query point itself
+fn query_filter(query_id: PointIdType, filter: Option<&Filter>) -> Filter {
+    let mut query_filter = Filter::new_must_not(Condition::HasId(HashSet::from([query_id]).into()));
+    query_filter.must = filter.map(|filter| vec![Condition::Filter(filter.clone())]);
+    query_filter
+}
+
+/// Recall measured in a local shard
+pub struct ShardRecallMeasurement {
+    pub segments: Vec<SegmentRecallEstimation>,
+    /// Results of every query for each of the targets, merged over all segments
+    pub measurements: Vec<Vec<SearchMeasurement>>,
+}
+
+impl LocalShard {
+    /// Random points of the shard, which satisfy the filter
+    pub async fn sample_recall_queries(
+        &self,
+        filter: Option<Filter>,
+        sample: usize,
+    ) -> CollectionResult<Vec<RecallQuery>> {
+        let segments = self.segments.clone();
+        tokio::task::spawn_blocking(move || -> CollectionResult<Vec<RecallQuery>> {
+            let segments = segments.read();
+
+            let mut point_ids = HashSet::new();
+            for (_, segment) in segments.iter() {
+                let segment = segment.get();
+                let segment = segment.read();
+                point_ids.extend(segment.read_filtered(None, None, filter.as_ref()));
+            }
+            let point_ids: Vec<_> = point_ids.into_iter().collect();
+
+            let mut queries = Vec::with_capacity(sample.min(point_ids.len()));
+            for &point_id in point_ids.choose_multiple(&mut thread_rng(), sample) {
+                let Some(segment) = segments
+                    .iter()
+                    .map(|(_, segment)| segment.get())
+                    .find(|segment| segment.read().has_point(point_id))
+                else {
+                    continue;
+                };
+                let vectors = segment.read().all_vectors(point_id)?;
+                queries.push(RecallQuery {
+                    id: point_id,
+                    vectors: vectors.into_owned_map(),
+                });
+            }
+            Ok(queries)
+        })
+        .await?
+    }
+
+    /// Search the queries in every segment of the shard, exactly and approximately
+    pub async fn measure_recall(
+        &self,
+        queries: Arc<Vec<RecallQuery>>,
+        filter: Option<Filter>,
+        limit: usize,
+        params: Option<SearchParams>,
+    ) -> CollectionResult<ShardRecallMeasurement> {
+        let targets = RecallTarget::all(&*self.collection_config.read().await);
+        let segments = self.segments.clone();
+
+        tokio::task::spawn_blocking(move || -> CollectionResult<ShardRecallMeasurement> {
+            let segments = segments.read();
+            let is_stopped = AtomicBool::new(false);
+            let exact_params = SearchParams {
+                exact: true,
+                ..Default::default()
+            };
+            let with_payload = WithPayload::from(false);
+            let with_vector = WithVector::Bool(false);
+
+            let mut shard_measurements =
+                vec![vec![SearchMeasurement::default(); queries.len()]; targets.len()];
+            let mut segment_estimations = Vec::with_capacity(segments.len());
+
+            for (&segment_id, segment) in segments.iter() {
+                let segment = segment.get();
+                let segment = segment.read();
+                let mut estimates = Vec::with_capacity(targets.len());
+
+                for (target, shard_measurements) in targets.iter().zip(&mut shard_measurements) {
+                    if !segment.config().vector_data.contains_key(&target.vector) {
+                        continue;
+                    }
+                    let approximate_params = target.quantization.search_params(params);
+
+                    let mut measurements = Vec::with_capacity(queries.len());
+                    for query in queries.iter() {
+                        let Some(vector) = query.vectors.get(&target.vector) else {
+                            measurements.push(SearchMeasurement::default());
+                            continue;
+                        };
+                        let query_vector = QueryVector::from(vector.clone());
+                        let query_filter = query_filter(query.id, filter.as_ref());
+
+                        let timer = Instant::now();
+                        let exact = segment.search(
+                            &target.vector,
+                            &query_vector,
+                            &with_payload,
+                            &with_vector,
+                            Some(&query_filter),
+                            limit,
+                            Some(&exact_params),
+                            &is_stopped,
+                        )?;
+                        let exact_time = timer.elapsed();
+
+                        let timer = Instant::now();
+                        let approximate = segment.search(
+                            &target.vector,
+                            &query_vector,
+                            &with_payload,
+                            &with_vector,
+                            Some(&query_filter),
+                            limit,
+                            Some(&approximate_params),
+                            &is_stopped,
+                        )?;
+                        let approximate_time = timer.elapsed();
+
+                        measurements.push(SearchMeasurement {
+                            exact,
+                            approximate,
+                            exact_time,
+                            approximate_time,
+                        });
+                    }
+
+                    estimates.push(recall_estimate(target, &measurements));
+                    for (shard_measurement, measurement) in
+                        shard_measurements.iter_mut().zip(measurements)
+                    {
+                        shard_measurement.merge(measurement, limit);
+                    }
+                }
+
+                segment_estimations.push(SegmentRecallEstimation {
+                    segment_id,
+                    segment_type: segment.segment_type(),
+                    points_count: segment.available_point_count(),
+                    estimates,
+                });
+            }
+            segment_estimations.sort_by_key(|estimation| estimation.segment_id);
+
+            Ok(ShardRecallMeasurement {
+                segments: segment_estimations,
+                measurements: shard_measurements,
+            })
+        })
+        .await?
+    }
+}
@@ -1,22 +1,31 @@
 use std::future::Future;
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
 use api::grpc::qdrant::collections_internal_client::CollectionsInternalClient;
+use api::grpc::qdrant::force_optimization_request::Operation as ForceOptimizationPb;
 use api::grpc::qdrant::points_internal_client::PointsInternalClient;
 use api::grpc::qdrant::{
     CollectionOperationResponse, CoreSearchBatchPointsInternal, CountPoints, CountPointsInternal,
+    CreateShardSnapshotRequest, DeleteShardSnapshotRequest, ForceOptimizationRequest,
     GetCollectionInfoRequest, GetCollectionInfoRequestInternal, GetPoints, GetPointsInternal,
-    InitiateShardTransferRequest, ScrollPoints, ScrollPointsInternal, SearchBatchPointsInternal,
+    GetShardDigestRequest, GetShardDigestResponse, GetShardSnapshotChunkRequest,
+    InitiateShardTransferRequest, MeasureRecallRequest, PayloadArrayOperation,
+    SampleRecallQueriesRequest, ScrollPoints, ScrollPointsInternal, SearchBatchPointsInternal,
+    WaitForOperationRequest,
 };
 use async_trait::async_trait;
 use parking_lot::Mutex;
 use segment::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
 };
+use segment::data_types::search_profile::SegmentSearchProfile;
 use segment::types::{
-    ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
+    ExtendedPointId, Filter, ScoredPoint, SearchParams, SeqNumberType, WithPayload,
+    WithPayloadInterface, WithVector,
 };
+use tokio::io::AsyncWriteExt;
 use tokio::runtime::Handle;
 use tonic::transport::{Channel, Uri};
 use tonic::Status;
@@ -25,6 +34,7 @@ use super::conversions::{
     internal_delete_vectors, internal_delete_vectors_by_filter, internal_update_vectors,
 };
 use crate::operations::conversions::try_record_from_grpc;
+use crate::operations::optimization_ops::OptimizationOperations;
 use crate::operations::payload_ops::PayloadOps;
 use crate::operations::point_ops::{PointOperations, WriteOrdering};
 use crate::operations::types::{
@@ -35,17 +45,32 @@ use crate::operations::types::{
 use crate::operations::vector_ops::VectorOperations;
 use crate::operations::{CollectionUpdateOperations, FieldIndexOperations};
 use crate::shards::channel_service::ChannelService;
+use crate::shards::consistency::{PointDigest, ShardDigest};
 use crate::shards::conversions::{
     internal_clear_payload, internal_clear_payload_by_filter, internal_create_index,
     internal_delete_index, internal_delete_payload, internal_delete_points,
-    internal_delete_points_by_filter, internal_set_payload, internal_sync_points,
-    internal_upsert_points, try_scored_point_from_grpc,
+    internal_delete_points_by_filter, internal_increment_payload, internal_set_payload,
+    internal_sync_points, internal_update_payload_array, internal_upsert_points,
+    try_scored_point_from_grpc,
 };
+use crate::shards::recall::{RecallQuery, ShardRecallMeasurement};
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::RemoteShardTelemetry;
 use crate::shards::CollectionId;
 
+/// Time for the network round trip of the wait for operation request
+const WAIT_FOR_OPERATION_EXTRA_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Creating a snapshot of a large shard may take much longer than a regular request
+const CREATE_SHARD_SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(3600);
+
+/// Exact searches of the recall estimation may take much longer than a regular request
+const MEASURE_RECALL_TIMEOUT: Duration = Duration::from_secs(3600);
+
+/// Size of the chunks, in which shard snapshots are downloaded from the remote peer
+const SHARD_SNAPSHOT_CHUNK_SIZE: u64 = 16 * 1024 * 1024;
+
 /// RemoteShard
 ///
 /// Remote Shard is a representation of a shard that is located on a remote peer.
@@ -148,6 +173,262 @@ impl RemoteShard {
         Ok(res)
     }
 
+    async fn get_shard_digest(
+        &self,
+        buckets: u32,
+        bucket: Option<u32>,
+    ) -> CollectionResult<GetShardDigestResponse> {
+        let res = self
+            .with_collections_client(|mut client| async move {
+                client
+                    .get_shard_digest(GetShardDigestRequest {
+                        collection_name: self.collection_id.clone(),
+                        shard_id: self.id,
+                        buckets,
+                        bucket,
+                    })
+                    .await
+            })
+            .await?
+            .into_inner();
+        Ok(res)
+    }
+
+    pub async fn digest(&self, buckets: u32) -> CollectionResult<ShardDigest> {
+        let res = self.get_shard_digest(buckets, None).await?;
+        Ok(ShardDigest {
+            root: res.root,
+            bucket_hashes: res.bucket_hashes,
+            points_count: res.points_count as usize,
+        })
+    }
+
+    pub async fn point_digests(
+        &self,
+        buckets: u32,
+        bucket: u32,
+    ) -> CollectionResult<Vec<PointDigest>> {
+        let res = self.get_shard_digest(buckets, Some(bucket)).await?;
+        let points: Result<Vec<_>, Status> = res
+            .points
+            .into_iter()
+            .map(|point| {
+                let id = point
+                    .id
+                    .ok_or_else(|| Status::invalid_argument("Point digest without id"))?
+                    .try_into()?;
+                Ok(PointDigest {
+                    id,
+                    version: point.version,
+                    hash: point.hash,
+                })
+            })
+            .collect();
+        Ok(points?)
+    }
+
+    /// Wait until the remote replica applies the operation with the given local number.
+    ///
+    /// Returns `false` if the operation is still not applied after the `timeout`.
+    pub async fn wait_for_operation(
+        &self,
+        operation_id: SeqNumberType,
+        timeout: Duration,
+    ) -> CollectionResult<bool> {
+        let current_address = self.current_address()?;
+        let res = self
+            .channel_service
+            .channel_pool
+            .with_channel_timeout(
+                &current_address,
+                |channel| async move {
+                    CollectionsInternalClient::new(channel)
+                        .wait_for_operation(WaitForOperationRequest {
+                            collection_name: self.collection_id.clone(),
+                            shard_id: self.id,
+                            operation_id,
+                            timeout: timeout.as_millis() as u64,
+                        })
+                        .await
+                },
+                // Request itself may take as long as the wait
+                Some(timeout + WAIT_FOR_OPERATION_EXTRA_TIMEOUT),
+                1,
+            )
+            .await?
+            .into_inner();
+        Ok(res.applied)
+    }
+
+    /// Create a snapshot of the shard on the remote peer
+    ///
+    /// Returns the name of the snapshot file, which is stored on the remote peer,
+    /// and its checksum, if the remote peer provides it.
+    pub async fn create_shard_snapshot(&self) -> CollectionResult<(String, Option<String>)> {
+        let current_address = self.current_address()?;
+        let res = self
+            .channel_service
+            .channel_pool
+            .with_channel_timeout(
+                &current_address,
+                |channel| async move {
+                    CollectionsInternalClient::new(channel)
+                        .create_shard_snapshot(CreateShardSnapshotRequest {
+                            collection_name: self.collection_id.clone(),
+                            shard_id: self.id,
+                        })
+                        .await
+                },
+                Some(CREATE_SHARD_SNAPSHOT_TIMEOUT),
+                1,
+            )
+            .await?
+            .into_inner();
+        Ok((res.snapshot_name, res.checksum))
+    }
+
+    /// Download the shard snapshot file from the remote peer to `target_path`
+    pub async fn download_shard_snapshot(
+        &self,
+        snapshot_name: &str,
+        target_path: &Path,
+    ) -> CollectionResult<()> {
+        let mut file = tokio::fs::File::create(target_path).await?;
+        let mut offset = 0;
+        loop {
+            let res = self
+                .with_collections_client(|mut client| async move {
+                    client
+                        .get_shard_snapshot_chunk(GetShardSnapshotChunkRequest {
+                            collection_name: self.collection_id.clone(),
+                            shard_id: self.id,
+                            snapshot_name: snapshot_name.to_string(),
+                            offset,
+                            length: SHARD_SNAPSHOT_CHUNK_SIZE,
+                        })
+                        .await
+                })
+                .await?
+                .into_inner();
+            file.write_all(&res.data).await?;
+            offset += res.data.len() as u64;
+            if (res.data.len() as u64) < SHARD_SNAPSHOT_CHUNK_SIZE {
+                break;
+            }
+        }
+        file.sync_all().await?;
+        Ok(())
+    }
+
+    /// Delete the shard snapshot file from the remote peer
+    pub async fn delete_shard_snapshot(&self, snapshot_name: &str) -> CollectionResult<bool> {
+        let res = self
+            .with_collections_client(|mut client| async move {
+                client
+                    .delete_shard_snapshot(DeleteShardSnapshotRequest {
+                        collection_name: self.collection_id.clone(),
+                        shard_id: self.id,
+                        snapshot_name: snapshot_name.to_string(),
+                    })
+                    .await
+            })
+            .await?
+            .into_inner();
+        Ok(res.result)
+    }
+
+    /// Start the merge or vacuum optimization in the shard on the remote peer
+    ///
+    /// Returns `true` if any optimization was started.
+    pub async fn force_optimization(
+        &self,
+        operation: &OptimizationOperations,
+    ) -> CollectionResult<bool> {
+        let operation = ForceOptimizationPb::try_from(operation.clone())?;
+        let res = self
+            .with_collections_client(|mut client| {
+                let operation = operation.clone();
+                async move {
+                    client
+                        .force_optimization(ForceOptimizationRequest {
+                            collection_name: self.collection_id.clone(),
+                            shard_id: self.id,
+                            operation: Some(operation),
+                        })
+                        .await
+                }
+            })
+            .await?
+            .into_inner();
+        Ok(res.started)
+    }
+
+    /// Sample recall estimation queries from the shard on the remote peer
+    pub async fn sample_recall_queries(
+        &self,
+        filter: Option<&Filter>,
+        sample: usize,
+    ) -> CollectionResult<Vec<RecallQuery>> {
+        let res = self
+            .with_collections_client(|mut client| async move {
+                client
+                    .sample_recall_queries(SampleRecallQueriesRequest {
+                        collection_name: self.collection_id.clone(),
+                        shard_id: self.id,
+                        filter: filter.cloned().map(Into::into),
+                        sample: sample as u64,
+                    })
+                    .await
+            })
+            .await?
+            .into_inner();
+        let queries = res
+            .queries
+            .into_iter()
+            .map(RecallQuery::try_from)
+            .collect::<Result<_, _>>()?;
+        Ok(queries)
+    }
+
+    /// Measure recall of the approximate search in the shard on the remote peer
+    pub async fn measure_recall(
+        &self,
+        queries: &[RecallQuery],
+        filter: Option<&Filter>,
+        limit: usize,
+        params: Option<SearchParams>,
+    ) -> CollectionResult<ShardRecallMeasurement> {
+        let queries: Vec<_> = queries.iter().cloned().map(Into::into).collect();
+        let current_address = self.current_address()?;
+        let res = self
+            .channel_service
+            .channel_pool
+            .with_channel_timeout(
+                &current_address,
+                |channel| {
+                    let queries = queries.clone();
+                    async move {
+                        CollectionsInternalClient::new(channel)
+                            .max_decoding_message_size(usize::MAX)
+                            .measure_recall(MeasureRecallRequest {
+                                collection_name: self.collection_id.clone(),
+                                shard_id: self.id,
+                                queries,
+                                filter: filter.cloned().map(Into::into),
+                                limit: limit as u64,
+                                params: params.map(Into::into),
+                            })
+                            .await
+                    }
+                },
+                Some(MEASURE_RECALL_TIMEOUT),
+                1,
+            )
+            .await?
+            .into_inner();
+        Ok(ShardRecallMeasurement::try_from(res)?)
+    }
+
     pub async fn forward_update(
         &self,
         operation: CollectionUpdateOperations,
@@ -357,6 +638,73 @@ impl RemoteShard {
                     .await?
                     .into_inner()
                 }
+                PayloadOps::Increment(increment_payload) => {
+                    let request = &internal_increment_payload(
+                        shard_id,
+                        collection_name,
+                        increment_payload,
+                        wait,
+                        ordering,
+                    );
+                    self.with_points_client(|mut client| async move {
+                        client
+                            .increment_payload(tonic::Request::new(request.clone()))
+                            .await
+                    })
+                    .await?
+                    .into_inner()
+                }
+                PayloadOps::ArrayAppend(update_payload_array) => {
+                    let request = &internal_update_payload_array(
+                        shard_id,
+                        collection_name,
+                        update_payload_array,
+                        PayloadArrayOperation::Append,
+                        wait,
+                        ordering,
+                    );
+                    self.with_points_client(|mut client| async move {
+                        client
+                            .update_payload_array(tonic::Request::new(request.clone()))
+                            .await
+                    })
+                    .await?
+                    .into_inner()
+                }
+                PayloadOps::ArrayRemove(update_payload_array) => {
+                    let request = &internal_update_payload_array(
+                        shard_id,
+                        collection_name,
+                        update_payload_array,
+                        PayloadArrayOperation::Remove,
+                        wait,
+                        ordering,
+                    );
+                    self.with_points_client(|mut client| async move {
+                        client
+                            .update_payload_array(tonic::Request::new(request.clone()))
+                            .await
+                    })
+                    .await?
+                    .into_inner()
+                }
+                PayloadOps::AddToSet(update_payload_array) => {
+                    let request = &internal_update_payload_array(
+                        shard_id,
+                        collection_name,
+                        update_payload_array,
+                        PayloadArrayOperation::AddToSet,
+                        wait,
+                        ordering,
+                    );
+                    self.with_points_client(|mut client| async move {
+                        client
+                            .update_payload_array(tonic::Request::new(request.clone()))
+                            .await
+                    })
+                    .await?
+                    .into_inner()
+                }
             },
             CollectionUpdateOperations::FieldIndexOperation(field_index_op) => match field_index_op
             {
@@ -438,6 +786,7 @@ impl ShardOperation for RemoteShard {
             with_payload: Some(with_payload_interface.clone().into()),
             with_vectors: Some(with_vector.clone().into()),
             read_consistency: None,
+            consistency_token: None,
         };
         let request = &ScrollPointsInternal {
             scroll_points: Some(scroll_points),
@@ -587,6 +936,65 @@ impl ShardOperation for RemoteShard {
         result
     }
 
+    async fn core_search_with_profile(
+        &self,
+        batch_request: Arc<CoreSearchRequestBatch>,
+        _search_runtime_handle: &Handle,
+    ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<SegmentSearchProfile>)> {
+        let mut timer = ScopeDurationMeasurer::new(&self.telemetry_search_durations);
+        timer.set_success(false);
+
+        let search_points = batch_request
+            .searches
+            .iter()
+            .map(|s| CollectionCoreSearchRequest((self.collection_id.clone(), s)).into())
+            .collect();
+
+        let request = &CoreSearchBatchPointsInternal {
+            collection_name: self.collection_id.clone(),
+            search_points,
+            shard_id: Some(self.id),
+        };
+        let search_batch_response = self
+            .with_points_client(|mut client| async move {
+                client
+                    .core_search_batch_profiled(tonic::Request::new(request.clone()))
+                    .await
+            })
+            .await?
+            .into_inner();
+
+        let result: Result<Vec<Vec<ScoredPoint>>, Status> = search_batch_response
+            .result
+            .into_iter()
+            .zip(batch_request.searches.iter())
+            .map(|(batch_result, request)| {
+                let is_payload_required = request
+                    .with_payload
+                    .as_ref()
+                    .map_or(false, |with_payload| with_payload.is_required());
+
+                batch_result
+                    .result
+                    .into_iter()
+                    .map(|point| try_scored_point_from_grpc(point, is_payload_required))
+                    .collect()
+            })
+            .collect();
+        let profiles: Result<Vec<SegmentSearchProfile>, Status> = search_batch_response
+            .profiles
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect();
+
+        let result = result.and_then(|result| Ok((result, profiles?)));
+        let result = result.map_err(|e| e.into());
+        if result.is_ok() {
+            timer.set_success(true);
+        }
+        result
+    }
+
     async fn count(&self, request: Arc<CountRequest>) -> CollectionResult<CountResult> {
         let count_points = CountPoints {
             collection_name: self.collection_id.clone(),
@@ -626,6 +1034,7 @@ impl ShardOperation for RemoteShard {
             with_payload: request.with_payload.clone().map(|wp| wp.into()),
             with_vectors: Some(with_vector.clone().into()),
             read_consistency: None,
+            consistency_token: None,
         };
         let request = &GetPointsInternal {
             get_points: Some(get_points),
@@ -1,9 +1,9 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Write as _;
 use std::ops::Deref as _;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use futures::future::{self, BoxFuture};
 use futures::stream::FuturesUnordered;
@@ -12,25 +12,34 @@ use itertools::Itertools;
 use rand::seq::SliceRandom as _;
 use schemars::JsonSchema;
 use segment::types::{
-    ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
-    WithVector,
+    ExtendedPointId, Filter, PointIdType, ScoredPoint, SearchParams, SeqNumberType, WithPayload,
+    WithPayloadInterface, WithVector,
 };
 use serde::{Deserialize, Serialize};
 use tokio::runtime::Handle;
 use tokio::sync::{Mutex, RwLock};
 
+use super::consistency::{check_replicas, PointDigest, ReplicaRef};
 use super::local_shard::LocalShard;
 use super::queue_proxy_shard::QueueProxyShard;
+use super::recall::{RecallQuery, ShardRecallMeasurement};
 use super::remote_shard::RemoteShard;
 use super::resolve::{Resolve, ResolveCondition};
 use super::{create_shard_dir, CollectionId};
 use crate::config::CollectionConfig;
-use crate::operations::consistency_params::{ReadConsistency, ReadConsistencyType};
-use crate::operations::point_ops::WriteOrdering;
+use crate::hash_ring::HashRing;
+use crate::operations::consistency_ops::ShardConsistencyReport;
+use crate::operations::consistency_params::{
+    ConsistencyToken, ReadConsistency, ReadConsistencyType,
+};
+use crate::operations::optimization_ops::OptimizationOperations;
+use crate::operations::point_ops::{PointOperations, WriteOrdering};
 use crate::operations::shared_storage_config::SharedStorageConfig;
+use crate::operations::snapshot_ops;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch, CountRequest,
-    CountResult, PointRequest, Record, SearchRequestBatch, UpdateResult,
+    CountResult, PointRequest, Record, SearchRequestBatch, ShardOptimizations, ShardSearchProfile,
+    UpdateResult,
 };
 use crate::operations::CollectionUpdateOperations;
 use crate::save_on_disk::SaveOnDisk;
@@ -41,6 +50,7 @@ use crate::shards::shard::Shard::{Dummy, ForwardProxy, Local, QueueProxy};
 use crate::shards::shard::{PeerId, Shard, ShardId};
 use crate::shards::shard_config::ShardConfig;
 use crate::shards::shard_trait::ShardOperation;
+use crate::shards::snapshot_manifest::ShardSnapshotManifest;
 use crate::shards::telemetry::ReplicaSetTelemetry;
 
 pub type ActivatePeer = Arc<dyn Fn(PeerId, ShardId) + Send + Sync>;
@@ -49,8 +59,14 @@ pub type OnPeerCreated = Arc<dyn Fn(PeerId, ShardId) + Send + Sync>;
 
 const DEFAULT_SHARD_DEACTIVATION_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// For how long a replica, which failed to apply an update of a consistency token in time,
+/// is avoided by the reads
+const LAGGING_REPLICA_EXPIRATION: Duration = Duration::from_secs(30);
+
 const REPLICA_STATE_FILE: &str = "replica_state.json";
 
+const DELETE_BATCH_SIZE: usize = 1000;
+
 //    │    Collection Created
 //    │
 //    ▼
@@ -167,6 +183,9 @@ pub struct ShardReplicaSet {
     /// If the state of the peer is changed in the consensus, it is removed from the list.
     /// Update and read operations are not performed on the peers marked as dead.
     locally_disabled_peers: parking_lot::RwLock<HashSet<PeerId>>,
+    /// Peers, which did not apply an update of a consistency token in time, and since when.
+    /// Reads prefer other replicas until the entry expires.
+    lagging_peers: parking_lot::RwLock<HashMap<PeerId, Instant>>,
     pub(crate) shard_path: PathBuf,
     pub(crate) shard_id: ShardId,
     notify_peer_failure_cb: ChangePeerState,
@@ -352,6 +371,7 @@ impl ShardReplicaSet {
             remotes: RwLock::new(remote_shards),
             replica_state: replica_state.into(),
             locally_disabled_peers: Default::default(),
+            lagging_peers: Default::default(),
             shard_path,
             notify_peer_failure_cb: on_peer_failure,
             channel_service,
@@ -572,6 +592,7 @@ impl ShardReplicaSet {
             replica_state: replica_state.into(),
             // TODO: move to collection config
             locally_disabled_peers: Default::default(),
+            lagging_peers: Default::default(),
             shard_path: shard_path.to_path_buf(),
             notify_peer_failure_cb: on_peer_failure,
             channel_service,
@@ -742,6 +763,153 @@ impl ShardReplicaSet {
         self.replica_state.read().get_peer_state(peer_id).copied()
     }
 
+    /// Active peers, which recently failed to apply an update of a consistency token in time.
+    ///
+    /// Empty if all active peers are lagging, as there is no better replica to read from.
+    fn lagging_peers(&self) -> HashSet<PeerId> {
+        let lagging_peers: HashSet<_> = self
+            .lagging_peers
+            .read()
+            .iter()
+            .filter(|(_, since)| since.elapsed() < LAGGING_REPLICA_EXPIRATION)
+            .map(|(peer_id, _)| *peer_id)
+            .filter(|peer_id| self.peer_is_active(peer_id))
+            .collect();
+
+        let all_lagging = self
+            .peers()
+            .keys()
+            .filter(|peer_id| self.peer_is_active(peer_id))
+            .all(|peer_id| lagging_peers.contains(peer_id));
+
+        if all_lagging {
+            HashSet::new()
+        } else {
+            lagging_peers
+        }
+    }
+
+    /// Wait until any replica applies the update, described by the `clocks` of a consistency token.
+    ///
+    /// Only active replicas, which have accepted the update, are awaited.
+    /// Returns as soon as the first replica has applied the update. Replicas, which failed to apply
+    /// it within the `timeout`, are marked as lagging, so the following reads prefer replicas that
+    /// are up to date. Replicas, which are still pending, are not marked.
+    /// Fails if none of the replicas has applied the update within the `timeout`.
+    pub async fn wait_for_operations(
+        &self,
+        clocks: &BTreeMap<PeerId, SeqNumberType>,
+        timeout: Duration,
+    ) -> CollectionResult<()> {
+        let this_peer_id = self.this_peer_id();
+
+        // Do not hold the lock on the local shard while waiting
+        let local_update_tracker = match clocks.get(&this_peer_id) {
+            Some(&operation_id) if self.peer_is_active(&this_peer_id) => self
+                .local
+                .read()
+                .await
+                .as_ref()
+                .and_then(Shard::update_tracker)
+                .cloned()
+                .map(|update_tracker| (update_tracker, operation_id)),
+            _ => None,
+        };
+
+        let remotes = self.remotes.read().await;
+
+        let local_wait = local_update_tracker.map(|(update_tracker, operation_id)| {
+            async move {
+                let applied = update_tracker
+                    .wait_for_operation(operation_id, timeout)
+                    .await;
+                (this_peer_id, applied)
+            }
+            .left_future()
+        });
+
+        let remote_waits = remotes
+            .iter()
+            .filter(|remote| self.peer_is_active(&remote.peer_id))
+            .filter_map(|remote| {
+                let operation_id = *clocks.get(&remote.peer_id)?;
+                let wait = async move {
+                    let applied = remote
+                        .wait_for_operation(operation_id, timeout)
+                        .await
+                        .unwrap_or_else(|err| {
+                            log::debug!(
+                                "Failed to wait for operation {operation_id} on peer {}: {err}",
+                                remote.peer_id
+                            );
+                            false
+                        });
+                    (remote.peer_id, applied)
+                };
+                Some(wait.right_future())
+            });
+
+        let mut waits: FuturesUnordered<_> = local_wait.into_iter().chain(remote_waits).collect();
+
+        // None of the replicas, which accepted the update, is active anymore
+        if waits.is_empty() {
+            return Ok(());
+        }
+
+        let mut applied_peer_id = None;
+        while let Some((peer_id, applied)) = waits.next().await {
+            if applied {
+                self.lagging_peers.write().remove(&peer_id);
+                applied_peer_id = Some(peer_id);
+                break;
+            }
+            log::debug!(
+                "Replica of shard {}:{} on peer {peer_id} is lagging behind the consistency token",
+                self.collection_id,
+                self.shard_id,
+            );
+            self.lagging_peers.write().insert(peer_id, Instant::now());
+        }
+        drop(waits);
+        drop(remotes);
+
+        if applied_peer_id.is_some() {
+            Ok(())
+        } else {
+            Err(CollectionError::timeout(
+                timeout.as_secs() as usize,
+                format!(
+                    "wait for the update of the consistency token on shard {}",
+                    self.shard_id
+                ),
+            ))
+        }
+    }
+
+    /// Wait until the local replica applies the operation.
+    ///
+    /// Returns `false` if the operation is still not applied after the `timeout`.
+    pub async fn wait_for_local_operation(
+        &self,
+        operation_id: SeqNumberType,
+        timeout: Duration,
+    ) -> CollectionResult<bool> {
+        let update_tracker = self
+            .local
+            .read()
+            .await
+            .as_ref()
+            .and_then(Shard::update_tracker)
+            .cloned()
+            .ok_or_else(|| {
+                CollectionError::service_error(format!("Local shard {} not found", self.shard_id))
+            })?;
+
+        Ok(update_tracker
+            .wait_for_operation(operation_id, timeout)
+            .await)
+    }
+
     /// Execute read op. on replica set:
     /// 1 - Prefer local replica
     /// 2 - Otherwise uses `read_fan_out_ratio` to compute list of active remote shards.
@@ -890,6 +1058,9 @@ impl ShardReplicaSet {
 
         let local_is_active = self.peer_is_active(&self.this_peer_id());
 
+        let lagging_peers = self.lagging_peers();
+        let local_is_lagging = lagging_peers.contains(&self.this_peer_id());
+
         let local_operation = if local_is_active {
             let local_operation = async {
                 let local = local.await;
@@ -915,6 +1086,14 @@ impl ShardReplicaSet {
             .collect();
 
         active_remotes.shuffle(&mut rand::thread_rng());
+        // Lagging replicas are only used if the others fail
+        active_remotes.sort_by_key(|remote| lagging_peers.contains(&remote.peer_id));
+
+        let up_to_date_count = usize::from(local_is_active && !local_is_lagging)
+            + active_remotes
+                .iter()
+                .filter(|remote| !lagging_peers.contains(&remote.peer_id))
+                .count();
 
         let remote_operations = active_remotes.into_iter().map(|remote| {
             read_operation(remote)
@@ -922,7 +1101,16 @@ impl ShardReplicaSet {
                 .right_future()
         });
 
-        let mut operations = local_operation.into_iter().chain(remote_operations);
+        let mut operations: Vec<_> = local_operation
+            .into_iter()
+            .chain(remote_operations)
+            .collect();
+
+        if local_is_active && local_is_lagging {
+            operations.rotate_left(1);
+        }
+
+        let mut operations = operations.into_iter();
 
         // Possible scenarios:
         //
@@ -930,7 +1118,7 @@ impl ShardReplicaSet {
         // - Local is not available: default fan-out is 1
         // - There is no local: default fan-out is 1
 
-        let default_fan_out = if is_local_ready && local_is_active {
+        let default_fan_out = if is_local_ready && local_is_active && !local_is_lagging {
             0
         } else {
             1
@@ -946,7 +1134,9 @@ impl ShardReplicaSet {
             .try_into()
             .expect("u32 can be converted into usize");
 
-        let initial_concurrent_operations = required_successful_results + read_fan_out_factor;
+        // Do not fan out to lagging replicas
+        let initial_concurrent_operations = (required_successful_results + read_fan_out_factor)
+            .min(up_to_date_count.max(required_successful_results));
 
         let mut pending_operations: FuturesUnordered<_> = operations
             .by_ref()
@@ -1047,6 +1237,224 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Optimizations of the local replica, if there is one
+    pub(crate) async fn optimizations(&self) -> Option<ShardOptimizations> {
+        let read_local = self.local.read().await;
+        let local_shard = read_local.as_ref()?.local_shard()?;
+        Some(local_shard.optimizations(self.shard_id).await)
+    }
+
+    /// Pause or resume optimizers of the local replica, if there is one
+    pub(crate) async fn set_optimizers_paused(&self, paused: bool) -> CollectionResult<()> {
+        let read_local = self.local.read().await;
+        match read_local.as_ref().and_then(Shard::local_shard) {
+            Some(local_shard) if paused => local_shard.pause_optimizers().await,
+            Some(local_shard) => local_shard.resume_optimizers().await,
+            None => Ok(()),
+        }
+    }
+
+    /// Start the requested optimization in the local and all active remote replicas
+    ///
+    /// Remote replicas are requested directly, the operation does not go through consensus.
+    /// Returns `true` if any optimization was started.
+    pub(crate) async fn force_optimization(
+        &self,
+        operation: &OptimizationOperations,
+    ) -> CollectionResult<bool> {
+        let remotes = self.remotes.read().await;
+        let remote_optimizations = remotes
+            .iter()
+            .filter(|remote| self.peer_is_active(&remote.peer_id))
+            .map(|remote| remote.force_optimization(operation));
+
+        let (local_started, remote_started) = future::try_join(
+            self.force_local_optimization(operation),
+            future::try_join_all(remote_optimizations),
+        )
+        .await?;
+
+        Ok(local_started || remote_started.into_iter().any(|started| started))
+    }
+
+    /// Start the requested optimization in the local replica
+    ///
+    /// Returns `true` if any optimization was started.
+    pub(crate) async fn force_local_optimization(
+        &self,
+        operation: &OptimizationOperations,
+    ) -> CollectionResult<bool> {
+        let read_local = self.local.read().await;
+        match read_local.as_ref().and_then(Shard::local_shard) {
+            Some(local_shard) => local_shard.force_optimization(operation).await,
+            None => Ok(false),
+        }
+    }
+
+    /// Sample recall estimation queries from an active replica, the local one if possible
+    pub(crate) async fn sample_recall_queries(
+        &self,
+        filter: Option<Filter>,
+        sample: usize,
+    ) -> CollectionResult<Vec<RecallQuery>> {
+        if self.peer_is_active(&self.this_peer_id()) {
+            return self.sample_local_recall_queries(filter, sample).await;
+        }
+        let remotes = self.remotes.read().await;
+        self.active_remote(&remotes)?
+            .sample_recall_queries(filter.as_ref(), sample)
+            .await
+    }
+
+    /// Measure recall of the approximate search in an active replica, the local one if possible
+    pub(crate) async fn measure_recall(
+        &self,
+        queries: Arc<Vec<RecallQuery>>,
+        filter: Option<Filter>,
+        limit: usize,
+        params: Option<SearchParams>,
+    ) -> CollectionResult<ShardRecallMeasurement> {
+        if self.peer_is_active(&self.this_peer_id()) {
+            return self
+                .measure_local_recall(queries, filter, limit, params)
+                .await;
+        }
+        let remotes = self.remotes.read().await;
+        self.active_remote(&remotes)?
+            .measure_recall(&queries, filter.as_ref(), limit, params)
+            .await
+    }
+
+    fn active_remote<'a>(&self, remotes: &'a [RemoteShard]) -> CollectionResult<&'a RemoteShard> {
+        remotes
+            .iter()
+            .find(|remote| self.peer_is_active(&remote.peer_id))
+            .ok_or_else(|| {
+                CollectionError::service_error(format!(
+                    "Shard {} has no active replicas",
+                    self.shard_id
+                ))
+            })
+    }
+
+    /// Sample recall estimation queries from the local replica
+    pub(crate) async fn sample_local_recall_queries(
+        &self,
+        filter: Option<Filter>,
+        sample: usize,
+    ) -> CollectionResult<Vec<RecallQuery>> {
+        let read_local = self.local.read().await;
+        match read_local.as_ref().and_then(Shard::local_shard) {
+            Some(local_shard) => local_shard.sample_recall_queries(filter, sample).await,
+            None => Err(CollectionError::service_error(format!(
+                "No local replica of shard {}",
+                self.shard_id
+            ))),
+        }
+    }
+
+    /// Measure recall of the approximate search in the local replica
+    pub(crate) async fn measure_local_recall(
+        &self,
+        queries: Arc<Vec<RecallQuery>>,
+        filter: Option<Filter>,
+        limit: usize,
+        params: Option<SearchParams>,
+    ) -> CollectionResult<ShardRecallMeasurement> {
+        let read_local = self.local.read().await;
+        match read_local.as_ref().and_then(Shard::local_shard) {
+            Some(local_shard) => {
+                local_shard
+                    .measure_recall(queries, filter, limit, params)
+                    .await
+            }
+            None => Err(CollectionError::service_error(format!(
+                "No local replica of shard {}",
+                self.shard_id
+            ))),
+        }
+    }
+
+    /// Digests of the points in the local replica, requested by other peers to compare replicas
+    pub(crate) async fn local_point_digests(
+        &self,
+        buckets: u32,
+        bucket: Option<u32>,
+    ) -> CollectionResult<Vec<PointDigest>> {
+        let read_local = self.local.read().await;
+        match read_local.as_ref().and_then(Shard::local_shard) {
+            Some(local_shard) => local_shard.point_digests(buckets, bucket).await,
+            None => Err(CollectionError::service_error(format!(
+                "No local replica of shard {}",
+                self.shard_id
+            ))),
+        }
+    }
+
+    /// Compare all active replicas of the shard and optionally repair the diverged points
+    pub(crate) async fn check_consistency(
+        &self,
+        repair: bool,
+    ) -> CollectionResult<ShardConsistencyReport> {
+        let read_local = self.local.read().await;
+        let read_remotes = self.remotes.read().await;
+        let this_peer_id = self.this_peer_id();
+
+        let mut replicas = Vec::new();
+        if let Some(local) = read_local.as_ref() {
+            if self.peer_is_active(&this_peer_id) {
+                replicas.push((this_peer_id, ReplicaRef::Local(local)));
+            }
+        }
+        for remote in read_remotes.iter() {
+            if self.peer_is_active(&remote.peer_id) {
+                replicas.push((remote.peer_id, ReplicaRef::Remote(remote)));
+            }
+        }
+        replicas.sort_by_key(|(peer_id, _)| *peer_id);
+
+        check_replicas(self.shard_id, &replicas, repair).await
+    }
+
+    /// Create a snapshot of the shard on an active remote replica and download it to `target_path`
+    ///
+    /// Returns the peer, which the snapshot was created on.
+    pub(crate) async fn download_remote_snapshot(
+        &self,
+        target_path: &Path,
+    ) -> CollectionResult<PeerId> {
+        let read_remotes = self.remotes.read().await;
+        let lagging_peers = self.lagging_peers();
+        let remote = read_remotes
+            .iter()
+            .filter(|remote| self.peer_is_active(&remote.peer_id))
+            .min_by_key(|remote| (lagging_peers.contains(&remote.peer_id), remote.peer_id))
+            .ok_or_else(|| {
+                CollectionError::service_error(format!(
+                    "No active replica of shard {} to create a snapshot from",
+                    self.shard_id
+                ))
+            })?;
+
+        let (snapshot_name, checksum) = remote.create_shard_snapshot().await?;
+        let mut downloaded = remote
+            .download_shard_snapshot(&snapshot_name, target_path)
+            .await;
+        if let (Ok(()), Some(checksum)) = (&downloaded, checksum) {
+            downloaded = snapshot_ops::verify_snapshot_checksum(target_path, &checksum).await;
+        }
+        if let Err(err) = remote.delete_shard_snapshot(&snapshot_name).await {
+            log::warn!(
+                "Failed to delete snapshot {snapshot_name} of shard {} on peer {}: {err}",
+                self.shard_id,
+                remote.peer_id
+            );
+        }
+        downloaded?;
+
+        Ok(remote.peer_id)
+    }
+
     pub(crate) async fn get_telemetry_data(&self) -> ReplicaSetTelemetry {
         let local_shard = self.local.read().await;
         let local = local_shard
@@ -1188,12 +1596,13 @@ impl ShardReplicaSet {
         temp_path: &Path,
         target_path: &Path,
         save_wal: bool,
+        base: Option<&ShardSnapshotManifest>,
     ) -> CollectionResult<()> {
         let local_read = self.local.read().await;
 
         if let Some(local) = &*local_read {
             local
-                .create_snapshot(temp_path, target_path, save_wal)
+                .create_snapshot(temp_path, target_path, save_wal, base)
                 .await?
         }
 
@@ -1206,6 +1615,25 @@ impl ShardReplicaSet {
     }
 
     pub async fn proxify_local(&self, remote_shard: RemoteShard) -> CollectionResult<()> {
+        self.proxify_local_with(remote_shard, None).await
+    }
+
+    /// Proxify local shard to forward points, which belong to the remote shard
+    /// according to the resharding hash ring.
+    pub async fn proxify_local_for_resharding(
+        &self,
+        remote_shard: RemoteShard,
+        resharding_ring: HashRing<ShardId>,
+    ) -> CollectionResult<()> {
+        self.proxify_local_with(remote_shard, Some(resharding_ring))
+            .await
+    }
+
+    async fn proxify_local_with(
+        &self,
+        remote_shard: RemoteShard,
+        resharding_ring: Option<HashRing<ShardId>>,
+    ) -> CollectionResult<()> {
         let mut local_write = self.local.write().await;
 
         match &*local_write {
@@ -1213,7 +1641,9 @@ impl ShardReplicaSet {
             Some(Local(_)) => {}
             // Unexpected states, error
             Some(ForwardProxy(proxy)) => {
-                return if proxy.remote_shard.peer_id == remote_shard.peer_id {
+                return if proxy.remote_shard.peer_id == remote_shard.peer_id
+                    && proxy.remote_shard.id == remote_shard.id
+                {
                     Ok(())
                 } else {
                     Err(CollectionError::service_error(format!(
@@ -1244,7 +1674,10 @@ impl ShardReplicaSet {
         };
 
         if let Some(Local(local)) = local_write.take() {
-            let proxy_shard = ForwardProxyShard::new(local, remote_shard);
+            let proxy_shard = match resharding_ring {
+                None => ForwardProxyShard::new(local, remote_shard),
+                Some(ring) => ForwardProxyShard::new_for_resharding(local, remote_shard, ring),
+            };
             let _ = local_write.insert(ForwardProxy(proxy_shard));
         }
 
@@ -1393,6 +1826,65 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Delete points of the local replica, which match the `predicate`.
+    ///
+    /// Used to drop points, which were migrated into another shard during resharding.
+    /// Returns the number of deleted points.
+    pub(crate) async fn delete_local_points_where(
+        &self,
+        predicate: impl Fn(&PointIdType) -> bool,
+    ) -> CollectionResult<usize> {
+        let mut ids = Vec::new();
+        {
+            let local = self.local.read().await;
+            let local_shard = match &*local {
+                None | Some(Dummy(_)) => return Ok(0),
+                Some(local_shard) => local_shard.get(),
+            };
+
+            let limit = DELETE_BATCH_SIZE + 1;
+            let mut offset = None;
+            loop {
+                let mut batch = local_shard
+                    .scroll_by(
+                        offset,
+                        limit,
+                        &WithPayloadInterface::Bool(false),
+                        &false.into(),
+                        None,
+                        &self.search_runtime,
+                    )
+                    .await?;
+
+                // Extra point is the first point of the next page
+                offset = if batch.len() < limit {
+                    None
+                } else {
+                    batch.pop().map(|point| point.id)
+                };
+
+                ids.extend(
+                    batch
+                        .into_iter()
+                        .map(|point| point.id)
+                        .filter(|point_id| predicate(point_id)),
+                );
+
+                if offset.is_none() {
+                    break;
+                }
+            }
+        }
+
+        let deleted = ids.len();
+        if deleted > 0 {
+            let operation =
+                CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints { ids });
+            self.update_local(operation, true).await?;
+        }
+        Ok(deleted)
+    }
+
     /// Custom operation for transferring data from one shard to another during transfer
     pub async fn transfer_batch(
         &self,
@@ -1621,6 +2113,7 @@ impl ShardReplicaSet {
                             .get()
                             .update(operation, local_wait)
                             .await
+                            .map(|res| (this_peer_id, res))
                             .map_err(|err| {
                                 let peer_id = err.remote_peer_id().unwrap_or(this_peer_id);
 
@@ -1639,6 +2132,7 @@ impl ShardReplicaSet {
                     remote
                         .update(operation, wait)
                         .await
+                        .map(|res| (remote.peer_id, res))
                         .map_err(|err| (remote.peer_id, err))
                 };
 
@@ -1717,11 +2211,18 @@ impl ShardReplicaSet {
                 return Err(err);
             }
         }
+        // Every replica numbers operations independently, so the token lists all of them
+        let mut consistency_token = ConsistencyToken::default();
+        for (peer_id, res) in &successes {
+            consistency_token.add(self.shard_id, *peer_id, res.operation_id);
+        }
+
         // there are enough successes, return the first one
-        let res = successes
+        let (_peer_id, mut res) = successes
             .into_iter()
             .next()
             .expect("successes is not empty");
+        res.consistency_token = Some(consistency_token);
         Ok(res)
     }
 
@@ -1817,6 +2318,38 @@ impl ShardReplicaSet {
         .await
     }
 
+    pub async fn core_search_with_profile(
+        &self,
+        request: Arc<CoreSearchRequestBatch>,
+        read_consistency: Option<ReadConsistency>,
+        local_only: bool,
+    ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, ShardSearchProfile)> {
+        let start = Instant::now();
+        let (result, segments) = self
+            .execute_and_resolve_read_operation(
+                |shard| {
+                    let request = request.clone();
+                    let search_runtime = self.search_runtime.clone();
+
+                    async move {
+                        shard
+                            .core_search_with_profile(request, &search_runtime)
+                            .await
+                    }
+                    .boxed()
+                },
+                read_consistency,
+                local_only,
+            )
+            .await?;
+        let profile = ShardSearchProfile {
+            shard_id: self.shard_id,
+            segments,
+            time: start.elapsed().as_secs_f64(),
+        };
+        Ok((result, profile))
+    }
+
     pub async fn count_local(
         &self,
         request: Arc<CountRequest>,
@@ -1912,6 +2445,8 @@ mod tests {
                 hnsw_config: None,
                 quantization_config: None,
                 on_disk: None,
+                disk_ann_config: None,
+                ivf_config: None,
             }),
             shard_number: NonZeroU32::new(4).unwrap(),
             replication_factor: NonZeroU32::new(3).unwrap(),
@@ -0,0 +1,386 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::future::Future;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Duration;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::time::sleep;
+
+use crate::common::stoppable_task_async::{spawn_async_stoppable, StoppableAsyncTaskHandle};
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::channel_service::ChannelService;
+use crate::shards::remote_shard::RemoteShard;
+use crate::shards::replica_set::ReplicaState;
+use crate::shards::shard::{PeerId, ShardId};
+use crate::shards::shard_holder::LockedShardHolder;
+use crate::shards::transfer::shard_transfer::{transfer_batches, ShardTransfer};
+use crate::shards::CollectionId;
+
+const RETRY_TIMEOUT: Duration = Duration::from_secs(1);
+const MAX_RETRY_COUNT: usize = 3;
+
+/// State of the resharding, which adds a new shard to the collection.
+///
+/// While resharding is in progress, the new shard is not a part of the hash ring.
+/// Each existing shard forwards points, which belong to the new shard according to the
+/// extended hash ring, from one of its replicas.
+/// Once all existing shards are migrated, the new shard is added to the hash ring
+/// and migrated points are deleted from the existing shards.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReshardingState {
+    /// ID of the new shard
+    pub shard_id: ShardId,
+    /// Peer, which holds the new shard
+    pub peer_id: PeerId,
+    /// Existing shards, which migrate points into the new shard,
+    /// along with the peers performing the migration
+    pub sources: BTreeMap<ShardId, PeerId>,
+    /// Existing shards, which have finished the migration
+    #[serde(default)]
+    pub migrated: BTreeSet<ShardId>,
+}
+
+impl ReshardingState {
+    pub fn new(shard_id: ShardId, peer_id: PeerId, sources: BTreeMap<ShardId, PeerId>) -> Self {
+        Self {
+            shard_id,
+            peer_id,
+            sources,
+            migrated: BTreeSet::new(),
+        }
+    }
+
+    /// Existing shards, which are migrated by the given peer
+    pub fn sources_of(&self, peer_id: PeerId) -> impl Iterator<Item = ShardId> + '_ {
+        self.sources
+            .iter()
+            .filter(move |(_, source_peer_id)| **source_peer_id == peer_id)
+            .map(|(shard_id, _)| *shard_id)
+    }
+
+    /// Existing shards, which are still migrating points into the new shard
+    pub fn pending_sources(&self) -> impl Iterator<Item = ShardId> + '_ {
+        self.sources
+            .keys()
+            .copied()
+            .filter(|shard_id| !self.migrated.contains(shard_id))
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.pending_sources().next().is_none()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct ReshardingInfo {
+    /// ID of the new shard
+    pub shard_id: ShardId,
+    /// Peer, which holds the new shard
+    pub peer_id: PeerId,
+    /// Existing shards, which are still migrating points into the new shard
+    pub pending_shards: Vec<ShardId>,
+}
+
+impl From<&ReshardingState> for ReshardingInfo {
+    fn from(state: &ReshardingState) -> Self {
+        Self {
+            shard_id: state.shard_id,
+            peer_id: state.peer_id,
+            pending_shards: state.pending_sources().collect(),
+        }
+    }
+}
+
+/// Confirms that the resharding makes sense with the current state of the collection
+///
+/// Checks:
+/// 1. There is no other resharding or shard transfer in progress
+/// 2. The new shard ID follows the existing ones
+/// 3. Every existing shard is migrated from the peer holding its active replica
+///
+/// If validation fails, return `BadRequest` error.
+pub fn validate_resharding(
+    resharding: &ReshardingState,
+    shard_number: u32,
+    shards: &HashMap<ShardId, HashMap<PeerId, ReplicaState>>,
+    current_transfers: &HashSet<ShardTransfer>,
+    current_resharding: Option<&ReshardingState>,
+) -> CollectionResult<()> {
+    if let Some(current_resharding) = current_resharding {
+        return Err(CollectionError::bad_request(format!(
+            "Collection is already being resharded into shard {}",
+            current_resharding.shard_id
+        )));
+    }
+
+    if !current_transfers.is_empty() {
+        return Err(CollectionError::bad_request(
+            "Can't reshard collection while shard transfers are in progress".to_string(),
+        ));
+    }
+
+    if resharding.shard_id != shard_number || shards.contains_key(&resharding.shard_id) {
+        return Err(CollectionError::bad_request(format!(
+            "Shard {} can't be added to collection with {shard_number} shards",
+            resharding.shard_id
+        )));
+    }
+
+    for (shard_id, replicas) in shards {
+        let Some(peer_id) = resharding.sources.get(shard_id) else {
+            return Err(CollectionError::bad_request(format!(
+                "Shard {shard_id} is not migrated during resharding"
+            )));
+        };
+
+        if replicas.get(peer_id) != Some(&ReplicaState::Active) {
+            return Err(CollectionError::bad_request(format!(
+                "Shard {shard_id} is not active on peer {peer_id}"
+            )));
+        }
+    }
+
+    if resharding.sources.len() != shards.len() {
+        return Err(CollectionError::bad_request(
+            "Resharding refers to shards, which don't exist".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Selects the best peer to create the new shard on.
+///
+/// Prefers the peer with the lowest number of shard replicas.
+pub fn suggest_peer_for_new_shard(
+    all_peers: &HashSet<PeerId>,
+    shards: &HashMap<ShardId, HashMap<PeerId, ReplicaState>>,
+) -> Option<PeerId> {
+    let mut peer_loads: HashMap<PeerId, usize> =
+        all_peers.iter().map(|peer_id| (*peer_id, 0)).collect();
+    for replicas in shards.values() {
+        for peer_id in replicas.keys() {
+            if let Some(load) = peer_loads.get_mut(peer_id) {
+                *load += 1;
+            }
+        }
+    }
+
+    peer_loads
+        .into_iter()
+        .min_by_key(|(peer_id, load)| (*load, *peer_id))
+        .map(|(peer_id, _)| peer_id)
+}
+
+/// Selects a peer to migrate points from, for each of the existing shards.
+///
+/// Requirements:
+/// 1. Peer should have an active replica of the shard
+/// 2. Prefer peer with the lowest number of migrations assigned
+///
+/// Returns `None` if some shard has no active replicas.
+pub fn suggest_migration_sources(
+    shards: &HashMap<ShardId, HashMap<PeerId, ReplicaState>>,
+) -> Option<BTreeMap<ShardId, PeerId>> {
+    let mut sources = BTreeMap::new();
+    let mut migration_counts: HashMap<PeerId, usize> = HashMap::new();
+
+    let mut shard_ids: Vec<_> = shards.keys().copied().collect();
+    shard_ids.sort_unstable();
+
+    for shard_id in shard_ids {
+        let peer_id = shards[&shard_id]
+            .iter()
+            .filter(|(_, state)| **state == ReplicaState::Active)
+            .map(|(peer_id, _)| *peer_id)
+            .min_by_key(|peer_id| {
+                (
+                    migration_counts.get(peer_id).copied().unwrap_or(0),
+                    *peer_id,
+                )
+            })?;
+
+        *migration_counts.entry(peer_id).or_insert(0) += 1;
+        sources.insert(shard_id, peer_id);
+    }
+
+    Some(sources)
+}
+
+/// Forward points of the shard, which belong to the new shard, to the peer holding it
+async fn migrate_points(
+    shard_holder: Arc<LockedShardHolder>,
+    shard_id: ShardId,
+    collection_id: CollectionId,
+    resharding: &ReshardingState,
+    channel_service: ChannelService,
+    stopped: Arc<AtomicBool>,
+) -> CollectionResult<()> {
+    let remote_shard = RemoteShard::new(
+        resharding.shard_id,
+        collection_id,
+        resharding.peer_id,
+        channel_service,
+    );
+
+    {
+        let shard_holder_guard = shard_holder.read().await;
+
+        let Some(ring) = shard_holder_guard.resharding_ring() else {
+            return Err(CollectionError::service_error(format!(
+                "Points of shard {shard_id} cannot be migrated, resharding is not in progress"
+            )));
+        };
+
+        let Some(replica_set) = shard_holder_guard.get_shard(&shard_id) else {
+            return Err(CollectionError::service_error(format!(
+                "Shard {shard_id} cannot be proxied because it does not exist"
+            )));
+        };
+
+        replica_set
+            .proxify_local_for_resharding(remote_shard, ring)
+            .await?;
+    }
+
+    // Migrate matching points batch by batch
+    transfer_batches(shard_holder.clone(), shard_id, stopped).await
+}
+
+pub fn spawn_resharding_task<T, F>(
+    shards_holder: Arc<LockedShardHolder>,
+    shard_id: ShardId,
+    resharding: ReshardingState,
+    collection_id: CollectionId,
+    channel_service: ChannelService,
+    on_finish: T,
+    on_error: F,
+) -> StoppableAsyncTaskHandle<bool>
+where
+    T: Future<Output = ()> + Send + 'static,
+    F: Future<Output = ()> + Send + 'static,
+{
+    spawn_async_stoppable(move |stopped| async move {
+        let mut tries = MAX_RETRY_COUNT;
+        let mut finished = false;
+        while !finished && tries > 0 {
+            let migration_result = migrate_points(
+                shards_holder.clone(),
+                shard_id,
+                collection_id.clone(),
+                &resharding,
+                channel_service.clone(),
+                stopped.clone(),
+            )
+            .await;
+            finished = match migration_result {
+                Ok(()) => true,
+                Err(error) => {
+                    if matches!(error, CollectionError::Cancelled { .. }) {
+                        return false;
+                    }
+                    log::error!(
+                        "Failed to migrate points of shard {} into shard {}: {}",
+                        shard_id,
+                        resharding.shard_id,
+                        error
+                    );
+                    false
+                }
+            };
+            if stopped.load(std::sync::atomic::Ordering::Relaxed) {
+                return false;
+            }
+            if !finished {
+                tries -= 1;
+                log::warn!(
+                    "Retrying migration of shard {} into shard {} (retry {})",
+                    shard_id,
+                    resharding.shard_id,
+                    MAX_RETRY_COUNT - tries
+                );
+                let exp_timeout = RETRY_TIMEOUT * (MAX_RETRY_COUNT - tries) as u32;
+                sleep(exp_timeout).await;
+            }
+        }
+
+        if finished {
+            on_finish.await;
+        } else {
+            on_error.await;
+        }
+        finished
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replicas(
+        replicas: &[(ShardId, &[(PeerId, ReplicaState)])],
+    ) -> HashMap<ShardId, HashMap<PeerId, ReplicaState>> {
+        replicas
+            .iter()
+            .map(|(shard_id, peers)| (*shard_id, peers.iter().copied().collect()))
+            .collect()
+    }
+
+    #[test]
+    fn test_suggest_migration_sources() {
+        let shards = replicas(&[
+            (0, &[(1, ReplicaState::Active), (2, ReplicaState::Active)]),
+            (1, &[(1, ReplicaState::Active), (2, ReplicaState::Active)]),
+            (2, &[(1, ReplicaState::Active), (2, ReplicaState::Dead)]),
+        ]);
+
+        let sources = suggest_migration_sources(&shards).unwrap();
+        assert_eq!(sources, BTreeMap::from([(0, 1), (1, 2), (2, 1)]));
+
+        let resharding = ReshardingState::new(3, 2, sources);
+        assert!(validate_resharding(&resharding, 3, &shards, &HashSet::new(), None).is_ok());
+        assert_eq!(resharding.sources_of(1).collect::<Vec<_>>(), vec![0, 2]);
+
+        let mut migrating = resharding.clone();
+        migrating.migrated.extend([0, 2]);
+        assert_eq!(migrating.pending_sources().collect::<Vec<_>>(), vec![1]);
+        assert!(!migrating.is_finished());
+        migrating.migrated.insert(1);
+        assert!(migrating.is_finished());
+
+        // Shard 2 has no active replica on peer 2
+        let mut invalid = resharding.clone();
+        invalid.sources.insert(2, 2);
+        assert!(validate_resharding(&invalid, 3, &shards, &HashSet::new(), None).is_err());
+
+        // New shard must follow the existing ones
+        let mut invalid = resharding.clone();
+        invalid.shard_id = 4;
+        assert!(validate_resharding(&invalid, 3, &shards, &HashSet::new(), None).is_err());
+
+        // Only one resharding at a time
+        assert!(
+            validate_resharding(&resharding, 3, &shards, &HashSet::new(), Some(&resharding))
+                .is_err()
+        );
+
+        let dead = replicas(&[(0, &[(1, ReplicaState::Dead)])]);
+        assert!(suggest_migration_sources(&dead).is_none());
+    }
+
+    #[test]
+    fn test_suggest_peer_for_new_shard() {
+        let shards = replicas(&[
+            (0, &[(1, ReplicaState::Active), (2, ReplicaState::Active)]),
+            (1, &[(1, ReplicaState::Active)]),
+        ]);
+
+        let all_peers = HashSet::from([1, 2]);
+        assert_eq!(suggest_peer_for_new_shard(&all_peers, &shards), Some(2));
+
+        let all_peers = HashSet::from([1, 2, 3]);
+        assert_eq!(suggest_peer_for_new_shard(&all_peers, &shards), Some(3));
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::hash;
 
+use segment::data_types::search_profile::SegmentSearchProfile;
 use segment::types::{Payload, ScoredPoint};
 use tinyvec::TinyVec;
 
@@ -44,6 +45,17 @@ impl Resolve for Vec<Vec<ScoredPoint>> {
     }
 }
 
+/// Search results are resolved as usual, profiles of all replicas are kept
+impl Resolve for (Vec<Vec<ScoredPoint>>, Vec<SegmentSearchProfile>) {
+    fn resolve(responses: Vec<Self>, condition: ResolveCondition) -> Self {
+        let (batches, profiles): (Vec<_>, Vec<_>) = responses.into_iter().unzip();
+        (
+            Resolve::resolve(batches, condition),
+            profiles.into_iter().flatten().collect(),
+        )
+    }
+}
+
 fn transpose<T>(vec: Vec<Vec<T>>) -> Vec<Vec<T>> {
     if vec.is_empty() {
         return Vec::new();
@@ -10,6 +10,7 @@ use crate::shards::local_shard::LocalShard;
 use crate::shards::proxy_shard::ProxyShard;
 use crate::shards::queue_proxy_shard::QueueProxyShard;
 use crate::shards::shard_trait::ShardOperation;
+use crate::shards::snapshot_manifest::ShardSnapshotManifest;
 use crate::shards::telemetry::LocalShardTelemetry;
 
 pub type ShardId = u32;
@@ -50,6 +51,17 @@ impl Shard {
         }
     }
 
+    /// Local shard holding the data, possibly wrapped by a proxy
+    pub fn local_shard(&self) -> Option<&LocalShard> {
+        match self {
+            Shard::Local(local_shard) => Some(local_shard),
+            Shard::Proxy(proxy_shard) => Some(&proxy_shard.wrapped_shard),
+            Shard::ForwardProxy(proxy_shard) => Some(&proxy_shard.wrapped_shard),
+            Shard::QueueProxy(proxy_shard) => Some(&proxy_shard.wrapped_shard),
+            Shard::Dummy(_) => None,
+        }
+    }
+
     pub fn get_telemetry_data(&self) -> LocalShardTelemetry {
         let mut telemetry = match self {
             Shard::Local(local_shard) => local_shard.get_telemetry_data(),
@@ -67,31 +79,32 @@ impl Shard {
         temp_path: &Path,
         target_path: &Path,
         save_wal: bool,
+        base: Option<&ShardSnapshotManifest>,
     ) -> CollectionResult<()> {
         match self {
             Shard::Local(local_shard) => {
                 local_shard
-                    .create_snapshot(temp_path, target_path, save_wal)
+                    .create_snapshot(temp_path, target_path, save_wal, base)
                     .await
             }
             Shard::Proxy(proxy_shard) => {
                 proxy_shard
-                    .create_snapshot(temp_path, target_path, save_wal)
+                    .create_snapshot(temp_path, target_path, save_wal, base)
                     .await
             }
             Shard::ForwardProxy(proxy_shard) => {
                 proxy_shard
-                    .create_snapshot(temp_path, target_path, save_wal)
+                    .create_snapshot(temp_path, target_path, save_wal, base)
                     .await
             }
             Shard::QueueProxy(proxy_shard) => {
                 proxy_shard
-                    .create_snapshot(temp_path, target_path, save_wal)
+                    .create_snapshot(temp_path, target_path, save_wal, base)
                     .await
             }
             Shard::Dummy(dummy_shard) => {
                 dummy_shard
-                    .create_snapshot(temp_path, target_path, save_wal)
+                    .create_snapshot(temp_path, target_path, save_wal, base)
                     .await
             }
         }
@@ -123,7 +136,7 @@ impl Shard {
         }
     }
 
-    fn update_tracker(&self) -> Option<&UpdateTracker> {
+    pub fn update_tracker(&self) -> Option<&UpdateTracker> {
         let update_tracker = match self {
             Self::Local(local_shard) => local_shard.update_tracker(),
             Self::Proxy(proxy_shard) => proxy_shard.update_tracker(),
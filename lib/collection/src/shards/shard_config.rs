@@ -19,6 +19,9 @@ pub enum ShardType {
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 pub struct ShardConfig {
     pub r#type: ShardType,
+    /// Optimizers of the local shard are paused and don't start new optimizations
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optimizers_paused: bool,
 }
 
 impl ShardConfig {
@@ -29,6 +32,7 @@ impl ShardConfig {
     pub fn new_replica_set() -> Self {
         Self {
             r#type: ShardType::ReplicaSet,
+            optimizers_paused: false,
         }
     }
 
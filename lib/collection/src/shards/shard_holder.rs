@@ -2,6 +2,8 @@ use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use segment::types::{PointIdType, ScoredPoint};
+use segment::utils::tar::HashingWriter;
 use tar::Builder as TarBuilder;
 use tokio::runtime::Handle;
 use tokio::sync::RwLock;
@@ -11,7 +13,8 @@ use crate::config::CollectionConfig;
 use crate::hash_ring::HashRing;
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::snapshot_ops::{
-    get_snapshot_description, list_snapshots_in_directory, SnapshotDescription,
+    get_snapshot_description, list_snapshots_in_directory, write_checksum_for_snapshot,
+    SnapshotDescription,
 };
 use crate::operations::types::{CollectionError, CollectionResult, ShardTransferInfo};
 use crate::operations::{OperationToShard, SplitByShard};
@@ -19,18 +22,27 @@ use crate::save_on_disk::SaveOnDisk;
 use crate::shards::channel_service::ChannelService;
 use crate::shards::local_shard::LocalShard;
 use crate::shards::replica_set::{ChangePeerState, ReplicaState, ShardReplicaSet}; // TODO rename ReplicaShard to ReplicaSetShard
+use crate::shards::resharding::ReshardingState;
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_config::{ShardConfig, ShardType};
 use crate::shards::shard_versioning::latest_shard_paths;
+use crate::shards::snapshot_manifest::ShardSnapshotManifest;
 use crate::shards::transfer::shard_transfer::{ShardTransfer, ShardTransferKey};
-use crate::shards::CollectionId;
+use crate::shards::{CollectionId, HASH_RING_SHARD_SCALE};
 
 const SHARD_TRANSFERS_FILE: &str = "shard_transfers";
+const RESHARDING_STATE_FILE: &str = "resharding_state";
 
 pub struct ShardHolder {
     shards: HashMap<ShardId, ShardReplicaSet>,
     pub(crate) shard_transfers: SaveOnDisk<HashSet<ShardTransfer>>,
+    /// Resharding in progress, if any.
+    /// The new shard is present in `shards`, but not in the `ring` until resharding is finished.
+    pub(crate) resharding_state: SaveOnDisk<Option<ReshardingState>>,
     ring: HashRing<ShardId>,
+    /// Shards, which may still contain points migrated into another shard by the resharding.
+    /// Reads drop points of these shards, which they don't own according to the `ring`.
+    foreign_points_shards: parking_lot::RwLock<HashSet<ShardId>>,
 }
 
 pub type LockedShardHolder = RwLock<ShardHolder>;
@@ -38,10 +50,14 @@ pub type LockedShardHolder = RwLock<ShardHolder>;
 impl ShardHolder {
     pub fn new(collection_path: &Path, hashring: HashRing<ShardId>) -> CollectionResult<Self> {
         let shard_transfers = SaveOnDisk::load_or_init(collection_path.join(SHARD_TRANSFERS_FILE))?;
+        let resharding_state =
+            SaveOnDisk::load_or_init(collection_path.join(RESHARDING_STATE_FILE))?;
         Ok(Self {
             shards: HashMap::new(),
             shard_transfers,
+            resharding_state,
             ring: hashring,
+            foreign_points_shards: Default::default(),
         })
     }
 
@@ -94,6 +110,17 @@ impl ShardHolder {
         self.shards.values()
     }
 
+    /// Shards, which are part of the hash ring.
+    ///
+    /// Excludes the shard, which is being filled by the resharding.
+    fn routed_shards(&self) -> impl Iterator<Item = &ShardReplicaSet> {
+        let resharding_shard_id = self.resharding_shard_id();
+        self.shards
+            .iter()
+            .filter(move |(shard_id, _)| Some(**shard_id) != resharding_shard_id)
+            .map(|(_, replica_set)| replica_set)
+    }
+
     pub fn split_by_shard<O: SplitByShard + Clone>(
         &self,
         operation: O,
@@ -105,13 +132,137 @@ impl ShardHolder {
                 .map(|(shard_id, operation)| (self.shards.get(&shard_id).unwrap(), operation))
                 .collect(),
             OperationToShard::ToAll(operation) => self
-                .all_shards()
+                .routed_shards()
                 .map(|shard| (shard, operation.clone()))
                 .collect(),
         };
         shard_ops
     }
 
+    pub fn resharding_state(&self) -> Option<ReshardingState> {
+        self.resharding_state.read().clone()
+    }
+
+    fn resharding_shard_id(&self) -> Option<ShardId> {
+        self.resharding_state
+            .read()
+            .as_ref()
+            .map(|resharding| resharding.shard_id)
+    }
+
+    /// Hash ring, which includes the shard being filled by the resharding.
+    ///
+    /// Returns `None` if resharding is not in progress.
+    pub fn resharding_ring(&self) -> Option<HashRing<ShardId>> {
+        if self.resharding_shard_id().is_none() {
+            return None;
+        }
+        // The new shard is already registered among the `shards`
+        let mut ring = HashRing::fair(HASH_RING_SHARD_SCALE);
+        for shard_id in self.shards.keys() {
+            ring.add(*shard_id);
+        }
+        Some(ring)
+    }
+
+    /// Register the new shard of the resharding.
+    ///
+    /// The shard doesn't receive any requests routed by the hash ring until resharding is finished.
+    pub fn register_start_resharding(
+        &mut self,
+        resharding: ReshardingState,
+        replica_set: ShardReplicaSet,
+    ) -> CollectionResult<()> {
+        let shard_id = resharding.shard_id;
+        self.resharding_state
+            .write(|state| *state = Some(resharding))?;
+        self.shards.insert(shard_id, replica_set);
+        Ok(())
+    }
+
+    /// Mark points of the shard as migrated into the new shard.
+    ///
+    /// Returns `true` if all shards are migrated.
+    pub fn register_finish_migration(&self, shard_id: ShardId) -> CollectionResult<bool> {
+        Ok(self.resharding_state.write(|state| match state {
+            Some(resharding) => {
+                resharding.migrated.insert(shard_id);
+                resharding.is_finished()
+            }
+            None => false,
+        })?)
+    }
+
+    /// Add the new shard to the hash ring and forget the resharding.
+    pub fn register_finish_resharding(&mut self) -> CollectionResult<Option<ReshardingState>> {
+        let resharding = self.resharding_state.write(|state| state.take())?;
+        if let Some(resharding) = &resharding {
+            self.ring.add(resharding.shard_id);
+            self.foreign_points_shards
+                .write()
+                .extend(resharding.sources.keys().copied());
+        }
+        Ok(resharding)
+    }
+
+    /// Forget the resharding and take out the new shard.
+    pub fn register_abort_resharding(
+        &mut self,
+    ) -> CollectionResult<Option<(ReshardingState, Option<ShardReplicaSet>)>> {
+        let resharding = self.resharding_state.write(|state| state.take())?;
+        Ok(resharding.map(|resharding| {
+            let replica_set = self.shards.remove(&resharding.shard_id);
+            (resharding, replica_set)
+        }))
+    }
+
+    /// Delete points from the local replica of the shard, which belong to other shards
+    /// according to the hash ring.
+    ///
+    /// Returns the number of deleted points.
+    pub async fn delete_foreign_points(&self, shard_id: ShardId) -> CollectionResult<usize> {
+        let replica_set = self
+            .get_shard(&shard_id)
+            .ok_or_else(|| shard_not_found_error(shard_id))?;
+        let deleted = replica_set
+            .delete_local_points_where(|point_id| !self.is_owned_point(shard_id, point_id))
+            .await?;
+        self.foreign_points_shards.write().remove(&shard_id);
+        Ok(deleted)
+    }
+
+    /// Whether the shard may still contain points, which belong to other shards
+    pub fn has_foreign_points(&self, shard_id: ShardId) -> bool {
+        self.foreign_points_shards.read().contains(&shard_id)
+    }
+
+    /// Whether the point belongs to the shard according to the hash ring
+    pub fn is_owned_point(&self, shard_id: ShardId, point_id: &PointIdType) -> bool {
+        self.ring.get(point_id) == Some(&shard_id)
+    }
+
+    /// Drop points, which were returned by the shard, but belong to other shards
+    ///
+    /// Source shards of the resharding keep the migrated points until they are cleaned up,
+    /// so the points would be returned twice otherwise.
+    pub fn retain_owned_points<T>(
+        &self,
+        shard_id: ShardId,
+        points: &mut Vec<T>,
+        point_id: impl Fn(&T) -> PointIdType,
+    ) {
+        if self.has_foreign_points(shard_id) {
+            points.retain(|point| self.is_owned_point(shard_id, &point_id(point)));
+        }
+    }
+
+    /// Same as [`ShardHolder::retain_owned_points`], for every search of the batch
+    pub fn retain_owned_search_results(&self, shard_id: ShardId, results: &mut [Vec<ScoredPoint>]) {
+        for points in results {
+            self.retain_owned_points(shard_id, points, |point| point.id);
+        }
+    }
+
     pub fn register_start_shard_transfer(&self, transfer: ShardTransfer) -> CollectionResult<bool> {
         Ok(self
             .shard_transfers
@@ -163,7 +314,7 @@ impl ShardHolder {
         shard_selection: Option<ShardId>,
     ) -> CollectionResult<Vec<&ShardReplicaSet>> {
         match shard_selection {
-            None => Ok(self.all_shards().collect()),
+            None => Ok(self.routed_shards().collect()),
             Some(shard_selection) => {
                 let shard_opt = self.get_shard(&shard_selection);
                 let shards = match shard_opt {
@@ -197,8 +348,9 @@ impl ShardHolder {
         search_runtime: Handle,
     ) {
         let shard_number = collection_config.read().await.params.shard_number.get();
+        let resharding_shard_id = self.resharding_shard_id();
         // ToDo: remove after version 0.11.0
-        for shard_id in 0..shard_number {
+        for shard_id in (0..shard_number).chain(resharding_shard_id) {
             for (path, _shard_version, shard_type) in
                 latest_shard_paths(collection_path, shard_id).await.unwrap()
             {
@@ -288,7 +440,12 @@ impl ShardHolder {
                         .expect("Failed to set local shard state");
                 }
 
-                self.add_shard(shard_id, replica_set);
+                if Some(shard_id) == resharding_shard_id {
+                    // Shard is not routed until resharding is finished
+                    self.shards.insert(shard_id, replica_set);
+                } else {
+                    self.add_shard(shard_id, replica_set);
+                }
             }
         }
     }
@@ -422,8 +579,15 @@ impl ShardHolder {
             .tempdir_in(temp_dir)?;
 
         shard
-            .create_snapshot(snapshot_temp_dir.path(), snapshot_target_dir.path(), false)
+            .create_snapshot(
+                snapshot_temp_dir.path(),
+                snapshot_target_dir.path(),
+                false,
+                None,
+            )
             .await?;
+        // Shard snapshots are always complete, the manifest is not needed
+        ShardSnapshotManifest::take(snapshot_target_dir.path())?;
 
         if let Err(err) = snapshot_temp_dir.close() {
             log::error!("Failed to remove temporary directory: {err}");
@@ -437,12 +601,11 @@ impl ShardHolder {
             let snapshot_target_dir = snapshot_target_dir.path().to_path_buf();
 
             tokio::task::spawn_blocking(move || -> CollectionResult<_> {
-                let mut tar = TarBuilder::new(temp_file.as_file_mut());
+                let mut tar = TarBuilder::new(HashingWriter::new(temp_file.as_file_mut()));
                 tar.append_dir_all(".", &snapshot_target_dir)?;
-                tar.finish()?;
-                drop(tar);
+                let checksum = tar.into_inner()?.checksum();
 
-                Ok(temp_file)
+                Ok((temp_file, checksum))
             })
         };
 
@@ -452,7 +615,7 @@ impl ShardHolder {
             log::error!("Failed to remove temporary directory: {err}");
         }
 
-        let temp_file = task_result??;
+        let (temp_file, checksum) = task_result??;
 
         let snapshot_path =
             self.shard_snapshot_path_unchecked(snapshots_path, shard_id, snapshot_file_name)?;
@@ -463,6 +626,7 @@ impl ShardHolder {
             }
         }
 
+        write_checksum_for_snapshot(&snapshot_path, &checksum).await?;
         move_file(temp_file.path(), &snapshot_path).await?;
 
         get_snapshot_description(&snapshot_path).await
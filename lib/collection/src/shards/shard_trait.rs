@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use segment::data_types::search_profile::SegmentSearchProfile;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -49,6 +50,13 @@ pub trait ShardOperation {
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>>;
 
+    /// Same as `core_search`, but also returns profiles of searches performed in every segment
+    async fn core_search_with_profile(
+        &self,
+        request: Arc<CoreSearchRequestBatch>,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<SegmentSearchProfile>)>;
+
     async fn count(&self, request: Arc<CountRequest>) -> CollectionResult<CountResult>;
 
     async fn retrieve(
@@ -0,0 +1,243 @@
+use std::collections::{BTreeMap, HashSet};
+use std::ffi::OsStr;
+use std::fs::File;
+use std::path::{Component, Path, PathBuf};
+
+use io::file_operations::{atomic_save_json, read_json};
+use segment::types::SeqNumberType;
+use segment::utils::tar::file_checksum;
+use serde::{Deserialize, Serialize};
+
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::local_shard::LocalShard;
+use crate::shards::shard::ShardId;
+use crate::shards::shard_versioning::versioned_shard_path;
+
+/// Name of the manifest file in the snapshot.
+/// In collection snapshots it is the first entry of the archive, so it can be read cheaply.
+pub const SNAPSHOT_MANIFEST_FILE: &str = "snapshot_manifest.json";
+
+/// Version of each segment, by segment ID
+pub type SegmentVersions = BTreeMap<String, SeqNumberType>;
+
+/// Content of a shard snapshot.
+///
+/// Incremental snapshots do not contain segments and WAL files, which are unchanged
+/// compared to the base snapshot, but they are still listed in the manifest.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShardSnapshotManifest {
+    /// Version of each segment at the moment of the snapshot
+    pub segments: SegmentVersions,
+    /// Checksum of each WAL file, by file name
+    pub wal: BTreeMap<String, String>,
+}
+
+impl ShardSnapshotManifest {
+    fn get_manifest_path(snapshot_shard_path: &Path) -> PathBuf {
+        snapshot_shard_path.join(SNAPSHOT_MANIFEST_FILE)
+    }
+
+    /// Build the manifest of the shard snapshot in `snapshot_shard_path`
+    /// and remove WAL files, which are the same in the `base` snapshot.
+    ///
+    /// This method performs blocking IO.
+    pub fn create(
+        snapshot_shard_path: &Path,
+        segments: SegmentVersions,
+        base: Option<&ShardSnapshotManifest>,
+    ) -> CollectionResult<Self> {
+        let mut wal = BTreeMap::new();
+
+        for entry in std::fs::read_dir(LocalShard::wal_path(snapshot_shard_path))? {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            let checksum = file_checksum(&entry.path())?;
+
+            if base.and_then(|base| base.wal.get(&file_name)) == Some(&checksum) {
+                std::fs::remove_file(entry.path())?;
+            }
+            wal.insert(file_name, checksum);
+        }
+
+        let manifest = Self { segments, wal };
+        atomic_save_json(&Self::get_manifest_path(snapshot_shard_path), &manifest)?;
+        Ok(manifest)
+    }
+
+    /// Read and remove the manifest from the shard snapshot,
+    /// `None` if the shard has no local data in the snapshot.
+    pub fn take(snapshot_shard_path: &Path) -> CollectionResult<Option<Self>> {
+        let manifest_path = Self::get_manifest_path(snapshot_shard_path);
+        if !manifest_path.exists() {
+            return Ok(None);
+        }
+        let manifest = read_json(&manifest_path)?;
+        std::fs::remove_file(&manifest_path)?;
+        Ok(Some(manifest))
+    }
+
+    /// Files of the shard snapshot, relative to the shard directory
+    fn files(&self) -> impl Iterator<Item = PathBuf> + '_ {
+        let segments = self
+            .segments
+            .keys()
+            .map(|segment_id| Path::new("segments").join(format!("{segment_id}.tar")));
+        let wal = self
+            .wal
+            .keys()
+            .map(|file_name| LocalShard::wal_path(Path::new("")).join(file_name));
+        segments.chain(wal)
+    }
+}
+
+/// Snapshot, which an incremental snapshot is based on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotBase {
+    /// File name of the base snapshot, expected to be next to the incremental one
+    pub name: String,
+    /// Checksum of the base snapshot file
+    pub checksum: String,
+}
+
+/// Content of a collection snapshot
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    /// Set for incremental snapshots
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<SnapshotBase>,
+    pub shards: BTreeMap<ShardId, ShardSnapshotManifest>,
+}
+
+impl SnapshotManifest {
+    /// Read the manifest from the collection snapshot archive,
+    /// `None` for snapshots created without it.
+    ///
+    /// This method performs blocking IO.
+    pub fn read_from_archive(snapshot_path: &Path) -> CollectionResult<Option<Self>> {
+        let mut archive = tar::Archive::new(File::open(snapshot_path)?);
+        // Manifest is always the first entry
+        let Some(entry) = archive.entries_with_seek()?.next() else {
+            return Ok(None);
+        };
+        let entry = entry?;
+        if normalize_path(&entry.path()?) != Path::new(SNAPSHOT_MANIFEST_FILE) {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_reader(entry)?))
+    }
+
+    /// Files of the snapshot, which are listed in the manifest, relative to the collection directory
+    fn files(&self) -> HashSet<PathBuf> {
+        self.shards
+            .iter()
+            .flat_map(|(shard_id, shard)| {
+                let shard_path = versioned_shard_path(Path::new(""), *shard_id, 0);
+                shard.files().map(move |file| shard_path.join(file))
+            })
+            .collect()
+    }
+}
+
+/// Unpack the collection snapshot into `target_dir`.
+///
+/// If the snapshot is incremental, files which it does not contain are extracted from the chain
+/// of its base snapshots. Base snapshots are expected to be in the same directory.
+///
+/// This method performs blocking IO.
+pub fn unpack_snapshot(snapshot_path: &Path, target_dir: &Path) -> CollectionResult<()> {
+    let manifest = SnapshotManifest::read_from_archive(snapshot_path)?;
+
+    let mut archive = tar::Archive::new(File::open(snapshot_path)?);
+    archive.unpack(target_dir)?;
+
+    let Some(manifest) = manifest else {
+        return Ok(());
+    };
+    std::fs::remove_file(target_dir.join(SNAPSHOT_MANIFEST_FILE))?;
+
+    let mut missing: HashSet<_> = manifest
+        .files()
+        .into_iter()
+        .filter(|file| !target_dir.join(file).exists())
+        .collect();
+
+    let mut next_base = manifest.base;
+
+    while let Some(base) = next_base.take() {
+        if missing.is_empty() {
+            break;
+        }
+
+        if Path::new(&base.name).file_name() != Some(OsStr::new(&base.name)) {
+            return Err(CollectionError::bad_input(format!(
+                "Invalid base snapshot name {}",
+                base.name,
+            )));
+        }
+
+        let base_path = snapshot_path.with_file_name(&base.name);
+        if !base_path.exists() {
+            return Err(CollectionError::bad_input(format!(
+                "Base snapshot {} is not found, it is required to recover snapshot {}",
+                base.name,
+                snapshot_path.display(),
+            )));
+        }
+
+        let checksum = file_checksum(&base_path)?;
+        if !checksum.eq_ignore_ascii_case(&base.checksum) {
+            return Err(CollectionError::bad_input(format!(
+                "Base snapshot {} checksum mismatch: expected {}, got {checksum}",
+                base.name, base.checksum,
+            )));
+        }
+
+        log::debug!(
+            "Extracting {} unchanged files from base snapshot {}",
+            missing.len(),
+            base.name,
+        );
+
+        let mut archive = tar::Archive::new(File::open(&base_path)?);
+        for entry in archive.entries_with_seek()? {
+            let mut entry = entry?;
+            let path = normalize_path(&entry.path()?);
+
+            if path == Path::new(SNAPSHOT_MANIFEST_FILE) {
+                let base_manifest: SnapshotManifest = serde_json::from_reader(&mut entry)?;
+                next_base = base_manifest.base;
+            } else if missing.remove(&path) {
+                let target_path = target_dir.join(&path);
+                if let Some(parent) = target_path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                entry.unpack(target_path)?;
+            }
+        }
+    }
+
+    if !missing.is_empty() {
+        let mut missing: Vec<_> = missing
+            .iter()
+            .map(|file| file.display().to_string())
+            .collect();
+        missing.sort_unstable();
+        return Err(CollectionError::bad_input(format!(
+            "Snapshot {} is incomplete, files are missing in it and its base snapshots: {}",
+            snapshot_path.display(),
+            missing.join(", "),
+        )));
+    }
+
+    Ok(())
+}
+
+/// Path of the archive entry without `.` components
+fn normalize_path(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect()
+}
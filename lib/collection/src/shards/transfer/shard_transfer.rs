@@ -12,6 +12,7 @@ use tokio::time::sleep;
 use crate::common::stoppable_task_async::{spawn_async_stoppable, StoppableAsyncTaskHandle};
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::shards::channel_service::ChannelService;
+use crate::shards::peer_labels::{shared_labels_count, PeerLabelsById};
 use crate::shards::remote_shard::RemoteShard;
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::shard::{PeerId, ShardId};
@@ -56,7 +57,7 @@ impl ShardTransfer {
     }
 }
 
-async fn transfer_batches(
+pub(crate) async fn transfer_batches(
     shard_holder: Arc<LockedShardHolder>,
     shard_id: ShardId,
     stopped: Arc<AtomicBool>,
@@ -384,10 +385,12 @@ pub fn suggest_transfer_source(
 ///
 /// Requirements:
 /// 1. Peer should not have an active replica of the shard
-/// 2. Peer should have minimal number of active transfers
+/// 2. Peer should share the least labels with the peers, which already have the shard
+/// 3. Peer should have minimal number of active transfers
 pub fn suggest_peer_to_add_replica(
     shard_id: ShardId,
     shard_distribution: HashMap<ShardId, HashSet<PeerId>>,
+    peer_labels: &PeerLabelsById,
 ) -> Option<PeerId> {
     let mut peer_loads: HashMap<PeerId, usize> = HashMap::new();
     for peers in shard_distribution.values() {
@@ -399,13 +402,20 @@ pub fn suggest_peer_to_add_replica(
         .get(&shard_id)
         .cloned()
         .unwrap_or_default();
-    for peer_with_shard in peers_with_shard {
-        peer_loads.remove(&peer_with_shard);
+    for peer_with_shard in &peers_with_shard {
+        peer_loads.remove(peer_with_shard);
     }
 
-    let mut candidates = peer_loads.into_iter().collect::<Vec<(PeerId, usize)>>();
-    candidates.sort_unstable_by_key(|(_, count)| *count);
-    candidates.first().map(|(peer_id, _)| *peer_id)
+    peer_loads
+        .into_iter()
+        .min_by_key(|(peer_id, count)| {
+            (
+                shared_labels_count(peer_labels, *peer_id, &peers_with_shard),
+                *count,
+                *peer_id,
+            )
+        })
+        .map(|(peer_id, _)| peer_id)
 }
 
 /// Selects the best peer to remove a replica from.
@@ -414,9 +424,11 @@ pub fn suggest_peer_to_add_replica(
 /// 1. Peer should have a replica of the shard
 /// 2. Peer should maximal number of active shards
 /// 3. Shard replica should preferably be non-active
+/// 4. Among active replicas, prefer the one sharing the most labels with other replicas
 pub fn suggest_peer_to_remove_replica(
     shard_distribution: HashMap<ShardId, HashSet<PeerId>>,
     shard_peers: HashMap<PeerId, ReplicaState>,
+    peer_labels: &PeerLabelsById,
 ) -> Option<PeerId> {
     let mut peer_loads: HashMap<PeerId, usize> = HashMap::new();
     for (_, peers) in shard_distribution {
@@ -426,12 +438,15 @@ pub fn suggest_peer_to_remove_replica(
     }
 
     let mut candidates: Vec<_> = shard_peers
-        .into_iter()
+        .iter()
         .map(|(peer_id, status)| {
             (
-                peer_id,
-                status,
-                peer_loads.get(&peer_id).copied().unwrap_or(0),
+                *peer_id,
+                *status,
+                (
+                    shared_labels_count(peer_labels, *peer_id, shard_peers.keys()),
+                    peer_loads.get(peer_id).copied().unwrap_or(0),
+                ),
             )
         })
         .collect();
@@ -1,22 +1,28 @@
 use std::future::{self, Future};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
+use segment::types::SeqNumberType;
 use tokio::sync::watch;
 
 #[derive(Clone, Debug)]
 pub struct UpdateTracker {
     update_operations: Arc<AtomicUsize>,
     update_notifier: Arc<watch::Sender<()>>,
+    /// Number of the last operation, processed by the update worker
+    applied_operation: Arc<watch::Sender<SeqNumberType>>,
 }
 
 impl Default for UpdateTracker {
     fn default() -> Self {
         let (update_notifier, _) = watch::channel(());
+        let (applied_operation, _) = watch::channel(0);
 
         Self {
             update_operations: Default::default(),
             update_notifier: Arc::new(update_notifier),
+            applied_operation: Arc::new(applied_operation),
         }
     }
 }
@@ -44,6 +50,31 @@ impl UpdateTracker {
 
         UpdateGuard::new(self.update_operations.clone())
     }
+
+    pub fn applied_operation(&self) -> SeqNumberType {
+        *self.applied_operation.borrow()
+    }
+
+    /// Operations are processed sequentially, so the applied operation number never decreases
+    pub fn set_applied_operation(&self, operation_id: SeqNumberType) {
+        self.applied_operation.send_if_modified(|applied| {
+            if operation_id > *applied {
+                *applied = operation_id;
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    /// Wait until the operation with the given number is applied.
+    ///
+    /// Returns `false` if the operation is still not applied after the `timeout`.
+    pub async fn wait_for_operation(&self, operation_id: SeqNumberType, timeout: Duration) -> bool {
+        let mut receiver = self.applied_operation.subscribe();
+        let wait = receiver.wait_for(|applied| *applied >= operation_id);
+        matches!(tokio::time::timeout(timeout, wait).await, Ok(Ok(_)))
+    }
 }
 
 #[derive(Debug)]
@@ -1,6 +1,7 @@
 mod snapshot_test;
 mod wal_recovery_test;
 
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -15,7 +16,8 @@ use crate::collection_manager::fixtures::{
     get_indexing_optimizer, get_merge_optimizer, random_segment,
 };
 use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder, SegmentId};
-use crate::collection_manager::optimizers::TrackerStatus;
+use crate::collection_manager::optimizers::segment_optimizer::SegmentOptimizer;
+use crate::collection_manager::optimizers::{OptimizationStage, Tracker, TrackerStatus};
 use crate::update_handler::{Optimizer, UpdateHandler};
 
 #[tokio::test]
@@ -152,6 +154,45 @@ async fn test_cancel_optimization() {
     }
 }
 
+#[test]
+fn test_optimization_progress() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let dim = 256;
+    let mut holder = SegmentHolder::default();
+
+    let segment_ids = vec![
+        holder.add(random_segment(dir.path(), 100, 3, dim)),
+        holder.add(random_segment(dir.path(), 100, 5, dim)),
+        holder.add(random_segment(dir.path(), 100, 7, dim)),
+    ];
+
+    let merge_optimizer = get_merge_optimizer(dir.path(), temp_dir.path(), dim);
+    let segments: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
+
+    let tracker = Tracker::start(merge_optimizer.name(), segment_ids.clone());
+    let progress = tracker.state.lock().progress;
+    assert_eq!(progress.stage, OptimizationStage::Preparing);
+    assert_eq!(progress.copied_points, 0);
+
+    let optimized = merge_optimizer
+        .optimize_with_progress(
+            segments.clone(),
+            segment_ids,
+            &AtomicBool::new(false),
+            &tracker.handle(),
+        )
+        .unwrap();
+    assert!(optimized);
+    assert_eq!(segments.read().len(), 1);
+
+    let progress = tracker.state.lock().progress;
+    assert_eq!(progress.stage, OptimizationStage::Finalizing);
+    assert_eq!(progress.total_points, 15);
+    assert_eq!(progress.copied_points, 15);
+}
+
 #[test]
 fn check_version_upgrade() {
     assert!(!Collection::can_upgrade_storage(
@@ -50,6 +50,8 @@ async fn _test_snapshot_collection(node_type: NodeType) {
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            disk_ann_config: None,
+            ivf_config: None,
         }),
         shard_number: NonZeroU32::new(4).unwrap(),
         replication_factor: NonZeroU32::new(3).unwrap(),
@@ -103,7 +105,7 @@ async fn _test_snapshot_collection(node_type: NodeType) {
 
     let snapshots_temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
     let snapshot_description = collection
-        .create_snapshot(snapshots_temp_dir.path(), 0)
+        .create_snapshot(snapshots_temp_dir.path(), 0, None)
         .await
         .unwrap();
 
@@ -27,6 +27,8 @@ fn create_collection_config() -> CollectionConfig {
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            disk_ann_config: None,
+            ivf_config: None,
         }),
         ..CollectionParams::empty()
     };
@@ -1,5 +1,6 @@
 use std::cmp::min;
 use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use itertools::Itertools;
@@ -15,7 +16,7 @@ use tokio::time::error::Elapsed;
 use tokio::time::{timeout, Duration};
 
 use crate::collection_manager::collection_updater::CollectionUpdater;
-use crate::collection_manager::holders::segment_holder::LockedSegmentHolder;
+use crate::collection_manager::holders::segment_holder::{LockedSegmentHolder, SegmentId};
 use crate::collection_manager::optimizers::segment_optimizer::SegmentOptimizer;
 use crate::collection_manager::optimizers::{Tracker, TrackerLog, TrackerStatus};
 use crate::common::stoppable_task::{
@@ -98,6 +99,8 @@ pub struct UpdateHandler {
     pub(super) max_ack_version: Arc<TokioMutex<Option<u64>>>,
     optimization_handles: Arc<TokioMutex<Vec<StoppableTaskHandle<bool>>>>,
     max_optimization_threads: usize,
+    /// If set, optimizers do not start new optimizations
+    optimizers_paused: Arc<AtomicBool>,
 }
 
 impl UpdateHandler {
@@ -111,6 +114,7 @@ impl UpdateHandler {
         wal: LockedWal,
         flush_interval_sec: u64,
         max_optimization_threads: usize,
+        optimizers_paused: bool,
     ) -> UpdateHandler {
         UpdateHandler {
             shared_storage_config,
@@ -127,6 +131,7 @@ impl UpdateHandler {
             flush_interval_sec,
             optimization_handles: Arc::new(TokioMutex::new(vec![])),
             max_optimization_threads,
+            optimizers_paused: Arc::new(AtomicBool::new(optimizers_paused)),
         }
     }
 
@@ -141,6 +146,7 @@ impl UpdateHandler {
             self.optimization_handles.clone(),
             self.optimizers_log.clone(),
             self.max_optimization_threads,
+            self.optimizers_paused.clone(),
         )));
         self.update_worker = Some(self.runtime_handle.spawn(Self::update_worker_fn(
             update_receiver,
@@ -197,6 +203,87 @@ impl UpdateHandler {
         Ok(())
     }
 
+    /// Whether optimizers are paused, so no new optimizations are started
+    pub fn optimizers_paused(&self) -> bool {
+        self.optimizers_paused.load(Ordering::Relaxed)
+    }
+
+    /// Pause optimizers: don't start new optimizations until resumed
+    ///
+    /// Running optimizations are cancelled in the background, this does not wait for them to stop.
+    /// Cancelled optimizations are rolled back, so segments stay as they were before.
+    pub fn pause_optimizers(&self) {
+        self.optimizers_paused.store(true, Ordering::Relaxed);
+
+        let optimization_handles = self.optimization_handles.clone();
+        self.runtime_handle.spawn(async move {
+            let opt_handles = std::mem::take(&mut *optimization_handles.lock().await);
+            let stopping_handles = opt_handles
+                .into_iter()
+                .filter_map(|h| h.stop())
+                .collect_vec();
+
+            for res in stopping_handles {
+                if let Err(err) = res.await {
+                    log::error!("Failed to cancel optimization: {err}");
+                }
+            }
+        });
+    }
+
+    /// Allow optimizers to start new optimizations again
+    ///
+    /// Optimizers are only triggered by the next optimizer signal.
+    pub fn resume_optimizers(&self) {
+        self.optimizers_paused.store(false, Ordering::Relaxed);
+    }
+
+    /// Start optimizations suggested by the given `optimizers` right away, even if optimizers
+    /// are paused
+    ///
+    /// Returns the number of started optimizations.
+    pub async fn launch_forced_optimization(&self, optimizers: Arc<Vec<Arc<Optimizer>>>) -> usize {
+        let mut new_handles = Self::launch_optimization(
+            optimizers,
+            self.optimizers_log.clone(),
+            self.segments.clone(),
+            |_optimization_result| {},
+        );
+        let launched = new_handles.len();
+        self.optimization_handles
+            .lock()
+            .await
+            .append(&mut new_handles);
+        launched
+    }
+
+    /// List optimizations, which optimizers would start next
+    ///
+    /// Segments under running optimizations are not considered.
+    /// Returns pairs of optimizer name and IDs of segments to optimize.
+    pub fn queued_optimizations(&self) -> Vec<(String, Vec<SegmentId>)> {
+        let mut scheduled_segment_ids: HashSet<_> = self
+            .optimizers_log
+            .lock()
+            .running_trackers()
+            .flat_map(|tracker| tracker.segment_ids.iter().copied())
+            .collect();
+
+        let mut queued = vec![];
+        for optimizer in self.optimizers.iter() {
+            loop {
+                let nonoptimal_segment_ids =
+                    optimizer.check_condition(self.segments.clone(), &scheduled_segment_ids);
+                if nonoptimal_segment_ids.is_empty() {
+                    break;
+                }
+                scheduled_segment_ids.extend(&nonoptimal_segment_ids);
+                queued.push((optimizer.name().to_string(), nonoptimal_segment_ids));
+            }
+        }
+        queued
+    }
+
     /// Checks if there are any failed operations.
     /// If so - attempts to re-apply all failed operations.
     async fn try_recover(segments: LockedSegmentHolder, wal: LockedWal) -> CollectionResult<usize> {
@@ -256,7 +343,12 @@ impl UpdateHandler {
                             optimizers_log.lock().register(tracker);
 
                             // Optimize and handle result
-                            match optimizer.as_ref().optimize(segments.clone(), nsi, stopped) {
+                            match optimizer.as_ref().optimize_with_progress(
+                                segments.clone(),
+                                nsi,
+                                stopped,
+                                &tracker_handle,
+                            ) {
                                 // Perform some actions when optimization if finished
                                 Ok(result) => {
                                     tracker_handle.update(TrackerStatus::Done);
@@ -368,6 +460,7 @@ impl UpdateHandler {
         optimization_handles: Arc<TokioMutex<Vec<StoppableTaskHandle<bool>>>>,
         optimizers_log: Arc<Mutex<TrackerLog>>,
         max_handles: usize,
+        optimizers_paused: Arc<AtomicBool>,
     ) {
         loop {
             let receiver = timeout(OPTIMIZER_CLEANUP_INTERVAL, receiver.recv());
@@ -383,6 +476,11 @@ impl UpdateHandler {
                 Err(Elapsed { .. }) => continue,
                 // Optimizer signal
                 Ok(Some(signal @ (OptimizerSignal::Nop | OptimizerSignal::Operation(_)))) => {
+                    // Optimizers are paused, wait for a signal after they are resumed
+                    if optimizers_paused.load(Ordering::Relaxed) {
+                        continue;
+                    }
+
                     // If not forcing with Nop, wait on next signal if we have too many handles
                     if signal != OptimizerSignal::Nop
                         && optimization_handles.lock().await.len() >= max_handles
@@ -415,6 +513,7 @@ impl UpdateHandler {
         wal: LockedWal,
         segments: LockedSegmentHolder,
     ) {
+        let update_tracker = segments.read().update_tracker();
         while let Some(signal) = receiver.recv().await {
             match signal {
                 UpdateSignal::Operation(OperationData {
@@ -436,6 +535,8 @@ impl UpdateHandler {
 
                     let operation_result = flush_res
                         .and_then(|_| CollectionUpdater::update(&segments, op_num, operation));
+                    // Failed operations are re-applied by the recovery, reads should not wait for them
+                    update_tracker.set_applied_operation(op_num);
 
                     let res = match operation_result {
                         Ok(update_res) => optimize_sender
@@ -10,6 +10,8 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use wal::{Wal, WalOptions};
 
+use crate::wal_archive::WalArchive;
+
 #[allow(clippy::enum_variant_names)]
 #[derive(Error, Debug)]
 #[error("{0}")]
@@ -20,6 +22,8 @@ pub enum WalError {
     WriteWalError(String),
     #[error("Can't truncate WAL: {0}")]
     TruncateWalError(String),
+    #[error("Can't archive WAL: {0}")]
+    ArchiveWalError(String),
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -67,6 +71,7 @@ pub struct SerdeWal<R> {
     wal: Wal,
     options: WalOptions,
     first_index: Option<u64>,
+    archive: Option<WalArchive>,
 }
 
 const FIRST_INDEX_FILE: &str = "first-index";
@@ -97,16 +102,28 @@ impl<'s, R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
             wal,
             options: wal_options,
             first_index,
+            archive: None,
         })
     }
 
+    /// Archive records into `archive_path` before they are truncated
+    pub fn enable_archive(&mut self, archive_path: &Path) -> Result<()> {
+        self.archive = Some(WalArchive::open(archive_path)?);
+        Ok(())
+    }
+
     /// Write a record to the WAL but does guarantee durability.
     pub fn write(&mut self, entity: &R) -> Result<u64> {
         // ToDo: Replace back to faster rmp, once this https://github.com/serde-rs/serde/issues/2055 solved
         let binary_entity = serde_cbor::to_vec(&entity).unwrap();
-        self.wal
+        let op_num = self
+            .wal
             .append(&binary_entity)
-            .map_err(|err| WalError::WriteWalError(format!("{err:?}")))
+            .map_err(|err| WalError::WriteWalError(format!("{err:?}")))?;
+        if let Some(archive) = &mut self.archive {
+            archive.record_write(op_num);
+        }
+        Ok(op_num)
     }
 
     pub fn read_all(&'s self) -> impl Iterator<Item = (u64, R)> + 's {
@@ -164,6 +181,18 @@ impl<'s, R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
     /// * `until_index` - the newest no longer required record sequence number
     ///
     pub fn ack(&mut self, until_index: u64) -> Result<()> {
+        // Archive records before they are truncated
+        if let Some(mut archive) = self.archive.take() {
+            let archived = archive.archive(self, until_index);
+            self.archive = Some(archive);
+            archived?;
+        }
+        // Times of the operations written since the last ack must survive a restart,
+        // even if nothing is archived yet
+        if let Some(archive) = &mut self.archive {
+            archive.save_pending_times()?;
+        }
+
         // Truncate WAL
         self.wal
             .prefix_truncate(until_index)
@@ -0,0 +1,429 @@
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use io::file_operations::{atomic_save_json, read_json};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::wal::{SerdeWal, WalError};
+
+const CHUNK_EXTENSION: &str = "chunk";
+const PENDING_TIMES_FILE: &str = "pending-times.json";
+const ABANDONED_DIR_PREFIX: &str = "abandoned-";
+
+type Result<T> = std::result::Result<T, WalError>;
+
+fn archive_error(err: impl std::fmt::Display) -> WalError {
+    WalError::ArchiveWalError(err.to_string())
+}
+
+/// Continuous range of archived WAL records
+#[derive(Debug, Deserialize, Serialize)]
+struct ArchiveChunk<R> {
+    /// Operation number of the first record
+    first: u64,
+    /// `(operation number, unix timestamp)` of the first operation written in every second
+    times: Vec<(u64, i64)>,
+    records: Vec<R>,
+}
+
+/// Chunk file in the archive directory
+#[derive(Debug)]
+struct ChunkFile {
+    path: PathBuf,
+    first: u64,
+    last: u64,
+}
+
+/// Archive of WAL records, which are acknowledged and may be truncated from the WAL.
+///
+/// Records are stored in chunks of continuous operation ranges, together with the times they
+/// were written, with the precision of one second. Archived records can be replayed on top of
+/// a recovered snapshot to restore the state at a given operation or time.
+///
+/// If the WAL is rewound, for example after recovery from a snapshot, chunks of the previous
+/// history are moved into an `abandoned-*` subdirectory.
+#[derive(Debug)]
+pub struct WalArchive {
+    path: PathBuf,
+    /// Last archived operation
+    archived_until: Option<u64>,
+    /// Write times of operations, which are not archived yet
+    pending_times: Vec<(u64, i64)>,
+    /// Pending times are changed since they were last saved
+    pending_times_changed: bool,
+}
+
+impl WalArchive {
+    pub fn open(path: &Path) -> Result<Self> {
+        fs::create_dir_all(path).map_err(|err| {
+            WalError::InitWalError(format!(
+                "can't create WAL archive directory {}: {err}",
+                path.display()
+            ))
+        })?;
+
+        let archived_until = list_chunks(path)?.last().map(|chunk| chunk.last);
+
+        let pending_times_path = path.join(PENDING_TIMES_FILE);
+        let pending_times = if pending_times_path.exists() {
+            read_json(&pending_times_path).map_err(|err| {
+                WalError::InitWalError(format!("failed to read WAL archive write times: {err}"))
+            })?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            archived_until,
+            pending_times,
+            pending_times_changed: false,
+        })
+    }
+
+    /// Remember the time of a written operation
+    pub fn record_write(&mut self, op_num: u64) {
+        if self.archived_until.map_or(false, |until| op_num <= until) {
+            if let Err(err) = self.abandon_from(op_num) {
+                log::error!(
+                    "Failed to move abandoned WAL archive chunks in {}: {err}",
+                    self.path.display()
+                );
+            }
+        }
+
+        let now = Utc::now().timestamp();
+        if self
+            .pending_times
+            .last()
+            .map_or(true, |(_, time)| *time < now)
+        {
+            self.pending_times.push((op_num, now));
+            self.pending_times_changed = true;
+        }
+    }
+
+    /// Operations starting from `op_num` are written again, so archived history after it is no longer
+    /// the history of the WAL. It is kept aside, but it is not replayed anymore.
+    fn abandon_from(&mut self, op_num: u64) -> Result<()> {
+        let abandoned_path = self.path.join(format!(
+            "{ABANDONED_DIR_PREFIX}{}",
+            Utc::now().format("%Y-%m-%d-%H-%M-%S-%f")
+        ));
+        fs::create_dir_all(&abandoned_path).map_err(archive_error)?;
+
+        log::warn!(
+            "WAL is rewound to operation {op_num}, moving archived operations after it into {}",
+            abandoned_path.display()
+        );
+
+        for chunk in list_chunks(&self.path)? {
+            if chunk.last >= op_num {
+                let file_name = chunk.path.file_name().unwrap_or_default();
+                fs::rename(&chunk.path, abandoned_path.join(file_name)).map_err(archive_error)?;
+            }
+        }
+
+        self.archived_until = list_chunks(&self.path)?.last().map(|chunk| chunk.last);
+        self.pending_times.retain(|(op, _)| *op < op_num);
+        self.pending_times_changed = true;
+        Ok(())
+    }
+
+    /// Archive records of the `wal` up to `until_index` inclusively, which are not archived yet.
+    ///
+    /// Must be called before records are truncated from the WAL.
+    pub fn archive<R>(&mut self, wal: &SerdeWal<R>, until_index: u64) -> Result<()>
+    where
+        R: DeserializeOwned + Serialize + std::fmt::Debug,
+    {
+        if wal.is_empty() {
+            return Ok(());
+        }
+
+        let first_available = wal.first_index();
+        let until_index = until_index.min(wal.last_index());
+
+        let mut from = self
+            .archived_until
+            .map_or(first_available, |until| until + 1);
+        if from > until_index {
+            return Ok(());
+        }
+        if from < first_available {
+            log::warn!(
+                "WAL archive {} misses operations {from}..{first_available}, \
+                 they were truncated before archiving was enabled",
+                self.path.display()
+            );
+            from = first_available;
+        }
+
+        let records: Vec<_> = wal
+            .read(from)
+            .take_while(|(op_num, _)| *op_num <= until_index)
+            .map(|(_, record)| record)
+            .collect();
+        let times = self
+            .pending_times
+            .iter()
+            .filter(|(op_num, _)| (from..=until_index).contains(op_num))
+            .copied()
+            .collect();
+        let chunk = ArchiveChunk {
+            first: from,
+            times,
+            records,
+        };
+
+        let chunk_path = self
+            .path
+            .join(format!("{from:020}-{until_index:020}.{CHUNK_EXTENSION}"));
+        let tmp_path = chunk_path.with_extension("tmp");
+        {
+            let mut writer = BufWriter::new(File::create(&tmp_path).map_err(archive_error)?);
+            serde_cbor::to_writer(&mut writer, &chunk).map_err(archive_error)?;
+            writer
+                .into_inner()
+                .map_err(archive_error)?
+                .sync_all()
+                .map_err(archive_error)?;
+        }
+        fs::rename(&tmp_path, &chunk_path).map_err(archive_error)?;
+
+        self.archived_until = Some(until_index);
+        self.pending_times
+            .retain(|(op_num, _)| *op_num > until_index);
+        self.pending_times_changed = true;
+
+        Ok(())
+    }
+
+    /// Persist write times of the operations, which are not archived yet, if they have changed
+    pub fn save_pending_times(&mut self) -> Result<()> {
+        if !self.pending_times_changed {
+            return Ok(());
+        }
+        atomic_save_json(&self.path.join(PENDING_TIMES_FILE), &self.pending_times)
+            .map_err(archive_error)?;
+        self.pending_times_changed = false;
+        Ok(())
+    }
+}
+
+/// Point to stop replaying archived operations at
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ReplayUntil {
+    /// Last operation number to replay
+    pub operation: Option<u64>,
+    /// Only replay operations written at this time or earlier
+    pub time: Option<DateTime<Utc>>,
+}
+
+/// Read archived records starting from the operation number `from` up to the `until` point.
+///
+/// Fails if some operations starting from `from` are missing in the archive.
+///
+/// This method performs blocking IO.
+pub fn read_archive<R: DeserializeOwned>(
+    path: &Path,
+    from: u64,
+    until: ReplayUntil,
+) -> Result<Vec<(u64, R)>> {
+    let until_time = until.time.map(|time| time.timestamp());
+
+    let mut records = Vec::new();
+    let mut next_op = from;
+    let mut current_time = None;
+
+    for chunk_file in list_chunks(path)? {
+        if chunk_file.last < next_op {
+            continue;
+        }
+        if chunk_file.first > next_op {
+            return Err(archive_error(format!(
+                "operations {next_op}..{} are missing in the WAL archive {}",
+                chunk_file.first,
+                path.display()
+            )));
+        }
+
+        let reader = BufReader::new(File::open(&chunk_file.path).map_err(archive_error)?);
+        let chunk: ArchiveChunk<R> = serde_cbor::from_reader(reader).map_err(archive_error)?;
+        let mut times = chunk.times.into_iter().peekable();
+
+        for (op_num, record) in (chunk.first..).zip(chunk.records) {
+            while let Some((_, time)) = times.next_if(|(time_op, _)| *time_op <= op_num) {
+                current_time = Some(time);
+            }
+            if op_num < next_op {
+                continue;
+            }
+            if until.operation.map_or(false, |until_op| op_num > until_op) {
+                return Ok(records);
+            }
+            if until_time.map_or(false, |until_time| {
+                current_time.map_or(false, |time| time > until_time)
+            }) {
+                return Ok(records);
+            }
+            records.push((op_num, record));
+            next_op = op_num + 1;
+        }
+    }
+
+    Ok(records)
+}
+
+/// Chunk files of the archive, ordered by operation numbers
+fn list_chunks(path: &Path) -> Result<Vec<ChunkFile>> {
+    let mut chunks = Vec::new();
+
+    for entry in fs::read_dir(path).map_err(archive_error)? {
+        let path = entry.map_err(archive_error)?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(CHUNK_EXTENSION) {
+            continue;
+        }
+        let Some((first, last)) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.split_once('-'))
+            .and_then(|(first, last)| Some((first.parse().ok()?, last.parse().ok()?)))
+        else {
+            log::warn!("Unexpected file in WAL archive: {}", path.display());
+            continue;
+        };
+        chunks.push(ChunkFile { path, first, last });
+    }
+
+    chunks.sort_unstable_by_key(|chunk| chunk.first);
+    Ok(chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+    use wal::WalOptions;
+
+    use super::*;
+
+    fn test_wal(dir: &Path) -> SerdeWal<u64> {
+        SerdeWal::new(
+            dir.to_str().unwrap(),
+            WalOptions {
+                segment_capacity: 1024 * 1024,
+                segment_queue_len: 0,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_archive_and_replay() {
+        let wal_dir = Builder::new().prefix("wal").tempdir().unwrap();
+        let archive_dir = Builder::new().prefix("wal_archive").tempdir().unwrap();
+
+        let mut wal = test_wal(wal_dir.path());
+        wal.enable_archive(archive_dir.path()).unwrap();
+
+        for i in 0..10 {
+            wal.write(&i).unwrap();
+        }
+        wal.ack(4).unwrap();
+        for i in 10..15 {
+            wal.write(&i).unwrap();
+        }
+        wal.ack(12).unwrap();
+
+        let all: Vec<(u64, u64)> =
+            read_archive(archive_dir.path(), 0, ReplayUntil::default()).unwrap();
+        assert_eq!(
+            all.iter().map(|(op, _)| *op).collect::<Vec<_>>(),
+            (0..=12).collect::<Vec<_>>()
+        );
+        assert_eq!(all[1].1, 1);
+
+        let until_op: Vec<(u64, u64)> = read_archive(
+            archive_dir.path(),
+            3,
+            ReplayUntil {
+                operation: Some(7),
+                time: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            until_op.iter().map(|(op, _)| *op).collect::<Vec<_>>(),
+            (3..=7).collect::<Vec<_>>()
+        );
+
+        // Everything is written just now
+        let until_past: Vec<(u64, u64)> = read_archive(
+            archive_dir.path(),
+            0,
+            ReplayUntil {
+                operation: None,
+                time: Some(Utc::now() - chrono::Duration::hours(1)),
+            },
+        )
+        .unwrap();
+        assert!(until_past.is_empty());
+    }
+
+    #[test]
+    fn test_pending_times_saved_on_ack() {
+        let wal_dir = Builder::new().prefix("wal").tempdir().unwrap();
+        let archive_dir = Builder::new().prefix("wal_archive").tempdir().unwrap();
+
+        let mut wal = test_wal(wal_dir.path());
+        wal.enable_archive(archive_dir.path()).unwrap();
+        for i in 0..5 {
+            wal.write(&i).unwrap();
+        }
+        wal.ack(4).unwrap();
+        for i in 5..8 {
+            wal.write(&i).unwrap();
+        }
+        // Nothing new to archive
+        wal.ack(4).unwrap();
+
+        let reopened = WalArchive::open(archive_dir.path()).unwrap();
+        assert_eq!(reopened.archived_until, Some(4));
+        assert_eq!(
+            reopened.pending_times.first().map(|(op_num, _)| *op_num),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn test_archive_rewind() {
+        let wal_dir = Builder::new().prefix("wal").tempdir().unwrap();
+        let archive_dir = Builder::new().prefix("wal_archive").tempdir().unwrap();
+
+        let mut wal = test_wal(wal_dir.path());
+        wal.enable_archive(archive_dir.path()).unwrap();
+        for i in 0..10 {
+            wal.write(&i).unwrap();
+        }
+        wal.ack(9).unwrap();
+
+        // WAL restored from an earlier state continues with already archived operation numbers
+        let rewound_dir = Builder::new().prefix("wal").tempdir().unwrap();
+        let mut rewound = test_wal(rewound_dir.path());
+        rewound.enable_archive(archive_dir.path()).unwrap();
+        for i in 100..103 {
+            rewound.write(&i).unwrap();
+        }
+        rewound.ack(2).unwrap();
+
+        let all: Vec<(u64, u64)> =
+            read_archive(archive_dir.path(), 0, ReplayUntil::default()).unwrap();
+        assert_eq!(
+            all.iter().map(|(_, record)| *record).collect::<Vec<_>>(),
+            vec![100, 101, 102]
+        );
+    }
+}
@@ -68,6 +68,8 @@ async fn test_collection_updater_with_shards(shard_number: u32) {
         limit: 3,
         offset: 0,
         score_threshold: None,
+        profile: None,
+        formula: None,
     };
 
     let search_res = collection.search(search_request, None, None).await;
@@ -125,6 +127,8 @@ async fn test_collection_search_with_payload_and_vector_with_shards(shard_number
         limit: 3,
         offset: 0,
         score_threshold: None,
+        profile: None,
+        formula: None,
     };
 
     let search_res = collection.search(search_request, None, None).await;
@@ -202,6 +206,7 @@ async fn test_collection_loading_with_shards(shard_number: u32) {
                 payload,
                 points: Some(vec![2.into(), 3.into()]),
                 filter: None,
+                key: None,
             }));
 
         collection
@@ -444,6 +449,7 @@ async fn test_collection_delete_points_by_filter_with_shards(shard_number: u32)
     let to_be_deleted: HashSet<PointIdType> = vec![0.into(), 3.into()].into_iter().collect();
     let delete_filter = segment::types::Filter {
         should: None,
+        min_should: None,
         must: Some(vec![Condition::HasId(HasIdCondition::from(to_be_deleted))]),
         must_not: None,
     };
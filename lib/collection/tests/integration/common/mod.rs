@@ -46,6 +46,8 @@ pub async fn simple_collection_fixture(collection_path: &Path, shard_number: u32
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            disk_ann_config: None,
+            ivf_config: None,
         }
         .into(),
         shard_number: NonZeroU32::new(shard_number).expect("Shard number can not be zero"),
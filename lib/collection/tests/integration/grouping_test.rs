@@ -42,6 +42,8 @@ mod group_by {
             with_payload: None,
             with_vector: None,
             score_threshold: None,
+            profile: None,
+            formula: None,
         });
 
         let request = GroupRequest::with_limit_from_request(source, "docId".to_string(), 3);
@@ -143,6 +145,7 @@ mod group_by {
                 negative: Vec::new(),
                 using: None,
                 lookup_from: None,
+                profile: None,
             }),
             "docId".to_string(),
             2,
@@ -206,6 +209,8 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                profile: None,
+                formula: None,
             }),
             "docId".to_string(),
             3,
@@ -241,6 +246,8 @@ mod group_by {
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: Some(WithVector::Bool(true)),
                 score_threshold: None,
+                profile: None,
+                formula: None,
             }),
             "docId".to_string(),
             3,
@@ -287,6 +294,8 @@ mod group_by {
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: Some(WithVector::Bool(true)),
                 score_threshold: None,
+                profile: None,
+                formula: None,
             }),
             "other_stuff".to_string(),
             3,
@@ -331,6 +340,8 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                profile: None,
+                formula: None,
             }),
             "docId".to_string(),
             0,
@@ -371,6 +382,8 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                profile: None,
+                formula: None,
             }),
             "docId".to_string(),
             3,
@@ -411,6 +424,8 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                profile: None,
+                formula: None,
             }),
             "docId".to_string(),
             3,
@@ -455,6 +470,8 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                profile: None,
+                formula: None,
             }),
             "docId".to_string(),
             400,
@@ -512,6 +529,8 @@ mod group_by_builder {
             with_payload: None,
             with_vector: None,
             score_threshold: None,
+            profile: None,
+            formula: None,
         });
 
         let request = GroupRequest::with_limit_from_request(source_request, "docId".to_string(), 3);
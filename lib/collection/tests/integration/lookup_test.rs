@@ -153,6 +153,7 @@ fn first_uuid() -> String {
 #[case::zero_int(0i64)]
 #[case::positive_int(1i64)]
 #[case::existing_uint(999u64)]
+#[case::non_uuid_string("not a uuid")]
 fn parsable_pseudo_id_to_point_id(#[case] value: impl Into<PseudoId>) {
     let value = value.into();
     assert!(PointIdType::try_from(value).is_ok());
@@ -160,7 +161,8 @@ fn parsable_pseudo_id_to_point_id(#[case] value: impl Into<PseudoId>) {
 
 #[rstest]
 #[case::negative_int(-1i64)]
-#[case::non_uuid_string("not a uuid")]
+#[case::empty_string("")]
+#[case::too_long_string("x".repeat(65))]
 fn non_parsable_pseudo_id_to_point_id(#[case] value: impl Into<PseudoId>) {
     let value = value.into();
     assert!(PointIdType::try_from(value).is_err());
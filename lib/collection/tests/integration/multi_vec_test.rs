@@ -41,6 +41,8 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         hnsw_config: None,
         quantization_config: None,
         on_disk: None,
+        disk_ann_config: None,
+        ivf_config: None,
     };
     let vector_params2 = VectorParams {
         size: NonZeroU64::new(4).unwrap(),
@@ -48,6 +50,8 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         hnsw_config: None,
         quantization_config: None,
         on_disk: None,
+        disk_ann_config: None,
+        ivf_config: None,
     };
 
     let mut vectors_config = BTreeMap::new();
@@ -126,6 +130,8 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
         with_vector: Some(true.into()),
         params: None,
         score_threshold: None,
+        profile: None,
+        formula: None,
     };
 
     let result = collection
@@ -154,6 +160,8 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
         with_vector: Some(true.into()),
         params: None,
         score_threshold: None,
+        profile: None,
+        formula: None,
     };
 
     let result = collection.search(failed_search_request, None, None).await;
@@ -176,6 +184,8 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
         with_vector: Some(true.into()),
         params: None,
         score_threshold: None,
+        profile: None,
+        formula: None,
     };
 
     let result = collection
@@ -50,6 +50,8 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
         with_vector: None,
         params: None,
         score_threshold: None,
+        profile: None,
+        formula: None,
     };
 
     let reference_result = collection
@@ -71,6 +73,8 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
         with_vector: None,
         params: None,
         score_threshold: None,
+        profile: None,
+        formula: None,
     };
 
     let page_1_result = collection.search(page_1_request, None, None).await.unwrap();
@@ -90,6 +94,8 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
         with_vector: None,
         params: None,
         score_threshold: None,
+        profile: None,
+        formula: None,
     };
 
     let page_9_result = collection.search(page_9_request, None, None).await.unwrap();
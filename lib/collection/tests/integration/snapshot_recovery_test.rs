@@ -32,6 +32,8 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
             hnsw_config: None,
             quantization_config: None,
             on_disk: None,
+            disk_ann_config: None,
+            ivf_config: None,
         }),
         ..CollectionParams::empty()
     };
@@ -109,7 +111,7 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
     // Take a snapshot
     let snapshots_temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
     let snapshot_description = collection
-        .create_snapshot(snapshots_temp_dir.path(), 0)
+        .create_snapshot(snapshots_temp_dir.path(), 0, None)
         .await
         .unwrap();
 
@@ -147,6 +149,8 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
         with_vector: Some(WithVector::Bool(true)),
         params: None,
         score_threshold: None,
+        profile: None,
+        formula: None,
     };
 
     let reference_result = collection
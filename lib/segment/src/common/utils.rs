@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use serde_json::Value;
 
+use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::vectors::VectorElementType;
 use crate::index::field_index::FieldIndex;
@@ -320,6 +321,110 @@ pub fn remove_value_from_json_map(
     }
 }
 
+/// Merge JSON map `src` into `dest`
+///
+/// Top-level keys of `src` override the ones in `dest`, `null` values remove the key.
+pub fn merge_json_map(
+    dest: &mut serde_json::Map<String, Value>,
+    src: &serde_json::Map<String, Value>,
+) {
+    for (key, value) in src {
+        match value {
+            Value::Null => dest.remove(key),
+            _ => dest.insert(key.to_owned(), value.to_owned()),
+        };
+    }
+}
+
+/// Merge JSON map `src` into the object located at the given JSON path of `dest`
+///
+/// Missing objects along the path are created, non-object values along the path are replaced
+/// with objects. Array path elements (`a[]`, `a[1]`) only focus on already existing elements.
+pub fn set_value_to_json_map(
+    path: &str,
+    dest: &mut serde_json::Map<String, Value>,
+    src: &serde_json::Map<String, Value>,
+) {
+    let (element, rest_path) = match path.split_once('.') {
+        Some((element, rest_path)) => (element, Some(rest_path)),
+        None => (path, None),
+    };
+
+    match parse_array_path(element) {
+        Some((array_element_path, array_index)) => {
+            if let Some(Value::Array(array)) = dest.get_mut(array_element_path) {
+                for (i, value) in array.iter_mut().enumerate() {
+                    if array_index.is_none() || array_index == Some(i as u32) {
+                        set_value_to_json_value(rest_path, value, src);
+                    }
+                }
+            }
+        }
+        None => {
+            let value = dest
+                .entry(element)
+                .or_insert_with(|| Value::Object(serde_json::Map::new()));
+            set_value_to_json_value(rest_path, value, src);
+        }
+    }
+}
+
+fn set_value_to_json_value(
+    rest_path: Option<&str>,
+    dest: &mut Value,
+    src: &serde_json::Map<String, Value>,
+) {
+    if !dest.is_object() {
+        *dest = Value::Object(serde_json::Map::new());
+    }
+    if let Value::Object(map) = dest {
+        match rest_path {
+            Some(rest_path) if !rest_path.is_empty() => set_value_to_json_map(rest_path, map, src),
+            _ => merge_json_map(map, src),
+        }
+    }
+}
+
+/// Get mutable reference to the value located at the given JSON path, inserting `null` if missing
+///
+/// Missing objects along the path are created. Only dot-separated paths of object keys are
+/// supported, array notation is rejected.
+pub fn get_or_insert_value_in_json_map<'a>(
+    path: &str,
+    json_map: &'a mut serde_json::Map<String, Value>,
+) -> OperationResult<&'a mut Value> {
+    if path.is_empty() || path.contains('[') {
+        return Err(OperationError::ValidationError {
+            description: format!(
+                "Unsupported payload key `{path}`, only dot-separated object keys are allowed"
+            ),
+        });
+    }
+
+    let mut current = json_map;
+    let mut elements = path.split('.').peekable();
+    while let Some(element) = elements.next() {
+        let value = current.entry(element).or_insert(Value::Null);
+        if elements.peek().is_none() {
+            return Ok(value);
+        }
+        if value.is_null() {
+            *value = Value::Object(serde_json::Map::new());
+        }
+        match value {
+            Value::Object(map) => current = map,
+            _ => {
+                return Err(OperationError::ValidationError {
+                    description: format!(
+                        "Payload value `{element}` of key `{path}` is not an object"
+                    ),
+                })
+            }
+        }
+    }
+    unreachable!("path is not empty")
+}
+
 /// Check if a path is included in a list of patterns
 ///
 /// Basically, it checks if either the pattern or path is a prefix of the other.
@@ -736,6 +841,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_set_value_to_json_map() {
+        let mut map = serde_json::from_str::<serde_json::Map<String, Value>>(
+            r#"
+            {
+                "a": {
+                    "b": { "c": 1, "d": 2 }
+                },
+                "e": 3,
+                "f": [
+                    { "g": 4 },
+                    { "g": 5 }
+                ]
+            }
+            "#,
+        )
+        .unwrap();
+
+        let src = serde_json::from_str::<serde_json::Map<String, Value>>(
+            r#"{ "c": 10, "d": null, "x": "y" }"#,
+        )
+        .unwrap();
+
+        // merge into existing nested object
+        set_value_to_json_map("a.b", &mut map, &src);
+        assert_eq!(
+            get_value_from_json_map("a.b", &map).values(),
+            vec![&serde_json::json!({ "c": 10, "x": "y" })]
+        );
+
+        // create missing objects and replace non-object values
+        set_value_to_json_map("e.h", &mut map, &src);
+        assert_eq!(
+            get_value_from_json_map("e", &map).values(),
+            vec![&serde_json::json!({ "h": { "c": 10, "x": "y" } })]
+        );
+
+        // merge into a specific array element
+        set_value_to_json_map("f[1]", &mut map, &src);
+        assert_eq!(
+            get_value_from_json_map("f", &map).values(),
+            vec![&serde_json::json!([{ "g": 4 }, { "g": 5, "c": 10, "x": "y" }])]
+        );
+
+        // merge into every array element
+        set_value_to_json_map("f[]", &mut map, &src);
+        assert_eq!(
+            get_value_from_json_map("f[].x", &map).values(),
+            vec![
+                &Value::String("y".to_string()),
+                &Value::String("y".to_string())
+            ]
+        );
+    }
+
     #[test]
     fn test_filter_json() {
         let map = serde_json::from_str::<serde_json::Map<String, Value>>(
@@ -0,0 +1,561 @@
+//! Formulas to compute the final score of the search results from the vector similarity score
+//! and payload values of the found points.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use common::types::ScoreType;
+use geo::prelude::HaversineDistance;
+use geo::Point;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use validator::{Validate, ValidationError, ValidationErrors};
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::payload_storage::query_checker::check_payload_with_id;
+use crate::types::{Filter, GeoPoint, Payload, PayloadContainer, PointIdType};
+
+/// Variable of the formula, which refers to the vector similarity score
+pub const SCORE_VARIABLE: &str = "$score";
+
+const DEFAULT_DECAY_TARGET: f64 = 0.0;
+const DEFAULT_DECAY_SCALE: f64 = 1.0;
+const DEFAULT_DECAY_MIDPOINT: f64 = 0.5;
+
+/// Formula to compute the final score of the search results.
+///
+/// Results are sorted by the computed score, larger is better.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct Formula {
+    /// Expression of the final score. Variable `$score` refers to the vector similarity score
+    #[validate]
+    pub expression: Expression,
+    /// Values of the payload keys, used if a found point has no value for the key
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub defaults: HashMap<String, Value>,
+    /// Number of the most similar points to rescore with the formula in each shard.
+    /// Default: `limit + offset`
+    #[validate(range(min = 1))]
+    pub candidates: Option<usize>,
+}
+
+/// Expression of the score formula
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Expression {
+    Constant(f64),
+    /// Payload key with a number value, or `$score`
+    Variable(String),
+    Filter(FilterExpression),
+    GeoDistance(GeoDistanceExpression),
+    Datetime(DatetimeExpression),
+    DatetimeKey(DatetimeKeyExpression),
+    Mult(MultExpression),
+    Sum(SumExpression),
+    Neg(NegExpression),
+    Div(DivExpression),
+    Abs(AbsExpression),
+    Sqrt(SqrtExpression),
+    Pow(PowExpression),
+    Exp(ExpExpression),
+    Ln(LnExpression),
+    LinDecay(LinDecayExpression),
+    ExpDecay(ExpDecayExpression),
+    GaussDecay(GaussDecayExpression),
+}
+
+/// 1 if the point matches the filter, 0 otherwise
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+pub struct FilterExpression {
+    #[validate]
+    pub filter: Filter,
+}
+
+/// Haversine distance in meters between the origin and the geo point in the payload key
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+pub struct GeoDistanceExpression {
+    pub geo_distance: GeoDistance,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+pub struct GeoDistance {
+    pub origin: GeoPoint,
+    /// Payload key with the geo point
+    pub to: String,
+}
+
+/// Datetime in RFC 3339 format, evaluated to seconds since the Unix epoch
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+pub struct DatetimeExpression {
+    pub datetime: DateTime<Utc>,
+}
+
+/// Payload key with a datetime in RFC 3339 format, evaluated to seconds since the Unix epoch
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+pub struct DatetimeKeyExpression {
+    pub datetime_key: String,
+}
+
+/// Product of the expressions
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+pub struct MultExpression {
+    #[validate]
+    pub mult: Vec<Expression>,
+}
+
+/// Sum of the expressions
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+pub struct SumExpression {
+    #[validate]
+    pub sum: Vec<Expression>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+pub struct NegExpression {
+    #[validate]
+    pub neg: Box<Expression>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+pub struct DivExpression {
+    #[validate]
+    pub div: DivParams,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+pub struct DivParams {
+    #[validate]
+    pub left: Box<Expression>,
+    #[validate]
+    pub right: Box<Expression>,
+    /// Result of the division, if `right` is zero
+    pub by_zero_default: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+pub struct AbsExpression {
+    #[validate]
+    pub abs: Box<Expression>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+pub struct SqrtExpression {
+    #[validate]
+    pub sqrt: Box<Expression>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+pub struct PowExpression {
+    #[validate]
+    pub pow: PowParams,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+pub struct PowParams {
+    #[validate]
+    pub base: Box<Expression>,
+    #[validate]
+    pub exponent: Box<Expression>,
+}
+
+/// Exponent of the expression, `e^x`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+pub struct ExpExpression {
+    #[validate]
+    pub exp: Box<Expression>,
+}
+
+/// Natural logarithm of the expression
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+pub struct LnExpression {
+    #[validate]
+    pub ln: Box<Expression>,
+}
+
+/// Linear decay, `max(0, 1 - (1 - midpoint) / scale * abs(x - target))`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+pub struct LinDecayExpression {
+    #[validate]
+    pub lin_decay: DecayParams,
+}
+
+/// Exponential decay, `exp(ln(midpoint) / scale * abs(x - target))`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+pub struct ExpDecayExpression {
+    #[validate]
+    pub exp_decay: DecayParams,
+}
+
+/// Gaussian decay, `exp(ln(midpoint) / scale^2 * (x - target)^2)`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+pub struct GaussDecayExpression {
+    #[validate]
+    pub gauss_decay: DecayParams,
+}
+
+/// Parameters of the decay functions, which are 1 at the `target` and decrease with the
+/// distance from it
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+pub struct DecayParams {
+    /// Value to decay
+    #[validate]
+    pub x: Box<Expression>,
+    /// Value of `x`, at which the function is 1. Default: 0
+    #[validate]
+    pub target: Option<Box<Expression>>,
+    /// Distance from the target, at which the function is equal to `midpoint`. Must be positive. Default: 1
+    #[validate(custom = "validate_decay_scale")]
+    pub scale: Option<f64>,
+    /// Value of the function at the `scale` distance from the target, in range (0, 1). Default: 0.5
+    #[validate(custom = "validate_decay_midpoint")]
+    pub midpoint: Option<f64>,
+}
+
+fn validate_decay_scale(scale: &f64) -> Result<(), ValidationError> {
+    if *scale > 0.0 {
+        Ok(())
+    } else {
+        Err(ValidationError::new("scale must be positive"))
+    }
+}
+
+fn validate_decay_midpoint(midpoint: &f64) -> Result<(), ValidationError> {
+    if 0.0 < *midpoint && *midpoint < 1.0 {
+        Ok(())
+    } else {
+        Err(ValidationError::new("midpoint must be in range (0, 1)"))
+    }
+}
+
+impl Validate for Expression {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            Expression::Constant(_)
+            | Expression::Variable(_)
+            | Expression::GeoDistance(_)
+            | Expression::Datetime(_)
+            | Expression::DatetimeKey(_) => Ok(()),
+            Expression::Filter(expression) => expression.validate(),
+            Expression::Mult(expression) => expression.validate(),
+            Expression::Sum(expression) => expression.validate(),
+            Expression::Neg(expression) => expression.validate(),
+            Expression::Div(expression) => expression.validate(),
+            Expression::Abs(expression) => expression.validate(),
+            Expression::Sqrt(expression) => expression.validate(),
+            Expression::Pow(expression) => expression.validate(),
+            Expression::Exp(expression) => expression.validate(),
+            Expression::Ln(expression) => expression.validate(),
+            Expression::LinDecay(expression) => expression.validate(),
+            Expression::ExpDecay(expression) => expression.validate(),
+            Expression::GaussDecay(expression) => expression.validate(),
+        }
+    }
+}
+
+/// Found point, for which the formula is evaluated
+struct FormulaContext<'a> {
+    point_id: PointIdType,
+    score: ScoreType,
+    payload: &'a Payload,
+    defaults: &'a HashMap<String, Value>,
+}
+
+fn formula_error(description: String) -> OperationError {
+    OperationError::ValidationError { description }
+}
+
+impl FormulaContext<'_> {
+    /// First value of the payload key, or the default value if the point has none
+    fn value(&self, key: &str) -> OperationResult<&Value> {
+        let value =
+            self.payload
+                .get_value(key)
+                .values()
+                .into_iter()
+                .next()
+                .map(|value| match value {
+                    Value::Array(values) => values.first(),
+                    value => Some(value),
+                });
+        value
+            .flatten()
+            .or_else(|| self.defaults.get(key))
+            .ok_or_else(|| {
+                formula_error(format!(
+                    "No value of `{key}` for point {}, and no default value is set",
+                    self.point_id,
+                ))
+            })
+    }
+
+    fn number(&self, key: &str) -> OperationResult<f64> {
+        self.value(key)?.as_f64().ok_or_else(|| {
+            formula_error(format!(
+                "Value of `{key}` for point {} is not a number",
+                self.point_id,
+            ))
+        })
+    }
+
+    fn datetime(&self, key: &str) -> OperationResult<f64> {
+        self.value(key)?
+            .as_str()
+            .and_then(|datetime| DateTime::parse_from_rfc3339(datetime).ok())
+            .map(|datetime| datetime.timestamp_millis() as f64 / 1000.0)
+            .ok_or_else(|| {
+                formula_error(format!(
+                    "Value of `{key}` for point {} is not a datetime in RFC 3339 format",
+                    self.point_id,
+                ))
+            })
+    }
+
+    fn geo_point(&self, key: &str) -> OperationResult<GeoPoint> {
+        let value = self.value(key)?;
+        let lon = value.get("lon").and_then(Value::as_f64);
+        let lat = value.get("lat").and_then(Value::as_f64);
+        lon.zip(lat)
+            .and_then(|(lon, lat)| GeoPoint::new(lon, lat).ok())
+            .ok_or_else(|| {
+                formula_error(format!(
+                    "Value of `{key}` for point {} is not a geo point",
+                    self.point_id,
+                ))
+            })
+    }
+}
+
+impl Formula {
+    /// Final score of the found point
+    ///
+    /// `score` is the vector similarity score, after post-processing.
+    pub fn evaluate(
+        &self,
+        point_id: PointIdType,
+        score: ScoreType,
+        payload: &Payload,
+    ) -> OperationResult<ScoreType> {
+        let context = FormulaContext {
+            point_id,
+            score,
+            payload,
+            defaults: &self.defaults,
+        };
+        let value = self.expression.evaluate(&context)?;
+        if !value.is_finite() {
+            return Err(formula_error(format!(
+                "Formula score of point {point_id} is not a finite number",
+            )));
+        }
+        Ok(value as ScoreType)
+    }
+}
+
+impl Expression {
+    fn evaluate(&self, context: &FormulaContext) -> OperationResult<f64> {
+        let value = match self {
+            Expression::Constant(constant) => *constant,
+            Expression::Variable(variable) if variable == SCORE_VARIABLE => context.score as f64,
+            Expression::Variable(key) => context.number(key)?,
+            Expression::Filter(FilterExpression { filter }) => {
+                if check_payload_with_id(context.payload, context.point_id, filter) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Expression::GeoDistance(GeoDistanceExpression { geo_distance }) => {
+                let point = context.geo_point(&geo_distance.to)?;
+                Point::new(geo_distance.origin.lon, geo_distance.origin.lat)
+                    .haversine_distance(&Point::new(point.lon, point.lat))
+            }
+            Expression::Datetime(DatetimeExpression { datetime }) => {
+                datetime.timestamp_millis() as f64 / 1000.0
+            }
+            Expression::DatetimeKey(DatetimeKeyExpression { datetime_key }) => {
+                context.datetime(datetime_key)?
+            }
+            Expression::Mult(MultExpression { mult }) => {
+                let mut product = 1.0;
+                for expression in mult {
+                    product *= expression.evaluate(context)?;
+                }
+                product
+            }
+            Expression::Sum(SumExpression { sum }) => {
+                let mut total = 0.0;
+                for expression in sum {
+                    total += expression.evaluate(context)?;
+                }
+                total
+            }
+            Expression::Neg(NegExpression { neg }) => -neg.evaluate(context)?,
+            Expression::Div(DivExpression { div }) => {
+                let left = div.left.evaluate(context)?;
+                let right = div.right.evaluate(context)?;
+                match div.by_zero_default {
+                    Some(by_zero_default) if right == 0.0 => by_zero_default,
+                    _ => left / right,
+                }
+            }
+            Expression::Abs(AbsExpression { abs }) => abs.evaluate(context)?.abs(),
+            Expression::Sqrt(SqrtExpression { sqrt }) => sqrt.evaluate(context)?.sqrt(),
+            Expression::Pow(PowExpression { pow }) => {
+                let base = pow.base.evaluate(context)?;
+                let exponent = pow.exponent.evaluate(context)?;
+                base.powf(exponent)
+            }
+            Expression::Exp(ExpExpression { exp }) => exp.evaluate(context)?.exp(),
+            Expression::Ln(LnExpression { ln }) => ln.evaluate(context)?.ln(),
+            Expression::LinDecay(LinDecayExpression { lin_decay }) => {
+                let (distance, scale, midpoint) = lin_decay.evaluate(context)?;
+                (1.0 - (1.0 - midpoint) / scale * distance).max(0.0)
+            }
+            Expression::ExpDecay(ExpDecayExpression { exp_decay }) => {
+                let (distance, scale, midpoint) = exp_decay.evaluate(context)?;
+                (midpoint.ln() / scale * distance).exp()
+            }
+            Expression::GaussDecay(GaussDecayExpression { gauss_decay }) => {
+                let (distance, scale, midpoint) = gauss_decay.evaluate(context)?;
+                (midpoint.ln() / scale.powi(2) * distance.powi(2)).exp()
+            }
+        };
+        Ok(value)
+    }
+}
+
+impl DecayParams {
+    /// Distance of `x` from the target, scale and midpoint of the decay
+    fn evaluate(&self, context: &FormulaContext) -> OperationResult<(f64, f64, f64)> {
+        let x = self.x.evaluate(context)?;
+        let target = match &self.target {
+            Some(target) => target.evaluate(context)?,
+            None => DEFAULT_DECAY_TARGET,
+        };
+        Ok((
+            (x - target).abs(),
+            self.scale.unwrap_or(DEFAULT_DECAY_SCALE),
+            self.midpoint.unwrap_or(DEFAULT_DECAY_MIDPOINT),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn evaluate(formula: Value, payload: Value, score: ScoreType) -> OperationResult<ScoreType> {
+        let formula: Formula = serde_json::from_value(formula).unwrap();
+        formula.validate().unwrap();
+        let payload: Payload = serde_json::from_value(payload).unwrap();
+        formula.evaluate(PointIdType::NumId(1), score, &payload)
+    }
+
+    #[test]
+    fn test_formula_arithmetic() {
+        let formula = json!({
+            "expression": {
+                "sum": [
+                    "$score",
+                    {"mult": [0.5, "popularity"]},
+                    {"div": {"left": 1.0, "right": "discount", "by_zero_default": 0.0}},
+                    {"neg": {"abs": -2.0}},
+                    {"pow": {"base": {"sqrt": 4.0}, "exponent": 3.0}}
+                ]
+            }
+        });
+        let score = evaluate(
+            formula.clone(),
+            json!({"popularity": 4, "discount": 4}),
+            0.25,
+        );
+        assert_eq!(score.unwrap(), 0.25 + 2.0 + 0.25 - 2.0 + 8.0);
+
+        let score = evaluate(formula, json!({"popularity": [4, 8], "discount": 0}), 0.25);
+        assert_eq!(score.unwrap(), 0.25 + 2.0 + 0.0 - 2.0 + 8.0);
+    }
+
+    #[test]
+    fn test_formula_defaults() {
+        let formula = json!({"expression": {"mult": ["$score", "boost"]}});
+        assert!(evaluate(formula, json!({}), 0.5).is_err());
+
+        let formula = json!({
+            "expression": {"mult": ["$score", "boost"]},
+            "defaults": {"boost": 3}
+        });
+        assert_eq!(evaluate(formula.clone(), json!({}), 0.5).unwrap(), 1.5);
+        assert_eq!(evaluate(formula, json!({"boost": 2}), 0.5).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_formula_filter() {
+        let formula = json!({
+            "expression": {
+                "sum": [
+                    "$score",
+                    {"filter": {"must": [{"key": "city", "match": {"value": "Berlin"}}]}},
+                    {"filter": {"must": [{"has_id": [1]}]}}
+                ]
+            }
+        });
+        let score = evaluate(formula.clone(), json!({"city": "Berlin"}), 0.5);
+        assert_eq!(score.unwrap(), 2.5);
+        let score = evaluate(formula, json!({"city": "London"}), 0.5);
+        assert_eq!(score.unwrap(), 1.5);
+    }
+
+    #[test]
+    fn test_formula_decay() {
+        let decay = |kind: &str, x: f64| {
+            let formula = json!({
+                "expression": {kind: {"x": "x", "target": 10.0, "scale": 5.0, "midpoint": 0.25}}
+            });
+            evaluate(formula, json!({ "x": x }), 0.0).unwrap()
+        };
+        for kind in ["lin_decay", "exp_decay", "gauss_decay"] {
+            assert_eq!(decay(kind, 10.0), 1.0, "{kind}");
+            assert!((decay(kind, 5.0) - 0.25).abs() < 1e-6, "{kind}");
+            assert!((decay(kind, 15.0) - 0.25).abs() < 1e-6, "{kind}");
+            assert!(decay(kind, 20.0) < 0.25, "{kind}");
+        }
+        assert_eq!(decay("lin_decay", 100.0), 0.0);
+
+        let formula = json!({"expression": {"exp_decay": {"x": 1.0, "midpoint": 1.5}}});
+        let formula: Formula = serde_json::from_value(formula).unwrap();
+        assert!(formula.validate().is_err());
+    }
+
+    #[test]
+    fn test_formula_datetime_and_geo() {
+        let formula = json!({
+            "expression": {
+                "sum": [
+                    {"datetime_key": "created_at"},
+                    {"neg": {"datetime": "2024-01-01T00:00:00Z"}}
+                ]
+            }
+        });
+        let score = evaluate(formula, json!({"created_at": "2024-01-01T01:00:00Z"}), 0.0);
+        assert_eq!(score.unwrap(), 3600.0);
+
+        let formula = json!({
+            "expression": {"geo_distance": {"origin": {"lon": 0.0, "lat": 0.0}, "to": "location"}}
+        });
+        let score = evaluate(formula, json!({"location": {"lon": 0.5, "lat": 0.5}}), 0.0);
+        assert!((score.unwrap() - 78626.29).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_formula_non_finite() {
+        let formula = json!({"expression": {"div": {"left": 1.0, "right": 0.0}}});
+        assert!(evaluate(formula, json!({}), 0.0).is_err());
+
+        let formula = json!({"expression": {"ln": "$score"}});
+        assert!(evaluate(formula, json!({}), 0.0).is_err());
+    }
+}
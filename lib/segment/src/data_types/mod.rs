@@ -1,5 +1,7 @@
+pub mod formula;
 pub mod groups;
 pub mod named_vectors;
+pub mod search_profile;
 pub mod text_index;
 pub mod tiny_map;
 pub mod vectors;
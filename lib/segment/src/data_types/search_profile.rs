@@ -0,0 +1,90 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::index::field_index::CardinalityEstimation;
+
+/// Strategy chosen by the vector index to perform a search
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchStrategy {
+    /// All vectors of the segment are scored
+    #[default]
+    Plain,
+    /// Exact search was requested, all vectors matching the filter are scored without index
+    Exact,
+    /// HNSW graph is traversed
+    Hnsw,
+    /// Points matching the filter are retrieved from the payload index and scored
+    PayloadIndex,
+    /// HNSW graph is traversed, filter is checked for every visited point
+    FilteredHnsw,
+    /// HNSW graph is traversed through two hops of neighbors, which don't match a selective filter
+    AcornHnsw,
+    /// DiskANN graph is read from disk, filter is checked for every found point
+    DiskAnn,
+    /// Closest inverted lists of the IVF index are scanned, filter is checked for every scanned point
+    Ivf,
+}
+
+/// Estimated number of points matching the filter
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct CardinalityEstimationProfile {
+    /// Minimal possible number of matched points
+    pub min: usize,
+    /// Expected number of matched points
+    pub exp: usize,
+    /// Maximal possible number of matched points
+    pub max: usize,
+}
+
+impl From<&CardinalityEstimation> for CardinalityEstimationProfile {
+    fn from(estimation: &CardinalityEstimation) -> Self {
+        Self {
+            min: estimation.min,
+            exp: estimation.exp,
+            max: estimation.max,
+        }
+    }
+}
+
+/// Details of how the vector index performed a search
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct VectorIndexSearchProfile {
+    pub strategy: SearchStrategy,
+    /// Cardinality of the filter, estimated by the payload index
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cardinality: Option<CardinalityEstimationProfile>,
+    /// Whether estimation was not conclusive and cardinality was checked by sampling points
+    #[serde(default)]
+    pub cardinality_sampled: bool,
+    /// Size of the dynamic candidate list used for HNSW or DiskANN search
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hnsw_ef: Option<usize>,
+    /// Number of vectors scored during the search
+    pub scored_vectors: usize,
+    /// Whether quantized vectors were used for scoring
+    #[serde(default)]
+    pub quantized: bool,
+    /// Number of candidates selected per query, including oversampling
+    pub candidates: usize,
+    /// Whether candidates were rescored with original vectors
+    #[serde(default)]
+    pub rescored: bool,
+}
+
+/// Details of a search in a single segment
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct SegmentSearchProfile {
+    /// Unique identifier of the segment
+    pub segment_id: String,
+    /// Name of the searched vector
+    pub vector_name: String,
+    /// Number of queries searched together in this segment
+    pub queries: usize,
+    pub search: VectorIndexSearchProfile,
+    /// Time spent searching the segment, in seconds
+    pub time: f64,
+}
@@ -4,6 +4,7 @@ use std::sync::atomic::AtomicBool;
 
 use crate::common::operation_error::{OperationResult, SegmentFailedState};
 use crate::data_types::named_vectors::NamedVectors;
+use crate::data_types::search_profile::SegmentSearchProfile;
 use crate::data_types::vectors::{QueryVector, VectorElementType};
 use crate::index::field_index::CardinalityEstimation;
 use crate::telemetry::SegmentTelemetry;
@@ -50,6 +51,20 @@ pub trait SegmentEntry {
         is_stopped: &AtomicBool,
     ) -> OperationResult<Vec<Vec<ScoredPoint>>>;
 
+    /// Same as [`SegmentEntry::search_batch`], but also reports how the search was performed
+    #[allow(clippy::too_many_arguments)]
+    fn search_batch_with_profile(
+        &self,
+        vector_name: &str,
+        query_vectors: &[&QueryVector],
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<(Vec<Vec<ScoredPoint>>, Vec<SegmentSearchProfile>)>;
+
     fn upsert_point(
         &mut self,
         op_num: SeqNumberType,
@@ -77,11 +92,13 @@ pub trait SegmentEntry {
         vector_name: &str,
     ) -> OperationResult<bool>;
 
+    /// Merge payload into the point's payload, or into its nested object at `key` if specified
     fn set_payload(
         &mut self,
         op_num: SeqNumberType,
         point_id: PointIdType,
         payload: &Payload,
+        key: &Option<PayloadKeyType>,
     ) -> OperationResult<bool>;
 
     fn set_full_payload(
@@ -205,6 +222,12 @@ pub trait SegmentEntry {
     fn take_snapshot(&self, temp_path: &Path, snapshot_dir_path: &Path)
         -> OperationResult<PathBuf>;
 
+    /// Versions of the segments written by [`SegmentEntry::take_snapshot`],
+    /// keyed by the name of the snapshot archive without extension.
+    ///
+    /// Used to detect segments which did not change since a previous snapshot.
+    fn snapshot_versions(&self) -> Vec<(String, SeqNumberType)>;
+
     // Get collected telemetry data of segment
     fn get_telemetry_data(&self) -> SegmentTelemetry;
 }
@@ -60,7 +60,7 @@ impl IdTracker for FixtureIdTracker {
                 assert!(id < self.ids.len() as u64);
                 id as PointOffsetType
             }
-            PointIdType::Uuid(_) => unreachable!(),
+            PointIdType::Uuid(_) | PointIdType::String(_) => unreachable!(),
         })
     }
 
@@ -106,7 +106,7 @@ impl IdTracker for FixtureIdTracker {
             None => 0,
             Some(id) => match id {
                 PointIdType::NumId(num) => num,
-                PointIdType::Uuid(_) => unreachable!(),
+                PointIdType::Uuid(_) | PointIdType::String(_) => unreachable!(),
             },
         } as PointOffsetType;
 
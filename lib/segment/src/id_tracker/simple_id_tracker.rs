@@ -9,15 +9,16 @@ use rocksdb::DB;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::common::operation_error::OperationResult;
+use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDeleteWrapper;
 use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_MAPPING_CF, DB_VERSIONS_CF};
 use crate::common::Flusher;
 use crate::id_tracker::IdTracker;
-use crate::types::{ExtendedPointId, PointIdType, SeqNumberType};
+use crate::types::{ExtendedPointId, PointIdType, SeqNumberType, StringId};
 
 /// Point Id type used for storing ids internally
 /// Should be serializable by `bincode`, therefore is not untagged.
+/// String IDs are stored since segment version 0.6.1, older versions refuse to load such segments.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
 enum StoredPointId {
     NumId(u64),
@@ -30,25 +31,32 @@ impl From<&ExtendedPointId> for StoredPointId {
         match point_id {
             ExtendedPointId::NumId(idx) => StoredPointId::NumId(*idx),
             ExtendedPointId::Uuid(uuid) => StoredPointId::Uuid(*uuid),
+            ExtendedPointId::String(id) => StoredPointId::String(id.as_str().to_owned()),
         }
     }
 }
 
-impl From<StoredPointId> for ExtendedPointId {
-    fn from(point_id: StoredPointId) -> Self {
+impl TryFrom<StoredPointId> for ExtendedPointId {
+    type Error = OperationError;
+
+    fn try_from(point_id: StoredPointId) -> Result<Self, Self::Error> {
         match point_id {
-            StoredPointId::NumId(idx) => ExtendedPointId::NumId(idx),
-            StoredPointId::Uuid(uuid) => ExtendedPointId::Uuid(uuid),
-            StoredPointId::String(str) => {
-                unimplemented!("cannot convert internal string id '{str}' to external id")
-            }
+            StoredPointId::NumId(idx) => Ok(ExtendedPointId::NumId(idx)),
+            StoredPointId::Uuid(uuid) => Ok(ExtendedPointId::Uuid(uuid)),
+            StoredPointId::String(str) => StringId::try_from(str.as_str())
+                .map(ExtendedPointId::String)
+                .map_err(|err| {
+                    OperationError::service_error(format!(
+                        "invalid stored string id '{str}': {err}"
+                    ))
+                }),
         }
     }
 }
 
 #[inline]
-fn stored_to_external_id(point_id: StoredPointId) -> PointIdType {
-    point_id.into()
+fn stored_to_external_id(point_id: StoredPointId) -> OperationResult<PointIdType> {
+    point_id.try_into()
 }
 
 #[inline]
@@ -62,6 +70,7 @@ pub struct SimpleIdTracker {
     internal_to_version: Vec<SeqNumberType>,
     external_to_internal_num: BTreeMap<u64, PointOffsetType>,
     external_to_internal_uuid: BTreeMap<Uuid, PointOffsetType>,
+    external_to_internal_string: BTreeMap<StringId, PointOffsetType>,
     mapping_db_wrapper: DatabaseColumnScheduledDeleteWrapper,
     versions_db_wrapper: DatabaseColumnScheduledDeleteWrapper,
 }
@@ -72,12 +81,14 @@ impl SimpleIdTracker {
         let mut internal_to_external: Vec<PointIdType> = Default::default();
         let mut external_to_internal_num: BTreeMap<u64, PointOffsetType> = Default::default();
         let mut external_to_internal_uuid: BTreeMap<Uuid, PointOffsetType> = Default::default();
+        let mut external_to_internal_string: BTreeMap<StringId, PointOffsetType> =
+            Default::default();
 
         let mapping_db_wrapper = DatabaseColumnScheduledDeleteWrapper::new(
             DatabaseColumnWrapper::new(store.clone(), DB_MAPPING_CF),
         );
         for (key, val) in mapping_db_wrapper.lock_db().iter()? {
-            let external_id = Self::restore_key(&key);
+            let external_id = Self::restore_key(&key)?;
             let internal_id: PointOffsetType =
                 bincode::deserialize::<PointOffsetType>(&val).unwrap();
             if internal_id as usize >= internal_to_external.len() {
@@ -105,6 +116,9 @@ impl SimpleIdTracker {
                     PointIdType::Uuid(uuid) => {
                         external_to_internal_uuid.remove(&uuid);
                     }
+                    PointIdType::String(id) => {
+                        external_to_internal_string.remove(&id);
+                    }
                 }
             }
             deleted.set(internal_id as usize, false);
@@ -116,6 +130,9 @@ impl SimpleIdTracker {
                 PointIdType::Uuid(uuid) => {
                     external_to_internal_uuid.insert(uuid, internal_id);
                 }
+                PointIdType::String(id) => {
+                    external_to_internal_string.insert(id, internal_id);
+                }
             }
         }
 
@@ -124,11 +141,12 @@ impl SimpleIdTracker {
             DatabaseColumnWrapper::new(store, DB_VERSIONS_CF),
         );
         for (key, val) in versions_db_wrapper.lock_db().iter()? {
-            let external_id = Self::restore_key(&key);
+            let external_id = Self::restore_key(&key)?;
             let version: SeqNumberType = bincode::deserialize(&val).unwrap();
             let internal_id = match external_id {
                 PointIdType::NumId(idx) => external_to_internal_num.get(&idx).copied(),
                 PointIdType::Uuid(uuid) => external_to_internal_uuid.get(&uuid).copied(),
+                PointIdType::String(id) => external_to_internal_string.get(&id).copied(),
             };
             if let Some(internal_id) = internal_id {
                 if internal_id as usize >= internal_to_version.len() {
@@ -161,6 +179,7 @@ impl SimpleIdTracker {
             internal_to_version,
             external_to_internal_num,
             external_to_internal_uuid,
+            external_to_internal_string,
             mapping_db_wrapper,
             versions_db_wrapper,
         })
@@ -170,7 +189,7 @@ impl SimpleIdTracker {
         bincode::serialize(&external_to_stored_id(external_id)).unwrap()
     }
 
-    fn restore_key(data: &[u8]) -> PointIdType {
+    fn restore_key(data: &[u8]) -> OperationResult<PointIdType> {
         let stored_external_id: StoredPointId = bincode::deserialize(data).unwrap();
         stored_to_external_id(stored_external_id)
     }
@@ -218,6 +237,7 @@ impl IdTracker for SimpleIdTracker {
         match external_id {
             PointIdType::NumId(idx) => self.external_to_internal_num.get(&idx).copied(),
             PointIdType::Uuid(uuid) => self.external_to_internal_uuid.get(&uuid).copied(),
+            PointIdType::String(id) => self.external_to_internal_string.get(&id).copied(),
         }
     }
 
@@ -242,6 +262,9 @@ impl IdTracker for SimpleIdTracker {
             PointIdType::Uuid(uuid) => {
                 self.external_to_internal_uuid.insert(uuid, internal_id);
             }
+            PointIdType::String(id) => {
+                self.external_to_internal_string.insert(id, internal_id);
+            }
         }
 
         let internal_id = internal_id as usize;
@@ -263,6 +286,7 @@ impl IdTracker for SimpleIdTracker {
         let internal_id = match &external_id {
             PointIdType::NumId(idx) => self.external_to_internal_num.remove(idx),
             PointIdType::Uuid(uuid) => self.external_to_internal_uuid.remove(uuid),
+            PointIdType::String(id) => self.external_to_internal_string.remove(id),
         };
         if let Some(internal_id) = internal_id {
             self.deleted.set(internal_id as usize, true);
@@ -283,8 +307,13 @@ impl IdTracker for SimpleIdTracker {
             .keys()
             .copied()
             .map(PointIdType::Uuid);
-        // order is important here, we want to iterate over the u64 ids first
-        Box::new(iter_num.chain(iter_uuid))
+        let iter_string = self
+            .external_to_internal_string
+            .keys()
+            .copied()
+            .map(PointIdType::String);
+        // order is important here, we want to iterate over the u64 ids first, then uuids
+        Box::new(iter_num.chain(iter_uuid).chain(iter_string))
     }
 
     fn iter_internal(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
@@ -318,25 +347,43 @@ impl IdTracker for SimpleIdTracker {
                 .range(offset..)
                 .map(|(k, v)| (PointIdType::Uuid(*k), *v))
         };
+        let full_string_iter = || {
+            self.external_to_internal_string
+                .iter()
+                .map(|(k, v)| (PointIdType::String(*k), *v))
+        };
+        let offset_string_iter = |offset: StringId| {
+            self.external_to_internal_string
+                .range(offset..)
+                .map(|(k, v)| (PointIdType::String(*k), *v))
+        };
 
         match external_id {
             None => {
                 let iter_num = full_num_iter();
                 let iter_uuid = full_uuid_iter();
-                // order is important here, we want to iterate over the u64 ids first
-                Box::new(iter_num.chain(iter_uuid))
+                let iter_string = full_string_iter();
+                // order is important here, we want to iterate over the u64 ids first, then uuids
+                Box::new(iter_num.chain(iter_uuid).chain(iter_string))
             }
             Some(offset) => match offset {
                 PointIdType::NumId(idx) => {
-                    // Because u64 keys are less that uuid key, we can just use the full iterator for uuid
+                    // Because u64 keys are less that uuid and string keys, we can just use the full iterators for them
                     let iter_num = offset_num_iter(idx);
                     let iter_uuid = full_uuid_iter();
-                    // order is important here, we want to iterate over the u64 ids first
-                    Box::new(iter_num.chain(iter_uuid))
+                    let iter_string = full_string_iter();
+                    // order is important here, we want to iterate over the u64 ids first, then uuids
+                    Box::new(iter_num.chain(iter_uuid).chain(iter_string))
                 }
                 PointIdType::Uuid(uuid) => {
-                    // if offset is a uuid, we can only iterate over uuids
-                    Box::new(offset_uuid_iter(uuid))
+                    // if offset is a uuid, we can skip all u64 ids
+                    let iter_uuid = offset_uuid_iter(uuid);
+                    let iter_string = full_string_iter();
+                    Box::new(iter_uuid.chain(iter_string))
+                }
+                PointIdType::String(id) => {
+                    // if offset is a string, we can only iterate over strings
+                    Box::new(offset_string_iter(id))
                 }
             },
         }
@@ -347,7 +394,9 @@ impl IdTracker for SimpleIdTracker {
     }
 
     fn available_point_count(&self) -> usize {
-        self.external_to_internal_num.len() + self.external_to_internal_uuid.len()
+        self.external_to_internal_num.len()
+            + self.external_to_internal_uuid.len()
+            + self.external_to_internal_string.len()
     }
 
     fn deleted_point_count(&self) -> usize {
@@ -471,4 +520,65 @@ mod tests {
 
         assert_eq!(sorted_from_tracker, values);
     }
+
+    #[test]
+    fn test_string_ids() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+
+        let string_id = |id: &str| PointIdType::String(StringId::try_from(id).unwrap());
+
+        let mut id_tracker = SimpleIdTracker::open(db.clone()).unwrap();
+
+        let values: Vec<PointIdType> = vec![
+            string_id("doc-b"),
+            100.into(),
+            string_id("doc-a"),
+            PointIdType::Uuid(Uuid::from_u128(123_u128)),
+            string_id("doc-c"),
+            10.into(),
+        ];
+
+        for (id, value) in values.iter().enumerate() {
+            id_tracker.set_link(*value, id as PointOffsetType).unwrap();
+        }
+        id_tracker.drop(string_id("doc-c")).unwrap();
+
+        let expected = vec![
+            10.into(),
+            100.into(),
+            PointIdType::Uuid(Uuid::from_u128(123_u128)),
+            string_id("doc-a"),
+            string_id("doc-b"),
+        ];
+        let sorted_from_tracker = id_tracker.iter_from(None).map(|(k, _)| k).collect_vec();
+        assert_eq!(sorted_from_tracker, expected);
+
+        let from_uuid = id_tracker
+            .iter_from(Some(PointIdType::Uuid(Uuid::from_u128(123_u128))))
+            .map(|(k, _)| k)
+            .collect_vec();
+        assert_eq!(from_uuid, expected[2..]);
+
+        let from_string = id_tracker
+            .iter_from(Some(string_id("doc-ab")))
+            .map(|(k, _)| k)
+            .collect_vec();
+        assert_eq!(from_string, vec![string_id("doc-b")]);
+
+        assert_eq!(id_tracker.internal_id(string_id("doc-a")), Some(2));
+        assert_eq!(id_tracker.external_id(0), Some(string_id("doc-b")));
+        assert_eq!(id_tracker.available_point_count(), 5);
+
+        id_tracker.mapping_flusher()().unwrap();
+        id_tracker.versions_flusher()().unwrap();
+        drop(id_tracker);
+
+        // String ids must survive reloading from the mapping storage
+        let id_tracker = SimpleIdTracker::open(db).unwrap();
+        let sorted_from_tracker = id_tracker.iter_from(None).map(|(k, _)| k).collect_vec();
+        assert_eq!(sorted_from_tracker, expected);
+        assert_eq!(id_tracker.internal_id(string_id("doc-a")), Some(2));
+        assert_eq!(id_tracker.internal_id(string_id("doc-c")), None);
+    }
 }
@@ -0,0 +1,42 @@
+use std::path::{Path, PathBuf};
+
+use io::file_operations::{atomic_save_json, read_json};
+use serde::{Deserialize, Serialize};
+
+use crate::common::operation_error::OperationResult;
+
+pub const DISK_ANN_INDEX_CONFIG_FILE: &str = "disk_ann_config.json";
+
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq, Eq)]
+pub struct DiskAnnGraphConfig {
+    /// Max number of neighbors of each node
+    pub max_degree: usize,
+    /// Number of candidates to search on construction
+    pub build_list_size: usize,
+    /// Default number of candidates to keep on search
+    pub search_list_size: usize,
+    /// Number of nodes read from disk at once on search
+    pub beam_width: usize,
+    /// We prefer a full scan search upto (excluding) this number of vectors.
+    ///
+    /// Note: this is number of vectors, not KiloBytes.
+    pub full_scan_threshold: usize,
+    #[serde(default)]
+    pub max_indexing_threads: usize,
+    #[serde(default)]
+    pub indexed_vector_count: Option<usize>,
+}
+
+impl DiskAnnGraphConfig {
+    pub fn get_config_path(path: &Path) -> PathBuf {
+        path.join(DISK_ANN_INDEX_CONFIG_FILE)
+    }
+
+    pub fn load(path: &Path) -> OperationResult<Self> {
+        Ok(read_json(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> OperationResult<()> {
+        Ok(atomic_save_json(path, self)?)
+    }
+}
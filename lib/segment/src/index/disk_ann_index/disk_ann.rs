@@ -0,0 +1,540 @@
+use std::fs::create_dir_all;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
+use common::types::{PointOffsetType, ScoredPointOffset};
+use log::debug;
+use parking_lot::Mutex;
+use rayon::prelude::*;
+
+use super::config::DiskAnnGraphConfig;
+use super::graph_file::{GraphFile, GraphFileHeader};
+use super::vamana::VamanaBuilder;
+use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
+use crate::common::operation_time_statistics::{
+    OperationDurationsAggregator, ScopeDurationMeasurer,
+};
+use crate::common::BYTES_IN_KB;
+use crate::data_types::search_profile::{SearchStrategy, VectorIndexSearchProfile};
+use crate::data_types::vectors::{QueryVector, VectorElementType};
+use crate::id_tracker::{IdTracker, IdTrackerSS};
+use crate::index::exact_scorer::ExactScorer;
+use crate::index::hnsw_index::max_rayon_threads;
+use crate::index::query_estimator::adjust_to_available_vectors;
+use crate::index::sample_estimation::sample_check_cardinality;
+use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::index::visited_pool::VisitedPool;
+use crate::index::{PayloadIndex, VectorIndex};
+use crate::telemetry::VectorIndexSearchesTelemetry;
+use crate::types::{
+    default_quantization_ignore_value, DiskAnnConfig, Filter, SearchParams, VECTOR_ELEMENT_SIZE,
+};
+use crate::vector_storage::{
+    new_raw_scorer, new_stoppable_raw_scorer, RawScorer, VectorStorage, VectorStorageEnum,
+};
+
+/// DiskANN index.
+///
+/// Neighbors of each point are stored on disk next to its full vector, so the graph and the
+/// vectors don't have to fit into RAM. On search, the closest unvisited candidates are read from
+/// disk in batches, their full vectors give the exact scores of the results, and their neighbors
+/// become new candidates, which are scored with quantized vectors if they are available.
+pub struct DiskAnnIndex {
+    id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+    vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+    payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    config: DiskAnnGraphConfig,
+    path: PathBuf,
+    graph: Option<GraphFile>,
+    visited_pool: VisitedPool,
+    searches_telemetry: SearchesTelemetry,
+}
+
+struct SearchesTelemetry {
+    unfiltered_plain: Arc<Mutex<OperationDurationsAggregator>>,
+    unfiltered_graph: Arc<Mutex<OperationDurationsAggregator>>,
+    small_cardinality: Arc<Mutex<OperationDurationsAggregator>>,
+    large_cardinality: Arc<Mutex<OperationDurationsAggregator>>,
+    exact_filtered: Arc<Mutex<OperationDurationsAggregator>>,
+    exact_unfiltered: Arc<Mutex<OperationDurationsAggregator>>,
+}
+
+impl DiskAnnIndex {
+    pub fn open(
+        path: &Path,
+        id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+        vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+        payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+        disk_ann_config: DiskAnnConfig,
+    ) -> OperationResult<Self> {
+        create_dir_all(path)?;
+
+        let config_path = DiskAnnGraphConfig::get_config_path(path);
+        let config = if config_path.exists() {
+            DiskAnnGraphConfig::load(&config_path)?
+        } else {
+            let vector_storage = vector_storage.borrow();
+            let full_scan_threshold = disk_ann_config
+                .full_scan_threshold
+                .saturating_mul(BYTES_IN_KB)
+                / (vector_storage.vector_dim() * VECTOR_ELEMENT_SIZE);
+
+            DiskAnnGraphConfig {
+                max_degree: disk_ann_config.max_degree,
+                build_list_size: disk_ann_config.build_list_size,
+                search_list_size: disk_ann_config.search_list_size,
+                beam_width: disk_ann_config.beam_width,
+                full_scan_threshold,
+                max_indexing_threads: disk_ann_config.max_indexing_threads,
+                indexed_vector_count: None,
+            }
+        };
+
+        let graph_path = GraphFile::get_path(path);
+        let graph = if graph_path.exists() {
+            Some(GraphFile::open(&graph_path)?)
+        } else {
+            None
+        };
+
+        Ok(DiskAnnIndex {
+            id_tracker,
+            vector_storage,
+            payload_index,
+            config,
+            path: path.to_owned(),
+            graph,
+            visited_pool: VisitedPool::new(),
+            searches_telemetry: SearchesTelemetry {
+                unfiltered_plain: OperationDurationsAggregator::new(),
+                unfiltered_graph: OperationDurationsAggregator::new(),
+                small_cardinality: OperationDurationsAggregator::new(),
+                large_cardinality: OperationDurationsAggregator::new(),
+                exact_filtered: OperationDurationsAggregator::new(),
+                exact_unfiltered: OperationDurationsAggregator::new(),
+            },
+        })
+    }
+
+    fn save_config(&self) -> OperationResult<()> {
+        let config_path = DiskAnnGraphConfig::get_config_path(&self.path);
+        self.config.save(&config_path)
+    }
+
+    fn is_quantized_search(&self, params: Option<&SearchParams>) -> bool {
+        let ignore_quantization = params
+            .and_then(|p| p.quantization)
+            .map(|q| q.ignore)
+            .unwrap_or(default_quantization_ignore_value());
+        self.vector_storage.borrow().quantized_storage().is_some() && !ignore_quantization
+    }
+
+    /// Point closest to the centroid of all `points`, the search starts from it
+    fn find_medoid(
+        vector_storage: &VectorStorageEnum,
+        id_tracker: &IdTrackerSS,
+        points: &[PointOffsetType],
+        stopped: &AtomicBool,
+    ) -> OperationResult<PointOffsetType> {
+        let mut centroid = vec![0.0; vector_storage.vector_dim()];
+        for &point in points {
+            check_process_stopped(stopped)?;
+            let vector = vector_storage.get_vector(point);
+            for (sum, value) in centroid.iter_mut().zip(vector.iter()) {
+                *sum += value;
+            }
+        }
+        let count = points.len() as VectorElementType;
+        centroid.iter_mut().for_each(|sum| *sum /= count);
+
+        let raw_scorer = new_raw_scorer(
+            centroid.into(),
+            vector_storage,
+            id_tracker.deleted_point_bitslice(),
+        );
+        raw_scorer
+            .peek_top_iter(&mut points.iter().copied(), 1)
+            .first()
+            .map(|scored| scored.idx)
+            .ok_or_else(|| OperationError::service_error("No points to find medoid among"))
+    }
+
+    fn search_with_graph(
+        &self,
+        vector: &QueryVector,
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+        profile: &mut VectorIndexSearchProfile,
+    ) -> Vec<ScoredPointOffset> {
+        let Some(graph) = &self.graph else {
+            return Vec::new();
+        };
+        if graph.header().num_nodes == 0 || top == 0 {
+            return Vec::new();
+        }
+
+        let list_size = params
+            .and_then(|params| params.hnsw_ef)
+            .unwrap_or(self.config.search_list_size)
+            .max(top);
+        profile.hnsw_ef = Some(list_size);
+        profile.candidates = top;
+
+        let id_tracker = self.id_tracker.borrow();
+        let payload_index = self.payload_index.borrow();
+        let vector_storage = self.vector_storage.borrow();
+
+        // Candidates are navigated with quantized vectors in RAM, if possible
+        let quantized = self.is_quantized_search(params);
+        profile.quantized = quantized;
+        let navigation_scorer = match vector_storage.quantized_storage() {
+            Some(quantized_storage) if quantized => quantized_storage.raw_scorer(
+                vector.to_owned(),
+                id_tracker.deleted_point_bitslice(),
+                vector_storage.deleted_vector_bitslice(),
+                is_stopped,
+            ),
+            _ => new_stoppable_raw_scorer(
+                vector.to_owned(),
+                &vector_storage,
+                id_tracker.deleted_point_bitslice(),
+                is_stopped,
+            ),
+        };
+        let exact_scorer = ExactScorer::new(vector, vector_storage.distance());
+        let filter_context = filter.map(|f| payload_index.filter_context(f));
+
+        let mut visited_list = self.visited_pool.get(graph.header().num_nodes as usize);
+        let entry_point = graph.header().entry_point as PointOffsetType;
+        visited_list.check_and_update_visited(entry_point);
+
+        // Candidates sorted from the closest, with a flag whether they are read from disk already
+        let mut candidates = vec![(
+            ScoredPointOffset {
+                idx: entry_point,
+                score: navigation_scorer.score_point(entry_point),
+            },
+            false,
+        )];
+        let mut nearest = FixedLengthPriorityQueue::new(top);
+        let mut beam = Vec::with_capacity(self.config.beam_width);
+        let mut new_candidates = Vec::new();
+
+        while !is_stopped.load(Ordering::Relaxed) {
+            beam.clear();
+            for (candidate, is_read) in candidates.iter_mut().filter(|(_, is_read)| !is_read) {
+                *is_read = true;
+                beam.push(candidate.idx);
+                if beam.len() >= self.config.beam_width {
+                    break;
+                }
+            }
+            if beam.is_empty() {
+                break;
+            }
+
+            new_candidates.clear();
+            let read_result = graph.read_nodes(&beam, |node, data| {
+                let is_result = navigation_scorer.check_vector(node)
+                    && filter_context
+                        .as_ref()
+                        .map_or(true, |context| context.check(node));
+                if is_result {
+                    nearest.push(ScoredPointOffset {
+                        idx: node,
+                        score: exact_scorer.score(data.vector),
+                    });
+                }
+                new_candidates.extend(
+                    data.neighbors
+                        .iter()
+                        .copied()
+                        .filter(|&neighbor| !visited_list.check_and_update_visited(neighbor)),
+                );
+            });
+            if let Err(err) = read_result {
+                log::error!(
+                    "Failed to read DiskANN graph in {}: {err}",
+                    self.path.display()
+                );
+                break;
+            }
+            profile.scored_vectors += beam.len() + new_candidates.len();
+
+            for &neighbor in &new_candidates {
+                let scored = ScoredPointOffset {
+                    idx: neighbor,
+                    score: navigation_scorer.score_point(neighbor),
+                };
+                let position = candidates.partition_point(|(candidate, _)| *candidate >= scored);
+                if position < list_size {
+                    candidates.insert(position, (scored, false));
+                    candidates.truncate(list_size);
+                }
+            }
+        }
+
+        self.visited_pool.return_back(visited_list);
+        nearest.into_vec()
+    }
+
+    fn search_vectors_with_graph(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+        profile: &mut VectorIndexSearchProfile,
+    ) -> Vec<Vec<ScoredPointOffset>> {
+        profile.strategy = SearchStrategy::DiskAnn;
+        vectors
+            .iter()
+            .map(|vector| self.search_with_graph(vector, filter, top, params, is_stopped, profile))
+            .collect()
+    }
+
+    /// Score all vectors matching the filter, or all vectors if there is no filter
+    fn search_vectors_plain(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        is_stopped: &AtomicBool,
+        profile: &mut VectorIndexSearchProfile,
+    ) -> Vec<Vec<ScoredPointOffset>> {
+        let id_tracker = self.id_tracker.borrow();
+        let payload_index = self.payload_index.borrow();
+        let vector_storage = self.vector_storage.borrow();
+
+        profile.candidates = top;
+        let filtered_points = filter.map(|filter| payload_index.query_points(filter));
+
+        vectors
+            .iter()
+            .map(|&vector| {
+                let raw_scorer = new_stoppable_raw_scorer(
+                    vector.to_owned(),
+                    &vector_storage,
+                    id_tracker.deleted_point_bitslice(),
+                    is_stopped,
+                );
+                match &filtered_points {
+                    Some(points) => {
+                        profile.scored_vectors += points.len();
+                        raw_scorer.peek_top_iter(&mut points.iter().copied(), top)
+                    }
+                    None => {
+                        profile.scored_vectors += vector_storage.available_vector_count();
+                        raw_scorer.peek_top_all(top)
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn search_vectors(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+        profile: &mut VectorIndexSearchProfile,
+    ) -> Vec<Vec<ScoredPointOffset>> {
+        let exact = params.map(|params| params.exact).unwrap_or(false);
+        let telemetry = &self.searches_telemetry;
+
+        let Some(query_filter) = filter else {
+            let available_vector_count = self.vector_storage.borrow().available_vector_count();
+            if exact {
+                let _timer = ScopeDurationMeasurer::new(&telemetry.exact_unfiltered);
+                profile.strategy = SearchStrategy::Exact;
+                return self.search_vectors_plain(vectors, None, top, is_stopped, profile);
+            }
+            if available_vector_count < self.config.full_scan_threshold {
+                let _timer = ScopeDurationMeasurer::new(&telemetry.unfiltered_plain);
+                profile.strategy = SearchStrategy::Plain;
+                return self.search_vectors_plain(vectors, None, top, is_stopped, profile);
+            }
+            let _timer = ScopeDurationMeasurer::new(&telemetry.unfiltered_graph);
+            return self.search_vectors_with_graph(vectors, None, top, params, is_stopped, profile);
+        };
+
+        if exact {
+            let _timer = ScopeDurationMeasurer::new(&telemetry.exact_filtered);
+            profile.strategy = SearchStrategy::Exact;
+            return self.search_vectors_plain(vectors, filter, top, is_stopped, profile);
+        }
+
+        let payload_index = self.payload_index.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        let available_vector_count = vector_storage.available_vector_count();
+        let query_point_cardinality = payload_index.estimate_cardinality(query_filter);
+        let query_cardinality = adjust_to_available_vectors(
+            query_point_cardinality,
+            available_vector_count,
+            id_tracker.available_point_count(),
+        );
+        profile.cardinality = Some((&query_cardinality).into());
+
+        let use_graph = if query_cardinality.max < self.config.full_scan_threshold {
+            false
+        } else if query_cardinality.min > self.config.full_scan_threshold {
+            true
+        } else {
+            // Fast cardinality estimation is not enough, do sample estimation of cardinality
+            let filter_context = payload_index.filter_context(query_filter);
+            profile.cardinality_sampled = true;
+            sample_check_cardinality(
+                id_tracker.sample_ids(Some(vector_storage.deleted_vector_bitslice())),
+                |idx| filter_context.check(idx),
+                self.config.full_scan_threshold,
+                available_vector_count,
+            )
+        };
+
+        if use_graph {
+            let _timer = ScopeDurationMeasurer::new(&telemetry.large_cardinality);
+            self.search_vectors_with_graph(vectors, filter, top, params, is_stopped, profile)
+        } else {
+            let _timer = ScopeDurationMeasurer::new(&telemetry.small_cardinality);
+            profile.strategy = SearchStrategy::PayloadIndex;
+            self.search_vectors_plain(vectors, filter, top, is_stopped, profile)
+        }
+    }
+}
+
+impl VectorIndex for DiskAnnIndex {
+    fn search_with_profile(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+    ) -> (Vec<Vec<ScoredPointOffset>>, VectorIndexSearchProfile) {
+        let mut profile = VectorIndexSearchProfile::default();
+        let result = self.search_vectors(vectors, filter, top, params, is_stopped, &mut profile);
+        (result, profile)
+    }
+
+    fn build_index(&mut self, stopped: &AtomicBool) -> OperationResult<()> {
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+
+        if vector_storage.distance().is_binary() {
+            return Err(OperationError::service_error(
+                "DiskANN index does not support binary vectors",
+            ));
+        }
+
+        let total_vector_count = vector_storage.total_vector_count();
+        let deleted_bitslice = vector_storage.deleted_vector_bitslice();
+        let ids: Vec<_> = id_tracker.iter_ids_excluding(deleted_bitslice).collect();
+
+        debug!("building DiskANN for {} vectors", ids.len());
+
+        let (links, entry_point) = if ids.is_empty() {
+            (vec![Vec::new(); total_vector_count], 0)
+        } else {
+            let entry_point = Self::find_medoid(&vector_storage, &id_tracker, &ids, stopped)?;
+            let builder = VamanaBuilder::new(
+                total_vector_count,
+                self.config.max_degree,
+                self.config.build_list_size,
+                entry_point,
+            );
+
+            let pool = rayon::ThreadPoolBuilder::new()
+                .thread_name(|idx| format!("disk-ann-build-{idx}"))
+                .num_threads(max_rayon_threads(self.config.max_indexing_threads))
+                .build()?;
+
+            for keep_pruned in [false, true] {
+                pool.install(|| {
+                    ids.par_iter().try_for_each(|&vector_id| {
+                        check_process_stopped(stopped)?;
+                        let vector = vector_storage.get_vector(vector_id).to_vec().into();
+                        let raw_scorer = match vector_storage.quantized_storage() {
+                            Some(quantized_storage) => quantized_storage.raw_scorer(
+                                vector,
+                                id_tracker.deleted_point_bitslice(),
+                                deleted_bitslice,
+                                stopped,
+                            ),
+                            None => new_raw_scorer(
+                                vector,
+                                &vector_storage,
+                                id_tracker.deleted_point_bitslice(),
+                            ),
+                        };
+                        builder.link_point(vector_id, raw_scorer.as_ref(), keep_pruned);
+                        Ok::<_, OperationError>(())
+                    })
+                })?;
+            }
+
+            let entry_point = builder.entry_point();
+            (builder.into_links(), entry_point)
+        };
+
+        debug!("finish DiskANN graph, writing it to disk");
+
+        // Nodes are addressed by point offsets, deleted points are stored without links
+        let header = GraphFileHeader::new(
+            total_vector_count,
+            vector_storage.vector_dim(),
+            self.config.max_degree,
+            entry_point,
+        );
+        let graph_path = GraphFile::get_path(&self.path);
+        GraphFile::create(
+            &graph_path,
+            header,
+            links.iter().enumerate().map(|(point_id, point_links)| {
+                (
+                    vector_storage.get_vector(point_id as PointOffsetType),
+                    point_links.as_slice(),
+                )
+            }),
+        )?;
+
+        self.config.indexed_vector_count.replace(ids.len());
+        self.graph = Some(GraphFile::open(&graph_path)?);
+        self.save_config()
+    }
+
+    fn get_telemetry_data(&self) -> VectorIndexSearchesTelemetry {
+        let tm = &self.searches_telemetry;
+
+        VectorIndexSearchesTelemetry {
+            index_name: None,
+            unfiltered_plain: tm.unfiltered_plain.lock().get_statistics(),
+            filtered_plain: Default::default(),
+            unfiltered_hnsw: tm.unfiltered_graph.lock().get_statistics(),
+            filtered_small_cardinality: tm.small_cardinality.lock().get_statistics(),
+            filtered_large_cardinality: tm.large_cardinality.lock().get_statistics(),
+            filtered_exact: tm.exact_filtered.lock().get_statistics(),
+            unfiltered_exact: tm.exact_unfiltered.lock().get_statistics(),
+        }
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        if self.graph.is_some() {
+            vec![GraphFile::get_path(&self.path)]
+        } else {
+            vec![]
+        }
+    }
+
+    fn indexed_vector_count(&self) -> usize {
+        self.config.indexed_vector_count.unwrap_or(0)
+    }
+}
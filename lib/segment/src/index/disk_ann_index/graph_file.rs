@@ -0,0 +1,286 @@
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::mem::size_of;
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+use memmap2::Mmap;
+use memory::madvise::{self, Advice};
+use memory::mmap_ops::{self, transmute_from_u8, transmute_from_u8_to_slice, transmute_to_u8};
+#[cfg(target_os = "linux")]
+use parking_lot::Mutex;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::data_types::vectors::VectorElementType;
+#[cfg(target_os = "linux")]
+use crate::vector_storage::async_io::UringReader;
+use crate::vector_storage::div_ceil;
+
+/// Size of a disk sector.
+/// Nodes never cross the border of a block of sectors, so each node is read with a single request.
+pub const SECTOR_SIZE: usize = 4096;
+
+const DISK_ANN_GRAPH_FILE: &str = "disk_ann_graph.bin";
+
+const GRAPH_FILE_MAGIC: [u8; 8] = *b"diskann1";
+
+/// Max number of io_uring readers to preserve for the following searches
+#[cfg(target_os = "linux")]
+const READER_POOL_KEEP_LIMIT: usize = 16;
+
+/// Layout of the graph file, stored in its first sector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub struct GraphFileHeader {
+    magic: [u8; 8],
+    pub num_nodes: u64,
+    pub dim: u64,
+    pub max_degree: u64,
+    /// Node to start every search from
+    pub entry_point: u64,
+    /// Size of a node: full vector, number of neighbors and the fixed size list of neighbors
+    node_size: u64,
+    /// Size of a block, which is read from disk at once, multiple of [`SECTOR_SIZE`]
+    block_size: u64,
+    nodes_per_block: u64,
+}
+
+impl GraphFileHeader {
+    pub fn new(
+        num_nodes: usize,
+        dim: usize,
+        max_degree: usize,
+        entry_point: PointOffsetType,
+    ) -> Self {
+        let node_size =
+            dim * size_of::<VectorElementType>() + (1 + max_degree) * size_of::<PointOffsetType>();
+        let block_size = div_ceil(node_size, SECTOR_SIZE) * SECTOR_SIZE;
+        Self {
+            magic: GRAPH_FILE_MAGIC,
+            num_nodes: num_nodes as u64,
+            dim: dim as u64,
+            max_degree: max_degree as u64,
+            entry_point: entry_point as u64,
+            node_size: node_size as u64,
+            block_size: block_size as u64,
+            nodes_per_block: (block_size / node_size) as u64,
+        }
+    }
+
+    fn num_blocks(&self) -> usize {
+        div_ceil(self.num_nodes as usize, self.nodes_per_block as usize)
+    }
+
+    fn block_of(&self, node: PointOffsetType) -> PointOffsetType {
+        (node as u64 / self.nodes_per_block) as PointOffsetType
+    }
+
+    /// Extract the node from the block it belongs to
+    fn node_in_block<'a>(&self, block: &'a [u8], node: PointOffsetType) -> Node<'a> {
+        let node_size = self.node_size as usize;
+        let start = (node as u64 % self.nodes_per_block) as usize * node_size;
+        let data = &block[start..start + node_size];
+
+        let vector_size = self.dim as usize * size_of::<VectorElementType>();
+        let (vector, links) = data.split_at(vector_size);
+        let links: &[PointOffsetType] = transmute_from_u8_to_slice(links);
+        let degree = links[0] as usize;
+
+        Node {
+            vector: transmute_from_u8_to_slice(vector),
+            neighbors: &links[1..=degree],
+        }
+    }
+}
+
+/// Node of the graph, as it is stored on disk
+pub struct Node<'a> {
+    pub vector: &'a [VectorElementType],
+    pub neighbors: &'a [PointOffsetType],
+}
+
+/// Graph file, where neighbors of each node are stored next to its full vector
+/// in sector aligned blocks.
+///
+/// Nodes are read with io_uring in batches on Linux, and from the memory map otherwise.
+pub struct GraphFile {
+    header: GraphFileHeader,
+    mmap: Mmap,
+    #[cfg(target_os = "linux")]
+    path: PathBuf,
+    /// Readers for io_uring based async IO, `None` if io_uring is not available
+    #[cfg(target_os = "linux")]
+    uring_readers: Option<Mutex<Vec<UringReader>>>,
+}
+
+impl GraphFile {
+    pub fn get_path(path: &Path) -> PathBuf {
+        path.join(DISK_ANN_GRAPH_FILE)
+    }
+
+    /// Write the graph file, `nodes` must yield all `header.num_nodes` nodes in order
+    pub fn create<'a>(
+        path: &Path,
+        header: GraphFileHeader,
+        nodes: impl Iterator<Item = (Cow<'a, [VectorElementType]>, &'a [PointOffsetType])>,
+    ) -> OperationResult<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        let mut header_sector = vec![0u8; SECTOR_SIZE];
+        let header_bytes = transmute_to_u8(&header);
+        header_sector[..header_bytes.len()].copy_from_slice(header_bytes);
+        writer.write_all(&header_sector)?;
+
+        let node_size = header.node_size as usize;
+        let nodes_per_block = header.nodes_per_block as usize;
+        let mut block = vec![0u8; header.block_size as usize];
+        let mut written = 0;
+
+        for (idx, (vector, neighbors)) in nodes.enumerate() {
+            debug_assert_eq!(vector.len(), header.dim as usize);
+            debug_assert!(neighbors.len() <= header.max_degree as usize);
+
+            let start = (idx % nodes_per_block) * node_size;
+            let node = &mut block[start..start + node_size];
+            let vector_bytes = mmap_ops::transmute_to_u8_slice(vector.as_ref());
+            let (vector_part, links_part) = node.split_at_mut(vector_bytes.len());
+            vector_part.copy_from_slice(vector_bytes);
+            links_part.fill(0);
+            links_part[..size_of::<PointOffsetType>()]
+                .copy_from_slice(&(neighbors.len() as PointOffsetType).to_ne_bytes());
+            let neighbors_bytes = mmap_ops::transmute_to_u8_slice(neighbors);
+            links_part[size_of::<PointOffsetType>()..][..neighbors_bytes.len()]
+                .copy_from_slice(neighbors_bytes);
+
+            written += 1;
+            if written % nodes_per_block == 0 {
+                writer.write_all(&block)?;
+                block.fill(0);
+            }
+        }
+
+        if written != header.num_nodes as usize {
+            return Err(OperationError::service_error(format!(
+                "DiskANN graph has {} nodes, {written} written",
+                header.num_nodes,
+            )));
+        }
+        if written % nodes_per_block != 0 {
+            writer.write_all(&block)?;
+        }
+
+        writer
+            .into_inner()
+            .map_err(|err| err.into_error())?
+            .sync_all()?;
+        Ok(())
+    }
+
+    pub fn open(path: &Path) -> OperationResult<Self> {
+        let mmap = mmap_ops::open_read_mmap(path)?;
+        // Nodes are accessed at random, read ahead is useless
+        madvise::madvise(&mmap, Advice::Random)?;
+
+        if mmap.len() < SECTOR_SIZE {
+            return Err(OperationError::service_error(format!(
+                "DiskANN graph file {} is too small",
+                path.display(),
+            )));
+        }
+        let header: GraphFileHeader = *transmute_from_u8(&mmap[..size_of::<GraphFileHeader>()]);
+        if header.magic != GRAPH_FILE_MAGIC {
+            return Err(OperationError::service_error(format!(
+                "DiskANN graph file {} is corrupted",
+                path.display(),
+            )));
+        }
+        let expected_len = SECTOR_SIZE + header.num_blocks() * header.block_size as usize;
+        if mmap.len() != expected_len {
+            return Err(OperationError::service_error(format!(
+                "DiskANN graph file {} has size {}, expected {expected_len}",
+                path.display(),
+                mmap.len(),
+            )));
+        }
+
+        #[cfg(target_os = "linux")]
+        let uring_readers = match Self::new_uring_reader(path, &header) {
+            Ok(reader) => Some(Mutex::new(vec![reader])),
+            Err(err) => {
+                log::warn!("io_uring is not available for DiskANN index, using mmap: {err}");
+                None
+            }
+        };
+
+        Ok(Self {
+            header,
+            mmap,
+            #[cfg(target_os = "linux")]
+            path: path.to_path_buf(),
+            #[cfg(target_os = "linux")]
+            uring_readers,
+        })
+    }
+
+    #[cfg(target_os = "linux")]
+    fn new_uring_reader(path: &Path, header: &GraphFileHeader) -> OperationResult<UringReader> {
+        UringReader::new(File::open(path)?, header.block_size as usize, SECTOR_SIZE)
+    }
+
+    pub fn header(&self) -> &GraphFileHeader {
+        &self.header
+    }
+
+    /// Read the node from the memory map
+    pub fn node(&self, node: PointOffsetType) -> Node {
+        let block_size = self.header.block_size as usize;
+        let start = SECTOR_SIZE + self.header.block_of(node) as usize * block_size;
+        self.header
+            .node_in_block(&self.mmap[start..start + block_size], node)
+    }
+
+    /// Read `nodes` from disk and call `callback` for each of them, in arbitrary order.
+    ///
+    /// Blocks of all nodes are requested at once, so they are read from disk in parallel.
+    pub fn read_nodes(
+        &self,
+        nodes: &[PointOffsetType],
+        mut callback: impl FnMut(PointOffsetType, Node),
+    ) -> OperationResult<()> {
+        #[cfg(target_os = "linux")]
+        if let Some(uring_readers) = &self.uring_readers {
+            let reader = uring_readers.lock().pop();
+            let mut reader = match reader {
+                Some(reader) => reader,
+                None => Self::new_uring_reader(&self.path, &self.header)?,
+            };
+
+            let mut blocks: Vec<_> = nodes
+                .iter()
+                .map(|&node| self.header.block_of(node))
+                .collect();
+            blocks.sort_unstable();
+            blocks.dedup();
+
+            let result = reader.read_stream_raw(blocks, |_, block_id, block| {
+                for &node in nodes {
+                    if self.header.block_of(node) == block_id {
+                        callback(node, self.header.node_in_block(block, node));
+                    }
+                }
+            });
+
+            let mut readers = uring_readers.lock();
+            if readers.len() < READER_POOL_KEEP_LIMIT {
+                readers.push(reader);
+            }
+            return result;
+        }
+
+        for &node in nodes {
+            callback(node, self.node(node));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,4 @@
+mod config;
+pub mod disk_ann;
+mod graph_file;
+mod vamana;
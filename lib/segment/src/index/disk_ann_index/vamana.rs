@@ -0,0 +1,167 @@
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
+use parking_lot::RwLock;
+
+use crate::index::hnsw_index::search_context::SearchContext;
+use crate::index::visited_pool::VisitedPool;
+use crate::vector_storage::RawScorer;
+
+/// Builder of the Vamana graph, which is the single layer graph of DiskANN.
+///
+/// Points are linked in parallel: each point is searched in the current graph, its neighbors
+/// are selected from the expanded nodes, and reverse links are added to the selected neighbors,
+/// which are pruned again if they exceed the max degree.
+///
+/// The graph is built in two passes. The second pass starts from the graph of the first one
+/// and keeps the closest of the pruned candidates in the free slots. It adds long range edges,
+/// like the relaxed pruning of Vamana, but without assuming that scores are metric distances.
+pub struct VamanaBuilder {
+    max_degree: usize,
+    build_list_size: usize,
+    entry_point: PointOffsetType,
+    links: Vec<RwLock<Vec<PointOffsetType>>>,
+    visited_pool: VisitedPool,
+}
+
+impl VamanaBuilder {
+    pub fn new(
+        num_points: usize,
+        max_degree: usize,
+        build_list_size: usize,
+        entry_point: PointOffsetType,
+    ) -> Self {
+        Self {
+            max_degree,
+            build_list_size,
+            entry_point,
+            links: (0..num_points).map(|_| RwLock::new(Vec::new())).collect(),
+            visited_pool: VisitedPool::new(),
+        }
+    }
+
+    /// Greedy search of the query of `scorer` in the current graph.
+    ///
+    /// Returns all expanded nodes, which are the candidates for neighbors of the query point.
+    fn search(&self, scorer: &dyn RawScorer) -> Vec<ScoredPointOffset> {
+        let mut visited_list = self.visited_pool.get(self.links.len());
+        visited_list.check_and_update_visited(self.entry_point);
+
+        let entry = ScoredPointOffset {
+            idx: self.entry_point,
+            score: scorer.score_point(self.entry_point),
+        };
+        let mut search_context = SearchContext::new(entry, self.build_list_size);
+        let mut expanded = vec![];
+        let mut neighbors = vec![];
+
+        while let Some(candidate) = search_context.candidates.pop() {
+            if candidate.score < search_context.lower_bound() {
+                break;
+            }
+            expanded.push(candidate);
+
+            neighbors.clear();
+            neighbors.extend(
+                self.links[candidate.idx as usize]
+                    .read()
+                    .iter()
+                    .copied()
+                    .filter(|&neighbor| !visited_list.check_and_update_visited(neighbor)),
+            );
+            for &neighbor in &neighbors {
+                search_context.process_candidate(ScoredPointOffset {
+                    idx: neighbor,
+                    score: scorer.score_point(neighbor),
+                });
+            }
+        }
+
+        self.visited_pool.return_back(visited_list);
+        expanded
+    }
+
+    /// Select neighbors from `candidates`, sorted from the closest.
+    ///
+    /// A candidate is skipped, if it is closer to one of the already selected neighbors than to the point.
+    /// If `keep_pruned` is set, free slots are filled with the closest skipped candidates.
+    fn prune(
+        &self,
+        candidates: &[ScoredPointOffset],
+        keep_pruned: bool,
+        mut score_internal: impl FnMut(PointOffsetType, PointOffsetType) -> ScoreType,
+    ) -> Vec<PointOffsetType> {
+        let mut selected = Vec::with_capacity(self.max_degree);
+        let mut pruned = vec![];
+
+        for candidate in candidates {
+            if selected.len() >= self.max_degree {
+                break;
+            }
+            let is_good = selected
+                .iter()
+                .all(|&neighbor| score_internal(candidate.idx, neighbor) <= candidate.score);
+            if is_good {
+                selected.push(candidate.idx);
+            } else if keep_pruned {
+                pruned.push(candidate.idx);
+            }
+        }
+
+        if keep_pruned {
+            let free_slots = self.max_degree - selected.len();
+            selected.extend(pruned.into_iter().take(free_slots));
+        }
+        selected
+    }
+
+    /// Link `point` into the graph, `scorer` must score against the vector of `point`
+    pub fn link_point(&self, point: PointOffsetType, scorer: &dyn RawScorer, keep_pruned: bool) {
+        let mut candidates = self.search(scorer);
+        // Neighbors from the previous pass remain candidates
+        candidates.extend(self.links[point as usize].read().iter().map(|&neighbor| {
+            ScoredPointOffset {
+                idx: neighbor,
+                score: scorer.score_point(neighbor),
+            }
+        }));
+        candidates.retain(|candidate| candidate.idx != point);
+        candidates.sort_unstable_by_key(|candidate| candidate.idx);
+        candidates.dedup_by_key(|candidate| candidate.idx);
+        candidates.sort_unstable_by(|a, b| b.cmp(a));
+
+        let selected = self.prune(&candidates, keep_pruned, |a, b| scorer.score_internal(a, b));
+        *self.links[point as usize].write() = selected.clone();
+
+        for neighbor in selected {
+            let mut neighbor_links = self.links[neighbor as usize].write();
+            if neighbor_links.contains(&point) {
+                continue;
+            }
+            if neighbor_links.len() < self.max_degree {
+                neighbor_links.push(point);
+                continue;
+            }
+
+            let mut neighbor_candidates: Vec<_> = neighbor_links
+                .iter()
+                .copied()
+                .chain(std::iter::once(point))
+                .map(|idx| ScoredPointOffset {
+                    idx,
+                    score: scorer.score_internal(neighbor, idx),
+                })
+                .collect();
+            neighbor_candidates.sort_unstable_by(|a, b| b.cmp(a));
+            *neighbor_links = self.prune(&neighbor_candidates, keep_pruned, |a, b| {
+                scorer.score_internal(a, b)
+            });
+        }
+    }
+
+    pub fn entry_point(&self) -> PointOffsetType {
+        self.entry_point
+    }
+
+    pub fn into_links(self) -> Vec<Vec<PointOffsetType>> {
+        self.links.into_iter().map(RwLock::into_inner).collect()
+    }
+}
@@ -0,0 +1,34 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{QueryVector, VectorElementType};
+use crate::types::Distance;
+use crate::vector_storage::query::reco_query::RecoQuery;
+
+/// Scores the query against full vectors, which are not read from a vector storage
+pub(crate) enum ExactScorer {
+    Nearest(Vec<VectorElementType>, Distance),
+    Recommend(RecoQuery<Vec<VectorElementType>>, Distance),
+}
+
+impl ExactScorer {
+    pub fn new(query: &QueryVector, distance: Distance) -> Self {
+        match query.clone() {
+            QueryVector::Nearest(vector) => {
+                Self::Nearest(distance.preprocess_vector(vector), distance)
+            }
+            QueryVector::Recommend(reco_query) => Self::Recommend(
+                reco_query.transform(|vector| distance.preprocess_vector(vector)),
+                distance,
+            ),
+        }
+    }
+
+    pub fn score(&self, vector: &[VectorElementType]) -> ScoreType {
+        match self {
+            Self::Nearest(query, distance) => distance.similarity(query, vector),
+            Self::Recommend(reco_query, distance) => {
+                reco_query.score_by(|example| distance.similarity(example, vector))
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::data_types::search_profile::{SearchStrategy, VectorIndexSearchProfile};
+use crate::index::field_index::CardinalityEstimation;
+
+/// Filters expected to match a smaller fraction of the available vectors are searched with
+/// the ACORN-style traversal, which walks through neighbors not matching the filter
+pub(super) const ACORN_SELECTIVITY_THRESHOLD: f64 = 0.1;
+
+/// Whether the graph should be traversed through points not matching the filter
+///
+/// Filters matching a small fraction of points break the connectivity of the graph,
+/// so the regular filtered traversal gets stuck in a local neighborhood.
+pub(super) fn use_acorn(
+    query_cardinality: &CardinalityEstimation,
+    available_vectors: usize,
+) -> bool {
+    (query_cardinality.exp as f64) < ACORN_SELECTIVITY_THRESHOLD * available_vectors as f64
+}
+
+/// Choose the traversal of filtered graph search by the estimated selectivity of the filter,
+/// and record it in the search profile
+pub(super) fn select_filtered_traversal(
+    query_cardinality: &CardinalityEstimation,
+    available_vectors: usize,
+    profile: &mut VectorIndexSearchProfile,
+) -> bool {
+    let acorn = use_acorn(query_cardinality, available_vectors);
+    profile.strategy = if acorn {
+        SearchStrategy::AcornHnsw
+    } else {
+        SearchStrategy::FilteredHnsw
+    };
+    acorn
+}
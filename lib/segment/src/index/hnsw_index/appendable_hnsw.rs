@@ -0,0 +1,627 @@
+use std::fs::create_dir_all;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use bitvec::slice::BitSlice;
+use common::types::{PointOffsetType, ScoredPointOffset};
+use log::debug;
+use parking_lot::Mutex;
+use rand::{thread_rng, Rng};
+use rayon::prelude::*;
+
+use super::acorn::select_filtered_traversal;
+use super::graph_links::{GraphLinksConverter, GraphLinksRam};
+use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
+use crate::common::operation_time_statistics::{
+    OperationDurationsAggregator, ScopeDurationMeasurer,
+};
+use crate::common::{Flusher, BYTES_IN_KB};
+use crate::data_types::search_profile::{SearchStrategy, VectorIndexSearchProfile};
+use crate::data_types::vectors::QueryVector;
+use crate::id_tracker::{IdTracker, IdTrackerSS};
+use crate::index::hnsw_index::config::HnswGraphConfig;
+use crate::index::hnsw_index::graph_layers::{GraphLayers, LayersContainer};
+use crate::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
+use crate::index::hnsw_index::max_rayon_threads;
+use crate::index::hnsw_index::point_scorer::FilteredScorer;
+use crate::index::query_estimator::adjust_to_available_vectors;
+use crate::index::sample_estimation::sample_check_cardinality;
+use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::index::visited_pool::VisitedPool;
+use crate::index::{PayloadIndex, VectorIndex};
+use crate::payload_storage::FilterContext;
+use crate::telemetry::VectorIndexSearchesTelemetry;
+use crate::types::{Filter, HnswConfig, SearchParams, VECTOR_ELEMENT_SIZE};
+use crate::vector_storage::{
+    new_raw_scorer, new_stoppable_raw_scorer, CountingRawScorer, RawScorer, VectorStorage,
+    VectorStorageEnum,
+};
+
+const HNSW_USE_HEURISTIC: bool = true;
+
+/// Number of additional entry points, kept in case the main ones get deleted
+const APPENDABLE_HNSW_ENTRY_POINTS: usize = 10;
+
+/// Links to deleted points are repaired, once the number of points deleted since the last
+/// repair exceeds this fraction of the points in the graph
+const REPAIR_DELETED_RATIO: f64 = 0.1;
+
+/// HNSW index of appendable segments.
+///
+/// New and changed vectors are linked into the graph as they arrive, so search over recent
+/// data doesn't fall back to full scan until the segment is indexed by the optimizer.
+/// Deleted points stay in the graph until enough of them accumulate, then the links to them
+/// are replaced with links to their neighbors. The graph is persisted in the same format as
+/// the one of [`super::hnsw::HNSWIndex`] on flush.
+pub struct AppendableHnswIndex {
+    id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+    vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+    payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    config: HnswGraphConfig,
+    path: PathBuf,
+    graph: GraphLayersBuilder,
+    /// Number of deleted points and vectors, as of the last repair of the graph
+    repaired_deleted_count: AtomicUsize,
+    /// Whether the graph was changed since the last flush
+    graph_dirty: Arc<AtomicBool>,
+    searches_telemetry: SearchesTelemetry,
+}
+
+struct SearchesTelemetry {
+    unfiltered_plain: Arc<Mutex<OperationDurationsAggregator>>,
+    unfiltered_hnsw: Arc<Mutex<OperationDurationsAggregator>>,
+    small_cardinality: Arc<Mutex<OperationDurationsAggregator>>,
+    large_cardinality: Arc<Mutex<OperationDurationsAggregator>>,
+    exact_filtered: Arc<Mutex<OperationDurationsAggregator>>,
+    exact_unfiltered: Arc<Mutex<OperationDurationsAggregator>>,
+}
+
+/// Excludes the point being linked from its own neighbors
+struct ExceptPointFilterContext {
+    point_id: PointOffsetType,
+}
+
+impl FilterContext for ExceptPointFilterContext {
+    fn check(&self, point_id: PointOffsetType) -> bool {
+        point_id != self.point_id
+    }
+}
+
+fn is_deleted(
+    point_deleted: &BitSlice,
+    vector_storage: &VectorStorageEnum,
+    point_id: PointOffsetType,
+) -> bool {
+    // Points not yet added to the id tracker are not deleted
+    point_deleted
+        .get(point_id as usize)
+        .map_or(false, |deleted| *deleted)
+        || vector_storage.is_deleted_vector(point_id)
+}
+
+impl AppendableHnswIndex {
+    pub fn open(
+        path: &Path,
+        id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+        vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+        payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+        hnsw_config: HnswConfig,
+    ) -> OperationResult<Self> {
+        create_dir_all(path)?;
+
+        let config_path = HnswGraphConfig::get_config_path(path);
+        let config = if config_path.exists() {
+            HnswGraphConfig::load(&config_path)?
+        } else {
+            let vector_storage = vector_storage.borrow();
+            let full_scan_threshold = hnsw_config.full_scan_threshold.saturating_mul(BYTES_IN_KB)
+                / (vector_storage.vector_dim() * VECTOR_ELEMENT_SIZE);
+
+            let config = HnswGraphConfig::new(
+                hnsw_config.m,
+                hnsw_config.ef_construct,
+                full_scan_threshold,
+                hnsw_config.max_indexing_threads,
+                None,
+                0,
+            );
+            config.save(&config_path)?;
+            config
+        };
+
+        let graph_path = GraphLayers::<GraphLinksRam>::get_path(path);
+        let graph_links_path = GraphLayers::<GraphLinksRam>::get_links_path(path);
+        let graph = if graph_path.exists() {
+            let graph_layers = GraphLayers::<GraphLinksRam>::load(&graph_path, &graph_links_path)?;
+            GraphLayersBuilder::from_graph_layers(graph_layers, HNSW_USE_HEURISTIC)
+        } else {
+            Self::new_graph(&config)
+        };
+
+        let mut index = AppendableHnswIndex {
+            id_tracker,
+            vector_storage,
+            payload_index,
+            config,
+            path: path.to_owned(),
+            graph,
+            repaired_deleted_count: AtomicUsize::new(0),
+            graph_dirty: Arc::new(AtomicBool::new(false)),
+            searches_telemetry: SearchesTelemetry {
+                unfiltered_plain: OperationDurationsAggregator::new(),
+                unfiltered_hnsw: OperationDurationsAggregator::new(),
+                small_cardinality: OperationDurationsAggregator::new(),
+                large_cardinality: OperationDurationsAggregator::new(),
+                exact_filtered: OperationDurationsAggregator::new(),
+                exact_unfiltered: OperationDurationsAggregator::new(),
+            },
+        };
+
+        // Vectors stored after the last flush of the graph
+        let total_vector_count = index.vector_storage.borrow().total_vector_count();
+        let mut rng = thread_rng();
+        for point_id in index.graph.num_points()..total_vector_count {
+            index.link_point(point_id as PointOffsetType, &mut rng);
+        }
+
+        Ok(index)
+    }
+
+    fn new_graph(config: &HnswGraphConfig) -> GraphLayersBuilder {
+        GraphLayersBuilder::new(
+            0,
+            config.m,
+            config.m0,
+            config.ef_construct,
+            APPENDABLE_HNSW_ENTRY_POINTS,
+            HNSW_USE_HEURISTIC,
+        )
+    }
+
+    /// Link the vector of `point_id` into the graph.
+    /// Changed vectors keep their level, their links are selected again.
+    fn link_point<R: Rng + ?Sized>(&mut self, point_id: PointOffsetType, rng: &mut R) {
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+
+        if (point_id as usize) < self.graph.num_points() {
+            self.graph.clear_links(point_id);
+        } else {
+            // Points without vectors are kept on the bottom level without links
+            for missing_id in self.graph.num_points()..point_id as usize {
+                self.graph.set_levels(missing_id as PointOffsetType, 0);
+            }
+            let level = self.graph.get_random_layer(rng);
+            self.graph.set_levels(point_id, level);
+        }
+
+        if is_deleted(
+            id_tracker.deleted_point_bitslice(),
+            &vector_storage,
+            point_id,
+        ) {
+            return;
+        }
+
+        let vector = vector_storage.get_vector(point_id).to_vec().into();
+        let raw_scorer =
+            new_raw_scorer(vector, &vector_storage, id_tracker.deleted_point_bitslice());
+        let filter_context = ExceptPointFilterContext { point_id };
+        let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&filter_context));
+        self.graph.link_new_point(point_id, points_scorer);
+        self.graph_dirty.store(true, Ordering::Relaxed);
+    }
+
+    /// Replace links to deleted points, if enough points were deleted since the last repair
+    fn repair_deleted_links(&self) {
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+
+        let deleted_count =
+            id_tracker.deleted_point_count() + vector_storage.deleted_vector_count();
+        let repaired_deleted_count = self.repaired_deleted_count.load(Ordering::Relaxed);
+        let newly_deleted = deleted_count.saturating_sub(repaired_deleted_count);
+        if newly_deleted as f64 <= REPAIR_DELETED_RATIO * self.graph.num_points() as f64 {
+            return;
+        }
+
+        let point_deleted = id_tracker.deleted_point_bitslice();
+        let Some(any_point) = id_tracker
+            .iter_ids_excluding(vector_storage.deleted_vector_bitslice())
+            .next()
+        else {
+            return;
+        };
+
+        debug!("repairing appendable HNSW after {newly_deleted} deletions");
+
+        // Only scores between stored vectors are used, the query is irrelevant
+        let raw_scorer = new_raw_scorer(
+            vector_storage.get_vector(any_point).to_vec().into(),
+            &vector_storage,
+            point_deleted,
+        );
+        self.graph.repair_links(
+            |point_id| is_deleted(point_deleted, &vector_storage, point_id),
+            |point_a, point_b| raw_scorer.score_internal(point_a, point_b),
+        );
+        self.repaired_deleted_count
+            .store(deleted_count, Ordering::Relaxed);
+        self.graph_dirty.store(true, Ordering::Relaxed);
+    }
+
+    fn save_graph(
+        path: &Path,
+        graph_layers: &GraphLayers<GraphLinksRam>,
+        links: Vec<LayersContainer>,
+    ) -> OperationResult<()> {
+        // Links are saved first, so entry points never refer to points missing in the links
+        GraphLinksConverter::new(links)
+            .save_as(&GraphLayers::<GraphLinksRam>::get_links_path(path))?;
+        graph_layers.save(&GraphLayers::<GraphLinksRam>::get_path(path))
+    }
+
+    /// Graph header with the current entry points, links are saved separately
+    fn graph_header(&self) -> GraphLayers<GraphLinksRam> {
+        GraphLayers {
+            m: self.config.m,
+            m0: self.config.m0,
+            ef_construct: self.config.ef_construct,
+            links: GraphLinksRam::default(),
+            entry_points: self.graph.get_entry_points().clone(),
+            visited_pool: VisitedPool::new(),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn search_with_graph(
+        &self,
+        vector: &QueryVector,
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        acorn: bool,
+        is_stopped: &AtomicBool,
+        count_scored: bool,
+        profile: &mut VectorIndexSearchProfile,
+    ) -> Vec<ScoredPointOffset> {
+        let ef = params
+            .and_then(|params| params.hnsw_ef)
+            .unwrap_or(self.config.ef);
+        profile.hnsw_ef = Some(ef);
+        profile.candidates = top;
+
+        let id_tracker = self.id_tracker.borrow();
+        let payload_index = self.payload_index.borrow();
+        let vector_storage = self.vector_storage.borrow();
+
+        let raw_scorer = new_stoppable_raw_scorer(
+            vector.to_owned(),
+            &vector_storage,
+            id_tracker.deleted_point_bitslice(),
+            is_stopped,
+        );
+        // Counting scored vectors has an overhead, only do it when profiling
+        let counting_scorer = count_scored.then(|| CountingRawScorer::new(raw_scorer.as_ref()));
+        let scorer: &dyn RawScorer = match &counting_scorer {
+            Some(counting_scorer) => counting_scorer,
+            None => raw_scorer.as_ref(),
+        };
+        let filter_context = filter.map(|f| payload_index.filter_context(f));
+        let points_scorer = FilteredScorer::new(scorer, filter_context.as_deref());
+
+        let result = self.graph.search(top, ef, points_scorer, acorn);
+        if let Some(counting_scorer) = &counting_scorer {
+            profile.scored_vectors += counting_scorer.scored();
+        }
+        result
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn search_vectors_with_graph(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        acorn: bool,
+        is_stopped: &AtomicBool,
+        count_scored: bool,
+        profile: &mut VectorIndexSearchProfile,
+    ) -> Vec<Vec<ScoredPointOffset>> {
+        vectors
+            .iter()
+            .map(|vector| {
+                self.search_with_graph(
+                    vector,
+                    filter,
+                    top,
+                    params,
+                    acorn,
+                    is_stopped,
+                    count_scored,
+                    profile,
+                )
+            })
+            .collect()
+    }
+
+    /// Score all vectors matching the filter, or all vectors if there is no filter
+    fn search_vectors_plain(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        is_stopped: &AtomicBool,
+        profile: &mut VectorIndexSearchProfile,
+    ) -> Vec<Vec<ScoredPointOffset>> {
+        let id_tracker = self.id_tracker.borrow();
+        let payload_index = self.payload_index.borrow();
+        let vector_storage = self.vector_storage.borrow();
+
+        profile.candidates = top;
+        let filtered_points = filter.map(|filter| payload_index.query_points(filter));
+
+        vectors
+            .iter()
+            .map(|&vector| {
+                let raw_scorer = new_stoppable_raw_scorer(
+                    vector.to_owned(),
+                    &vector_storage,
+                    id_tracker.deleted_point_bitslice(),
+                    is_stopped,
+                );
+                match &filtered_points {
+                    Some(points) => {
+                        profile.scored_vectors += points.len();
+                        raw_scorer.peek_top_iter(&mut points.iter().copied(), top)
+                    }
+                    None => {
+                        profile.scored_vectors += vector_storage.available_vector_count();
+                        raw_scorer.peek_top_all(top)
+                    }
+                }
+            })
+            .collect()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn search_vectors(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+        count_scored: bool,
+        profile: &mut VectorIndexSearchProfile,
+    ) -> Vec<Vec<ScoredPointOffset>> {
+        let exact = params.map(|params| params.exact).unwrap_or(false);
+        let telemetry = &self.searches_telemetry;
+
+        let Some(query_filter) = filter else {
+            let available_vector_count = self.vector_storage.borrow().available_vector_count();
+            if exact {
+                let _timer = ScopeDurationMeasurer::new(&telemetry.exact_unfiltered);
+                profile.strategy = SearchStrategy::Exact;
+                return self.search_vectors_plain(vectors, None, top, is_stopped, profile);
+            }
+            if available_vector_count < self.config.full_scan_threshold {
+                let _timer = ScopeDurationMeasurer::new(&telemetry.unfiltered_plain);
+                profile.strategy = SearchStrategy::Plain;
+                return self.search_vectors_plain(vectors, None, top, is_stopped, profile);
+            }
+            let _timer = ScopeDurationMeasurer::new(&telemetry.unfiltered_hnsw);
+            profile.strategy = SearchStrategy::Hnsw;
+            return self.search_vectors_with_graph(
+                vectors,
+                None,
+                top,
+                params,
+                false,
+                is_stopped,
+                count_scored,
+                profile,
+            );
+        };
+
+        if exact {
+            let _timer = ScopeDurationMeasurer::new(&telemetry.exact_filtered);
+            profile.strategy = SearchStrategy::Exact;
+            return self.search_vectors_plain(vectors, filter, top, is_stopped, profile);
+        }
+
+        let payload_index = self.payload_index.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        let available_vector_count = vector_storage.available_vector_count();
+        let query_point_cardinality = payload_index.estimate_cardinality(query_filter);
+        let query_cardinality = adjust_to_available_vectors(
+            query_point_cardinality,
+            available_vector_count,
+            id_tracker.available_point_count(),
+        );
+        profile.cardinality = Some((&query_cardinality).into());
+
+        let use_graph = if query_cardinality.max < self.config.full_scan_threshold {
+            false
+        } else if query_cardinality.min > self.config.full_scan_threshold {
+            true
+        } else {
+            // Fast cardinality estimation is not enough, do sample estimation of cardinality
+            let filter_context = payload_index.filter_context(query_filter);
+            profile.cardinality_sampled = true;
+            sample_check_cardinality(
+                id_tracker.sample_ids(Some(vector_storage.deleted_vector_bitslice())),
+                |idx| filter_context.check(idx),
+                self.config.full_scan_threshold,
+                available_vector_count,
+            )
+        };
+
+        if use_graph {
+            let _timer = ScopeDurationMeasurer::new(&telemetry.large_cardinality);
+            let acorn =
+                select_filtered_traversal(&query_cardinality, available_vector_count, profile);
+            self.search_vectors_with_graph(
+                vectors,
+                filter,
+                top,
+                params,
+                acorn,
+                is_stopped,
+                count_scored,
+                profile,
+            )
+        } else {
+            let _timer = ScopeDurationMeasurer::new(&telemetry.small_cardinality);
+            profile.strategy = SearchStrategy::PayloadIndex;
+            self.search_vectors_plain(vectors, filter, top, is_stopped, profile)
+        }
+    }
+}
+
+impl VectorIndex for AppendableHnswIndex {
+    fn search(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+    ) -> Vec<Vec<ScoredPointOffset>> {
+        let mut profile = VectorIndexSearchProfile::default();
+        self.search_vectors(
+            vectors,
+            filter,
+            top,
+            params,
+            is_stopped,
+            false,
+            &mut profile,
+        )
+    }
+
+    fn search_with_profile(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+    ) -> (Vec<Vec<ScoredPointOffset>>, VectorIndexSearchProfile) {
+        let mut profile = VectorIndexSearchProfile::default();
+        let result =
+            self.search_vectors(vectors, filter, top, params, is_stopped, true, &mut profile);
+        (result, profile)
+    }
+
+    fn build_index(&mut self, stopped: &AtomicBool) -> OperationResult<()> {
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let mut rng = thread_rng();
+
+        let total_vector_count = vector_storage.total_vector_count();
+        debug!("building appendable HNSW for {total_vector_count} vectors");
+
+        let mut graph = Self::new_graph(&self.config);
+        for point_id in 0..total_vector_count as PointOffsetType {
+            let level = graph.get_random_layer(&mut rng);
+            graph.set_levels(point_id, level);
+        }
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .thread_name(|idx| format!("hnsw-build-{idx}"))
+            .num_threads(max_rayon_threads(self.config.max_indexing_threads))
+            .build()?;
+
+        let point_deleted = id_tracker.deleted_point_bitslice();
+        pool.install(|| {
+            (0..total_vector_count as PointOffsetType)
+                .into_par_iter()
+                .filter(|&point_id| !is_deleted(point_deleted, &vector_storage, point_id))
+                .try_for_each(|point_id| {
+                    check_process_stopped(stopped)?;
+                    let vector = vector_storage.get_vector(point_id).to_vec().into();
+                    let raw_scorer = new_raw_scorer(vector, &vector_storage, point_deleted);
+                    let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), None);
+                    graph.link_new_point(point_id, points_scorer);
+                    Ok::<_, OperationError>(())
+                })
+        })?;
+
+        let deleted_count =
+            id_tracker.deleted_point_count() + vector_storage.deleted_vector_count();
+        drop(vector_storage);
+        drop(id_tracker);
+
+        self.graph = graph;
+        self.repaired_deleted_count
+            .store(deleted_count, Ordering::Relaxed);
+        self.graph_dirty.store(false, Ordering::Relaxed);
+        Self::save_graph(
+            &self.path,
+            &self.graph_header(),
+            self.graph.links_snapshot(),
+        )
+    }
+
+    fn get_telemetry_data(&self) -> VectorIndexSearchesTelemetry {
+        let tm = &self.searches_telemetry;
+
+        VectorIndexSearchesTelemetry {
+            index_name: None,
+            unfiltered_plain: tm.unfiltered_plain.lock().get_statistics(),
+            filtered_plain: Default::default(),
+            unfiltered_hnsw: tm.unfiltered_hnsw.lock().get_statistics(),
+            filtered_small_cardinality: tm.small_cardinality.lock().get_statistics(),
+            filtered_large_cardinality: tm.large_cardinality.lock().get_statistics(),
+            filtered_exact: tm.exact_filtered.lock().get_statistics(),
+            unfiltered_exact: tm.exact_unfiltered.lock().get_statistics(),
+        }
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        [
+            HnswGraphConfig::get_config_path(&self.path),
+            GraphLayers::<GraphLinksRam>::get_path(&self.path),
+            GraphLayers::<GraphLinksRam>::get_links_path(&self.path),
+        ]
+        .into_iter()
+        .filter(|path| path.exists())
+        .collect()
+    }
+
+    fn indexed_vector_count(&self) -> usize {
+        // All available vectors are linked on insertion
+        self.vector_storage.borrow().available_vector_count()
+    }
+
+    fn update_vector(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        self.repair_deleted_links();
+        self.link_point(id, &mut thread_rng());
+        Ok(())
+    }
+
+    fn flusher(&self) -> Flusher {
+        self.repair_deleted_links();
+        if !self.graph_dirty.swap(false, Ordering::Relaxed) {
+            return Box::new(|| Ok(()));
+        }
+
+        let path = self.path.clone();
+        let graph_header = self.graph_header();
+        let links = self.graph.links_snapshot();
+        let graph_dirty = self.graph_dirty.clone();
+
+        Box::new(move || {
+            Self::save_graph(&path, &graph_header, links).map_err(|err| {
+                // Retry on the next flush
+                graph_dirty.store(true, Ordering::Relaxed);
+                err
+            })
+        })
+    }
+}
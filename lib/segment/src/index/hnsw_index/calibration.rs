@@ -0,0 +1,246 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+use bitvec::prelude::BitVec;
+use bitvec::slice::BitSlice;
+use common::types::{PointOffsetType, ScoredPointOffset};
+use io::file_operations::{atomic_save_json, read_json};
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng};
+
+use super::acorn::ACORN_SELECTIVITY_THRESHOLD;
+use super::graph_layers::GraphLayers;
+use super::graph_links::GraphLinks;
+use super::point_scorer::FilteredScorer;
+use crate::common::operation_error::{check_process_stopped, OperationResult};
+use crate::data_types::vectors::QueryVector;
+use crate::id_tracker::{IdTracker, IdTrackerSS};
+use crate::payload_storage::FilterContext;
+use crate::types::{RecallCalibrationPoint, SearchCalibration};
+use crate::vector_storage::{
+    new_stoppable_raw_scorer, RawScorer, VectorStorage, VectorStorageEnum,
+};
+
+pub const HNSW_CALIBRATION_FILE: &str = "hnsw_calibration.json";
+
+/// Number of stored vectors, used as calibration queries
+const CALIBRATION_QUERIES: usize = 32;
+
+/// Number of nearest neighbors, recall is measured on
+const CALIBRATION_TOP: usize = 10;
+
+/// Fractions of vectors, matching the filter of calibration queries
+const CALIBRATION_SELECTIVITIES: [f64; 3] = [1.0, 0.25, 0.05];
+
+const CALIBRATION_EFS: [usize; 6] = [16, 32, 64, 128, 256, 512];
+
+/// Oversampling factors, measured if vectors are quantized
+const CALIBRATION_OVERSAMPLINGS: [f64; 3] = [1.0, 2.0, 4.0];
+
+pub fn get_calibration_path(path: &Path) -> PathBuf {
+    path.join(HNSW_CALIBRATION_FILE)
+}
+
+pub fn load_calibration(path: &Path) -> OperationResult<SearchCalibration> {
+    Ok(read_json(path)?)
+}
+
+pub fn save_calibration(path: &Path, calibration: &SearchCalibration) -> OperationResult<()> {
+    Ok(atomic_save_json(path, calibration)?)
+}
+
+/// Simulates a filter of given selectivity, query point itself is excluded from the results
+struct CalibrationFilterContext<'a> {
+    selected: &'a BitSlice,
+    query_point: PointOffsetType,
+}
+
+impl FilterContext for CalibrationFilterContext<'_> {
+    fn check(&self, point_id: PointOffsetType) -> bool {
+        point_id != self.query_point
+            && self
+                .selected
+                .get(point_id as usize)
+                .map_or(false, |selected| *selected)
+    }
+}
+
+/// Measurement of the recall of the graph search for a grid of filter selectivities, `ef`
+/// values and quantization oversampling factors.
+///
+/// Stored vectors are sampled as queries, results are compared with the exact search
+/// among the points, matching a random filter of each selectivity.
+/// Queries are measured one at a time with [`CalibrationProgress::step`], so the segment
+/// doesn't have to be locked for the whole calibration.
+pub struct CalibrationProgress {
+    queries: Vec<PointOffsetType>,
+    oversamplings: Vec<Option<f64>>,
+    /// Index of the currently measured selectivity in [`CALIBRATION_SELECTIVITIES`]
+    selectivity_idx: usize,
+    /// Index of the next query to measure in `queries`
+    query_idx: usize,
+    /// Points matching the random filter of the current selectivity
+    selected: BitVec,
+    /// Measurements for the current selectivity, indexed as `oversamplings x efs`
+    found: Vec<usize>,
+    expected_total: usize,
+    points: Vec<RecallCalibrationPoint>,
+}
+
+impl CalibrationProgress {
+    pub fn new(id_tracker: &IdTrackerSS, vector_storage: &VectorStorageEnum) -> Self {
+        let available_ids: Vec<_> = id_tracker
+            .iter_ids_excluding(vector_storage.deleted_vector_bitslice())
+            .collect();
+        let queries = if available_ids.len() <= CALIBRATION_TOP {
+            vec![]
+        } else {
+            available_ids
+                .choose_multiple(&mut thread_rng(), CALIBRATION_QUERIES)
+                .copied()
+                .collect()
+        };
+
+        let oversamplings: Vec<Option<f64>> = if vector_storage.quantized_storage().is_some() {
+            CALIBRATION_OVERSAMPLINGS.into_iter().map(Some).collect()
+        } else {
+            vec![None]
+        };
+        let found = vec![0; oversamplings.len() * CALIBRATION_EFS.len()];
+
+        Self {
+            queries,
+            oversamplings,
+            selectivity_idx: 0,
+            query_idx: 0,
+            selected: BitVec::new(),
+            found,
+            expected_total: 0,
+            points: Vec::new(),
+        }
+    }
+
+    /// Measure the next query, returns the calibration once all queries are measured
+    pub fn step<TGraphLinks: GraphLinks>(
+        &mut self,
+        graph: &GraphLayers<TGraphLinks>,
+        id_tracker: &IdTrackerSS,
+        vector_storage: &VectorStorageEnum,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Option<SearchCalibration>> {
+        check_process_stopped(stopped)?;
+
+        // Too few points to measure the recall on
+        if self.queries.is_empty() {
+            return Ok(Some(SearchCalibration::default()));
+        }
+
+        let selectivity = CALIBRATION_SELECTIVITIES[self.selectivity_idx];
+        let total_vector_count = vector_storage.total_vector_count();
+        if self.query_idx == 0 {
+            let mut rng = thread_rng();
+            self.selected = (0..total_vector_count)
+                .map(|_| rng.gen_bool(selectivity))
+                .collect();
+        }
+        let acorn = selectivity < ACORN_SELECTIVITY_THRESHOLD;
+
+        let query_point = self.queries[self.query_idx];
+        let query: QueryVector = vector_storage.get_vector(query_point).to_vec().into();
+        let filter_context = CalibrationFilterContext {
+            selected: &self.selected,
+            query_point,
+        };
+        let point_deleted = id_tracker.deleted_point_bitslice();
+        let quantized_storage = vector_storage.quantized_storage();
+
+        let exact_scorer =
+            new_stoppable_raw_scorer(query.clone(), vector_storage, point_deleted, stopped);
+        let expected: HashSet<_> = exact_scorer
+            .peek_top_iter(
+                &mut id_tracker
+                    .iter_ids_excluding(vector_storage.deleted_vector_bitslice())
+                    .filter(|&idx| filter_context.check(idx)),
+                CALIBRATION_TOP,
+            )
+            .into_iter()
+            .map(|scored| scored.idx)
+            .collect();
+        self.expected_total += expected.len();
+
+        let search_scorer = match quantized_storage {
+            Some(quantized_storage) => quantized_storage.raw_scorer(
+                query.clone(),
+                point_deleted,
+                vector_storage.deleted_vector_bitslice(),
+                stopped,
+            ),
+            None => new_stoppable_raw_scorer(query.clone(), vector_storage, point_deleted, stopped),
+        };
+
+        for (oversampling_idx, oversampling) in self.oversamplings.iter().enumerate() {
+            let oversampled_top = (oversampling.unwrap_or(1.0) * CALIBRATION_TOP as f64) as usize;
+            for (ef_idx, &ef) in CALIBRATION_EFS.iter().enumerate() {
+                let points_scorer =
+                    FilteredScorer::new(search_scorer.as_ref(), Some(&filter_context));
+                let mut result = if acorn {
+                    graph.search_acorn(oversampled_top, ef, points_scorer)
+                } else {
+                    graph.search(oversampled_top, ef, points_scorer)
+                };
+                if oversampling.is_some() {
+                    result = rescore(&result, exact_scorer.as_ref());
+                }
+                self.found[oversampling_idx * CALIBRATION_EFS.len() + ef_idx] += result
+                    .iter()
+                    .take(CALIBRATION_TOP)
+                    .filter(|scored| expected.contains(&scored.idx))
+                    .count();
+            }
+        }
+        // Measurements are stopped, if the search was interrupted
+        check_process_stopped(stopped)?;
+
+        self.query_idx += 1;
+        if self.query_idx < self.queries.len() {
+            return Ok(None);
+        }
+
+        // All queries of the current selectivity are measured
+        if self.expected_total > 0 {
+            for (oversampling_idx, &oversampling) in self.oversamplings.iter().enumerate() {
+                for (ef_idx, &hnsw_ef) in CALIBRATION_EFS.iter().enumerate() {
+                    let found = self.found[oversampling_idx * CALIBRATION_EFS.len() + ef_idx];
+                    self.points.push(RecallCalibrationPoint {
+                        selectivity,
+                        hnsw_ef,
+                        oversampling,
+                        recall: found as f64 / self.expected_total as f64,
+                    });
+                }
+            }
+        }
+        self.found.iter_mut().for_each(|found| *found = 0);
+        self.expected_total = 0;
+        self.query_idx = 0;
+        self.selectivity_idx += 1;
+
+        if self.selectivity_idx < CALIBRATION_SELECTIVITIES.len() {
+            return Ok(None);
+        }
+        Ok(Some(SearchCalibration {
+            num_queries: self.queries.len(),
+            points: std::mem::take(&mut self.points),
+        }))
+    }
+}
+
+/// Score quantized search results with original vectors, same as the rescoring of the search
+fn rescore(result: &[ScoredPointOffset], exact_scorer: &dyn RawScorer) -> Vec<ScoredPointOffset> {
+    let mut ids_iterator = result.iter().map(|scored| scored.idx);
+    let mut rescored = exact_scorer.score_points_unfiltered(&mut ids_iterator);
+    rescored.sort_unstable();
+    rescored.reverse();
+    rescored
+}
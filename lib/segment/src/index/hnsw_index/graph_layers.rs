@@ -11,7 +11,7 @@ use serde::{Deserialize, Serialize};
 use super::graph_links::{GraphLinks, GraphLinksMmap};
 use crate::common::operation_error::OperationResult;
 use crate::common::utils::rev_range;
-use crate::index::hnsw_index::entry_points::EntryPoints;
+use crate::index::hnsw_index::entry_points::{EntryPoint, EntryPoints};
 use crate::index::hnsw_index::graph_links::GraphLinksConverter;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::index::hnsw_index::search_context::SearchContext;
@@ -91,6 +91,60 @@ pub trait GraphLayersBase {
         }
     }
 
+    /// Greedy search for closest points within a single graph layer, which also walks through
+    /// neighbors not matching the filter (ACORN-style).
+    ///
+    /// Neighbors, which don't match the filter, are not scored. Instead, their own neighbors
+    /// are scored together with the direct ones. It keeps the graph connected for selective
+    /// filters, which would otherwise cut most of the links of the matching points.
+    fn _search_on_level_acorn(
+        &self,
+        searcher: &mut SearchContext,
+        level: usize,
+        visited_list: &mut VisitedList,
+        points_scorer: &mut FilteredScorer,
+    ) {
+        let limit = self.get_m(level);
+        let mut points_ids: Vec<PointOffsetType> = Vec::with_capacity(2 * limit);
+        let mut filtered_out_ids: Vec<PointOffsetType> = Vec::with_capacity(limit);
+
+        while let Some(candidate) = searcher.candidates.pop() {
+            if candidate.score < searcher.lower_bound() {
+                break;
+            }
+
+            points_ids.clear();
+            filtered_out_ids.clear();
+            self.links_map(candidate.idx, level, |link| {
+                if !visited_list.check_and_update_visited(link) {
+                    if points_scorer.check_vector(link) {
+                        points_ids.push(link);
+                    } else {
+                        filtered_out_ids.push(link);
+                    }
+                }
+            });
+
+            // Second hop. Points not matching the filter are not marked as visited here,
+            // so they can still be expanded as direct neighbors of another candidate.
+            for &filtered_out_id in &filtered_out_ids {
+                self.links_map(filtered_out_id, level, |link| {
+                    if points_scorer.check_vector(link)
+                        && !visited_list.check_and_update_visited(link)
+                    {
+                        points_ids.push(link);
+                    }
+                });
+            }
+
+            let scores = points_scorer.score_points(&mut points_ids, limit);
+            scores
+                .iter()
+                .copied()
+                .for_each(|score_point| searcher.process_candidate(score_point));
+        }
+    }
+
     fn search_on_level(
         &self,
         level_entry: ScoredPointOffset,
@@ -108,6 +162,25 @@ pub trait GraphLayersBase {
         search_context.nearest
     }
 
+    /// Same as [`GraphLayersBase::search_on_level`], but walks through points not matching
+    /// the filter, see [`GraphLayersBase::_search_on_level_acorn`]
+    fn search_on_level_acorn(
+        &self,
+        level_entry: ScoredPointOffset,
+        level: usize,
+        ef: usize,
+        points_scorer: &mut FilteredScorer,
+    ) -> FixedLengthPriorityQueue<ScoredPointOffset> {
+        let mut visited_list = self.get_visited_list_from_pool();
+        visited_list.check_and_update_visited(level_entry.idx);
+        let mut search_context = SearchContext::new(level_entry, ef);
+
+        self._search_on_level_acorn(&mut search_context, level, &mut visited_list, points_scorer);
+
+        self.return_visited_list_to_pool(visited_list);
+        search_context.nearest
+    }
+
     /// Greedy searches for entry point of level `target_level`.
     /// Beam size is 1.
     fn search_entry(
@@ -146,6 +219,28 @@ pub trait GraphLayersBase {
         }
         current_point
     }
+
+    /// Search for `top` closest points, starting from `entry_point` and descending to the bottom level
+    ///
+    /// If `acorn` is set, the bottom level is searched with [`GraphLayersBase::search_on_level_acorn`]
+    fn search_from_entry(
+        &self,
+        entry_point: &EntryPoint,
+        top: usize,
+        ef: usize,
+        points_scorer: &mut FilteredScorer,
+        acorn: bool,
+    ) -> Vec<ScoredPointOffset> {
+        let zero_level_entry =
+            self.search_entry(entry_point.point_id, entry_point.level, 0, points_scorer);
+
+        let nearest = if acorn {
+            self.search_on_level_acorn(zero_level_entry, 0, max(top, ef), points_scorer)
+        } else {
+            self.search_on_level(zero_level_entry, 0, max(top, ef), points_scorer)
+        };
+        nearest.into_iter().take(top).collect_vec()
+    }
 }
 
 impl<TGraphLinks: GraphLinks> GraphLayersBase for GraphLayers<TGraphLinks> {
@@ -184,10 +279,32 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
     }
 
     pub fn search(
+        &self,
+        top: usize,
+        ef: usize,
+        points_scorer: FilteredScorer,
+    ) -> Vec<ScoredPointOffset> {
+        self.search_impl(top, ef, points_scorer, false)
+    }
+
+    /// Search, which walks through two hops of neighbors not matching the filter on the bottom level
+    ///
+    /// Improves recall for selective filters, which break the connectivity of the graph.
+    pub fn search_acorn(
+        &self,
+        top: usize,
+        ef: usize,
+        points_scorer: FilteredScorer,
+    ) -> Vec<ScoredPointOffset> {
+        self.search_impl(top, ef, points_scorer, true)
+    }
+
+    fn search_impl(
         &self,
         top: usize,
         ef: usize,
         mut points_scorer: FilteredScorer,
+        acorn: bool,
     ) -> Vec<ScoredPointOffset> {
         let entry_point = match self
             .entry_points
@@ -197,15 +314,7 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
             Some(ep) => ep,
         };
 
-        let zero_level_entry = self.search_entry(
-            entry_point.point_id,
-            entry_point.level,
-            0,
-            &mut points_scorer,
-        );
-
-        let nearest = self.search_on_level(zero_level_entry, 0, max(top, ef), &mut points_scorer);
-        nearest.into_iter().take(top).collect_vec()
+        self.search_from_entry(&entry_point, top, ef, &mut points_scorer, acorn)
     }
 
     pub fn get_path(path: &Path) -> PathBuf {
@@ -294,6 +403,7 @@ mod tests {
     };
     use crate::index::hnsw_index::graph_links::GraphLinksRam;
     use crate::index::hnsw_index::tests::create_graph_layer_fixture;
+    use crate::payload_storage::FilterContext;
     use crate::spaces::metric::Metric;
     use crate::spaces::simple::{CosineMetric, DotProductMetric};
 
@@ -368,6 +478,58 @@ mod tests {
         }
     }
 
+    struct EvenFilterContext {}
+
+    impl FilterContext for EvenFilterContext {
+        fn check(&self, point_id: PointOffsetType) -> bool {
+            point_id % 2 == 0
+        }
+    }
+
+    #[test]
+    fn test_search_on_level_acorn() {
+        let dim = 8;
+        let m = 8;
+        let num_vectors = 10;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let vector_holder =
+            TestRawScorerProducer::<DotProductMetric>::new(dim, num_vectors, &mut rng);
+
+        // Points matching the filter are only reachable through points not matching it
+        let mut graph_links = vec![vec![Vec::new()]; num_vectors];
+        graph_links[0][0] = vec![1, 3, 5];
+        graph_links[1][0] = vec![2, 4];
+        graph_links[3][0] = vec![6];
+        graph_links[5][0] = vec![7, 8];
+
+        let graph_layers = GraphLayers {
+            m,
+            m0: 2 * m,
+            ef_construct: 32,
+            links: GraphLinksRam::from_converter(GraphLinksConverter::new(graph_links)).unwrap(),
+            entry_points: EntryPoints::new(10),
+            visited_pool: VisitedPool::new(),
+        };
+
+        let filter_context = EvenFilterContext {};
+        let query = vector_holder.vectors.get(9).to_vec();
+        let raw_scorer = vector_holder.get_raw_scorer(query);
+        let mut scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&filter_context));
+        let entry = ScoredPointOffset {
+            idx: 0,
+            score: scorer.score_point(0),
+        };
+
+        let nearest = graph_layers.search_on_level(entry, 0, 32, &mut scorer);
+        assert_eq!(nearest.len(), 1);
+
+        let nearest = graph_layers.search_on_level_acorn(entry, 0, 32, &mut scorer);
+        let found: Vec<_> = nearest.into_iter().map(|p| p.idx).sorted().collect();
+        assert_eq!(found, vec![0, 2, 4, 6, 8]);
+    }
+
     #[test]
     fn test_save_and_load() {
         let num_vectors = 100;
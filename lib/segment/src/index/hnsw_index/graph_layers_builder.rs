@@ -12,7 +12,9 @@ use rand::Rng;
 use super::graph_links::GraphLinks;
 use crate::common::operation_error::OperationResult;
 use crate::index::hnsw_index::entry_points::EntryPoints;
-use crate::index::hnsw_index::graph_layers::{GraphLayers, GraphLayersBase, LinkContainer};
+use crate::index::hnsw_index::graph_layers::{
+    GraphLayers, GraphLayersBase, LayersContainer, LinkContainer,
+};
 use crate::index::hnsw_index::graph_links::GraphLinksConverter;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::index::hnsw_index::search_context::SearchContext;
@@ -186,10 +188,176 @@ impl GraphLayersBuilder {
         self.visited_pool.return_back(visited_list);
     }
 
-    fn num_points(&self) -> usize {
+    /// Create builder from the existing graph, to continue inserting points into it
+    pub fn from_graph_layers<TGraphLinks: GraphLinks>(
+        graph_layers: GraphLayers<TGraphLinks>,
+        use_heuristic: bool,
+    ) -> Self {
+        let links_layers: Vec<LockedLayersContainer> = (0..graph_layers.num_points()
+            as PointOffsetType)
+            .map(|point_id| {
+                (0..=graph_layers.point_level(point_id))
+                    .map(|level| RwLock::new(graph_layers.links.links(point_id, level).to_vec()))
+                    .collect()
+            })
+            .collect();
+        let max_level = links_layers
+            .iter()
+            .map(|layers| layers.len().saturating_sub(1))
+            .max()
+            .unwrap_or(0);
+
+        Self {
+            max_level: AtomicUsize::new(max_level),
+            m: graph_layers.m,
+            m0: graph_layers.m0,
+            ef_construct: graph_layers.ef_construct,
+            level_factor: 1.0 / (max(graph_layers.m, 2) as f64).ln(),
+            use_heuristic,
+            links_layers,
+            entry_points: Mutex::new(graph_layers.entry_points),
+            visited_pool: graph_layers.visited_pool,
+        }
+    }
+
+    /// Copy of the current links of all points, which can be saved while the graph keeps changing
+    pub fn links_snapshot(&self) -> Vec<LayersContainer> {
+        self.links_layers
+            .iter()
+            .map(|layers| layers.iter().map(|links| links.read().clone()).collect())
+            .collect()
+    }
+
+    /// Search for `top` closest points in the graph, which is still being built
+    pub fn search(
+        &self,
+        top: usize,
+        ef: usize,
+        mut points_scorer: FilteredScorer,
+        acorn: bool,
+    ) -> Vec<ScoredPointOffset> {
+        let entry_point = self
+            .entry_points
+            .lock()
+            .get_entry_point(|point_id| points_scorer.check_vector(point_id));
+        match entry_point {
+            None => vec![],
+            Some(entry_point) => {
+                self.search_from_entry(&entry_point, top, ef, &mut points_scorer, acorn)
+            }
+        }
+    }
+
+    pub fn num_points(&self) -> usize {
         self.links_layers.len()
     }
 
+    /// Remove all links of the point, before linking its changed vector again.
+    /// Links of other points to it are kept, they are replaced with closer points over time.
+    pub fn clear_links(&self, point_id: PointOffsetType) {
+        if let Some(layers) = self.links_layers.get(point_id as usize) {
+            layers.iter().for_each(|links| links.write().clear());
+        }
+    }
+
+    /// Replace links to deleted points with the closest of their neighbors
+    ///
+    /// Remaining neighbors of a deleted point are candidates to take its place in links of
+    /// the points linked to it. Candidates are selected in the same way as on linking, so
+    /// the graph stays navigable without rebuilding. Links of deleted points are cleared.
+    pub fn repair_links<D, F>(&self, is_deleted: D, mut score_internal: F)
+    where
+        D: Fn(PointOffsetType) -> bool,
+        F: FnMut(PointOffsetType, PointOffsetType) -> ScoreType,
+    {
+        let mut visited_list = self.get_visited_list_from_pool();
+        let mut candidates = vec![];
+
+        for point_id in 0..self.num_points() as PointOffsetType {
+            if is_deleted(point_id) {
+                continue;
+            }
+            for (level, links) in self.links_layers[point_id as usize].iter().enumerate() {
+                let current_links = links.read().clone();
+                if !current_links.iter().any(|&link| is_deleted(link)) {
+                    continue;
+                }
+
+                visited_list.next_iteration();
+                visited_list.check_and_update_visited(point_id);
+                candidates.clear();
+                for link in current_links {
+                    if !is_deleted(link) {
+                        if !visited_list.check_and_update_visited(link) {
+                            candidates.push(link);
+                        }
+                        continue;
+                    }
+                    let Some(deleted_links) = self.links_layers[link as usize].get(level) else {
+                        continue;
+                    };
+                    for &second_hop in deleted_links.read().iter() {
+                        if !is_deleted(second_hop)
+                            && !visited_list.check_and_update_visited(second_hop)
+                        {
+                            candidates.push(second_hop);
+                        }
+                    }
+                }
+
+                let mut scored_candidates: Vec<_> = candidates
+                    .iter()
+                    .map(|&candidate| ScoredPointOffset {
+                        idx: candidate,
+                        score: score_internal(point_id, candidate),
+                    })
+                    .collect();
+                scored_candidates.sort_unstable();
+                scored_candidates.reverse();
+
+                let level_m = self.get_m(level);
+                let selected = if self.use_heuristic {
+                    Self::select_candidate_with_heuristic_from_sorted(
+                        scored_candidates.into_iter(),
+                        level_m,
+                        &mut score_internal,
+                    )
+                } else {
+                    scored_candidates
+                        .into_iter()
+                        .take(level_m)
+                        .map(|candidate| candidate.idx)
+                        .collect()
+                };
+                links.write().clone_from(&selected);
+            }
+        }
+        self.return_visited_list_to_pool(visited_list);
+
+        let mut highest_point: Option<(PointOffsetType, usize)> = None;
+        for (point_id, layers) in self.links_layers.iter().enumerate() {
+            let point_id = point_id as PointOffsetType;
+            if is_deleted(point_id) {
+                layers.iter().for_each(|links| links.write().clear());
+            } else if !layers.is_empty()
+                && highest_point.map_or(true, |(_, level)| layers.len() - 1 > level)
+            {
+                highest_point = Some((point_id, layers.len() - 1));
+            }
+        }
+
+        // Deleted entry points are skipped on search, make sure there is a remaining one
+        let mut entry_points = self.entry_points.lock();
+        if entry_points
+            .get_entry_point(|point_id| !is_deleted(point_id))
+            .is_none()
+        {
+            if let Some((point_id, level)) = highest_point {
+                entry_points.new_point(point_id, level, |point_id| !is_deleted(point_id));
+            }
+        }
+    }
+
     /// Generate random level for a new point, according to geometric distribution
     pub fn get_random_layer<R>(&self, rng: &mut R) -> usize
     where
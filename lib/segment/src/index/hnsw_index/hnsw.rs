@@ -1,8 +1,8 @@
-use std::fs::create_dir_all;
+use std::fs::{create_dir_all, remove_file};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 use atomic_refcell::AtomicRefCell;
 #[cfg(debug_assertions)]
@@ -15,14 +15,20 @@ use rand::thread_rng;
 use rayon::prelude::*;
 use rayon::ThreadPool;
 
+use super::acorn::select_filtered_traversal;
+use super::calibration::{
+    get_calibration_path, load_calibration, save_calibration, CalibrationProgress,
+};
 use super::graph_links::{GraphLinks, GraphLinksMmap};
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
 };
 use crate::common::BYTES_IN_KB;
+use crate::data_types::search_profile::{SearchStrategy, VectorIndexSearchProfile};
 use crate::data_types::vectors::QueryVector;
 use crate::id_tracker::{IdTracker, IdTrackerSS};
+use crate::index::field_index::CardinalityEstimation;
 use crate::index::hnsw_index::build_condition_checker::BuildConditionChecker;
 use crate::index::hnsw_index::config::HnswGraphConfig;
 use crate::index::hnsw_index::graph_layers::GraphLayers;
@@ -38,11 +44,13 @@ use crate::telemetry::VectorIndexSearchesTelemetry;
 use crate::types::Condition::Field;
 use crate::types::{
     default_quantization_ignore_value, default_quantization_oversampling_value, FieldCondition,
-    Filter, HnswConfig, QuantizationSearchParams, SearchParams, VECTOR_ELEMENT_SIZE,
+    Filter, HnswConfig, QuantizationSearchParams, SearchCalibration, SearchParams,
+    VECTOR_ELEMENT_SIZE,
 };
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::{
-    new_raw_scorer, new_stoppable_raw_scorer, RawScorer, VectorStorage, VectorStorageEnum,
+    new_raw_scorer, new_stoppable_raw_scorer, CountingRawScorer, RawScorer, VectorStorage,
+    VectorStorageEnum,
 };
 
 const HNSW_USE_HEURISTIC: bool = true;
@@ -54,6 +62,12 @@ pub struct HNSWIndex<TGraphLinks: GraphLinks> {
     config: HnswGraphConfig,
     path: PathBuf,
     graph: Option<GraphLayers<TGraphLinks>>,
+    /// Measured recall of the graph, used to choose search parameters for `target_recall`.
+    /// Measured in the background after the graph is built, so neither indexing nor searches
+    /// pay for it. Searches use the default `ef` until it is finished.
+    calibration: OnceLock<SearchCalibration>,
+    /// Calibration in progress, see [`VectorIndex::calibrate_search_step`]
+    calibration_progress: Mutex<Option<CalibrationProgress>>,
     searches_telemetry: SearchesTelemetry,
 }
 
@@ -103,6 +117,12 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             None
         };
 
+        let calibration_path = get_calibration_path(path);
+        let calibration = OnceLock::new();
+        if calibration_path.exists() {
+            let _ = calibration.set(load_calibration(&calibration_path)?);
+        }
+
         Ok(HNSWIndex {
             id_tracker,
             vector_storage,
@@ -110,6 +130,8 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             config,
             path: path.to_owned(),
             graph,
+            calibration,
+            calibration_progress: Mutex::new(None),
             searches_telemetry: SearchesTelemetry {
                 unfiltered_hnsw: OperationDurationsAggregator::new(),
                 unfiltered_plain: OperationDurationsAggregator::new(),
@@ -141,6 +163,37 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         Ok(())
     }
 
+    /// Fill `hnsw_ef` and quantization oversampling from the calibration, if `target_recall`
+    /// is requested. Explicitly specified parameters are kept.
+    fn tune_search_params(
+        &self,
+        params: Option<&SearchParams>,
+        selectivity: f64,
+    ) -> Option<SearchParams> {
+        let mut params = *params?;
+        let Some(target_recall) = params.target_recall else {
+            return Some(params);
+        };
+        let Some((ef, oversampling)) = self
+            .calibration
+            .get()
+            .and_then(|calibration| calibration.tune(target_recall, selectivity))
+        else {
+            return Some(params);
+        };
+
+        params.hnsw_ef.get_or_insert(ef);
+        if let Some(oversampling) = oversampling {
+            let quantization = params.quantization.get_or_insert(QuantizationSearchParams {
+                ignore: default_quantization_ignore_value(),
+                rescore: None,
+                oversampling: default_quantization_oversampling_value(),
+            });
+            quantization.oversampling.get_or_insert(oversampling);
+        }
+        Some(params)
+    }
+
     pub fn build_filtered_graph(
         &self,
         pool: &ThreadPool,
@@ -188,7 +241,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 .try_for_each(|block_point_id| {
                     check_process_stopped(stopped)?;
 
-                    let vector = vector_storage.get_vector(block_point_id).into();
+                    let vector = vector_storage.get_vector(block_point_id).to_vec().into();
                     let raw_scorer = match vector_storage.quantized_storage() {
                         Some(quantized_storage) => quantized_storage.raw_scorer(
                             vector,
@@ -215,17 +268,22 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn search_with_graph(
         &self,
         vector: &QueryVector,
         filter: Option<&Filter>,
         top: usize,
         params: Option<&SearchParams>,
+        acorn: bool,
         is_stopped: &AtomicBool,
+        count_scored: bool,
+        profile: &mut VectorIndexSearchProfile,
     ) -> Vec<ScoredPointOffset> {
         let ef = params
             .and_then(|params| params.hnsw_ef)
             .unwrap_or(self.config.ef);
+        profile.hnsw_ef = Some(ef);
 
         let id_tracker = self.id_tracker.borrow();
         let payload_index = self.payload_index.borrow();
@@ -242,33 +300,103 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         );
         let oversampled_top =
             Self::get_oversampled_top(vector_storage.quantized_storage(), params, top);
-
+        profile.quantized = Self::is_quantized_search(quantized_storage, params);
+        profile.candidates = oversampled_top;
+
+        // Counting scored vectors has an overhead, only do it when profiling
+        let counting_scorer = count_scored.then(|| CountingRawScorer::new(raw_scorer.as_ref()));
+        let scorer: &dyn RawScorer = match &counting_scorer {
+            Some(counting_scorer) => counting_scorer,
+            None => raw_scorer.as_ref(),
+        };
         let filter_context = filter.map(|f| payload_index.filter_context(f));
-        let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), filter_context.as_deref());
+        let points_scorer = FilteredScorer::new(scorer, filter_context.as_deref());
 
         match &self.graph {
             Some(graph) => {
-                let search_result = graph.search(oversampled_top, ef, points_scorer);
-                self.postprocess_search_result(search_result, vector, params, top, is_stopped)
+                let search_result = if acorn {
+                    graph.search_acorn(oversampled_top, ef, points_scorer)
+                } else {
+                    graph.search(oversampled_top, ef, points_scorer)
+                };
+                if let Some(counting_scorer) = &counting_scorer {
+                    profile.scored_vectors += counting_scorer.scored();
+                }
+                self.postprocess_search_result(
+                    search_result,
+                    vector,
+                    params,
+                    top,
+                    is_stopped,
+                    profile,
+                )
             }
             None => Default::default(),
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn search_vectors_with_graph(
         &self,
         vectors: &[&QueryVector],
         filter: Option<&Filter>,
         top: usize,
         params: Option<&SearchParams>,
+        acorn: bool,
         is_stopped: &AtomicBool,
+        count_scored: bool,
+        profile: &mut VectorIndexSearchProfile,
     ) -> Vec<Vec<ScoredPointOffset>> {
         vectors
             .iter()
-            .map(|vector| self.search_with_graph(vector, filter, top, params, is_stopped))
+            .map(|vector| {
+                self.search_with_graph(
+                    vector,
+                    filter,
+                    top,
+                    params,
+                    acorn,
+                    is_stopped,
+                    count_scored,
+                    profile,
+                )
+            })
             .collect()
     }
 
+    /// Filtered search with the graph, with traversal chosen by the estimated selectivity
+    #[allow(clippy::too_many_arguments)]
+    fn search_vectors_with_filtered_graph(
+        &self,
+        vectors: &[&QueryVector],
+        filter: &Filter,
+        query_cardinality: &CardinalityEstimation,
+        available_vectors: usize,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+        count_scored: bool,
+        profile: &mut VectorIndexSearchProfile,
+    ) -> Vec<Vec<ScoredPointOffset>> {
+        let acorn = select_filtered_traversal(query_cardinality, available_vectors, profile);
+        let selectivity = if available_vectors > 0 {
+            query_cardinality.exp as f64 / available_vectors as f64
+        } else {
+            1.0
+        };
+        let params = self.tune_search_params(params, selectivity);
+        self.search_vectors_with_graph(
+            vectors,
+            Some(filter),
+            top,
+            params.as_ref(),
+            acorn,
+            is_stopped,
+            count_scored,
+            profile,
+        )
+    }
+
     fn search_plain(
         &self,
         vector: &QueryVector,
@@ -276,6 +404,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         top: usize,
         params: Option<&SearchParams>,
         is_stopped: &AtomicBool,
+        profile: &mut VectorIndexSearchProfile,
     ) -> Vec<ScoredPointOffset> {
         let id_tracker = self.id_tracker.borrow();
         let payload_index = self.payload_index.borrow();
@@ -293,11 +422,15 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         let oversampled_top =
             Self::get_oversampled_top(vector_storage.quantized_storage(), params, top);
 
+        profile.quantized = Self::is_quantized_search(quantized_storage, params);
+        profile.candidates = oversampled_top;
+
         let filtered_points = payload_index.query_points(filter);
+        profile.scored_vectors += filtered_points.len();
         let search_result =
             raw_scorer.peek_top_iter(&mut filtered_points.iter().copied(), oversampled_top);
 
-        self.postprocess_search_result(search_result, vector, params, top, is_stopped)
+        self.postprocess_search_result(search_result, vector, params, top, is_stopped, profile)
     }
 
     fn search_vectors_plain(
@@ -307,10 +440,11 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         top: usize,
         params: Option<&SearchParams>,
         is_stopped: &AtomicBool,
+        profile: &mut VectorIndexSearchProfile,
     ) -> Vec<Vec<ScoredPointOffset>> {
         vectors
             .iter()
-            .map(|vector| self.search_plain(vector, filter, top, params, is_stopped))
+            .map(|vector| self.search_plain(vector, filter, top, params, is_stopped, profile))
             .collect()
     }
 
@@ -377,6 +511,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         params: Option<&SearchParams>,
         top: usize,
         is_stopped: &AtomicBool,
+        profile: &mut VectorIndexSearchProfile,
     ) -> Vec<ScoredPointOffset> {
         let id_tracker = self.id_tracker.borrow();
         let vector_storage = self.vector_storage.borrow();
@@ -392,6 +527,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 .and_then(|p| p.quantization)
                 .and_then(|q| q.rescore)
                 .unwrap_or(default_rescoring);
+        profile.rescored = rescore;
 
         let mut postprocess_result = if rescore {
             let raw_scorer = new_stoppable_raw_scorer(
@@ -421,14 +557,17 @@ impl HNSWIndex<GraphLinksMmap> {
     }
 }
 
-impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
-    fn search(
+impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
+    #[allow(clippy::too_many_arguments)]
+    fn search_vectors(
         &self,
         vectors: &[&QueryVector],
         filter: Option<&Filter>,
         top: usize,
         params: Option<&SearchParams>,
         is_stopped: &AtomicBool,
+        count_scored: bool,
+        profile: &mut VectorIndexSearchProfile,
     ) -> Vec<Vec<ScoredPointOffset>> {
         let exact = params.map(|params| params.exact).unwrap_or(false);
         match filter {
@@ -450,6 +589,14 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     } else {
                         &self.searches_telemetry.unfiltered_plain
                     });
+                    profile.strategy = if exact {
+                        SearchStrategy::Exact
+                    } else {
+                        SearchStrategy::Plain
+                    };
+                    profile.scored_vectors =
+                        vector_storage.available_vector_count() * vectors.len();
+                    profile.candidates = top;
                     vectors
                         .iter()
                         .map(|&vector| {
@@ -465,7 +612,18 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                 } else {
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_hnsw);
-                    self.search_vectors_with_graph(vectors, None, top, params, is_stopped)
+                    profile.strategy = SearchStrategy::Hnsw;
+                    let params = self.tune_search_params(params, 1.0);
+                    self.search_vectors_with_graph(
+                        vectors,
+                        None,
+                        top,
+                        params.as_ref(),
+                        false,
+                        is_stopped,
+                        count_scored,
+                        profile,
+                    )
                 }
             }
             Some(query_filter) => {
@@ -486,12 +644,14 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     });
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.exact_filtered);
+                    profile.strategy = SearchStrategy::Exact;
                     return self.search_vectors_plain(
                         vectors,
                         query_filter,
                         top,
                         exact_params.as_ref(),
                         is_stopped,
+                        profile,
                     );
                 }
 
@@ -505,17 +665,20 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     available_vector_count,
                     id_tracker.available_point_count(),
                 );
+                profile.cardinality = Some((&query_cardinality).into());
 
                 if query_cardinality.max < self.config.full_scan_threshold {
                     // if cardinality is small - use plain index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
+                    profile.strategy = SearchStrategy::PayloadIndex;
                     return self.search_vectors_plain(
                         vectors,
                         query_filter,
                         top,
                         params,
                         is_stopped,
+                        profile,
                     );
                 }
 
@@ -523,14 +686,24 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     // if cardinality is high enough - use HNSW index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
-                    return self
-                        .search_vectors_with_graph(vectors, filter, top, params, is_stopped);
+                    return self.search_vectors_with_filtered_graph(
+                        vectors,
+                        query_filter,
+                        &query_cardinality,
+                        available_vector_count,
+                        top,
+                        params,
+                        is_stopped,
+                        count_scored,
+                        profile,
+                    );
                 }
 
                 let filter_context = payload_index.filter_context(query_filter);
 
                 // Fast cardinality estimation is not enough, do sample estimation of cardinality
                 let id_tracker = self.id_tracker.borrow();
+                profile.cardinality_sampled = true;
                 if sample_check_cardinality(
                     id_tracker.sample_ids(Some(vector_storage.deleted_vector_bitslice())),
                     |idx| filter_context.check(idx),
@@ -540,16 +713,70 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     // if cardinality is high enough - use HNSW index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
-                    self.search_vectors_with_graph(vectors, filter, top, params, is_stopped)
+                    self.search_vectors_with_filtered_graph(
+                        vectors,
+                        query_filter,
+                        &query_cardinality,
+                        available_vector_count,
+                        top,
+                        params,
+                        is_stopped,
+                        count_scored,
+                        profile,
+                    )
                 } else {
                     // if cardinality is small - use plain index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
-                    self.search_vectors_plain(vectors, query_filter, top, params, is_stopped)
+                    profile.strategy = SearchStrategy::PayloadIndex;
+                    self.search_vectors_plain(
+                        vectors,
+                        query_filter,
+                        top,
+                        params,
+                        is_stopped,
+                        profile,
+                    )
                 }
             }
         }
     }
+}
+
+impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
+    fn search(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+    ) -> Vec<Vec<ScoredPointOffset>> {
+        let mut profile = VectorIndexSearchProfile::default();
+        self.search_vectors(
+            vectors,
+            filter,
+            top,
+            params,
+            is_stopped,
+            false,
+            &mut profile,
+        )
+    }
+
+    fn search_with_profile(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+    ) -> (Vec<Vec<ScoredPointOffset>>, VectorIndexSearchProfile) {
+        let mut profile = VectorIndexSearchProfile::default();
+        let result =
+            self.search_vectors(vectors, filter, top, params, is_stopped, true, &mut profile);
+        (result, profile)
+    }
 
     fn build_index(&mut self, stopped: &AtomicBool) -> OperationResult<()> {
         // Build main index graph
@@ -596,7 +823,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
             pool.install(|| {
                 ids.into_par_iter().try_for_each(|vector_id| {
                     check_process_stopped(stopped)?;
-                    let vector = vector_storage.get_vector(vector_id).into();
+                    let vector = vector_storage.get_vector(vector_id).to_vec().into();
                     let raw_scorer = if let Some(quantized_storage) =
                         vector_storage.quantized_storage()
                     {
@@ -681,7 +908,16 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
         self.config.indexed_vector_count.replace(indexed_vectors);
 
         let graph_links_path = GraphLayers::<TGraphLinks>::get_links_path(&self.path);
-        self.graph = Some(graph_layers_builder.into_graph_layers(Some(&graph_links_path))?);
+        let graph = graph_layers_builder.into_graph_layers(Some(&graph_links_path))?;
+
+        self.graph = Some(graph);
+        // Recall of the new graph is measured in the background
+        self.calibration = OnceLock::new();
+        self.calibration_progress = Mutex::new(None);
+        let calibration_path = get_calibration_path(&self.path);
+        if calibration_path.exists() {
+            remove_file(calibration_path)?;
+        }
 
         #[cfg(debug_assertions)]
         {
@@ -713,14 +949,20 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
     }
 
     fn files(&self) -> Vec<PathBuf> {
-        if self.graph.is_some() {
+        let mut files = if self.graph.is_some() {
             vec![
                 GraphLayers::<TGraphLinks>::get_path(&self.path),
                 GraphLayers::<TGraphLinks>::get_links_path(&self.path),
             ]
         } else {
             vec![]
+        };
+        // Calibration is saved once it is measured in the background
+        let calibration_path = get_calibration_path(&self.path);
+        if calibration_path.exists() {
+            files.push(calibration_path);
         }
+        files
     }
 
     fn indexed_vector_count(&self) -> usize {
@@ -730,4 +972,40 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
             .or_else(|| self.graph.as_ref().map(|graph| graph.num_points()))
             .unwrap_or(0)
     }
+
+    fn search_calibration(&self) -> Option<SearchCalibration> {
+        self.calibration.get().cloned()
+    }
+
+    fn calibrate_search_step(&self, stopped: &AtomicBool) -> OperationResult<bool> {
+        if self.calibration.get().is_some() {
+            return Ok(true);
+        }
+        let Some(graph) = &self.graph else {
+            return Ok(true);
+        };
+        if self.config.m == 0 {
+            return Ok(true);
+        }
+
+        let mut calibration_progress = self.calibration_progress.lock();
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let progress = calibration_progress.get_or_insert_with(|| {
+            debug!("calibrating HNSW search");
+            CalibrationProgress::new(id_tracker.deref(), &vector_storage)
+        });
+        let Some(calibration) =
+            progress.step(graph, id_tracker.deref(), &vector_storage, stopped)?
+        else {
+            return Ok(false);
+        };
+        *calibration_progress = None;
+
+        if let Err(err) = save_calibration(&get_calibration_path(&self.path), &calibration) {
+            log::warn!("Failed to save HNSW search calibration: {err}");
+        }
+        let _ = self.calibration.set(calibration);
+        Ok(true)
+    }
 }
@@ -1,5 +1,8 @@
+mod acorn;
+pub mod appendable_hnsw;
 mod build_cache;
 pub mod build_condition_checker;
+pub mod calibration;
 mod config;
 mod entry_points;
 pub mod graph_layers;
@@ -7,7 +10,7 @@ pub mod graph_layers_builder;
 pub mod graph_links;
 pub mod hnsw;
 pub mod point_scorer;
-mod search_context;
+pub(crate) mod search_context;
 
 #[cfg(test)]
 mod tests;
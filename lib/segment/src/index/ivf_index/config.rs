@@ -0,0 +1,44 @@
+use std::path::{Path, PathBuf};
+
+use io::file_operations::{atomic_save_json, read_json};
+use serde::{Deserialize, Serialize};
+
+use crate::common::operation_error::OperationResult;
+use crate::types::CompressionRatio;
+
+pub const IVF_INDEX_CONFIG_FILE: &str = "ivf_config.json";
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct IvfIndexConfig {
+    /// Number of lists to train, chosen by the number of vectors if not set
+    pub num_lists: Option<usize>,
+    /// Default number of lists to scan on search
+    pub nprobe: usize,
+    /// Compression of residual PQ codes, no codes are built if not set
+    pub product_quantization: Option<CompressionRatio>,
+    /// We prefer a full scan search upto (excluding) this number of vectors.
+    ///
+    /// Note: this is number of vectors, not KiloBytes.
+    pub full_scan_threshold: usize,
+    #[serde(default)]
+    pub max_indexing_threads: usize,
+    /// Parameters of the built residual PQ codes
+    #[serde(default)]
+    pub pq_vector_parameters: Option<quantization::VectorParameters>,
+    #[serde(default)]
+    pub indexed_vector_count: Option<usize>,
+}
+
+impl IvfIndexConfig {
+    pub fn get_config_path(path: &Path) -> PathBuf {
+        path.join(IVF_INDEX_CONFIG_FILE)
+    }
+
+    pub fn load(path: &Path) -> OperationResult<Self> {
+        Ok(read_json(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> OperationResult<()> {
+        Ok(atomic_save_json(path, self)?)
+    }
+}
@@ -0,0 +1,740 @@
+use std::collections::HashMap;
+use std::fs::{create_dir_all, remove_file};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
+use common::types::{PointOffsetType, ScoredPointOffset};
+use io::file_operations::{atomic_save_bin, read_bin};
+use log::debug;
+use parking_lot::Mutex;
+use quantization::{EncodedVectors, EncodedVectorsPQ};
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+use rayon::prelude::*;
+
+use super::config::IvfIndexConfig;
+use super::kmeans::{nearest_centroid, train_centroids};
+use super::posting_lists::PostingLists;
+use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
+use crate::common::operation_time_statistics::{
+    OperationDurationsAggregator, ScopeDurationMeasurer,
+};
+use crate::common::vector_utils::TrySetCapacityExact;
+use crate::common::{Flusher, BYTES_IN_KB};
+use crate::data_types::search_profile::{SearchStrategy, VectorIndexSearchProfile};
+use crate::data_types::vectors::{QueryVector, VectorType};
+use crate::id_tracker::{IdTracker, IdTrackerSS};
+use crate::index::exact_scorer::ExactScorer;
+use crate::index::hnsw_index::max_rayon_threads;
+use crate::index::query_estimator::adjust_to_available_vectors;
+use crate::index::sample_estimation::sample_check_cardinality;
+use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::index::{PayloadIndex, VectorIndex};
+use crate::telemetry::VectorIndexSearchesTelemetry;
+use crate::types::{
+    default_quantization_ignore_value, default_quantization_oversampling_value, Distance, Filter,
+    IvfConfig, SearchParams, VECTOR_ELEMENT_SIZE,
+};
+use crate::vector_storage::chunked_vectors::ChunkedVectors;
+use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
+use crate::vector_storage::{new_stoppable_raw_scorer, VectorStorage, VectorStorageEnum};
+
+const IVF_CENTROIDS_FILE: &str = "ivf_centroids.bin";
+const IVF_UPDATES_FILE: &str = "ivf_updates.bin";
+const IVF_PQ_DATA_FILE: &str = "ivf_pq.data";
+const IVF_PQ_META_FILE: &str = "ivf_pq.meta.json";
+
+/// Number of sampled vectors per list to train the centroids on
+const TRAINING_SAMPLE_PER_LIST: usize = 256;
+
+/// IVF (inverted file) index.
+///
+/// Vectors are assigned to the closest of the centroids, trained with k-means on build.
+/// Search scans only the lists of the closest centroids. If product quantization is enabled,
+/// lists are scanned with PQ codes of the residuals of vectors to their centroids.
+///
+/// New and changed vectors are assigned to the closest centroid without retraining. They are
+/// kept in separate lists, which are scored with original vectors and persisted on flush.
+pub struct IvfIndex {
+    id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+    vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+    payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    config: IvfIndexConfig,
+    path: PathBuf,
+    /// Trained lists, `None` until the index is built
+    lists: Option<IvfLists>,
+    /// Whether vectors were updated since the last flush
+    updates_dirty: Arc<AtomicBool>,
+    searches_telemetry: SearchesTelemetry,
+}
+
+struct SearchesTelemetry {
+    unfiltered_plain: Arc<Mutex<OperationDurationsAggregator>>,
+    unfiltered_lists: Arc<Mutex<OperationDurationsAggregator>>,
+    small_cardinality: Arc<Mutex<OperationDurationsAggregator>>,
+    large_cardinality: Arc<Mutex<OperationDurationsAggregator>>,
+    exact_filtered: Arc<Mutex<OperationDurationsAggregator>>,
+    exact_unfiltered: Arc<Mutex<OperationDurationsAggregator>>,
+}
+
+struct IvfLists {
+    centroids: Vec<VectorType>,
+    /// Points assigned to each centroid on build
+    posting_lists: PostingLists,
+    /// Residual PQ codes of points, as of build
+    codes: Option<EncodedVectorsPQ<ChunkedVectors<u8>>>,
+    /// Points inserted or changed after build, with the lists they are assigned to.
+    /// Their codes and entries in `posting_lists` are stale.
+    updated_points: HashMap<PointOffsetType, PointOffsetType>,
+    /// Updated points of each list
+    updated_lists: Vec<Vec<PointOffsetType>>,
+}
+
+impl IvfLists {
+    fn load(path: &Path, config: &IvfIndexConfig) -> OperationResult<Option<Self>> {
+        let centroids_path = path.join(IVF_CENTROIDS_FILE);
+        if !centroids_path.exists() {
+            return Ok(None);
+        }
+
+        let centroids: Vec<VectorType> = read_bin(&centroids_path)?;
+        let posting_lists = PostingLists::open(&PostingLists::get_path(path))?;
+        if posting_lists.num_lists() != centroids.len() {
+            return Err(OperationError::service_error(format!(
+                "IVF index in {} has {} centroids and {} lists",
+                path.display(),
+                centroids.len(),
+                posting_lists.num_lists(),
+            )));
+        }
+
+        let codes = match &config.pq_vector_parameters {
+            Some(vector_parameters) => Some(EncodedVectorsPQ::<ChunkedVectors<u8>>::load(
+                &path.join(IVF_PQ_DATA_FILE),
+                &path.join(IVF_PQ_META_FILE),
+                vector_parameters,
+            )?),
+            None => None,
+        };
+
+        let mut lists = Self {
+            updated_lists: vec![Vec::new(); centroids.len()],
+            centroids,
+            posting_lists,
+            codes,
+            updated_points: HashMap::new(),
+        };
+
+        let updates_path = path.join(IVF_UPDATES_FILE);
+        if updates_path.exists() {
+            let updates: Vec<(PointOffsetType, PointOffsetType)> = read_bin(&updates_path)?;
+            for (point, list) in updates {
+                lists.assign(point, list);
+            }
+        }
+
+        Ok(Some(lists))
+    }
+
+    /// Move `point` into the updated points of `list`
+    fn assign(&mut self, point: PointOffsetType, list: PointOffsetType) {
+        if let Some(previous) = self.updated_points.insert(point, list) {
+            self.updated_lists[previous as usize].retain(|&updated| updated != point);
+        }
+        self.updated_lists[list as usize].push(point);
+    }
+}
+
+impl IvfIndex {
+    pub fn open(
+        path: &Path,
+        id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+        vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+        payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+        ivf_config: IvfConfig,
+    ) -> OperationResult<Self> {
+        create_dir_all(path)?;
+
+        let config_path = IvfIndexConfig::get_config_path(path);
+        let config = if config_path.exists() {
+            IvfIndexConfig::load(&config_path)?
+        } else {
+            let vector_storage = vector_storage.borrow();
+            let full_scan_threshold = ivf_config.full_scan_threshold.saturating_mul(BYTES_IN_KB)
+                / (vector_storage.vector_dim() * VECTOR_ELEMENT_SIZE);
+
+            IvfIndexConfig {
+                num_lists: ivf_config.num_lists,
+                nprobe: ivf_config.nprobe,
+                product_quantization: ivf_config.product_quantization,
+                full_scan_threshold,
+                max_indexing_threads: ivf_config.max_indexing_threads,
+                pq_vector_parameters: None,
+                indexed_vector_count: None,
+            }
+        };
+
+        let lists = IvfLists::load(path, &config)?;
+
+        Ok(IvfIndex {
+            id_tracker,
+            vector_storage,
+            payload_index,
+            config,
+            path: path.to_owned(),
+            lists,
+            updates_dirty: Arc::new(AtomicBool::new(false)),
+            searches_telemetry: SearchesTelemetry {
+                unfiltered_plain: OperationDurationsAggregator::new(),
+                unfiltered_lists: OperationDurationsAggregator::new(),
+                small_cardinality: OperationDurationsAggregator::new(),
+                large_cardinality: OperationDurationsAggregator::new(),
+                exact_filtered: OperationDurationsAggregator::new(),
+                exact_unfiltered: OperationDurationsAggregator::new(),
+            },
+        })
+    }
+
+    fn save_config(&self) -> OperationResult<()> {
+        let config_path = IvfIndexConfig::get_config_path(&self.path);
+        self.config.save(&config_path)
+    }
+
+    fn get_oversampled_top(params: Option<&SearchParams>, top: usize) -> usize {
+        let oversampling_value = params
+            .and_then(|p| p.quantization)
+            .map(|q| q.oversampling)
+            .unwrap_or(default_quantization_oversampling_value());
+
+        match oversampling_value {
+            Some(oversampling) if oversampling > 1.0 => (oversampling * top as f64) as usize,
+            _ => top,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn search_with_lists(
+        &self,
+        lists: &IvfLists,
+        vector: &QueryVector,
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+        profile: &mut VectorIndexSearchProfile,
+    ) -> Vec<ScoredPointOffset> {
+        let id_tracker = self.id_tracker.borrow();
+        let payload_index = self.payload_index.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let distance = vector_storage.distance();
+
+        let nprobe = params
+            .and_then(|params| params.nprobe)
+            .unwrap_or(self.config.nprobe)
+            .clamp(1, lists.centroids.len());
+
+        let query_scorer = ExactScorer::new(vector, distance);
+        let mut closest_lists = FixedLengthPriorityQueue::new(nprobe);
+        for (list, centroid) in lists.centroids.iter().enumerate() {
+            closest_lists.push(ScoredPointOffset {
+                idx: list as PointOffsetType,
+                score: query_scorer.score(centroid),
+            });
+        }
+        profile.scored_vectors += lists.centroids.len();
+
+        let raw_scorer = new_stoppable_raw_scorer(
+            vector.to_owned(),
+            &vector_storage,
+            id_tracker.deleted_point_bitslice(),
+            is_stopped,
+        );
+        let filter_context = filter.map(|f| payload_index.filter_context(f));
+        let is_result = |point: PointOffsetType| {
+            raw_scorer.check_vector(point)
+                && filter_context
+                    .as_ref()
+                    .map_or(true, |context| context.check(point))
+        };
+
+        // Residual codes are used for nearest search only
+        let ignore_quantization = params
+            .and_then(|p| p.quantization)
+            .map(|q| q.ignore)
+            .unwrap_or(default_quantization_ignore_value());
+        let codes = match (&lists.codes, vector) {
+            (Some(codes), QueryVector::Nearest(query)) if !ignore_quantization => {
+                Some((codes, distance.preprocess_vector(query.clone())))
+            }
+            _ => None,
+        };
+        profile.quantized = codes.is_some();
+
+        let candidates_count = if codes.is_some() {
+            Self::get_oversampled_top(params, top)
+        } else {
+            top
+        };
+        profile.candidates = candidates_count;
+        let mut nearest = FixedLengthPriorityQueue::new(candidates_count);
+        let mut has_updated_candidates = false;
+
+        for list in closest_lists.into_vec() {
+            if is_stopped.load(Ordering::Relaxed) {
+                break;
+            }
+            let list_idx = list.idx as usize;
+
+            // Points updated after build are scanned in their updated lists
+            let built_points = lists
+                .posting_lists
+                .list(list_idx)
+                .iter()
+                .copied()
+                .filter(|point| !lists.updated_points.contains_key(point))
+                .filter(|&point| is_result(point));
+
+            match &codes {
+                Some((codes, query)) => {
+                    // Score of a vector is estimated from its centroid and the code of its residual:
+                    // dot products are added up, distances are measured from the shifted query
+                    let (encoded_query, centroid_score) = match distance {
+                        Distance::Cosine | Distance::Dot => (codes.encode_query(query), list.score),
+                        Distance::Euclid
+                        | Distance::Manhattan
+                        | Distance::Hamming
+                        | Distance::Jaccard => {
+                            let shifted_query: Vec<_> = query
+                                .iter()
+                                .zip(&lists.centroids[list_idx])
+                                .map(|(value, centroid)| value - centroid)
+                                .collect();
+                            (codes.encode_query(&shifted_query), 0.0)
+                        }
+                    };
+                    for point in built_points {
+                        profile.scored_vectors += 1;
+                        nearest.push(ScoredPointOffset {
+                            idx: point,
+                            score: centroid_score + codes.score_point(&encoded_query, point),
+                        });
+                    }
+                }
+                None => {
+                    for point in built_points {
+                        profile.scored_vectors += 1;
+                        nearest.push(ScoredPointOffset {
+                            idx: point,
+                            score: raw_scorer.score_point(point),
+                        });
+                    }
+                }
+            }
+
+            // Updated points have no codes, they are scored with original vectors
+            for &point in &lists.updated_lists[list_idx] {
+                if is_result(point) {
+                    profile.scored_vectors += 1;
+                    has_updated_candidates = true;
+                    nearest.push(ScoredPointOffset {
+                        idx: point,
+                        score: raw_scorer.score_point(point),
+                    });
+                }
+            }
+        }
+
+        // Exact scores of updated points are not comparable with estimated scores of codes,
+        // so candidates are rescored whenever updated points are among them
+        let rescore = codes.is_some()
+            && (has_updated_candidates
+                || params
+                    .and_then(|p| p.quantization)
+                    .and_then(|q| q.rescore)
+                    .unwrap_or(true));
+        profile.rescored = rescore;
+
+        let mut result = if rescore {
+            let mut ids_iterator = nearest.into_vec().into_iter().map(|x| x.idx);
+            let mut re_scored = raw_scorer.score_points_unfiltered(&mut ids_iterator);
+            re_scored.sort_unstable();
+            re_scored.reverse();
+            re_scored
+        } else {
+            nearest.into_vec()
+        };
+        result.truncate(top);
+        result
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn search_vectors_with_lists(
+        &self,
+        lists: &IvfLists,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+        profile: &mut VectorIndexSearchProfile,
+    ) -> Vec<Vec<ScoredPointOffset>> {
+        profile.strategy = SearchStrategy::Ivf;
+        vectors
+            .iter()
+            .map(|vector| {
+                self.search_with_lists(lists, vector, filter, top, params, is_stopped, profile)
+            })
+            .collect()
+    }
+
+    /// Score all vectors matching the filter, or all vectors if there is no filter
+    fn search_vectors_plain(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        is_stopped: &AtomicBool,
+        profile: &mut VectorIndexSearchProfile,
+    ) -> Vec<Vec<ScoredPointOffset>> {
+        let id_tracker = self.id_tracker.borrow();
+        let payload_index = self.payload_index.borrow();
+        let vector_storage = self.vector_storage.borrow();
+
+        profile.candidates = top;
+        let filtered_points = filter.map(|filter| payload_index.query_points(filter));
+
+        vectors
+            .iter()
+            .map(|&vector| {
+                let raw_scorer = new_stoppable_raw_scorer(
+                    vector.to_owned(),
+                    &vector_storage,
+                    id_tracker.deleted_point_bitslice(),
+                    is_stopped,
+                );
+                match &filtered_points {
+                    Some(points) => {
+                        profile.scored_vectors += points.len();
+                        raw_scorer.peek_top_iter(&mut points.iter().copied(), top)
+                    }
+                    None => {
+                        profile.scored_vectors += vector_storage.available_vector_count();
+                        raw_scorer.peek_top_all(top)
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn search_vectors(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+        profile: &mut VectorIndexSearchProfile,
+    ) -> Vec<Vec<ScoredPointOffset>> {
+        let exact = params.map(|params| params.exact).unwrap_or(false);
+        let telemetry = &self.searches_telemetry;
+
+        let Some(query_filter) = filter else {
+            let available_vector_count = self.vector_storage.borrow().available_vector_count();
+            if exact {
+                let _timer = ScopeDurationMeasurer::new(&telemetry.exact_unfiltered);
+                profile.strategy = SearchStrategy::Exact;
+                return self.search_vectors_plain(vectors, None, top, is_stopped, profile);
+            }
+            match &self.lists {
+                Some(lists) if available_vector_count >= self.config.full_scan_threshold => {
+                    let _timer = ScopeDurationMeasurer::new(&telemetry.unfiltered_lists);
+                    return self.search_vectors_with_lists(
+                        lists, vectors, None, top, params, is_stopped, profile,
+                    );
+                }
+                _ => {
+                    let _timer = ScopeDurationMeasurer::new(&telemetry.unfiltered_plain);
+                    profile.strategy = SearchStrategy::Plain;
+                    return self.search_vectors_plain(vectors, None, top, is_stopped, profile);
+                }
+            }
+        };
+
+        if exact {
+            let _timer = ScopeDurationMeasurer::new(&telemetry.exact_filtered);
+            profile.strategy = SearchStrategy::Exact;
+            return self.search_vectors_plain(vectors, filter, top, is_stopped, profile);
+        }
+
+        let Some(lists) = &self.lists else {
+            let _timer = ScopeDurationMeasurer::new(&telemetry.small_cardinality);
+            profile.strategy = SearchStrategy::PayloadIndex;
+            return self.search_vectors_plain(vectors, filter, top, is_stopped, profile);
+        };
+
+        let payload_index = self.payload_index.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        let available_vector_count = vector_storage.available_vector_count();
+        let query_point_cardinality = payload_index.estimate_cardinality(query_filter);
+        let query_cardinality = adjust_to_available_vectors(
+            query_point_cardinality,
+            available_vector_count,
+            id_tracker.available_point_count(),
+        );
+        profile.cardinality = Some((&query_cardinality).into());
+
+        let use_lists = if query_cardinality.max < self.config.full_scan_threshold {
+            false
+        } else if query_cardinality.min > self.config.full_scan_threshold {
+            true
+        } else {
+            // Fast cardinality estimation is not enough, do sample estimation of cardinality
+            let filter_context = payload_index.filter_context(query_filter);
+            profile.cardinality_sampled = true;
+            sample_check_cardinality(
+                id_tracker.sample_ids(Some(vector_storage.deleted_vector_bitslice())),
+                |idx| filter_context.check(idx),
+                self.config.full_scan_threshold,
+                available_vector_count,
+            )
+        };
+
+        if use_lists {
+            let _timer = ScopeDurationMeasurer::new(&telemetry.large_cardinality);
+            self.search_vectors_with_lists(lists, vectors, filter, top, params, is_stopped, profile)
+        } else {
+            let _timer = ScopeDurationMeasurer::new(&telemetry.small_cardinality);
+            profile.strategy = SearchStrategy::PayloadIndex;
+            self.search_vectors_plain(vectors, filter, top, is_stopped, profile)
+        }
+    }
+}
+
+impl VectorIndex for IvfIndex {
+    fn search_with_profile(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+    ) -> (Vec<Vec<ScoredPointOffset>>, VectorIndexSearchProfile) {
+        let mut profile = VectorIndexSearchProfile::default();
+        let result = self.search_vectors(vectors, filter, top, params, is_stopped, &mut profile);
+        (result, profile)
+    }
+
+    fn build_index(&mut self, stopped: &AtomicBool) -> OperationResult<()> {
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let distance = vector_storage.distance();
+
+        if distance.is_binary() {
+            return Err(OperationError::service_error(
+                "IVF index does not support binary vectors",
+            ));
+        }
+
+        // Updates of the previous build are assigned again from scratch
+        self.lists = None;
+        let updates_path = self.path.join(IVF_UPDATES_FILE);
+        if updates_path.exists() {
+            remove_file(&updates_path)?;
+        }
+
+        let total_vector_count = vector_storage.total_vector_count();
+        let deleted_bitslice = vector_storage.deleted_vector_bitslice();
+        let ids: Vec<_> = id_tracker.iter_ids_excluding(deleted_bitslice).collect();
+        self.config.indexed_vector_count.replace(ids.len());
+
+        if ids.is_empty() {
+            self.config.pq_vector_parameters = None;
+            return self.save_config();
+        }
+
+        debug!("building IVF for {} vectors", ids.len());
+
+        let num_lists = self
+            .config
+            .num_lists
+            .unwrap_or_else(|| (4.0 * (ids.len() as f64).sqrt()) as usize)
+            .clamp(1, ids.len());
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .thread_name(|idx| format!("ivf-build-{idx}"))
+            .num_threads(max_rayon_threads(self.config.max_indexing_threads))
+            .build()?;
+
+        let sample: Vec<VectorType> = ids
+            .choose_multiple(&mut thread_rng(), num_lists * TRAINING_SAMPLE_PER_LIST)
+            .map(|&id| vector_storage.get_vector(id).to_vec())
+            .collect();
+        let centroids = train_centroids(&sample, num_lists, distance, &pool, stopped)?;
+        drop(sample);
+
+        let assignments: Vec<(PointOffsetType, usize)> = pool.install(|| {
+            ids.par_iter()
+                .map(|&id| {
+                    check_process_stopped(stopped)?;
+                    let vector = vector_storage.get_vector(id);
+                    Ok((id, nearest_centroid(&centroids, &vector, distance)))
+                })
+                .collect::<OperationResult<_>>()
+        })?;
+
+        let mut posting_lists = vec![Vec::new(); centroids.len()];
+        for &(id, list) in &assignments {
+            posting_lists[list].push(id);
+        }
+
+        debug!("finish IVF lists, encoding residuals");
+
+        let codes = match self.config.product_quantization {
+            Some(compression) => {
+                // Codes are addressed by point offsets, deleted points are encoded as zero residuals
+                let dim = vector_storage.vector_dim();
+                let mut residuals = vec![0.0; total_vector_count * dim];
+                for &(id, list) in &assignments {
+                    let vector = vector_storage.get_vector(id);
+                    let residual = &mut residuals[id as usize * dim..][..dim];
+                    for ((residual, value), centroid) in
+                        residual.iter_mut().zip(vector.iter()).zip(&centroids[list])
+                    {
+                        *residual = value - centroid;
+                    }
+                }
+
+                let vector_parameters = QuantizedVectors::construct_vector_parameters(
+                    distance,
+                    dim,
+                    total_vector_count,
+                )?;
+                let bucket_size = QuantizedVectors::get_bucket_size(compression);
+                let quantized_vector_size =
+                    EncodedVectorsPQ::<ChunkedVectors<u8>>::get_quantized_vector_size(
+                        &vector_parameters,
+                        bucket_size,
+                    );
+                let mut storage_builder = ChunkedVectors::<u8>::new(quantized_vector_size);
+                storage_builder.try_set_capacity_exact(total_vector_count)?;
+
+                let codes = EncodedVectorsPQ::encode(
+                    residuals.chunks_exact(dim),
+                    storage_builder,
+                    &vector_parameters,
+                    bucket_size,
+                    max_rayon_threads(self.config.max_indexing_threads),
+                    || stopped.load(Ordering::Relaxed),
+                )?;
+                codes.save(
+                    &self.path.join(IVF_PQ_DATA_FILE),
+                    &self.path.join(IVF_PQ_META_FILE),
+                )?;
+                self.config.pq_vector_parameters = Some(vector_parameters);
+                Some(codes)
+            }
+            None => {
+                self.config.pq_vector_parameters = None;
+                None
+            }
+        };
+
+        let posting_lists_path = PostingLists::get_path(&self.path);
+        PostingLists::create(&posting_lists_path, &posting_lists)?;
+        atomic_save_bin(&self.path.join(IVF_CENTROIDS_FILE), &centroids)?;
+
+        self.lists = Some(IvfLists {
+            updated_lists: vec![Vec::new(); centroids.len()],
+            centroids,
+            posting_lists: PostingLists::open(&posting_lists_path)?,
+            codes,
+            updated_points: HashMap::new(),
+        });
+        self.save_config()
+    }
+
+    fn get_telemetry_data(&self) -> VectorIndexSearchesTelemetry {
+        let tm = &self.searches_telemetry;
+
+        VectorIndexSearchesTelemetry {
+            index_name: None,
+            unfiltered_plain: tm.unfiltered_plain.lock().get_statistics(),
+            filtered_plain: Default::default(),
+            unfiltered_hnsw: tm.unfiltered_lists.lock().get_statistics(),
+            filtered_small_cardinality: tm.small_cardinality.lock().get_statistics(),
+            filtered_large_cardinality: tm.large_cardinality.lock().get_statistics(),
+            filtered_exact: tm.exact_filtered.lock().get_statistics(),
+            unfiltered_exact: tm.exact_unfiltered.lock().get_statistics(),
+        }
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        let Some(lists) = &self.lists else {
+            return vec![];
+        };
+
+        let mut files = vec![
+            self.path.join(IVF_CENTROIDS_FILE),
+            PostingLists::get_path(&self.path),
+        ];
+        if lists.codes.is_some() {
+            files.push(self.path.join(IVF_PQ_DATA_FILE));
+            files.push(self.path.join(IVF_PQ_META_FILE));
+        }
+        let updates_path = self.path.join(IVF_UPDATES_FILE);
+        if updates_path.exists() {
+            files.push(updates_path);
+        }
+        files
+    }
+
+    fn indexed_vector_count(&self) -> usize {
+        self.config.indexed_vector_count.unwrap_or(0)
+    }
+
+    fn update_vector(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        // Until the index is built, all vectors are searched without it
+        let Some(lists) = &mut self.lists else {
+            return Ok(());
+        };
+
+        let vector_storage = self.vector_storage.borrow();
+        if vector_storage.is_deleted_vector(id) {
+            return Ok(());
+        }
+        let vector = vector_storage.get_vector(id);
+        let list = nearest_centroid(&lists.centroids, &vector, vector_storage.distance());
+        lists.assign(id, list as PointOffsetType);
+        self.updates_dirty.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn flusher(&self) -> Flusher {
+        let Some(lists) = &self.lists else {
+            return Box::new(|| Ok(()));
+        };
+        if !self.updates_dirty.swap(false, Ordering::Relaxed) {
+            return Box::new(|| Ok(()));
+        }
+
+        let updates: Vec<(PointOffsetType, PointOffsetType)> = lists
+            .updated_points
+            .iter()
+            .map(|(&point, &list)| (point, list))
+            .collect();
+        let updates_path = self.path.join(IVF_UPDATES_FILE);
+        let updates_dirty = self.updates_dirty.clone();
+
+        Box::new(move || {
+            atomic_save_bin(&updates_path, &updates).map_err(|err| {
+                // Retry on the next flush
+                updates_dirty.store(true, Ordering::Relaxed);
+                OperationError::from(err)
+            })
+        })
+    }
+}
@@ -0,0 +1,86 @@
+use std::sync::atomic::AtomicBool;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rayon::prelude::*;
+use rayon::ThreadPool;
+
+use crate::common::operation_error::{check_process_stopped, OperationResult};
+use crate::data_types::vectors::{VectorElementType, VectorType};
+use crate::types::Distance;
+
+const KMEANS_ITERATIONS: usize = 10;
+
+/// Fixed seed, so the same vectors always produce the same centroids
+const KMEANS_SEED: u64 = 42;
+
+/// Index of the centroid with the best score against `vector`
+pub fn nearest_centroid(
+    centroids: &[VectorType],
+    vector: &[VectorElementType],
+    distance: Distance,
+) -> usize {
+    centroids
+        .iter()
+        .map(|centroid| distance.similarity(vector, centroid))
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map_or(0, |(idx, _)| idx)
+}
+
+/// Train up to `num_centroids` centroids of the `sample` vectors with k-means.
+///
+/// Centroids are preprocessed like vectors of the `distance`, so they are normalized for cosine.
+/// Centroids of empty clusters are moved to random vectors of the sample.
+pub fn train_centroids(
+    sample: &[VectorType],
+    num_centroids: usize,
+    distance: Distance,
+    pool: &ThreadPool,
+    stopped: &AtomicBool,
+) -> OperationResult<Vec<VectorType>> {
+    let mut rng = StdRng::seed_from_u64(KMEANS_SEED);
+    let Some(dim) = sample.first().map(|vector| vector.len()) else {
+        return Ok(Vec::new());
+    };
+
+    let mut centroids: Vec<VectorType> = sample
+        .choose_multiple(&mut rng, num_centroids)
+        .cloned()
+        .collect();
+
+    for _ in 0..KMEANS_ITERATIONS {
+        check_process_stopped(stopped)?;
+
+        let assignments: Vec<usize> = pool.install(|| {
+            sample
+                .par_iter()
+                .map(|vector| nearest_centroid(&centroids, vector, distance))
+                .collect()
+        });
+
+        let mut sums = vec![vec![0.0; dim]; centroids.len()];
+        let mut counts = vec![0usize; centroids.len()];
+        for (vector, &centroid) in sample.iter().zip(&assignments) {
+            counts[centroid] += 1;
+            for (sum, value) in sums[centroid].iter_mut().zip(vector) {
+                *sum += value;
+            }
+        }
+
+        for ((centroid, sum), count) in centroids.iter_mut().zip(sums).zip(counts) {
+            *centroid = if count == 0 {
+                sample.choose(&mut rng).cloned().unwrap_or(sum)
+            } else {
+                let mean = sum
+                    .into_iter()
+                    .map(|value| value / count as VectorElementType)
+                    .collect();
+                distance.preprocess_vector(mean)
+            };
+        }
+    }
+
+    Ok(centroids)
+}
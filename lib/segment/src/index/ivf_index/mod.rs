@@ -0,0 +1,4 @@
+mod config;
+pub mod ivf;
+mod kmeans;
+mod posting_lists;
@@ -0,0 +1,90 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::mem::size_of;
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+use memmap2::Mmap;
+use memory::mmap_ops::{self, transmute_from_u8_to_slice, transmute_to_u8_slice};
+
+use crate::common::operation_error::{OperationError, OperationResult};
+
+const IVF_LISTS_FILE: &str = "ivf_lists.bin";
+
+/// Inverted lists of the IVF index: points assigned to each centroid on build.
+///
+/// File layout: number of lists (`u64`), offsets of the lists in point ids (`u64`, one more
+/// than the number of lists), and point ids of all lists one after another (`u32`).
+pub struct PostingLists {
+    mmap: Mmap,
+    num_lists: usize,
+}
+
+impl PostingLists {
+    pub fn get_path(path: &Path) -> PathBuf {
+        path.join(IVF_LISTS_FILE)
+    }
+
+    pub fn create(path: &Path, lists: &[Vec<PointOffsetType>]) -> OperationResult<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        writer.write_all(&(lists.len() as u64).to_ne_bytes())?;
+        let mut offset = 0u64;
+        writer.write_all(&offset.to_ne_bytes())?;
+        for list in lists {
+            offset += list.len() as u64;
+            writer.write_all(&offset.to_ne_bytes())?;
+        }
+        for list in lists {
+            writer.write_all(transmute_to_u8_slice(list))?;
+        }
+
+        writer
+            .into_inner()
+            .map_err(|err| err.into_error())?
+            .sync_all()?;
+        Ok(())
+    }
+
+    pub fn open(path: &Path) -> OperationResult<Self> {
+        let mmap = mmap_ops::open_read_mmap(path)?;
+        let corrupted = || {
+            OperationError::service_error(
+                format!("IVF lists file {} is corrupted", path.display(),),
+            )
+        };
+
+        if mmap.len() < size_of::<u64>() {
+            return Err(corrupted());
+        }
+        let num_lists = transmute_from_u8_to_slice::<u64>(&mmap[..size_of::<u64>()])[0] as usize;
+        let ids_start = (num_lists + 2) * size_of::<u64>();
+        if mmap.len() < ids_start {
+            return Err(corrupted());
+        }
+
+        let posting_lists = Self { mmap, num_lists };
+        let total_ids = posting_lists.offsets()[num_lists] as usize;
+        if posting_lists.mmap.len() != ids_start + total_ids * size_of::<PointOffsetType>() {
+            return Err(corrupted());
+        }
+        Ok(posting_lists)
+    }
+
+    fn offsets(&self) -> &[u64] {
+        let end = (self.num_lists + 2) * size_of::<u64>();
+        transmute_from_u8_to_slice(&self.mmap[size_of::<u64>()..end])
+    }
+
+    pub fn num_lists(&self) -> usize {
+        self.num_lists
+    }
+
+    /// Points assigned to the list `idx` on build
+    pub fn list(&self, idx: usize) -> &[PointOffsetType] {
+        let offsets = self.offsets();
+        let ids_start = (self.num_lists + 2) * size_of::<u64>();
+        let ids: &[PointOffsetType] = transmute_from_u8_to_slice(&self.mmap[ids_start..]);
+        &ids[offsets[idx] as usize..offsets[idx + 1] as usize]
+    }
+}
@@ -1,5 +1,8 @@
+pub mod disk_ann_index;
+mod exact_scorer;
 pub mod field_index;
 pub mod hnsw_index;
+pub mod ivf_index;
 mod key_encoding;
 mod payload_config;
 mod payload_index_base;
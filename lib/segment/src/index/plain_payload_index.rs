@@ -15,6 +15,7 @@ use crate::common::operation_time_statistics::{
 };
 use crate::common::utils::JsonPathPayload;
 use crate::common::Flusher;
+use crate::data_types::search_profile::{SearchStrategy, VectorIndexSearchProfile};
 use crate::data_types::vectors::QueryVector;
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
@@ -156,7 +157,12 @@ impl PayloadIndex for PlainPayloadIndex {
         Box::new(vec![].into_iter())
     }
 
-    fn assign(&mut self, _point_id: PointOffsetType, _payload: &Payload) -> OperationResult<()> {
+    fn assign(
+        &mut self,
+        _point_id: PointOffsetType,
+        _payload: &Payload,
+        _key: &Option<PayloadKeyType>,
+    ) -> OperationResult<()> {
         unreachable!()
     }
 
@@ -221,14 +227,14 @@ impl PlainIndex {
 }
 
 impl VectorIndex for PlainIndex {
-    fn search(
+    fn search_with_profile(
         &self,
         vectors: &[&QueryVector],
         filter: Option<&Filter>,
         top: usize,
         _params: Option<&SearchParams>,
         is_stopped: &AtomicBool,
-    ) -> Vec<Vec<ScoredPointOffset>> {
+    ) -> (Vec<Vec<ScoredPointOffset>>, VectorIndexSearchProfile) {
         match filter {
             Some(filter) => {
                 let _timer = ScopeDurationMeasurer::new(&self.filtered_searches_telemetry);
@@ -236,7 +242,13 @@ impl VectorIndex for PlainIndex {
                 let payload_index = self.payload_index.borrow();
                 let vector_storage = self.vector_storage.borrow();
                 let filtered_ids_vec = payload_index.query_points(filter);
-                vectors
+                let profile = VectorIndexSearchProfile {
+                    strategy: SearchStrategy::PayloadIndex,
+                    scored_vectors: filtered_ids_vec.len() * vectors.len(),
+                    candidates: top,
+                    ..Default::default()
+                };
+                let result = vectors
                     .iter()
                     .map(|&vector| {
                         new_stoppable_raw_scorer(
@@ -247,13 +259,20 @@ impl VectorIndex for PlainIndex {
                         )
                         .peek_top_iter(&mut filtered_ids_vec.iter().copied(), top)
                     })
-                    .collect()
+                    .collect();
+                (result, profile)
             }
             None => {
                 let _timer = ScopeDurationMeasurer::new(&self.unfiltered_searches_telemetry);
                 let vector_storage = self.vector_storage.borrow();
                 let id_tracker = self.id_tracker.borrow();
-                vectors
+                let profile = VectorIndexSearchProfile {
+                    strategy: SearchStrategy::Plain,
+                    scored_vectors: vector_storage.available_vector_count() * vectors.len(),
+                    candidates: top,
+                    ..Default::default()
+                };
+                let result = vectors
                     .iter()
                     .map(|&vector| {
                         new_stoppable_raw_scorer(
@@ -264,7 +283,8 @@ impl VectorIndex for PlainIndex {
                         )
                         .peek_top_all(top)
                     })
-                    .collect()
+                    .collect();
+                (result, profile)
             }
         }
     }
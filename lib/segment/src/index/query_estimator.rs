@@ -8,7 +8,7 @@ use std::cmp::{max, min};
 use itertools::Itertools;
 
 use crate::index::field_index::{CardinalityEstimation, PrimaryCondition};
-use crate::types::{Condition, Filter};
+use crate::types::{Condition, Filter, MinShould};
 
 /// Re-estimate cardinality based on number of available vectors
 /// Assuming that deleted vectors are not correlated with the filter
@@ -125,6 +125,67 @@ pub fn combine_should_estimations(
     }
 }
 
+/// Combine estimations of conditions, at least `min_count` of which should match
+///
+/// Expected count assumes conditions to be independent.
+pub fn combine_min_should_estimations(
+    estimations: &[CardinalityEstimation],
+    min_count: usize,
+    total: usize,
+) -> CardinalityEstimation {
+    if min_count == 0 {
+        // Every point matches
+        return CardinalityEstimation::exact(total);
+    }
+    if min_count == 1 {
+        return combine_should_estimations(estimations, total);
+    }
+    if estimations.len() < min_count {
+        // Not enough conditions, nothing matches
+        return CardinalityEstimation::exact(0);
+    }
+
+    // Every matched point matches at least one condition,
+    // so primary clauses of all conditions together cover the result
+    let mut clauses: Vec<PrimaryCondition> = vec![];
+    for estimation in estimations {
+        if estimation.primary_clauses.is_empty() {
+            clauses = vec![];
+            break;
+        }
+        clauses.append(&mut estimation.primary_clauses.clone());
+    }
+
+    // Each matched point is counted in at least `min_count` conditions,
+    // other points are counted in at most `min_count - 1` of them
+    let min_sum: usize = estimations.iter().map(|x| x.min).sum();
+    let max_sum: usize = estimations.iter().map(|x| x.max).sum();
+    let min_estimation =
+        min_sum.saturating_sub((min_count - 1) * total) / (estimations.len() - min_count + 1);
+    let max_estimation = min(max_sum / min_count, total);
+
+    // Probability of at least `min_count` hits, `hits_prob[i]` is a probability of exactly `i` hits
+    let mut hits_prob = vec![0.0; min_count + 1];
+    hits_prob[0] = 1.0;
+    for estimation in estimations {
+        let hit_prob = estimation.exp as f64 / total as f64;
+        // The last bucket accumulates `min_count` and more hits
+        hits_prob[min_count] += hits_prob[min_count - 1] * hit_prob;
+        for hits in (1..min_count).rev() {
+            hits_prob[hits] = hits_prob[hits] * (1.0 - hit_prob) + hits_prob[hits - 1] * hit_prob;
+        }
+        hits_prob[0] *= 1.0 - hit_prob;
+    }
+    let exp_estimation = (hits_prob[min_count] * total as f64).round() as usize;
+
+    CardinalityEstimation {
+        primary_clauses: clauses,
+        min: min_estimation,
+        exp: exp_estimation.max(min_estimation).min(max_estimation),
+        max: max_estimation,
+    }
+}
+
 pub fn combine_must_estimations(
     estimations: &[CardinalityEstimation],
     total: usize,
@@ -196,6 +257,17 @@ where
             }
         }
     }
+    match &filter.min_should {
+        None => {}
+        Some(MinShould {
+            conditions,
+            min_count,
+        }) => {
+            filter_estimations.push(estimate_min_should(
+                estimator, conditions, *min_count, total,
+            ));
+        }
+    }
     match &filter.must_not {
         None => {}
         Some(conditions) => {
@@ -221,6 +293,20 @@ where
     combine_should_estimations(&should_estimations, total)
 }
 
+fn estimate_min_should<F>(
+    estimator: &F,
+    conditions: &[Condition],
+    min_count: usize,
+    total: usize,
+) -> CardinalityEstimation
+where
+    F: Fn(&Condition) -> CardinalityEstimation,
+{
+    let estimate = |x| estimate_condition(estimator, x, total);
+    let min_should_estimations = conditions.iter().map(estimate).collect_vec();
+    combine_min_should_estimations(&min_should_estimations, min_count, total)
+}
+
 fn estimate_must<F>(estimator: &F, conditions: &[Condition], total: usize) -> CardinalityEstimation
 where
     F: Fn(&Condition) -> CardinalityEstimation,
@@ -342,6 +428,7 @@ mod tests {
     fn must_estimation_query_test() {
         let query = Filter {
             should: None,
+            min_should: None,
             must: Some(vec![
                 test_condition("color".to_owned()),
                 test_condition("size".to_owned()),
@@ -368,6 +455,7 @@ mod tests {
                 test_condition("color".to_owned()),
                 test_condition("size".to_owned()),
             ]),
+            min_should: None,
             must: None,
             must_not: None,
         };
@@ -387,6 +475,7 @@ mod tests {
                 test_condition("size".to_owned()),
                 test_condition("un-indexed".to_owned()),
             ]),
+            min_should: None,
             must: None,
             must_not: None,
         };
@@ -405,6 +494,7 @@ mod tests {
             should: Some(vec![
                 Condition::Filter(Filter {
                     should: None,
+                    min_should: None,
                     must: Some(vec![
                         test_condition("color".to_owned()),
                         test_condition("size".to_owned()),
@@ -413,6 +503,7 @@ mod tests {
                 }),
                 Condition::Filter(Filter {
                     should: None,
+                    min_should: None,
                     must: Some(vec![
                         test_condition("price".to_owned()),
                         test_condition("size".to_owned()),
@@ -420,6 +511,7 @@ mod tests {
                     must_not: None,
                 }),
             ]),
+            min_should: None,
             must: None,
             must_not: Some(vec![Condition::HasId(HasIdCondition {
                 has_id: HashSet::from_iter([1, 2, 3, 4, 5].into_iter().map(|x| x.into())),
@@ -437,8 +529,10 @@ mod tests {
     fn another_complex_estimation_query_test() {
         let query = Filter {
             should: None,
+            min_should: None,
             must: Some(vec![
                 Condition::Filter(Filter {
+                    min_should: None,
                     must: None,
                     should: Some(vec![
                         test_condition("color".to_owned()),
@@ -447,6 +541,7 @@ mod tests {
                     must_not: None,
                 }),
                 Condition::Filter(Filter {
+                    min_should: None,
                     must: None,
                     should: Some(vec![
                         test_condition("price".to_owned()),
@@ -485,4 +580,58 @@ mod tests {
         let res = combine_must_estimations(&estimations, 10_000);
         eprintln!("res = {res:#?}");
     }
+
+    #[test]
+    fn test_combine_min_should_estimations() {
+        let estimation = |exp| CardinalityEstimation {
+            primary_clauses: vec![],
+            min: exp,
+            exp,
+            max: exp,
+        };
+        let estimations = vec![estimation(500), estimation(500), estimation(500)];
+
+        // Independent conditions with probability 0.5: P(at least 2 of 3) = 0.5
+        let res = combine_min_should_estimations(&estimations, 2, TOTAL);
+        assert_eq!(res.exp, 500);
+        assert_eq!(res.min, 250);
+        assert_eq!(res.max, 750);
+
+        let res = combine_min_should_estimations(&estimations, 3, TOTAL);
+        assert_eq!(res.exp, 125);
+        assert_eq!(res.max, 500);
+
+        // Same as `should`
+        let res = combine_min_should_estimations(&estimations, 1, TOTAL);
+        assert_eq!(res.exp, 875);
+
+        let res = combine_min_should_estimations(&estimations, 4, TOTAL);
+        assert_eq!(res.max, 0);
+
+        let res = combine_min_should_estimations(&estimations, 0, TOTAL);
+        assert_eq!(res.min, TOTAL);
+    }
+
+    #[test]
+    fn min_should_estimation_query_test() {
+        let query = Filter {
+            should: None,
+            min_should: Some(MinShould {
+                conditions: vec![
+                    test_condition("color".to_owned()),
+                    test_condition("size".to_owned()),
+                    test_condition("price".to_owned()),
+                ],
+                min_count: 2,
+            }),
+            must: None,
+            must_not: None,
+        };
+
+        let estimation = estimate_filter(&test_estimator, &query, TOTAL);
+        assert_eq!(estimation.primary_clauses.len(), 3);
+        assert!(estimation.max <= TOTAL);
+        assert!(estimation.exp <= estimation.max);
+        assert!(estimation.min <= estimation.exp);
+    }
 }
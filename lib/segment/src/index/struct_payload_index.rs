@@ -38,6 +38,16 @@ use crate::types::{
 
 pub const PAYLOAD_FIELD_INDEX_PATH: &str = "fields";
 
+/// Check if one of the JSON paths is a prefix of the other, ignoring array indices
+fn paths_overlap(a: &str, b: &str) -> bool {
+    fn field(segment: &str) -> &str {
+        segment.split('[').next().unwrap_or(segment)
+    }
+    a.split('.')
+        .zip(b.split('.'))
+        .all(|(a_segment, b_segment)| field(a_segment) == field(b_segment))
+}
+
 /// `PayloadIndex` implementation, which actually uses index structures for providing faster search
 pub struct StructPayloadIndex {
     /// Payload storage
@@ -55,6 +65,37 @@ pub struct StructPayloadIndex {
 }
 
 impl StructPayloadIndex {
+    /// Bring field indexes of the point in sync with its current payload
+    ///
+    /// Only fields, which paths overlap with any of the `changed_paths`, are re-indexed.
+    fn update_field_indexes(
+        &mut self,
+        point_id: PointOffsetType,
+        changed_paths: &[&str],
+    ) -> OperationResult<()> {
+        let affected_fields: Vec<_> = self
+            .field_indexes
+            .keys()
+            .filter(|field| changed_paths.iter().any(|path| paths_overlap(field, path)))
+            .cloned()
+            .collect();
+        if affected_fields.is_empty() {
+            return Ok(());
+        }
+
+        let payload = self.payload(point_id)?;
+        for field in affected_fields {
+            let field_value = payload.get_value(&field);
+            if let Some(field_index) = self.field_indexes.get_mut(&field) {
+                for index in field_index {
+                    index.remove_point(point_id)?;
+                    index.add_point(point_id, &field_value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn estimate_field_condition(
         &self,
         condition: &FieldCondition,
@@ -476,14 +517,27 @@ impl PayloadIndex for StructPayloadIndex {
         }
     }
 
-    fn assign(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
-        for (field, field_index) in &mut self.field_indexes {
-            let field_value = &payload.get_value(field);
-            for index in field_index {
-                index.add_point(point_id, field_value)?;
-            }
-        }
-        self.payload.borrow_mut().assign(point_id, payload)
+    fn assign(
+        &mut self,
+        point_id: PointOffsetType,
+        payload: &Payload,
+        key: &Option<PayloadKeyType>,
+    ) -> OperationResult<()> {
+        match key {
+            Some(key) => self
+                .payload
+                .borrow_mut()
+                .assign_by_key(point_id, payload, key)?,
+            None => self.payload.borrow_mut().assign(point_id, payload)?,
+        };
+
+        // Merged payload may have changed values of indexed fields under the updated keys,
+        // including the nested ones
+        let changed_paths: Vec<&str> = match key {
+            Some(key) => vec![key.as_str()],
+            None => payload.iter().map(|(key, _)| key.as_str()).collect(),
+        };
+        self.update_field_indexes(point_id, &changed_paths)
     }
 
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
@@ -495,12 +549,12 @@ impl PayloadIndex for StructPayloadIndex {
         point_id: PointOffsetType,
         key: PayloadKeyTypeRef,
     ) -> OperationResult<Vec<Value>> {
-        if let Some(indexes) = self.field_indexes.get_mut(key) {
-            for index in indexes {
-                index.remove_point(point_id)?;
-            }
+        let removed = self.payload.borrow_mut().delete(point_id, key)?;
+        if !removed.is_empty() {
+            // Key may be a parent (or an array element) of some indexed fields
+            self.update_field_indexes(point_id, &[key])?;
         }
-        self.payload.borrow_mut().delete(point_id, key)
+        Ok(removed)
     }
 
     fn drop(&mut self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
@@ -1,15 +1,20 @@
 use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 
-use common::types::ScoredPointOffset;
+use common::types::{PointOffsetType, ScoredPointOffset};
 
+use super::disk_ann_index::disk_ann::DiskAnnIndex;
+use super::hnsw_index::appendable_hnsw::AppendableHnswIndex;
 use super::hnsw_index::graph_links::{GraphLinksMmap, GraphLinksRam};
 use super::hnsw_index::hnsw::HNSWIndex;
+use super::ivf_index::ivf::IvfIndex;
 use super::plain_payload_index::PlainIndex;
 use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
+use crate::data_types::search_profile::VectorIndexSearchProfile;
 use crate::data_types::vectors::QueryVector;
 use crate::telemetry::VectorIndexSearchesTelemetry;
-use crate::types::{Filter, SearchParams};
+use crate::types::{Filter, SearchCalibration, SearchParams};
 
 /// Trait for vector searching
 pub trait VectorIndex {
@@ -21,7 +26,20 @@ pub trait VectorIndex {
         top: usize,
         params: Option<&SearchParams>,
         is_stopped: &AtomicBool,
-    ) -> Vec<Vec<ScoredPointOffset>>;
+    ) -> Vec<Vec<ScoredPointOffset>> {
+        self.search_with_profile(vectors, filter, top, params, is_stopped)
+            .0
+    }
+
+    /// Same as [`VectorIndex::search`], but also reports how the search was performed
+    fn search_with_profile(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+    ) -> (Vec<Vec<ScoredPointOffset>>, VectorIndexSearchProfile);
 
     /// Force internal index rebuild.
     fn build_index(&mut self, stopped: &AtomicBool) -> OperationResult<()>;
@@ -32,12 +50,39 @@ pub trait VectorIndex {
 
     /// The number of indexed vectors, currently accessible
     fn indexed_vector_count(&self) -> usize;
+
+    /// Add the vector of point `id` to the index, after it was inserted or changed in the storage.
+    ///
+    /// Only indexes of appendable segments are updated, others are rebuilt by the optimizers.
+    fn update_vector(&mut self, _id: PointOffsetType) -> OperationResult<()> {
+        Ok(())
+    }
+
+    /// Persist the changes made by [`VectorIndex::update_vector`]
+    fn flusher(&self) -> Flusher {
+        Box::new(|| Ok(()))
+    }
+
+    /// Measured recall of the index, if it was calibrated
+    fn search_calibration(&self) -> Option<SearchCalibration> {
+        None
+    }
+
+    /// Measure a part of the recall of the index, see [`VectorIndex::search_calibration`]
+    ///
+    /// Returns `true` once the calibration is finished, or if the index doesn't need one.
+    fn calibrate_search_step(&self, _stopped: &AtomicBool) -> OperationResult<bool> {
+        Ok(true)
+    }
 }
 
 pub enum VectorIndexEnum {
     Plain(PlainIndex),
     HnswRam(HNSWIndex<GraphLinksRam>),
     HnswMmap(HNSWIndex<GraphLinksMmap>),
+    DiskAnn(DiskAnnIndex),
+    Ivf(IvfIndex),
+    HnswAppendable(AppendableHnswIndex),
 }
 
 impl VectorIndexEnum {
@@ -46,6 +91,10 @@ impl VectorIndexEnum {
             Self::Plain(_) => false,
             Self::HnswRam(_) => true,
             Self::HnswMmap(_) => true,
+            Self::DiskAnn(_) => true,
+            Self::Ivf(_) => true,
+            // Segment is not indexed until converted by the indexing optimizer
+            Self::HnswAppendable(_) => false,
         }
     }
 }
@@ -67,6 +116,43 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::HnswMmap(index) => {
                 index.search(vectors, filter, top, params, is_stopped)
             }
+            VectorIndexEnum::DiskAnn(index) => {
+                index.search(vectors, filter, top, params, is_stopped)
+            }
+            VectorIndexEnum::Ivf(index) => index.search(vectors, filter, top, params, is_stopped),
+            VectorIndexEnum::HnswAppendable(index) => {
+                index.search(vectors, filter, top, params, is_stopped)
+            }
+        }
+    }
+
+    fn search_with_profile(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+    ) -> (Vec<Vec<ScoredPointOffset>>, VectorIndexSearchProfile) {
+        match self {
+            VectorIndexEnum::Plain(index) => {
+                index.search_with_profile(vectors, filter, top, params, is_stopped)
+            }
+            VectorIndexEnum::HnswRam(index) => {
+                index.search_with_profile(vectors, filter, top, params, is_stopped)
+            }
+            VectorIndexEnum::HnswMmap(index) => {
+                index.search_with_profile(vectors, filter, top, params, is_stopped)
+            }
+            VectorIndexEnum::DiskAnn(index) => {
+                index.search_with_profile(vectors, filter, top, params, is_stopped)
+            }
+            VectorIndexEnum::Ivf(index) => {
+                index.search_with_profile(vectors, filter, top, params, is_stopped)
+            }
+            VectorIndexEnum::HnswAppendable(index) => {
+                index.search_with_profile(vectors, filter, top, params, is_stopped)
+            }
         }
     }
 
@@ -75,6 +161,9 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::Plain(index) => index.build_index(stopped),
             VectorIndexEnum::HnswRam(index) => index.build_index(stopped),
             VectorIndexEnum::HnswMmap(index) => index.build_index(stopped),
+            VectorIndexEnum::DiskAnn(index) => index.build_index(stopped),
+            VectorIndexEnum::Ivf(index) => index.build_index(stopped),
+            VectorIndexEnum::HnswAppendable(index) => index.build_index(stopped),
         }
     }
 
@@ -83,6 +172,9 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::Plain(index) => index.get_telemetry_data(),
             VectorIndexEnum::HnswRam(index) => index.get_telemetry_data(),
             VectorIndexEnum::HnswMmap(index) => index.get_telemetry_data(),
+            VectorIndexEnum::DiskAnn(index) => index.get_telemetry_data(),
+            VectorIndexEnum::Ivf(index) => index.get_telemetry_data(),
+            VectorIndexEnum::HnswAppendable(index) => index.get_telemetry_data(),
         }
     }
 
@@ -91,6 +183,9 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::Plain(index) => index.files(),
             VectorIndexEnum::HnswRam(index) => index.files(),
             VectorIndexEnum::HnswMmap(index) => index.files(),
+            VectorIndexEnum::DiskAnn(index) => index.files(),
+            VectorIndexEnum::Ivf(index) => index.files(),
+            VectorIndexEnum::HnswAppendable(index) => index.files(),
         }
     }
 
@@ -99,6 +194,53 @@ impl VectorIndex for VectorIndexEnum {
             Self::Plain(index) => index.indexed_vector_count(),
             Self::HnswRam(index) => index.indexed_vector_count(),
             Self::HnswMmap(index) => index.indexed_vector_count(),
+            Self::DiskAnn(index) => index.indexed_vector_count(),
+            Self::Ivf(index) => index.indexed_vector_count(),
+            Self::HnswAppendable(index) => index.indexed_vector_count(),
+        }
+    }
+
+    fn update_vector(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        match self {
+            Self::Plain(index) => index.update_vector(id),
+            Self::HnswRam(index) => index.update_vector(id),
+            Self::HnswMmap(index) => index.update_vector(id),
+            Self::DiskAnn(index) => index.update_vector(id),
+            Self::Ivf(index) => index.update_vector(id),
+            Self::HnswAppendable(index) => index.update_vector(id),
+        }
+    }
+
+    fn flusher(&self) -> Flusher {
+        match self {
+            Self::Plain(index) => index.flusher(),
+            Self::HnswRam(index) => index.flusher(),
+            Self::HnswMmap(index) => index.flusher(),
+            Self::DiskAnn(index) => index.flusher(),
+            Self::Ivf(index) => index.flusher(),
+            Self::HnswAppendable(index) => index.flusher(),
+        }
+    }
+
+    fn search_calibration(&self) -> Option<SearchCalibration> {
+        match self {
+            Self::Plain(index) => index.search_calibration(),
+            Self::HnswRam(index) => index.search_calibration(),
+            Self::HnswMmap(index) => index.search_calibration(),
+            Self::DiskAnn(index) => index.search_calibration(),
+            Self::Ivf(index) => index.search_calibration(),
+            Self::HnswAppendable(index) => index.search_calibration(),
+        }
+    }
+
+    fn calibrate_search_step(&self, stopped: &AtomicBool) -> OperationResult<bool> {
+        match self {
+            Self::Plain(index) => index.calibrate_search_step(stopped),
+            Self::HnswRam(index) => index.calibrate_search_step(stopped),
+            Self::HnswMmap(index) => index.calibrate_search_step(stopped),
+            Self::DiskAnn(index) => index.calibrate_search_step(stopped),
+            Self::Ivf(index) => index.calibrate_search_step(stopped),
+            Self::HnswAppendable(index) => index.calibrate_search_step(stopped),
         }
     }
 }
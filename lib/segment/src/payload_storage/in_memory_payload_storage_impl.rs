@@ -20,6 +20,19 @@ impl PayloadStorage for InMemoryPayloadStorage {
         Ok(())
     }
 
+    fn assign_by_key(
+        &mut self,
+        point_id: PointOffsetType,
+        payload: &Payload,
+        key: PayloadKeyTypeRef,
+    ) -> OperationResult<()> {
+        self.payload
+            .entry(point_id)
+            .or_default()
+            .merge_by_key(payload, key);
+        Ok(())
+    }
+
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
         match self.payload.get(&point_id) {
             Some(payload) => Ok(payload.to_owned()),
@@ -88,6 +101,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: Some(vec![
                 Condition::Field(FieldCondition::new_match("age".to_string(), 43.into())),
                 Condition::Field(FieldCondition::new_match(
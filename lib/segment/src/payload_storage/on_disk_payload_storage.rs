@@ -81,6 +81,17 @@ impl PayloadStorage for OnDiskPayloadStorage {
         Ok(())
     }
 
+    fn assign_by_key(
+        &mut self,
+        point_id: PointOffsetType,
+        payload: &Payload,
+        key: PayloadKeyTypeRef,
+    ) -> OperationResult<()> {
+        let mut point_payload = self.read_payload(point_id)?.unwrap_or_default();
+        point_payload.merge_by_key(payload, key);
+        self.update_storage(point_id, &point_payload)
+    }
+
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
         let payload = self.read_payload(point_id)?;
         match payload {
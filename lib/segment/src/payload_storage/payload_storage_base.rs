@@ -17,6 +17,14 @@ pub trait PayloadStorage {
     /// Assign payload to a concrete point with a concrete payload value
     fn assign(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()>;
 
+    /// Assign payload to a concrete point, merging it into the nested object at the given key
+    fn assign_by_key(
+        &mut self,
+        point_id: PointOffsetType,
+        payload: &Payload,
+        key: PayloadKeyTypeRef,
+    ) -> OperationResult<()>;
+
     /// Get payload for point
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload>;
 
@@ -13,8 +13,8 @@ use crate::payload_storage::condition_checker::ValueChecker;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::ConditionChecker;
 use crate::types::{
-    Condition, FieldCondition, Filter, IsEmptyCondition, IsNullCondition, OwnedPayloadRef, Payload,
-    PayloadContainer, PayloadKeyType,
+    Condition, FieldCondition, Filter, IsEmptyCondition, IsNullCondition, MinShould,
+    OwnedPayloadRef, Payload, PayloadContainer, PayloadKeyType, PointIdType,
 };
 
 fn check_condition<F>(checker: &F, condition: &Condition) -> bool
@@ -32,6 +32,7 @@ where
     F: Fn(&Condition) -> bool,
 {
     check_should(checker, &filter.should)
+        && check_min_should(checker, &filter.min_should)
         && check_must(checker, &filter.must)
         && check_must_not(checker, &filter.must_not)
 }
@@ -47,6 +48,26 @@ where
     }
 }
 
+fn check_min_should<F>(checker: &F, min_should: &Option<MinShould>) -> bool
+where
+    F: Fn(&Condition) -> bool,
+{
+    match min_should {
+        None => true,
+        Some(MinShould {
+            conditions,
+            min_count,
+        }) => {
+            conditions
+                .iter()
+                .filter(|condition| check_condition(checker, condition))
+                .take(*min_count)
+                .count()
+                == *min_count
+        }
+    }
+}
+
 fn check_must<F>(checker: &F, must: &Option<Vec<Condition>>) -> bool
 where
     F: Fn(&Condition) -> bool,
@@ -130,6 +151,38 @@ where
     check_filter(&checker, query)
 }
 
+/// Check filter against the payload of a point, fetched from a segment
+///
+/// `HasId` conditions are checked against the given external id, field indexes are not used.
+pub fn check_payload_with_id(payload: &Payload, point_id: PointIdType, query: &Filter) -> bool {
+    let no_indexes: HashMap<PayloadKeyType, Vec<FieldIndex>> = HashMap::new();
+    let checker = |condition: &Condition| match condition {
+        Condition::Field(field_condition) => {
+            check_field_condition(field_condition, payload, &no_indexes)
+        }
+        Condition::IsEmpty(is_empty) => check_is_empty_condition(is_empty, payload),
+        Condition::IsNull(is_null) => check_is_null_condition(is_null, payload),
+        Condition::HasId(has_id) => has_id.has_id.contains(&point_id),
+        Condition::Nested(nested) => payload
+            .get_value(&nested.array_key())
+            .values()
+            .iter()
+            .filter_map(|value| value.as_object())
+            .any(|object| {
+                check_payload(
+                    Box::new(|| OwnedPayloadRef::from(object)),
+                    None,
+                    &nested.nested.filter,
+                    0,
+                    &no_indexes,
+                )
+            }),
+        Condition::Filter(_) => unreachable!(),
+    };
+
+    check_filter(&checker, query)
+}
+
 pub fn check_is_empty_condition(
     is_empty: &IsEmptyCondition,
     payload: &impl PayloadContainer,
@@ -447,6 +500,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: Some(vec![match_red.clone()]),
             must_not: None,
         };
@@ -454,6 +508,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: Some(vec![match_blue.clone()]),
             must_not: None,
         };
@@ -461,6 +516,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![match_blue.clone()]),
         };
@@ -468,6 +524,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![match_red.clone()]),
         };
@@ -475,6 +532,7 @@ mod tests {
 
         let query = Filter {
             should: Some(vec![match_red.clone(), match_blue.clone()]),
+            min_should: None,
             must: Some(vec![with_delivery.clone(), in_berlin.clone()]),
             must_not: None,
         };
@@ -482,24 +540,61 @@ mod tests {
 
         let query = Filter {
             should: Some(vec![match_red.clone(), match_blue.clone()]),
+            min_should: None,
             must: Some(vec![with_delivery, in_moscow.clone()]),
             must_not: None,
         };
         assert!(!payload_checker.check(0, &query));
 
+        let query = Filter {
+            should: None,
+            min_should: Some(MinShould {
+                conditions: vec![match_red.clone(), in_berlin.clone(), in_moscow.clone()],
+                min_count: 2,
+            }),
+            must: None,
+            must_not: None,
+        };
+        assert!(payload_checker.check(0, &query));
+
+        let query = Filter {
+            should: None,
+            min_should: Some(MinShould {
+                conditions: vec![match_blue.clone(), in_berlin.clone(), in_moscow.clone()],
+                min_count: 2,
+            }),
+            must: None,
+            must_not: None,
+        };
+        assert!(!payload_checker.check(0, &query));
+
+        let query = Filter {
+            should: None,
+            min_should: Some(MinShould {
+                conditions: vec![match_red.clone()],
+                min_count: 2,
+            }),
+            must: None,
+            must_not: None,
+        };
+        assert!(!payload_checker.check(0, &query));
+
         let query = Filter {
             should: Some(vec![
                 Condition::Filter(Filter {
                     should: None,
+                    min_should: None,
                     must: Some(vec![match_red.clone(), in_moscow.clone()]),
                     must_not: None,
                 }),
                 Condition::Filter(Filter {
                     should: None,
+                    min_should: None,
                     must: Some(vec![match_blue.clone(), in_berlin.clone()]),
                     must_not: None,
                 }),
             ]),
+            min_should: None,
             must: None,
             must_not: None,
         };
@@ -509,15 +604,18 @@ mod tests {
             should: Some(vec![
                 Condition::Filter(Filter {
                     should: None,
+                    min_should: None,
                     must: Some(vec![match_blue, in_moscow]),
                     must_not: None,
                 }),
                 Condition::Filter(Filter {
                     should: None,
+                    min_should: None,
                     must: Some(vec![match_red, in_berlin]),
                     must_not: None,
                 }),
             ]),
+            min_should: None,
             must: None,
             must_not: None,
         };
@@ -525,6 +623,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![with_bad_rating]),
         };
@@ -534,6 +633,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![Condition::HasId(ids.into())]),
         };
@@ -543,6 +643,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![Condition::HasId(ids.into())]),
         };
@@ -552,6 +653,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: Some(vec![Condition::HasId(ids.into())]),
             must_not: None,
         };
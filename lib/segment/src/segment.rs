@@ -5,6 +5,7 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
+use std::time::Instant;
 
 use atomic_refcell::AtomicRefCell;
 use common::types::{PointOffsetType, ScoredPointOffset};
@@ -24,6 +25,7 @@ use crate::common::{
     check_named_vectors, check_query_vectors, check_stopped, check_vector, check_vector_name,
 };
 use crate::data_types::named_vectors::NamedVectors;
+use crate::data_types::search_profile::SegmentSearchProfile;
 use crate::data_types::vectors::{QueryVector, VectorElementType};
 use crate::entry::entry_point::SegmentEntry;
 use crate::id_tracker::IdTrackerSS;
@@ -102,6 +104,7 @@ impl VectorData {
 
         let storage_task = match &*self.vector_storage.borrow() {
             VectorStorageEnum::Memmap(storage) => storage.prefault_mmap_pages(),
+            VectorStorageEnum::MemmapBinary(storage) => storage.prefault_mmap_pages(),
             _ => None,
         };
 
@@ -110,6 +113,14 @@ impl VectorData {
 }
 
 impl Segment {
+    /// Segment ID, which is the name of the segment directory
+    fn segment_id(&self) -> &str {
+        self.current_path
+            .file_stem()
+            .and_then(|f| f.to_str())
+            .unwrap()
+    }
+
     /// Replace vectors in-place
     ///
     /// This replaces all named vectors for this point with the given set of named vectors.
@@ -136,6 +147,11 @@ impl Segment {
                 Some(vector) => {
                     let mut vector_storage = vector_data.vector_storage.borrow_mut();
                     vector_storage.insert_vector(internal_id, vector)?;
+                    drop(vector_storage);
+                    vector_data
+                        .vector_index
+                        .borrow_mut()
+                        .update_vector(internal_id)?;
                 }
                 None => {
                     // No vector provided, so we remove it
@@ -173,6 +189,10 @@ impl Segment {
                 .vector_storage
                 .borrow_mut()
                 .insert_vector(internal_id, new_vector.as_ref())?;
+            vector_data
+                .vector_index
+                .borrow_mut()
+                .update_vector(internal_id)?;
         }
         Ok(())
     }
@@ -201,6 +221,11 @@ impl Segment {
                 }
                 Some(vec) => {
                     vector_storage.insert_vector(new_index, vec)?;
+                    drop(vector_storage);
+                    vector_data
+                        .vector_index
+                        .borrow_mut()
+                        .update_vector(new_index)?;
                 }
             }
         }
@@ -715,6 +740,24 @@ impl Segment {
             ))
             .spawn(move || tasks.iter().for_each(mmap_ops::PrefaultMmapPages::exec));
     }
+
+    /// Measure a part of the recall of vector indexes, used to choose search parameters for
+    /// `target_recall`. Expected to be called repeatedly in the background, each call is about
+    /// as expensive as a single search.
+    ///
+    /// Returns `true` once indexes of all vectors are calibrated.
+    pub fn calibrate_search_step(&self, stopped: &AtomicBool) -> OperationResult<bool> {
+        for vector_data in self.vector_data.values() {
+            if !vector_data
+                .vector_index
+                .borrow()
+                .calibrate_search_step(stopped)?
+            {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
 }
 
 /// This is a basic implementation of `SegmentEntry`,
@@ -787,6 +830,49 @@ impl SegmentEntry for Segment {
         res
     }
 
+    fn search_batch_with_profile(
+        &self,
+        vector_name: &str,
+        query_vectors: &[&QueryVector],
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<(Vec<Vec<ScoredPoint>>, Vec<SegmentSearchProfile>)> {
+        check_query_vectors(vector_name, query_vectors, &self.segment_config)?;
+        let start = Instant::now();
+        let vector_data = &self.vector_data[vector_name];
+        let (internal_results, search_profile) = vector_data
+            .vector_index
+            .borrow()
+            .search_with_profile(query_vectors, filter, top, params, is_stopped);
+
+        check_stopped(is_stopped)?;
+
+        let res = internal_results
+            .iter()
+            .map(|internal_result| {
+                self.process_search_result(internal_result, with_payload, with_vector)
+            })
+            .collect::<OperationResult<_>>()?;
+
+        let profile = SegmentSearchProfile {
+            segment_id: self
+                .current_path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            vector_name: vector_name.to_string(),
+            queries: query_vectors.len(),
+            search: search_profile,
+            time: start.elapsed().as_secs_f64(),
+        };
+
+        Ok((res, vec![profile]))
+    }
+
     fn upsert_point(
         &mut self,
         op_num: SeqNumberType,
@@ -916,6 +1002,7 @@ impl SegmentEntry for Segment {
         op_num: SeqNumberType,
         point_id: PointIdType,
         payload: &Payload,
+        key: &Option<PayloadKeyType>,
     ) -> OperationResult<bool> {
         let internal_id = self.id_tracker.borrow().internal_id(point_id);
         self.handle_version_and_failure(op_num, internal_id, |segment| match internal_id {
@@ -923,7 +1010,7 @@ impl SegmentEntry for Segment {
                 segment
                     .payload_index
                     .borrow_mut()
-                    .assign(internal_id, payload)?;
+                    .assign(internal_id, payload, key)?;
                 Ok((true, Some(internal_id)))
             }
             None => Err(OperationError::PointIdError {
@@ -1128,11 +1215,13 @@ impl SegmentEntry for Segment {
             .map(|(key, vector_data)| {
                 let vector_storage = vector_data.vector_storage.borrow();
                 let num_vectors = vector_storage.available_vector_count();
-                let is_indexed = vector_data.vector_index.borrow().is_index();
+                let vector_index = vector_data.vector_index.borrow();
+                let is_indexed = vector_index.is_index();
                 let vector_data_info = VectorDataInfo {
                     num_vectors,
                     num_indexed_vectors: if is_indexed { num_vectors } else { 0 },
                     num_deleted_vectors: vector_storage.deleted_vector_count(),
+                    search_calibration: vector_index.search_calibration(),
                 };
                 (key.to_string(), vector_data_info)
             })
@@ -1192,6 +1281,11 @@ impl SegmentEntry for Segment {
             .values()
             .map(|v| v.vector_storage.borrow().flusher())
             .collect();
+        let vector_index_flushers: Vec<_> = self
+            .vector_data
+            .values()
+            .map(|v| v.vector_index.borrow().flusher())
+            .collect();
         let state = self.get_state();
         let current_path = self.current_path.clone();
         let id_tracker_mapping_flusher = self.id_tracker.borrow().mapping_flusher();
@@ -1256,6 +1350,11 @@ impl SegmentEntry for Segment {
                     OperationError::service_error(format!("Failed to flush vector_storage: {err}"))
                 })?;
             }
+            for vector_index_flusher in vector_index_flushers {
+                vector_index_flusher().map_err(|err| {
+                    OperationError::service_error(format!("Failed to flush vector_index: {err}"))
+                })?;
+            }
             payload_index_flusher().map_err(|err| {
                 OperationError::service_error(format!("Failed to flush payload_index: {err}"))
             })?;
@@ -1420,11 +1519,7 @@ impl SegmentEntry for Segment {
             .borrow()
             .take_database_snapshot(&payload_index_db_backup_path)?;
 
-        let segment_id = self
-            .current_path
-            .file_stem()
-            .and_then(|f| f.to_str())
-            .unwrap();
+        let segment_id = self.segment_id();
 
         let archive_path = snapshot_dir_path.join(format!("{segment_id}.tar"));
 
@@ -1501,6 +1596,10 @@ impl SegmentEntry for Segment {
         Ok(archive_path)
     }
 
+    fn snapshot_versions(&self) -> Vec<(String, SeqNumberType)> {
+        vec![(self.segment_id().to_string(), self.version())]
+    }
+
     fn get_telemetry_data(&self) -> SegmentTelemetry {
         let vector_index_searches: Vec<_> = self
             .vector_data
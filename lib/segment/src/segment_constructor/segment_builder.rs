@@ -142,6 +142,7 @@ impl SegmentBuilder {
                         payload_index.assign(
                             new_internal_id,
                             &other_payload_index.payload(old_internal_id)?,
+                            &None,
                         )?;
                     }
                     Some(existing_internal_id) => {
@@ -158,6 +159,7 @@ impl SegmentBuilder {
                             payload_index.assign(
                                 new_internal_id,
                                 &other_payload_index.payload(old_internal_id)?,
+                                &None,
                             )?;
                             existing_internal_id
                         } else {
@@ -229,7 +231,13 @@ impl SegmentBuilder {
                     .get(vector_name)
                     .map(|config| &config.index)
                 {
-                    Some(Indexes::Hnsw(hnsw)) => max_rayon_threads(hnsw.max_indexing_threads),
+                    Some(Indexes::Hnsw(hnsw) | Indexes::AppendableHnsw(hnsw)) => {
+                        max_rayon_threads(hnsw.max_indexing_threads)
+                    }
+                    Some(Indexes::DiskAnn(disk_ann)) => {
+                        max_rayon_threads(disk_ann.max_indexing_threads)
+                    }
+                    Some(Indexes::Ivf(ivf)) => max_rayon_threads(ivf.max_indexing_threads),
                     _ => 1,
                 };
                 vector_data.vector_storage.borrow_mut().quantize(
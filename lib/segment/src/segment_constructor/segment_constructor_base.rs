@@ -17,8 +17,11 @@ use crate::common::version::StorageVersion;
 use crate::data_types::vectors::DEFAULT_VECTOR_NAME;
 use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
 use crate::id_tracker::IdTracker;
+use crate::index::disk_ann_index::disk_ann::DiskAnnIndex;
+use crate::index::hnsw_index::appendable_hnsw::AppendableHnswIndex;
 use crate::index::hnsw_index::graph_links::{GraphLinksMmap, GraphLinksRam};
 use crate::index::hnsw_index::hnsw::HNSWIndex;
+use crate::index::ivf_index::ivf::IvfIndex;
 use crate::index::plain_payload_index::PlainIndex;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::VectorIndexEnum;
@@ -30,7 +33,9 @@ use crate::types::{
     VectorStorageType,
 };
 use crate::vector_storage::appendable_mmap_vector_storage::open_appendable_memmap_vector_storage;
+use crate::vector_storage::memmap_binary_vector_storage::open_memmap_binary_vector_storage;
 use crate::vector_storage::memmap_vector_storage::open_memmap_vector_storage;
+use crate::vector_storage::simple_binary_vector_storage::open_simple_binary_vector_storage;
 use crate::vector_storage::simple_vector_storage::open_simple_vector_storage;
 use crate::vector_storage::VectorStorage;
 
@@ -101,6 +106,32 @@ fn create_segment(
 
         // Select suitable vector storage type based on configuration
         let vector_storage = match vector_config.storage_type {
+            // Binary vectors are stored bit-packed, in memory
+            VectorStorageType::Memory if vector_config.distance.is_binary() => {
+                let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
+                open_simple_binary_vector_storage(
+                    database.clone(),
+                    &db_column_name,
+                    vector_config.size,
+                    vector_config.distance,
+                )?
+            }
+            // Binary vectors are stored bit-packed, mmap on disk, not appendable
+            VectorStorageType::Mmap if vector_config.distance.is_binary() => {
+                open_memmap_binary_vector_storage(
+                    &vector_storage_path,
+                    vector_config.size,
+                    vector_config.distance,
+                )?
+            }
+            storage_type if vector_config.distance.is_binary() => {
+                return Err(OperationError::ValidationError {
+                    description: format!(
+                        "{storage_type:?} storage is not supported for vector {vector_name} with {:?} distance",
+                        vector_config.distance,
+                    ),
+                });
+            }
             // In memory
             VectorStorageType::Memory => {
                 let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
@@ -167,6 +198,29 @@ fn create_segment(
                     vector_hnsw_config.clone(),
                 )?)
             }),
+            Indexes::DiskAnn(disk_ann_config) => sp(VectorIndexEnum::DiskAnn(DiskAnnIndex::open(
+                &vector_index_path,
+                id_tracker.clone(),
+                vector_storage.clone(),
+                payload_index.clone(),
+                disk_ann_config.clone(),
+            )?)),
+            Indexes::Ivf(ivf_config) => sp(VectorIndexEnum::Ivf(IvfIndex::open(
+                &vector_index_path,
+                id_tracker.clone(),
+                vector_storage.clone(),
+                payload_index.clone(),
+                ivf_config.clone(),
+            )?)),
+            Indexes::AppendableHnsw(vector_hnsw_config) => {
+                sp(VectorIndexEnum::HnswAppendable(AppendableHnswIndex::open(
+                    &vector_index_path,
+                    id_tracker.clone(),
+                    vector_storage.clone(),
+                    payload_index.clone(),
+                    vector_hnsw_config.clone(),
+                )?))
+            }
         };
 
         vector_data.insert(
@@ -132,6 +132,7 @@ mod tests {
                 3,
                 1.into(),
                 &json!({ "color": vec!["red".to_owned(), "green".to_owned()] }).into(),
+                &None,
             )
             .unwrap();
 
@@ -140,6 +141,7 @@ mod tests {
                 3,
                 2.into(),
                 &json!({ "color": vec!["red".to_owned(), "blue".to_owned()] }).into(),
+                &None,
             )
             .unwrap();
 
@@ -148,6 +150,7 @@ mod tests {
                 3,
                 3.into(),
                 &json!({ "color": vec!["red".to_owned(), "yellow".to_owned()] }).into(),
+                &None,
             )
             .unwrap();
 
@@ -156,6 +159,7 @@ mod tests {
                 3,
                 4.into(),
                 &json!({ "color": vec!["red".to_owned(), "green".to_owned()] }).into(),
+                &None,
             )
             .unwrap();
 
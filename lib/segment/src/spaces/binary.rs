@@ -0,0 +1,319 @@
+use common::types::ScoreType;
+
+use super::metric::Metric;
+use crate::data_types::vectors::{VectorElementType, VectorType};
+use crate::types::Distance;
+use crate::vector_storage::div_ceil;
+
+#[cfg(any(
+    target_arch = "x86_64",
+    all(target_arch = "aarch64", target_feature = "neon")
+))]
+const MIN_PACKED_SIZE_SIMD: usize = 16;
+
+/// Metric over binary vectors, which are stored bit-packed with 8 dimensions per byte
+///
+/// Float representation of the same vector is used for queries and retrieval,
+/// every positive element is considered as set bit.
+pub trait BinaryMetric: Metric {
+    /// Greater the value - closer the vectors
+    fn similarity_packed(v1: &[u8], v2: &[u8]) -> ScoreType;
+}
+
+#[derive(Clone)]
+pub struct HammingMetric;
+
+#[derive(Clone)]
+pub struct JaccardMetric;
+
+/// Number of bytes required to store bit-packed vector of given dimensionality
+#[inline]
+pub const fn packed_size(dim: usize) -> usize {
+    div_ceil(dim, u8::BITS as usize)
+}
+
+#[inline]
+fn is_bit_set(value: VectorElementType) -> bool {
+    value > 0.0
+}
+
+/// Pack float vector into bits, writes result into `packed`
+///
+/// Unused trailing bits of the last byte are always zero.
+pub fn pack_binary_vector_into(vector: &[VectorElementType], packed: &mut [u8]) {
+    debug_assert_eq!(packed.len(), packed_size(vector.len()));
+    packed.fill(0);
+    for (i, value) in vector.iter().copied().enumerate() {
+        if is_bit_set(value) {
+            packed[i / 8] |= 1 << (i % 8);
+        }
+    }
+}
+
+pub fn pack_binary_vector(vector: &[VectorElementType]) -> Vec<u8> {
+    let mut packed = vec![0; packed_size(vector.len())];
+    pack_binary_vector_into(vector, &mut packed);
+    packed
+}
+
+/// Unpack bits into float vector of `0.0` and `1.0` values
+pub fn unpack_binary_vector(packed: &[u8], dim: usize) -> VectorType {
+    (0..dim)
+        .map(|i| {
+            if packed[i / 8] & (1 << (i % 8)) != 0 {
+                1.0
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+impl Metric for HammingMetric {
+    fn distance() -> Distance {
+        Distance::Hamming
+    }
+
+    fn similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        let differ = v1
+            .iter()
+            .zip(v2)
+            .filter(|(a, b)| is_bit_set(**a) != is_bit_set(**b))
+            .count();
+        -(differ as ScoreType)
+    }
+
+    fn preprocess(vector: VectorType) -> VectorType {
+        binary_preprocess(vector)
+    }
+
+    fn postprocess(score: ScoreType) -> ScoreType {
+        score.abs()
+    }
+}
+
+impl BinaryMetric for HammingMetric {
+    fn similarity_packed(v1: &[u8], v2: &[u8]) -> ScoreType {
+        -(hamming_packed(v1, v2) as ScoreType)
+    }
+}
+
+impl Metric for JaccardMetric {
+    fn distance() -> Distance {
+        Distance::Jaccard
+    }
+
+    fn similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        let (intersection, union) =
+            v1.iter()
+                .zip(v2)
+                .fold((0, 0), |(intersection, union), (a, b)| {
+                    let (a, b) = (is_bit_set(*a), is_bit_set(*b));
+                    (intersection + (a && b) as u32, union + (a || b) as u32)
+                });
+        jaccard_similarity(intersection, union)
+    }
+
+    fn preprocess(vector: VectorType) -> VectorType {
+        binary_preprocess(vector)
+    }
+
+    fn postprocess(score: ScoreType) -> ScoreType {
+        score.abs()
+    }
+}
+
+impl BinaryMetric for JaccardMetric {
+    fn similarity_packed(v1: &[u8], v2: &[u8]) -> ScoreType {
+        let (intersection, union) = jaccard_packed(v1, v2);
+        jaccard_similarity(intersection, union)
+    }
+}
+
+pub fn binary_preprocess(vector: VectorType) -> VectorType {
+    vector
+        .into_iter()
+        .map(|x| if is_bit_set(x) { 1.0 } else { 0.0 })
+        .collect()
+}
+
+/// Negated Jaccard distance, vectors without any set bits are considered equal
+#[inline]
+fn jaccard_similarity(intersection: u32, union: u32) -> ScoreType {
+    if union == 0 {
+        return 0.0;
+    }
+    -(1.0 - intersection as ScoreType / union as ScoreType)
+}
+
+pub fn hamming_packed(v1: &[u8], v2: &[u8]) -> u32 {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("popcnt") && v1.len() >= MIN_PACKED_SIZE_SIMD {
+            return unsafe { hamming_packed_popcnt(v1, v2) };
+        }
+    }
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") && v1.len() >= MIN_PACKED_SIZE_SIMD {
+            return unsafe { hamming_packed_neon(v1, v2) };
+        }
+    }
+
+    hamming_packed_impl(v1, v2)
+}
+
+/// Returns bit counts of intersection and union of given vectors
+pub fn jaccard_packed(v1: &[u8], v2: &[u8]) -> (u32, u32) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("popcnt") && v1.len() >= MIN_PACKED_SIZE_SIMD {
+            return unsafe { jaccard_packed_popcnt(v1, v2) };
+        }
+    }
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") && v1.len() >= MIN_PACKED_SIZE_SIMD {
+            return unsafe { jaccard_packed_neon(v1, v2) };
+        }
+    }
+
+    jaccard_packed_impl(v1, v2)
+}
+
+/// Apply `count` to every pair of 64 bit words of given vectors and sum results
+#[inline(always)]
+fn count_packed_words(v1: &[u8], v2: &[u8], count: impl Fn(u64, u64) -> u32) -> u32 {
+    let chunks1 = v1.chunks_exact(8);
+    let chunks2 = v2.chunks_exact(8);
+    let tail = chunks1
+        .remainder()
+        .iter()
+        .zip(chunks2.remainder())
+        .map(|(a, b)| count(u64::from(*a), u64::from(*b)))
+        .sum::<u32>();
+    chunks1
+        .zip(chunks2)
+        .map(|(a, b)| {
+            count(
+                u64::from_ne_bytes(a.try_into().unwrap()),
+                u64::from_ne_bytes(b.try_into().unwrap()),
+            )
+        })
+        .sum::<u32>()
+        + tail
+}
+
+#[inline(always)]
+fn hamming_packed_impl(v1: &[u8], v2: &[u8]) -> u32 {
+    count_packed_words(v1, v2, |a, b| (a ^ b).count_ones())
+}
+
+#[inline(always)]
+fn jaccard_packed_impl(v1: &[u8], v2: &[u8]) -> (u32, u32) {
+    (
+        count_packed_words(v1, v2, |a, b| (a & b).count_ones()),
+        count_packed_words(v1, v2, |a, b| (a | b).count_ones()),
+    )
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "popcnt")]
+unsafe fn hamming_packed_popcnt(v1: &[u8], v2: &[u8]) -> u32 {
+    hamming_packed_impl(v1, v2)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "popcnt")]
+unsafe fn jaccard_packed_popcnt(v1: &[u8], v2: &[u8]) -> (u32, u32) {
+    jaccard_packed_impl(v1, v2)
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+unsafe fn hamming_packed_neon(v1: &[u8], v2: &[u8]) -> u32 {
+    use std::arch::aarch64::*;
+
+    let n = v1.len();
+    let m = n - (n % 16);
+    let ptr1 = v1.as_ptr();
+    let ptr2 = v2.as_ptr();
+    let mut result: u32 = 0;
+    let mut i: usize = 0;
+    while i < m {
+        let xor = veorq_u8(vld1q_u8(ptr1.add(i)), vld1q_u8(ptr2.add(i)));
+        result += u32::from(vaddvq_u8(vcntq_u8(xor)));
+        i += 16;
+    }
+    result + hamming_packed_impl(&v1[m..], &v2[m..])
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+unsafe fn jaccard_packed_neon(v1: &[u8], v2: &[u8]) -> (u32, u32) {
+    use std::arch::aarch64::*;
+
+    let n = v1.len();
+    let m = n - (n % 16);
+    let ptr1 = v1.as_ptr();
+    let ptr2 = v2.as_ptr();
+    let mut intersection: u32 = 0;
+    let mut union: u32 = 0;
+    let mut i: usize = 0;
+    while i < m {
+        let a = vld1q_u8(ptr1.add(i));
+        let b = vld1q_u8(ptr2.add(i));
+        intersection += u32::from(vaddvq_u8(vcntq_u8(vandq_u8(a, b))));
+        union += u32::from(vaddvq_u8(vcntq_u8(vorrq_u8(a, b))));
+        i += 16;
+    }
+    let (tail_intersection, tail_union) = jaccard_packed_impl(&v1[m..], &v2[m..]);
+    (intersection + tail_intersection, union + tail_union)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn test_pack_unpack() {
+        let vector = vec![1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0];
+        let packed = pack_binary_vector(&vector);
+        assert_eq!(packed, vec![0b0001_1001, 0b0000_0010]);
+        assert_eq!(unpack_binary_vector(&packed, vector.len()), vector);
+    }
+
+    #[test]
+    fn test_binary_metrics() {
+        let v1 = vec![1.0, 1.0, 0.0, 0.0];
+        let v2 = vec![1.0, 0.0, 1.0, 0.0];
+
+        assert_eq!(HammingMetric::similarity(&v1, &v2), -2.0);
+        assert_eq!(JaccardMetric::similarity(&v1, &v2), -(1.0 - 1.0 / 3.0));
+        assert_eq!(JaccardMetric::similarity(&[0.0; 4], &[0.0; 4]), 0.0);
+    }
+
+    #[test]
+    fn test_packed_matches_unpacked() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for dim in [1, 7, 64, 129, 1000] {
+            let v1: Vec<VectorElementType> = (0..dim).map(|_| rng.gen_range(0..2) as f32).collect();
+            let v2: Vec<VectorElementType> = (0..dim).map(|_| rng.gen_range(0..2) as f32).collect();
+            let (p1, p2) = (pack_binary_vector(&v1), pack_binary_vector(&v2));
+
+            assert_eq!(
+                HammingMetric::similarity_packed(&p1, &p2),
+                HammingMetric::similarity(&v1, &v2),
+            );
+            assert_eq!(
+                JaccardMetric::similarity_packed(&p1, &p2),
+                JaccardMetric::similarity(&v1, &v2),
+            );
+            assert_eq!(hamming_packed_impl(&p1, &p2), hamming_packed(&p1, &p2));
+            assert_eq!(jaccard_packed_impl(&p1, &p2), jaccard_packed(&p1, &p2));
+        }
+    }
+}
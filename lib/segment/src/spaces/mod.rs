@@ -1,3 +1,4 @@
+pub mod binary;
 pub mod metric;
 pub mod simple;
 pub mod tools;
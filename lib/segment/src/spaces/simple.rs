@@ -29,6 +29,9 @@ pub struct CosineMetric;
 #[derive(Clone)]
 pub struct EuclidMetric;
 
+#[derive(Clone)]
+pub struct ManhattanMetric;
+
 impl Metric for EuclidMetric {
     fn distance() -> Distance {
         Distance::Euclid
@@ -71,6 +74,48 @@ impl Metric for EuclidMetric {
     }
 }
 
+impl Metric for ManhattanMetric {
+    fn distance() -> Distance {
+        Distance::Manhattan
+    }
+
+    fn similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx")
+                && is_x86_feature_detected!("fma")
+                && v1.len() >= MIN_DIM_SIZE_AVX
+            {
+                return unsafe { manhattan_similarity_avx(v1, v2) };
+            }
+        }
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("sse") && v1.len() >= MIN_DIM_SIZE_SIMD {
+                return unsafe { manhattan_similarity_sse(v1, v2) };
+            }
+        }
+
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") && v1.len() >= MIN_DIM_SIZE_SIMD {
+                return unsafe { manhattan_similarity_neon(v1, v2) };
+            }
+        }
+
+        manhattan_similarity(v1, v2)
+    }
+
+    fn preprocess(vector: VectorType) -> VectorType {
+        vector
+    }
+
+    fn postprocess(score: ScoreType) -> ScoreType {
+        score.abs()
+    }
+}
+
 impl Metric for DotProductMetric {
     fn distance() -> Distance {
         Distance::Dot
@@ -190,6 +235,16 @@ pub fn euclid_similarity(v1: &[VectorElementType], v2: &[VectorElementType]) ->
     -s
 }
 
+pub fn manhattan_similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+    let s: ScoreType = v1
+        .iter()
+        .copied()
+        .zip(v2.iter().copied())
+        .map(|(a, b)| (a - b).abs())
+        .sum();
+    -s
+}
+
 pub fn cosine_preprocess(vector: VectorType) -> VectorType {
     let mut length: f32 = vector.iter().map(|x| x * x).sum();
     if length < f32::EPSILON {
@@ -212,4 +267,13 @@ mod tests {
         let res = CosineMetric::preprocess(vec![0.0, 0.0, 0.0, 0.0]);
         assert_eq!(res, vec![0.0, 0.0, 0.0, 0.0]);
     }
+
+    #[test]
+    fn test_manhattan_distance() {
+        let v1 = vec![1.0, -2.0, 3.0];
+        let v2 = vec![4.0, 2.0, 3.0];
+        let score = ManhattanMetric::similarity(&v1, &v2);
+        assert_eq!(score, -7.0);
+        assert_eq!(ManhattanMetric::postprocess(score), 7.0);
+    }
 }
@@ -99,6 +99,7 @@ impl Anonymize for VectorDataInfo {
             num_vectors: self.num_vectors.anonymize(),
             num_indexed_vectors: self.num_indexed_vectors.anonymize(),
             num_deleted_vectors: self.num_deleted_vectors.anonymize(),
+            search_calibration: self.search_calibration.clone(),
         }
     }
 }
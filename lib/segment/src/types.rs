@@ -1,7 +1,7 @@
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Formatter;
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
 use std::mem::size_of;
 use std::ops::Deref;
 use std::rc::Rc;
@@ -12,9 +12,11 @@ use geo::prelude::HaversineDistance;
 use geo::{Contains, Coord, LineString, Point, Polygon};
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Schema, SchemaObject, StringValidation};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use serde_json::{Map, Value};
+use serde_json::{Map, Number, Value};
 use smol_str::SmolStr;
 use uuid::Uuid;
 use validator::{Validate, ValidationError, ValidationErrors};
@@ -27,8 +29,9 @@ use crate::common::utils::{
 };
 use crate::data_types::text_index::TextIndexParams;
 use crate::data_types::vectors::{VectorElementType, VectorStruct, VectorType};
+use crate::spaces::binary::{HammingMetric, JaccardMetric};
 use crate::spaces::metric::Metric;
-use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric};
+use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 
 pub type PayloadKeyType = String;
 pub type PayloadKeyTypeRef<'a> = &'a str;
@@ -42,12 +45,143 @@ pub type IntPayloadType = i64;
 
 pub const VECTOR_ELEMENT_SIZE: usize = size_of::<VectorElementType>();
 
+/// Maximal length of a string point ID in bytes
+pub const MAX_STRING_ID_LENGTH: usize = 64;
+
+#[derive(Debug, thiserror::Error)]
+pub enum InvalidStringId {
+    #[error("string point ID must be from 1 to {MAX_STRING_ID_LENGTH} bytes long, got {0} bytes")]
+    Length(usize),
+    #[error("string point ID {0} is a UUID, use a UUID point ID instead")]
+    Uuid(String),
+}
+
+/// Arbitrary UTF-8 string used as a point ID
+///
+/// The string is stored inline with a bounded length, so that point IDs stay `Copy`.
+/// Canonical UUID strings are not valid string IDs, they are always UUID point IDs.
+#[derive(Copy, Clone)]
+pub struct StringId {
+    len: u8,
+    bytes: [u8; MAX_STRING_ID_LENGTH],
+}
+
+impl StringId {
+    pub fn as_str(&self) -> &str {
+        // Only constructed from valid `&str`
+        std::str::from_utf8(self.as_bytes()).expect("string point ID is valid UTF-8")
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+}
+
+impl TryFrom<&str> for StringId {
+    type Error = InvalidStringId;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if value.is_empty() || value.len() > MAX_STRING_ID_LENGTH {
+            return Err(InvalidStringId::Length(value.len()));
+        }
+        // Would be indistinguishable from the UUID point ID in JSON
+        if is_canonical_uuid(value) {
+            return Err(InvalidStringId::Uuid(value.to_string()));
+        }
+        let mut bytes = [0; MAX_STRING_ID_LENGTH];
+        bytes[..value.len()].copy_from_slice(value.as_bytes());
+        Ok(StringId {
+            len: value.len() as u8,
+            bytes,
+        })
+    }
+}
+
+fn is_canonical_uuid(value: &str) -> bool {
+    Uuid::try_parse(value).map_or(false, |uuid| uuid.to_string() == value)
+}
+
+// Compare and hash only the used bytes, byte order of UTF-8 strings matches their `str` order
+impl PartialEq for StringId {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for StringId {}
+
+impl Ord for StringId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_bytes().cmp(other.as_bytes())
+    }
+}
+
+impl PartialOrd for StringId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Hash for StringId {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state)
+    }
+}
+
+impl std::fmt::Debug for StringId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+
+impl std::fmt::Display for StringId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for StringId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for StringId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        StringId::try_from(value.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+impl JsonSchema for StringId {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "StringId".to_string()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        Schema::Object(SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            string: Some(Box::new(StringValidation {
+                max_length: Some(MAX_STRING_ID_LENGTH as u32),
+                min_length: Some(1),
+                pattern: None,
+            })),
+            ..Default::default()
+        })
+    }
+}
+
 /// Type, used for specifying point ID in user interface
 #[derive(Debug, Serialize, Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, JsonSchema)]
 #[serde(untagged)]
 pub enum ExtendedPointId {
     NumId(u64),
     Uuid(Uuid),
+    String(StringId),
 }
 
 impl std::fmt::Display for ExtendedPointId {
@@ -55,6 +189,7 @@ impl std::fmt::Display for ExtendedPointId {
         match self {
             ExtendedPointId::NumId(idx) => write!(f, "{idx}"),
             ExtendedPointId::Uuid(uuid) => write!(f, "{uuid}"),
+            ExtendedPointId::String(id) => write!(f, "{id}"),
         }
     }
 }
@@ -65,19 +200,32 @@ impl From<u64> for ExtendedPointId {
     }
 }
 
+impl ExtendedPointId {
+    /// Parse point ID given as a JSON string
+    ///
+    /// JSON has no UUID type, so canonical UUID strings are UUID IDs, any other string is a
+    /// string ID. Numeric strings stay string IDs, so the original string is never lost.
+    pub fn parse_str(s: &str) -> Result<Self, InvalidStringId> {
+        if is_canonical_uuid(s) {
+            if let Ok(uuid) = Uuid::try_parse(s) {
+                return Ok(Self::Uuid(uuid));
+            }
+        }
+        StringId::try_from(s).map(Self::String)
+    }
+}
+
+/// Parse point ID given in a URL path, where numbers can't be told apart from strings
 impl FromStr for ExtendedPointId {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let try_num: Result<u64, _> = s.parse();
-        if let Ok(num) = try_num {
-            return Ok(Self::NumId(num));
-        }
-        let try_uuid = Uuid::from_str(s);
-        if let Ok(uuid) = try_uuid {
-            return Ok(Self::Uuid(uuid));
+        if let Ok(num) = s.parse::<u64>() {
+            if num.to_string() == s {
+                return Ok(Self::NumId(num));
+            }
         }
-        Err(())
+        Self::parse_str(s).map_err(|_| ())
     }
 }
 
@@ -91,17 +239,23 @@ impl<'de> serde::Deserialize<'de> for ExtendedPointId {
             Err(err) => return Err(err),
         };
 
+        if let serde_value::Value::String(s) = &value {
+            return ExtendedPointId::parse_str(s).map_err(serde::de::Error::custom);
+        }
+
         if let Ok(num) = value.clone().deserialize_into() {
             return Ok(ExtendedPointId::NumId(num));
         }
 
+        // Binary formats store UUIDs as bytes
         if let Ok(uuid) = value.clone().deserialize_into() {
             return Ok(ExtendedPointId::Uuid(uuid));
         }
 
         Err(serde::de::Error::custom(format!(
             "value {} is not a valid point ID, \
-             valid values are either an unsigned integer or a UUID",
+             valid values are either an unsigned integer, a UUID or a string \
+             of at most {MAX_STRING_ID_LENGTH} bytes",
             crate::utils::fmt::SerdeValue(&value),
         )))
     }
@@ -122,6 +276,14 @@ pub enum Distance {
     Euclid,
     // <https://en.wikipedia.org/wiki/Dot_product>
     Dot,
+    // <https://simple.wikipedia.org/wiki/Manhattan_distance>
+    Manhattan,
+    // <https://en.wikipedia.org/wiki/Hamming_distance>
+    // Binary vectors, stored bit-packed
+    Hamming,
+    // <https://en.wikipedia.org/wiki/Jaccard_index>
+    // Binary vectors, stored bit-packed
+    Jaccard,
 }
 
 impl Distance {
@@ -130,6 +292,9 @@ impl Distance {
             Distance::Cosine => CosineMetric::preprocess(vector),
             Distance::Euclid => EuclidMetric::preprocess(vector),
             Distance::Dot => DotProductMetric::preprocess(vector),
+            Distance::Manhattan => ManhattanMetric::preprocess(vector),
+            Distance::Hamming => HammingMetric::preprocess(vector),
+            Distance::Jaccard => JaccardMetric::preprocess(vector),
         }
     }
 
@@ -138,16 +303,31 @@ impl Distance {
             Distance::Cosine => CosineMetric::postprocess(score),
             Distance::Euclid => EuclidMetric::postprocess(score),
             Distance::Dot => DotProductMetric::postprocess(score),
+            Distance::Manhattan => ManhattanMetric::postprocess(score),
+            Distance::Hamming => HammingMetric::postprocess(score),
+            Distance::Jaccard => JaccardMetric::postprocess(score),
         }
     }
 
     pub fn distance_order(&self) -> Order {
         match self {
             Distance::Cosine | Distance::Dot => Order::LargeBetter,
-            Distance::Euclid => Order::SmallBetter,
+            Distance::Euclid | Distance::Manhattan | Distance::Hamming | Distance::Jaccard => {
+                Order::SmallBetter
+            }
         }
     }
 
+    /// Whether vectors of this distance can be quantized
+    pub fn supports_quantization(&self) -> bool {
+        !self.is_binary()
+    }
+
+    /// Whether vectors of this distance are binary and stored bit-packed
+    pub fn is_binary(&self) -> bool {
+        matches!(self, Distance::Hamming | Distance::Jaccard)
+    }
+
     /// Checks if score satisfies threshold condition
     pub fn check_threshold(&self, score: ScoreType, threshold: ScoreType) -> bool {
         match self.distance_order() {
@@ -164,6 +344,9 @@ impl Distance {
             Distance::Cosine => CosineMetric::similarity(v1, v2),
             Distance::Euclid => EuclidMetric::similarity(v1, v2),
             Distance::Dot => DotProductMetric::similarity(v1, v2),
+            Distance::Manhattan => ManhattanMetric::similarity(v1, v2),
+            Distance::Hamming => HammingMetric::similarity(v1, v2),
+            Distance::Jaccard => JaccardMetric::similarity(v1, v2),
         }
     }
 }
@@ -251,16 +434,105 @@ impl PayloadIndexInfo {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct VectorDataInfo {
     pub num_vectors: usize,
     pub num_indexed_vectors: usize,
     pub num_deleted_vectors: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_calibration: Option<SearchCalibration>,
+}
+
+/// Recall of the index search with given parameters, measured against exact search
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct RecallCalibrationPoint {
+    /// Fraction of vectors, matching the filter of calibration queries
+    pub selectivity: f64,
+    /// Size of the beam in a beam-search
+    pub hnsw_ef: usize,
+    /// Oversampling factor for quantization. Not set, if vectors are not quantized
+    pub oversampling: Option<f64>,
+    /// Fraction of true nearest neighbors found
+    pub recall: f64,
+}
+
+/// Recall of the index, measured on queries sampled from the stored vectors.
+/// Used to choose search parameters for the requested `target_recall`.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchCalibration {
+    /// Number of sampled queries
+    pub num_queries: usize,
+    /// Measured recall for each combination of selectivity and search parameters
+    pub points: Vec<RecallCalibrationPoint>,
+}
+
+impl SearchCalibration {
+    /// Choose the cheapest measured search parameters, reaching `target_recall` for a filter
+    /// of given `selectivity`. Returns `hnsw_ef` and quantization oversampling.
+    ///
+    /// Measurements of the closest lower selectivity are used, as smaller fraction of matching
+    /// vectors requires more effort to reach the same recall.
+    pub fn tune(&self, target_recall: f64, selectivity: f64) -> Option<(usize, Option<f64>)> {
+        let lowest_selectivity = self
+            .points
+            .iter()
+            .map(|point| point.selectivity)
+            .min_by(|a, b| a.total_cmp(b))?;
+        let calibrated_selectivity = self
+            .points
+            .iter()
+            .map(|point| point.selectivity)
+            .filter(|&calibrated| calibrated <= selectivity)
+            .max_by(|a, b| a.total_cmp(b))
+            .unwrap_or(lowest_selectivity);
+
+        let cost = |point: &RecallCalibrationPoint| {
+            point.hnsw_ef as f64 * point.oversampling.unwrap_or(1.0)
+        };
+
+        let candidates = self
+            .points
+            .iter()
+            .filter(|point| point.selectivity == calibrated_selectivity);
+
+        let reaching_target = candidates
+            .clone()
+            .filter(|point| point.recall >= target_recall)
+            .min_by(|a, b| cost(a).total_cmp(&cost(b)));
+
+        // If target recall is not reachable, use the most accurate measured parameters
+        let point = reaching_target.or_else(|| {
+            candidates.max_by(|a, b| {
+                a.recall
+                    .total_cmp(&b.recall)
+                    .then_with(|| cost(b).total_cmp(&cost(a)))
+            })
+        })?;
+        Some((point.hnsw_ef, point.oversampling))
+    }
+
+    /// Combine calibrations of multiple indexes, keeping the lowest recall of each measurement
+    pub fn merge(&mut self, other: &SearchCalibration) {
+        self.num_queries += other.num_queries;
+        for other_point in &other.points {
+            let existing = self.points.iter_mut().find(|point| {
+                point.selectivity == other_point.selectivity
+                    && point.hnsw_ef == other_point.hnsw_ef
+                    && point.oversampling == other_point.oversampling
+            });
+            match existing {
+                Some(point) => point.recall = point.recall.min(other_point.recall),
+                None => self.points.push(*other_point),
+            }
+        }
+    }
 }
 
 /// Aggregated information about segment
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct SegmentInfo {
     pub segment_type: SegmentType,
@@ -317,6 +589,18 @@ pub struct SearchParams {
     /// Size of the beam in a beam-search. Larger the value - more accurate the result, more time required for search.
     pub hnsw_ef: Option<usize>,
 
+    /// Params relevant to IVF index
+    /// Number of closest inverted lists to scan. Larger the value - more accurate the result, more time required for search.
+    #[validate(range(min = 1))]
+    pub nprobe: Option<usize>,
+
+    /// Desired fraction of true nearest neighbors to find, between 0 and 1.
+    /// Search parameters are chosen by the recall of the index, calibrated in the background
+    /// after the index is built. Default parameters are used until then.
+    /// Explicitly set `hnsw_ef` and quantization `oversampling` take precedence.
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub target_recall: Option<f64>,
+
     /// Search without approximation. If set to true, search may run long but with exact results.
     #[serde(default)]
     pub exact: bool,
@@ -344,6 +628,15 @@ pub enum Indexes {
     /// Use filterable HNSW index for approximate search. Is very fast even on a very huge collections,
     /// but require additional space to store index and additional time to build it.
     Hnsw(HnswConfig),
+    /// Use DiskANN graph index, which is stored on disk together with the full vectors.
+    /// Suitable for collections, which don't fit into RAM.
+    DiskAnn(DiskAnnConfig),
+    /// Use IVF index, which groups vectors into inverted lists around k-means centroids.
+    /// Is fast to build and accepts new vectors without rebuilding, suitable for write-heavy collections.
+    Ivf(IvfConfig),
+    /// Use HNSW index, which is updated on every insertion. Used in appendable segments,
+    /// which are still converted into regular HNSW index by the indexing optimizer.
+    AppendableHnsw(HnswConfig),
 }
 
 impl Indexes {
@@ -351,6 +644,10 @@ impl Indexes {
         match self {
             Indexes::Plain {} => false,
             Indexes::Hnsw(_) => true,
+            Indexes::DiskAnn(_) => true,
+            Indexes::Ivf(_) => true,
+            // Segment still needs to be indexed by the optimizer
+            Indexes::AppendableHnsw(_) => false,
         }
     }
 }
@@ -381,6 +678,11 @@ pub struct HnswConfig {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")] // Better backward compatibility
     pub payload_m: Option<usize>,
+    /// Maintain HNSW index in appendable segments, inserting new points into the graph as they arrive.
+    /// If set to false, appendable segments are searched with full scan until indexed by the optimizer. Default: false
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")] // Better backward compatibility
+    pub appendable: Option<bool>,
 }
 
 impl HnswConfig {
@@ -408,6 +710,143 @@ const fn default_max_indexing_threads() -> usize {
     0
 }
 
+/// Config of DiskANN index
+///
+/// Neighbors of each point are stored on disk next to its full vector, so a single read returns
+/// both. The graph is navigated with quantized vectors, if quantization is enabled, which keeps
+/// the number of disk reads per search low.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct DiskAnnConfig {
+    /// Maximal number of edges per node in the index graph. Larger the value - more accurate the search, more space required. Default: 64
+    #[serde(default = "default_disk_ann_max_degree")]
+    #[validate(range(min = 4))]
+    pub max_degree: usize,
+    /// Number of neighbours to consider during the index building. Larger the value - more accurate the search, more time required to build index. Default: 100
+    #[serde(default = "default_disk_ann_build_list_size")]
+    #[validate(range(min = 4))]
+    pub build_list_size: usize,
+    /// Number of candidates to keep during the search, if not specified with `hnsw_ef` search param.
+    /// Larger the value - more accurate the search, more reads from disk required. Default: 100
+    #[serde(default = "default_disk_ann_search_list_size")]
+    #[validate(range(min = 1))]
+    pub search_list_size: usize,
+    /// Number of nodes read from disk in parallel on every search step. Default: 4
+    #[serde(default = "default_disk_ann_beam_width")]
+    #[validate(range(min = 1))]
+    pub beam_width: usize,
+    /// Minimal size (in KiloBytes) of vectors to search with the index.
+    /// If there are fewer vectors, or fewer of them match the filter, full-scan search is used instead.
+    /// Note: 1Kb = 1 vector of size 256
+    #[serde(default = "default_disk_ann_full_scan_threshold")]
+    pub full_scan_threshold: usize,
+    /// Number of parallel threads used for background index building. If 0 - auto selection.
+    #[serde(default = "default_max_indexing_threads")]
+    pub max_indexing_threads: usize,
+}
+
+impl DiskAnnConfig {
+    /// Detect configuration mismatch against `other` that requires rebuilding
+    ///
+    /// Search params and `max_indexing_threads` do not affect the built graph.
+    pub fn mismatch_requires_rebuild(&self, other: &Self) -> bool {
+        self.max_degree != other.max_degree || self.build_list_size != other.build_list_size
+    }
+}
+
+const fn default_disk_ann_max_degree() -> usize {
+    64
+}
+
+const fn default_disk_ann_build_list_size() -> usize {
+    100
+}
+
+const fn default_disk_ann_search_list_size() -> usize {
+    100
+}
+
+const fn default_disk_ann_beam_width() -> usize {
+    4
+}
+
+const fn default_disk_ann_full_scan_threshold() -> usize {
+    DEFAULT_FULL_SCAN_THRESHOLD
+}
+
+impl Default for DiskAnnConfig {
+    fn default() -> Self {
+        DiskAnnConfig {
+            max_degree: default_disk_ann_max_degree(),
+            build_list_size: default_disk_ann_build_list_size(),
+            search_list_size: default_disk_ann_search_list_size(),
+            beam_width: default_disk_ann_beam_width(),
+            full_scan_threshold: default_disk_ann_full_scan_threshold(),
+            max_indexing_threads: default_max_indexing_threads(),
+        }
+    }
+}
+
+/// Config of IVF (inverted file) index
+///
+/// Vectors are assigned to the closest of `num_lists` centroids, trained with k-means.
+/// On search, only the lists of the closest `nprobe` centroids are scanned.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct IvfConfig {
+    /// Number of inverted lists (centroids). If not set - 4 * sqrt(number of vectors) is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub num_lists: Option<usize>,
+    /// Number of closest lists to scan, if not specified with `nprobe` search param.
+    /// Larger the value - more accurate the search, more time required. Default: 16
+    #[serde(default = "default_ivf_nprobe")]
+    #[validate(range(min = 1))]
+    pub nprobe: usize,
+    /// If set, residuals of vectors to their centroids are encoded with product quantization
+    /// of the given compression, and scanned lists are scored with the codes.
+    /// Results are rescored with original vectors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub product_quantization: Option<CompressionRatio>,
+    /// Minimal size (in KiloBytes) of vectors to search with the index.
+    /// If there are fewer vectors, or fewer of them match the filter, full-scan search is used instead.
+    /// Note: 1Kb = 1 vector of size 256
+    #[serde(default = "default_ivf_full_scan_threshold")]
+    pub full_scan_threshold: usize,
+    /// Number of parallel threads used for background index building. If 0 - auto selection.
+    #[serde(default = "default_max_indexing_threads")]
+    pub max_indexing_threads: usize,
+}
+
+impl IvfConfig {
+    /// Detect configuration mismatch against `other` that requires rebuilding
+    ///
+    /// Search params and `max_indexing_threads` do not affect the built index.
+    pub fn mismatch_requires_rebuild(&self, other: &Self) -> bool {
+        self.num_lists != other.num_lists || self.product_quantization != other.product_quantization
+    }
+}
+
+const fn default_ivf_nprobe() -> usize {
+    16
+}
+
+const fn default_ivf_full_scan_threshold() -> usize {
+    DEFAULT_FULL_SCAN_THRESHOLD
+}
+
+impl Default for IvfConfig {
+    fn default() -> Self {
+        IvfConfig {
+            num_lists: None,
+            nprobe: default_ivf_nprobe(),
+            product_quantization: None,
+            full_scan_threshold: default_ivf_full_scan_threshold(),
+            max_indexing_threads: default_max_indexing_threads(),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum CompressionRatio {
@@ -564,6 +1003,7 @@ impl Default for HnswConfig {
             max_indexing_threads: 0,
             on_disk: Some(false),
             payload_m: None,
+            appendable: None,
         }
     }
 }
@@ -702,6 +1142,10 @@ impl VectorDataConfig {
         let is_index_appendable = match self.index {
             Indexes::Plain {} => true,
             Indexes::Hnsw(_) => false,
+            Indexes::DiskAnn(_) => false,
+            // New vectors are added to the lists of the closest centroids
+            Indexes::Ivf(_) => true,
+            Indexes::AppendableHnsw(_) => true,
         };
         let is_storage_appendable = match self.storage_type {
             VectorStorageType::Memory => true,
@@ -796,18 +1240,108 @@ pub struct Payload(pub Map<String, Value>);
 
 impl Payload {
     pub fn merge(&mut self, value: &Payload) {
-        for (key, value) in &value.0 {
-            match value {
-                Value::Null => self.0.remove(key),
-                _ => self.0.insert(key.to_owned(), value.to_owned()),
-            };
-        }
+        utils::merge_json_map(&mut self.0, &value.0)
+    }
+
+    /// Merge payload into the nested object located at the given JSON path
+    pub fn merge_by_key(&mut self, value: &Payload, key: PayloadKeyTypeRef) {
+        utils::set_value_to_json_map(key, &mut self.0, &value.0)
     }
 
     pub fn remove(&mut self, path: &str) -> Vec<Value> {
         utils::remove_value_from_json_map(path, &mut self.0).values()
     }
 
+    /// Add `value` to the number at the given key
+    ///
+    /// Missing value is initialized with `initial` (or 0) before incrementing.
+    /// Integers stay integers if both operands are integers, otherwise float arithmetic is used.
+    pub fn increment(
+        &mut self,
+        key: PayloadKeyTypeRef,
+        value: &Number,
+        initial: Option<&Number>,
+    ) -> OperationResult<()> {
+        let entry = utils::get_or_insert_value_in_json_map(key, &mut self.0)?;
+        let current = match entry {
+            Value::Null => initial.cloned().unwrap_or_else(|| Number::from(0)),
+            Value::Number(number) => number.clone(),
+            _ => {
+                return Err(OperationError::ValidationError {
+                    description: format!(
+                        "Can't increment non-numeric payload value of key `{key}`"
+                    ),
+                })
+            }
+        };
+
+        let result = match (current.as_i64(), value.as_i64()) {
+            (Some(current), Some(value)) => current.checked_add(value).map(Number::from),
+            _ => current
+                .as_f64()
+                .zip(value.as_f64())
+                .and_then(|(current, value)| Number::from_f64(current + value)),
+        };
+
+        match result {
+            Some(result) => {
+                *entry = Value::Number(result);
+                Ok(())
+            }
+            None => Err(OperationError::ValidationError {
+                description: format!("Numeric overflow while incrementing payload key `{key}`"),
+            }),
+        }
+    }
+
+    /// Append values to the array at the given key, missing array is created
+    ///
+    /// If `unique` is set, only values which are not present in the array yet are appended.
+    pub fn array_append(
+        &mut self,
+        key: PayloadKeyTypeRef,
+        values: &[Value],
+        unique: bool,
+    ) -> OperationResult<()> {
+        let entry = utils::get_or_insert_value_in_json_map(key, &mut self.0)?;
+        if entry.is_null() {
+            *entry = Value::Array(Vec::with_capacity(values.len()));
+        }
+        let Value::Array(array) = entry else {
+            return Err(OperationError::ValidationError {
+                description: format!("Payload value of key `{key}` is not an array"),
+            });
+        };
+
+        for value in values {
+            if !unique || !array.contains(value) {
+                array.push(value.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove all occurrences of the values from the array at the given key
+    pub fn array_remove(
+        &mut self,
+        key: PayloadKeyTypeRef,
+        values: &[Value],
+    ) -> OperationResult<()> {
+        if self.get_value(key).check_is_empty() {
+            // Nothing to remove, avoid creating missing objects along the path
+            return Ok(());
+        }
+        let entry = utils::get_or_insert_value_in_json_map(key, &mut self.0)?;
+        let Value::Array(array) = entry else {
+            return Err(OperationError::ValidationError {
+                description: format!("Payload value of key `{key}` is not an array"),
+            });
+        };
+
+        array.retain(|value| !values.contains(value));
+        Ok(())
+    }
+
     pub fn len(&self) -> usize {
         self.0.len()
     }
@@ -1873,6 +2407,16 @@ pub struct WithPayload {
     pub payload_selector: Option<PayloadSelector>,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "snake_case")]
+pub struct MinShould {
+    #[validate]
+    pub conditions: Vec<Condition>,
+    /// Minimal number of conditions, which should match
+    pub min_count: usize,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Default)]
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "snake_case")]
@@ -1880,6 +2424,9 @@ pub struct Filter {
     /// At least one of those conditions should match
     #[validate]
     pub should: Option<Vec<Condition>>,
+    /// At least minimum amount of given conditions should match
+    #[validate]
+    pub min_should: Option<MinShould>,
     /// All conditions must match
     #[validate]
     pub must: Option<Vec<Condition>>,
@@ -1892,6 +2439,7 @@ impl Filter {
     pub fn new_should(condition: Condition) -> Self {
         Filter {
             should: Some(vec![condition]),
+            min_should: None,
             must: None,
             must_not: None,
         }
@@ -1900,6 +2448,7 @@ impl Filter {
     pub fn new_must(condition: Condition) -> Self {
         Filter {
             should: None,
+            min_should: None,
             must: Some(vec![condition]),
             must_not: None,
         }
@@ -1908,6 +2457,7 @@ impl Filter {
     pub fn new_must_not(condition: Condition) -> Self {
         Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![condition]),
         }
@@ -1925,9 +2475,27 @@ impl Filter {
                 }
             }
         };
+        // Two `min_should` clauses can't be combined into one,
+        // so the one of the other filter is moved into `must` as a nested filter
+        let (min_should, other_must) = match (&self.min_should, &other.min_should) {
+            (Some(this), Some(other_min_should)) => {
+                let nested = Condition::Filter(Filter {
+                    min_should: Some(other_min_should.clone()),
+                    ..Default::default()
+                });
+                let mut other_must = other.must.clone().unwrap_or_default();
+                other_must.push(nested);
+                (Some(this.clone()), Some(other_must))
+            }
+            (this, other_min_should) => (
+                this.clone().or_else(|| other_min_should.clone()),
+                other.must.clone(),
+            ),
+        };
         Filter {
             should: merge_component(self.should.clone(), other.should.clone()),
-            must: merge_component(self.must.clone(), other.must.clone()),
+            min_should,
+            must: merge_component(self.must.clone(), other_must),
             must_not: merge_component(self.must_not.clone(), other.must_not.clone()),
         }
     }
@@ -2119,6 +2687,7 @@ mod tests {
     #[test]
     fn test_serialize_query() {
         let filter = Filter {
+            min_should: None,
             must: Some(vec![Condition::Field(FieldCondition::new_match(
                 "hello".to_owned(),
                 "world".to_owned().into(),
@@ -3004,6 +3573,191 @@ mod tests {
         });
         assert_eq!(payload, expected.into());
     }
+
+    #[test]
+    fn test_point_id_parsing() {
+        let id: ExtendedPointId = serde_json::from_value(json!(123)).unwrap();
+        assert_eq!(id, ExtendedPointId::NumId(123));
+
+        let uuid = "550e8400-e29b-41d4-a716-446655440000";
+        let id_uuid: ExtendedPointId = serde_json::from_value(json!(uuid)).unwrap();
+        assert_eq!(
+            id_uuid,
+            ExtendedPointId::Uuid(Uuid::from_str(uuid).unwrap())
+        );
+
+        let id: ExtendedPointId = serde_json::from_value(json!("document/42")).unwrap();
+        let expected = ExtendedPointId::String(StringId::try_from("document/42").unwrap());
+        assert_eq!(id, expected);
+        assert_eq!(serde_json::to_value(id).unwrap(), json!("document/42"));
+        assert_eq!(ExtendedPointId::from_str("document/42"), Ok(expected));
+        assert_eq!(
+            ExtendedPointId::from_str("42"),
+            Ok(ExtendedPointId::NumId(42))
+        );
+
+        // Strings are string IDs in JSON, even if they look like numbers, and round-trip exactly
+        for s in ["42", "042", "+42", "550E8400-E29B-41D4-A716-446655440000"] {
+            let id: ExtendedPointId = serde_json::from_value(json!(s)).unwrap();
+            assert!(matches!(id, ExtendedPointId::String(_)));
+            assert_eq!(serde_json::to_value(id).unwrap(), json!(s));
+        }
+        let string_42: ExtendedPointId = serde_json::from_value(json!("42")).unwrap();
+        assert_ne!(string_42, ExtendedPointId::NumId(42));
+
+        // Canonical UUIDs are UUID IDs only, they can't be string IDs
+        assert!(StringId::try_from(uuid).is_err());
+        assert_eq!(ExtendedPointId::from_str(uuid), Ok(id_uuid));
+
+        let max_length = "x".repeat(MAX_STRING_ID_LENGTH);
+        assert!(serde_json::from_value::<ExtendedPointId>(json!(max_length)).is_ok());
+        let too_long = "x".repeat(MAX_STRING_ID_LENGTH + 1);
+        assert!(serde_json::from_value::<ExtendedPointId>(json!(too_long)).is_err());
+        assert!(serde_json::from_value::<ExtendedPointId>(json!("")).is_err());
+        assert!(serde_json::from_value::<ExtendedPointId>(json!(-1)).is_err());
+
+        // String ids are ordered after numeric and UUID ids, and between each other as strings
+        let mut ids = vec![
+            ExtendedPointId::String(StringId::try_from("b").unwrap()),
+            ExtendedPointId::String(StringId::try_from("ab").unwrap()),
+            ExtendedPointId::Uuid(Uuid::from_u128(1)),
+            ExtendedPointId::NumId(u64::MAX),
+        ];
+        ids.sort();
+        assert_eq!(ids[0], ExtendedPointId::NumId(u64::MAX));
+        assert_eq!(ids[1], ExtendedPointId::Uuid(Uuid::from_u128(1)));
+        assert_eq!(ids[2].to_string(), "ab");
+        assert_eq!(ids[3].to_string(), "b");
+    }
+
+    #[test]
+    fn test_payload_increment() {
+        let mut payload: Payload = json!({
+            "views": 10,
+            "stats": { "score": 1.5 },
+            "name": "John",
+        })
+        .into();
+
+        payload.increment("views", &Number::from(5), None).unwrap();
+        payload
+            .increment("stats.score", &Number::from_f64(0.25).unwrap(), None)
+            .unwrap();
+        payload
+            .increment("stats.likes", &Number::from(-1), Some(&Number::from(100)))
+            .unwrap();
+        payload
+            .increment("counter", &Number::from(1), None)
+            .unwrap();
+
+        assert!(payload.increment("name", &Number::from(1), None).is_err());
+        assert!(payload
+            .increment("name.len", &Number::from(1), None)
+            .is_err());
+        assert!(payload
+            .increment("tags[0]", &Number::from(1), None)
+            .is_err());
+        assert!(payload
+            .increment("views", &Number::from(i64::MAX), None)
+            .is_err());
+
+        let expected = json!({
+            "views": 15,
+            "stats": { "score": 1.75, "likes": 99 },
+            "name": "John",
+            "counter": 1,
+        });
+        assert_eq!(payload, expected.into());
+    }
+
+    #[test]
+    fn test_payload_array_operations() {
+        let mut payload: Payload = json!({
+            "tags": ["a", "b"],
+            "meta": { "ids": [1, 2, 1] },
+            "name": "John",
+        })
+        .into();
+
+        payload
+            .array_append("tags", &[json!("b"), json!("c")], false)
+            .unwrap();
+        payload
+            .array_append("meta.ids", &[json!(2), json!(3)], true)
+            .unwrap();
+        payload.array_append("new.list", &[json!(1)], true).unwrap();
+        payload.array_remove("meta.ids", &[json!(1)]).unwrap();
+        payload.array_remove("missing.list", &[json!(1)]).unwrap();
+
+        assert!(payload.array_append("name", &[json!(1)], false).is_err());
+        assert!(payload.array_remove("name", &[json!(1)]).is_err());
+
+        let expected = json!({
+            "tags": ["a", "b", "b", "c"],
+            "meta": { "ids": [2, 3] },
+            "new": { "list": [1] },
+            "name": "John",
+        });
+        assert_eq!(payload, expected.into());
+    }
+
+    fn calibration_point(selectivity: f64, hnsw_ef: usize, recall: f64) -> RecallCalibrationPoint {
+        RecallCalibrationPoint {
+            selectivity,
+            hnsw_ef,
+            oversampling: None,
+            recall,
+        }
+    }
+
+    #[test]
+    fn test_search_calibration_tune() {
+        let calibration = SearchCalibration {
+            num_queries: 10,
+            points: vec![
+                calibration_point(1.0, 16, 0.8),
+                calibration_point(1.0, 64, 0.95),
+                calibration_point(1.0, 256, 0.99),
+                calibration_point(0.1, 16, 0.5),
+                calibration_point(0.1, 64, 0.8),
+                calibration_point(0.1, 256, 0.9),
+            ],
+        };
+
+        assert_eq!(calibration.tune(0.9, 1.0), Some((64, None)));
+        assert_eq!(calibration.tune(0.99, 1.0), Some((256, None)));
+        // Closest lower selectivity is used
+        assert_eq!(calibration.tune(0.9, 0.5), Some((256, None)));
+        // Selectivity below all measured ones uses the lowest
+        assert_eq!(calibration.tune(0.5, 0.01), Some((16, None)));
+        // Unreachable recall uses the most accurate parameters
+        assert_eq!(calibration.tune(0.999, 1.0), Some((256, None)));
+        assert_eq!(SearchCalibration::default().tune(0.9, 1.0), None);
+    }
+
+    #[test]
+    fn test_search_calibration_merge() {
+        let mut calibration = SearchCalibration {
+            num_queries: 10,
+            points: vec![calibration_point(1.0, 16, 0.8)],
+        };
+        calibration.merge(&SearchCalibration {
+            num_queries: 20,
+            points: vec![
+                calibration_point(1.0, 16, 0.7),
+                calibration_point(1.0, 64, 0.9),
+            ],
+        });
+
+        assert_eq!(calibration.num_queries, 30);
+        assert_eq!(
+            calibration.points,
+            vec![
+                calibration_point(1.0, 16, 0.7),
+                calibration_point(1.0, 64, 0.9),
+            ],
+        );
+    }
 }
 
 pub type TheMap<K, V> = BTreeMap<K, V>;
@@ -1,6 +1,8 @@
 use std::path::Path;
 use std::{fmt, io};
 
+use sha2::{Digest, Sha256};
+
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::utils;
 
@@ -39,3 +41,73 @@ pub fn failed_to_append_error(path: &Path, err: impl fmt::Display) -> OperationE
         "failed to append {path:?} path to the archive: {err}"
     ))
 }
+
+/// Writer, which computes SHA-256 checksum of everything written through it.
+///
+/// Wrap the archive file with it to get the checksum of the archive without reading it again.
+pub struct HashingWriter<W> {
+    inner: W,
+    hasher: Sha256,
+}
+
+impl<W: io::Write> HashingWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// Hex-encoded checksum of the written data
+    pub fn checksum(self) -> String {
+        format!("{:x}", self.hasher.finalize())
+    }
+}
+
+impl<W: io::Write> io::Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Hex-encoded SHA-256 checksum of the file, same as computed by [`HashingWriter`]
+///
+/// This method performs blocking IO.
+pub fn file_checksum(path: &Path) -> io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = HashingWriter::new(io::sink());
+    io::copy(&mut file, &mut hasher)?;
+    Ok(hasher.checksum())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn test_hashing_writer() {
+        let mut writer = HashingWriter::new(Vec::new());
+        writer.write_all(b"hello ").unwrap();
+        writer.write_all(b"world").unwrap();
+        assert_eq!(writer.inner, b"hello world");
+        assert_eq!(
+            writer.checksum(),
+            "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+        );
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), b"hello world").unwrap();
+        assert_eq!(
+            file_checksum(file.path()).unwrap(),
+            "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+        );
+    }
+}
@@ -15,7 +15,7 @@ use crate::types::{Distance, QuantizationConfig};
 use crate::vector_storage::chunked_mmap_vectors::ChunkedMmapVectors;
 use crate::vector_storage::dynamic_mmap_flags::DynamicMmapFlags;
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
-use crate::vector_storage::{VectorStorage, VectorStorageEnum};
+use crate::vector_storage::{DenseVectorStorage, VectorStorage, VectorStorageEnum};
 
 const VECTORS_DIR_PATH: &str = "vectors";
 const DELETED_DIR_PATH: &str = "deleted";
@@ -86,6 +86,12 @@ impl AppendableMmapVectorStorage {
     }
 }
 
+impl DenseVectorStorage for AppendableMmapVectorStorage {
+    fn get_dense(&self, key: PointOffsetType) -> &[VectorElementType] {
+        self.vectors.get(key)
+    }
+}
+
 impl VectorStorage for AppendableMmapVectorStorage {
     fn vector_dim(&self) -> usize {
         self.vectors.dim()
@@ -99,10 +105,6 @@ impl VectorStorage for AppendableMmapVectorStorage {
         self.vectors.len()
     }
 
-    fn get_vector(&self, key: PointOffsetType) -> &[VectorElementType] {
-        self.vectors.get(key)
-    }
-
     fn insert_vector(
         &mut self,
         key: PointOffsetType,
@@ -125,7 +127,7 @@ impl VectorStorage for AppendableMmapVectorStorage {
             // Do not perform preprocessing - vectors should be already processed
             let other_deleted = other.is_deleted_vector(point_id);
             let other_vector = other.get_vector(point_id);
-            let new_id = self.vectors.push(other_vector)?;
+            let new_id = self.vectors.push(&other_vector)?;
             self.set_deleted(new_id, other_deleted)?;
         }
         let end_index = self.vectors.len() as PointOffsetType;
@@ -74,6 +74,19 @@ impl UringReader {
         &mut self,
         points: impl IntoIterator<Item = PointOffsetType>,
         mut callback: impl FnMut(usize, PointOffsetType, &[VectorElementType]),
+    ) -> OperationResult<()> {
+        self.read_stream_raw(points, |idx, point, data| {
+            callback(idx, point, transmute_from_u8_to_slice(data))
+        })
+    }
+
+    /// Same as [`UringReader::read_stream`], but yields raw bytes of each record.
+    ///
+    /// Record `i` is read from the offset `header_size + raw_size * i` of the file.
+    pub fn read_stream_raw(
+        &mut self,
+        points: impl IntoIterator<Item = PointOffsetType>,
+        mut callback: impl FnMut(usize, PointOffsetType, &[u8]),
     ) -> OperationResult<()> {
         // Take `UringReader::io_uring`, so that if we return an error or panic during `read_stream`,
         // `IoUring` would be transparently dropped.
@@ -153,7 +166,7 @@ fn submit_and_read(
     io_uring: &mut IoUring,
     buffers: &mut BufferStore,
     unused_buffer_ids: &mut Vec<usize>,
-    mut callback: impl FnMut(usize, PointOffsetType, &[VectorElementType]),
+    mut callback: impl FnMut(usize, PointOffsetType, &[u8]),
     raw_size: usize,
 ) -> OperationResult<()> {
     let buffers_count = buffers.buffers.len();
@@ -180,8 +193,7 @@ fn submit_and_read(
         let buffer_id = entry.user_data() as usize;
         let meta = buffers.buffers[buffer_id].meta.take().unwrap();
         let buffer = &buffers.buffers[buffer_id].buffer;
-        let vector = transmute_from_u8_to_slice(buffer);
-        callback(meta.index, meta.point_id, vector);
+        callback(meta.index, meta.point_id, buffer);
         unused_buffer_ids.push(buffer_id);
     }
 
@@ -7,8 +7,9 @@ use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
 use super::query_scorer::reco_query_scorer::RecoQueryScorer;
 use crate::common::operation_error::OperationResult;
 use crate::data_types::vectors::QueryVector;
+use crate::spaces::binary::{HammingMetric, JaccardMetric};
 use crate::spaces::metric::Metric;
-use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric};
+use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 use crate::types::Distance;
 use crate::vector_storage::memmap_vector_storage::MemmapVectorStorage;
 use crate::vector_storage::mmap_vectors::MmapVectors;
@@ -241,6 +242,9 @@ impl<'a> AsyncRawScorerBuilder<'a> {
             Distance::Cosine => self._build_with_metric::<CosineMetric>(),
             Distance::Euclid => self._build_with_metric::<EuclidMetric>(),
             Distance::Dot => self._build_with_metric::<DotProductMetric>(),
+            Distance::Manhattan => self._build_with_metric::<ManhattanMetric>(),
+            Distance::Hamming => self._build_with_metric::<HammingMetric>(),
+            Distance::Jaccard => self._build_with_metric::<JaccardMetric>(),
         }
     }
 
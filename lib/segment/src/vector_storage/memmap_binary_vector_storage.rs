@@ -0,0 +1,325 @@
+use std::fs::{create_dir_all, OpenOptions};
+use std::io::Write;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use bitvec::prelude::BitSlice;
+use common::types::PointOffsetType;
+use memmap2::Mmap;
+use memory::mmap_ops;
+
+use super::mmap_vectors::{
+    deleted_mmap_data_start, deleted_mmap_size, ensure_mmap_file_size, DELETED_HEADER, HEADER_SIZE,
+    VECTORS_HEADER,
+};
+use super::quantized::quantized_vectors::QuantizedVectors;
+use super::vector_storage_base::{BinaryVectorStorage, VectorStorage};
+use super::VectorStorageEnum;
+use crate::common::error_logging::LogError;
+use crate::common::mmap_type::MmapBitSlice;
+use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
+use crate::common::Flusher;
+use crate::data_types::vectors::VectorElementType;
+use crate::spaces::binary::{pack_binary_vector, packed_size, unpack_binary_vector};
+use crate::types::{Distance, QuantizationConfig};
+
+const VECTORS_PATH: &str = "matrix.dat";
+const DELETED_PATH: &str = "deleted.dat";
+
+/// Stores bit-packed binary vectors in mem-mapped file
+///
+/// Vectors are stored bit-packed, 8 dimensions per byte.
+/// Like [`MemmapVectorStorage`](super::memmap_vector_storage::MemmapVectorStorage), it is not
+/// possible to insert new vectors, it can only be constructed from another storage.
+pub struct MemmapBinaryVectorStorage {
+    dim: usize,
+    distance: Distance,
+    vectors_path: PathBuf,
+    deleted_path: PathBuf,
+    mmap_store: Option<MmapBinaryVectors>,
+}
+
+/// Mem-mapped bit-packed vectors and their deletion flags
+struct MmapBinaryVectors {
+    num_vectors: usize,
+    /// Memory mapped file for packed vector data
+    ///
+    /// Has an exact size to fit a header and `num_vectors` of packed vectors.
+    mmap: Arc<Mmap>,
+    /// Memory mapped deletion flags
+    deleted: MmapBitSlice,
+    /// Current number of deleted vectors.
+    deleted_count: usize,
+}
+
+pub fn open_memmap_binary_vector_storage(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    debug_assert!(distance.is_binary());
+    create_dir_all(path)?;
+
+    let vectors_path = path.join(VECTORS_PATH);
+    let deleted_path = path.join(DELETED_PATH);
+    let mmap_store = MmapBinaryVectors::open(&vectors_path, &deleted_path, dim)?;
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::MemmapBinary(Box::new(MemmapBinaryVectorStorage {
+            dim,
+            distance,
+            vectors_path,
+            deleted_path,
+            mmap_store: Some(mmap_store),
+        })),
+    )))
+}
+
+impl MmapBinaryVectors {
+    fn open(vectors_path: &Path, deleted_path: &Path, dim: usize) -> OperationResult<Self> {
+        // Allocate/open vectors mmap
+        ensure_mmap_file_size(vectors_path, VECTORS_HEADER, None)
+            .describe("Create mmap data file")?;
+        let mmap = mmap_ops::open_read_mmap(vectors_path).describe("Open mmap for reading")?;
+        let num_vectors = (mmap.len() - HEADER_SIZE) / packed_size(dim);
+
+        // Allocate/open deleted mmap
+        let deleted_mmap_size = deleted_mmap_size(num_vectors);
+        ensure_mmap_file_size(deleted_path, DELETED_HEADER, Some(deleted_mmap_size as u64))
+            .describe("Create mmap deleted file")?;
+        let deleted_mmap =
+            mmap_ops::open_write_mmap(deleted_path).describe("Open mmap deleted for writing")?;
+        let deleted = MmapBitSlice::try_from(deleted_mmap, deleted_mmap_data_start())?;
+        let deleted_count = deleted.count_ones();
+
+        Ok(Self {
+            num_vectors,
+            mmap: mmap.into(),
+            deleted,
+            deleted_count,
+        })
+    }
+}
+
+impl MemmapBinaryVectorStorage {
+    pub fn prefault_mmap_pages(&self) -> Option<mmap_ops::PrefaultMmapPages> {
+        let mmap_store = self.mmap_store.as_ref()?;
+        Some(mmap_ops::PrefaultMmapPages::new(
+            mmap_store.mmap.clone(),
+            Some(&self.vectors_path),
+        ))
+    }
+
+    /// Unpack the stored vector into floats, including deleted ones
+    pub fn get_unpacked(&self, key: PointOffsetType) -> Vec<VectorElementType> {
+        unpack_binary_vector(self.get_packed(key), self.dim)
+    }
+}
+
+impl BinaryVectorStorage for MemmapBinaryVectorStorage {
+    fn get_packed(&self, key: PointOffsetType) -> &[u8] {
+        let mmap_store = self.mmap_store.as_ref().unwrap();
+        let size = packed_size(self.dim);
+        let offset = key as usize * size + HEADER_SIZE;
+        &mmap_store.mmap[offset..offset + size]
+    }
+}
+
+impl VectorStorage for MemmapBinaryVectorStorage {
+    fn vector_dim(&self) -> usize {
+        self.dim
+    }
+
+    fn distance(&self) -> Distance {
+        self.distance
+    }
+
+    fn total_vector_count(&self) -> usize {
+        self.mmap_store.as_ref().unwrap().num_vectors
+    }
+
+    fn insert_vector(
+        &mut self,
+        _key: PointOffsetType,
+        _vector: &[VectorElementType],
+    ) -> OperationResult<()> {
+        panic!("Can't directly update vector in mmap storage")
+    }
+
+    fn update_from(
+        &mut self,
+        other: &VectorStorageEnum,
+        other_ids: &mut dyn Iterator<Item = PointOffsetType>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        let start_index = self.total_vector_count() as PointOffsetType;
+        let mut end_index = start_index;
+
+        // Release current mapping before extending the file
+        self.mmap_store.take();
+
+        // Extend vectors file, write packed vectors of other storage into it
+        let mut vectors_file = OpenOptions::new().append(true).open(&self.vectors_path)?;
+        let mut deleted_ids = vec![];
+        for id in other_ids {
+            check_process_stopped(stopped)?;
+            // Copy packed data directly if possible, avoid unpacking
+            match other {
+                VectorStorageEnum::SimpleBinary(other) => {
+                    vectors_file.write_all(other.get_packed(id))?
+                }
+                VectorStorageEnum::MemmapBinary(other) => {
+                    vectors_file.write_all(other.get_packed(id))?
+                }
+                other => vectors_file.write_all(&pack_binary_vector(&other.get_vector(id)))?,
+            }
+
+            // Remember deleted IDs so we can propagate deletions later
+            if other.is_deleted_vector(id) {
+                deleted_ids.push(end_index);
+            }
+            end_index += 1;
+        }
+        vectors_file.flush()?;
+        drop(vectors_file);
+
+        // Load store with updated files, this also resizes the deleted flags
+        let mut mmap_store =
+            MmapBinaryVectors::open(&self.vectors_path, &self.deleted_path, self.dim)?;
+        for id in deleted_ids {
+            check_process_stopped(stopped)?;
+            if !mmap_store.deleted.replace(id as usize, true) {
+                mmap_store.deleted_count += 1;
+            }
+        }
+        self.mmap_store.replace(mmap_store);
+
+        Ok(start_index..end_index)
+    }
+
+    fn flusher(&self) -> Flusher {
+        match &self.mmap_store {
+            Some(mmap_store) => mmap_store.deleted.flusher(),
+            None => Box::new(|| Ok(())),
+        }
+    }
+
+    /// Binary vectors already use a single bit per dimension, they can't be quantized further
+    fn quantize(
+        &mut self,
+        _path: &Path,
+        _quantization_config: &QuantizationConfig,
+        _max_threads: usize,
+        _stopped: &AtomicBool,
+    ) -> OperationResult<()> {
+        Err(OperationError::ValidationError {
+            description: format!(
+                "Quantization is not supported for binary vectors with {:?} distance",
+                self.distance,
+            ),
+        })
+    }
+
+    fn load_quantization(&mut self, _path: &Path) -> OperationResult<()> {
+        Ok(())
+    }
+
+    fn quantized_storage(&self) -> Option<&QuantizedVectors> {
+        None
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        vec![self.vectors_path.clone(), self.deleted_path.clone()]
+    }
+
+    fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
+        let mmap_store = self.mmap_store.as_mut().unwrap();
+        if mmap_store.num_vectors <= key as usize {
+            return Ok(false);
+        }
+        let is_deleted = !mmap_store.deleted.replace(key as usize, true);
+        if is_deleted {
+            mmap_store.deleted_count += 1;
+        }
+        Ok(is_deleted)
+    }
+
+    fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
+        self.mmap_store.as_ref().unwrap().deleted[key as usize]
+    }
+
+    fn deleted_vector_count(&self) -> usize {
+        self.mmap_store.as_ref().unwrap().deleted_count
+    }
+
+    fn deleted_vector_bitslice(&self) -> &BitSlice {
+        &self.mmap_store.as_ref().unwrap().deleted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitvec::prelude::BitVec;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
+    use crate::vector_storage::new_raw_scorer;
+    use crate::vector_storage::simple_binary_vector_storage::open_simple_binary_vector_storage;
+
+    #[test]
+    fn test_binary_persistence() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let points = vec![
+            vec![1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+            vec![0.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+            vec![1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0],
+        ];
+
+        let db_dir = Builder::new().prefix("db_dir").tempdir().unwrap();
+        let db = open_db(db_dir.path(), &[DB_VECTOR_CF]).unwrap();
+        let simple_storage =
+            open_simple_binary_vector_storage(db, DB_VECTOR_CF, 9, Distance::Hamming).unwrap();
+        {
+            let mut simple_storage = simple_storage.borrow_mut();
+            for (i, point) in points.iter().enumerate() {
+                simple_storage.insert_vector(i as _, point).unwrap();
+            }
+            simple_storage.delete_vector(1).unwrap();
+        }
+
+        {
+            let storage =
+                open_memmap_binary_vector_storage(dir.path(), 9, Distance::Hamming).unwrap();
+            let mut storage = storage.borrow_mut();
+            storage
+                .update_from(&simple_storage.borrow(), &mut (0..3), &Default::default())
+                .unwrap();
+            storage.flusher()().unwrap();
+        }
+
+        // Reopen and check packed vectors and deletions survived
+        let storage = open_memmap_binary_vector_storage(dir.path(), 9, Distance::Hamming).unwrap();
+        let storage = storage.borrow();
+        assert_eq!(storage.total_vector_count(), 3);
+        assert_eq!(storage.deleted_vector_count(), 1);
+        assert!(storage.is_deleted_vector(1));
+        for (i, point) in points.iter().enumerate() {
+            assert_eq!(storage.get_vector(i as _).as_ref(), point.as_slice());
+        }
+
+        // Deleted vector is skipped, others are scored on packed data
+        let raw_scorer = new_raw_scorer(
+            points[0].clone().into(),
+            &storage,
+            &BitVec::repeat(false, 3),
+        );
+        let closest = raw_scorer.peek_top_all(3);
+        assert_eq!(closest.len(), 2);
+        assert_eq!(closest[0].idx, 0);
+        assert_eq!(closest[1].idx, 2);
+    }
+}
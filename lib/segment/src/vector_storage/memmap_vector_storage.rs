@@ -18,7 +18,7 @@ use crate::data_types::vectors::VectorElementType;
 use crate::types::{Distance, QuantizationConfig};
 use crate::vector_storage::common::get_async_scorer;
 use crate::vector_storage::mmap_vectors::MmapVectors;
-use crate::vector_storage::VectorStorage;
+use crate::vector_storage::{DenseVectorStorage, VectorStorage};
 
 const VECTORS_PATH: &str = "matrix.dat";
 const DELETED_PATH: &str = "deleted.dat";
@@ -87,6 +87,12 @@ impl MemmapVectorStorage {
     }
 }
 
+impl DenseVectorStorage for MemmapVectorStorage {
+    fn get_dense(&self, key: PointOffsetType) -> &[VectorElementType] {
+        self.mmap_store.as_ref().unwrap().get_vector(key)
+    }
+}
+
 impl VectorStorage for MemmapVectorStorage {
     fn vector_dim(&self) -> usize {
         self.mmap_store.as_ref().unwrap().dim
@@ -100,10 +106,6 @@ impl VectorStorage for MemmapVectorStorage {
         self.mmap_store.as_ref().unwrap().num_vectors
     }
 
-    fn get_vector(&self, key: PointOffsetType) -> &[VectorElementType] {
-        self.mmap_store.as_ref().unwrap().get_vector(key)
-    }
-
     fn insert_vector(
         &mut self,
         _key: PointOffsetType,
@@ -134,7 +136,7 @@ impl VectorStorage for MemmapVectorStorage {
         for id in other_ids {
             check_process_stopped(stopped)?;
             let vector = other.get_vector(id);
-            let raw_bites = mmap_ops::transmute_to_u8_slice(vector);
+            let raw_bites = mmap_ops::transmute_to_u8_slice(vector.as_ref());
             vectors_file.write_all(raw_bites)?;
             end_index += 1;
 
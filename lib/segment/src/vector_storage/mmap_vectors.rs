@@ -24,9 +24,9 @@ use crate::vector_storage::async_io::UringReader;
 use crate::vector_storage::async_io_mock::UringReader;
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 
-const HEADER_SIZE: usize = 4;
-const VECTORS_HEADER: &[u8; HEADER_SIZE] = b"data";
-const DELETED_HEADER: &[u8; HEADER_SIZE] = b"drop";
+pub(super) const HEADER_SIZE: usize = 4;
+pub(super) const VECTORS_HEADER: &[u8; HEADER_SIZE] = b"data";
+pub(super) const DELETED_HEADER: &[u8; HEADER_SIZE] = b"drop";
 
 /// Mem-mapped file
 pub struct MmapVectors {
@@ -260,7 +260,11 @@ impl MmapVectors {
 /// * `path`: path of the file.
 /// * `header`: header to set when the file is newly created.
 /// * `size`: set the file size in bytes, filled with zeroes.
-fn ensure_mmap_file_size(path: &Path, header: &[u8], size: Option<u64>) -> OperationResult<()> {
+pub(super) fn ensure_mmap_file_size(
+    path: &Path,
+    header: &[u8],
+    size: Option<u64>,
+) -> OperationResult<()> {
     // If it exists, only set the length
     if path.exists() {
         if let Some(size) = size {
@@ -283,7 +287,7 @@ fn ensure_mmap_file_size(path: &Path, header: &[u8], size: Option<u64>) -> Opera
 
 /// Get start position of flags `BitSlice` in deleted mmap.
 #[inline]
-const fn deleted_mmap_data_start() -> usize {
+pub(super) const fn deleted_mmap_data_start() -> usize {
     let align = mem::align_of::<usize>();
     div_ceil(HEADER_SIZE, align) * align
 }
@@ -291,7 +295,7 @@ const fn deleted_mmap_data_start() -> usize {
 /// Calculate size for deleted mmap to hold the given number of vectors.
 ///
 /// The mmap will hold a file header and an aligned `BitSlice`.
-fn deleted_mmap_size(num: usize) -> usize {
+pub(super) fn deleted_mmap_size(num: usize) -> usize {
     let unit_size = mem::size_of::<usize>();
     let num_bytes = div_ceil(num, 8);
     let num_usizes = div_ceil(num_bytes, unit_size);
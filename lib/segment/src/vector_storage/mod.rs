@@ -5,10 +5,12 @@ mod chunked_mmap_vectors;
 mod chunked_utils;
 pub mod chunked_vectors;
 mod dynamic_mmap_flags;
+pub mod memmap_binary_vector_storage;
 pub mod memmap_vector_storage;
 mod mmap_vectors;
 pub mod quantized;
 pub mod raw_scorer;
+pub mod simple_binary_vector_storage;
 pub mod simple_vector_storage;
 mod vector_storage_base;
 
@@ -16,7 +18,7 @@ mod vector_storage_base;
 mod tests;
 
 #[cfg(target_os = "linux")]
-mod async_io;
+pub(crate) mod async_io;
 mod async_io_mock;
 pub mod common;
 pub mod query;
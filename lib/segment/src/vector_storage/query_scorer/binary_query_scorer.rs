@@ -0,0 +1,90 @@
+use std::marker::PhantomData;
+
+use common::types::{PointOffsetType, ScoreType};
+
+use crate::data_types::vectors::{VectorElementType, VectorType};
+use crate::spaces::binary::{pack_binary_vector, BinaryMetric};
+use crate::vector_storage::query::reco_query::RecoQuery;
+use crate::vector_storage::query_scorer::QueryScorer;
+use crate::vector_storage::BinaryVectorStorage;
+
+/// Scores bit-packed stored vectors against a packed query, without unpacking
+pub struct BinaryQueryScorer<'a, TMetric: BinaryMetric, TVectorStorage: BinaryVectorStorage> {
+    vector_storage: &'a TVectorStorage,
+    query: Vec<u8>,
+    metric: PhantomData<TMetric>,
+}
+
+impl<'a, TMetric: BinaryMetric, TVectorStorage: BinaryVectorStorage>
+    BinaryQueryScorer<'a, TMetric, TVectorStorage>
+{
+    pub fn new(query: VectorType, vector_storage: &'a TVectorStorage) -> Self {
+        Self {
+            query: pack_binary_vector(&query),
+            vector_storage,
+            metric: PhantomData,
+        }
+    }
+}
+
+impl<'a, TMetric: BinaryMetric, TVectorStorage: BinaryVectorStorage> QueryScorer
+    for BinaryQueryScorer<'a, TMetric, TVectorStorage>
+{
+    #[inline]
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        TMetric::similarity_packed(&self.query, self.vector_storage.get_packed(idx))
+    }
+
+    #[inline]
+    fn score(&self, v2: &[VectorElementType]) -> ScoreType {
+        TMetric::similarity_packed(&self.query, &pack_binary_vector(v2))
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        let v1 = self.vector_storage.get_packed(point_a);
+        let v2 = self.vector_storage.get_packed(point_b);
+        TMetric::similarity_packed(v1, v2)
+    }
+}
+
+/// Scores bit-packed stored vectors against packed recommendation examples, without unpacking
+pub struct BinaryRecoQueryScorer<'a, TMetric: BinaryMetric, TVectorStorage: BinaryVectorStorage> {
+    vector_storage: &'a TVectorStorage,
+    query: RecoQuery<Vec<u8>>,
+    metric: PhantomData<TMetric>,
+}
+
+impl<'a, TMetric: BinaryMetric, TVectorStorage: BinaryVectorStorage>
+    BinaryRecoQueryScorer<'a, TMetric, TVectorStorage>
+{
+    pub fn new(query: RecoQuery<VectorType>, vector_storage: &'a TVectorStorage) -> Self {
+        Self {
+            query: query.transform(|vector| pack_binary_vector(&vector)),
+            vector_storage,
+            metric: PhantomData,
+        }
+    }
+
+    fn score_packed(&self, packed: &[u8]) -> ScoreType {
+        self.query
+            .score_by(|example| TMetric::similarity_packed(example, packed))
+    }
+}
+
+impl<'a, TMetric: BinaryMetric, TVectorStorage: BinaryVectorStorage> QueryScorer
+    for BinaryRecoQueryScorer<'a, TMetric, TVectorStorage>
+{
+    #[inline]
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        self.score_packed(self.vector_storage.get_packed(idx))
+    }
+
+    #[inline]
+    fn score(&self, against: &[VectorElementType]) -> ScoreType {
+        self.score_packed(&pack_binary_vector(against))
+    }
+
+    fn score_internal(&self, _point_a: PointOffsetType, _point_b: PointOffsetType) -> ScoreType {
+        unimplemented!("Recommendation scorer compares against multiple vectors, not just one")
+    }
+}
@@ -3,11 +3,13 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use bitvec::prelude::BitSlice;
 use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
 
+use super::query_scorer::binary_query_scorer::{BinaryQueryScorer, BinaryRecoQueryScorer};
 use super::query_scorer::reco_query_scorer::RecoQueryScorer;
-use super::{VectorStorage, VectorStorageEnum};
+use super::{BinaryVectorStorage, DenseVectorStorage, VectorStorage, VectorStorageEnum};
 use crate::data_types::vectors::QueryVector;
+use crate::spaces::binary::{BinaryMetric, HammingMetric, JaccardMetric};
 use crate::spaces::metric::Metric;
-use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric};
+use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 use crate::spaces::tools::peek_top_largest_iterable;
 use crate::types::Distance;
 use crate::vector_storage::query_scorer::metric_query_scorer::MetricQueryScorer;
@@ -18,7 +20,9 @@ use crate::vector_storage::query_scorer::QueryScorer;
 /// │                │   │              │   │  - Cosine         │
 /// │       ┌─────┐  │   │     ┌─────┐  │   │  - Dot            │
 /// │       │     │◄─┼───┤     │     │◄─┼───┤  - Euclidean      │
-/// │       └─────┘  │   │     └─────┘  │   │                   │
+/// │       └─────┘  │   │     └─────┘  │   │  - Manhattan      │
+/// │                │   │              │   │  - Hamming        │
+/// │                │   │              │   │  - Jaccard        │
 /// │                │   │              │   │                   │
 /// └────────────────┘   └──────────────┘   └───────────────────┘
 /// - Deletions          - Scoring logic    - Vector Distance
@@ -89,6 +93,14 @@ pub fn new_stoppable_raw_scorer<'a>(
     match vector_storage {
         VectorStorageEnum::Simple(vs) => raw_scorer_impl(query, vs, point_deleted, is_stopped),
 
+        VectorStorageEnum::SimpleBinary(vs) => {
+            binary_raw_scorer_impl(query, vs, point_deleted, is_stopped)
+        }
+
+        VectorStorageEnum::MemmapBinary(vs) => {
+            binary_raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
+
         VectorStorageEnum::Memmap(vs) => {
             if vs.has_async_reader() {
                 #[cfg(target_os = "linux")]
@@ -124,7 +136,7 @@ pub fn new_raw_scorer<'a>(
     new_stoppable_raw_scorer(vector, vector_storage, point_deleted, &DEFAULT_STOPPED)
 }
 
-pub fn raw_scorer_impl<'a, TVectorStorage: VectorStorage>(
+pub fn raw_scorer_impl<'a, TVectorStorage: DenseVectorStorage>(
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
     point_deleted: &'a BitSlice,
@@ -149,10 +161,53 @@ pub fn raw_scorer_impl<'a, TVectorStorage: VectorStorage>(
             point_deleted,
             is_stopped,
         ),
+        Distance::Manhattan => new_scorer_with_metric::<ManhattanMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Hamming => new_scorer_with_metric::<HammingMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Jaccard => new_scorer_with_metric::<JaccardMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+    }
+}
+
+fn binary_raw_scorer_impl<'a, TVectorStorage: BinaryVectorStorage>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> Box<dyn RawScorer + 'a> {
+    match vector_storage.distance() {
+        Distance::Hamming => new_binary_scorer_with_metric::<HammingMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Jaccard => new_binary_scorer_with_metric::<JaccardMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Cosine | Distance::Euclid | Distance::Dot | Distance::Manhattan => {
+            unreachable!("binary vector storage is only created for binary distances")
+        }
     }
 }
 
-fn new_scorer_with_metric<'a, TMetric: Metric + 'a, TVectorStorage: VectorStorage>(
+fn new_scorer_with_metric<'a, TMetric: Metric + 'a, TVectorStorage: DenseVectorStorage>(
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
     point_deleted: &'a BitSlice,
@@ -175,6 +230,33 @@ fn new_scorer_with_metric<'a, TMetric: Metric + 'a, TVectorStorage: VectorStorag
     }
 }
 
+fn new_binary_scorer_with_metric<
+    'a,
+    TMetric: BinaryMetric + 'a,
+    TVectorStorage: BinaryVectorStorage,
+>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> Box<dyn RawScorer + 'a> {
+    let vec_deleted = vector_storage.deleted_vector_bitslice();
+    match query {
+        QueryVector::Nearest(vector) => raw_scorer_from_query_scorer(
+            BinaryQueryScorer::<TMetric, TVectorStorage>::new(vector, vector_storage),
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+        QueryVector::Recommend(reco_query) => raw_scorer_from_query_scorer(
+            BinaryRecoQueryScorer::<TMetric, TVectorStorage>::new(reco_query, vector_storage),
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+    }
+}
+
 pub fn raw_scorer_from_query_scorer<'a, TQueryScorer: QueryScorer + 'a>(
     query_scorer: TQueryScorer,
     point_deleted: &'a BitSlice,
@@ -286,3 +368,72 @@ where
         peek_top_largest_iterable(scores, top)
     }
 }
+
+/// Wrapper around [`RawScorer`] which counts number of scored vectors
+///
+/// Used to report amount of work done by a search.
+pub struct CountingRawScorer<'a> {
+    raw_scorer: &'a dyn RawScorer,
+    scored: std::cell::Cell<usize>,
+}
+
+impl<'a> CountingRawScorer<'a> {
+    pub fn new(raw_scorer: &'a dyn RawScorer) -> Self {
+        Self {
+            raw_scorer,
+            scored: Default::default(),
+        }
+    }
+
+    /// Number of vectors scored so far
+    pub fn scored(&self) -> usize {
+        self.scored.get()
+    }
+
+    fn add_scored(&self, count: usize) {
+        self.scored.set(self.scored.get() + count);
+    }
+}
+
+impl<'a> RawScorer for CountingRawScorer<'a> {
+    fn score_points(&self, points: &[PointOffsetType], scores: &mut [ScoredPointOffset]) -> usize {
+        let count = self.raw_scorer.score_points(points, scores);
+        self.add_scored(count);
+        count
+    }
+
+    fn score_points_unfiltered(
+        &self,
+        points: &mut dyn Iterator<Item = PointOffsetType>,
+    ) -> Vec<ScoredPointOffset> {
+        let scores = self.raw_scorer.score_points_unfiltered(points);
+        self.add_scored(scores.len());
+        scores
+    }
+
+    fn check_vector(&self, point: PointOffsetType) -> bool {
+        self.raw_scorer.check_vector(point)
+    }
+
+    fn score_point(&self, point: PointOffsetType) -> ScoreType {
+        self.add_scored(1);
+        self.raw_scorer.score_point(point)
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        self.raw_scorer.score_internal(point_a, point_b)
+    }
+
+    fn peek_top_iter(
+        &self,
+        points: &mut dyn Iterator<Item = PointOffsetType>,
+        top: usize,
+    ) -> Vec<ScoredPointOffset> {
+        let mut counted = points.inspect(|_| self.add_scored(1));
+        self.raw_scorer.peek_top_iter(&mut counted, top)
+    }
+
+    fn peek_top_all(&self, top: usize) -> Vec<ScoredPointOffset> {
+        self.raw_scorer.peek_top_all(top)
+    }
+}
@@ -0,0 +1,253 @@
+use std::ops::Range;
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use bitvec::prelude::{BitSlice, BitVec};
+use common::types::PointOffsetType;
+use log::debug;
+use parking_lot::RwLock;
+use rocksdb::DB;
+use serde::{Deserialize, Serialize};
+
+use super::chunked_vectors::ChunkedVectors;
+use super::simple_vector_storage::bitvec_set_deleted;
+use super::vector_storage_base::{BinaryVectorStorage, VectorStorage};
+use super::VectorStorageEnum;
+use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
+use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::common::Flusher;
+use crate::data_types::vectors::VectorElementType;
+use crate::spaces::binary::{pack_binary_vector_into, packed_size, unpack_binary_vector};
+use crate::types::{Distance, QuantizationConfig};
+use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
+
+/// In-memory storage of binary vectors with on-update persistence using `store`
+///
+/// Vectors are stored bit-packed, 8 dimensions per byte.
+pub struct SimpleBinaryVectorStorage {
+    dim: usize,
+    distance: Distance,
+    vectors: ChunkedVectors<u8>,
+    db_wrapper: DatabaseColumnWrapper,
+    update_buffer: StoredRecord,
+    /// BitVec for deleted flags. Grows dynamically upto last set flag.
+    deleted: BitVec,
+    /// Current number of deleted vectors.
+    deleted_count: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+struct StoredRecord {
+    pub deleted: bool,
+    pub vector: Vec<u8>,
+}
+
+pub fn open_simple_binary_vector_storage(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    debug_assert!(distance.is_binary());
+    let mut vectors = ChunkedVectors::new(packed_size(dim));
+    let (mut deleted, mut deleted_count) = (BitVec::new(), 0);
+
+    let db_wrapper = DatabaseColumnWrapper::new(database, database_column_name);
+
+    for (key, value) in db_wrapper.lock_db().iter()? {
+        let point_id: PointOffsetType = bincode::deserialize(&key)
+            .map_err(|_| OperationError::service_error("cannot deserialize point id from db"))?;
+        let stored_record: StoredRecord = bincode::deserialize(&value)
+            .map_err(|_| OperationError::service_error("cannot deserialize record from db"))?;
+
+        // Propagate deleted flag
+        if stored_record.deleted {
+            bitvec_set_deleted(&mut deleted, point_id, true);
+            deleted_count += 1;
+        }
+        vectors.insert(point_id, &stored_record.vector)?;
+    }
+
+    debug!("Segment binary vectors: {}", vectors.len());
+    debug!(
+        "Estimated segment size {} MB",
+        vectors.len() * packed_size(dim) / 1024 / 1024
+    );
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::SimpleBinary(SimpleBinaryVectorStorage {
+            dim,
+            distance,
+            vectors,
+            db_wrapper,
+            update_buffer: StoredRecord {
+                deleted: false,
+                vector: vec![0; packed_size(dim)],
+            },
+            deleted,
+            deleted_count,
+        }),
+    )))
+}
+
+impl SimpleBinaryVectorStorage {
+    /// Set deleted flag for given key. Returns previous deleted state.
+    #[inline]
+    fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> bool {
+        if key as usize >= self.vectors.len() {
+            return false;
+        }
+        let was_deleted = bitvec_set_deleted(&mut self.deleted, key, deleted);
+        if was_deleted != deleted {
+            if !was_deleted {
+                self.deleted_count += 1;
+            } else {
+                self.deleted_count -= 1;
+            }
+        }
+        was_deleted
+    }
+
+    fn update_stored(
+        &mut self,
+        key: PointOffsetType,
+        deleted: bool,
+        packed: Option<&[u8]>,
+    ) -> OperationResult<()> {
+        // Write vector state to buffer record
+        let record = &mut self.update_buffer;
+        record.deleted = deleted;
+        if let Some(packed) = packed {
+            record.vector.copy_from_slice(packed);
+        }
+
+        // Store updated record
+        self.db_wrapper.put(
+            bincode::serialize(&key).unwrap(),
+            bincode::serialize(&record).unwrap(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Unpack the stored vector into floats, including deleted ones
+    pub fn get_unpacked(&self, key: PointOffsetType) -> Vec<VectorElementType> {
+        unpack_binary_vector(self.vectors.get(key), self.dim)
+    }
+
+    fn pack(&self, vector: &[VectorElementType]) -> Vec<u8> {
+        let mut packed = vec![0; packed_size(self.dim)];
+        pack_binary_vector_into(vector, &mut packed);
+        packed
+    }
+}
+
+impl BinaryVectorStorage for SimpleBinaryVectorStorage {
+    fn get_packed(&self, key: PointOffsetType) -> &[u8] {
+        self.vectors.get(key)
+    }
+}
+
+impl VectorStorage for SimpleBinaryVectorStorage {
+    fn vector_dim(&self) -> usize {
+        self.dim
+    }
+
+    fn distance(&self) -> Distance {
+        self.distance
+    }
+
+    fn total_vector_count(&self) -> usize {
+        self.vectors.len()
+    }
+
+    fn insert_vector(
+        &mut self,
+        key: PointOffsetType,
+        vector: &[VectorElementType],
+    ) -> OperationResult<()> {
+        let packed = self.pack(vector);
+        self.vectors.insert(key, &packed)?;
+        self.set_deleted(key, false);
+        self.update_stored(key, false, Some(&packed))?;
+        Ok(())
+    }
+
+    fn update_from(
+        &mut self,
+        other: &VectorStorageEnum,
+        other_ids: &mut dyn Iterator<Item = PointOffsetType>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        let start_index = self.vectors.len() as PointOffsetType;
+        for point_id in other_ids {
+            check_process_stopped(stopped)?;
+            let other_deleted = other.is_deleted_vector(point_id);
+            // Copy packed data directly if possible, avoid unpacking
+            let packed = match other {
+                VectorStorageEnum::SimpleBinary(other) => other.get_packed(point_id).to_vec(),
+                VectorStorageEnum::MemmapBinary(other) => other.get_packed(point_id).to_vec(),
+                other => self.pack(&other.get_vector(point_id)),
+            };
+            let new_id = self.vectors.push(&packed)?;
+            self.set_deleted(new_id, other_deleted);
+            self.update_stored(new_id, other_deleted, Some(&packed))?;
+        }
+        let end_index = self.vectors.len() as PointOffsetType;
+        Ok(start_index..end_index)
+    }
+
+    fn flusher(&self) -> Flusher {
+        self.db_wrapper.flusher()
+    }
+
+    /// Binary vectors already use a single bit per dimension, they can't be quantized further
+    fn quantize(
+        &mut self,
+        _path: &Path,
+        _quantization_config: &QuantizationConfig,
+        _max_threads: usize,
+        _stopped: &AtomicBool,
+    ) -> OperationResult<()> {
+        Err(OperationError::ValidationError {
+            description: format!(
+                "Quantization is not supported for binary vectors with {:?} distance",
+                self.distance,
+            ),
+        })
+    }
+
+    fn load_quantization(&mut self, _path: &Path) -> OperationResult<()> {
+        Ok(())
+    }
+
+    fn quantized_storage(&self) -> Option<&QuantizedVectors> {
+        None
+    }
+
+    fn files(&self) -> Vec<std::path::PathBuf> {
+        vec![]
+    }
+
+    fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
+        let is_deleted = !self.set_deleted(key, true);
+        if is_deleted {
+            self.update_stored(key, true, None)?;
+        }
+        Ok(is_deleted)
+    }
+
+    fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
+        self.deleted.get(key as usize).map(|b| *b).unwrap_or(false)
+    }
+
+    fn deleted_vector_count(&self) -> usize {
+        self.deleted_count
+    }
+
+    fn deleted_vector_bitslice(&self) -> &BitSlice {
+        self.deleted.as_bitslice()
+    }
+}
@@ -13,7 +13,7 @@ use rocksdb::DB;
 use serde::{Deserialize, Serialize};
 
 use super::chunked_vectors::ChunkedVectors;
-use super::vector_storage_base::VectorStorage;
+use super::vector_storage_base::{DenseVectorStorage, VectorStorage};
 use super::VectorStorageEnum;
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
@@ -131,6 +131,12 @@ impl SimpleVectorStorage {
     }
 }
 
+impl DenseVectorStorage for SimpleVectorStorage {
+    fn get_dense(&self, key: PointOffsetType) -> &[VectorElementType] {
+        self.vectors.get(key)
+    }
+}
+
 impl VectorStorage for SimpleVectorStorage {
     fn vector_dim(&self) -> usize {
         self.dim
@@ -144,10 +150,6 @@ impl VectorStorage for SimpleVectorStorage {
         self.vectors.len()
     }
 
-    fn get_vector(&self, key: PointOffsetType) -> &[VectorElementType] {
-        self.vectors.get(key)
-    }
-
     fn insert_vector(
         &mut self,
         key: PointOffsetType,
@@ -171,9 +173,9 @@ impl VectorStorage for SimpleVectorStorage {
             // Do not perform preprocessing - vectors should be already processed
             let other_vector = other.get_vector(point_id);
             let other_deleted = other.is_deleted_vector(point_id);
-            let new_id = self.vectors.push(other_vector)?;
+            let new_id = self.vectors.push(&other_vector)?;
             self.set_deleted(new_id, other_deleted);
-            self.update_stored(new_id, other_deleted, Some(other_vector))?;
+            self.update_stored(new_id, other_deleted, Some(&other_vector))?;
         }
         let end_index = self.vectors.len() as PointOffsetType;
         Ok(start_index..end_index)
@@ -251,7 +253,11 @@ impl VectorStorage for SimpleVectorStorage {
 ///
 /// Returns previous deleted state of the given point.
 #[inline]
-fn bitvec_set_deleted(bitvec: &mut BitVec, point_id: PointOffsetType, deleted: bool) -> bool {
+pub(super) fn bitvec_set_deleted(
+    bitvec: &mut BitVec,
+    point_id: PointOffsetType,
+    deleted: bool,
+) -> bool {
     // Set deleted flag if bitvec is large enough, no need to check bounds
     if (point_id as usize) < bitvec.len() {
         return unsafe { bitvec.replace_unchecked(point_id as usize, deleted) };
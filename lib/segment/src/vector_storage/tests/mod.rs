@@ -2,4 +2,5 @@
 mod async_raw_scorer;
 mod reco_query_scorer;
 mod test_appendable_vector_storage;
+mod test_binary_vector_storage;
 mod utils;
@@ -0,0 +1,112 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use common::types::PointOffsetType;
+use tempfile::Builder;
+
+use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
+use crate::fixtures::payload_context_fixture::FixtureIdTracker;
+use crate::id_tracker::{IdTracker, IdTrackerSS};
+use crate::types::Distance;
+use crate::vector_storage::simple_binary_vector_storage::open_simple_binary_vector_storage;
+use crate::vector_storage::simple_vector_storage::open_simple_vector_storage;
+use crate::vector_storage::{new_raw_scorer, VectorStorage, VectorStorageEnum};
+
+fn binary_points() -> Vec<Vec<f32>> {
+    vec![
+        vec![1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0],
+        vec![1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0],
+        vec![0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0],
+        vec![1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0],
+        vec![0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    ]
+}
+
+fn search(storage: &VectorStorageEnum, query: &[f32], top: usize) -> Vec<(PointOffsetType, f32)> {
+    let id_tracker: Arc<AtomicRefCell<IdTrackerSS>> = Arc::new(AtomicRefCell::new(
+        FixtureIdTracker::new(storage.total_vector_count()),
+    ));
+    let borrowed_id_tracker = id_tracker.borrow();
+    new_raw_scorer(
+        query.into(),
+        storage,
+        borrowed_id_tracker.deleted_point_bitslice(),
+    )
+    .peek_top_all(top)
+    .into_iter()
+    .map(|scored| {
+        (
+            scored.idx,
+            storage.distance().postprocess_score(scored.score),
+        )
+    })
+    .collect()
+}
+
+#[test]
+fn test_binary_storage_hamming() {
+    let dir = Builder::new().prefix("db_dir").tempdir().unwrap();
+    let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+    let storage =
+        open_simple_binary_vector_storage(db.clone(), DB_VECTOR_CF, 10, Distance::Hamming).unwrap();
+
+    {
+        let mut borrowed_storage = storage.borrow_mut();
+        for (i, vec) in binary_points().iter().enumerate() {
+            borrowed_storage
+                .insert_vector(i as PointOffsetType, vec)
+                .unwrap();
+        }
+        borrowed_storage.delete_vector(3).unwrap();
+
+        // Vectors are unpacked on retrieval
+        assert_eq!(borrowed_storage.get_vector(2).to_vec(), binary_points()[2]);
+
+        let query = [1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+        let result = search(&borrowed_storage, &query, 10);
+        assert_eq!(result, vec![(0, 1.0), (1, 2.0), (4, 4.0), (2, 9.0)]);
+
+        borrowed_storage.flusher()().unwrap();
+    }
+    drop(storage);
+
+    // Reload from the database
+    let storage =
+        open_simple_binary_vector_storage(db, DB_VECTOR_CF, 10, Distance::Hamming).unwrap();
+    let borrowed_storage = storage.borrow();
+    assert_eq!(borrowed_storage.total_vector_count(), 5);
+    assert!(borrowed_storage.is_deleted_vector(3));
+    assert_eq!(borrowed_storage.get_vector(0).to_vec(), binary_points()[0]);
+}
+
+#[test]
+fn test_binary_storage_jaccard_update_from() {
+    let dir = Builder::new().prefix("db_dir").tempdir().unwrap();
+    let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+
+    // Source storage keeps vectors as floats
+    let source = open_simple_vector_storage(db.clone(), "source", 10, Distance::Jaccard).unwrap();
+    {
+        let mut borrowed_source = source.borrow_mut();
+        for (i, vec) in binary_points().iter().enumerate() {
+            borrowed_source
+                .insert_vector(i as PointOffsetType, vec)
+                .unwrap();
+        }
+        borrowed_source.delete_vector(1).unwrap();
+    }
+
+    let storage =
+        open_simple_binary_vector_storage(db, DB_VECTOR_CF, 10, Distance::Jaccard).unwrap();
+    let mut borrowed_storage = storage.borrow_mut();
+    let range = borrowed_storage
+        .update_from(&source.borrow(), &mut (0..5), &AtomicBool::new(false))
+        .unwrap();
+    assert_eq!(range, 0..5);
+    assert!(borrowed_storage.is_deleted_vector(1));
+
+    let query = [1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0];
+    let result = search(&borrowed_storage, &query, 2);
+    assert_eq!(result, vec![(0, 0.0), (3, 0.5)]);
+}
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
@@ -5,8 +6,10 @@ use std::sync::atomic::AtomicBool;
 use bitvec::prelude::BitSlice;
 use common::types::PointOffsetType;
 
+use super::memmap_binary_vector_storage::MemmapBinaryVectorStorage;
 use super::memmap_vector_storage::MemmapVectorStorage;
 use super::quantized::quantized_vectors::QuantizedVectors;
+use super::simple_binary_vector_storage::SimpleBinaryVectorStorage;
 use super::simple_vector_storage::SimpleVectorStorage;
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
@@ -39,9 +42,6 @@ pub trait VectorStorage {
             .saturating_sub(self.deleted_vector_count())
     }
 
-    /// Number of all stored vectors including deleted
-    fn get_vector(&self, key: PointOffsetType) -> &[VectorElementType];
-
     fn insert_vector(
         &mut self,
         key: PointOffsetType,
@@ -104,17 +104,48 @@ pub trait VectorStorage {
     fn deleted_vector_bitslice(&self) -> &BitSlice;
 }
 
+/// Vector storage keeping vectors as floats, which can be borrowed directly
+pub trait DenseVectorStorage: VectorStorage {
+    /// Get vector by key, including deleted ones
+    fn get_dense(&self, key: PointOffsetType) -> &[VectorElementType];
+}
+
+/// Vector storage keeping binary vectors bit-packed, 8 dimensions per byte
+pub trait BinaryVectorStorage: VectorStorage {
+    /// Get bit-packed representation of the stored vector, including deleted ones
+    fn get_packed(&self, key: PointOffsetType) -> &[u8];
+}
+
 pub enum VectorStorageEnum {
     Simple(SimpleVectorStorage),
+    SimpleBinary(SimpleBinaryVectorStorage),
     Memmap(Box<MemmapVectorStorage>),
+    MemmapBinary(Box<MemmapBinaryVectorStorage>),
     AppendableMemmap(Box<AppendableMmapVectorStorage>),
 }
 
+impl VectorStorageEnum {
+    /// Get vector by key, including deleted ones
+    ///
+    /// Binary vectors are unpacked into floats, vectors of other storages are borrowed.
+    pub fn get_vector(&self, key: PointOffsetType) -> Cow<[VectorElementType]> {
+        match self {
+            VectorStorageEnum::Simple(v) => Cow::Borrowed(v.get_dense(key)),
+            VectorStorageEnum::SimpleBinary(v) => Cow::Owned(v.get_unpacked(key)),
+            VectorStorageEnum::Memmap(v) => Cow::Borrowed(v.get_dense(key)),
+            VectorStorageEnum::MemmapBinary(v) => Cow::Owned(v.get_unpacked(key)),
+            VectorStorageEnum::AppendableMemmap(v) => Cow::Borrowed(v.get_dense(key)),
+        }
+    }
+}
+
 impl VectorStorage for VectorStorageEnum {
     fn vector_dim(&self) -> usize {
         match self {
             VectorStorageEnum::Simple(v) => v.vector_dim(),
+            VectorStorageEnum::SimpleBinary(v) => v.vector_dim(),
             VectorStorageEnum::Memmap(v) => v.vector_dim(),
+            VectorStorageEnum::MemmapBinary(v) => v.vector_dim(),
             VectorStorageEnum::AppendableMemmap(v) => v.vector_dim(),
         }
     }
@@ -122,7 +153,9 @@ impl VectorStorage for VectorStorageEnum {
     fn distance(&self) -> Distance {
         match self {
             VectorStorageEnum::Simple(v) => v.distance(),
+            VectorStorageEnum::SimpleBinary(v) => v.distance(),
             VectorStorageEnum::Memmap(v) => v.distance(),
+            VectorStorageEnum::MemmapBinary(v) => v.distance(),
             VectorStorageEnum::AppendableMemmap(v) => v.distance(),
         }
     }
@@ -130,19 +163,13 @@ impl VectorStorage for VectorStorageEnum {
     fn total_vector_count(&self) -> usize {
         match self {
             VectorStorageEnum::Simple(v) => v.total_vector_count(),
+            VectorStorageEnum::SimpleBinary(v) => v.total_vector_count(),
             VectorStorageEnum::Memmap(v) => v.total_vector_count(),
+            VectorStorageEnum::MemmapBinary(v) => v.total_vector_count(),
             VectorStorageEnum::AppendableMemmap(v) => v.total_vector_count(),
         }
     }
 
-    fn get_vector(&self, key: PointOffsetType) -> &[VectorElementType] {
-        match self {
-            VectorStorageEnum::Simple(v) => v.get_vector(key),
-            VectorStorageEnum::Memmap(v) => v.get_vector(key),
-            VectorStorageEnum::AppendableMemmap(v) => v.get_vector(key),
-        }
-    }
-
     fn insert_vector(
         &mut self,
         key: PointOffsetType,
@@ -150,7 +177,9 @@ impl VectorStorage for VectorStorageEnum {
     ) -> OperationResult<()> {
         match self {
             VectorStorageEnum::Simple(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::SimpleBinary(v) => v.insert_vector(key, vector),
             VectorStorageEnum::Memmap(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::MemmapBinary(v) => v.insert_vector(key, vector),
             VectorStorageEnum::AppendableMemmap(v) => v.insert_vector(key, vector),
         }
     }
@@ -163,7 +192,9 @@ impl VectorStorage for VectorStorageEnum {
     ) -> OperationResult<Range<PointOffsetType>> {
         match self {
             VectorStorageEnum::Simple(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::SimpleBinary(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::Memmap(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::MemmapBinary(v) => v.update_from(other, other_ids, stopped),
             VectorStorageEnum::AppendableMemmap(v) => v.update_from(other, other_ids, stopped),
         }
     }
@@ -171,7 +202,9 @@ impl VectorStorage for VectorStorageEnum {
     fn flusher(&self) -> Flusher {
         match self {
             VectorStorageEnum::Simple(v) => v.flusher(),
+            VectorStorageEnum::SimpleBinary(v) => v.flusher(),
             VectorStorageEnum::Memmap(v) => v.flusher(),
+            VectorStorageEnum::MemmapBinary(v) => v.flusher(),
             VectorStorageEnum::AppendableMemmap(v) => v.flusher(),
         }
     }
@@ -187,9 +220,15 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::Simple(v) => {
                 v.quantize(data_path, quantization_config, max_threads, stopped)
             }
+            VectorStorageEnum::SimpleBinary(v) => {
+                v.quantize(data_path, quantization_config, max_threads, stopped)
+            }
             VectorStorageEnum::Memmap(v) => {
                 v.quantize(data_path, quantization_config, max_threads, stopped)
             }
+            VectorStorageEnum::MemmapBinary(v) => {
+                v.quantize(data_path, quantization_config, max_threads, stopped)
+            }
             VectorStorageEnum::AppendableMemmap(v) => {
                 v.quantize(data_path, quantization_config, max_threads, stopped)
             }
@@ -199,7 +238,9 @@ impl VectorStorage for VectorStorageEnum {
     fn load_quantization(&mut self, data_path: &Path) -> OperationResult<()> {
         match self {
             VectorStorageEnum::Simple(v) => v.load_quantization(data_path),
+            VectorStorageEnum::SimpleBinary(v) => v.load_quantization(data_path),
             VectorStorageEnum::Memmap(v) => v.load_quantization(data_path),
+            VectorStorageEnum::MemmapBinary(v) => v.load_quantization(data_path),
             VectorStorageEnum::AppendableMemmap(v) => v.load_quantization(data_path),
         }
     }
@@ -207,7 +248,9 @@ impl VectorStorage for VectorStorageEnum {
     fn quantized_storage(&self) -> Option<&QuantizedVectors> {
         match self {
             VectorStorageEnum::Simple(v) => v.quantized_storage(),
+            VectorStorageEnum::SimpleBinary(v) => v.quantized_storage(),
             VectorStorageEnum::Memmap(v) => v.quantized_storage(),
+            VectorStorageEnum::MemmapBinary(v) => v.quantized_storage(),
             VectorStorageEnum::AppendableMemmap(v) => v.quantized_storage(),
         }
     }
@@ -215,7 +258,9 @@ impl VectorStorage for VectorStorageEnum {
     fn files(&self) -> Vec<PathBuf> {
         match self {
             VectorStorageEnum::Simple(v) => v.files(),
+            VectorStorageEnum::SimpleBinary(v) => v.files(),
             VectorStorageEnum::Memmap(v) => v.files(),
+            VectorStorageEnum::MemmapBinary(v) => v.files(),
             VectorStorageEnum::AppendableMemmap(v) => v.files(),
         }
     }
@@ -223,7 +268,9 @@ impl VectorStorage for VectorStorageEnum {
     fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
         match self {
             VectorStorageEnum::Simple(v) => v.delete_vector(key),
+            VectorStorageEnum::SimpleBinary(v) => v.delete_vector(key),
             VectorStorageEnum::Memmap(v) => v.delete_vector(key),
+            VectorStorageEnum::MemmapBinary(v) => v.delete_vector(key),
             VectorStorageEnum::AppendableMemmap(v) => v.delete_vector(key),
         }
     }
@@ -231,7 +278,9 @@ impl VectorStorage for VectorStorageEnum {
     fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
         match self {
             VectorStorageEnum::Simple(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::SimpleBinary(v) => v.is_deleted_vector(key),
             VectorStorageEnum::Memmap(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::MemmapBinary(v) => v.is_deleted_vector(key),
             VectorStorageEnum::AppendableMemmap(v) => v.is_deleted_vector(key),
         }
     }
@@ -239,7 +288,9 @@ impl VectorStorage for VectorStorageEnum {
     fn deleted_vector_count(&self) -> usize {
         match self {
             VectorStorageEnum::Simple(v) => v.deleted_vector_count(),
+            VectorStorageEnum::SimpleBinary(v) => v.deleted_vector_count(),
             VectorStorageEnum::Memmap(v) => v.deleted_vector_count(),
+            VectorStorageEnum::MemmapBinary(v) => v.deleted_vector_count(),
             VectorStorageEnum::AppendableMemmap(v) => v.deleted_vector_count(),
         }
     }
@@ -247,7 +298,9 @@ impl VectorStorage for VectorStorageEnum {
     fn deleted_vector_bitslice(&self) -> &BitSlice {
         match self {
             VectorStorageEnum::Simple(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::SimpleBinary(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::Memmap(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::MemmapBinary(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::AppendableMemmap(v) => v.deleted_vector_bitslice(),
         }
     }
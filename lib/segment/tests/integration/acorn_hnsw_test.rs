@@ -0,0 +1,151 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::AtomicBool;
+
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+use segment::data_types::search_profile::SearchStrategy;
+use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::random_vector;
+use segment::index::hnsw_index::graph_links::GraphLinksRam;
+use segment::index::hnsw_index::hnsw::HNSWIndex;
+use segment::index::{PayloadIndex, VectorIndex};
+use segment::segment_constructor::build_segment;
+use segment::types::{
+    Condition, Distance, FieldCondition, Filter, HnswConfig, Indexes, Payload, PayloadSchemaType,
+    Range, SearchParams, SegmentConfig, SeqNumberType, VectorDataConfig, VectorStorageType,
+};
+use serde_json::json;
+use tempfile::Builder;
+
+fn int_range_filter(key: &str, lt: f64) -> Filter {
+    Filter::new_must(Condition::Field(FieldCondition::new_range(
+        key.to_owned(),
+        Range {
+            lt: Some(lt),
+            gt: None,
+            gte: None,
+            lte: None,
+        },
+    )))
+}
+
+#[test]
+fn test_acorn_hnsw_selective_filter() {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 16;
+    let num_vectors: u64 = 5_000;
+    let top = 10;
+    let int_key = "int";
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Cosine,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+            },
+        )]),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    for n in 0..num_vectors {
+        let idx = n.into();
+        let vector = random_vector(&mut rnd, dim);
+        let payload: Payload = json!({ int_key: n % 100 }).into();
+
+        segment
+            .upsert_point(n as SeqNumberType, idx, only_default_vector(&vector))
+            .unwrap();
+        segment
+            .set_full_payload(n as SeqNumberType, idx, &payload)
+            .unwrap();
+    }
+    segment
+        .payload_index
+        .borrow_mut()
+        .set_indexed(int_key, PayloadSchemaType::Integer.into())
+        .unwrap();
+
+    let hnsw_config = HnswConfig {
+        m: 8,
+        ef_construct: 64,
+        full_scan_threshold: 4, // KB
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+        appendable: None,
+    };
+
+    let mut hnsw_index = HNSWIndex::<GraphLinksRam>::open(
+        hnsw_dir.path(),
+        segment.id_tracker.clone(),
+        segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        segment.payload_index.clone(),
+        hnsw_config,
+    )
+    .unwrap();
+    hnsw_index.build_index(&stopped).unwrap();
+
+    let params = SearchParams {
+        hnsw_ef: Some(64),
+        ..Default::default()
+    };
+    let plain_index = &segment.vector_data[DEFAULT_VECTOR_NAME].vector_index;
+    let payload_index = segment.payload_index.borrow();
+
+    // Filter matching 5% of points is searched through filtered-out neighbors
+    let selective_filter = int_range_filter(int_key, 5.0);
+    let matching: HashSet<_> = payload_index
+        .query_points(&selective_filter)
+        .into_iter()
+        .collect();
+
+    let attempts = 30;
+    let mut found = 0;
+    for _ in 0..attempts {
+        let query = random_vector(&mut rnd, dim).into();
+
+        let (index_result, profile) = hnsw_index.search_with_profile(
+            &[&query],
+            Some(&selective_filter),
+            top,
+            Some(&params),
+            &stopped,
+        );
+        assert_eq!(profile.strategy, SearchStrategy::AcornHnsw);
+        assert_eq!(index_result[0].len(), top);
+        assert!(index_result[0].iter().all(|p| matching.contains(&p.idx)));
+
+        let plain_result =
+            plain_index
+                .borrow()
+                .search(&[&query], Some(&selective_filter), top, None, &stopped);
+        let expected: HashSet<_> = plain_result[0].iter().map(|p| p.idx).collect();
+        found += index_result[0]
+            .iter()
+            .filter(|p| expected.contains(&p.idx))
+            .count();
+    }
+    let recall = found as f64 / (attempts * top) as f64;
+    assert!(recall > 0.9, "recall: {recall}");
+
+    // Filter matching half of the points keeps the regular filtered traversal
+    let wide_filter = int_range_filter(int_key, 50.0);
+    let query = random_vector(&mut rnd, dim).into();
+    let (_, profile) =
+        hnsw_index.search_with_profile(&[&query], Some(&wide_filter), top, Some(&params), &stopped);
+    assert_eq!(profile.strategy, SearchStrategy::FilteredHnsw);
+}
@@ -0,0 +1,158 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::AtomicBool;
+
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+use segment::data_types::search_profile::SearchStrategy;
+use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::random_vector;
+use segment::id_tracker::IdTracker;
+use segment::index::VectorIndex;
+use segment::segment::Segment;
+use segment::segment_constructor::{build_segment, load_segment};
+use segment::types::{
+    Distance, HnswConfig, Indexes, SearchParams, SegmentConfig, SeqNumberType, VectorDataConfig,
+    VectorStorageType,
+};
+use tempfile::Builder;
+
+fn check_recall(segment: &Segment, rnd: &mut StdRng, dim: usize, min_recall: f64) {
+    let stopped = AtomicBool::new(false);
+    let vector_index = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_index
+        .borrow();
+    let exact = SearchParams {
+        exact: true,
+        ..Default::default()
+    };
+    let top = 10;
+
+    let attempts = 50;
+    let mut found = 0;
+    for _ in 0..attempts {
+        let query = random_vector(rnd, dim).into();
+
+        let (index_result, profile) =
+            vector_index.search_with_profile(&[&query], None, top, None, &stopped);
+        assert_eq!(profile.strategy, SearchStrategy::Hnsw);
+        assert_eq!(index_result[0].len(), top);
+
+        let exact_result = vector_index.search(&[&query], None, top, Some(&exact), &stopped);
+
+        let expected: HashSet<_> = exact_result[0].iter().map(|p| p.idx).collect();
+        found += index_result[0]
+            .iter()
+            .filter(|p| expected.contains(&p.idx))
+            .count();
+    }
+    let recall = found as f64 / (attempts * top) as f64;
+    assert!(recall > min_recall, "recall: {recall}");
+}
+
+#[test]
+fn test_appendable_hnsw() {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 16;
+    let num_vectors: u64 = 2_000;
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let hnsw_config = HnswConfig {
+        m: 16,
+        ef_construct: 64,
+        full_scan_threshold: 1, // KB
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+        appendable: Some(true),
+    };
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Cosine,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::AppendableHnsw(hnsw_config),
+                quantization_config: None,
+            },
+        )]),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    assert!(segment.is_appendable());
+
+    // Points are linked into the graph on insertion
+    for n in 0..num_vectors {
+        let vector = random_vector(&mut rnd, dim);
+        segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+    check_recall(&segment, &mut rnd, dim, 0.9);
+
+    // Updated point is found by its new vector
+    let updated_vector = random_vector(&mut rnd, dim);
+    segment
+        .upsert_point(num_vectors, 0.into(), only_default_vector(&updated_vector))
+        .unwrap();
+    let updated_offset = segment.id_tracker.borrow().internal_id(0.into()).unwrap();
+    let query = updated_vector.into();
+    let result = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_index
+        .borrow()
+        .search(&[&query], None, 1, None, &stopped);
+    assert_eq!(result[0][0].idx, updated_offset);
+
+    // Links to deleted points are repaired on the next insertion
+    let mut version = num_vectors + 1;
+    for n in (1..num_vectors).step_by(3) {
+        segment.delete_point(version, n.into()).unwrap();
+        version += 1;
+    }
+    let new_vector = random_vector(&mut rnd, dim);
+    segment
+        .upsert_point(
+            version,
+            num_vectors.into(),
+            only_default_vector(&new_vector),
+        )
+        .unwrap();
+    check_recall(&segment, &mut rnd, dim, 0.9);
+
+    let query = random_vector(&mut rnd, dim).into();
+    let result = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_index
+        .borrow()
+        .search(&[&query], None, 10, None, &stopped);
+    let id_tracker = segment.id_tracker.borrow();
+    assert!(result[0]
+        .iter()
+        .all(|p| !id_tracker.is_deleted_point(p.idx)));
+    drop(id_tracker);
+
+    // Graph is persisted on flush
+    segment.flush(true).unwrap();
+    let path = segment.current_path.clone();
+    drop(segment);
+
+    let segment = load_segment(&path).unwrap().unwrap();
+    check_recall(&segment, &mut rnd, dim, 0.9);
+
+    let new_offset = segment
+        .id_tracker
+        .borrow()
+        .internal_id(num_vectors.into())
+        .unwrap();
+    let query = new_vector.into();
+    let result = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_index
+        .borrow()
+        .search(&[&query], None, 1, None, &stopped);
+    assert_eq!(result[0][0].idx, new_offset);
+}
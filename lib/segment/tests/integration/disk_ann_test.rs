@@ -0,0 +1,177 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::AtomicBool;
+
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+use segment::data_types::search_profile::SearchStrategy;
+use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::{random_int_payload, random_vector};
+use segment::id_tracker::IdTracker;
+use segment::index::disk_ann_index::disk_ann::DiskAnnIndex;
+use segment::index::{PayloadIndex, VectorIndex};
+use segment::segment_constructor::build_segment;
+use segment::types::{
+    Condition, DiskAnnConfig, Distance, FieldCondition, Filter, Indexes, Payload,
+    PayloadSchemaType, Range, SearchParams, SegmentConfig, SeqNumberType, VectorDataConfig,
+    VectorStorageType,
+};
+use serde_json::json;
+use tempfile::Builder;
+
+#[test]
+fn test_disk_ann_search() {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 16;
+    let num_vectors: u64 = 3_000;
+    let distance = Distance::Cosine;
+    let top = 10;
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let disk_ann_dir = Builder::new().prefix("disk_ann_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+            },
+        )]),
+        payload_storage_type: Default::default(),
+    };
+
+    let int_key = "int";
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    for n in 0..num_vectors {
+        let idx = n.into();
+        let vector = random_vector(&mut rnd, dim);
+        let payload: Payload = json!({ int_key: random_int_payload(&mut rnd, 1..=1) }).into();
+
+        segment
+            .upsert_point(n as SeqNumberType, idx, only_default_vector(&vector))
+            .unwrap();
+        segment
+            .set_full_payload(n as SeqNumberType, idx, &payload)
+            .unwrap();
+    }
+    segment
+        .payload_index
+        .borrow_mut()
+        .set_indexed(int_key, PayloadSchemaType::Integer.into())
+        .unwrap();
+
+    let disk_ann_config = DiskAnnConfig {
+        max_degree: 16,
+        build_list_size: 32,
+        search_list_size: 64,
+        beam_width: 4,
+        full_scan_threshold: 1, // KB
+        max_indexing_threads: 2,
+    };
+
+    let vector_storage = &segment.vector_data[DEFAULT_VECTOR_NAME].vector_storage;
+    let mut disk_ann_index = DiskAnnIndex::open(
+        disk_ann_dir.path(),
+        segment.id_tracker.clone(),
+        vector_storage.clone(),
+        segment.payload_index.clone(),
+        disk_ann_config.clone(),
+    )
+    .unwrap();
+    disk_ann_index.build_index(&stopped).unwrap();
+    assert_eq!(disk_ann_index.indexed_vector_count(), num_vectors as usize);
+
+    let plain_index = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_index
+        .clone();
+
+    let attempts = 50;
+    let mut found = 0;
+    let mut queries = Vec::new();
+    for _ in 0..attempts {
+        let query = random_vector(&mut rnd, dim).into();
+
+        let (index_result, profile) =
+            disk_ann_index.search_with_profile(&[&query], None, top, None, &stopped);
+        assert_eq!(profile.strategy, SearchStrategy::DiskAnn);
+        assert_eq!(index_result[0].len(), top);
+
+        let plain_result = plain_index
+            .borrow()
+            .search(&[&query], None, top, None, &stopped);
+
+        let expected: HashSet<_> = plain_result[0].iter().map(|p| p.idx).collect();
+        found += index_result[0]
+            .iter()
+            .filter(|p| expected.contains(&p.idx))
+            .count();
+
+        // Scores are exact, as they are computed with full vectors from disk
+        for (index_point, plain_point) in index_result[0].iter().zip(&plain_result[0]) {
+            if index_point.idx == plain_point.idx {
+                assert!((index_point.score - plain_point.score).abs() < 1e-5);
+            }
+        }
+
+        queries.push((query, index_result));
+    }
+    let recall = found as f64 / (attempts * top) as f64;
+    assert!(recall > 0.9, "recall: {recall}");
+
+    // Reopened index reads the same graph from disk
+    drop(disk_ann_index);
+    let disk_ann_index = DiskAnnIndex::open(
+        disk_ann_dir.path(),
+        segment.id_tracker.clone(),
+        vector_storage.clone(),
+        segment.payload_index.clone(),
+        disk_ann_config,
+    )
+    .unwrap();
+    for (query, expected) in &queries {
+        let result = disk_ann_index.search(&[query], None, top, None, &stopped);
+        assert_eq!(&result, expected);
+    }
+
+    // Filtered results only contain matching points
+    let filter = Filter::new_must(Condition::Field(FieldCondition::new_range(
+        int_key.to_owned(),
+        Range {
+            lt: None,
+            gt: None,
+            gte: Some(0.0),
+            lte: Some(200.0),
+        },
+    )));
+    let query = random_vector(&mut rnd, dim).into();
+    let result = disk_ann_index.search(
+        &[&query],
+        Some(&filter),
+        top,
+        Some(&SearchParams {
+            hnsw_ef: Some(128),
+            ..Default::default()
+        }),
+        &stopped,
+    );
+    assert!(!result[0].is_empty());
+    let payload_index = segment.payload_index.borrow();
+    let matching: HashSet<_> = payload_index.query_points(&filter).into_iter().collect();
+    assert!(result[0].iter().all(|p| matching.contains(&p.idx)));
+
+    // Deleted points are not returned
+    let deleted = result[0][0].idx;
+    drop(payload_index);
+    let external_id = segment.id_tracker.borrow().external_id(deleted).unwrap();
+    segment.delete_point(num_vectors, external_id).unwrap();
+    let result = disk_ann_index.search(&[&query], Some(&filter), top, None, &stopped);
+    assert!(result[0].iter().all(|p| p.idx != deleted));
+}
@@ -79,6 +79,7 @@ fn exact_search_test() {
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        appendable: None,
     };
 
     let mut hnsw_index = HNSWIndex::<GraphLinksRam>::open(
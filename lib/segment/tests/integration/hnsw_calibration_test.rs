@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+use segment::data_types::search_profile::SearchStrategy;
+use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::random_vector;
+use segment::index::hnsw_index::graph_links::GraphLinksRam;
+use segment::index::hnsw_index::hnsw::HNSWIndex;
+use segment::index::VectorIndex;
+use segment::segment_constructor::build_segment;
+use segment::types::{
+    Distance, HnswConfig, Indexes, SearchParams, SegmentConfig, SeqNumberType, VectorDataConfig,
+    VectorStorageType,
+};
+use tempfile::Builder;
+
+#[test]
+fn test_hnsw_search_calibration() {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 16;
+    let num_vectors: u64 = 2_000;
+    let top = 10;
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Cosine,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+            },
+        )]),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    for n in 0..num_vectors {
+        let vector = random_vector(&mut rnd, dim);
+        segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+
+    let hnsw_config = HnswConfig {
+        m: 8,
+        ef_construct: 32,
+        full_scan_threshold: 1, // KB
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+        appendable: None,
+    };
+
+    let open_index = || {
+        HNSWIndex::<GraphLinksRam>::open(
+            hnsw_dir.path(),
+            segment.id_tracker.clone(),
+            segment.vector_data[DEFAULT_VECTOR_NAME]
+                .vector_storage
+                .clone(),
+            segment.payload_index.clone(),
+            hnsw_config.clone(),
+        )
+        .unwrap()
+    };
+
+    let mut hnsw_index = open_index();
+    assert!(hnsw_index.search_calibration().is_none());
+    hnsw_index.build_index(&stopped).unwrap();
+
+    // Graph is calibrated in the background, not on build or on search
+    assert!(hnsw_index.search_calibration().is_none());
+    let query = random_vector(&mut rnd, dim).into();
+    let params = SearchParams {
+        target_recall: Some(0.9),
+        ..Default::default()
+    };
+    let (default_result, default_profile) =
+        hnsw_index.search_with_profile(&[&query], None, top, None, &stopped);
+    let (result, profile) =
+        hnsw_index.search_with_profile(&[&query], None, top, Some(&params), &stopped);
+    assert!(hnsw_index.search_calibration().is_none());
+    assert_eq!(profile.strategy, SearchStrategy::Hnsw);
+    assert_eq!(profile.hnsw_ef, default_profile.hnsw_ef);
+    assert_eq!(result, default_result);
+
+    // Calibration is measured a query at a time
+    let mut steps = 1;
+    while !hnsw_index.calibrate_search_step(&stopped).unwrap() {
+        assert!(hnsw_index.search_calibration().is_none());
+        steps += 1;
+    }
+    assert_eq!(steps, 3 * 32);
+    assert!(hnsw_index.calibrate_search_step(&stopped).unwrap());
+
+    // Recall is measured for every selectivity and `ef`, vectors are not quantized
+    let calibration = hnsw_index.search_calibration().unwrap();
+    assert_eq!(calibration.num_queries, 32);
+    assert_eq!(calibration.points.len(), 3 * 6);
+    assert!(calibration
+        .points
+        .iter()
+        .all(|point| { point.oversampling.is_none() && (0.0..=1.0).contains(&point.recall) }));
+    let best_unfiltered_recall = calibration
+        .points
+        .iter()
+        .filter(|point| point.selectivity == 1.0)
+        .map(|point| point.recall)
+        .fold(0.0, f64::max);
+    assert!(
+        best_unfiltered_recall > 0.9,
+        "recall: {best_unfiltered_recall}"
+    );
+
+    // Lowest measured `ef` is enough for any recall
+    let (lowest_ef, _) = calibration.tune(0.0, 1.0).unwrap();
+    assert_eq!(lowest_ef, 16);
+
+    for target_recall in [0.5, 0.9, 0.99] {
+        let (expected_ef, _) = calibration.tune(target_recall, 1.0).unwrap();
+        let params = SearchParams {
+            target_recall: Some(target_recall),
+            ..Default::default()
+        };
+        let (result, profile) =
+            hnsw_index.search_with_profile(&[&query], None, top, Some(&params), &stopped);
+        assert_eq!(result[0].len(), top);
+        assert_eq!(profile.strategy, SearchStrategy::Hnsw);
+        assert_eq!(profile.hnsw_ef, Some(expected_ef));
+    }
+
+    // Explicit `ef` takes precedence over the target recall
+    let params = SearchParams {
+        hnsw_ef: Some(100),
+        target_recall: Some(0.99),
+        ..Default::default()
+    };
+    let (_, profile) =
+        hnsw_index.search_with_profile(&[&query], None, top, Some(&params), &stopped);
+    assert_eq!(profile.hnsw_ef, Some(100));
+
+    // Calibration is persisted together with the graph
+    drop(hnsw_index);
+    let hnsw_index = open_index();
+    let loaded_calibration = hnsw_index.search_calibration().unwrap();
+    assert_eq!(loaded_calibration.num_queries, calibration.num_queries);
+    assert_eq!(loaded_calibration.points.len(), calibration.points.len());
+    for (loaded, measured) in loaded_calibration.points.iter().zip(&calibration.points) {
+        assert_eq!(loaded.selectivity, measured.selectivity);
+        assert_eq!(loaded.hnsw_ef, measured.hnsw_ef);
+        assert!((loaded.recall - measured.recall).abs() < 1e-9);
+    }
+}
@@ -108,6 +108,7 @@ fn hnsw_quantized_search_test(
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        appendable: None,
     };
 
     let mut hnsw_index = HNSWIndex::<GraphLinksRam>::open(
@@ -311,6 +312,20 @@ fn hnsw_quantized_search_euclid_test() {
     );
 }
 
+#[test]
+fn hnsw_quantized_search_manhattan_test() {
+    hnsw_quantized_search_test(
+        Distance::Manhattan,
+        5003,
+        ScalarQuantizationConfig {
+            r#type: Default::default(),
+            quantile: None,
+            always_ram: None,
+        }
+        .into(),
+    );
+}
+
 #[test]
 fn hnsw_product_quantization_cosine_test() {
     hnsw_quantized_search_test(
@@ -336,3 +351,16 @@ fn hnsw_product_quantization_euclid_test() {
         .into(),
     );
 }
+
+#[test]
+fn hnsw_product_quantization_manhattan_test() {
+    hnsw_quantized_search_test(
+        Distance::Manhattan,
+        1003,
+        ProductQuantizationConfig {
+            compression: CompressionRatio::X4,
+            always_ram: Some(true),
+        }
+        .into(),
+    );
+}
@@ -0,0 +1,304 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::AtomicBool;
+
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+use segment::data_types::search_profile::SearchStrategy;
+use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::{random_int_payload, random_vector};
+use segment::id_tracker::IdTracker;
+use segment::index::ivf_index::ivf::IvfIndex;
+use segment::index::{PayloadIndex, VectorIndex};
+use segment::segment::Segment;
+use segment::segment_constructor::build_segment;
+use segment::types::{
+    CompressionRatio, Condition, Distance, FieldCondition, Filter, Indexes, IvfConfig, Payload,
+    PayloadSchemaType, QuantizationSearchParams, Range, SearchParams, SegmentConfig, SeqNumberType,
+    VectorDataConfig, VectorStorageType,
+};
+use segment::vector_storage::VectorStorage;
+use serde_json::json;
+use tempfile::Builder;
+
+const INT_KEY: &str = "int";
+
+fn build_test_segment(
+    path: &std::path::Path,
+    rnd: &mut StdRng,
+    dim: usize,
+    distance: Distance,
+    num_vectors: u64,
+) -> Segment {
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+            },
+        )]),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(path, &config, true).unwrap();
+    for n in 0..num_vectors {
+        let idx = n.into();
+        let vector = random_vector(rnd, dim);
+        let payload: Payload = json!({ INT_KEY: random_int_payload(rnd, 1..=1) }).into();
+
+        segment
+            .upsert_point(n as SeqNumberType, idx, only_default_vector(&vector))
+            .unwrap();
+        segment
+            .set_full_payload(n as SeqNumberType, idx, &payload)
+            .unwrap();
+    }
+    segment
+        .payload_index
+        .borrow_mut()
+        .set_indexed(INT_KEY, PayloadSchemaType::Integer.into())
+        .unwrap();
+    segment
+}
+
+fn check_recall(
+    segment: &Segment,
+    ivf_index: &IvfIndex,
+    rnd: &mut StdRng,
+    params: Option<&SearchParams>,
+    min_recall: f64,
+) {
+    let stopped = AtomicBool::new(false);
+    let dim = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_storage
+        .borrow()
+        .vector_dim();
+    let plain_index = &segment.vector_data[DEFAULT_VECTOR_NAME].vector_index;
+    let top = 10;
+
+    let attempts = 50;
+    let mut found = 0;
+    for _ in 0..attempts {
+        let query = random_vector(rnd, dim).into();
+
+        let (index_result, profile) =
+            ivf_index.search_with_profile(&[&query], None, top, params, &stopped);
+        assert_eq!(profile.strategy, SearchStrategy::Ivf);
+        assert_eq!(index_result[0].len(), top);
+
+        let plain_result = plain_index
+            .borrow()
+            .search(&[&query], None, top, None, &stopped);
+
+        let expected: HashSet<_> = plain_result[0].iter().map(|p| p.idx).collect();
+        found += index_result[0]
+            .iter()
+            .filter(|p| expected.contains(&p.idx))
+            .count();
+    }
+    let recall = found as f64 / (attempts * top) as f64;
+    assert!(recall > min_recall, "recall: {recall}");
+}
+
+#[test]
+fn test_ivf_search() {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 16;
+    let num_vectors: u64 = 3_000;
+    let top = 10;
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let ivf_dir = Builder::new().prefix("ivf_dir").tempdir().unwrap();
+
+    let mut segment = build_test_segment(dir.path(), &mut rnd, dim, Distance::Cosine, num_vectors);
+
+    let ivf_config = IvfConfig {
+        num_lists: Some(32),
+        nprobe: 12,
+        product_quantization: None,
+        full_scan_threshold: 1, // KB
+        max_indexing_threads: 2,
+    };
+
+    let vector_storage = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_storage
+        .clone();
+    let mut ivf_index = IvfIndex::open(
+        ivf_dir.path(),
+        segment.id_tracker.clone(),
+        vector_storage.clone(),
+        segment.payload_index.clone(),
+        ivf_config.clone(),
+    )
+    .unwrap();
+    ivf_index.build_index(&stopped).unwrap();
+    assert_eq!(ivf_index.indexed_vector_count(), num_vectors as usize);
+
+    check_recall(&segment, &ivf_index, &mut rnd, None, 0.9);
+
+    // Scanning all lists gives exact results
+    let query = random_vector(&mut rnd, dim).into();
+    let all_lists = SearchParams {
+        nprobe: Some(32),
+        ..Default::default()
+    };
+    let result = ivf_index.search(&[&query], None, top, Some(&all_lists), &stopped);
+    let plain_result = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_index
+        .borrow()
+        .search(&[&query], None, top, None, &stopped);
+    assert_eq!(result, plain_result);
+
+    // New points are searchable without rebuilding
+    let new_vector = random_vector(&mut rnd, dim);
+    let new_id = num_vectors.into();
+    segment
+        .upsert_point(num_vectors, new_id, only_default_vector(&new_vector))
+        .unwrap();
+    let new_offset = segment.id_tracker.borrow().internal_id(new_id).unwrap();
+    ivf_index.update_vector(new_offset).unwrap();
+
+    let query = new_vector.into();
+    let result = ivf_index.search(&[&query], None, 1, None, &stopped);
+    assert_eq!(result[0][0].idx, new_offset);
+
+    // Updates are persisted on flush
+    ivf_index.flusher()().unwrap();
+    drop(ivf_index);
+    let ivf_index = IvfIndex::open(
+        ivf_dir.path(),
+        segment.id_tracker.clone(),
+        vector_storage,
+        segment.payload_index.clone(),
+        ivf_config,
+    )
+    .unwrap();
+    assert_eq!(ivf_index.indexed_vector_count(), num_vectors as usize);
+    let (result, profile) = ivf_index.search_with_profile(&[&query], None, 1, None, &stopped);
+    assert_eq!(profile.strategy, SearchStrategy::Ivf);
+    assert_eq!(result[0][0].idx, new_offset);
+
+    // Filtered results only contain matching points
+    let filter = Filter::new_must(Condition::Field(FieldCondition::new_range(
+        INT_KEY.to_owned(),
+        Range {
+            lt: None,
+            gt: None,
+            gte: Some(0.0),
+            lte: Some(200.0),
+        },
+    )));
+    let query = random_vector(&mut rnd, dim).into();
+    let result = ivf_index.search(&[&query], Some(&filter), top, None, &stopped);
+    assert!(!result[0].is_empty());
+    let payload_index = segment.payload_index.borrow();
+    let matching: HashSet<_> = payload_index.query_points(&filter).into_iter().collect();
+    assert!(result[0].iter().all(|p| matching.contains(&p.idx)));
+
+    // Deleted points are not returned
+    let deleted = result[0][0].idx;
+    drop(payload_index);
+    let external_id = segment.id_tracker.borrow().external_id(deleted).unwrap();
+    segment.delete_point(num_vectors + 1, external_id).unwrap();
+    let result = ivf_index.search(&[&query], Some(&filter), top, None, &stopped);
+    assert!(result[0].iter().all(|p| p.idx != deleted));
+}
+
+#[test]
+fn test_ivf_product_quantization() {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 16;
+    let num_vectors: u64 = 3_000;
+    let top = 10;
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let ivf_dir = Builder::new().prefix("ivf_dir").tempdir().unwrap();
+
+    let mut segment = build_test_segment(dir.path(), &mut rnd, dim, Distance::Euclid, num_vectors);
+
+    let ivf_config = IvfConfig {
+        num_lists: Some(32),
+        nprobe: 12,
+        product_quantization: Some(CompressionRatio::X4),
+        full_scan_threshold: 1, // KB
+        max_indexing_threads: 2,
+    };
+
+    let mut ivf_index = IvfIndex::open(
+        ivf_dir.path(),
+        segment.id_tracker.clone(),
+        segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        segment.payload_index.clone(),
+        ivf_config,
+    )
+    .unwrap();
+    ivf_index.build_index(&stopped).unwrap();
+
+    let params = SearchParams {
+        quantization: Some(QuantizationSearchParams {
+            oversampling: Some(2.0),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    check_recall(&segment, &ivf_index, &mut rnd, Some(&params), 0.85);
+
+    // Residual codes are used for scanning, results are rescored with original vectors
+    let query = random_vector(&mut rnd, dim).into();
+    let (result, profile) =
+        ivf_index.search_with_profile(&[&query], None, top, Some(&params), &stopped);
+    assert!(profile.quantized);
+    assert!(profile.rescored);
+    let raw_scores: Vec<_> = {
+        let plain_index = segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_index
+            .borrow();
+        let ids: HashSet<_> = result[0].iter().map(|p| p.idx).collect();
+        plain_index
+            .search(&[&query], None, num_vectors as usize, None, &stopped)
+            .remove(0)
+            .into_iter()
+            .filter(|p| ids.contains(&p.idx))
+            .collect()
+    };
+    assert_eq!(result[0], raw_scores);
+
+    // Updated points are scored with original vectors
+    let new_vector = random_vector(&mut rnd, dim);
+    let new_id = num_vectors.into();
+    segment
+        .upsert_point(num_vectors, new_id, only_default_vector(&new_vector))
+        .unwrap();
+    let new_offset = segment.id_tracker.borrow().internal_id(new_id).unwrap();
+    ivf_index.update_vector(new_offset).unwrap();
+
+    let query = new_vector.into();
+    let result = ivf_index.search(&[&query], None, 1, Some(&params), &stopped);
+    assert_eq!(result[0][0].idx, new_offset);
+    assert!(result[0][0].score.abs() < 1e-5);
+
+    // Estimated scores are not mixed with exact scores of updated points
+    let no_rescore_params = SearchParams {
+        quantization: Some(QuantizationSearchParams {
+            rescore: Some(false),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let (result, profile) =
+        ivf_index.search_with_profile(&[&query], None, top, Some(&no_rescore_params), &stopped);
+    assert!(profile.rescored);
+    assert_eq!(result[0][0].idx, new_offset);
+}
@@ -1,8 +1,14 @@
 #[cfg(test)]
+pub mod acorn_hnsw_test;
+#[cfg(test)]
+pub mod appendable_hnsw_test;
+#[cfg(test)]
 pub mod batch_search_test;
 #[cfg(test)]
 pub mod disbalanced_vectors_test;
 #[cfg(test)]
+pub mod disk_ann_test;
+#[cfg(test)]
 pub mod exact_search_test;
 #[cfg(test)]
 pub mod fail_recovery_test;
@@ -13,14 +19,20 @@ pub mod filtrable_hnsw_test;
 #[cfg(test)]
 pub mod fixtures;
 #[cfg(test)]
+pub mod hnsw_calibration_test;
+#[cfg(test)]
 pub mod hnsw_quantized_search_test;
 #[cfg(test)]
+pub mod ivf_test;
+#[cfg(test)]
 pub mod nested_filtering_test;
 #[cfg(test)]
 pub mod payload_index_test;
 #[cfg(test)]
 pub mod scroll_filtering_test;
 #[cfg(test)]
+pub mod search_profile_test;
+#[cfg(test)]
 pub mod segment_builder_test;
 #[cfg(test)]
 pub mod segment_tests;
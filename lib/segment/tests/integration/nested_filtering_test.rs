@@ -96,6 +96,7 @@ fn test_filtering_context_consistency() {
         let nested_condition_0 = Condition::new_nested(
             "arr1",
             Filter {
+                min_should: None,
                 must: Some(vec![
                     // E.g. idx = 6 => { "a" = 1, "b" = 7, "c" = 1, "d" = 0 }
                     Condition::Field(FieldCondition::new_match("a", 1.into())),
@@ -137,6 +138,7 @@ fn test_filtering_context_consistency() {
         let nested_condition_1 = Condition::new_nested(
             "arr1",
             Filter {
+                min_should: None,
                 must: Some(vec![
                     // E.g. idx = 6 => { "a" = 1, "b" = 7, "c" = 1, "d" = 0 }
                     Condition::Field(FieldCondition::new_match("a", 1.into())),
@@ -168,6 +170,7 @@ fn test_filtering_context_consistency() {
         let nested_condition_2 = Condition::new_nested(
             "arr1",
             Filter {
+                min_should: None,
                 must: Some(vec![
                     // E.g. idx = 6 => { "a" = 1, "b" = 7, "c" = 1, "d" = 0 }
                     Condition::Field(FieldCondition::new_match("a", 1.into())),
@@ -201,6 +204,7 @@ fn test_filtering_context_consistency() {
         let nested_condition_3 = Condition::new_nested(
             "arr1",
             Filter {
+                min_should: None,
                 must: Some(vec![Condition::Field(FieldCondition::new_match(
                     "b",
                     1.into(),
@@ -213,9 +217,11 @@ fn test_filtering_context_consistency() {
         let nester_condition_3_1 = Condition::new_nested(
             "arr2",
             Filter {
+                min_should: None,
                 must: Some(vec![Condition::new_nested(
                     "arr3",
                     Filter {
+                        min_should: None,
                         must: Some(vec![Condition::Field(FieldCondition::new_match(
                             "b",
                             10.into(),
@@ -230,6 +236,7 @@ fn test_filtering_context_consistency() {
         );
 
         let nested_filter_3 = Filter {
+            min_should: None,
             must: Some(vec![nested_condition_3, nester_condition_3_1]),
             should: None,
             must_not: None,
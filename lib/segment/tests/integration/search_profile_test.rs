@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+use segment::data_types::search_profile::SearchStrategy;
+use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::random_vector;
+use segment::index::hnsw_index::graph_links::GraphLinksRam;
+use segment::index::hnsw_index::hnsw::HNSWIndex;
+use segment::index::{PayloadIndex, VectorIndex};
+use segment::segment_constructor::build_segment;
+use segment::types::{
+    Condition, Distance, FieldCondition, Filter, HnswConfig, Indexes, Payload, PayloadSchemaType,
+    SearchParams, SegmentConfig, SeqNumberType, VectorDataConfig, VectorStorageType,
+};
+use serde_json::json;
+use tempfile::Builder;
+
+#[test]
+fn test_search_profile_strategies() {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 8;
+    let num_vectors: u64 = 2_000;
+    let ef = 32;
+    let top = 5;
+    let int_key = "int";
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Dot,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+            },
+        )]),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    for n in 0..num_vectors {
+        let idx = n.into();
+        let vector = random_vector(&mut rnd, dim);
+        let payload: Payload = json!({ int_key: n % 100 }).into();
+
+        segment
+            .upsert_point(n as SeqNumberType, idx, only_default_vector(&vector))
+            .unwrap();
+        segment
+            .set_full_payload(n as SeqNumberType, idx, &payload)
+            .unwrap();
+    }
+
+    segment
+        .payload_index
+        .borrow_mut()
+        .set_indexed(int_key, PayloadSchemaType::Integer.into())
+        .unwrap();
+
+    let query = random_vector(&mut rnd, dim).into();
+
+    // Plain index scores every vector in the segment
+    let (plain_result, plain_profile) = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_index
+        .borrow()
+        .search_with_profile(&[&query], None, top, None, &stopped);
+    assert_eq!(plain_result[0].len(), top);
+    assert_eq!(plain_profile.strategy, SearchStrategy::Plain);
+    assert_eq!(plain_profile.scored_vectors, num_vectors as usize);
+    assert!(plain_profile.cardinality.is_none());
+
+    let hnsw_config = HnswConfig {
+        m: 8,
+        ef_construct: 16,
+        full_scan_threshold: 16, // KB
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+        appendable: None,
+    };
+
+    let mut hnsw_index = HNSWIndex::<GraphLinksRam>::open(
+        hnsw_dir.path(),
+        segment.id_tracker.clone(),
+        segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        segment.payload_index.clone(),
+        hnsw_config,
+    )
+    .unwrap();
+    hnsw_index.build_index(&stopped).unwrap();
+
+    let params = SearchParams {
+        hnsw_ef: Some(ef),
+        ..Default::default()
+    };
+
+    // Unfiltered search traverses the graph and scores only a fraction of vectors
+    let (hnsw_result, hnsw_profile) =
+        hnsw_index.search_with_profile(&[&query], None, top, Some(&params), &stopped);
+    assert_eq!(hnsw_result[0].len(), top);
+    assert_eq!(hnsw_profile.strategy, SearchStrategy::Hnsw);
+    assert_eq!(hnsw_profile.hnsw_ef, Some(ef));
+    assert!(hnsw_profile.scored_vectors > 0);
+    assert!(hnsw_profile.scored_vectors < num_vectors as usize);
+
+    // Filter with small cardinality is resolved with the payload index
+    let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+        int_key,
+        7.into(),
+    )));
+    let (filtered_result, filtered_profile) =
+        hnsw_index.search_with_profile(&[&query], Some(&filter), top, Some(&params), &stopped);
+    assert_eq!(filtered_result[0].len(), top);
+    assert_eq!(filtered_profile.strategy, SearchStrategy::PayloadIndex);
+    let cardinality = filtered_profile.cardinality.unwrap();
+    assert_eq!(cardinality.exp, num_vectors as usize / 100);
+    assert_eq!(filtered_profile.scored_vectors, num_vectors as usize / 100);
+
+    // Exact search does not use the graph
+    let exact_params = SearchParams {
+        exact: true,
+        ..Default::default()
+    };
+    let (_, exact_profile) =
+        hnsw_index.search_with_profile(&[&query], None, top, Some(&exact_params), &stopped);
+    assert_eq!(exact_profile.strategy, SearchStrategy::Exact);
+    assert_eq!(exact_profile.scored_vectors, num_vectors as usize);
+}
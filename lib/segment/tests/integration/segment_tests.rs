@@ -9,7 +9,11 @@ use segment::entry::entry_point::SegmentEntry;
 use segment::fixtures::index_fixtures::random_vector;
 use segment::segment_constructor::load_segment;
 use segment::segment_constructor::simple_segment_constructor::build_simple_segment;
-use segment::types::{Condition, Distance, Filter, SearchParams, WithPayload};
+use segment::types::{
+    Condition, Distance, FieldCondition, Filter, PayloadFieldSchema, PayloadSchemaType,
+    SearchParams, WithPayload,
+};
+use serde_json::json;
 use tempfile::Builder;
 
 use crate::fixtures::segment::{build_segment_1, build_segment_3};
@@ -44,6 +48,7 @@ fn test_point_exclusion() {
 
     let frt = Filter {
         should: None,
+        min_should: None,
         must: None,
         must_not: Some(vec![Condition::HasId(ids.into())]),
     };
@@ -103,6 +108,7 @@ fn test_named_vector_search() {
 
     let frt = Filter {
         should: None,
+        min_should: None,
         must: None,
         must_not: Some(vec![Condition::HasId(ids.into())]),
     };
@@ -261,6 +267,8 @@ fn test_update_named_vector() {
     // do exact search
     let search_params = SearchParams {
         hnsw_ef: None,
+        nprobe: None,
+        target_recall: None,
         exact: true,
         quantization: None,
         indexed_only: false,
@@ -329,3 +337,63 @@ fn test_update_named_vector() {
     // check that nearests are the same
     assert_eq!(nearest_upsert.id, nearest_update.id);
 }
+
+#[test]
+fn test_nested_payload_key_update() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let mut segment = build_segment_1(dir.path());
+
+    segment
+        .create_field_index(
+            7,
+            "metadata.author.name",
+            Some(&PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword)),
+        )
+        .unwrap();
+
+    let payload = json!({ "metadata": { "author": { "name": "John" }, "year": 2020 } }).into();
+    segment.set_payload(8, 1.into(), &payload, &None).unwrap();
+
+    let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+        "metadata.author.name".to_string(),
+        "Jane".to_string().into(),
+    )));
+    assert!(segment.read_filtered(None, None, Some(&filter)).is_empty());
+
+    // Merge into the nested object, sibling keys must be preserved
+    let payload = json!({ "name": "Jane" }).into();
+    segment
+        .set_payload(9, 1.into(), &payload, &Some("metadata.author".to_string()))
+        .unwrap();
+
+    assert_eq!(
+        segment
+            .payload(1.into())
+            .unwrap()
+            .0
+            .get("metadata")
+            .cloned(),
+        Some(json!({ "author": { "name": "Jane" }, "year": 2020 })),
+    );
+    assert_eq!(
+        segment.read_filtered(None, None, Some(&filter)),
+        vec![1.into()],
+    );
+
+    // Deleting the parent object must also clean up the nested field index
+    segment
+        .delete_payload(10, 1.into(), "metadata.author")
+        .unwrap();
+
+    assert_eq!(
+        segment
+            .payload(1.into())
+            .unwrap()
+            .0
+            .get("metadata")
+            .cloned(),
+        Some(json!({ "year": 2020 })),
+    );
+    assert!(segment.read_filtered(None, None, Some(&filter)).is_empty());
+}
@@ -5,6 +5,7 @@ use collection::operations::config_diff::{
 };
 use collection::operations::types::{VectorsConfig, VectorsConfigDiff};
 use collection::shards::replica_set::ReplicaState;
+use collection::shards::resharding::ReshardingState;
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::transfer::shard_transfer::{ShardTransfer, ShardTransferKey};
 use collection::shards::{replica_set, CollectionId};
@@ -276,6 +277,16 @@ pub enum ShardTransferOperations {
     },
 }
 
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
+pub enum ReshardingOperations {
+    Start(ReshardingState),
+    /// All points of the existing shard are migrated into the new shard
+    FinishMigration(ShardId),
+    Abort {
+        reason: String,
+    },
+}
+
 /// Sets the state of shard replica
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
 pub struct SetShardReplicaState {
@@ -293,6 +304,15 @@ pub struct SetShardReplicaState {
     pub from_state: Option<ReplicaState>,
 }
 
+/// Pauses or resumes optimizers of all shards of the collection
+///
+/// Only the paused flag goes through consensus, one-off optimizations are sent to peers directly.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
+pub struct SetOptimizersPaused {
+    pub collection_name: String,
+    pub paused: bool,
+}
+
 /// Enumeration of all possible collection update operations
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -302,7 +322,9 @@ pub enum CollectionMetaOperations {
     DeleteCollection(DeleteCollectionOperation),
     ChangeAliases(ChangeAliasesOperation),
     TransferShard(CollectionId, ShardTransferOperations),
+    Resharding(CollectionId, ReshardingOperations),
     SetShardReplicaState(SetShardReplicaState),
+    SetOptimizersPaused(SetOptimizersPaused),
     Nop { token: usize }, // Empty operation
 }
 
@@ -1,4 +1,5 @@
 use std::cmp;
+use std::collections::HashSet;
 use std::fs::{create_dir_all, File};
 use std::io::BufWriter;
 use std::path::{Path, PathBuf};
@@ -6,6 +7,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use atomicwrites::{AllowOverwrite, AtomicFile};
+use collection::shards::peer_labels::{PeerLabels, PeerLabelsById};
 use collection::shards::shard::PeerId;
 use http::Uri;
 use parking_lot::RwLock;
@@ -39,6 +41,12 @@ pub struct Persistent {
     /// Last known cluster topology
     #[serde(with = "serialize_peer_addresses")]
     pub peer_address_by_id: Arc<RwLock<PeerAddressById>>,
+    /// Peers, which replicas are being moved off, so they can be safely removed
+    #[serde(default)]
+    pub draining_peers: HashSet<PeerId>,
+    /// Labels of the peers, used for the replica placement
+    #[serde(default)]
+    pub peer_labels: PeerLabelsById,
     pub this_peer_id: PeerId,
     #[serde(skip)]
     pub path: PathBuf,
@@ -60,8 +68,12 @@ impl Persistent {
         &mut self,
         meta: &SnapshotMetadata,
         address_by_id: PeerAddressById,
+        draining_peers: HashSet<PeerId>,
+        peer_labels: PeerLabelsById,
     ) -> Result<(), StorageError> {
         *self.peer_address_by_id.write() = address_by_id;
+        self.draining_peers = draining_peers;
+        self.peer_labels = peer_labels;
         self.state.conf_state = meta.get_conf_state().clone();
         self.state.hard_state.term = cmp::max(self.state.hard_state.term, meta.term);
         self.state.hard_state.commit = meta.index;
@@ -150,6 +162,48 @@ impl Persistent {
         self.save()
     }
 
+    /// Returns `true` if the draining state of the peer was changed
+    pub fn set_peer_draining(
+        &mut self,
+        peer_id: PeerId,
+        draining: bool,
+    ) -> Result<bool, StorageError> {
+        let changed = if draining {
+            self.draining_peers.insert(peer_id)
+        } else {
+            self.draining_peers.remove(&peer_id)
+        };
+        if changed {
+            self.save()?;
+        }
+        Ok(changed)
+    }
+
+    pub fn draining_peers(&self) -> HashSet<PeerId> {
+        self.draining_peers.clone()
+    }
+
+    /// Returns `true` if the labels of the peer were changed
+    pub fn set_peer_labels(
+        &mut self,
+        peer_id: PeerId,
+        labels: PeerLabels,
+    ) -> Result<bool, StorageError> {
+        let changed = if labels.is_empty() {
+            self.peer_labels.remove(&peer_id).is_some()
+        } else {
+            self.peer_labels.insert(peer_id, labels.clone()) != Some(labels)
+        };
+        if changed {
+            self.save()?;
+        }
+        Ok(changed)
+    }
+
+    pub fn peer_labels(&self) -> PeerLabelsById {
+        self.peer_labels.clone()
+    }
+
     pub fn last_applied_entry(&self) -> Option<u64> {
         self.apply_progress_queue.get_last_applied()
     }
@@ -188,6 +242,8 @@ impl Persistent {
             },
             apply_progress_queue: Default::default(),
             peer_address_by_id: Default::default(),
+            draining_peers: Default::default(),
+            peer_labels: Default::default(),
             this_peer_id,
             path,
             latest_snapshot_meta: Default::default(),
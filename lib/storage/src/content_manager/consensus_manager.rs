@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::fmt::Display;
 use std::future::Future;
@@ -10,12 +10,13 @@ use anyhow::{anyhow, Context};
 use chrono::Utc;
 use collection::collection_state;
 use collection::common::is_ready::IsReady;
+use collection::shards::peer_labels::{PeerLabels, PeerLabelsById};
 use collection::shards::shard::PeerId;
 use collection::shards::CollectionId;
 use futures::future::join_all;
 use parking_lot::{Mutex, RwLock};
 use raft::eraftpb::{ConfChangeType, ConfChangeV2, Entry as RaftEntry};
-use raft::{GetEntriesContext, RaftState, RawNode, SoftState, Storage};
+use raft::{GetEntriesContext, RaftState, RawNode, SoftState, StateRole, Storage};
 use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
 use tokio::sync::broadcast::Receiver;
@@ -32,7 +33,7 @@ use crate::content_manager::consensus::operation_sender::OperationSender;
 use crate::content_manager::consensus::persistent::Persistent;
 use crate::types::{
     ClusterInfo, ClusterStatus, ConsensusThreadStatus, MessageSendErrors, PeerAddressById,
-    PeerInfo, RaftInfo,
+    PeerInfo, RaftInfo, RebalancerStatus,
 };
 
 pub const DEFAULT_META_OP_WAIT: Duration = Duration::from_secs(10);
@@ -48,6 +49,10 @@ pub struct SnapshotData {
     pub collections_data: CollectionsSnapshot,
     #[serde(with = "crate::serialize_peer_addresses")]
     pub address_by_id: PeerAddressById,
+    #[serde(default)]
+    pub draining_peers: HashSet<PeerId>,
+    #[serde(default)]
+    pub peer_labels: PeerLabelsById,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -93,6 +98,8 @@ pub struct ConsensusManager<C: CollectionContainer> {
     consensus_thread_status: RwLock<ConsensusThreadStatus>,
     /// Consensus thread errors, changed by the consensus thread
     message_send_failures: RwLock<HashMap<String, MessageSendErrors>>,
+    /// Last status of the rebalancer, changed by the rebalancer task
+    rebalancer_status: RwLock<Option<RebalancerStatus>>,
 }
 
 impl<C: CollectionContainer> ConsensusManager<C> {
@@ -115,6 +122,7 @@ impl<C: CollectionContainer> ConsensusManager<C> {
                 last_update: Utc::now(),
             }),
             message_send_failures: Default::default(),
+            rebalancer_status: Default::default(),
         }
     }
 
@@ -167,6 +175,10 @@ impl<C: CollectionContainer> ConsensusManager<C> {
         }
     }
 
+    pub fn set_rebalancer_status(&self, status: RebalancerStatus) {
+        *self.rebalancer_status.write() = Some(status)
+    }
+
     pub fn set_raft_soft_state(&self, state: &SoftState) {
         *self.soft_state.write() = Some(SoftState { ..*state });
     }
@@ -175,6 +187,21 @@ impl<C: CollectionContainer> ConsensusManager<C> {
         self.persistent.read().this_peer_id
     }
 
+    pub fn is_leader(&self) -> bool {
+        self.soft_state
+            .read()
+            .as_ref()
+            .map_or(false, |state| state.raft_state == StateRole::Leader)
+    }
+
+    pub fn draining_peers(&self) -> HashSet<PeerId> {
+        self.persistent.read().draining_peers()
+    }
+
+    pub fn peer_labels(&self) -> PeerLabelsById {
+        self.persistent.read().peer_labels()
+    }
+
     pub fn first_voter(&self) -> PeerId {
         match self.first_voter.read().as_ref() {
             Some(id) => *id,
@@ -199,6 +226,11 @@ impl<C: CollectionContainer> ConsensusManager<C> {
                     peer_id,
                     PeerInfo {
                         uri: uri.to_string(),
+                        labels: persistent
+                            .peer_labels
+                            .get(&peer_id)
+                            .cloned()
+                            .unwrap_or_default(),
                     },
                 )
             })
@@ -222,6 +254,7 @@ impl<C: CollectionContainer> ConsensusManager<C> {
             },
             consensus_thread_status: self.consensus_thread_status.read().clone(),
             message_send_failures: self.message_send_failures.read().clone(),
+            rebalancer: self.rebalancer_status.read().clone(),
         })
     }
 
@@ -449,6 +482,30 @@ impl<C: CollectionContainer> ConsensusManager<C> {
                 Ok(false)
             }
 
+            ConsensusOperations::DrainPeer(peer_id) => {
+                if self.peer_address_by_id().contains_key(&peer_id) {
+                    self.persistent.write().set_peer_draining(peer_id, true)
+                } else {
+                    Err(StorageError::bad_request(format!(
+                        "Peer {peer_id} does not exist"
+                    )))
+                }
+            }
+
+            ConsensusOperations::CancelDrainPeer(peer_id) => {
+                self.persistent.write().set_peer_draining(peer_id, false)
+            }
+
+            ConsensusOperations::UpdatePeerLabels { peer_id, labels } => {
+                if self.peer_address_by_id().contains_key(&peer_id) {
+                    self.persistent.write().set_peer_labels(peer_id, labels)
+                } else {
+                    Err(StorageError::bad_request(format!(
+                        "Peer {peer_id} does not exist"
+                    )))
+                }
+            }
+
             ConsensusOperations::RequestSnapshot | ConsensusOperations::ReportSnapshot { .. } => {
                 unreachable!()
             }
@@ -472,9 +529,12 @@ impl<C: CollectionContainer> ConsensusManager<C> {
         let data: SnapshotData = snapshot.get_data().try_into()?;
         self.toc.apply_collections_snapshot(data.collections_data)?;
         self.wal.lock().clear()?;
-        self.persistent
-            .write()
-            .update_from_snapshot(meta, data.address_by_id)?;
+        self.persistent.write().update_from_snapshot(
+            meta,
+            data.address_by_id,
+            data.draining_peers,
+            data.peer_labels,
+        )?;
 
         Ok(Ok(()))
     }
@@ -520,7 +580,10 @@ impl<C: CollectionContainer> ConsensusManager<C> {
         // plus we need to make additional removing in the `channel_pool`.
         // So we handle `remove_peer` inside the `toc` and persist changes in the `persistent` after that.
         self.toc.remove_peer(peer_id)?;
-        self.persistent.read().save()
+        let mut persistent = self.persistent.write();
+        persistent.draining_peers.remove(&peer_id);
+        persistent.peer_labels.remove(&peer_id);
+        persistent.save()
     }
 
     async fn await_receiver(
@@ -682,6 +745,37 @@ impl<C: CollectionContainer> ConsensusManager<C> {
         Ok(res)
     }
 
+    /// Propose new labels of this peer, if they differ from the ones known to the consensus
+    ///
+    /// Fails if this peer is not registered in the consensus yet.
+    pub async fn update_peer_labels(&self, labels: PeerLabels) -> Result<(), StorageError> {
+        let peer_id = self.this_peer_id();
+        if !self.peer_address_by_id().contains_key(&peer_id) {
+            return Err(StorageError::service_error(format!(
+                "Peer {peer_id} is not registered in the consensus yet"
+            )));
+        }
+
+        let known_labels = self
+            .persistent
+            .read()
+            .peer_labels
+            .get(&peer_id)
+            .cloned()
+            .unwrap_or_default();
+        if known_labels == labels {
+            return Ok(());
+        }
+
+        log::info!("Updating labels of peer {peer_id}: {labels:?}");
+        self.propose_consensus_op_with_await(
+            ConsensusOperations::UpdatePeerLabels { peer_id, labels },
+            None,
+        )
+        .await?;
+        Ok(())
+    }
+
     pub fn peer_address_by_id(&self) -> PeerAddressById {
         self.persistent.read().peer_address_by_id()
     }
@@ -767,6 +861,8 @@ impl<C: CollectionContainer> Storage for ConsensusManager<C> {
             let snapshot = SnapshotData {
                 collections_data,
                 address_by_id: persistent.peer_address_by_id(),
+                draining_peers: persistent.draining_peers(),
+                peer_labels: persistent.peer_labels(),
             };
             Ok(raft::eraftpb::Snapshot {
                 data: serde_cbor::to_vec(&snapshot).map_err(raft_error_other)?,
@@ -0,0 +1,54 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use collection::operations::consistency_ops::ConsistencyCheckRequest;
+
+use super::toc::TableOfContent;
+use crate::types::ConsistencyCheckConfig;
+
+/// Background task, which periodically compares replicas of all collections.
+///
+/// Runs on every peer, but each peer only checks the shards it leads,
+/// so every shard is compared once per interval.
+pub struct ConsistencyChecker {
+    toc: Arc<TableOfContent>,
+    interval: Duration,
+    repair: bool,
+}
+
+impl ConsistencyChecker {
+    /// Returns `None` if scheduled checks are disabled
+    pub fn new(toc: Arc<TableOfContent>, config: &ConsistencyCheckConfig) -> Option<Self> {
+        let interval_sec = config.interval_sec?;
+        Some(Self {
+            toc,
+            interval: Duration::from_secs(interval_sec),
+            repair: config.repair,
+        })
+    }
+
+    pub async fn run(self) {
+        loop {
+            tokio::time::sleep(self.interval).await;
+            self.check_all_collections().await;
+        }
+    }
+
+    async fn check_all_collections(&self) {
+        for collection_name in self.toc.all_collections().await {
+            let collection = match self.toc.get_collection(&collection_name).await {
+                Ok(collection) => collection,
+                // Collection was removed in the meantime
+                Err(_) => continue,
+            };
+
+            let request = ConsistencyCheckRequest {
+                shard_id: None,
+                repair: self.repair,
+            };
+            if let Err(err) = collection.check_consistency(request, true).await {
+                log::warn!("Failed to check consistency of collection {collection_name}: {err}");
+            }
+        }
+    }
+}
@@ -9,25 +9,28 @@ pub mod collection_meta_ops;
 mod collections_ops;
 pub mod consensus;
 pub mod consensus_manager;
+pub mod consistency_checker;
 pub mod conversions;
 mod data_transfer;
 pub mod errors;
+pub mod rebalancer;
 pub mod shard_distribution;
 pub mod snapshots;
 pub mod toc;
 
 pub mod consensus_ops {
+    use collection::shards::peer_labels::PeerLabels;
     use collection::shards::replica_set::ReplicaState;
     use collection::shards::replica_set::ReplicaState::Initializing;
-    use collection::shards::shard::PeerId;
+    use collection::shards::shard::{PeerId, ShardId};
     use collection::shards::transfer::shard_transfer::ShardTransfer;
     use collection::shards::{replica_set, CollectionId};
     use raft::eraftpb::Entry as RaftEntry;
     use serde::{Deserialize, Serialize};
 
     use crate::content_manager::collection_meta_ops::{
-        CollectionMetaOperations, SetShardReplicaState, ShardTransferOperations, UpdateCollection,
-        UpdateCollectionOperation,
+        CollectionMetaOperations, ReshardingOperations, SetShardReplicaState,
+        ShardTransferOperations, UpdateCollection, UpdateCollectionOperation,
     };
 
     /// Operation that should pass consensus
@@ -44,6 +47,15 @@ pub mod consensus_ops {
             peer_id: PeerId,
             status: SnapshotStatus,
         },
+        /// Move all replicas off the peer, so it can be safely removed
+        DrainPeer(PeerId),
+        /// Stop moving replicas off the peer
+        CancelDrainPeer(PeerId),
+        /// Set labels of the peer, used to spread replicas across zones, racks, etc.
+        UpdatePeerLabels {
+            peer_id: PeerId,
+            labels: PeerLabels,
+        },
     }
 
     impl TryFrom<&RaftEntry> for ConsensusOperations {
@@ -76,6 +88,22 @@ pub mod consensus_ops {
             )))
         }
 
+        pub fn finish_resharding_migration(collection_id: CollectionId, shard_id: ShardId) -> Self {
+            ConsensusOperations::CollectionMeta(Box::new(CollectionMetaOperations::Resharding(
+                collection_id,
+                ReshardingOperations::FinishMigration(shard_id),
+            )))
+        }
+
+        pub fn abort_resharding(collection_id: CollectionId, reason: &str) -> Self {
+            ConsensusOperations::CollectionMeta(Box::new(CollectionMetaOperations::Resharding(
+                collection_id,
+                ReshardingOperations::Abort {
+                    reason: reason.to_string(),
+                },
+            )))
+        }
+
         pub fn set_replica_state(
             collection_name: CollectionId,
             shard_id: u32,
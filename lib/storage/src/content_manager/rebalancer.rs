@@ -0,0 +1,570 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use collection::shards::peer_labels::{shared_labels_count, PeerLabelsById};
+use collection::shards::replica_set::ReplicaState;
+use collection::shards::shard::{PeerId, ShardId};
+use collection::shards::transfer::shard_transfer::ShardTransfer;
+use collection::shards::CollectionId;
+
+use super::consensus_manager::ConsensusStateRef;
+use super::consensus_ops::ConsensusOperations;
+use super::errors::StorageError;
+use super::toc::TableOfContent;
+use crate::types::{RebalancerConfig, RebalancerStatus};
+
+/// Replicas of the collection shards, which the rebalancer may move
+#[derive(Debug, Clone, Default)]
+pub struct CollectionPlacement {
+    pub shards: HashMap<ShardId, HashMap<PeerId, ReplicaState>>,
+    pub transfers: HashSet<ShardTransfer>,
+    /// Collection is being resharded, its replicas should not be moved
+    pub is_locked: bool,
+}
+
+/// Single step of moving replicas between peers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RebalanceAction {
+    /// Move active replica to another peer
+    Move {
+        collection: CollectionId,
+        shard_id: ShardId,
+        from: PeerId,
+        to: PeerId,
+    },
+    /// Drop a replica, which is not active, while the shard has active replicas on other peers
+    Drop {
+        collection: CollectionId,
+        shard_id: ShardId,
+        peer_id: PeerId,
+    },
+}
+
+impl RebalanceAction {
+    fn into_consensus_operation(self) -> ConsensusOperations {
+        match self {
+            RebalanceAction::Move {
+                collection,
+                shard_id,
+                from,
+                to,
+            } => ConsensusOperations::start_transfer(
+                collection,
+                ShardTransfer {
+                    shard_id,
+                    from,
+                    to,
+                    sync: false,
+                },
+            ),
+            RebalanceAction::Drop {
+                collection,
+                shard_id,
+                peer_id,
+            } => ConsensusOperations::remove_replica(collection, shard_id, peer_id),
+        }
+    }
+}
+
+/// Background task, which moves replicas off the draining peers
+/// and optionally evens out the number of replicas across peers.
+///
+/// Runs on every peer to report the progress, but only the leader proposes the transfers.
+pub struct Rebalancer {
+    toc: Arc<TableOfContent>,
+    consensus_state: ConsensusStateRef,
+    config: RebalancerConfig,
+}
+
+impl Rebalancer {
+    pub fn new(
+        toc: Arc<TableOfContent>,
+        consensus_state: ConsensusStateRef,
+        config: RebalancerConfig,
+    ) -> Self {
+        Self {
+            toc,
+            consensus_state,
+            config,
+        }
+    }
+
+    pub async fn run(self) {
+        let interval = Duration::from_secs(self.config.interval_sec);
+        loop {
+            tokio::time::sleep(interval).await;
+            if let Err(err) = self.rebalance().await {
+                log::warn!("Failed to rebalance replicas: {err}");
+            }
+        }
+    }
+
+    async fn rebalance(&self) -> Result<(), StorageError> {
+        let peers: HashSet<_> = self
+            .consensus_state
+            .peer_address_by_id()
+            .into_keys()
+            .collect();
+        let draining_peers = self.consensus_state.draining_peers();
+        let peer_labels = self.consensus_state.peer_labels();
+        let collections: HashMap<_, _> = self
+            .toc
+            .collections_snapshot()
+            .await
+            .collections
+            .into_iter()
+            .map(|(collection, state)| {
+                let placement = CollectionPlacement {
+                    shards: state
+                        .shards
+                        .into_iter()
+                        .map(|(shard_id, shard_info)| (shard_id, shard_info.replicas))
+                        .collect(),
+                    transfers: state.transfers,
+                    is_locked: state.resharding.is_some(),
+                };
+                (collection, placement)
+            })
+            .collect();
+
+        let running_transfers = collections
+            .values()
+            .map(|placement| placement.transfers.len())
+            .sum();
+        let actions = plan_rebalance(
+            &peers,
+            &draining_peers,
+            &peer_labels,
+            &collections,
+            self.config.auto_rebalance,
+        );
+
+        self.consensus_state
+            .set_rebalancer_status(RebalancerStatus {
+                auto_rebalance: self.config.auto_rebalance,
+                draining_peers: count_replicas(&draining_peers, &collections),
+                running_transfers,
+                pending_moves: actions.len(),
+                last_run: Utc::now(),
+            });
+
+        if !self.consensus_state.is_leader() {
+            return Ok(());
+        }
+
+        let budget = self
+            .config
+            .max_concurrent_transfers
+            .saturating_sub(running_transfers);
+        for action in actions.into_iter().take(budget) {
+            log::debug!("Rebalancing replicas: {action:?}");
+            self.consensus_state
+                .propose_consensus_op_with_await(action.into_consensus_operation(), None)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+fn count_replicas(
+    peers: &HashSet<PeerId>,
+    collections: &HashMap<CollectionId, CollectionPlacement>,
+) -> HashMap<PeerId, usize> {
+    let mut counts: HashMap<_, _> = peers.iter().map(|peer_id| (*peer_id, 0)).collect();
+    for placement in collections.values() {
+        for replicas in placement.shards.values() {
+            for peer_id in replicas.keys() {
+                if let Some(count) = counts.get_mut(peer_id) {
+                    *count += 1;
+                }
+            }
+        }
+    }
+    counts
+}
+
+/// Plans replica moves, at most one per shard:
+///
+/// 1. Move all replicas off the draining peers
+/// 2. If `auto_rebalance` is set, move replicas sharing labels (e.g. zone) with other replicas
+///    of the same shard to peers with distinct labels
+/// 3. If `auto_rebalance` is set, move replicas from the most loaded peers to the least loaded ones,
+///    until the number of replicas differs by at most one, without breaking the label spread
+///
+/// Shards with running transfers and collections being resharded are left as is.
+pub fn plan_rebalance(
+    peers: &HashSet<PeerId>,
+    draining_peers: &HashSet<PeerId>,
+    peer_labels: &PeerLabelsById,
+    collections: &HashMap<CollectionId, CollectionPlacement>,
+    auto_rebalance: bool,
+) -> Vec<RebalanceAction> {
+    // Ordered for deterministic plans
+    let mut shards: BTreeMap<(CollectionId, ShardId), &HashMap<PeerId, ReplicaState>> =
+        BTreeMap::new();
+    for (collection, placement) in collections {
+        if placement.is_locked {
+            continue;
+        }
+        for (shard_id, replicas) in &placement.shards {
+            let is_transferring = placement
+                .transfers
+                .iter()
+                .any(|transfer| transfer.shard_id == *shard_id);
+            if !is_transferring {
+                shards.insert((collection.clone(), *shard_id), replicas);
+            }
+        }
+    }
+
+    let non_draining_peers: HashSet<_> = peers.difference(draining_peers).copied().collect();
+    let mut loads: BTreeMap<PeerId, usize> = count_replicas(&non_draining_peers, collections)
+        .into_iter()
+        .collect();
+
+    // Number of labels the peer would share with the other replicas, if replica is moved there from `from`
+    let shared_labels =
+        |peer_id: PeerId, from: PeerId, replicas: &HashMap<PeerId, ReplicaState>| {
+            shared_labels_count(
+                peer_labels,
+                peer_id,
+                replicas.keys().filter(|other| **other != from),
+            )
+        };
+
+    let least_loaded_peer = |loads: &BTreeMap<PeerId, usize>,
+                             from: PeerId,
+                             replicas: &HashMap<PeerId, ReplicaState>| {
+        loads
+            .iter()
+            .filter(|(peer_id, _)| !replicas.contains_key(peer_id))
+            .map(|(peer_id, load)| (shared_labels(*peer_id, from, replicas), *load, *peer_id))
+            .min()
+    };
+
+    let mut actions = Vec::new();
+    let mut planned: HashSet<(CollectionId, ShardId)> = HashSet::new();
+
+    for ((collection, shard_id), replicas) in &shards {
+        let mut draining_replicas: Vec<_> = replicas
+            .iter()
+            .filter(|(peer_id, _)| draining_peers.contains(peer_id))
+            .collect();
+        draining_replicas.sort_unstable_by_key(|(peer_id, _)| **peer_id);
+        let Some((peer_id, state)) = draining_replicas.first() else {
+            continue;
+        };
+
+        let has_other_active = replicas
+            .iter()
+            .any(|(other, state)| other != *peer_id && *state == ReplicaState::Active);
+
+        if **state == ReplicaState::Active {
+            let Some((_, _, target)) = least_loaded_peer(&loads, **peer_id, replicas) else {
+                continue;
+            };
+            *loads.entry(target).or_default() += 1;
+            actions.push(RebalanceAction::Move {
+                collection: collection.clone(),
+                shard_id: *shard_id,
+                from: **peer_id,
+                to: target,
+            });
+        } else if has_other_active {
+            actions.push(RebalanceAction::Drop {
+                collection: collection.clone(),
+                shard_id: *shard_id,
+                peer_id: **peer_id,
+            });
+        } else {
+            continue;
+        }
+        planned.insert((collection.clone(), *shard_id));
+    }
+
+    if !auto_rebalance {
+        return actions;
+    }
+
+    for ((collection, shard_id), replicas) in &shards {
+        if planned.contains(&(collection.clone(), *shard_id)) {
+            continue;
+        }
+
+        let mut active_replicas: Vec<_> = replicas
+            .iter()
+            .filter(|(peer_id, state)| {
+                **state == ReplicaState::Active && !draining_peers.contains(peer_id)
+            })
+            .map(|(peer_id, _)| *peer_id)
+            .collect();
+        active_replicas.sort_unstable();
+
+        let target = active_replicas.into_iter().find_map(|from| {
+            let current = shared_labels(from, from, replicas);
+            if current == 0 {
+                return None;
+            }
+            least_loaded_peer(&loads, from, replicas)
+                .filter(|(shared, _, _)| *shared < current)
+                .map(|(_, _, to)| (from, to))
+        });
+        let Some((from, to)) = target else {
+            continue;
+        };
+
+        if let Some(load) = loads.get_mut(&from) {
+            *load -= 1;
+        }
+        *loads.entry(to).or_default() += 1;
+        planned.insert((collection.clone(), *shard_id));
+        actions.push(RebalanceAction::Move {
+            collection: collection.clone(),
+            shard_id: *shard_id,
+            from,
+            to,
+        });
+    }
+
+    loop {
+        let mut descending: Vec<_> = loads
+            .iter()
+            .map(|(peer_id, load)| (*load, *peer_id))
+            .collect();
+        descending.sort_unstable_by_key(|(load, peer_id)| (std::cmp::Reverse(*load), *peer_id));
+        let mut ascending = descending.clone();
+        ascending.sort_unstable();
+
+        // Try pairs from the most loaded peer to the least loaded one first,
+        // if there is nothing to move between them, try the next pair
+        let mut next_move = None;
+        'pairs: for &(max_load, max_peer) in &descending {
+            for &(min_load, min_peer) in &ascending {
+                if max_load <= min_load + 1 {
+                    break;
+                }
+
+                let candidate = shards.iter().find(|(key, replicas)| {
+                    !planned.contains(key)
+                        && replicas.get(&max_peer) == Some(&ReplicaState::Active)
+                        && !replicas.contains_key(&min_peer)
+                        && shared_labels(min_peer, max_peer, replicas)
+                            <= shared_labels(max_peer, max_peer, replicas)
+                });
+                if let Some(((collection, shard_id), _)) = candidate {
+                    next_move = Some((collection.clone(), *shard_id, max_peer, min_peer));
+                    break 'pairs;
+                }
+            }
+        }
+
+        let Some((collection, shard_id, from, to)) = next_move else {
+            break;
+        };
+
+        *loads.get_mut(&from).unwrap() -= 1;
+        *loads.get_mut(&to).unwrap() += 1;
+        planned.insert((collection.clone(), shard_id));
+        actions.push(RebalanceAction::Move {
+            collection,
+            shard_id,
+            from,
+            to,
+        });
+    }
+
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+    use collection::shards::peer_labels::PeerLabels;
+
+    use super::*;
+
+    fn placement(shards: &[&[(PeerId, ReplicaState)]]) -> CollectionPlacement {
+        CollectionPlacement {
+            shards: shards
+                .iter()
+                .enumerate()
+                .map(|(shard_id, replicas)| {
+                    (shard_id as ShardId, replicas.iter().copied().collect())
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_plan_drain() {
+        let peers = HashSet::from([1, 2, 3]);
+        let draining = HashSet::from([1]);
+        let no_labels = PeerLabelsById::new();
+        let collections = HashMap::from([(
+            "test".to_string(),
+            placement(&[
+                &[(1, ReplicaState::Active)],
+                &[(2, ReplicaState::Active)],
+                &[(1, ReplicaState::Dead), (3, ReplicaState::Active)],
+            ]),
+        )]);
+
+        let actions = plan_rebalance(&peers, &draining, &no_labels, &collections, false);
+        assert_eq!(
+            actions,
+            vec![
+                RebalanceAction::Move {
+                    collection: "test".to_string(),
+                    shard_id: 0,
+                    from: 1,
+                    to: 2,
+                },
+                RebalanceAction::Drop {
+                    collection: "test".to_string(),
+                    shard_id: 2,
+                    peer_id: 1,
+                },
+            ]
+        );
+
+        // Nothing to do without draining peers
+        let actions = plan_rebalance(&peers, &HashSet::new(), &no_labels, &collections, false);
+        assert!(actions.is_empty());
+    }
+
+    #[test]
+    fn test_plan_auto_rebalance() {
+        // Peer 3 has joined recently
+        let peers = HashSet::from([1, 2, 3]);
+        let no_labels = PeerLabelsById::new();
+        let collections = HashMap::from([(
+            "test".to_string(),
+            placement(&[
+                &[(1, ReplicaState::Active)],
+                &[(2, ReplicaState::Active)],
+                &[(1, ReplicaState::Active)],
+                &[(2, ReplicaState::Active)],
+                &[(1, ReplicaState::Active)],
+                &[(2, ReplicaState::Active)],
+            ]),
+        )]);
+
+        let actions = plan_rebalance(&peers, &HashSet::new(), &no_labels, &collections, true);
+        assert_eq!(actions.len(), 2);
+        for action in &actions {
+            let RebalanceAction::Move { to, .. } = action else {
+                panic!("Unexpected action: {action:?}");
+            };
+            assert_eq!(*to, 3);
+        }
+
+        // Balanced cluster stays as is
+        let actions = plan_rebalance(
+            &HashSet::from([1, 2]),
+            &HashSet::new(),
+            &no_labels,
+            &collections,
+            true,
+        );
+        assert!(actions.is_empty());
+    }
+
+    #[test]
+    fn test_plan_auto_rebalance_skips_stuck_pair() {
+        let peers = HashSet::from([1, 2, 3]);
+        let no_labels = PeerLabelsById::new();
+        // Replicas of the most loaded peer 1 are not active, so they can't be moved
+        let collections = HashMap::from([(
+            "test".to_string(),
+            placement(&[
+                &[(1, ReplicaState::Dead), (2, ReplicaState::Active)],
+                &[(1, ReplicaState::Dead), (2, ReplicaState::Active)],
+                &[(1, ReplicaState::Dead)],
+                &[(2, ReplicaState::Active)],
+                &[(1, ReplicaState::Dead)],
+            ]),
+        )]);
+
+        // Next loaded peer 2 gives a replica to peer 3 instead
+        let actions = plan_rebalance(&peers, &HashSet::new(), &no_labels, &collections, true);
+        assert_eq!(
+            actions,
+            vec![RebalanceAction::Move {
+                collection: "test".to_string(),
+                shard_id: 0,
+                from: 2,
+                to: 3,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_plan_spread_across_zones() {
+        let peers = HashSet::from([1, 2, 3, 4]);
+        let zone = |zone: &str| PeerLabels::from([("zone".to_string(), zone.to_string())]);
+        let peer_labels = PeerLabelsById::from([
+            (1, zone("a")),
+            (2, zone("a")),
+            (3, zone("b")),
+            (4, zone("b")),
+        ]);
+        let collections = HashMap::from([(
+            "test".to_string(),
+            placement(&[
+                &[(1, ReplicaState::Active), (2, ReplicaState::Active)],
+                &[(3, ReplicaState::Active), (4, ReplicaState::Active)],
+            ]),
+        )]);
+
+        // Both replicas of each shard are in the same zone
+        let actions = plan_rebalance(&peers, &HashSet::new(), &peer_labels, &collections, true);
+        assert_eq!(actions.len(), 2);
+        for action in &actions {
+            let RebalanceAction::Move { from, to, .. } = action else {
+                panic!("Unexpected action: {action:?}");
+            };
+            assert_ne!(peer_labels[from], peer_labels[to]);
+        }
+
+        // Without auto rebalance, replicas stay in place
+        let actions = plan_rebalance(&peers, &HashSet::new(), &peer_labels, &collections, false);
+        assert!(actions.is_empty());
+    }
+
+    #[test]
+    fn test_plan_drain_keeps_zones() {
+        let peers = HashSet::from([1, 2, 3, 4]);
+        let zone = |zone: &str| PeerLabels::from([("zone".to_string(), zone.to_string())]);
+        let peer_labels = PeerLabelsById::from([
+            (1, zone("a")),
+            (2, zone("a")),
+            (3, zone("b")),
+            (4, zone("b")),
+        ]);
+        let collections = HashMap::from([(
+            "test".to_string(),
+            placement(&[&[(1, ReplicaState::Active), (3, ReplicaState::Active)]]),
+        )]);
+
+        // Replica from zone `a` is moved to the other peer in zone `a`, not next to the replica in zone `b`
+        let actions = plan_rebalance(
+            &peers,
+            &HashSet::from([1]),
+            &peer_labels,
+            &collections,
+            false,
+        );
+        assert_eq!(
+            actions,
+            vec![RebalanceAction::Move {
+                collection: "test".to_string(),
+                shard_id: 0,
+                from: 1,
+                to: 2,
+            }]
+        );
+    }
+}
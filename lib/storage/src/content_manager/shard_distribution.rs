@@ -1,31 +1,13 @@
-use std::cmp::{self, Reverse};
-use std::collections::BinaryHeap;
+use std::cmp;
+use std::collections::HashMap;
 use std::num::NonZeroU32;
 
 use collection::shards::collection_shard_distribution::CollectionShardDistribution;
+use collection::shards::peer_labels::{shared_labels_count, PeerLabelsById};
 use collection::shards::shard::{PeerId, ShardId};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
-struct PeerShardCount {
-    shard_count: usize, // self.shard_count and other.shard_count are compared first to determine eq & ord
-    peer_id: PeerId,
-}
-
-impl PeerShardCount {
-    fn new(peer_id: PeerId) -> Self {
-        Self {
-            shard_count: 0,
-            peer_id,
-        }
-    }
-
-    fn inc_shard_count(&mut self) {
-        self.shard_count += 1;
-    }
-}
-
 #[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone)]
 pub struct ShardDistributionProposal {
     /// A shard can be located on several peers if it has replicas
@@ -35,28 +17,36 @@ pub struct ShardDistributionProposal {
 impl ShardDistributionProposal {
     /// Builds a proposal for the distribution of shards.
     /// It will propose to allocate shards so that all peers have the same number of shards of this collection  at the end.
+    /// Replicas of the same shard are spread across peers with distinct labels (e.g. zones or racks), if possible.
     pub fn new(
         shard_number: NonZeroU32,
         replication_factor: NonZeroU32,
         known_peers: &[PeerId],
+        peer_labels: &PeerLabelsById,
     ) -> Self {
-        // min number of shard_count on top to make this a min-heap
-        let mut min_heap: BinaryHeap<Reverse<PeerShardCount>> =
-            BinaryHeap::with_capacity(known_peers.len());
-        for peer in known_peers {
-            min_heap.push(Reverse(PeerShardCount::new(*peer)));
-        }
+        let mut shard_counts: HashMap<PeerId, usize> =
+            known_peers.iter().map(|peer_id| (*peer_id, 0)).collect();
 
         let mut distribution = Vec::with_capacity(shard_number.get() as usize);
         // There should not be more than 1 replica per peer
         let n_replicas = cmp::min(replication_factor.get() as usize, known_peers.len());
 
         for shard_id in 0..shard_number.get() {
-            let mut replicas = Vec::new();
+            let mut replicas = Vec::with_capacity(n_replicas);
             for _replica in 0..n_replicas {
-                let mut least_loaded_peer = min_heap.peek_mut().unwrap();
-                let selected_peer = least_loaded_peer.0.peer_id;
-                least_loaded_peer.0.inc_shard_count();
+                let selected_peer = shard_counts
+                    .iter()
+                    .filter(|(peer_id, _)| !replicas.contains(*peer_id))
+                    .min_by_key(|(peer_id, shard_count)| {
+                        (
+                            shared_labels_count(peer_labels, **peer_id, &replicas),
+                            **shard_count,
+                            **peer_id,
+                        )
+                    })
+                    .map(|(peer_id, _)| *peer_id)
+                    .expect("There are more peers than replicas");
+                *shard_counts.get_mut(&selected_peer).unwrap() += 1;
                 replicas.push(selected_peer);
             }
             distribution.push((shard_id, replicas))
@@ -102,6 +92,8 @@ impl From<ShardDistributionProposal> for CollectionShardDistribution {
 
 #[cfg(test)]
 mod tests {
+    use collection::shards::peer_labels::PeerLabels;
+
     use super::*;
 
     #[test]
@@ -111,6 +103,7 @@ mod tests {
             NonZeroU32::new(6).unwrap(),
             NonZeroU32::new(1).unwrap(),
             &known_peers,
+            &PeerLabelsById::new(),
         );
 
         // Check it distribution is as even as possible
@@ -131,4 +124,28 @@ mod tests {
         assert_eq!(shard_counts.iter().min(), Some(&1));
         assert_eq!(shard_counts.iter().max(), Some(&2));
     }
+
+    #[test]
+    fn test_distribution_spreads_replicas_across_zones() {
+        let known_peers = vec![1, 2, 3, 4];
+        let zone = |zone: &str| PeerLabels::from([("zone".to_string(), zone.to_string())]);
+        let peer_labels = PeerLabelsById::from([
+            (1, zone("a")),
+            (2, zone("a")),
+            (3, zone("b")),
+            (4, zone("b")),
+        ]);
+
+        let distribution = ShardDistributionProposal::new(
+            NonZeroU32::new(4).unwrap(),
+            NonZeroU32::new(2).unwrap(),
+            &known_peers,
+            &peer_labels,
+        );
+
+        for (_shard_id, peers) in &distribution.distribution {
+            assert_eq!(peers.len(), 2);
+            assert_ne!(peer_labels[&peers[0]], peer_labels[&peers[1]]);
+        }
+    }
 }
@@ -0,0 +1,372 @@
+use std::collections::{BTreeMap, HashMap};
+use std::num::NonZeroU32;
+use std::path::Path;
+use std::time::Duration;
+
+use collection::common::file_utils::move_file;
+use collection::config::CollectionConfig;
+use collection::operations::snapshot_ops::{
+    get_snapshot_description, verify_snapshot_checksum, write_checksum_for_snapshot,
+    ClusterSnapshotRecover, SnapshotDescription,
+};
+use collection::shards::replica_set::ReplicaState;
+use collection::shards::shard::{PeerId, ShardId};
+use segment::utils::tar::HashingWriter;
+use serde::{Deserialize, Serialize};
+use tar::Builder as TarBuilder;
+
+use crate::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, CreateCollectionOperation,
+};
+use crate::content_manager::snapshots::download::download_snapshot;
+use crate::content_manager::snapshots::recover::activate_shard;
+use crate::dispatcher::Dispatcher;
+use crate::StorageError;
+
+/// Name of the manifest file in the cluster snapshot archive
+pub const CLUSTER_SNAPSHOT_MANIFEST_FILE: &str = "manifest.json";
+
+/// How long to wait for all peers to reach the consensus commit before taking the snapshot
+const CLUSTER_SNAPSHOT_CONSENSUS_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Describes the content of the cluster snapshot of a collection
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClusterSnapshotManifest {
+    /// Name of the collection, the snapshot was taken of
+    pub collection_name: String,
+    /// Consensus commit, which all peers have reached before taking the snapshot
+    pub commit: u64,
+    /// Consensus term of the commit
+    pub term: u64,
+    pub config: CollectionConfig,
+    /// Snapshots of every shard of the collection
+    pub shards: BTreeMap<ShardId, ClusterSnapshotShard>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClusterSnapshotShard {
+    /// Name of the shard snapshot file in the archive
+    pub snapshot: String,
+    /// Peer, which the shard snapshot was created on
+    pub peer_id: PeerId,
+    /// Replicas of the shard at the moment of the snapshot
+    pub replicas: HashMap<PeerId, ReplicaState>,
+}
+
+impl ClusterSnapshotManifest {
+    pub fn load(dir: &Path) -> Result<Self, StorageError> {
+        let manifest_path = dir.join(CLUSTER_SNAPSHOT_MANIFEST_FILE);
+        let manifest = std::fs::read(&manifest_path).map_err(|err| {
+            StorageError::bad_input(format!(
+                "Not a cluster snapshot, can't read {CLUSTER_SNAPSHOT_MANIFEST_FILE}: {err}"
+            ))
+        })?;
+        serde_json::from_slice(&manifest).map_err(|err| {
+            StorageError::bad_input(format!(
+                "Invalid cluster snapshot manifest {CLUSTER_SNAPSHOT_MANIFEST_FILE}: {err}"
+            ))
+        })
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<(), StorageError> {
+        let manifest = serde_json::to_vec_pretty(self).map_err(|err| {
+            StorageError::service_error(format!("Can't serialize cluster snapshot manifest: {err}"))
+        })?;
+        std::fs::write(dir.join(CLUSTER_SNAPSHOT_MANIFEST_FILE), manifest)?;
+        Ok(())
+    }
+}
+
+pub async fn do_create_cluster_snapshot(
+    dispatcher: &Dispatcher,
+    collection_name: &str,
+    wait: bool,
+) -> Result<Option<SnapshotDescription>, StorageError> {
+    let dispatcher = dispatcher.clone();
+    let collection_name = collection_name.to_string();
+    let task =
+        tokio::spawn(
+            async move { _do_create_cluster_snapshot(&dispatcher, &collection_name).await },
+        );
+    if wait {
+        Ok(Some(task.await??))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Creates a snapshot of every shard of the collection, each on one of its active replicas,
+/// and archives them together with the manifest into the collection snapshots directory.
+///
+/// Before taking the snapshots, all peers are required to reach the current consensus commit,
+/// so that they agree on the collection config and the shard distribution.
+async fn _do_create_cluster_snapshot(
+    dispatcher: &Dispatcher,
+    collection_name: &str,
+) -> Result<SnapshotDescription, StorageError> {
+    let toc = dispatcher.toc();
+
+    let (commit, term) = match dispatcher.consensus_state() {
+        Some(state) => {
+            let hard_state = state.hard_state();
+            toc.await_commit_on_all_peers(
+                hard_state.commit,
+                hard_state.term,
+                CLUSTER_SNAPSHOT_CONSENSUS_TIMEOUT,
+            )
+            .await?;
+            (hard_state.commit, hard_state.term)
+        }
+        None => (0, 0),
+    };
+
+    let collection = toc.get_collection(collection_name).await?;
+    let state = collection.state().await;
+    if state.resharding.is_some() {
+        return Err(StorageError::bad_request(format!(
+            "Can't create cluster snapshot of collection {collection_name} while it is being resharded"
+        )));
+    }
+
+    let snapshot_name = format!(
+        "{collection_name}-cluster-{}.snapshot",
+        chrono::Utc::now().format("%Y-%m-%d-%H-%M-%S")
+    );
+    log::info!("Creating cluster snapshot {snapshot_name} at consensus commit {commit}");
+
+    let temp_path = toc.optional_temp_or_snapshot_temp_path()?;
+    let snapshot_temp_dir = tempfile::Builder::new()
+        .prefix(&format!("{snapshot_name}-target-"))
+        .tempdir_in(&temp_path)?;
+
+    let shard_replicas: BTreeMap<_, _> = state
+        .shards
+        .into_iter()
+        .map(|(shard_id, shard_info)| (shard_id, shard_info.replicas))
+        .collect();
+
+    let mut shards = BTreeMap::new();
+    for (shard_id, replicas) in shard_replicas {
+        let shard_snapshot = format!("shard-{shard_id}.snapshot");
+        let peer_id = collection
+            .snapshot_shard_replica(
+                shard_id,
+                &temp_path,
+                &snapshot_temp_dir.path().join(&shard_snapshot),
+            )
+            .await?;
+        log::debug!("Snapshot of shard {shard_id} is created on peer {peer_id}");
+        shards.insert(
+            shard_id,
+            ClusterSnapshotShard {
+                snapshot: shard_snapshot,
+                peer_id,
+                replicas,
+            },
+        );
+    }
+
+    let current_shards = collection.state().await.shards;
+    if current_shards.len() != shards.len()
+        || !current_shards
+            .keys()
+            .all(|shard_id| shards.contains_key(shard_id))
+    {
+        return Err(StorageError::service_error(format!(
+            "Shards of collection {collection_name} have changed while creating cluster snapshot, try again"
+        )));
+    }
+
+    let manifest = ClusterSnapshotManifest {
+        collection_name: collection_name.to_string(),
+        commit,
+        term,
+        config: state.config,
+        shards,
+    };
+    manifest.save(snapshot_temp_dir.path())?;
+
+    // Dedicated temporary file for archiving this snapshot (deleted on drop)
+    let mut snapshot_temp_arc_file = tempfile::Builder::new()
+        .prefix(&format!("{snapshot_name}-arc-"))
+        .tempfile_in(&temp_path)?;
+
+    let snapshot_temp_dir_path = snapshot_temp_dir.path().to_path_buf();
+    let archiving = tokio::task::spawn_blocking(move || -> Result<_, StorageError> {
+        let mut builder = TarBuilder::new(HashingWriter::new(snapshot_temp_arc_file.as_file_mut()));
+        builder.append_dir_all(".", &snapshot_temp_dir_path)?;
+        let checksum = builder.into_inner()?.checksum();
+        // return ownership of the file
+        Ok((snapshot_temp_arc_file, checksum))
+    });
+    let (snapshot_temp_arc_file, checksum) = archiving.await??;
+
+    let snapshots_path = toc.create_snapshots_path(collection_name).await?;
+    let snapshot_path = snapshots_path.join(&snapshot_name);
+    write_checksum_for_snapshot(&snapshot_path, &checksum).await?;
+    move_file(snapshot_temp_arc_file.path(), &snapshot_path).await?;
+
+    log::info!(
+        "Cluster snapshot {} completed into {:?}",
+        snapshot_name,
+        snapshot_path
+    );
+    Ok(get_snapshot_description(&snapshot_path).await?)
+}
+
+pub async fn do_recover_from_cluster_snapshot(
+    dispatcher: &Dispatcher,
+    collection_name: &str,
+    source: ClusterSnapshotRecover,
+    wait: bool,
+) -> Result<bool, StorageError> {
+    let dispatcher = dispatcher.clone();
+    let collection_name = collection_name.to_string();
+    let recovery = tokio::spawn(async move {
+        _do_recover_from_cluster_snapshot(&dispatcher, &collection_name, source).await
+    });
+    if wait {
+        Ok(recovery.await??)
+    } else {
+        Ok(true)
+    }
+}
+
+/// Recovers a new collection from the cluster snapshot.
+///
+/// The collection is created with the snapshot config and a fresh shard distribution over the
+/// current peers, so the snapshot can be recovered on a cluster with a different set of peers.
+/// This peer gets a replica of every shard and recovers it from the snapshot, the other replicas
+/// are marked as dead and get synchronized from this peer by the regular shard transfers.
+async fn _do_recover_from_cluster_snapshot(
+    dispatcher: &Dispatcher,
+    collection_name: &str,
+    source: ClusterSnapshotRecover,
+) -> Result<bool, StorageError> {
+    let ClusterSnapshotRecover { location, checksum } = source;
+    let toc = dispatcher.toc();
+    let this_peer_id = toc.this_peer_id;
+
+    if toc.get_collection(collection_name).await.is_ok() {
+        return Err(StorageError::bad_input(format!(
+            "Collection {collection_name} already exists, cluster snapshot can only be recovered into a new collection"
+        )));
+    }
+
+    let download_dir = toc.snapshots_download_tempdir()?;
+
+    log::debug!(
+        "Downloading cluster snapshot from {} to {}",
+        location,
+        download_dir.path().display()
+    );
+
+    let snapshot_path = download_snapshot(location, download_dir.path()).await?;
+
+    if let Some(checksum) = checksum {
+        verify_snapshot_checksum(&snapshot_path, &checksum).await?;
+    }
+
+    let tmp_snapshot_dir = tempfile::Builder::new()
+        .prefix(&format!("col-{collection_name}-cluster-recovery-"))
+        .tempdir_in(toc.optional_temp_or_storage_temp_path()?)?;
+
+    log::debug!(
+        "Unpacking cluster snapshot to {}",
+        tmp_snapshot_dir.path().display()
+    );
+
+    let tmp_snapshot_dir_path = tmp_snapshot_dir.path().to_path_buf();
+    let unpacking = tokio::task::spawn_blocking(move || -> Result<_, StorageError> {
+        let archive_file = std::fs::File::open(&snapshot_path)?;
+        let mut ar = tar::Archive::new(archive_file);
+        ar.unpack(&tmp_snapshot_dir_path)?;
+        ClusterSnapshotManifest::load(&tmp_snapshot_dir_path)
+    });
+    let manifest = unpacking.await??;
+    manifest.config.validate_and_warn();
+
+    if manifest.shards.len() != manifest.config.params.shard_number.get() as usize {
+        return Err(StorageError::bad_input(format!(
+            "Cluster snapshot is incomplete: {} of {} shards are present",
+            manifest.shards.len(),
+            manifest.config.params.shard_number,
+        )));
+    }
+
+    let mut operation =
+        CreateCollectionOperation::new(collection_name.to_string(), manifest.config.clone().into());
+    if let Some(state) = dispatcher.consensus_state() {
+        let mut distribution = toc
+            .suggest_shard_distribution(
+                &operation,
+                NonZeroU32::new(state.0.peer_count() as u32)
+                    .expect("Peer count should be always >= 1"),
+                &state.0.peer_labels(),
+            )
+            .await;
+        // This peer recovers all shards, other replicas are synchronized from it
+        for (_shard_id, peers) in distribution.distribution.iter_mut() {
+            if !peers.contains(&this_peer_id) {
+                peers.pop();
+                peers.insert(0, this_peer_id);
+            }
+        }
+        operation.set_distribution(distribution);
+    }
+
+    log::debug!("Creating collection {collection_name} from cluster snapshot");
+    dispatcher
+        .submit_collection_meta_op(CollectionMetaOperations::CreateCollection(operation), None)
+        .await?;
+
+    let collection = toc.get_collection(collection_name).await?;
+    let temp_path = toc.optional_temp_or_snapshot_temp_path()?;
+
+    for (shard_id, shard) in &manifest.shards {
+        log::debug!(
+            "Recovering shard {shard_id} of collection {collection_name} from cluster snapshot"
+        );
+        collection
+            .restore_shard_snapshot(
+                *shard_id,
+                &tmp_snapshot_dir.path().join(&shard.snapshot),
+                this_peer_id,
+                toc.is_distributed(),
+                &temp_path,
+            )
+            .await?;
+
+        activate_shard(toc, &collection, this_peer_id, shard_id).await?;
+
+        // Other replicas were created empty, mark them as dead to synchronize them with this peer
+        let other_peers = collection
+            .state()
+            .await
+            .shards
+            .get(shard_id)
+            .map(|shard_info| {
+                shard_info
+                    .replicas
+                    .keys()
+                    .copied()
+                    .filter(|peer_id| *peer_id != this_peer_id)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        for peer_id in other_peers {
+            toc.send_set_replica_state_proposal(
+                collection_name.to_string(),
+                peer_id,
+                *shard_id,
+                ReplicaState::Dead,
+                None,
+            )?;
+        }
+    }
+
+    // Remove tmp snapshot dir
+    tokio::fs::remove_dir_all(&tmp_snapshot_dir).await?;
+
+    Ok(true)
+}
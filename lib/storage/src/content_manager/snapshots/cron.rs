@@ -0,0 +1,247 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+
+/// How far ahead to look for the next matching time.
+/// Covers leap years, so expressions like `0 0 29 2 *` are still found.
+const MAX_LOOKAHEAD_DAYS: i64 = 366 * 5;
+
+/// Parsed cron expression of 5 fields: `minute hour day-of-month month day-of-week`.
+///
+/// Each field accepts `*`, single values, ranges `a-b`, steps `*/s` or `a-b/s`, and lists of those
+/// separated by commas. Day of week is `0-7`, where both `0` and `7` are Sunday.
+/// As in the classic cron, if both day of month and day of week are restricted,
+/// a day matches if either of them matches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+impl CronSchedule {
+    /// First matching time strictly after `time`, with the minute precision
+    pub fn next_after(&self, time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut next = time.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = next + Duration::days(MAX_LOOKAHEAD_DAYS);
+
+        while next < limit {
+            if !self.matches_day(&next) {
+                let next_day = next.date_naive().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                next = Utc.from_utc_datetime(&next_day);
+                continue;
+            }
+            if !contains(self.hours, next.hour()) {
+                next = next.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if !contains(self.minutes, next.minute()) {
+                next += Duration::minutes(1);
+                continue;
+            }
+            return Some(next);
+        }
+
+        None
+    }
+
+    fn matches_day(&self, time: &DateTime<Utc>) -> bool {
+        if !contains(self.months, time.month()) {
+            return false;
+        }
+
+        let day_of_month = contains(self.days_of_month, time.day());
+        let day_of_week = contains(self.days_of_week, time.weekday().num_days_from_sunday());
+
+        match (self.any_day_of_month, self.any_day_of_week) {
+            (true, true) => true,
+            (true, false) => day_of_week,
+            (false, true) => day_of_month,
+            (false, false) => day_of_month || day_of_week,
+        }
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = String;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<_> = expression.split_whitespace().collect();
+        let [minutes, hours, days_of_month, months, days_of_week] = fields[..] else {
+            return Err(format!(
+                "Cron expression `{expression}` should have 5 fields: minute hour day-of-month month day-of-week"
+            ));
+        };
+
+        let mut days_of_week_mask = parse_field(days_of_week, 0, 7)
+            .map_err(|err| format!("Invalid day of week in `{expression}`: {err}"))?;
+        // Both 0 and 7 are Sunday
+        if contains(days_of_week_mask, 7) {
+            days_of_week_mask = (days_of_week_mask | 1) & !(1 << 7);
+        }
+
+        Ok(CronSchedule {
+            minutes: parse_field(minutes, 0, 59)
+                .map_err(|err| format!("Invalid minute in `{expression}`: {err}"))?,
+            hours: parse_field(hours, 0, 23)
+                .map_err(|err| format!("Invalid hour in `{expression}`: {err}"))?,
+            days_of_month: parse_field(days_of_month, 1, 31)
+                .map_err(|err| format!("Invalid day of month in `{expression}`: {err}"))?,
+            months: parse_field(months, 1, 12)
+                .map_err(|err| format!("Invalid month in `{expression}`: {err}"))?,
+            days_of_week: days_of_week_mask,
+            any_day_of_month: days_of_month == "*",
+            any_day_of_week: days_of_week == "*",
+        })
+    }
+}
+
+fn contains(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+/// Parse a single field into a bit mask of the matching values
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let mut mask = 0;
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().map_err(|_| format!("invalid step `{step}`"))?;
+                if step == 0 {
+                    return Err("step should be positive".to_string());
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (parse_value(start, min, max)?, parse_value(end, min, max)?)
+        } else {
+            let value = parse_value(range, min, max)?;
+            // `a/s` means from `a` to the maximum with step `s`
+            if part.contains('/') {
+                (value, max)
+            } else {
+                (value, value)
+            }
+        };
+
+        if start > end {
+            return Err(format!("empty range `{range}`"));
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+
+    Ok(mask)
+}
+
+fn parse_value(value: &str, min: u32, max: u32) -> Result<u32, String> {
+    let parsed: u32 = value
+        .parse()
+        .map_err(|_| format!("invalid value `{value}`"))?;
+    if parsed < min || parsed > max {
+        return Err(format!("value {parsed} is out of range {min}-{max}"));
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn next(expression: &str, after: &str) -> DateTime<Utc> {
+        let schedule: CronSchedule = expression.parse().unwrap();
+        schedule.next_after(time(after)).unwrap()
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for expression in [
+            "",
+            "* * * *",
+            "* * * * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "a * * * *",
+        ] {
+            assert!(
+                expression.parse::<CronSchedule>().is_err(),
+                "`{expression}` should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn test_next_after() {
+        // Every minute, strictly after the given time
+        assert_eq!(
+            next("* * * * *", "2023-05-10T10:15:30Z"),
+            time("2023-05-10T10:16:00Z")
+        );
+        assert_eq!(
+            next("* * * * *", "2023-05-10T10:15:00Z"),
+            time("2023-05-10T10:16:00Z")
+        );
+        // Daily at 03:00
+        assert_eq!(
+            next("0 3 * * *", "2023-05-10T10:15:00Z"),
+            time("2023-05-11T03:00:00Z")
+        );
+        // Every 15 minutes
+        assert_eq!(
+            next("*/15 * * * *", "2023-05-10T10:15:00Z"),
+            time("2023-05-10T10:30:00Z")
+        );
+        // Lists and ranges
+        assert_eq!(
+            next("0 8-10,20 * * *", "2023-05-10T10:15:00Z"),
+            time("2023-05-10T20:00:00Z")
+        );
+        // Sundays, given as 7
+        assert_eq!(
+            next("30 1 * * 7", "2023-05-10T10:15:00Z"),
+            time("2023-05-14T01:30:00Z")
+        );
+        // First day of the next year
+        assert_eq!(
+            next("0 0 1 1 *", "2023-05-10T10:15:00Z"),
+            time("2024-01-01T00:00:00Z")
+        );
+        // Leap day
+        assert_eq!(
+            next("0 0 29 2 *", "2023-05-10T10:15:00Z"),
+            time("2024-02-29T00:00:00Z")
+        );
+        // Day of month or Monday
+        assert_eq!(
+            next("0 0 20 * 1", "2023-05-10T10:15:00Z"),
+            time("2023-05-15T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn test_never_matches() {
+        let schedule: CronSchedule = "0 0 31 2 *".parse().unwrap();
+        assert_eq!(schedule.next_after(time("2023-05-10T10:15:00Z")), None);
+    }
+}
@@ -1,12 +1,17 @@
+pub mod cluster;
+mod cron;
 pub mod download;
 pub mod recover;
+pub mod scheduler;
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use collection::operations::snapshot_ops::{
-    get_snapshot_description, list_snapshots_in_directory, SnapshotDescription,
+    get_checksum_path, get_snapshot_description, list_snapshots_in_directory, remove_snapshot,
+    write_checksum_for_snapshot, SnapshotDescription,
 };
+use segment::utils::tar::HashingWriter;
 use serde::{Deserialize, Serialize};
 use tar::Builder as TarBuilder;
 use tokio::io::AsyncWriteExt;
@@ -60,7 +65,7 @@ async fn _do_delete_full_snapshot(
 ) -> Result<bool, StorageError> {
     let snapshot_dir = get_full_snapshot_path(dispatcher.toc(), snapshot_name).await?;
     log::info!("Deleting full storage snapshot {:?}", snapshot_dir);
-    tokio::fs::remove_file(snapshot_dir).await?;
+    remove_snapshot(&snapshot_dir).await?;
     Ok(true)
 }
 
@@ -91,9 +96,7 @@ async fn _do_delete_collection_snapshot(
     snapshot_name: &str,
 ) -> Result<bool, StorageError> {
     let collection = dispatcher.get_collection(collection_name).await?;
-    let file_name = collection.get_snapshot_path(snapshot_name).await?;
-    log::info!("Deleting collection snapshot {:?}", file_name);
-    tokio::fs::remove_file(file_name).await?;
+    collection.delete_snapshot(snapshot_name).await?;
     Ok(true)
 }
 
@@ -127,7 +130,7 @@ async fn _do_create_full_snapshot(
     let all_collections = dispatcher.all_collections().await;
     let mut created_snapshots: Vec<(&str, SnapshotDescription)> = vec![];
     for collection_name in &all_collections {
-        let snapshot_details = dispatcher.create_snapshot(collection_name).await?;
+        let snapshot_details = dispatcher.create_snapshot(collection_name, None).await?;
         created_snapshots.push((collection_name, snapshot_details));
     }
     let current_time = chrono::Utc::now().format("%Y-%m-%d-%H-%M-%S").to_string();
@@ -176,21 +179,23 @@ async fn _do_create_full_snapshot(
     let archiving = tokio::task::spawn_blocking(move || {
         // have to use std here, cause TarBuilder is not async
         let file = std::fs::File::create(&full_snapshot_path_clone)?;
-        let mut builder = TarBuilder::new(file);
+        let mut builder = TarBuilder::new(HashingWriter::new(file));
         for (collection_name, snapshot_details) in created_snapshots_clone {
             let snapshot_path = snapshot_dir
                 .join(collection_name)
                 .join(&snapshot_details.name);
             builder.append_path_with_name(&snapshot_path, &snapshot_details.name)?;
-            std::fs::remove_file(snapshot_path)?;
+            std::fs::remove_file(&snapshot_path)?;
+            std::fs::remove_file(get_checksum_path(&snapshot_path)).ok();
         }
         builder.append_path_with_name(&config_path_clone, "config.json")?;
 
-        builder.finish()?;
-        Ok::<(), StorageError>(())
+        let checksum = builder.into_inner()?.checksum();
+        Ok::<_, StorageError>(checksum)
     });
-    archiving.await??;
+    let checksum = archiving.await??;
     tokio::fs::remove_file(&config_path).await?;
+    write_checksum_for_snapshot(&full_snapshot_path, &checksum).await?;
 
     Ok(get_snapshot_description(&full_snapshot_path).await?)
 }
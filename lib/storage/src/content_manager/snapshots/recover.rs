@@ -1,10 +1,16 @@
+use std::path::Path;
+
 use collection::collection::Collection;
 use collection::config::CollectionConfig;
-use collection::operations::snapshot_ops::{SnapshotPriority, SnapshotRecover};
+use collection::operations::snapshot_ops::{
+    verify_snapshot_checksum, SnapshotPriority, SnapshotRecover,
+};
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::shard_config::ShardType;
 use collection::shards::shard_versioning::latest_shard_paths;
+use collection::shards::snapshot_manifest::SnapshotManifest;
+use url::Url;
 
 use crate::content_manager::collection_meta_ops::{
     CollectionMetaOperations, CreateCollectionOperation,
@@ -64,12 +70,52 @@ pub async fn do_recover_from_snapshot(
     }
 }
 
+/// Download the chain of base snapshots of an incremental snapshot next to it.
+///
+/// Base snapshots are expected at the same location as the incremental one.
+/// Local snapshots are left as is, their bases are looked up in the same directory on recovery.
+async fn download_base_snapshots(
+    location: &Url,
+    snapshot_path: &Path,
+    download_dir: &Path,
+) -> Result<(), StorageError> {
+    if location.scheme() == "file" {
+        return Ok(());
+    }
+
+    let mut snapshot_path = snapshot_path.to_path_buf();
+    loop {
+        let manifest = {
+            let snapshot_path = snapshot_path.clone();
+            tokio::task::spawn_blocking(move || SnapshotManifest::read_from_archive(&snapshot_path))
+                .await??
+        };
+        let Some(base) = manifest.and_then(|manifest| manifest.base) else {
+            return Ok(());
+        };
+
+        let base_location = location.join(&base.name).map_err(|err| {
+            StorageError::bad_input(format!("Invalid base snapshot name {}: {err}", base.name))
+        })?;
+        log::debug!("Downloading base snapshot from {base_location}");
+
+        snapshot_path = download_snapshot(base_location, download_dir).await?;
+        // Also guarantees that the chain ends
+        verify_snapshot_checksum(&snapshot_path, &base.checksum).await?;
+    }
+}
+
 async fn _do_recover_from_snapshot(
     dispatcher: Dispatcher,
     collection_name: &str,
     source: SnapshotRecover,
 ) -> Result<bool, StorageError> {
-    let SnapshotRecover { location, priority } = source;
+    let SnapshotRecover {
+        location,
+        priority,
+        checksum,
+        wal_replay,
+    } = source;
     let toc = dispatcher.toc();
 
     let this_peer_id = toc.this_peer_id;
@@ -84,10 +130,16 @@ async fn _do_recover_from_snapshot(
         download_dir.path().display()
     );
 
-    let snapshot_path = download_snapshot(location, download_dir.path()).await?;
+    let snapshot_path = download_snapshot(location.clone(), download_dir.path()).await?;
 
     log::debug!("Snapshot downloaded to {}", snapshot_path.display());
 
+    if let Some(checksum) = checksum {
+        verify_snapshot_checksum(&snapshot_path, &checksum).await?;
+    }
+
+    download_base_snapshots(&location, &snapshot_path, download_dir.path()).await?;
+
     let temp_storage_path = toc.optional_temp_or_storage_temp_path()?;
 
     let tmp_collection_dir = tempfile::Builder::new()
@@ -198,6 +250,16 @@ async fn _do_recover_from_snapshot(
                 snapshot_shard_path.display()
             );
 
+            // Archive is read before recovery, which moves archived operations after the snapshot aside
+            let archived_operations = match wal_replay {
+                Some(wal_replay) => Some(
+                    collection
+                        .read_wal_archive(*shard_id, &snapshot_shard_path, wal_replay.into())
+                        .await?,
+                ),
+                None => None,
+            };
+
             let recovered = collection
                 .recover_local_shard_from(&snapshot_shard_path, *shard_id)
                 .await?;
@@ -207,6 +269,16 @@ async fn _do_recover_from_snapshot(
                 continue;
             }
 
+            if let Some(operations) = archived_operations {
+                log::info!(
+                    "Replaying {} archived operations on shard {} of collection {}",
+                    operations.len(),
+                    shard_id,
+                    collection_name
+                );
+                collection.replay_operations(*shard_id, operations).await?;
+            }
+
             // If this is the only replica, we can activate it
             // If not - de-sync is possible, so we need to run synchronization
             let other_active_replicas: Vec<_> = shard_info
@@ -0,0 +1,519 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, NaiveDateTime, Utc};
+use collection::operations::snapshot_ops::{get_checksum_path, remove_snapshot};
+use parking_lot::RwLock;
+
+use super::cron::CronSchedule;
+use crate::content_manager::errors::StorageError;
+use crate::content_manager::toc::TableOfContent;
+use crate::types::{
+    ScheduledSnapshotsStatus, SnapshotRetention, SnapshotSchedule, SnapshotScheduleConfig,
+};
+
+/// How often to check if any of the collections is due for a snapshot
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Format of the time in the collection snapshot names
+const SNAPSHOT_TIME_FORMAT: &str = "%Y-%m-%d-%H-%M-%S";
+
+/// State of the scheduled snapshots by collection name
+pub type SnapshotSchedulerStatusRef = Arc<RwLock<HashMap<String, ScheduledSnapshotsStatus>>>;
+
+#[derive(Debug, Clone)]
+enum Trigger {
+    Interval(chrono::Duration),
+    Cron(CronSchedule),
+}
+
+#[derive(Debug, Clone)]
+struct Schedule {
+    trigger: Trigger,
+    retention: SnapshotRetention,
+}
+
+impl Schedule {
+    fn from_config(config: &SnapshotSchedule) -> Result<Self, String> {
+        let trigger = match (config.interval_sec, &config.cron) {
+            (Some(0), None) => return Err("`interval_sec` should be positive".to_string()),
+            (Some(interval_sec), None) => {
+                Trigger::Interval(chrono::Duration::seconds(interval_sec as i64))
+            }
+            (None, Some(expression)) => {
+                let cron: CronSchedule = expression.parse()?;
+                if cron.next_after(Utc::now()).is_none() {
+                    return Err(format!("Cron expression `{expression}` never matches"));
+                }
+                Trigger::Cron(cron)
+            }
+            _ => return Err("Exactly one of `interval_sec` and `cron` should be set".to_string()),
+        };
+
+        Ok(Schedule {
+            trigger,
+            retention: config.retention.clone(),
+        })
+    }
+
+    /// Time of the first snapshot after the service start
+    fn first_run(
+        &self,
+        now: DateTime<Utc>,
+        last_snapshot: Option<DateTime<Utc>>,
+    ) -> Option<DateTime<Utc>> {
+        match &self.trigger {
+            // Continue the interval from the last scheduled snapshot, which survived the restart
+            Trigger::Interval(interval) => Some(last_snapshot.unwrap_or(now) + *interval),
+            Trigger::Cron(cron) => cron.next_after(now),
+        }
+    }
+
+    fn next_run(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match &self.trigger {
+            Trigger::Interval(interval) => Some(now + *interval),
+            Trigger::Cron(cron) => cron.next_after(now),
+        }
+    }
+}
+
+/// Background task, which creates collection snapshots by schedule
+/// and deletes the old ones according to the retention policy.
+///
+/// Runs on every peer and snapshots the local shards, same as the regular collection snapshot.
+/// Only snapshots created by the schedule are deleted, manual snapshots are never touched.
+pub struct SnapshotScheduler {
+    toc: Arc<TableOfContent>,
+    default: Option<Schedule>,
+    collections: HashMap<String, Schedule>,
+    status: SnapshotSchedulerStatusRef,
+}
+
+impl SnapshotScheduler {
+    /// Invalid schedules are reported and ignored
+    pub fn new(toc: Arc<TableOfContent>, config: &SnapshotScheduleConfig) -> Self {
+        let default =
+            config
+                .default
+                .as_ref()
+                .and_then(|schedule| match Schedule::from_config(schedule) {
+                    Ok(schedule) => Some(schedule),
+                    Err(err) => {
+                        log::error!("Invalid default snapshot schedule: {err}");
+                        None
+                    }
+                });
+
+        let collections = config
+            .collections
+            .iter()
+            .filter_map(
+                |(collection_name, schedule)| match Schedule::from_config(schedule) {
+                    Ok(schedule) => Some((collection_name.clone(), schedule)),
+                    Err(err) => {
+                        log::error!(
+                            "Invalid snapshot schedule of collection {collection_name}: {err}"
+                        );
+                        None
+                    }
+                },
+            )
+            .collect();
+
+        Self {
+            toc,
+            default,
+            collections,
+            status: Default::default(),
+        }
+    }
+
+    pub fn status(&self) -> SnapshotSchedulerStatusRef {
+        self.status.clone()
+    }
+
+    pub async fn run(self) {
+        if self.default.is_none() && self.collections.is_empty() {
+            return;
+        }
+
+        loop {
+            self.snapshot_due_collections().await;
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    }
+
+    fn schedule_for(&self, collection_name: &str) -> Option<&Schedule> {
+        self.collections
+            .get(collection_name)
+            .or(self.default.as_ref())
+    }
+
+    async fn snapshot_due_collections(&self) {
+        let collections = self.toc.all_collections().await;
+
+        // Forget about removed collections
+        self.status
+            .write()
+            .retain(|collection_name, _| collections.contains(collection_name));
+
+        for collection_name in collections {
+            let Some(schedule) = self.schedule_for(&collection_name) else {
+                continue;
+            };
+
+            let now = Utc::now();
+            let known_next_snapshot_time = self
+                .status
+                .read()
+                .get(&collection_name)
+                .map(|status| status.next_snapshot_time);
+
+            let next_snapshot_time = match known_next_snapshot_time {
+                Some(next_snapshot_time) => next_snapshot_time,
+                None => {
+                    let last_snapshot = self
+                        .scheduled_snapshots(&collection_name)
+                        .await
+                        .ok()
+                        .and_then(|snapshots| snapshots.into_iter().map(|(_, time)| time).max())
+                        .map(|time| DateTime::from_naive_utc_and_offset(time, Utc));
+                    let first_run = schedule.first_run(now, last_snapshot);
+                    self.status
+                        .write()
+                        .entry(collection_name.clone())
+                        .or_default()
+                        .next_snapshot_time = first_run;
+                    first_run
+                }
+            };
+
+            let Some(next_snapshot_time) = next_snapshot_time else {
+                continue;
+            };
+
+            if next_snapshot_time > now {
+                continue;
+            }
+
+            let result = self.snapshot_collection(&collection_name, schedule).await;
+
+            let now = Utc::now();
+            let mut status_guard = self.status.write();
+            let status = status_guard.entry(collection_name.clone()).or_default();
+            status.next_snapshot_time = schedule.next_run(now);
+            match result {
+                Ok((snapshot_name, pruned)) => {
+                    status.last_snapshot = Some(snapshot_name);
+                    status.last_snapshot_time = Some(now);
+                    status.pruned += pruned;
+                }
+                Err(err) => {
+                    log::error!("Failed to create scheduled snapshot of collection {collection_name}: {err}");
+                    status.last_error = Some(err.to_string());
+                    status.last_error_time = Some(now);
+                    status.failures += 1;
+                }
+            }
+        }
+    }
+
+    /// Create a snapshot and prune old ones, returns the name of the new snapshot
+    /// and the number of deleted snapshots
+    async fn snapshot_collection(
+        &self,
+        collection_name: &str,
+        schedule: &Schedule,
+    ) -> Result<(String, usize), StorageError> {
+        let snapshot = self.toc.create_snapshot(collection_name, None).await?;
+
+        // Mark the snapshot as scheduled, so it is distinguishable from the manual ones
+        let snapshot_name = scheduled_snapshot_name(collection_name, &snapshot.name);
+        let snapshots_path = self.toc.snapshots_path_for_collection(collection_name);
+        let (old_path, new_path) = (
+            snapshots_path.join(&snapshot.name),
+            snapshots_path.join(&snapshot_name),
+        );
+        tokio::fs::rename(get_checksum_path(&old_path), get_checksum_path(&new_path)).await?;
+        tokio::fs::rename(&old_path, &new_path).await?;
+        log::info!("Created scheduled snapshot {snapshot_name} of collection {collection_name}");
+
+        let snapshots = self.scheduled_snapshots(collection_name).await?;
+        let to_prune = snapshots_to_prune(snapshots, &schedule.retention);
+
+        let collection = self.toc.get_collection(collection_name).await?;
+        let to_prune = if to_prune.is_empty() {
+            to_prune
+        } else {
+            keep_snapshot_bases(to_prune, &collection.snapshot_bases().await?)
+        };
+        for snapshot_name in &to_prune {
+            let snapshot_path = collection.get_snapshot_path(snapshot_name).await?;
+            log::info!("Deleting expired snapshot {snapshot_name} of collection {collection_name}");
+            remove_snapshot(&snapshot_path).await?;
+        }
+
+        Ok((snapshot_name, to_prune.len()))
+    }
+
+    /// Names and creation times of the snapshots made by the schedule
+    async fn scheduled_snapshots(
+        &self,
+        collection_name: &str,
+    ) -> Result<Vec<(String, NaiveDateTime)>, StorageError> {
+        let collection = self.toc.get_collection(collection_name).await?;
+        let snapshots = collection.list_snapshots().await?;
+        Ok(snapshots
+            .into_iter()
+            .filter_map(|snapshot| {
+                let time = scheduled_snapshot_time(collection_name, &snapshot.name)?;
+                Some((snapshot.name, time))
+            })
+            .collect())
+    }
+}
+
+/// Turns `{collection}-{peer_id}-{time}.snapshot` into `{collection}-scheduled-{peer_id}-{time}.snapshot`
+fn scheduled_snapshot_name(collection_name: &str, snapshot_name: &str) -> String {
+    let suffix = snapshot_name
+        .strip_prefix(collection_name)
+        .and_then(|suffix| suffix.strip_prefix('-'))
+        .unwrap_or(snapshot_name);
+    format!("{collection_name}-scheduled-{suffix}")
+}
+
+/// Creation time of the scheduled snapshot, `None` if the snapshot is not a scheduled one
+fn scheduled_snapshot_time(collection_name: &str, snapshot_name: &str) -> Option<NaiveDateTime> {
+    let suffix = snapshot_name
+        .strip_prefix(collection_name)?
+        .strip_prefix("-scheduled-")?
+        .strip_suffix(".snapshot")?;
+    let (_peer_id, time) = suffix.split_once('-')?;
+    NaiveDateTime::parse_from_str(time, SNAPSHOT_TIME_FORMAT).ok()
+}
+
+/// Select snapshots, which are not kept by any of the retention rules
+fn snapshots_to_prune(
+    mut snapshots: Vec<(String, NaiveDateTime)>,
+    retention: &SnapshotRetention,
+) -> Vec<String> {
+    if retention.keep_last.is_none()
+        && retention.keep_daily.is_none()
+        && retention.keep_weekly.is_none()
+    {
+        return Vec::new();
+    }
+
+    // Most recent first
+    snapshots.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+    let mut keep = vec![false; snapshots.len()];
+    let keep_last = retention.keep_last.unwrap_or(0).max(1);
+    keep.iter_mut()
+        .take(keep_last)
+        .for_each(|keep| *keep = true);
+
+    keep_one_per_period(&snapshots, &mut keep, retention.keep_daily, |time| {
+        (time.year(), time.ordinal())
+    });
+    keep_one_per_period(&snapshots, &mut keep, retention.keep_weekly, |time| {
+        let week = time.iso_week();
+        (week.year(), week.week())
+    });
+
+    snapshots
+        .into_iter()
+        .zip(keep)
+        .filter(|(_, keep)| !keep)
+        .map(|((name, _), _)| name)
+        .collect()
+}
+
+/// Exclude snapshots from pruning, which are bases of the kept incremental snapshots.
+///
+/// `bases` maps names of the incremental snapshots to their base snapshots.
+fn keep_snapshot_bases(mut to_prune: Vec<String>, bases: &HashMap<String, String>) -> Vec<String> {
+    let mut required = HashSet::new();
+    for snapshot in bases.keys().filter(|snapshot| !to_prune.contains(*snapshot)) {
+        // Whole chain of bases is needed to recover the incremental snapshot
+        let mut current = snapshot;
+        while let Some(base) = bases.get(current) {
+            if !required.insert(base.clone()) {
+                break;
+            }
+            current = base;
+        }
+    }
+    to_prune.retain(|snapshot| !required.contains(snapshot));
+    to_prune
+}
+
+/// Mark the most recent snapshot of each of the last `periods` periods to be kept.
+/// `snapshots` should be sorted from the most recent.
+fn keep_one_per_period<P: PartialEq>(
+    snapshots: &[(String, NaiveDateTime)],
+    keep: &mut [bool],
+    periods: Option<usize>,
+    period_of: impl Fn(&NaiveDateTime) -> P,
+) {
+    let Some(periods) = periods else {
+        return;
+    };
+
+    let mut last_period = None;
+    let mut kept = 0;
+    for ((_, time), keep) in snapshots.iter().zip(keep.iter_mut()) {
+        if kept >= periods {
+            break;
+        }
+        let period = period_of(time);
+        if last_period.as_ref() != Some(&period) {
+            *keep = true;
+            kept += 1;
+            last_period = Some(period);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(time: &str) -> (String, NaiveDateTime) {
+        let time = NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M").unwrap();
+        (
+            format!(
+                "test-scheduled-1-{}.snapshot",
+                time.format(SNAPSHOT_TIME_FORMAT)
+            ),
+            time,
+        )
+    }
+
+    fn pruned(snapshots: &[(String, NaiveDateTime)], retention: SnapshotRetention) -> Vec<String> {
+        let mut pruned = snapshots_to_prune(snapshots.to_vec(), &retention);
+        pruned.sort();
+        pruned
+    }
+
+    #[test]
+    fn test_scheduled_snapshot_name() {
+        let name = scheduled_snapshot_name(
+            "my-collection",
+            "my-collection-123-2023-05-10-10-15-00.snapshot",
+        );
+        assert_eq!(
+            name,
+            "my-collection-scheduled-123-2023-05-10-10-15-00.snapshot"
+        );
+        assert_eq!(
+            scheduled_snapshot_time("my-collection", &name),
+            Some(snapshot("2023-05-10 10:15").1)
+        );
+
+        // Manual and cluster snapshots are not scheduled
+        assert_eq!(
+            scheduled_snapshot_time(
+                "my-collection",
+                "my-collection-123-2023-05-10-10-15-00.snapshot"
+            ),
+            None
+        );
+        assert_eq!(
+            scheduled_snapshot_time(
+                "my-collection",
+                "my-collection-cluster-2023-05-10-10-15-00.snapshot"
+            ),
+            None
+        );
+        // Snapshot of another collection with the same prefix
+        assert_eq!(
+            scheduled_snapshot_time(
+                "my",
+                "my-collection-scheduled-123-2023-05-10-10-15-00.snapshot"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_keep_snapshot_bases() {
+        let name = |name: &str| name.to_string();
+        // c -> b -> a, e -> d
+        let bases = HashMap::from([
+            (name("c"), name("b")),
+            (name("b"), name("a")),
+            (name("e"), name("d")),
+        ]);
+
+        // Chain of the kept incremental snapshot is kept
+        let to_prune = vec![name("a"), name("b"), name("d"), name("x")];
+        assert_eq!(
+            keep_snapshot_bases(to_prune, &bases),
+            vec![name("d"), name("x")]
+        );
+
+        // Pruned incremental snapshots don't need their bases
+        let to_prune = vec![name("a"), name("b"), name("c"), name("d"), name("e")];
+        assert_eq!(keep_snapshot_bases(to_prune.clone(), &bases), to_prune);
+    }
+
+    #[test]
+    fn test_retention() {
+        let snapshots = vec![
+            snapshot("2023-05-01 10:00"), // Monday
+            snapshot("2023-05-08 10:00"), // Monday
+            snapshot("2023-05-09 10:00"),
+            snapshot("2023-05-09 22:00"),
+            snapshot("2023-05-10 10:00"),
+            snapshot("2023-05-10 12:00"),
+        ];
+        let name = |i: usize| snapshots[i].0.clone();
+
+        // No rules - keep everything
+        assert!(pruned(&snapshots, SnapshotRetention::default()).is_empty());
+
+        let retention = SnapshotRetention {
+            keep_last: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            pruned(&snapshots, retention),
+            vec![name(0), name(1), name(2), name(3)]
+        );
+
+        // The latest one is always kept
+        let retention = SnapshotRetention {
+            keep_last: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(pruned(&snapshots, retention).len(), snapshots.len() - 1);
+
+        let retention = SnapshotRetention {
+            keep_daily: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            pruned(&snapshots, retention),
+            vec![name(0), name(1), name(2), name(4)]
+        );
+
+        let retention = SnapshotRetention {
+            keep_weekly: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            pruned(&snapshots, retention),
+            vec![name(1), name(2), name(3), name(4)]
+        );
+
+        // Rules are combined
+        let retention = SnapshotRetention {
+            keep_last: Some(1),
+            keep_daily: Some(3),
+            keep_weekly: Some(3),
+        };
+        assert_eq!(pruned(&snapshots, retention), vec![name(2), name(4)]);
+    }
+}
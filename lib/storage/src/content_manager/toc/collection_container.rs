@@ -73,6 +73,10 @@ impl CollectionContainer for TableOfContent {
                 Self::on_transfer_failure_callback(self.consensus_proposal_sender.clone());
             let transfer_success_callback =
                 Self::on_transfer_success_callback(self.consensus_proposal_sender.clone());
+            let resharding_failure_callback =
+                Self::on_resharding_failure_callback(self.consensus_proposal_sender.clone());
+            let migration_success_callback =
+                Self::on_migration_success_callback(self.consensus_proposal_sender.clone());
 
             for collection in collections.values() {
                 let finish_shard_initialize = Self::change_peer_state_callback(
@@ -98,6 +102,8 @@ impl CollectionContainer for TableOfContent {
                     .sync_local_state(
                         transfer_failure_callback.clone(),
                         transfer_success_callback.clone(),
+                        resharding_failure_callback.clone(),
+                        migration_success_callback.clone(),
                         finish_shard_initialize,
                         convert_to_listener_callback,
                         convert_from_listener_to_active_callback,
@@ -114,7 +120,7 @@ impl TableOfContent {
         self.general_runtime.block_on(self.collections_snapshot())
     }
 
-    async fn collections_snapshot(&self) -> consensus_manager::CollectionsSnapshot {
+    pub(crate) async fn collections_snapshot(&self) -> consensus_manager::CollectionsSnapshot {
         let mut collections: HashMap<CollectionId, collection_state::State> = HashMap::new();
         for (id, collection) in self.collections.read().await.iter() {
             collections.insert(id.clone(), collection.state().await);
@@ -293,4 +299,43 @@ impl TableOfContent {
             }
         })
     }
+
+    fn on_resharding_failure_callback(
+        proposal_sender: Option<OperationSender>,
+    ) -> collection::collection::OnReshardingFailure {
+        Arc::new(move |collection_name, reason| {
+            if let Some(proposal_sender) = &proposal_sender {
+                let operation =
+                    ConsensusOperations::abort_resharding(collection_name.clone(), reason);
+                if let Err(send_error) = proposal_sender.send(operation) {
+                    log::error!(
+                        "Can't send proposal to abort resharding of collection {}. Error: {}",
+                        collection_name,
+                        send_error
+                    );
+                }
+            }
+        })
+    }
+
+    fn on_migration_success_callback(
+        proposal_sender: Option<OperationSender>,
+    ) -> collection::collection::OnMigrationSuccess {
+        Arc::new(move |shard_id, collection_name| {
+            if let Some(proposal_sender) = &proposal_sender {
+                let operation = ConsensusOperations::finish_resharding_migration(
+                    collection_name.clone(),
+                    shard_id,
+                );
+                if let Err(send_error) = proposal_sender.send(operation) {
+                    log::error!(
+                        "Can't send proposal to complete migration of shard {} of collection {}. Error: {}",
+                        shard_id,
+                        collection_name,
+                        send_error
+                    );
+                }
+            }
+        })
+    }
 }
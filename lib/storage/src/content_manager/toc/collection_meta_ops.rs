@@ -62,10 +62,23 @@ impl TableOfContent {
                     .await
                     .map(|()| true)
             }
+            CollectionMetaOperations::Resharding(collection, operation) => {
+                log::debug!("Resharding {:?} of {}", operation, collection);
+
+                self.handle_resharding(collection, operation)
+                    .await
+                    .map(|()| true)
+            }
             CollectionMetaOperations::SetShardReplicaState(operation) => {
                 log::debug!("Set shard replica state {:?}", operation);
                 self.set_shard_replica_state(operation).await.map(|()| true)
             }
+            CollectionMetaOperations::SetOptimizersPaused(operation) => {
+                log::debug!("Set optimizers paused {:?}", operation);
+                let collection = self.get_collection(&operation.collection_name).await?;
+                collection.set_optimizers_paused(operation.paused).await?;
+                Ok(true)
+            }
             CollectionMetaOperations::Nop { .. } => Ok(true),
         }
     }
@@ -226,7 +239,14 @@ impl TableOfContent {
                     config: _,
                     shards,
                     transfers,
+                    resharding,
                 } = collection.state().await;
+                if let Some(resharding) = resharding {
+                    return Err(StorageError::bad_request(format!(
+                        "Can't transfer shard {} while collection {collection_id} is resharded into shard {}",
+                        transfer.shard_id, resharding.shard_id
+                    )));
+                }
                 let all_peers: HashSet<_> = self
                     .channel_service
                     .id_to_address
@@ -299,6 +319,86 @@ impl TableOfContent {
         Ok(())
     }
 
+    async fn handle_resharding(
+        &self,
+        collection_id: CollectionId,
+        resharding_operation: ReshardingOperations,
+    ) -> Result<(), StorageError> {
+        let collection = self.get_collection(&collection_id).await?;
+        let proposal_sender = if let Some(proposal_sender) = self.consensus_proposal_sender.clone()
+        {
+            proposal_sender
+        } else {
+            return Err(StorageError::service_error(
+                "Can't handle resharding, this is a single node deployment",
+            ));
+        };
+
+        match resharding_operation {
+            ReshardingOperations::Start(resharding) => {
+                let peer_exists = self
+                    .channel_service
+                    .id_to_address
+                    .read()
+                    .contains_key(&resharding.peer_id);
+                if !peer_exists {
+                    return Err(StorageError::bad_request(format!(
+                        "Peer {} does not exist",
+                        resharding.peer_id
+                    )));
+                }
+
+                let on_finish_sender = proposal_sender.clone();
+                let on_finish_collection_id = collection_id.clone();
+                let on_finish = move |shard_id| {
+                    let sender = on_finish_sender.clone();
+                    let collection_id = on_finish_collection_id.clone();
+                    async move {
+                        let operation = ConsensusOperations::finish_resharding_migration(
+                            collection_id,
+                            shard_id,
+                        );
+                        if let Err(error) = sender.send(operation) {
+                            log::error!("Can't report resharding progress to consensus: {}", error)
+                        };
+                    }
+                };
+
+                let on_failure = move |shard_id| {
+                    let sender = proposal_sender.clone();
+                    let collection_id = collection_id.clone();
+                    async move {
+                        let operation = ConsensusOperations::abort_resharding(
+                            collection_id,
+                            &format!("migration of shard {shard_id} failed"),
+                        );
+                        if let Err(error) = sender.send(operation) {
+                            log::error!("Can't report resharding progress to consensus: {}", error)
+                        };
+                    }
+                };
+
+                collection
+                    .start_resharding(resharding, on_finish, on_failure)
+                    .await?;
+            }
+            ReshardingOperations::FinishMigration(shard_id) => {
+                collection.finish_resharding_migration(shard_id).await?;
+            }
+            ReshardingOperations::Abort { reason } => {
+                // Validate resharding exists to prevent double handling
+                if collection.resharding_state().await.is_none() {
+                    return Err(StorageError::bad_request(format!(
+                        "Collection {collection_id} is not being resharded"
+                    )));
+                }
+                log::warn!("Aborting resharding: {reason}");
+                collection.abort_resharding().await?;
+            }
+        };
+        Ok(())
+    }
+
     async fn set_shard_replica_state(
         &self,
         operation: SetShardReplicaState,
@@ -58,6 +58,28 @@ impl TableOfContent {
             )));
         }
 
+        for (vector_name, params) in vectors.params_iter() {
+            if params.disk_ann_config.is_some() && params.distance.is_binary() {
+                return Err(StorageError::bad_input(format!(
+                    "DiskANN index is not supported for vector {vector_name} with {:?} distance",
+                    params.distance,
+                )));
+            }
+            if params.ivf_config.is_some() {
+                if params.disk_ann_config.is_some() {
+                    return Err(StorageError::bad_input(format!(
+                        "Only one of DiskANN and IVF indexes can be configured for vector {vector_name}",
+                    )));
+                }
+                if params.distance.is_binary() {
+                    return Err(StorageError::bad_input(format!(
+                        "IVF index is not supported for vector {vector_name} with {:?} distance",
+                        params.distance,
+                    )));
+                }
+            }
+        }
+
         if let Some(init_from) = &init_from {
             self.check_collections_compatibility(&vectors, &init_from.collection)
                 .await?;
@@ -117,6 +139,7 @@ impl TableOfContent {
             None => self.storage_config.quantization.clone(),
             Some(diff) => Some(diff),
         };
+        collection_params.check_vector_params(quantization_config.as_ref())?;
 
         let collection_config = CollectionConfig {
             wal_config,
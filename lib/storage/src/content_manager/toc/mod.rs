@@ -21,6 +21,7 @@ use collection::collection::{Collection, RequestShardTransfer};
 use collection::config::{default_replication_factor, CollectionConfig};
 use collection::operations::types::*;
 use collection::shards::channel_service::ChannelService;
+use collection::shards::peer_labels::PeerLabelsById;
 use collection::shards::replica_set;
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
@@ -294,6 +295,7 @@ impl TableOfContent {
         &self,
         op: &CreateCollectionOperation,
         suggested_shard_number: NonZeroU32,
+        peer_labels: &PeerLabelsById,
     ) -> ShardDistributionProposal {
         let shard_number = op
             .create_collection
@@ -315,8 +317,12 @@ impl TableOfContent {
             .and_then(NonZeroU32::new)
             .unwrap_or_else(default_replication_factor);
 
-        let shard_distribution =
-            ShardDistributionProposal::new(shard_number, replication_factor, &known_peers);
+        let shard_distribution = ShardDistributionProposal::new(
+            shard_number,
+            replication_factor,
+            &known_peers,
+            peer_labels,
+        );
 
         log::debug!(
             "Suggesting distribution for {} shards for collection '{}' among {} peers {:?}",
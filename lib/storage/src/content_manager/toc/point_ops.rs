@@ -39,6 +39,24 @@ impl TableOfContent {
         .map_err(|err| err.into())
     }
 
+    /// Same as [`TableOfContent::recommend`], but also returns details of how the search was performed
+    pub async fn recommend_with_profile(
+        &self,
+        collection_name: &str,
+        request: RecommendRequest,
+        read_consistency: Option<ReadConsistency>,
+    ) -> Result<(Vec<ScoredPoint>, SearchProfile), StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        recommendations::recommend_by_with_profile(
+            request,
+            &collection,
+            |name| self.get_collection_opt(name),
+            read_consistency,
+        )
+        .await
+        .map_err(|err| err.into())
+    }
+
     /// Recommend points in a batching fashion using positive and negative example from the request
     ///
     /// # Arguments
@@ -91,6 +109,21 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Same as [`TableOfContent::search`], but also returns details of how the search was performed
+    pub async fn search_with_profile(
+        &self,
+        collection_name: &str,
+        request: SearchRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: Option<ShardId>,
+    ) -> Result<(Vec<ScoredPoint>, SearchProfile), StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .search_with_profile(request, read_consistency, shard_selection)
+            .await
+            .map_err(|err| err.into())
+    }
+
     /// Search in a batching fashion for the closest points using vector similarity with given restrictions defined
     /// in the request
     ///
@@ -132,6 +165,20 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    pub async fn core_search_batch_with_profile(
+        &self,
+        collection_name: &str,
+        request: CoreSearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: Option<ShardId>,
+    ) -> Result<(Vec<Vec<ScoredPoint>>, SearchProfile), StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .core_search_batch_with_profile(request, read_consistency, shard_selection)
+            .await
+            .map_err(|err| err.into())
+    }
+
     /// Count points in the collection.
     ///
     /// # Arguments
@@ -42,16 +42,18 @@ impl TableOfContent {
         Ok(snapshots_path)
     }
 
+    /// Create a snapshot of the collection, incremental if the name of the `base` snapshot is given
     pub async fn create_snapshot(
         &self,
         collection_name: &str,
+        base: Option<&str>,
     ) -> Result<SnapshotDescription, StorageError> {
         let collection = self.get_collection(collection_name).await?;
         // We want to use temp dir inside the temp_path (storage if not specified), because it is possible, that
         // snapshot directory is mounted as network share and multiple writes to it could be slow
         let temp_dir = self.optional_temp_or_storage_temp_path()?;
         Ok(collection
-            .create_snapshot(&temp_dir, self.this_peer_id)
+            .create_snapshot(&temp_dir, self.this_peer_id, base)
             .await?)
     }
 
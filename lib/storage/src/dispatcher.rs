@@ -60,6 +60,7 @@ impl Dispatcher {
                                 &op,
                                 NonZeroU32::new(number_of_peers as u32)
                                     .expect("Peer count should be always >= 1"),
+                                &state.0.peer_labels(),
                             )
                             .await;
 
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::num::NonZeroUsize;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
@@ -7,6 +8,7 @@ use collection::config::WalConfig;
 use collection::operations::shared_storage_config::SharedStorageConfig;
 use collection::operations::types::NodeType;
 use collection::optimizers_builder::OptimizersConfig;
+use collection::shards::peer_labels::PeerLabels;
 use collection::shards::shard::PeerId;
 use memory::madvise;
 use schemars::JsonSchema;
@@ -72,6 +74,15 @@ pub struct StorageConfig {
     pub recovery_mode: Option<String>,
     #[serde(default)]
     pub update_concurrency: Option<NonZeroUsize>,
+    /// Automatic creation and cleanup of collection snapshots
+    #[serde(default)]
+    pub snapshot_schedule: SnapshotScheduleConfig,
+    /// If provided - WAL operations are archived into this directory before WAL truncation,
+    /// so collections can be recovered to a point in time after a snapshot.
+    /// Can be a mounted object storage.
+    #[validate(length(min = 1))]
+    #[serde(default)]
+    pub wal_archive_path: Option<String>,
 }
 
 impl StorageConfig {
@@ -86,10 +97,53 @@ impl StorageConfig {
                 .map(|x| Duration::from_secs(x as u64)),
             self.update_concurrency,
             is_distributed,
+            self.wal_archive_path.as_ref().map(PathBuf::from),
         )
     }
 }
 
+/// Schedules of automatic collection snapshots
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct SnapshotScheduleConfig {
+    /// Schedule applied to every collection without an own schedule.
+    /// If not set, only collections listed in `collections` are snapshotted
+    #[serde(default)]
+    pub default: Option<SnapshotSchedule>,
+    /// Schedules of specific collections, override the default one
+    #[serde(default)]
+    pub collections: HashMap<String, SnapshotSchedule>,
+}
+
+/// When to create snapshots of a collection and how many of them to keep.
+/// Exactly one of `interval_sec` and `cron` should be set.
+#[derive(Debug, Deserialize, Clone)]
+pub struct SnapshotSchedule {
+    /// Create a snapshot every this many seconds
+    #[serde(default)]
+    pub interval_sec: Option<u64>,
+    /// Create snapshots by cron expression `minute hour day-of-month month day-of-week`, in UTC
+    #[serde(default)]
+    pub cron: Option<String>,
+    #[serde(default)]
+    pub retention: SnapshotRetention,
+}
+
+/// Which scheduled snapshots to keep, the rest are deleted.
+/// A snapshot is kept if any of the rules selects it, the most recent snapshot is always kept.
+/// If no rules are set, all snapshots are kept.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct SnapshotRetention {
+    /// Keep this many most recent snapshots
+    #[serde(default)]
+    pub keep_last: Option<usize>,
+    /// Keep the most recent snapshot of each of this many last days with snapshots
+    #[serde(default)]
+    pub keep_daily: Option<usize>,
+    /// Keep the most recent snapshot of each of this many last weeks with snapshots
+    #[serde(default)]
+    pub keep_weekly: Option<usize>,
+}
+
 fn default_snapshots_path() -> String {
     "./snapshots".to_string()
 }
@@ -106,6 +160,9 @@ const fn default_mmap_advice() -> madvise::Advice {
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 pub struct PeerInfo {
     pub uri: String,
+    /// Labels of the peer, such as zone or rack
+    #[serde(default)]
+    pub labels: PeerLabels,
     // ToDo: How long ago was the last communication? In milliseconds
     // pub last_responded_millis: usize
 }
@@ -175,6 +232,103 @@ pub struct ClusterInfo {
     /// Consequent failures of message send operations in consensus by peer address.
     /// On the first success to send to that peer - entry is removed from this hashmap.
     pub message_send_failures: HashMap<String, MessageSendErrors>,
+    /// Progress of draining peers and rebalancing replicas across the cluster
+    pub rebalancer: Option<RebalancerStatus>,
+}
+
+/// Progress of draining peers and rebalancing replicas across the cluster
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default)]
+pub struct RebalancerStatus {
+    /// If `true`, replicas are moved automatically to even out their number across peers
+    pub auto_rebalance: bool,
+    /// Number of replicas left on each of the draining peers
+    pub draining_peers: HashMap<PeerId, usize>,
+    /// Number of shard transfers currently running in the cluster
+    pub running_transfers: usize,
+    /// Number of replica moves left to drain peers and balance the cluster
+    pub pending_moves: usize,
+    /// Time of the last rebalancer run
+    pub last_run: DateTime<Utc>,
+}
+
+/// State of the scheduled snapshots of a collection on this peer
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default)]
+pub struct ScheduledSnapshotsStatus {
+    /// Name of the last snapshot created by the schedule
+    pub last_snapshot: Option<String>,
+    /// Time of the last successfully created snapshot
+    pub last_snapshot_time: Option<DateTime<Utc>>,
+    /// Time of the next scheduled snapshot
+    pub next_snapshot_time: Option<DateTime<Utc>>,
+    /// Error of the last failed snapshot creation or cleanup
+    pub last_error: Option<String>,
+    /// Time of the last failure
+    pub last_error_time: Option<DateTime<Utc>>,
+    /// Total number of failed snapshot creations and cleanups
+    pub failures: usize,
+    /// Total number of snapshots deleted by the retention policy
+    pub pruned: usize,
+}
+
+impl Anonymize for ScheduledSnapshotsStatus {
+    fn anonymize(&self) -> Self {
+        ScheduledSnapshotsStatus {
+            last_snapshot: self.last_snapshot.anonymize(),
+            last_snapshot_time: self.last_snapshot_time,
+            next_snapshot_time: self.next_snapshot_time,
+            last_error: self.last_error.anonymize(),
+            last_error_time: self.last_error_time,
+            failures: self.failures,
+            pruned: self.pruned,
+        }
+    }
+}
+
+/// Configuration of the background task, which moves replicas between peers
+#[derive(Debug, Deserialize, Clone, Validate)]
+pub struct RebalancerConfig {
+    /// Move replicas automatically to even out their number across peers.
+    /// Draining peers works regardless of this flag.
+    #[serde(default)]
+    pub auto_rebalance: bool,
+    /// How often to check the replica placement
+    #[serde(default = "default_rebalancer_interval_sec")]
+    #[validate(range(min = 1))]
+    pub interval_sec: u64,
+    /// Maximum number of shard transfers running in the cluster at the same time
+    #[serde(default = "default_rebalancer_max_transfers")]
+    #[validate(range(min = 1))]
+    pub max_concurrent_transfers: usize,
+}
+
+impl Default for RebalancerConfig {
+    fn default() -> Self {
+        RebalancerConfig {
+            auto_rebalance: false,
+            interval_sec: default_rebalancer_interval_sec(),
+            max_concurrent_transfers: default_rebalancer_max_transfers(),
+        }
+    }
+}
+
+const fn default_rebalancer_interval_sec() -> u64 {
+    10
+}
+
+const fn default_rebalancer_max_transfers() -> usize {
+    2
+}
+
+/// Configuration of the background task, which compares replicas of the shards
+#[derive(Debug, Deserialize, Clone, Default, Validate)]
+pub struct ConsistencyCheckConfig {
+    /// How often to compare replicas. Scheduled checks are disabled if not set
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub interval_sec: Option<u64>,
+    /// Copy the content of diverged points, on which the majority of replicas agree, to the other replicas
+    #[serde(default)]
+    pub repair: bool,
 }
 
 /// Information about current cluster status and structure
@@ -200,6 +354,7 @@ impl Anonymize for PeerInfo {
     fn anonymize(&self) -> Self {
         PeerInfo {
             uri: self.uri.anonymize(),
+            labels: self.labels.clone(),
         }
     }
 }
@@ -229,6 +384,7 @@ impl Anonymize for ClusterInfo {
             raft_info: self.raft_info.anonymize(),
             consensus_thread_status: self.consensus_thread_status.clone(),
             message_send_failures: self.message_send_failures.clone(),
+            rebalancer: self.rebalancer.clone(),
         }
     }
 }
@@ -57,6 +57,8 @@ fn test_alias_operation() {
         async_scorer: false,
         update_concurrency: Some(NonZeroUsize::new(2).unwrap()),
         // update_concurrency: None,
+        snapshot_schedule: Default::default(),
+        wal_archive_path: None,
     };
 
     let search_runtime = Runtime::new().unwrap();
@@ -92,6 +94,8 @@ fn test_alias_operation() {
                             hnsw_config: None,
                             quantization_config: None,
                             on_disk: None,
+                            disk_ann_config: None,
+                            ivf_config: None,
                         }
                         .into(),
                         hnsw_config: None,
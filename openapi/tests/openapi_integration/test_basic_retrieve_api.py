@@ -211,6 +211,34 @@ def test_is_null_condition():
     must_not_is_null("city")
     must_not_is_null("city[]")
 
+
+def test_min_should_filter():
+    def match_city(city: str):
+        return {"key": "city", "match": {"value": city}}
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "filter": {
+                "min_should": {
+                    "conditions": [
+                        match_city("Berlin"),
+                        match_city("London"),
+                        match_city("Moscow"),
+                    ],
+                    "min_count": 2
+                }
+            },
+            "limit": 10
+        }
+    )
+    assert response.ok
+
+    ids = sorted(x['id'] for x in response.json()['result']['points'])
+    assert ids == [2, 3, 4]
+
 def test_recommendation():
     response = request_with_validation(
         api='/collections/{collection_name}/points/recommend',
@@ -0,0 +1,106 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_binary'
+
+
+def binary_collection_setup(collection_name, distance):
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="DELETE",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 10,
+                "distance": distance,
+            }
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": [1, 0, 1, 1, 0, 0, 0, 0, 1, 1]},
+                {"id": 2, "vector": [1, 0, 1, 0, 0, 0, 0, 0, 1, 1]},
+                {"id": 3, "vector": [0, 1, 0, 0, 1, 1, 1, 1, 0, 0]},
+                {"id": 4, "vector": [1, 1, 1, 1, 1, 1, 1, 1, 1, 1]},
+            ]
+        }
+    )
+    assert response.ok
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def search(query, limit=4):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": query,
+            "limit": limit,
+            "with_vector": True,
+        }
+    )
+    assert response.ok
+    return response.json()['result']
+
+
+def test_hamming_distance():
+    binary_collection_setup(collection_name, "Hamming")
+
+    result = search([1, 0, 1, 1, 0, 0, 0, 0, 1, 0])
+    assert [point['id'] for point in result] == [1, 2, 4, 3]
+    assert [point['score'] for point in result] == [1.0, 2.0, 6.0, 9.0]
+
+    # Vectors are returned unpacked
+    assert result[0]['vector'] == [1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0]
+
+
+def test_jaccard_distance():
+    binary_collection_setup(collection_name, "Jaccard")
+
+    result = search([1, 0, 1, 1, 0, 0, 0, 0, 1, 1], limit=3)
+    assert [point['id'] for point in result] == [1, 2, 4]
+    assert abs(result[0]['score'] - 0.0) < 0.0001
+    assert abs(result[1]['score'] - 0.2) < 0.0001
+    assert abs(result[2]['score'] - 0.5) < 0.0001
+
+
+@pytest.mark.parametrize("params", [
+    {"on_disk": True},
+    {"quantization_config": {"scalar": {"type": "int8"}}},
+])
+def test_unsupported_binary_params(params):
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 10,
+                "distance": "Hamming",
+                **params,
+            }
+        }
+    )
+    assert response.status_code == 400
@@ -0,0 +1,85 @@
+import pytest
+
+from .helpers.collection_setup import geo_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_formula'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    geo_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def search(formula, limit=3):
+    return request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "limit": limit,
+            "formula": formula,
+        }
+    )
+
+
+def test_formula_payload_value():
+    response = search({"expression": "value", "candidates": 6})
+    assert response.ok
+
+    result = response.json()['result']
+    assert [point['id'] for point in result] == [6, 5, 4]
+    assert [point['score'] for point in result] == [6.0, 5.0, 4.0]
+
+
+def test_formula_filter_boost():
+    response = search({
+        "expression": {
+            "sum": [
+                "$score",
+                {"mult": [10, {"filter": {"must": [{"key": "value", "range": {"gte": 5}}]}}]},
+            ]
+        },
+        "candidates": 6,
+    }, limit=6)
+    assert response.ok
+
+    result = response.json()['result']
+    assert len(result) == 6
+    assert {point['id'] for point in result[:2]} == {5, 6}
+    assert all(point['score'] > 10 for point in result[:2])
+    assert all(point['score'] < 10 for point in result[2:])
+
+
+def test_formula_geo_distance_with_defaults():
+    origin = {"lon": 50.5200, "lat": 50.4050}
+    formula = {
+        "expression": {"neg": {"geo_distance": {"origin": origin, "to": "location"}}},
+        "candidates": 6,
+    }
+
+    # Point 6 has no location
+    response = search(formula, limit=2)
+    assert response.status_code == 400
+
+    response = search({**formula, "defaults": {"location": origin}}, limit=2)
+    assert response.ok
+
+    result = response.json()['result']
+    assert {point['id'] for point in result} == {1, 6}
+    assert all(point['score'] == 0.0 for point in result)
+
+
+def test_formula_decay():
+    response = search({
+        "expression": {"gauss_decay": {"x": "value", "target": 3, "scale": 1, "midpoint": 0.5}},
+        "candidates": 6,
+    }, limit=1)
+    assert response.ok
+
+    [point] = response.json()['result']
+    assert point['id'] == 3
+    assert point['score'] == 1.0
@@ -0,0 +1,67 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_optimizations'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def get_optimizations():
+    response = request_with_validation(
+        api='/collections/{collection_name}/optimizations',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    return response.json()['result']
+
+
+def update_optimizations(operation):
+    response = request_with_validation(
+        api='/collections/{collection_name}/optimizations',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=operation,
+    )
+    assert response.ok
+    return response.json()['result']
+
+
+def test_get_optimizations():
+    shards = get_optimizations()['shards']
+    assert len(shards) == 1
+    assert shards[0]['shard_id'] == 0
+    assert shards[0]['paused'] is False
+    assert shards[0]['running'] == []
+
+
+def test_pause_resume_optimizers():
+    assert update_optimizations({"pause": {}})
+    assert get_optimizations()['shards'][0]['paused'] is True
+
+    assert update_optimizations({"resume": {}})
+    assert get_optimizations()['shards'][0]['paused'] is False
+
+
+def test_force_optimizations():
+    # Collection has no deleted points, so there is nothing to vacuum
+    assert not update_optimizations({"vacuum": {}})
+
+    update_optimizations({"merge": {"max_segments": 1}})
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"exact": True},
+    )
+    assert response.ok
+    assert response.json()['result']['count'] == 8
+
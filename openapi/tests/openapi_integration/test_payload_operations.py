@@ -275,3 +275,184 @@ def test_payload_operations():
     )
     assert response.ok
     assert len(response.json()['result']['points']) == 0
+
+
+def test_nested_payload_operations():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {"metadata": {"author": {"name": "John", "age": 42}, "year": 2020}},
+            "points": [6]
+        }
+    )
+    assert response.ok
+
+    # set payload into nested object
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {"name": "Jane"},
+            "key": "metadata.author",
+            "points": [6]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 6},
+    )
+    assert response.ok
+    assert response.json()['result']['payload'] == {
+        "metadata": {"author": {"name": "Jane", "age": 42}, "year": 2020}
+    }
+
+    # delete nested key
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "keys": ["metadata.author.age"],
+            "points": [6]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 6},
+    )
+    assert response.ok
+    assert response.json()['result']['payload'] == {
+        "metadata": {"author": {"name": "Jane"}, "year": 2020}
+    }
+
+    # key is not allowed for overwrite
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {"name": "Jane"},
+            "key": "metadata.author",
+            "points": [6]
+        }
+    )
+    assert response.status_code == 400
+
+
+def test_atomic_payload_operations():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {"views": 10, "stats": {"rating": 1.5}, "tags": ["a", "b"]},
+            "points": [5]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload/increment',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "key": "views",
+            "value": 5,
+            "points": [5]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload/increment',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "key": "stats.likes",
+            "value": -1,
+            "initial": 100,
+            "points": [5]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload/array/append',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "key": "tags",
+            "values": ["b", "c"],
+            "points": [5]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload/array/add_to_set',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "key": "tags",
+            "values": ["c", "d"],
+            "points": [5]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload/array/remove',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "key": "tags",
+            "values": ["b"],
+            "points": [5]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 5},
+    )
+    assert response.ok
+    assert response.json()['result']['payload'] == {
+        "views": 15,
+        "stats": {"rating": 1.5, "likes": 99},
+        "tags": ["a", "c", "d"],
+    }
+
+    # can't increment non-numeric value
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload/increment',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "key": "tags",
+            "value": 1,
+            "points": [5]
+        }
+    )
+    assert response.status_code == 400
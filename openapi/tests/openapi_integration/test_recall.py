@@ -0,0 +1,55 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_recall'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def estimate_recall(request):
+    response = request_with_validation(
+        api='/collections/{collection_name}/recall',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=request,
+    )
+    assert response.ok
+    return response.json()['result']
+
+
+def test_estimate_recall():
+    report = estimate_recall({"sample": 5, "limit": 3})
+    assert report['sample'] == 5
+
+    # Vectors are not quantized, so only the non-quantized search is measured
+    [estimate] = report['estimates']
+    assert estimate['vector'] == ""
+    assert estimate['quantization'] == "disabled"
+    assert estimate['queries'] == 5
+    # Segments are too small to be indexed, approximate search is exact
+    assert estimate['recall'] == 1.0
+
+    [shard] = report['shards']
+    assert shard['shard_id'] == 0
+    assert shard['estimates'] == report['estimates']
+    assert sum(segment['points_count'] for segment in shard['segments']) == 8
+
+
+def test_estimate_recall_with_filter():
+    report = estimate_recall({
+        "sample": 100,
+        "filter": {"must": [{"key": "city", "match": {"value": "Berlin"}}]},
+        "params": {"hnsw_ef": 32},
+    })
+    # Only points matching the filter are sampled
+    assert report['sample'] == 3
+    assert report['estimates'][0]['queries'] == 3
+    assert report['estimates'][0]['recall'] == 1.0
+
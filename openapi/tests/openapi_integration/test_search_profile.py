@@ -0,0 +1,108 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_search_profile'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_search_without_profile():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "limit": 3
+        }
+    )
+    assert response.ok
+    assert len(response.json()['result']) == 3
+    assert 'profile' not in response.json()
+
+
+def test_search_with_profile():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "limit": 3,
+            "profile": True
+        }
+    )
+    assert response.ok
+    assert len(response.json()['result']) == 3
+
+    shards = response.json()['profile']['shards']
+    assert len(shards) == 1
+    assert shards[0]['shard_id'] == 0
+
+    segments = shards[0]['segments']
+    assert len(segments) > 0
+
+    scored_vectors = 0
+    for segment in segments:
+        assert segment['queries'] == 1
+        assert segment['vector_name'] == ""
+        assert segment['search']['strategy'] == "plain"
+        scored_vectors += segment['search']['scored_vectors']
+
+    # Collection is small, so all points are scored without index
+    assert scored_vectors == 8
+
+
+def test_search_with_profile_filtered():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "limit": 3,
+            "filter": {
+                "must": [
+                    {
+                        "key": "city",
+                        "match": {
+                            "value": "London"
+                        }
+                    }
+                ]
+            },
+            "profile": True
+        }
+    )
+    assert response.ok
+
+    segments = response.json()['profile']['shards'][0]['segments']
+    for segment in segments:
+        assert segment['search']['strategy'] == "payload_index"
+
+
+def test_recommend_with_profile():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/recommend',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "positive": [1, 2],
+            "limit": 3,
+            "profile": True
+        }
+    )
+    assert response.ok
+    assert len(response.json()['result']) == 3
+
+    segments = response.json()['profile']['shards'][0]['segments']
+    assert len(segments) > 0
+    for segment in segments:
+        assert segment['queries'] == 1
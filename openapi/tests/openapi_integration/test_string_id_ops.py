@@ -0,0 +1,82 @@
+import pytest
+
+from .helpers.helpers import request_with_validation
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+
+collection_name = 'test_collection_string_id'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_string_id_operations():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {
+                    "id": "doc-1",
+                    "vector": [0.15, 0.21, 0.96, 0.04],
+                    "payload": {"city": "Berlin"}
+                },
+                {
+                    "id": "doc-2",
+                    "vector": [0.19, 0.81, 0.75, 0.11],
+                    "payload": {"city": ["Berlin", "London"]}
+                }
+            ]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': "doc-1"},
+    )
+    assert response.ok
+    assert response.json()['result']['id'] == "doc-1"
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"offset": "doc-2", "limit": 10}
+    )
+    assert response.ok
+    ids = [point['id'] for point in response.json()['result']['points']]
+    assert "doc-2" in ids
+    assert "doc-1" not in ids
+
+
+def test_numeric_string_id_is_distinct():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": "42", "vector": [0.15, 0.21, 0.96, 0.04], "payload": {"kind": "string"}},
+                {"id": 42, "vector": [0.19, 0.81, 0.75, 0.11], "payload": {"kind": "number"}},
+            ]
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"ids": ["42", 42], "with_payload": True}
+    )
+    assert response.ok
+    kinds = {point['id']: point['payload']['kind'] for point in response.json()['result']}
+    assert kinds == {"42": "string", 42: "number"}
@@ -68,9 +68,75 @@ async fn remove_peer(
     process_response(response, timing)
 }
 
+#[derive(Debug, Deserialize, Validate)]
+struct DrainParams {
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    timeout: Option<u64>,
+}
+
+async fn propose_drain(
+    dispatcher: &Dispatcher,
+    peer_id: u64,
+    operation: ConsensusOperations,
+    timeout: Option<u64>,
+) -> Result<bool, StorageError> {
+    let Some(consensus_state) = dispatcher.consensus_state() else {
+        return Err(StorageError::BadRequest {
+            description: "Distributed mode disabled.".to_string(),
+        });
+    };
+    if !consensus_state.peer_address_by_id().contains_key(&peer_id) {
+        return Err(StorageError::NotFound {
+            description: format!("Peer {peer_id} does not exist"),
+        });
+    }
+    consensus_state
+        .propose_consensus_op_with_await(operation, timeout.map(std::time::Duration::from_secs))
+        .await
+}
+
+#[post("/cluster/peer/{peer_id}/drain")]
+async fn drain_peer(
+    dispatcher: web::Data<Dispatcher>,
+    peer_id: web::Path<u64>,
+    Query(params): Query<DrainParams>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let peer_id = peer_id.into_inner();
+    let response = propose_drain(
+        &dispatcher,
+        peer_id,
+        ConsensusOperations::DrainPeer(peer_id),
+        params.timeout,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[delete("/cluster/peer/{peer_id}/drain")]
+async fn cancel_drain_peer(
+    dispatcher: web::Data<Dispatcher>,
+    peer_id: web::Path<u64>,
+    Query(params): Query<DrainParams>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let peer_id = peer_id.into_inner();
+    let response = propose_drain(
+        &dispatcher,
+        peer_id,
+        ConsensusOperations::CancelDrainPeer(peer_id),
+        params.timeout,
+    )
+    .await;
+    process_response(response, timing)
+}
+
 // Configure services
 pub fn config_cluster_api(cfg: &mut web::ServiceConfig) {
     cfg.service(cluster_status)
         .service(remove_peer)
+        .service(drain_peer)
+        .service(cancel_drain_peer)
         .service(recover_current_peer);
 }
@@ -4,6 +4,9 @@ use actix_web::rt::time::Instant;
 use actix_web::{delete, get, patch, post, put, web, Responder};
 use actix_web_validator::{Json, Path, Query};
 use collection::operations::cluster_ops::ClusterOperations;
+use collection::operations::consistency_ops::ConsistencyCheckRequest;
+use collection::operations::optimization_ops::OptimizationOperations;
+use collection::operations::recall_ops::RecallEstimationRequest;
 use serde::Deserialize;
 use storage::content_manager::collection_meta_ops::{
     ChangeAliasesOperation, CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
@@ -170,6 +173,60 @@ async fn update_collection_cluster(
     process_response(response, timing)
 }
 
+#[get("/collections/{name}/optimizations")]
+async fn get_collection_optimizations(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_get_collection_optimizations(toc.get_ref(), &collection.name).await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/optimizations")]
+async fn update_collection_optimizations(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    operation: Json<OptimizationOperations>,
+    Query(query): Query<WaitTimeout>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let wait_timeout = query.timeout();
+    let response = do_update_collection_optimizations(
+        dispatcher.get_ref(),
+        collection.name.clone(),
+        operation.into_inner(),
+        wait_timeout,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/recall")]
+async fn estimate_collection_recall(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<RecallEstimationRequest>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response =
+        do_estimate_collection_recall(toc.get_ref(), &collection.name, request.into_inner()).await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/cluster/consistency")]
+async fn check_collection_consistency(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    request: Json<ConsistencyCheckRequest>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response =
+        do_check_collection_consistency(toc.get_ref(), &collection.name, request.into_inner())
+            .await;
+    process_response(response, timing)
+}
+
 // Configure services
 pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
     cfg.service(get_collections)
@@ -181,7 +238,11 @@ pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
         .service(get_collection_aliases)
         .service(update_aliases)
         .service(get_cluster_info)
-        .service(update_collection_cluster);
+        .service(update_collection_cluster)
+        .service(check_collection_consistency)
+        .service(get_collection_optimizations)
+        .service(update_collection_optimizations)
+        .service(estimate_collection_recall);
 }
 
 #[cfg(test)]
@@ -1,13 +1,31 @@
-use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::consistency_params::{ConsistencyToken, ReadConsistency};
 use schemars::JsonSchema;
 use serde::Deserialize;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
 use validator::Validate;
 
-#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Deserialize, JsonSchema, Validate)]
+use crate::common::points::do_wait_for_consistency_token;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, JsonSchema, Validate)]
 pub struct ReadParams {
     #[serde(default, deserialize_with = "deserialize_read_consistency")]
     #[validate]
     pub consistency: Option<ReadConsistency>,
+    /// Token from the update result, reads observe the update if it is set
+    #[serde(default)]
+    pub consistency_token: Option<ConsistencyToken>,
+}
+
+impl ReadParams {
+    /// Wait until the replicas apply the updates of the consistency token, if it is set
+    pub async fn wait_for_consistency_token(
+        &self,
+        toc: &TableOfContent,
+        collection_name: &str,
+    ) -> Result<(), StorageError> {
+        do_wait_for_consistency_token(toc, collection_name, self.consistency_token.as_ref()).await
+    }
 }
 
 fn deserialize_read_consistency<'de, D>(
@@ -67,6 +85,18 @@ mod test {
         assert!(try_deserialize(&str("0")).is_err());
     }
 
+    #[test]
+    fn deserialize_consistency_token() {
+        let params = deserialize("consistency=2&consistency_token=0%3A1%3A10%2C0%3A2%3A12");
+        assert_eq!(params.consistency, Some(ReadConsistency::Factor(2)));
+        assert_eq!(
+            params.consistency_token.unwrap().to_string(),
+            "0:1:10,0:2:12"
+        );
+
+        assert!(try_deserialize("consistency_token=0%3A1").is_err());
+    }
+
     fn test(value: &str, params: ReadParams) {
         test_str(&str(value), params);
     }
@@ -90,12 +120,14 @@ mod test {
     fn from_type(r#type: ReadConsistencyType) -> ReadParams {
         ReadParams {
             consistency: Some(ReadConsistency::Type(r#type)),
+            consistency_token: None,
         }
     }
 
     fn from_factor(factor: usize) -> ReadParams {
         ReadParams {
             consistency: Some(ReadConsistency::Factor(factor)),
+            consistency_token: None,
         }
     }
 }
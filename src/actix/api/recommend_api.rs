@@ -11,7 +11,7 @@ use storage::content_manager::toc::TableOfContent;
 
 use super::read_params::ReadParams;
 use super::CollectionPath;
-use crate::actix::helpers::process_response;
+use crate::actix::helpers::{process_response, process_response_with_profile};
 
 async fn do_recommend_points(
     toc: &TableOfContent,
@@ -32,13 +32,25 @@ async fn recommend_points(
 ) -> impl Responder {
     let timing = Instant::now();
 
-    let response = do_recommend_points(
-        toc.get_ref(),
-        &collection.name,
-        request.into_inner(),
-        params.consistency,
-    )
-    .await;
+    if let Err(err) = params
+        .wait_for_consistency_token(toc.get_ref(), &collection.name)
+        .await
+    {
+        return process_response::<()>(Err(err), timing);
+    }
+
+    let request = request.into_inner();
+
+    if request.profile.unwrap_or(false) {
+        let response = toc
+            .recommend_with_profile(&collection.name, request, params.consistency)
+            .await;
+
+        return process_response_with_profile(response, timing);
+    }
+
+    let response =
+        do_recommend_points(toc.get_ref(), &collection.name, request, params.consistency).await;
 
     process_response(response, timing)
 }
@@ -62,6 +74,13 @@ async fn recommend_batch_points(
 ) -> impl Responder {
     let timing = Instant::now();
 
+    if let Err(err) = params
+        .wait_for_consistency_token(toc.get_ref(), &collection.name)
+        .await
+    {
+        return process_response::<()>(Err(err), timing);
+    }
+
     let response = do_recommend_batch_points(
         toc.get_ref(),
         &collection.name,
@@ -82,6 +101,13 @@ async fn recommend_point_groups(
 ) -> impl Responder {
     let timing = Instant::now();
 
+    if let Err(err) = params
+        .wait_for_consistency_token(toc.get_ref(), &collection.name)
+        .await
+    {
+        return process_response::<()>(Err(err), timing);
+    }
+
     let response = crate::common::points::do_recommend_point_groups(
         toc.get_ref(),
         &collection.name,
@@ -57,6 +57,13 @@ async fn get_point(
 ) -> impl Responder {
     let timing = Instant::now();
 
+    if let Err(err) = params
+        .wait_for_consistency_token(toc.get_ref(), &collection.name)
+        .await
+    {
+        return process_response::<()>(Err(err), timing);
+    }
+
     let point_id: PointIdType = {
         let parse_res = point.id.parse();
         match parse_res {
@@ -99,6 +106,13 @@ async fn get_points(
 ) -> impl Responder {
     let timing = Instant::now();
 
+    if let Err(err) = params
+        .wait_for_consistency_token(toc.get_ref(), &collection.name)
+        .await
+    {
+        return process_response::<()>(Err(err), timing);
+    }
+
     let response = do_get_points(
         toc.get_ref(),
         &collection.name,
@@ -119,6 +133,13 @@ async fn scroll_points(
 ) -> impl Responder {
     let timing = Instant::now();
 
+    if let Err(err) = params
+        .wait_for_consistency_token(toc.get_ref(), &collection.name)
+        .await
+    {
+        return process_response::<()>(Err(err), timing);
+    }
+
     let response = scroll_get_points(
         toc.get_ref(),
         &collection.name,
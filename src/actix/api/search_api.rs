@@ -6,8 +6,10 @@ use storage::content_manager::toc::TableOfContent;
 
 use super::read_params::ReadParams;
 use super::CollectionPath;
-use crate::actix::helpers::process_response;
-use crate::common::points::{do_search_batch_points, do_search_point_groups, do_search_points};
+use crate::actix::helpers::{process_response, process_response_with_profile};
+use crate::common::points::{
+    do_search_batch_points, do_search_point_groups, do_search_points, do_search_points_with_profile,
+};
 
 #[post("/collections/{name}/points/search")]
 async fn search_points(
@@ -18,10 +20,32 @@ async fn search_points(
 ) -> impl Responder {
     let timing = Instant::now();
 
+    if let Err(err) = params
+        .wait_for_consistency_token(toc.get_ref(), &collection.name)
+        .await
+    {
+        return process_response::<()>(Err(err), timing);
+    }
+
+    let request = request.into_inner();
+
+    if request.profile.unwrap_or(false) {
+        let response = do_search_points_with_profile(
+            toc.get_ref(),
+            &collection.name,
+            request,
+            params.consistency,
+            None,
+        )
+        .await;
+
+        return process_response_with_profile(response, timing);
+    }
+
     let response = do_search_points(
         toc.get_ref(),
         &collection.name,
-        request.into_inner(),
+        request,
         params.consistency,
         None,
     )
@@ -39,6 +63,13 @@ async fn batch_search_points(
 ) -> impl Responder {
     let timing = Instant::now();
 
+    if let Err(err) = params
+        .wait_for_consistency_token(toc.get_ref(), &collection.name)
+        .await
+    {
+        return process_response::<()>(Err(err), timing);
+    }
+
     let response = do_search_batch_points(
         toc.get_ref(),
         &collection.name,
@@ -60,6 +91,13 @@ async fn search_point_groups(
 ) -> impl Responder {
     let timing = Instant::now();
 
+    if let Err(err) = params
+        .wait_for_consistency_token(toc.get_ref(), &collection.name)
+        .await
+    {
+        return process_response::<()>(Err(err), timing);
+    }
+
     let response = do_search_point_groups(
         toc.get_ref(),
         &collection.name,
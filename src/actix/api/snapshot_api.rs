@@ -10,7 +10,8 @@ use actix_web_validator as valid;
 use collection::collection::Collection;
 use collection::common::file_utils::move_file;
 use collection::operations::snapshot_ops::{
-    ShardSnapshotLocation, ShardSnapshotRecover, SnapshotPriority, SnapshotRecover,
+    remove_snapshot, verify_snapshot_checksum, ClusterSnapshotRecover, ShardSnapshotLocation,
+    ShardSnapshotRecover, SnapshotPriority, SnapshotRecover,
 };
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::ShardId;
@@ -18,6 +19,9 @@ use reqwest::Url;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use storage::content_manager::errors::StorageError;
+use storage::content_manager::snapshots::cluster::{
+    do_create_cluster_snapshot, do_recover_from_cluster_snapshot,
+};
 use storage::content_manager::snapshots::recover::{activate_shard, do_recover_from_snapshot};
 use storage::content_manager::snapshots::{
     self, do_create_full_snapshot, do_delete_collection_snapshot, do_delete_full_snapshot,
@@ -47,6 +51,10 @@ struct SnapshotPath {
 pub struct SnapshotUploadingParam {
     pub wait: Option<bool>,
     pub priority: Option<SnapshotPriority>,
+
+    /// Optional SHA256 checksum to verify snapshot integrity before recovery.
+    #[serde(default)]
+    pub checksum: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
@@ -54,6 +62,15 @@ pub struct SnapshottingParam {
     pub wait: Option<bool>,
 }
 
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct CollectionSnapshottingParam {
+    pub wait: Option<bool>,
+
+    /// Name of an existing snapshot of the collection to create an incremental snapshot from.
+    #[serde(default)]
+    pub base: Option<String>,
+}
+
 #[derive(MultipartForm)]
 pub struct SnapshottingForm {
     snapshot: TempFile,
@@ -133,13 +150,15 @@ async fn list_snapshots(toc: web::Data<TableOfContent>, path: web::Path<String>)
 async fn create_snapshot(
     dispatcher: web::Data<Dispatcher>,
     path: web::Path<String>,
-    params: valid::Query<SnapshottingParam>,
+    params: valid::Query<CollectionSnapshottingParam>,
 ) -> impl Responder {
     let collection_name = path.into_inner();
+    let params = params.into_inner();
     let wait = params.wait.unwrap_or(true);
 
     let timing = Instant::now();
-    let response = do_create_snapshot(dispatcher.get_ref(), &collection_name, wait).await;
+    let response =
+        do_create_snapshot(dispatcher.get_ref(), &collection_name, params.base, wait).await;
     match response {
         Err(_) => process_response(response, timing),
         Ok(_) if wait => process_response(response, timing),
@@ -158,6 +177,13 @@ async fn upload_snapshot(
     let snapshot = form.snapshot;
     let wait = params.wait.unwrap_or(true);
 
+    // Refuse corrupted uploads before they are stored
+    if let Some(checksum) = &params.checksum {
+        if let Err(err) = verify_snapshot_checksum(snapshot.file.path(), checksum).await {
+            return process_response::<()>(Err(err.into()), timing);
+        }
+    }
+
     let snapshot_location =
         match do_save_uploaded_snapshot(dispatcher.get_ref(), &collection.name, snapshot).await {
             Ok(location) => location,
@@ -167,6 +193,9 @@ async fn upload_snapshot(
     let snapshot_recover = SnapshotRecover {
         location: snapshot_location,
         priority: params.priority,
+        // Already verified
+        checksum: None,
+        wal_replay: None,
     };
 
     let response = do_recover_from_snapshot(
@@ -208,6 +237,47 @@ async fn recover_from_snapshot(
     }
 }
 
+#[post("/collections/{name}/snapshots/cluster")]
+async fn create_cluster_snapshot(
+    dispatcher: web::Data<Dispatcher>,
+    collection: valid::Path<CollectionPath>,
+    params: valid::Query<SnapshottingParam>,
+) -> impl Responder {
+    let wait = params.wait.unwrap_or(true);
+
+    let timing = Instant::now();
+    let response = do_create_cluster_snapshot(dispatcher.get_ref(), &collection.name, wait).await;
+    match response {
+        Err(_) => process_response(response, timing),
+        Ok(_) if wait => process_response(response, timing),
+        Ok(_) => accepted_response(timing),
+    }
+}
+
+#[put("/collections/{name}/snapshots/cluster/recover")]
+async fn recover_from_cluster_snapshot(
+    dispatcher: web::Data<Dispatcher>,
+    collection: valid::Path<CollectionPath>,
+    request: valid::Json<ClusterSnapshotRecover>,
+    params: valid::Query<SnapshottingParam>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let wait = params.wait.unwrap_or(true);
+
+    let response = do_recover_from_cluster_snapshot(
+        dispatcher.get_ref(),
+        &collection.name,
+        request.into_inner(),
+        wait,
+    )
+    .await;
+    match response {
+        Err(_) => process_response(response, timing),
+        Ok(_) if wait => process_response(response, timing),
+        Ok(_) => accepted_response(timing),
+    }
+}
+
 #[get("/collections/{name}/snapshots/{snapshot_name}")]
 async fn get_snapshot(
     toc: web::Data<TableOfContent>,
@@ -352,6 +422,10 @@ async fn recover_shard_snapshot(
             }
         };
 
+        if let Some(checksum) = &request.checksum {
+            verify_snapshot_checksum(&snapshot_path, checksum).await?;
+        }
+
         recover_shard_snapshot_impl(
             &toc,
             &collection,
@@ -375,13 +449,21 @@ async fn upload_shard_snapshot(
     query: web::Query<SnapshotUploadingParam>,
     MultipartForm(form): MultipartForm<SnapshottingForm>,
 ) -> impl Responder {
-    let SnapshotUploadingParam { wait, priority } = query.into_inner();
+    let SnapshotUploadingParam {
+        wait,
+        priority,
+        checksum,
+    } = query.into_inner();
 
     let future = async move {
         let (collection, shard) = path.into_inner();
         let collection = toc.get_collection(&collection).await?;
         collection.assert_shard_exists(shard).await?;
 
+        if let Some(checksum) = &checksum {
+            verify_snapshot_checksum(form.snapshot.file.path(), checksum).await?;
+        }
+
         recover_shard_snapshot_impl(
             &toc,
             &collection,
@@ -421,7 +503,7 @@ async fn delete_shard_snapshot(
         let snapshot_path = collection.get_shard_snapshot_path(shard, &snapshot).await?;
 
         check_shard_snapshot_file_exists(&snapshot_path)?;
-        std::fs::remove_file(&snapshot_path)?;
+        remove_snapshot(&snapshot_path).await?;
 
         Ok(true)
     };
@@ -523,6 +605,8 @@ pub fn config_snapshots_api(cfg: &mut web::ServiceConfig) {
         .service(create_snapshot)
         .service(upload_snapshot)
         .service(recover_from_snapshot)
+        .service(create_cluster_snapshot)
+        .service(recover_from_cluster_snapshot)
         .service(get_snapshot)
         .service(list_full_snapshots)
         .service(create_full_snapshot)
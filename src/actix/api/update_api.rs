@@ -1,7 +1,9 @@
 use actix_web::rt::time::Instant;
 use actix_web::{delete, post, put, web, Responder};
 use actix_web_validator::{Json, Path, Query};
-use collection::operations::payload_ops::{DeletePayload, SetPayload};
+use collection::operations::payload_ops::{
+    DeletePayload, IncrementPayload, SetPayload, UpdatePayloadArray,
+};
 use collection::operations::point_ops::{PointInsertOperations, PointsSelector, WriteOrdering};
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::JsonSchema;
@@ -12,9 +14,10 @@ use validator::Validate;
 use super::CollectionPath;
 use crate::actix::helpers::process_response;
 use crate::common::points::{
+    do_add_to_set_payload, do_array_append_payload, do_array_remove_payload,
     do_batch_update_points, do_clear_payload, do_create_index, do_delete_index, do_delete_payload,
-    do_delete_points, do_delete_vectors, do_overwrite_payload, do_set_payload, do_update_vectors,
-    do_upsert_points, CreateFieldIndex, UpdateOperations,
+    do_delete_points, do_delete_vectors, do_increment_payload, do_overwrite_payload,
+    do_set_payload, do_update_vectors, do_upsert_points, CreateFieldIndex, UpdateOperations,
 };
 
 #[derive(Deserialize, Validate)]
@@ -222,6 +225,102 @@ async fn clear_payload(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/points/payload/increment")]
+async fn increment_payload(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    operation: Json<IncrementPayload>,
+    params: Query<UpdateParam>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let operation = operation.into_inner();
+    let wait = params.wait.unwrap_or(false);
+    let ordering = params.ordering.unwrap_or_default();
+
+    let response = do_increment_payload(
+        toc.get_ref(),
+        &collection.name,
+        operation,
+        None,
+        wait,
+        ordering,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/payload/array/append")]
+async fn array_append_payload(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    operation: Json<UpdatePayloadArray>,
+    params: Query<UpdateParam>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let operation = operation.into_inner();
+    let wait = params.wait.unwrap_or(false);
+    let ordering = params.ordering.unwrap_or_default();
+
+    let response = do_array_append_payload(
+        toc.get_ref(),
+        &collection.name,
+        operation,
+        None,
+        wait,
+        ordering,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/payload/array/remove")]
+async fn array_remove_payload(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    operation: Json<UpdatePayloadArray>,
+    params: Query<UpdateParam>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let operation = operation.into_inner();
+    let wait = params.wait.unwrap_or(false);
+    let ordering = params.ordering.unwrap_or_default();
+
+    let response = do_array_remove_payload(
+        toc.get_ref(),
+        &collection.name,
+        operation,
+        None,
+        wait,
+        ordering,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/payload/array/add_to_set")]
+async fn add_to_set_payload(
+    toc: web::Data<TableOfContent>,
+    collection: Path<CollectionPath>,
+    operation: Json<UpdatePayloadArray>,
+    params: Query<UpdateParam>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let operation = operation.into_inner();
+    let wait = params.wait.unwrap_or(false);
+    let ordering = params.ordering.unwrap_or_default();
+
+    let response = do_add_to_set_payload(
+        toc.get_ref(),
+        &collection.name,
+        operation,
+        None,
+        wait,
+        ordering,
+    )
+    .await;
+    process_response(response, timing)
+}
+
 #[post("/collections/{name}/points/batch")]
 async fn update_batch(
     toc: web::Data<TableOfContent>,
@@ -302,6 +401,10 @@ pub fn config_update_api(cfg: &mut web::ServiceConfig) {
         .service(overwrite_payload)
         .service(delete_payload)
         .service(clear_payload)
+        .service(increment_payload)
+        .service(array_append_payload)
+        .service(array_remove_payload)
+        .service(add_to_set_payload)
         .service(create_field_index)
         .service(delete_field_index)
         .service(update_batch);
@@ -73,6 +73,34 @@ where
     }
 }
 
+#[derive(Serialize)]
+struct ApiResponseWithProfile<D, P> {
+    result: D,
+    status: ApiStatus,
+    time: f64,
+    profile: P,
+}
+
+/// Same as [`process_response`], but attaches the search profile to a successful response
+pub fn process_response_with_profile<D, P>(
+    response: Result<(D, P), StorageError>,
+    timing: Instant,
+) -> HttpResponse
+where
+    D: Serialize,
+    P: Serialize,
+{
+    match response {
+        Ok((res, profile)) => HttpResponse::Ok().json(ApiResponseWithProfile {
+            result: res,
+            status: ApiStatus::Ok,
+            time: timing.elapsed().as_secs_f64(),
+            profile,
+        }),
+        Err(err) => process_response::<()>(Err(err), timing),
+    }
+}
+
 pub async fn time<T, Fut>(future: Fut) -> impl actix_web::Responder
 where
     Fut: Future<Output = HttpResult<T>>,
@@ -1,21 +1,27 @@
+use std::collections::{HashMap, HashSet};
 use std::time::Duration;
 
 use api::grpc::models::{CollectionDescription, CollectionsResponse};
 use collection::operations::cluster_ops::{
-    AbortTransferOperation, ClusterOperations, DropReplicaOperation, MoveShardOperation,
-    ReplicateShardOperation,
+    AbortReshardingOperation, AbortTransferOperation, ClusterOperations, DropReplicaOperation,
+    MoveShardOperation, ReplicateShardOperation, StartReshardingOperation,
 };
+use collection::operations::consistency_ops::{ConsistencyCheckRequest, ConsistencyReport};
+use collection::operations::optimization_ops::OptimizationOperations;
+use collection::operations::recall_ops::{RecallEstimationReport, RecallEstimationRequest};
 use collection::operations::snapshot_ops::SnapshotDescription;
 use collection::operations::types::{
-    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionsAliasesResponse,
+    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionOptimizations,
+    CollectionsAliasesResponse,
 };
 use collection::shards::replica_set;
+use collection::shards::resharding::{self, ReshardingState};
 use collection::shards::shard::ShardId;
 use collection::shards::transfer::shard_transfer::{ShardTransfer, ShardTransferKey};
 use itertools::Itertools;
 use storage::content_manager::collection_meta_ops::ShardTransferOperations::{Abort, Start};
 use storage::content_manager::collection_meta_ops::{
-    CollectionMetaOperations, UpdateCollectionOperation,
+    CollectionMetaOperations, ReshardingOperations, SetOptimizersPaused, UpdateCollectionOperation,
 };
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
@@ -76,11 +82,16 @@ pub async fn do_list_snapshots(
 pub async fn do_create_snapshot(
     dispatcher: &Dispatcher,
     collection_name: &str,
+    base: Option<String>,
     wait: bool,
 ) -> Result<SnapshotDescription, StorageError> {
     let collection = collection_name.to_string();
     let dispatcher = dispatcher.clone();
-    let snapshot = tokio::spawn(async move { dispatcher.create_snapshot(&collection).await });
+    let snapshot = tokio::spawn(async move {
+        dispatcher
+            .create_snapshot(&collection, base.as_deref())
+            .await
+    });
     if wait {
         Ok(snapshot.await??)
     } else {
@@ -88,6 +99,7 @@ pub async fn do_create_snapshot(
             name: "".to_string(),
             creation_time: None,
             size: 0,
+            checksum: None,
         })
     }
 }
@@ -253,5 +265,134 @@ pub async fn do_update_collection_cluster(
                 )
                 .await
         }
+        ClusterOperations::StartResharding(StartReshardingOperation { start_resharding }) => {
+            let state = collection.state().await;
+            let shards: HashMap<_, _> = state
+                .shards
+                .into_iter()
+                .map(|(shard_id, shard_info)| (shard_id, shard_info.replicas))
+                .collect();
+
+            let peer_id = match start_resharding.peer_id {
+                Some(peer_id) => {
+                    validate_peer_exists(peer_id)?;
+                    peer_id
+                }
+                None => {
+                    let all_peers: HashSet<_> = consensus_state
+                        .persistent
+                        .read()
+                        .peer_address_by_id
+                        .read()
+                        .keys()
+                        .copied()
+                        .collect();
+                    resharding::suggest_peer_for_new_shard(&all_peers, &shards).ok_or_else(
+                        || StorageError::BadRequest {
+                            description: "No peer available for the new shard".to_string(),
+                        },
+                    )?
+                }
+            };
+
+            let sources = resharding::suggest_migration_sources(&shards).ok_or_else(|| {
+                StorageError::BadRequest {
+                    description: format!(
+                        "Collection {collection_name} has shards without active replicas"
+                    ),
+                }
+            })?;
+
+            let resharding =
+                ReshardingState::new(state.config.params.shard_number.get(), peer_id, sources);
+
+            dispatcher
+                .submit_collection_meta_op(
+                    CollectionMetaOperations::Resharding(
+                        collection_name,
+                        ReshardingOperations::Start(resharding),
+                    ),
+                    wait_timeout,
+                )
+                .await
+        }
+        ClusterOperations::AbortResharding(AbortReshardingOperation {
+            abort_resharding: _,
+        }) => {
+            if collection.resharding_state().await.is_none() {
+                return Err(StorageError::NotFound {
+                    description: format!("Collection {collection_name} is not being resharded"),
+                });
+            }
+
+            dispatcher
+                .submit_collection_meta_op(
+                    CollectionMetaOperations::Resharding(
+                        collection_name,
+                        ReshardingOperations::Abort {
+                            reason: "user request".to_string(),
+                        },
+                    ),
+                    wait_timeout,
+                )
+                .await
+        }
     }
 }
+
+pub async fn do_get_collection_optimizations(
+    toc: &TableOfContent,
+    name: &str,
+) -> Result<CollectionOptimizations, StorageError> {
+    let collection = toc.get_collection(name).await?;
+    Ok(collection.optimizations().await)
+}
+
+/// Apply optimization control operation on all peers
+///
+/// Pausing and resuming goes through consensus, so the paused flag is persisted on every peer.
+/// Merge and vacuum are one-off operations, they are sent to all replicas directly.
+pub async fn do_update_collection_optimizations(
+    dispatcher: &Dispatcher,
+    collection_name: String,
+    operation: OptimizationOperations,
+    wait_timeout: Option<Duration>,
+) -> Result<bool, StorageError> {
+    let collection = dispatcher.toc().get_collection(&collection_name).await?;
+    let paused = match operation {
+        OptimizationOperations::Pause(_) => true,
+        OptimizationOperations::Resume(_) => false,
+        OptimizationOperations::Merge(_) | OptimizationOperations::Vacuum(_) => {
+            return Ok(collection.force_optimization(&operation).await?);
+        }
+    };
+    // Don't hold the collection while waiting for consensus to apply the operation
+    drop(collection);
+    dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::SetOptimizersPaused(SetOptimizersPaused {
+                collection_name,
+                paused,
+            }),
+            wait_timeout,
+        )
+        .await
+}
+
+pub async fn do_estimate_collection_recall(
+    toc: &TableOfContent,
+    name: &str,
+    request: RecallEstimationRequest,
+) -> Result<RecallEstimationReport, StorageError> {
+    let collection = toc.get_collection(name).await?;
+    Ok(collection.estimate_recall(request).await?)
+}
+
+pub async fn do_check_collection_consistency(
+    toc: &TableOfContent,
+    name: &str,
+    request: ConsistencyCheckRequest,
+) -> Result<ConsistencyReport, StorageError> {
+    let collection = toc.get_collection(name).await?;
+    Ok(collection.check_consistency(request, false).await?)
+}
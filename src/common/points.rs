@@ -1,12 +1,16 @@
-use collection::operations::consistency_params::ReadConsistency;
-use collection::operations::payload_ops::{DeletePayload, PayloadOps, SetPayload};
+use collection::operations::consistency_params::{
+    ConsistencyToken, ReadConsistency, CONSISTENCY_TOKEN_TIMEOUT,
+};
+use collection::operations::payload_ops::{
+    DeletePayload, IncrementPayload, PayloadOps, SetPayload, UpdatePayloadArray,
+};
 use collection::operations::point_ops::{
     PointInsertOperations, PointOperations, PointsSelector, WriteOrdering,
 };
 use collection::operations::types::{
     CoreSearchRequestBatch, CountRequest, CountResult, GroupsResult, PointRequest,
     RecommendGroupsRequest, Record, ScrollRequest, ScrollResult, SearchGroupsRequest,
-    SearchRequest, SearchRequestBatch, UpdateResult,
+    SearchProfile, SearchRequest, SearchRequestBatch, UpdateResult,
 };
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors, VectorOperations};
 use collection::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
@@ -61,6 +65,30 @@ pub struct ClearPayloadOperation {
     clear_payload: PointsSelector,
 }
 
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct IncrementPayloadOperation {
+    #[validate]
+    increment_payload: IncrementPayload,
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct ArrayAppendOperation {
+    #[validate]
+    array_append: UpdatePayloadArray,
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct ArrayRemoveOperation {
+    #[validate]
+    array_remove: UpdatePayloadArray,
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct AddToSetOperation {
+    #[validate]
+    add_to_set: UpdatePayloadArray,
+}
+
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
 pub struct UpdateVectorsOperation {
     #[validate]
@@ -85,6 +113,10 @@ pub enum UpdateOperation {
     ClearPayload(ClearPayloadOperation),
     UpdateVectors(UpdateVectorsOperation),
     DeleteVectors(DeleteVectorsOperation),
+    IncrementPayload(IncrementPayloadOperation),
+    ArrayAppend(ArrayAppendOperation),
+    ArrayRemove(ArrayRemoveOperation),
+    AddToSet(AddToSetOperation),
 }
 
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
@@ -103,6 +135,10 @@ impl Validate for UpdateOperation {
             UpdateOperation::ClearPayload(op) => op.validate(),
             UpdateOperation::UpdateVectors(op) => op.validate(),
             UpdateOperation::DeleteVectors(op) => op.validate(),
+            UpdateOperation::IncrementPayload(op) => op.validate(),
+            UpdateOperation::ArrayAppend(op) => op.validate(),
+            UpdateOperation::ArrayRemove(op) => op.validate(),
+            UpdateOperation::AddToSet(op) => op.validate(),
         }
     }
 }
@@ -248,6 +284,11 @@ pub async fn do_overwrite_payload(
     wait: bool,
     ordering: WriteOrdering,
 ) -> Result<UpdateResult, StorageError> {
+    if operation.key.is_some() {
+        return Err(StorageError::BadRequest {
+            description: "Payload key is not supported for overwrite payload operation".to_string(),
+        });
+    }
     let collection_operation =
         CollectionUpdateOperations::PayloadOperation(PayloadOps::OverwritePayload(operation));
     toc.update(
@@ -280,6 +321,86 @@ pub async fn do_delete_payload(
     .await
 }
 
+pub async fn do_increment_payload(
+    toc: &TableOfContent,
+    collection_name: &str,
+    operation: IncrementPayload,
+    shard_selection: Option<ShardId>,
+    wait: bool,
+    ordering: WriteOrdering,
+) -> Result<UpdateResult, StorageError> {
+    let collection_operation =
+        CollectionUpdateOperations::PayloadOperation(PayloadOps::Increment(operation));
+    toc.update(
+        collection_name,
+        collection_operation,
+        shard_selection,
+        wait,
+        ordering,
+    )
+    .await
+}
+
+pub async fn do_array_append_payload(
+    toc: &TableOfContent,
+    collection_name: &str,
+    operation: UpdatePayloadArray,
+    shard_selection: Option<ShardId>,
+    wait: bool,
+    ordering: WriteOrdering,
+) -> Result<UpdateResult, StorageError> {
+    let collection_operation =
+        CollectionUpdateOperations::PayloadOperation(PayloadOps::ArrayAppend(operation));
+    toc.update(
+        collection_name,
+        collection_operation,
+        shard_selection,
+        wait,
+        ordering,
+    )
+    .await
+}
+
+pub async fn do_array_remove_payload(
+    toc: &TableOfContent,
+    collection_name: &str,
+    operation: UpdatePayloadArray,
+    shard_selection: Option<ShardId>,
+    wait: bool,
+    ordering: WriteOrdering,
+) -> Result<UpdateResult, StorageError> {
+    let collection_operation =
+        CollectionUpdateOperations::PayloadOperation(PayloadOps::ArrayRemove(operation));
+    toc.update(
+        collection_name,
+        collection_operation,
+        shard_selection,
+        wait,
+        ordering,
+    )
+    .await
+}
+
+pub async fn do_add_to_set_payload(
+    toc: &TableOfContent,
+    collection_name: &str,
+    operation: UpdatePayloadArray,
+    shard_selection: Option<ShardId>,
+    wait: bool,
+    ordering: WriteOrdering,
+) -> Result<UpdateResult, StorageError> {
+    let collection_operation =
+        CollectionUpdateOperations::PayloadOperation(PayloadOps::AddToSet(operation));
+    toc.update(
+        collection_name,
+        collection_operation,
+        shard_selection,
+        wait,
+        ordering,
+    )
+    .await
+}
+
 pub async fn do_clear_payload(
     toc: &TableOfContent,
     collection_name: &str,
@@ -407,6 +528,50 @@ pub async fn do_batch_update_points(
                 )
                 .await
             }
+            UpdateOperation::IncrementPayload(operation) => {
+                do_increment_payload(
+                    toc,
+                    collection_name,
+                    operation.increment_payload,
+                    shard_selection,
+                    wait,
+                    ordering,
+                )
+                .await
+            }
+            UpdateOperation::ArrayAppend(operation) => {
+                do_array_append_payload(
+                    toc,
+                    collection_name,
+                    operation.array_append,
+                    shard_selection,
+                    wait,
+                    ordering,
+                )
+                .await
+            }
+            UpdateOperation::ArrayRemove(operation) => {
+                do_array_remove_payload(
+                    toc,
+                    collection_name,
+                    operation.array_remove,
+                    shard_selection,
+                    wait,
+                    ordering,
+                )
+                .await
+            }
+            UpdateOperation::AddToSet(operation) => {
+                do_add_to_set_payload(
+                    toc,
+                    collection_name,
+                    operation.add_to_set,
+                    shard_selection,
+                    wait,
+                    ordering,
+                )
+                .await
+            }
         }?;
         results.push(result);
     }
@@ -469,6 +634,17 @@ pub async fn do_search_points(
         .await
 }
 
+pub async fn do_search_points_with_profile(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: SearchRequest,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: Option<ShardId>,
+) -> Result<(Vec<ScoredPoint>, SearchProfile), StorageError> {
+    toc.search_with_profile(collection_name, request, read_consistency, shard_selection)
+        .await
+}
+
 pub async fn do_search_batch_points(
     toc: &TableOfContent,
     collection_name: &str,
@@ -547,3 +723,20 @@ pub async fn do_scroll_points(
     toc.scroll(collection_name, request, read_consistency, shard_selection)
         .await
 }
+
+/// Wait until the replicas apply the updates of the consistency token, if it is set
+pub async fn do_wait_for_consistency_token(
+    toc: &TableOfContent,
+    collection_name: &str,
+    consistency_token: Option<&ConsistencyToken>,
+) -> Result<(), StorageError> {
+    let Some(consistency_token) = consistency_token.filter(|token| !token.is_empty()) else {
+        return Ok(());
+    };
+
+    toc.get_collection(collection_name)
+        .await?
+        .wait_for_consistency_token(consistency_token, CONSISTENCY_TOKEN_TIMEOUT)
+        .await?;
+    Ok(())
+}
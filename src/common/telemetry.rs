@@ -4,6 +4,7 @@ use parking_lot::Mutex;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use serde::{Deserialize, Serialize};
+use storage::content_manager::snapshots::scheduler::SnapshotSchedulerStatusRef;
 use storage::dispatcher::Dispatcher;
 use uuid::Uuid;
 
@@ -13,12 +14,14 @@ use crate::common::telemetry_ops::collections_telemetry::CollectionsTelemetry;
 use crate::common::telemetry_ops::requests_telemetry::{
     ActixTelemetryCollector, RequestsTelemetry, TonicTelemetryCollector,
 };
+use crate::common::telemetry_ops::snapshots_telemetry::SnapshotsTelemetry;
 use crate::settings::Settings;
 
 pub struct TelemetryCollector {
     process_id: Uuid,
     settings: Settings,
     dispatcher: Arc<Dispatcher>,
+    snapshot_scheduler_status: SnapshotSchedulerStatusRef,
     pub app_telemetry_collector: AppBuildTelemetryCollector,
     pub actix_telemetry_collector: Arc<Mutex<ActixTelemetryCollector>>,
     pub tonic_telemetry_collector: Arc<Mutex<TonicTelemetryCollector>>,
@@ -32,6 +35,7 @@ pub struct TelemetryData {
     pub(crate) collections: CollectionsTelemetry,
     pub(crate) cluster: ClusterTelemetry,
     pub(crate) requests: RequestsTelemetry,
+    pub(crate) snapshots: SnapshotsTelemetry,
 }
 
 impl Anonymize for TelemetryData {
@@ -42,6 +46,7 @@ impl Anonymize for TelemetryData {
             collections: self.collections.anonymize(),
             cluster: self.cluster.anonymize(),
             requests: self.requests.anonymize(),
+            snapshots: self.snapshots.anonymize(),
         }
     }
 }
@@ -55,11 +60,17 @@ impl TelemetryCollector {
         Uuid::new_v4()
     }
 
-    pub fn new(settings: Settings, dispatcher: Arc<Dispatcher>, id: Uuid) -> Self {
+    pub fn new(
+        settings: Settings,
+        dispatcher: Arc<Dispatcher>,
+        snapshot_scheduler_status: SnapshotSchedulerStatusRef,
+        id: Uuid,
+    ) -> Self {
         Self {
             process_id: id,
             settings,
             dispatcher,
+            snapshot_scheduler_status,
             app_telemetry_collector: AppBuildTelemetryCollector::new(),
             actix_telemetry_collector: Arc::new(Mutex::new(ActixTelemetryCollector {
                 workers: Vec::new(),
@@ -80,6 +91,7 @@ impl TelemetryCollector {
                 &self.actix_telemetry_collector.lock(),
                 &self.tonic_telemetry_collector.lock(),
             ),
+            snapshots: SnapshotsTelemetry::collect(level, &self.snapshot_scheduler_status),
         }
     }
 }
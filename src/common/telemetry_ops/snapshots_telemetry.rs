@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
+use serde::{Deserialize, Serialize};
+use storage::content_manager::snapshots::scheduler::SnapshotSchedulerStatusRef;
+use storage::types::ScheduledSnapshotsStatus;
+
+#[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
+pub struct SnapshotsTelemetry {
+    /// State of the scheduled snapshots by collection name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheduled: Option<HashMap<String, ScheduledSnapshotsStatus>>,
+}
+
+impl SnapshotsTelemetry {
+    pub fn collect(level: usize, status: &SnapshotSchedulerStatusRef) -> Self {
+        let scheduled = if level > 0 {
+            Some(status.read().clone())
+        } else {
+            None
+        };
+
+        SnapshotsTelemetry { scheduled }
+    }
+}
+
+impl Anonymize for SnapshotsTelemetry {
+    fn anonymize(&self) -> Self {
+        SnapshotsTelemetry {
+            scheduled: self.scheduled.anonymize(),
+        }
+    }
+}
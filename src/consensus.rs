@@ -1222,6 +1222,8 @@ mod tests {
                                 hnsw_config: None,
                                 quantization_config: None,
                                 on_disk: None,
+                                disk_ann_config: None,
+                                ivf_config: None,
                             }
                             .into(),
                             hnsw_config: None,
@@ -28,6 +28,9 @@ use startup::setup_panic_hook;
 use storage::content_manager::consensus::operation_sender::OperationSender;
 use storage::content_manager::consensus::persistent::Persistent;
 use storage::content_manager::consensus_manager::{ConsensusManager, ConsensusStateRef};
+use storage::content_manager::consistency_checker::ConsistencyChecker;
+use storage::content_manager::rebalancer::Rebalancer;
+use storage::content_manager::snapshots::scheduler::SnapshotScheduler;
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
 #[cfg(not(target_env = "msvc"))]
@@ -256,6 +259,12 @@ fn main() -> anyhow::Result<()> {
     // It decides if query should go directly to the ToC or through the consensus.
     let mut dispatcher = Dispatcher::new(toc_arc.clone());
 
+    // Creates and cleans up collection snapshots by schedule, on every peer
+    let snapshot_scheduler =
+        SnapshotScheduler::new(toc_arc.clone(), &settings.storage.snapshot_schedule);
+    let snapshot_scheduler_status = snapshot_scheduler.status();
+    runtime_handle.spawn(snapshot_scheduler.run());
+
     let (telemetry_collector, dispatcher_arc) = if is_distributed_deployment {
         let consensus_state: ConsensusStateRef = ConsensusManager::new(
             persistent_consensus_state,
@@ -271,8 +280,12 @@ fn main() -> anyhow::Result<()> {
         let dispatcher_arc = Arc::new(dispatcher);
 
         // Monitoring and telemetry.
-        let telemetry_collector =
-            TelemetryCollector::new(settings.clone(), dispatcher_arc.clone(), reporting_id);
+        let telemetry_collector = TelemetryCollector::new(
+            settings.clone(),
+            dispatcher_arc.clone(),
+            snapshot_scheduler_status,
+            reporting_id,
+        );
         let tonic_telemetry_collector = telemetry_collector.tonic_telemetry_collector.clone();
 
         // `raft` crate uses `slog` crate so it is needed to use `slog_stdlog::StdLog` to forward
@@ -321,6 +334,36 @@ fn main() -> anyhow::Result<()> {
             restored_collections
         };
 
+        runtime_handle.spawn(
+            Rebalancer::new(
+                toc_arc.clone(),
+                consensus_state.clone(),
+                settings.cluster.rebalancer.clone(),
+            )
+            .run(),
+        );
+
+        if let Some(checker) =
+            ConsistencyChecker::new(toc_arc.clone(), &settings.cluster.consistency_check)
+        {
+            runtime_handle.spawn(checker.run());
+        }
+
+        let consensus_state_clone = consensus_state.clone();
+        let peer_labels = settings.cluster.labels.clone();
+        runtime_handle.spawn(async move {
+            consensus_state_clone.is_leader_established.await_ready();
+            // Peer might not be registered yet, if it has just joined the cluster
+            let retry_interval = Duration::from_secs(5);
+            while let Err(err) = consensus_state_clone
+                .update_peer_labels(peer_labels.clone())
+                .await
+            {
+                log::warn!("Can't update peer labels, retrying in {retry_interval:?}: {err}");
+                tokio::time::sleep(retry_interval).await;
+            }
+        });
+
         if !collections_to_recover_in_consensus.is_empty() {
             runtime_handle.spawn(handle_existing_collections(
                 toc_arc.clone(),
@@ -337,8 +380,12 @@ fn main() -> anyhow::Result<()> {
         let dispatcher_arc = Arc::new(dispatcher);
 
         // Monitoring and telemetry.
-        let telemetry_collector =
-            TelemetryCollector::new(settings.clone(), dispatcher_arc.clone(), reporting_id);
+        let telemetry_collector = TelemetryCollector::new(
+            settings.clone(),
+            dispatcher_arc.clone(),
+            snapshot_scheduler_status,
+            reporting_id,
+        );
         (telemetry_collector, dispatcher_arc)
     };
 
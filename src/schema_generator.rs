@@ -1,16 +1,22 @@
 use api::grpc::models::CollectionsResponse;
 use collection::operations::cluster_ops::ClusterOperations;
+use collection::operations::consistency_ops::{ConsistencyCheckRequest, ConsistencyReport};
 use collection::operations::consistency_params::ReadConsistency;
-use collection::operations::payload_ops::{DeletePayload, SetPayload};
+use collection::operations::optimization_ops::OptimizationOperations;
+use collection::operations::payload_ops::{
+    DeletePayload, IncrementPayload, SetPayload, UpdatePayloadArray,
+};
 use collection::operations::point_ops::{PointInsertOperations, PointsSelector, WriteOrdering};
+use collection::operations::recall_ops::{RecallEstimationReport, RecallEstimationRequest};
 use collection::operations::snapshot_ops::{
-    ShardSnapshotRecover, SnapshotDescription, SnapshotRecover,
+    ClusterSnapshotRecover, ShardSnapshotRecover, SnapshotDescription, SnapshotRecover,
 };
 use collection::operations::types::{
-    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionsAliasesResponse,
-    CountRequest, CountResult, GroupsResult, PointGroup, PointRequest, RecommendGroupsRequest,
-    RecommendRequest, RecommendRequestBatch, Record, ScrollRequest, ScrollResult,
-    SearchGroupsRequest, SearchRequest, SearchRequestBatch, UpdateResult,
+    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionOptimizations,
+    CollectionsAliasesResponse, CountRequest, CountResult, GroupsResult, PointGroup, PointRequest,
+    RecommendGroupsRequest, RecommendRequest, RecommendRequestBatch, Record, ScrollRequest,
+    ScrollResult, SearchGroupsRequest, SearchProfile, SearchRequest, SearchRequestBatch,
+    UpdateResult,
 };
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::gen::SchemaSettings;
@@ -75,6 +81,16 @@ struct AllDefinitions {
     b7: GroupsResult,
     b8: UpdateOperations,
     b9: ShardSnapshotRecover,
+    ba: IncrementPayload,
+    bb: UpdatePayloadArray,
+    bc: SearchProfile,
+    bd: OptimizationOperations,
+    be: CollectionOptimizations,
+    bf: ConsistencyCheckRequest,
+    bg: ConsistencyReport,
+    bh: ClusterSnapshotRecover,
+    bi: RecallEstimationRequest,
+    bj: RecallEstimationReport,
 }
 
 fn save_schema<T: JsonSchema>() {
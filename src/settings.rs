@@ -4,10 +4,11 @@ use api::grpc::transport_channel_pool::{
     DEFAULT_CONNECT_TIMEOUT, DEFAULT_GRPC_TIMEOUT, DEFAULT_POOL_SIZE,
 };
 use collection::operations::validation;
+use collection::shards::peer_labels::PeerLabels;
 use config::{Config, ConfigError, Environment, File, FileFormat, Source};
 use segment::common::cpu::get_num_cpus;
 use serde::Deserialize;
-use storage::types::StorageConfig;
+use storage::types::{ConsistencyCheckConfig, RebalancerConfig, StorageConfig};
 use validator::Validate;
 
 const DEFAULT_CONFIG: &str = include_str!("../config/config.yaml");
@@ -53,6 +54,15 @@ pub struct ClusterConfig {
     #[serde(default)]
     #[validate]
     pub consensus: ConsensusConfig,
+    #[serde(default)]
+    #[validate]
+    pub rebalancer: RebalancerConfig,
+    #[serde(default)]
+    #[validate]
+    pub consistency_check: ConsistencyCheckConfig,
+    /// Labels of this peer, such as zone or rack
+    #[serde(default)]
+    pub labels: PeerLabels,
 }
 
 #[derive(Debug, Deserialize, Clone, Validate)]
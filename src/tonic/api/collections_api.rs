@@ -4,11 +4,14 @@ use std::time::{Duration, Instant};
 use api::grpc::qdrant::collections_server::Collections;
 use api::grpc::qdrant::{
     AliasDescription, ChangeAliases, CollectionClusterInfoRequest, CollectionClusterInfoResponse,
-    CollectionOperationResponse, CreateCollection, DeleteCollection, GetCollectionInfoRequest,
-    GetCollectionInfoResponse, ListAliasesRequest, ListAliasesResponse,
-    ListCollectionAliasesRequest, ListCollectionsRequest, ListCollectionsResponse,
-    UpdateCollection, UpdateCollectionClusterSetupRequest, UpdateCollectionClusterSetupResponse,
+    CollectionOperationResponse, CollectionOptimizationsRequest, CollectionOptimizationsResponse,
+    CreateCollection, DeleteCollection, GetCollectionInfoRequest, GetCollectionInfoResponse,
+    ListAliasesRequest, ListAliasesResponse, ListCollectionAliasesRequest, ListCollectionsRequest,
+    ListCollectionsResponse, UpdateCollection, UpdateCollectionClusterSetupRequest,
+    UpdateCollectionClusterSetupResponse, UpdateCollectionOptimizationsRequest,
+    UpdateCollectionOptimizationsResponse,
 };
+use collection::operations::optimization_ops::OptimizationOperations;
 use storage::content_manager::conversions::error_to_status;
 use storage::dispatcher::Dispatcher;
 use tonic::{Request, Response, Status};
@@ -210,6 +213,48 @@ impl Collections for CollectionsService {
             result,
         }))
     }
+
+    async fn collection_optimizations(
+        &self,
+        request: Request<CollectionOptimizationsRequest>,
+    ) -> Result<Response<CollectionOptimizationsResponse>, Status> {
+        validate(request.get_ref())?;
+        let response = do_get_collection_optimizations(
+            self.dispatcher.toc(),
+            request.into_inner().collection_name.as_str(),
+        )
+        .await
+        .map_err(error_to_status)?
+        .into();
+
+        Ok(Response::new(response))
+    }
+
+    async fn update_collection_optimizations(
+        &self,
+        request: Request<UpdateCollectionOptimizationsRequest>,
+    ) -> Result<Response<UpdateCollectionOptimizationsResponse>, Status> {
+        validate(request.get_ref())?;
+        let UpdateCollectionOptimizationsRequest {
+            collection_name,
+            operation,
+        } = request.into_inner();
+        let operation: OptimizationOperations = operation
+            .ok_or(Status::new(tonic::Code::InvalidArgument, "empty operation"))?
+            .into();
+        validate(&operation)?;
+        let result = do_update_collection_optimizations(
+            self.dispatcher.as_ref(),
+            collection_name,
+            operation,
+            None,
+        )
+        .await
+        .map_err(error_to_status)?;
+        Ok(Response::new(UpdateCollectionOptimizationsResponse {
+            result,
+        }))
+    }
 }
 
 trait WithTimeout {
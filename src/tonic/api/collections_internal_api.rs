@@ -1,11 +1,19 @@
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use api::grpc::qdrant::collections_internal_server::CollectionsInternal;
 use api::grpc::qdrant::{
-    CollectionOperationResponse, GetCollectionInfoRequestInternal, GetCollectionInfoResponse,
-    InitiateShardTransferRequest,
+    CollectionOperationResponse, CreateShardSnapshotRequest, CreateShardSnapshotResponse,
+    DeleteShardSnapshotRequest, ForceOptimizationRequest, ForceOptimizationResponse,
+    GetCollectionInfoRequestInternal, GetCollectionInfoResponse, GetShardDigestRequest,
+    GetShardDigestResponse, GetShardSnapshotChunkRequest, GetShardSnapshotChunkResponse,
+    InitiateShardTransferRequest, MeasureRecallRequest, MeasureRecallResponse, PointDigest,
+    RecallTargetMeasurements, SampleRecallQueriesRequest, SampleRecallQueriesResponse,
+    WaitForOperationRequest, WaitForOperationResponse,
 };
+use collection::operations::optimization_ops::OptimizationOperations;
+use collection::shards::consistency::ShardDigest;
+use collection::shards::recall::RecallQuery;
 use storage::content_manager::conversions::error_to_status;
 use storage::content_manager::toc::TableOfContent;
 use tonic::{Request, Response, Status};
@@ -68,4 +76,283 @@ impl CollectionsInternal for CollectionsInternalService {
         };
         Ok(Response::new(response))
     }
+
+    async fn get_shard_digest(
+        &self,
+        request: Request<GetShardDigestRequest>,
+    ) -> Result<Response<GetShardDigestResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let timing = Instant::now();
+        let GetShardDigestRequest {
+            collection_name,
+            shard_id,
+            buckets,
+            bucket,
+        } = request.into_inner();
+
+        let points = self
+            .toc
+            .get_collection(&collection_name)
+            .await
+            .map_err(error_to_status)?
+            .local_point_digests(shard_id, buckets, bucket)
+            .await
+            .map_err(|err| error_to_status(err.into()))?;
+
+        let response = match bucket {
+            Some(_) => GetShardDigestResponse {
+                points: points
+                    .into_iter()
+                    .map(|point| PointDigest {
+                        id: Some(point.id.into()),
+                        version: point.version,
+                        hash: point.hash,
+                    })
+                    .collect(),
+                time: timing.elapsed().as_secs_f64(),
+                ..Default::default()
+            },
+            None => {
+                let digest = ShardDigest::new(&points, buckets);
+                GetShardDigestResponse {
+                    root: digest.root,
+                    bucket_hashes: digest.bucket_hashes,
+                    points_count: digest.points_count as u64,
+                    points: vec![],
+                    time: timing.elapsed().as_secs_f64(),
+                }
+            }
+        };
+        Ok(Response::new(response))
+    }
+
+    async fn wait_for_operation(
+        &self,
+        request: Request<WaitForOperationRequest>,
+    ) -> Result<Response<WaitForOperationResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let timing = Instant::now();
+        let WaitForOperationRequest {
+            collection_name,
+            shard_id,
+            operation_id,
+            timeout,
+        } = request.into_inner();
+
+        let applied = self
+            .toc
+            .get_collection(&collection_name)
+            .await
+            .map_err(error_to_status)?
+            .wait_for_local_operation(shard_id, operation_id, Duration::from_millis(timeout))
+            .await
+            .map_err(|err| error_to_status(err.into()))?;
+
+        let response = WaitForOperationResponse {
+            applied,
+            time: timing.elapsed().as_secs_f64(),
+        };
+        Ok(Response::new(response))
+    }
+
+    async fn create_shard_snapshot(
+        &self,
+        request: Request<CreateShardSnapshotRequest>,
+    ) -> Result<Response<CreateShardSnapshotResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let timing = Instant::now();
+        let CreateShardSnapshotRequest {
+            collection_name,
+            shard_id,
+        } = request.into_inner();
+
+        let temp_dir = self
+            .toc
+            .optional_temp_or_snapshot_temp_path()
+            .map_err(|err| error_to_status(err.into()))?;
+        let snapshot = self
+            .toc
+            .get_collection(&collection_name)
+            .await
+            .map_err(error_to_status)?
+            .create_shard_snapshot(shard_id, &temp_dir)
+            .await
+            .map_err(|err| error_to_status(err.into()))?;
+
+        let response = CreateShardSnapshotResponse {
+            snapshot_name: snapshot.name,
+            size: snapshot.size,
+            time: timing.elapsed().as_secs_f64(),
+            checksum: snapshot.checksum,
+        };
+        Ok(Response::new(response))
+    }
+
+    async fn get_shard_snapshot_chunk(
+        &self,
+        request: Request<GetShardSnapshotChunkRequest>,
+    ) -> Result<Response<GetShardSnapshotChunkResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let timing = Instant::now();
+        let GetShardSnapshotChunkRequest {
+            collection_name,
+            shard_id,
+            snapshot_name,
+            offset,
+            length,
+        } = request.into_inner();
+
+        let data = self
+            .toc
+            .get_collection(&collection_name)
+            .await
+            .map_err(error_to_status)?
+            .read_shard_snapshot_chunk(shard_id, &snapshot_name, offset, length)
+            .await
+            .map_err(|err| error_to_status(err.into()))?;
+
+        let response = GetShardSnapshotChunkResponse {
+            data,
+            time: timing.elapsed().as_secs_f64(),
+        };
+        Ok(Response::new(response))
+    }
+
+    async fn delete_shard_snapshot(
+        &self,
+        request: Request<DeleteShardSnapshotRequest>,
+    ) -> Result<Response<CollectionOperationResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let timing = Instant::now();
+        let DeleteShardSnapshotRequest {
+            collection_name,
+            shard_id,
+            snapshot_name,
+        } = request.into_inner();
+
+        let result = self
+            .toc
+            .get_collection(&collection_name)
+            .await
+            .map_err(error_to_status)?
+            .delete_shard_snapshot(shard_id, &snapshot_name)
+            .await
+            .map_err(|err| error_to_status(err.into()))?;
+
+        let response = CollectionOperationResponse {
+            result,
+            time: timing.elapsed().as_secs_f64(),
+        };
+        Ok(Response::new(response))
+    }
+
+    async fn force_optimization(
+        &self,
+        request: Request<ForceOptimizationRequest>,
+    ) -> Result<Response<ForceOptimizationResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let timing = Instant::now();
+        let ForceOptimizationRequest {
+            collection_name,
+            shard_id,
+            operation,
+        } = request.into_inner();
+        let operation: OptimizationOperations = operation
+            .ok_or_else(|| Status::invalid_argument("empty operation"))?
+            .into();
+
+        let started = self
+            .toc
+            .get_collection(&collection_name)
+            .await
+            .map_err(error_to_status)?
+            .force_local_optimization(shard_id, &operation)
+            .await
+            .map_err(|err| error_to_status(err.into()))?;
+
+        let response = ForceOptimizationResponse {
+            started,
+            time: timing.elapsed().as_secs_f64(),
+        };
+        Ok(Response::new(response))
+    }
+
+    async fn sample_recall_queries(
+        &self,
+        request: Request<SampleRecallQueriesRequest>,
+    ) -> Result<Response<SampleRecallQueriesResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let timing = Instant::now();
+        let SampleRecallQueriesRequest {
+            collection_name,
+            shard_id,
+            filter,
+            sample,
+        } = request.into_inner();
+        let filter = filter.map(TryInto::try_into).transpose()?;
+
+        let queries = self
+            .toc
+            .get_collection(&collection_name)
+            .await
+            .map_err(error_to_status)?
+            .sample_local_recall_queries(shard_id, filter, sample as usize)
+            .await
+            .map_err(|err| error_to_status(err.into()))?;
+
+        let response = SampleRecallQueriesResponse {
+            queries: queries.into_iter().map(Into::into).collect(),
+            time: timing.elapsed().as_secs_f64(),
+        };
+        Ok(Response::new(response))
+    }
+
+    async fn measure_recall(
+        &self,
+        request: Request<MeasureRecallRequest>,
+    ) -> Result<Response<MeasureRecallResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let timing = Instant::now();
+        let MeasureRecallRequest {
+            collection_name,
+            shard_id,
+            queries,
+            filter,
+            limit,
+            params,
+        } = request.into_inner();
+        let queries = queries
+            .into_iter()
+            .map(RecallQuery::try_from)
+            .collect::<Result<_, _>>()?;
+        let filter = filter.map(TryInto::try_into).transpose()?;
+
+        let measurement = self
+            .toc
+            .get_collection(&collection_name)
+            .await
+            .map_err(error_to_status)?
+            .measure_local_recall(
+                shard_id,
+                queries,
+                filter,
+                limit as usize,
+                params.map(Into::into),
+            )
+            .await
+            .map_err(|err| error_to_status(err.into()))?;
+
+        let response = MeasureRecallResponse {
+            segments: measurement.segments.into_iter().map(Into::into).collect(),
+            measurements: measurement
+                .measurements
+                .into_iter()
+                .map(|queries| RecallTargetMeasurements {
+                    queries: queries.into_iter().map(Into::into).collect(),
+                })
+                .collect(),
+            time: timing.elapsed().as_secs_f64(),
+        };
+        Ok(Response::new(response))
+    }
 }
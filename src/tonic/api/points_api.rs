@@ -4,23 +4,25 @@ use api::grpc::qdrant::points_server::Points;
 use api::grpc::qdrant::{
     ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
     DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors, DeletePoints, GetPoints,
-    GetResponse, PointsOperationResponse, RecommendBatchPoints, RecommendBatchResponse,
-    RecommendGroupsResponse, RecommendPointGroups, RecommendPoints, RecommendResponse,
-    ScrollPoints, ScrollResponse, SearchBatchPoints, SearchBatchResponse, SearchGroupsResponse,
-    SearchPointGroups, SearchPoints, SearchResponse, SetPayloadPoints, UpdateBatchPoints,
-    UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
+    GetResponse, IncrementPayloadPoints, PointsOperationResponse, RecommendBatchPoints,
+    RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups, RecommendPoints,
+    RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchPoints, SearchBatchResponse,
+    SearchGroupsResponse, SearchPointGroups, SearchPoints, SearchResponse, SetPayloadPoints,
+    UpdateBatchPoints, UpdateBatchResponse, UpdatePayloadArrayPoints, UpdatePointVectors,
+    UpsertPoints,
 };
 use storage::content_manager::toc::TableOfContent;
 use tonic::{Request, Response, Status};
 
 use super::points_common::{
-    delete_vectors, recommend_groups, search_groups, update_batch, update_vectors,
+    delete_vectors, increment_payload, recommend_groups, search_groups, update_batch,
+    update_payload_array, update_vectors,
 };
 use super::validate;
 use crate::tonic::api::points_common::{
     clear_payload, count, create_field_index, delete, delete_field_index, delete_payload, get,
     overwrite_payload, recommend, recommend_batch, scroll, search, search_batch, set_payload,
-    upsert,
+    upsert, wait_for_consistency_token,
 };
 
 pub struct PointsService {
@@ -104,6 +106,22 @@ impl Points for PointsService {
         clear_payload(self.toc.as_ref(), request.into_inner(), None).await
     }
 
+    async fn increment_payload(
+        &self,
+        request: Request<IncrementPayloadPoints>,
+    ) -> Result<Response<PointsOperationResponse>, Status> {
+        validate(request.get_ref())?;
+        increment_payload(self.toc.as_ref(), request.into_inner(), None).await
+    }
+
+    async fn update_payload_array(
+        &self,
+        request: Request<UpdatePayloadArrayPoints>,
+    ) -> Result<Response<PointsOperationResponse>, Status> {
+        validate(request.get_ref())?;
+        update_payload_array(self.toc.as_ref(), request.into_inner(), None).await
+    }
+
     async fn update_batch(
         &self,
         request: Request<UpdateBatchPoints>,
@@ -145,7 +163,9 @@ impl Points for PointsService {
             collection_name,
             search_points,
             read_consistency,
+            consistency_token,
         } = request.into_inner();
+        wait_for_consistency_token(self.toc.as_ref(), &collection_name, consistency_token).await?;
         search_batch(
             self.toc.as_ref(),
             collection_name,
@@ -189,7 +209,9 @@ impl Points for PointsService {
             collection_name,
             recommend_points,
             read_consistency,
+            consistency_token,
         } = request.into_inner();
+        wait_for_consistency_token(self.toc.as_ref(), &collection_name, consistency_token).await?;
         recommend_batch(
             self.toc.as_ref(),
             collection_name,
@@ -1,20 +1,22 @@
 use std::time::Instant;
 
-use api::grpc::conversions::proto_to_payloads;
+use api::grpc::conversions::{proto_to_json, proto_to_payloads};
 use api::grpc::qdrant::payload_index_params::IndexParams;
 use api::grpc::qdrant::{
-    points_update_operation, BatchResult, ClearPayloadPoints, CoreSearchPoints, CountPoints,
-    CountResponse, CreateFieldIndexCollection, DeleteFieldIndexCollection, DeletePayloadPoints,
-    DeletePointVectors, DeletePoints, FieldType, GetPoints, GetResponse, PayloadIndexParams,
+    points_update_operation, BatchResult, ClearPayloadPoints, CoreSearchBatchProfiledResponse,
+    CoreSearchPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
+    DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors, DeletePoints, FieldType,
+    GetPoints, GetResponse, IncrementPayloadPoints, PayloadArrayOperation, PayloadIndexParams,
     PointsOperationResponse, PointsSelector, ReadConsistency as ReadConsistencyGrpc,
     RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups, RecommendPoints,
     RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchResponse, SearchGroupsResponse,
     SearchPointGroups, SearchPoints, SearchResponse, SetPayloadPoints, SyncPoints,
-    UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
+    UpdateBatchPoints, UpdateBatchResponse, UpdatePayloadArrayPoints, UpdatePointVectors,
+    UpsertPoints,
 };
-use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::consistency_params::{ConsistencyToken, ReadConsistency};
 use collection::operations::conversions::write_ordering_from_proto;
-use collection::operations::payload_ops::DeletePayload;
+use collection::operations::payload_ops::{DeletePayload, IncrementPayload, UpdatePayloadArray};
 use collection::operations::point_ops::{
     self, PointInsertOperations, PointOperations, PointSyncOperation,
 };
@@ -34,10 +36,12 @@ use storage::content_manager::toc::TableOfContent;
 use tonic::{Response, Status};
 
 use crate::common::points::{
-    do_clear_payload, do_core_search_batch_points, do_count_points, do_create_index,
-    do_delete_index, do_delete_payload, do_delete_points, do_delete_vectors, do_get_points,
+    do_add_to_set_payload, do_array_append_payload, do_array_remove_payload, do_clear_payload,
+    do_core_search_batch_points, do_count_points, do_create_index, do_delete_index,
+    do_delete_payload, do_delete_points, do_delete_vectors, do_get_points, do_increment_payload,
     do_overwrite_payload, do_scroll_points, do_search_batch_points, do_search_points,
-    do_set_payload, do_update_vectors, do_upsert_points, CreateFieldIndex,
+    do_search_points_with_profile, do_set_payload, do_update_vectors, do_upsert_points,
+    do_wait_for_consistency_token, CreateFieldIndex,
 };
 
 fn extract_points_selector(
@@ -55,6 +59,13 @@ fn extract_points_selector(
     Ok((points, filter))
 }
 
+fn proto_to_number(value: api::grpc::qdrant::Value) -> Result<serde_json::Number, Status> {
+    match proto_to_json(value)? {
+        serde_json::Value::Number(number) => Ok(number),
+        _ => Err(Status::invalid_argument("numeric value is expected")),
+    }
+}
+
 pub fn points_operation_response(
     timing: Instant,
     update_result: collection::operations::types::UpdateResult,
@@ -269,6 +280,7 @@ pub async fn set_payload(
         payload,
         points_selector,
         ordering,
+        key,
     } = set_payload_points;
 
     let (points, filter) = extract_points_selector(points_selector)?;
@@ -276,6 +288,7 @@ pub async fn set_payload(
         payload: proto_to_payloads(payload)?,
         points,
         filter,
+        key,
     };
 
     let timing = Instant::now();
@@ -305,6 +318,7 @@ pub async fn overwrite_payload(
         payload,
         points_selector,
         ordering,
+        key,
     } = set_payload_points;
 
     let (points, filter) = extract_points_selector(points_selector)?;
@@ -312,6 +326,7 @@ pub async fn overwrite_payload(
         payload: proto_to_payloads(payload)?,
         points,
         filter,
+        key,
     };
 
     let timing = Instant::now();
@@ -399,6 +414,120 @@ pub async fn clear_payload(
     Ok(Response::new(response))
 }
 
+pub async fn increment_payload(
+    toc: &TableOfContent,
+    increment_payload_points: IncrementPayloadPoints,
+    shard_selection: Option<ShardId>,
+) -> Result<Response<PointsOperationResponse>, Status> {
+    let IncrementPayloadPoints {
+        collection_name,
+        wait,
+        key,
+        value,
+        initial,
+        points_selector,
+        ordering,
+    } = increment_payload_points;
+
+    let (points, filter) = extract_points_selector(points_selector)?;
+    let value = value.ok_or_else(|| Status::invalid_argument("value is expected"))?;
+    let operation = IncrementPayload {
+        key,
+        value: proto_to_number(value)?,
+        initial: initial.map(proto_to_number).transpose()?,
+        points,
+        filter,
+    };
+
+    let timing = Instant::now();
+    let result = do_increment_payload(
+        toc,
+        &collection_name,
+        operation,
+        shard_selection,
+        wait.unwrap_or(false),
+        write_ordering_from_proto(ordering)?,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    let response = points_operation_response(timing, result);
+    Ok(Response::new(response))
+}
+
+pub async fn update_payload_array(
+    toc: &TableOfContent,
+    update_payload_array_points: UpdatePayloadArrayPoints,
+    shard_selection: Option<ShardId>,
+) -> Result<Response<PointsOperationResponse>, Status> {
+    let UpdatePayloadArrayPoints {
+        collection_name,
+        wait,
+        key,
+        values,
+        operation,
+        points_selector,
+        ordering,
+    } = update_payload_array_points;
+
+    let array_operation = PayloadArrayOperation::from_i32(operation).ok_or_else(|| {
+        Status::invalid_argument(format!("Unknown payload array operation: {operation}"))
+    })?;
+    let (points, filter) = extract_points_selector(points_selector)?;
+    let operation = UpdatePayloadArray {
+        key,
+        values: values
+            .into_iter()
+            .map(proto_to_json)
+            .collect::<Result<_, _>>()?,
+        points,
+        filter,
+    };
+    let wait = wait.unwrap_or(false);
+    let ordering = write_ordering_from_proto(ordering)?;
+
+    let timing = Instant::now();
+    let result = match array_operation {
+        PayloadArrayOperation::Append => {
+            do_array_append_payload(
+                toc,
+                &collection_name,
+                operation,
+                shard_selection,
+                wait,
+                ordering,
+            )
+            .await
+        }
+        PayloadArrayOperation::Remove => {
+            do_array_remove_payload(
+                toc,
+                &collection_name,
+                operation,
+                shard_selection,
+                wait,
+                ordering,
+            )
+            .await
+        }
+        PayloadArrayOperation::AddToSet => {
+            do_add_to_set_payload(
+                toc,
+                &collection_name,
+                operation,
+                shard_selection,
+                wait,
+                ordering,
+            )
+            .await
+        }
+    }
+    .map_err(error_to_status)?;
+
+    let response = points_operation_response(timing, result);
+    Ok(Response::new(response))
+}
+
 pub async fn update_batch(
     toc: &TableOfContent,
     update_batch_points: UpdateBatchPoints,
@@ -450,6 +579,7 @@ pub async fn update_batch(
                 points_update_operation::SetPayload {
                     payload,
                     points_selector,
+                    key,
                 },
             ) => {
                 set_payload(
@@ -460,6 +590,7 @@ pub async fn update_batch(
                         payload,
                         points_selector,
                         ordering,
+                        key,
                     },
                     shard_selection,
                 )
@@ -469,6 +600,7 @@ pub async fn update_batch(
                 points_update_operation::SetPayload {
                     payload,
                     points_selector,
+                    key,
                 },
             ) => {
                 overwrite_payload(
@@ -479,6 +611,7 @@ pub async fn update_batch(
                         payload,
                         points_selector,
                         ordering,
+                        key,
                     },
                     shard_selection,
                 )
@@ -550,6 +683,52 @@ pub async fn update_batch(
                 )
                 .await
             }
+            points_update_operation::Operation::IncrementPayload(
+                points_update_operation::IncrementPayload {
+                    key,
+                    value,
+                    initial,
+                    points_selector,
+                },
+            ) => {
+                increment_payload(
+                    toc,
+                    IncrementPayloadPoints {
+                        collection_name,
+                        wait,
+                        key,
+                        value,
+                        initial,
+                        points_selector,
+                        ordering,
+                    },
+                    shard_selection,
+                )
+                .await
+            }
+            points_update_operation::Operation::UpdatePayloadArray(
+                points_update_operation::UpdatePayloadArray {
+                    key,
+                    values,
+                    operation,
+                    points_selector,
+                },
+            ) => {
+                update_payload_array(
+                    toc,
+                    UpdatePayloadArrayPoints {
+                        collection_name,
+                        wait,
+                        key,
+                        values,
+                        operation,
+                        points_selector,
+                        ordering,
+                    },
+                    shard_selection,
+                )
+                .await
+            }
         }?;
         results.push(result);
     }
@@ -674,6 +853,9 @@ pub async fn search(
         vector_name,
         with_vectors,
         read_consistency,
+        profile,
+        consistency_token,
+        formula,
     } = search_points;
 
     let search_request = SearchRequest {
@@ -692,20 +874,37 @@ pub async fn search(
                 .unwrap_or_default(),
         ),
         score_threshold,
+        profile,
+        formula: formula.map(|f| f.try_into()).transpose()?,
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    wait_for_consistency_token(toc, &collection_name, consistency_token).await?;
 
     let timing = Instant::now();
-    let scored_points = do_search_points(
-        toc,
-        &collection_name,
-        search_request,
-        read_consistency,
-        shard_selection,
-    )
-    .await
-    .map_err(error_to_status)?;
+    let (scored_points, profile) = if profile.unwrap_or(false) {
+        let (scored_points, profile) = do_search_points_with_profile(
+            toc,
+            &collection_name,
+            search_request,
+            read_consistency,
+            shard_selection,
+        )
+        .await
+        .map_err(error_to_status)?;
+        (scored_points, Some(profile.into()))
+    } else {
+        let scored_points = do_search_points(
+            toc,
+            &collection_name,
+            search_request,
+            read_consistency,
+            shard_selection,
+        )
+        .await
+        .map_err(error_to_status)?;
+        (scored_points, None)
+    };
 
     let response = SearchResponse {
         result: scored_points
@@ -713,6 +912,7 @@ pub async fn search(
             .map(|point| point.into())
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        profile,
     };
 
     Ok(Response::new(response))
@@ -802,6 +1002,54 @@ pub async fn core_search_batch(
     Ok(Response::new(response))
 }
 
+/// Same as [`core_search_batch`], but also returns details of how the search was performed
+/// in each segment of the selected shard
+pub async fn core_search_batch_profiled(
+    toc: &TableOfContent,
+    collection_name: String,
+    search_points: Vec<CoreSearchPoints>,
+    read_consistency: Option<ReadConsistencyGrpc>,
+    shard_selection: Option<ShardId>,
+) -> Result<Response<CoreSearchBatchProfiledResponse>, Status> {
+    let searches: Result<Vec<_>, Status> =
+        search_points.into_iter().map(TryInto::try_into).collect();
+
+    let search_requests = CoreSearchRequestBatch {
+        searches: searches?,
+    };
+
+    let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+
+    let timing = Instant::now();
+    let (scored_points, profile) = toc
+        .core_search_batch_with_profile(
+            &collection_name,
+            search_requests,
+            read_consistency,
+            shard_selection,
+        )
+        .await
+        .map_err(error_to_status)?;
+
+    let response = CoreSearchBatchProfiledResponse {
+        result: scored_points
+            .into_iter()
+            .map(|points| BatchResult {
+                result: points.into_iter().map(|p| p.into()).collect(),
+            })
+            .collect(),
+        profiles: profile
+            .shards
+            .into_iter()
+            .flat_map(|shard| shard.segments)
+            .map(Into::into)
+            .collect(),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
 pub async fn search_groups(
     toc: &TableOfContent,
     search_point_groups: SearchPointGroups,
@@ -812,10 +1060,12 @@ pub async fn search_groups(
     let SearchPointGroups {
         collection_name,
         read_consistency,
+        consistency_token,
         ..
     } = search_point_groups;
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    wait_for_consistency_token(toc, &collection_name, consistency_token).await?;
 
     let timing = Instant::now();
     let groups_result = crate::common::points::do_search_point_groups(
@@ -857,6 +1107,8 @@ pub async fn recommend(
         with_vectors,
         lookup_from,
         read_consistency,
+        profile,
+        consistency_token,
     } = recommend_points;
 
     let positive_ids = positive
@@ -896,15 +1148,26 @@ pub async fn recommend(
         score_threshold,
         using: using.map(|u| u.into()),
         lookup_from: lookup_from.map(|l| l.into()),
+        profile,
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    wait_for_consistency_token(toc, &collection_name, consistency_token).await?;
 
     let timing = Instant::now();
-    let recommended_points = toc
-        .recommend(&collection_name, request, read_consistency)
-        .await
-        .map_err(error_to_status)?;
+    let (recommended_points, profile) = if profile.unwrap_or(false) {
+        let (recommended_points, profile) = toc
+            .recommend_with_profile(&collection_name, request, read_consistency)
+            .await
+            .map_err(error_to_status)?;
+        (recommended_points, Some(profile.into()))
+    } else {
+        let recommended_points = toc
+            .recommend(&collection_name, request, read_consistency)
+            .await
+            .map_err(error_to_status)?;
+        (recommended_points, None)
+    };
 
     let response = RecommendResponse {
         result: recommended_points
@@ -912,6 +1175,7 @@ pub async fn recommend(
             .map(|point| point.into())
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        profile,
     };
 
     Ok(Response::new(response))
@@ -961,10 +1225,12 @@ pub async fn recommend_groups(
     let RecommendPointGroups {
         collection_name,
         read_consistency,
+        consistency_token,
         ..
     } = recommend_point_groups;
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    wait_for_consistency_token(toc, &collection_name, consistency_token).await?;
 
     let timing = Instant::now();
     let groups_result = crate::common::points::do_recommend_point_groups(
@@ -996,6 +1262,7 @@ pub async fn scroll(
         with_payload,
         with_vectors,
         read_consistency,
+        consistency_token,
     } = scroll_points;
 
     let scroll_request = ScrollRequest {
@@ -1009,6 +1276,7 @@ pub async fn scroll(
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    wait_for_consistency_token(toc, &collection_name, consistency_token).await?;
 
     let timing = Instant::now();
     let scrolled_points = do_scroll_points(
@@ -1074,6 +1342,7 @@ pub async fn get(
         with_payload,
         with_vectors,
         read_consistency,
+        consistency_token,
     } = get_points;
 
     let point_request = PointRequest {
@@ -1088,6 +1357,7 @@ pub async fn get(
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+    wait_for_consistency_token(toc, &collection_name, consistency_token).await?;
 
     let timing = Instant::now();
 
@@ -1108,3 +1378,19 @@ pub async fn get(
 
     Ok(Response::new(response))
 }
+
+/// Wait until the replicas apply the updates of the consistency token, if it is set
+pub async fn wait_for_consistency_token(
+    toc: &TableOfContent,
+    collection_name: &str,
+    consistency_token: Option<String>,
+) -> Result<(), Status> {
+    let consistency_token = consistency_token
+        .map(|token| token.parse::<ConsistencyToken>())
+        .transpose()
+        .map_err(Status::invalid_argument)?;
+
+    do_wait_for_consistency_token(toc, collection_name, consistency_token.as_ref())
+        .await
+        .map_err(error_to_status)
+}
@@ -2,18 +2,21 @@ use std::sync::Arc;
 
 use api::grpc::qdrant::points_internal_server::PointsInternal;
 use api::grpc::qdrant::{
-    ClearPayloadPointsInternal, CoreSearchBatchPointsInternal, CountPointsInternal, CountResponse,
-    CreateFieldIndexCollectionInternal, DeleteFieldIndexCollectionInternal,
-    DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, GetPointsInternal,
-    GetResponse, PointsOperationResponse, RecommendPointsInternal, RecommendResponse,
-    ScrollPointsInternal, ScrollResponse, SearchBatchPointsInternal, SearchBatchResponse,
-    SearchPointsInternal, SearchResponse, SetPayloadPointsInternal, SyncPointsInternal,
+    ClearPayloadPointsInternal, CoreSearchBatchPointsInternal, CoreSearchBatchProfiledResponse,
+    CountPointsInternal, CountResponse, CreateFieldIndexCollectionInternal,
+    DeleteFieldIndexCollectionInternal, DeletePayloadPointsInternal, DeletePointsInternal,
+    DeleteVectorsInternal, GetPointsInternal, GetResponse, IncrementPayloadPointsInternal,
+    PointsOperationResponse, RecommendPointsInternal, RecommendResponse, ScrollPointsInternal,
+    ScrollResponse, SearchBatchPointsInternal, SearchBatchResponse, SearchPointsInternal,
+    SearchResponse, SetPayloadPointsInternal, SyncPointsInternal, UpdatePayloadArrayPointsInternal,
     UpdateVectorsInternal, UpsertPointsInternal,
 };
 use storage::content_manager::toc::TableOfContent;
 use tonic::{Request, Response, Status};
 
-use super::points_common::core_search_batch;
+use super::points_common::{
+    core_search_batch, core_search_batch_profiled, increment_payload, update_payload_array,
+};
 use super::validate_and_log;
 use crate::tonic::api::points_common::{
     clear_payload, count, create_field_index, delete, delete_field_index, delete_payload,
@@ -144,6 +147,38 @@ impl PointsInternal for PointsInternalService {
         clear_payload(self.toc.as_ref(), clear_payload_points, shard_id).await
     }
 
+    async fn increment_payload(
+        &self,
+        request: Request<IncrementPayloadPointsInternal>,
+    ) -> Result<Response<PointsOperationResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let IncrementPayloadPointsInternal {
+            increment_payload_points,
+            shard_id,
+        } = request.into_inner();
+
+        let increment_payload_points = increment_payload_points
+            .ok_or_else(|| Status::invalid_argument("IncrementPayloadPoints is missing"))?;
+
+        increment_payload(self.toc.as_ref(), increment_payload_points, shard_id).await
+    }
+
+    async fn update_payload_array(
+        &self,
+        request: Request<UpdatePayloadArrayPointsInternal>,
+    ) -> Result<Response<PointsOperationResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let UpdatePayloadArrayPointsInternal {
+            update_payload_array_points,
+            shard_id,
+        } = request.into_inner();
+
+        let update_payload_array_points = update_payload_array_points
+            .ok_or_else(|| Status::invalid_argument("UpdatePayloadArrayPoints is missing"))?;
+
+        update_payload_array(self.toc.as_ref(), update_payload_array_points, shard_id).await
+    }
+
     async fn create_field_index(
         &self,
         request: Request<CreateFieldIndexCollectionInternal>,
@@ -252,6 +287,27 @@ impl PointsInternal for PointsInternalService {
         .await
     }
 
+    async fn core_search_batch_profiled(
+        &self,
+        request: Request<CoreSearchBatchPointsInternal>,
+    ) -> Result<Response<CoreSearchBatchProfiledResponse>, Status> {
+        validate_and_log(request.get_ref());
+        let CoreSearchBatchPointsInternal {
+            collection_name,
+            search_points,
+            shard_id,
+        } = request.into_inner();
+
+        core_search_batch_profiled(
+            self.toc.as_ref(),
+            collection_name,
+            search_points,
+            None, // *Has* to be `None`!
+            shard_id,
+        )
+        .await
+    }
+
     async fn recommend(
         &self,
         request: Request<RecommendPointsInternal>,
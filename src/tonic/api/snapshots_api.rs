@@ -8,6 +8,7 @@ use api::grpc::qdrant::{
     ListFullSnapshotsRequest, ListSnapshotsRequest, ListSnapshotsResponse,
 };
 use storage::content_manager::conversions::error_to_status;
+use storage::content_manager::snapshots::cluster::do_create_cluster_snapshot;
 use storage::content_manager::snapshots::{
     do_create_full_snapshot, do_delete_collection_snapshot, do_delete_full_snapshot,
     do_list_full_snapshots,
@@ -35,10 +36,13 @@ impl Snapshots for SnapshotsService {
         request: Request<CreateSnapshotRequest>,
     ) -> Result<Response<CreateSnapshotResponse>, Status> {
         validate(request.get_ref())?;
-        let collection_name = request.into_inner().collection_name;
+        let CreateSnapshotRequest {
+            collection_name,
+            base_snapshot,
+        } = request.into_inner();
         let timing = Instant::now();
         let dispatcher = self.dispatcher.clone();
-        let response = do_create_snapshot(&dispatcher, &collection_name, true)
+        let response = do_create_snapshot(&dispatcher, &collection_name, base_snapshot, true)
             .await
             .map_err(error_to_status)?;
         Ok(Response::new(CreateSnapshotResponse {
@@ -127,4 +131,28 @@ impl Snapshots for SnapshotsService {
             time: timing.elapsed().as_secs_f64(),
         }))
     }
+
+    async fn create_cluster(
+        &self,
+        request: Request<CreateSnapshotRequest>,
+    ) -> Result<Response<CreateSnapshotResponse>, Status> {
+        validate(request.get_ref())?;
+        let CreateSnapshotRequest {
+            collection_name,
+            base_snapshot,
+        } = request.into_inner();
+        if base_snapshot.is_some() {
+            return Err(Status::invalid_argument(
+                "Incremental snapshots are not supported for cluster snapshots",
+            ));
+        }
+        let timing = Instant::now();
+        let response = do_create_cluster_snapshot(&self.dispatcher, &collection_name, true)
+            .await
+            .map_err(error_to_status)?;
+        Ok(Response::new(CreateSnapshotResponse {
+            snapshot_description: response.map(|x| x.into()),
+            time: timing.elapsed().as_secs_f64(),
+        }))
+    }
 }
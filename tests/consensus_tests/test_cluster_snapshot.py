@@ -0,0 +1,83 @@
+import pathlib
+
+from .fixtures import create_collection, upsert_random_points
+from .utils import *
+from .assertions import assert_http_ok
+
+N_PEERS = 3
+N_RESTORED_PEERS = 2
+N_SHARDS = 3
+N_POINTS = 300
+COLLECTION_NAME = "test_collection"
+
+
+def create_cluster_snapshot(peer_api_uri: str) -> str:
+    r = requests.post(f"{peer_api_uri}/collections/{COLLECTION_NAME}/snapshots/cluster")
+    assert_http_ok(r)
+    return r.json()["result"]["name"]
+
+
+def recover_cluster_snapshot(peer_api_uri: str, snapshot_url: str):
+    r = requests.put(
+        f"{peer_api_uri}/collections/{COLLECTION_NAME}/snapshots/cluster/recover",
+        json={"location": snapshot_url},
+    )
+    assert_http_ok(r)
+    return r.json()["result"]
+
+
+def count_points(peer_api_uri: str) -> int:
+    r = requests.post(
+        f"{peer_api_uri}/collections/{COLLECTION_NAME}/points/count", json={"exact": True}
+    )
+    assert_http_ok(r)
+    return r.json()["result"]["count"]
+
+
+def test_cluster_snapshot(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, peer_dirs, bootstrap_uri = start_cluster(tmp_path, N_PEERS)
+
+    # Every peer holds a single shard, so the snapshot has to collect shards from all of them
+    create_collection(peer_api_uris[0], shard_number=N_SHARDS, replication_factor=1)
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name=COLLECTION_NAME,
+        peer_api_uris=peer_api_uris
+    )
+
+    upsert_random_points(peer_api_uris[0], N_POINTS)
+
+    snapshot_name = create_cluster_snapshot(peer_api_uris[0])
+    assert "-cluster-" in snapshot_name
+
+    r = requests.get(f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/snapshots")
+    assert_http_ok(r)
+    assert snapshot_name in [snapshot["name"] for snapshot in r.json()["result"]]
+
+    # Recover on a new cluster with a different set of peers
+    restored_path = tmp_path / "restored"
+    restored_path.mkdir()
+    restored_api_uris, _, _ = start_cluster(restored_path, N_RESTORED_PEERS)
+
+    snapshot_url = f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/snapshots/{snapshot_name}"
+    assert recover_cluster_snapshot(restored_api_uris[0], snapshot_url)
+
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name=COLLECTION_NAME,
+        peer_api_uris=restored_api_uris
+    )
+
+    # Recovering peer holds all shards
+    cluster_info = get_collection_cluster_info(restored_api_uris[0], COLLECTION_NAME)
+    assert len(cluster_info["local_shards"]) == N_SHARDS
+
+    for uri in restored_api_uris:
+        assert count_points(uri) == N_POINTS
+
+    # Cluster snapshot can't overwrite an existing collection
+    r = requests.put(
+        f"{restored_api_uris[1]}/collections/{COLLECTION_NAME}/snapshots/cluster/recover",
+        json={"location": snapshot_url},
+    )
+    assert r.status_code == 400
@@ -0,0 +1,123 @@
+import pathlib
+
+from .fixtures import create_collection, upsert_random_points
+from .utils import *
+from .assertions import assert_http_ok
+
+N_PEERS = 3
+N_SHARDS = 2
+N_REPLICA = 1
+N_POINTS = 1000
+
+
+def check_resharding_finished(peer_api_uri: str, collection_name: str, expected_shard_count: int) -> bool:
+    collection_cluster_info = get_collection_cluster_info(peer_api_uri, collection_name)
+    return collection_cluster_info.get("resharding") is None and \
+        collection_cluster_info["shard_count"] == expected_shard_count
+
+
+def count_points(peer_api_uri: str, collection_name: str) -> int:
+    r = requests.post(
+        f"{peer_api_uri}/collections/{collection_name}/points/count", json={"exact": True}
+    )
+    assert_http_ok(r)
+    return r.json()["result"]["count"]
+
+
+def check_points_count(peer_api_uri: str, collection_name: str, expected_count: int) -> bool:
+    return count_points(peer_api_uri, collection_name) == expected_count
+
+
+def test_collection_resharding(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, peer_dirs, bootstrap_uri = start_cluster(tmp_path, N_PEERS)
+
+    create_collection(peer_api_uris[0], shard_number=N_SHARDS, replication_factor=N_REPLICA)
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name="test_collection",
+        peer_api_uris=peer_api_uris
+    )
+
+    upsert_random_points(peer_api_uris[0], N_POINTS)
+
+    # Add a shard
+    r = requests.post(
+        f"{peer_api_uris[0]}/collections/test_collection/cluster", json={
+            "start_resharding": {}
+        })
+    assert_http_ok(r)
+
+    # Concurrent resharding is rejected
+    r = requests.post(
+        f"{peer_api_uris[0]}/collections/test_collection/cluster", json={
+            "start_resharding": {}
+        })
+    assert r.status_code == 400
+
+    # Updates during resharding are not lost
+    upsert_random_points(peer_api_uris[1], 100, offset=N_POINTS)
+
+    for uri in peer_api_uris:
+        wait_for(check_resharding_finished, uri, "test_collection", N_SHARDS + 1)
+
+    # Every point is served exactly once, after migrated points are cleaned up
+    for uri in peer_api_uris:
+        wait_for(check_points_count, uri, "test_collection", N_POINTS + 100)
+
+    # New shard got its part of the points
+    local_shards = []
+    for uri in peer_api_uris:
+        local_shards += get_collection_cluster_info(uri, "test_collection")["local_shards"]
+    new_shard = next(shard for shard in local_shards if shard["shard_id"] == N_SHARDS)
+    assert new_shard["state"] == "Active"
+    assert new_shard["points_count"] > 0
+
+    # Points are still searchable by id after resharding
+    r = requests.post(
+        f"{peer_api_uris[2]}/collections/test_collection/points", json={
+            "ids": list(range(N_POINTS + 100)),
+        })
+    assert_http_ok(r)
+    assert len(r.json()["result"]) == N_POINTS + 100
+
+    # Scroll returns every point once
+    r = requests.post(
+        f"{peer_api_uris[1]}/collections/test_collection/points/scroll", json={
+            "limit": N_POINTS + 200,
+        })
+    assert_http_ok(r)
+    assert sorted(point["id"] for point in r.json()["result"]["points"]) == list(range(N_POINTS + 100))
+
+
+def test_collection_resharding_abort(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, peer_dirs, bootstrap_uri = start_cluster(tmp_path, N_PEERS)
+
+    create_collection(peer_api_uris[0], shard_number=N_SHARDS, replication_factor=N_REPLICA)
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name="test_collection",
+        peer_api_uris=peer_api_uris
+    )
+
+    upsert_random_points(peer_api_uris[0], N_POINTS)
+
+    r = requests.post(
+        f"{peer_api_uris[0]}/collections/test_collection/cluster", json={
+            "start_resharding": {}
+        })
+    assert_http_ok(r)
+
+    # Resharding might be finished already, abort is best effort
+    requests.post(
+        f"{peer_api_uris[0]}/collections/test_collection/cluster", json={
+            "abort_resharding": {}
+        })
+
+    for uri in peer_api_uris:
+        wait_for(lambda: get_collection_cluster_info(uri, "test_collection").get("resharding") is None)
+
+    # No points are lost or duplicated either way
+    for uri in peer_api_uris:
+        assert count_points(uri, "test_collection") == N_POINTS
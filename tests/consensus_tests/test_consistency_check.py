@@ -0,0 +1,56 @@
+import pathlib
+
+from .fixtures import create_collection, upsert_random_points
+from .utils import *
+from .assertions import assert_http_ok
+
+N_PEERS = 3
+N_SHARDS = 2
+N_REPLICA = 3
+N_POINTS = 500
+
+
+def check_consistency(peer_api_uri: str, collection_name: str, body: dict) -> dict:
+    r = requests.post(
+        f"{peer_api_uri}/collections/{collection_name}/cluster/consistency", json=body
+    )
+    assert_http_ok(r)
+    return r.json()["result"]
+
+
+def test_consistency_check(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, peer_dirs, bootstrap_uri = start_cluster(tmp_path, N_PEERS)
+
+    create_collection(peer_api_uris[0], shard_number=N_SHARDS, replication_factor=N_REPLICA)
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name="test_collection",
+        peer_api_uris=peer_api_uris
+    )
+
+    upsert_random_points(peer_api_uris[0], N_POINTS)
+
+    # Any peer can compare all replicas
+    for uri in peer_api_uris:
+        report = check_consistency(uri, "test_collection", {})
+        assert len(report["shards"]) == N_SHARDS
+        for shard in report["shards"]:
+            assert shard["consistent"]
+            assert shard["mismatched_buckets"] == []
+            assert shard["missing_points"] == 0
+            assert shard["conflicting_points"] == 0
+            assert len(shard["replicas"]) == N_REPLICA
+            assert len({replica["digest"] for replica in shard["replicas"]}) == 1
+        assert sum(shard["replicas"][0]["points_count"] for shard in report["shards"]) == N_POINTS
+
+    # Repair of consistent replicas copies nothing
+    report = check_consistency(peer_api_uris[1], "test_collection", {"shard_id": 0, "repair": True})
+    assert len(report["shards"]) == 1
+    assert report["shards"][0]["repaired_points"] == 0
+
+    # Unknown shard
+    r = requests.post(
+        f"{peer_api_uris[0]}/collections/test_collection/cluster/consistency", json={"shard_id": 100}
+    )
+    assert r.status_code == 400
@@ -0,0 +1,82 @@
+import pathlib
+
+from .fixtures import create_collection, random_vector
+from .utils import *
+from .assertions import assert_http_ok
+
+N_PEERS = 3
+N_SHARDS = 2
+N_REPLICA = 3
+
+
+def test_read_your_writes(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, peer_dirs, bootstrap_uri = start_cluster(tmp_path, N_PEERS)
+
+    create_collection(peer_api_uris[0], shard_number=N_SHARDS, replication_factor=N_REPLICA)
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name="test_collection",
+        peer_api_uris=peer_api_uris
+    )
+
+    for i in range(20):
+        writer = peer_api_uris[i % N_PEERS]
+        reader = peer_api_uris[(i + 1) % N_PEERS]
+
+        # Do not wait for the update to be applied
+        r = requests.put(
+            f"{writer}/collections/test_collection/points?wait=false", json={
+                "points": [
+                    {"id": i, "vector": random_vector(), "payload": {"item": i}}
+                ]
+            })
+        assert_http_ok(r)
+        token = r.json()["result"]["consistency_token"]
+
+        # Every replica, which accepted the update, is listed in the token
+        entries = token.split(",")
+        assert len(entries) == N_REPLICA
+        assert len({entry.split(":")[0] for entry in entries}) == 1
+
+        r = requests.get(
+            f"{reader}/collections/test_collection/points/{i}",
+            params={"consistency_token": token}
+        )
+        assert_http_ok(r)
+        assert r.json()["result"]["payload"] == {"item": i}
+
+        r = requests.post(
+            f"{reader}/collections/test_collection/points/scroll",
+            params={"consistency_token": token},
+            json={"limit": 100}
+        )
+        assert_http_ok(r)
+        assert len(r.json()["result"]["points"]) == i + 1
+
+    # Batch over several shards covers all of them
+    r = requests.put(
+        f"{peer_api_uris[0]}/collections/test_collection/points?wait=false", json={
+            "points": [
+                {"id": 100 + i, "vector": random_vector()} for i in range(10)
+            ]
+        })
+    assert_http_ok(r)
+    token = r.json()["result"]["consistency_token"]
+    assert len({entry.split(":")[0] for entry in token.split(",")}) == N_SHARDS
+
+    r = requests.post(
+        f"{peer_api_uris[2]}/collections/test_collection/points",
+        params={"consistency_token": token},
+        json={"ids": [100 + i for i in range(10)]}
+    )
+    assert_http_ok(r)
+    assert len(r.json()["result"]) == 10
+
+    # Malformed token
+    r = requests.post(
+        f"{peer_api_uris[0]}/collections/test_collection/points/scroll",
+        params={"consistency_token": "not-a-token"},
+        json={"limit": 10}
+    )
+    assert r.status_code == 400
@@ -0,0 +1,86 @@
+import pathlib
+
+from .fixtures import create_collection, upsert_random_points
+from .utils import *
+from .assertions import assert_http_ok
+
+N_PEERS = 1
+N_POINTS = 1000
+COLLECTION_NAME = "test_collection"
+
+
+def create_snapshot(peer_api_uri: str, base: Optional[str] = None) -> dict:
+    # Snapshot names have a precision of one second
+    time.sleep(1)
+    params = {"base": base} if base is not None else {}
+    r = requests.post(f"{peer_api_uri}/collections/{COLLECTION_NAME}/snapshots", params=params)
+    assert_http_ok(r)
+    return r.json()["result"]
+
+
+def recover_snapshot(peer_api_uri: str, snapshot_name: str) -> requests.Response:
+    snapshot_url = f"{peer_api_uri}/collections/{COLLECTION_NAME}/snapshots/{snapshot_name}"
+    return requests.put(
+        f"{peer_api_uri}/collections/{COLLECTION_NAME}/snapshots/recover",
+        json={"location": snapshot_url},
+    )
+
+
+def count_points(peer_api_uri: str) -> int:
+    r = requests.post(
+        f"{peer_api_uri}/collections/{COLLECTION_NAME}/points/count", json={"exact": True}
+    )
+    assert_http_ok(r)
+    return r.json()["result"]["count"]
+
+
+def test_incremental_snapshot(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, _, _ = start_cluster(tmp_path, N_PEERS)
+    peer_api_uri = peer_api_uris[0]
+
+    create_collection(peer_api_uri, shard_number=2, replication_factor=1)
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name=COLLECTION_NAME,
+        peer_api_uris=peer_api_uris
+    )
+    upsert_random_points(peer_api_uri, N_POINTS)
+
+    base = create_snapshot(peer_api_uri)
+
+    # Nothing changed, the increment is much smaller than the base
+    unchanged = create_snapshot(peer_api_uri, base=base["name"])
+    assert unchanged["size"] < base["size"]
+
+    # Unknown base is rejected
+    r = requests.post(
+        f"{peer_api_uri}/collections/{COLLECTION_NAME}/snapshots",
+        params={"base": "missing.snapshot"},
+    )
+    assert r.status_code == 404
+
+    upsert_random_points(peer_api_uri, 10, offset=N_POINTS)
+    increment = create_snapshot(peer_api_uri, base=unchanged["name"])
+
+    # Drop points, recovery should bring them back from the chain
+    r = requests.post(
+        f"{peer_api_uri}/collections/{COLLECTION_NAME}/points/delete?wait=true",
+        json={"filter": {}},
+    )
+    assert_http_ok(r)
+
+    r = recover_snapshot(peer_api_uri, increment["name"])
+    assert_http_ok(r)
+    assert count_points(peer_api_uri) == N_POINTS + 10
+
+    # Bases of the incremental snapshots can't be deleted
+    r = requests.delete(f"{peer_api_uri}/collections/{COLLECTION_NAME}/snapshots/{base['name']}")
+    assert r.status_code == 400
+
+    # Chain can be deleted starting from the last increment
+    for snapshot in [increment, unchanged, base]:
+        r = requests.delete(
+            f"{peer_api_uri}/collections/{COLLECTION_NAME}/snapshots/{snapshot['name']}"
+        )
+        assert_http_ok(r)
@@ -0,0 +1,62 @@
+import pathlib
+
+from .fixtures import create_collection, upsert_random_points
+from .utils import *
+from .assertions import assert_http_ok
+
+N_PEERS = 3
+N_SHARDS = 3
+N_REPLICA = 1
+N_POINTS = 1000
+
+
+def check_peer_drained(peer_api_uri: str, peer_id: int) -> bool:
+    cluster_info = get_cluster_info(peer_api_uri)
+    rebalancer = cluster_info.get("rebalancer")
+    if rebalancer is None:
+        return False
+    return rebalancer["draining_peers"].get(str(peer_id)) == 0 and rebalancer["running_transfers"] == 0
+
+
+def count_points(peer_api_uri: str, collection_name: str) -> int:
+    r = requests.post(
+        f"{peer_api_uri}/collections/{collection_name}/points/count", json={"exact": True}
+    )
+    assert_http_ok(r)
+    return r.json()["result"]["count"]
+
+
+def test_peer_drain(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, peer_dirs, bootstrap_uri = start_cluster(
+        tmp_path, N_PEERS, extra_env={"QDRANT__CLUSTER__REBALANCER__INTERVAL_SEC": "1"}
+    )
+
+    create_collection(peer_api_uris[0], shard_number=N_SHARDS, replication_factor=N_REPLICA)
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name="test_collection",
+        peer_api_uris=peer_api_uris
+    )
+
+    upsert_random_points(peer_api_uris[0], N_POINTS)
+
+    drained_uri = peer_api_uris[-1]
+    drained_peer_id = get_cluster_info(drained_uri)["peer_id"]
+
+    r = requests.post(f"{peer_api_uris[0]}/cluster/peer/{drained_peer_id}/drain")
+    assert_http_ok(r)
+
+    # Unknown peer can't be drained
+    r = requests.post(f"{peer_api_uris[0]}/cluster/peer/123/drain")
+    assert r.status_code == 404
+
+    wait_for(check_peer_drained, peer_api_uris[0], drained_peer_id)
+    wait_for(lambda: len(get_collection_cluster_info(drained_uri, "test_collection")["local_shards"]) == 0)
+
+    # Drained peer can be removed without forcing
+    r = requests.delete(f"{peer_api_uris[0]}/cluster/peer/{drained_peer_id}")
+    assert_http_ok(r)
+
+    for uri in peer_api_uris[:-1]:
+        assert count_points(uri, "test_collection") == N_POINTS
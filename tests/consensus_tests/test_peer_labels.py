@@ -0,0 +1,22 @@
+import pathlib
+
+from .utils import *
+
+N_PEERS = 3
+
+
+def check_all_peers_labeled(peer_api_uri: str, expected_labels: dict) -> bool:
+    peers = get_cluster_info(peer_api_uri)["peers"]
+    return len(peers) == N_PEERS and all(peer["labels"] == expected_labels for peer in peers.values())
+
+
+def test_peer_labels(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, peer_dirs, bootstrap_uri = start_cluster(
+        tmp_path, N_PEERS, extra_env={"QDRANT__CLUSTER__LABELS__ZONE": "zone-a"}
+    )
+
+    # Labels are propagated through consensus to every peer
+    for uri in peer_api_uris:
+        wait_for(check_all_peers_labeled, uri, {"zone": "zone-a"})
@@ -0,0 +1,78 @@
+import hashlib
+import pathlib
+
+from .fixtures import create_collection, upsert_random_points
+from .utils import *
+from .assertions import assert_http_ok
+
+N_PEERS = 1
+N_POINTS = 100
+COLLECTION_NAME = "test_collection"
+
+
+def upload_snapshot(peer_api_uri: str, snapshot_path: pathlib.Path, checksum: str):
+    with open(snapshot_path, "rb") as f:
+        return requests.post(
+            f"{peer_api_uri}/collections/{COLLECTION_NAME}/snapshots/upload",
+            params={"checksum": checksum},
+            files={"snapshot": f},
+        )
+
+
+def count_points(peer_api_uri: str) -> int:
+    r = requests.post(
+        f"{peer_api_uri}/collections/{COLLECTION_NAME}/points/count", json={"exact": True}
+    )
+    assert_http_ok(r)
+    return r.json()["result"]["count"]
+
+
+def test_snapshot_checksum(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, _, _ = start_cluster(tmp_path, N_PEERS)
+    peer_api_uri = peer_api_uris[0]
+
+    create_collection(peer_api_uri, shard_number=1, replication_factor=1)
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name=COLLECTION_NAME,
+        peer_api_uris=peer_api_uris
+    )
+    upsert_random_points(peer_api_uri, N_POINTS)
+
+    r = requests.post(f"{peer_api_uri}/collections/{COLLECTION_NAME}/snapshots")
+    assert_http_ok(r)
+    snapshot = r.json()["result"]
+    checksum = snapshot["checksum"]
+
+    # Checksum is returned in the list and matches the content
+    r = requests.get(f"{peer_api_uri}/collections/{COLLECTION_NAME}/snapshots")
+    assert_http_ok(r)
+    assert [s["checksum"] for s in r.json()["result"] if s["name"] == snapshot["name"]] == [checksum]
+
+    r = requests.get(f"{peer_api_uri}/collections/{COLLECTION_NAME}/snapshots/{snapshot['name']}")
+    assert_http_ok(r)
+    assert hashlib.sha256(r.content).hexdigest() == checksum
+
+    snapshot_path = tmp_path / "snapshot.snapshot"
+    snapshot_path.write_bytes(r.content)
+
+    # Truncated upload is refused before anything is unpacked
+    truncated_path = tmp_path / "truncated.snapshot"
+    truncated_path.write_bytes(r.content[: len(r.content) // 2])
+    r = upload_snapshot(peer_api_uri, truncated_path, checksum)
+    assert r.status_code == 400
+    assert "checksum mismatch" in r.json()["status"]["error"]
+    assert count_points(peer_api_uri) == N_POINTS
+
+    # Recovery from URL is refused as well
+    snapshot_url = f"{peer_api_uri}/collections/{COLLECTION_NAME}/snapshots/{snapshot['name']}"
+    r = requests.put(
+        f"{peer_api_uri}/collections/{COLLECTION_NAME}/snapshots/recover",
+        json={"location": snapshot_url, "checksum": "0" * 64},
+    )
+    assert r.status_code == 400
+
+    r = upload_snapshot(peer_api_uri, snapshot_path, checksum.upper())
+    assert_http_ok(r)
+    assert count_points(peer_api_uri) == N_POINTS
@@ -0,0 +1,80 @@
+import pathlib
+from datetime import datetime, timezone
+
+from .fixtures import upsert_random_points
+from .utils import *
+from .assertions import assert_http_ok
+
+N_PEERS = 1
+N_POINTS = 100
+COLLECTION_NAME = "test_collection"
+
+
+def create_collection(peer_api_uri: str):
+    r = requests.put(
+        f"{peer_api_uri}/collections/{COLLECTION_NAME}", json={
+            "vectors": {
+                "size": 4,
+                "distance": "Dot"
+            },
+            # Flush often, so acknowledged operations get archived quickly
+            "optimizers_config": {
+                "flush_interval_sec": 1
+            },
+        })
+    assert_http_ok(r)
+
+
+def count_points(peer_api_uri: str) -> int:
+    r = requests.post(
+        f"{peer_api_uri}/collections/{COLLECTION_NAME}/points/count", json={"exact": True}
+    )
+    assert_http_ok(r)
+    return r.json()["result"]["count"]
+
+
+def recover_snapshot(peer_api_uri: str, snapshot_name: str, wal_replay: dict) -> requests.Response:
+    snapshot_url = f"{peer_api_uri}/collections/{COLLECTION_NAME}/snapshots/{snapshot_name}"
+    return requests.put(
+        f"{peer_api_uri}/collections/{COLLECTION_NAME}/snapshots/recover",
+        json={"location": snapshot_url, "wal_replay": wal_replay},
+    )
+
+
+def test_wal_archive_replay(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    archive_path = tmp_path / "wal_archive"
+    env = {"QDRANT__STORAGE__WAL_ARCHIVE_PATH": str(archive_path)}
+
+    peer_api_uris, _, _ = start_cluster(tmp_path, N_PEERS, extra_env=env)
+    peer_api_uri = peer_api_uris[0]
+
+    create_collection(peer_api_uri)
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name=COLLECTION_NAME,
+        peer_api_uris=peer_api_uris
+    )
+    upsert_random_points(peer_api_uri, N_POINTS)
+
+    r = requests.post(f"{peer_api_uri}/collections/{COLLECTION_NAME}/snapshots")
+    assert_http_ok(r)
+    snapshot_name = r.json()["result"]["name"]
+
+    upsert_random_points(peer_api_uri, 10, offset=N_POINTS)
+
+    # Archive has a precision of one second
+    time.sleep(2)
+    restore_time = datetime.now(timezone.utc).isoformat()
+    time.sleep(2)
+
+    upsert_random_points(peer_api_uri, 10, offset=N_POINTS + 10)
+
+    # Wait for operations to be flushed and archived
+    time.sleep(3)
+    assert count_points(peer_api_uri) == N_POINTS + 20
+
+    # Restore the state between the two batches
+    r = recover_snapshot(peer_api_uri, snapshot_name, {"until_time": restore_time})
+    assert_http_ok(r)
+    assert count_points(peer_api_uri) == N_POINTS + 10